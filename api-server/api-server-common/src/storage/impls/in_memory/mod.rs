@@ -17,16 +17,18 @@ pub mod transactional;
 
 use crate::storage::storage_api::{
     block_aux_data::{BlockAuxData, BlockWithExtraData},
-    ApiServerStorageError, BlockInfo, CoinOrTokenStatistic, Delegation, FungibleTokenData,
-    LockedUtxo, PoolBlockStats, TransactionInfo, Utxo, UtxoLock, UtxoWithExtraInfo,
+    ApiServerStorageError, BlockInfo, CoinOrTokenStatistic, DataDepositInfo, Delegation,
+    FungibleTokenData, LockedUtxo, PoolBlockStats, TokenAllowance, TransactionInfo, Utxo, UtxoLock,
+    UtxoWithExtraInfo, WebhookSubscription,
 };
 use common::{
     chain::{
         block::timestamp::BlockTimestamp,
         tokens::{NftIssuance, TokenId},
-        Block, ChainConfig, DelegationId, Destination, Genesis, PoolId, Transaction, UtxoOutPoint,
+        Block, ChainConfig, DelegationId, Destination, Genesis, PoolId, Transaction, TxOutput,
+        UtxoOutPoint,
     },
-    primitives::{id::WithId, Amount, BlockHeight, CoinOrTokenId, Id},
+    primitives::{id::WithId, Amount, BlockHeight, CoinOrTokenId, Id, Idable},
 };
 use pos_accounting::PoolData;
 use std::{
@@ -45,6 +47,7 @@ struct ApiServerInMemoryStorage {
     address_balance_table: BTreeMap<String, BTreeMap<(CoinOrTokenId, BlockHeight), Amount>>,
     address_locked_balance_table: BTreeMap<String, BTreeMap<(CoinOrTokenId, BlockHeight), Amount>>,
     address_transactions_table: BTreeMap<String, BTreeMap<BlockHeight, Vec<Id<Transaction>>>>,
+    token_transactions_table: BTreeMap<TokenId, BTreeMap<(BlockHeight, u32), Id<Transaction>>>,
     delegation_table: BTreeMap<DelegationId, BTreeMap<BlockHeight, Delegation>>,
     main_chain_blocks_table: BTreeMap<BlockHeight, Id<Block>>,
     pool_data_table: BTreeMap<PoolId, BTreeMap<BlockHeight, PoolData>>,
@@ -57,6 +60,10 @@ struct ApiServerInMemoryStorage {
     nft_token_issuances: BTreeMap<TokenId, BTreeMap<BlockHeight, NftIssuance>>,
     statistics:
         BTreeMap<CoinOrTokenStatistic, BTreeMap<CoinOrTokenId, BTreeMap<BlockHeight, Amount>>>,
+    webhook_subscriptions_table: BTreeMap<u64, WebhookSubscription>,
+    next_webhook_id: u64,
+    webhook_cursor: Option<BlockHeight>,
+    address_token_allowances_table: BTreeMap<String, BTreeMap<(TokenId, String), Amount>>,
     best_block: BlockAuxData,
     genesis_block: Arc<WithId<Genesis>>,
     storage_version: u32,
@@ -70,6 +77,7 @@ impl ApiServerInMemoryStorage {
             address_balance_table: BTreeMap::new(),
             address_locked_balance_table: BTreeMap::new(),
             address_transactions_table: BTreeMap::new(),
+            token_transactions_table: BTreeMap::new(),
             delegation_table: BTreeMap::new(),
             main_chain_blocks_table: BTreeMap::new(),
             pool_data_table: BTreeMap::new(),
@@ -81,6 +89,10 @@ impl ApiServerInMemoryStorage {
             fungible_token_issuances: BTreeMap::new(),
             nft_token_issuances: BTreeMap::new(),
             statistics: BTreeMap::new(),
+            webhook_subscriptions_table: BTreeMap::new(),
+            next_webhook_id: 1,
+            webhook_cursor: None,
+            address_token_allowances_table: BTreeMap::new(),
             genesis_block: chain_config.genesis_block().clone(),
             best_block: BlockAuxData::new(
                 chain_config.genesis_block_id(),
@@ -143,6 +155,20 @@ impl ApiServerInMemoryStorage {
             }))
     }
 
+    fn get_block_address_transactions(
+        &self,
+        block_height: BlockHeight,
+    ) -> Result<BTreeMap<String, BTreeSet<Id<Transaction>>>, ApiServerStorageError> {
+        Ok(self
+            .address_transactions_table
+            .iter()
+            .filter_map(|(address, transactions)| {
+                let transactions = transactions.get(&block_height)?;
+                Some((address.clone(), transactions.iter().copied().collect()))
+            })
+            .collect())
+    }
+
     fn get_block(&self, block_id: Id<Block>) -> Result<Option<BlockInfo>, ApiServerStorageError> {
         let block_result = self.block_table.get(&block_id);
         let block = match block_result {
@@ -186,6 +212,32 @@ impl ApiServerInMemoryStorage {
             .collect())
     }
 
+    fn get_token_transactions(
+        &self,
+        token_id: TokenId,
+        len: u32,
+        offset: u32,
+    ) -> Result<Vec<(BlockAuxData, TransactionInfo)>, ApiServerStorageError> {
+        Ok(self
+            .token_transactions_table
+            .get(&token_id)
+            .map_or_else(Vec::new, |transactions| {
+                transactions
+                    .values()
+                    .rev()
+                    .skip(offset as usize)
+                    .take(len as usize)
+                    .map(|tx_id| {
+                        let (block_id, tx) = self.transaction_table.get(tx_id).expect("must exist");
+                        let block_id = block_id.expect("must be in a block");
+                        let block_aux =
+                            self.block_aux_data_table.get(&block_id).expect("must exist");
+                        (*block_aux, tx.clone())
+                    })
+                    .collect()
+            }))
+    }
+
     #[allow(clippy::type_complexity)]
     fn get_transaction_with_block(
         &self,
@@ -576,6 +628,47 @@ impl ApiServerInMemoryStorage {
             .collect())
     }
 
+    fn get_data_deposits_by_prefix(
+        &self,
+        len: u32,
+        offset: u32,
+        prefix: &[u8],
+    ) -> Result<Vec<DataDepositInfo>, ApiServerStorageError> {
+        Ok(self
+            .main_chain_blocks_table
+            .iter()
+            .rev()
+            .flat_map(|(block_height, block_id)| {
+                let block = self.block_table.get(block_id).expect("must exist");
+                block.block.transactions().iter().flat_map(move |tx| {
+                    tx.outputs().iter().filter_map(move |output| match output {
+                        TxOutput::DataDeposit(data) if data.starts_with(prefix) => {
+                            Some(DataDepositInfo {
+                                tx_id: tx.transaction().get_id(),
+                                block_height: *block_height,
+                                data: data.clone(),
+                            })
+                        }
+                        TxOutput::Transfer(_, _)
+                        | TxOutput::LockThenTransfer(_, _, _)
+                        | TxOutput::Burn(_)
+                        | TxOutput::CreateStakePool(_, _)
+                        | TxOutput::ProduceBlockFromStake(_, _)
+                        | TxOutput::CreateDelegationId(_, _)
+                        | TxOutput::DelegateStaking(_, _)
+                        | TxOutput::IssueFungibleToken(_)
+                        | TxOutput::IssueNft(_, _, _)
+                        | TxOutput::DataDeposit(_)
+                        | TxOutput::Htlc(_, _)
+                        | TxOutput::AnyoneCanTake(_) => None,
+                    })
+                })
+            })
+            .skip(offset as usize)
+            .take(len as usize)
+            .collect())
+    }
+
     fn get_statistic(
         &self,
         statistic: CoinOrTokenStatistic,
@@ -601,6 +694,36 @@ impl ApiServerInMemoryStorage {
             .collect())
     }
 
+    fn get_webhook_subscriptions(
+        &self,
+    ) -> Result<BTreeMap<u64, WebhookSubscription>, ApiServerStorageError> {
+        Ok(self.webhook_subscriptions_table.clone())
+    }
+
+    fn get_webhook_cursor(&self) -> Result<Option<BlockHeight>, ApiServerStorageError> {
+        Ok(self.webhook_cursor)
+    }
+
+    fn get_address_token_allowances(
+        &self,
+        address: &str,
+    ) -> Result<Vec<TokenAllowance>, ApiServerStorageError> {
+        Ok(self
+            .address_token_allowances_table
+            .get(address)
+            .map(|allowances| {
+                allowances
+                    .iter()
+                    .map(|((token_id, spender), allowance)| TokenAllowance {
+                        token_id: *token_id,
+                        spender: spender.clone(),
+                        allowance: *allowance,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
     fn set_statistic(
         &mut self,
         statistic: CoinOrTokenStatistic,
@@ -629,6 +752,48 @@ impl ApiServerInMemoryStorage {
         });
         Ok(())
     }
+
+    fn add_webhook_subscription(
+        &mut self,
+        subscription: &WebhookSubscription,
+        max_subscriptions: usize,
+    ) -> Result<Option<u64>, ApiServerStorageError> {
+        if self.webhook_subscriptions_table.len() >= max_subscriptions {
+            return Ok(None);
+        }
+
+        let id = self.next_webhook_id;
+        self.next_webhook_id += 1;
+        self.webhook_subscriptions_table.insert(id, subscription.clone());
+        Ok(Some(id))
+    }
+
+    fn del_webhook_subscription(&mut self, id: u64) -> Result<bool, ApiServerStorageError> {
+        Ok(self.webhook_subscriptions_table.remove(&id).is_some())
+    }
+
+    fn set_webhook_cursor(
+        &mut self,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError> {
+        self.webhook_cursor = Some(block_height);
+        Ok(())
+    }
+
+    fn set_address_token_allowance(
+        &mut self,
+        address: &str,
+        allowance: &TokenAllowance,
+    ) -> Result<(), ApiServerStorageError> {
+        self.address_token_allowances_table
+            .entry(address.to_string())
+            .or_default()
+            .insert(
+                (allowance.token_id, allowance.spender.clone()),
+                allowance.allowance,
+            );
+        Ok(())
+    }
 }
 
 impl ApiServerInMemoryStorage {
@@ -650,6 +815,7 @@ impl ApiServerInMemoryStorage {
         self.address_balance_table.clear();
         self.address_locked_balance_table.clear();
         self.address_transactions_table.clear();
+        self.token_transactions_table.clear();
         self.delegation_table.clear();
         self.main_chain_blocks_table.clear();
         self.pool_data_table.clear();
@@ -658,6 +824,9 @@ impl ApiServerInMemoryStorage {
         self.address_utxos.clear();
         self.fungible_token_issuances.clear();
         self.nft_token_issuances.clear();
+        self.webhook_subscriptions_table.clear();
+        self.webhook_cursor = None;
+        self.address_token_allowances_table.clear();
 
         self.initialize_storage(chain_config)
     }
@@ -724,6 +893,19 @@ impl ApiServerInMemoryStorage {
         Ok(())
     }
 
+    fn del_token_transactions_above_height(
+        &mut self,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError> {
+        // Inefficient, but acceptable for testing with InMemoryStorage
+
+        self.token_transactions_table.iter_mut().for_each(|(_, transactions)| {
+            transactions.retain(|(height, _), _| *height <= block_height);
+        });
+
+        Ok(())
+    }
+
     fn set_address_balance_at_height(
         &mut self,
         address: &str,
@@ -772,6 +954,21 @@ impl ApiServerInMemoryStorage {
         Ok(())
     }
 
+    fn set_token_transactions_at_height(
+        &mut self,
+        token_id: TokenId,
+        transaction_ids: BTreeMap<u32, Id<Transaction>>,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError> {
+        self.token_transactions_table.entry(token_id).or_default().extend(
+            transaction_ids
+                .into_iter()
+                .map(|(tx_index, tx_id)| ((block_height, tx_index), tx_id)),
+        );
+
+        Ok(())
+    }
+
     fn set_mainchain_block(
         &mut self,
         block_id: Id<Block>,
@@ -15,11 +15,11 @@
 
 use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 
-use common::chain::ChainConfig;
+use common::{chain::ChainConfig, primitives::BlockHeight};
 
 use crate::storage::storage_api::{
     ApiServerStorage, ApiServerStorageError, ApiServerTransactionRo, ApiServerTransactionRw,
-    Transactional,
+    ApiServerWebhookStorage, Transactional, WebhookSubscription,
 };
 
 use super::ApiServerInMemoryStorage;
@@ -120,4 +120,29 @@ impl<'tx> Transactional<'tx> for TransactionalApiServerInMemoryStorage {
     }
 }
 
+#[async_trait::async_trait]
+impl ApiServerWebhookStorage for TransactionalApiServerInMemoryStorage {
+    async fn add_webhook_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+        max_subscriptions: usize,
+    ) -> Result<Option<u64>, ApiServerStorageError> {
+        self.storage
+            .write()
+            .await
+            .add_webhook_subscription(subscription, max_subscriptions)
+    }
+
+    async fn del_webhook_subscription(&self, id: u64) -> Result<bool, ApiServerStorageError> {
+        self.storage.write().await.del_webhook_subscription(id)
+    }
+
+    async fn set_webhook_cursor(
+        &self,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError> {
+        self.storage.write().await.set_webhook_cursor(block_height)
+    }
+}
+
 impl ApiServerStorage for TransactionalApiServerInMemoryStorage {}
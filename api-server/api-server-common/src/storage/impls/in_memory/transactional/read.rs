@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use common::{
     chain::{
@@ -27,8 +27,8 @@ use pos_accounting::PoolData;
 
 use crate::storage::storage_api::{
     block_aux_data::BlockAuxData, ApiServerStorageError, ApiServerStorageRead, BlockInfo,
-    CoinOrTokenStatistic, Delegation, FungibleTokenData, PoolBlockStats, TransactionInfo, Utxo,
-    UtxoWithExtraInfo,
+    CoinOrTokenStatistic, DataDepositInfo, Delegation, FungibleTokenData, PoolBlockStats,
+    TokenAllowance, TransactionInfo, Utxo, UtxoWithExtraInfo, WebhookSubscription,
 };
 
 use super::ApiServerInMemoryStorageTransactionalRo;
@@ -62,6 +62,13 @@ impl<'t> ApiServerStorageRead for ApiServerInMemoryStorageTransactionalRo<'t> {
         self.transaction.get_address_transactions(address)
     }
 
+    async fn get_block_address_transactions(
+        &self,
+        block_height: BlockHeight,
+    ) -> Result<BTreeMap<String, BTreeSet<Id<Transaction>>>, ApiServerStorageError> {
+        self.transaction.get_block_address_transactions(block_height)
+    }
+
     async fn get_block(
         &self,
         block_id: Id<Block>,
@@ -91,6 +98,15 @@ impl<'t> ApiServerStorageRead for ApiServerInMemoryStorageTransactionalRo<'t> {
         self.transaction.get_transactions_with_block(len, offset)
     }
 
+    async fn get_token_transactions(
+        &self,
+        token_id: TokenId,
+        len: u32,
+        offset: u32,
+    ) -> Result<Vec<(BlockAuxData, TransactionInfo)>, ApiServerStorageError> {
+        self.transaction.get_token_transactions(token_id, len, offset)
+    }
+
     async fn get_delegation(
         &self,
         delegation_id: DelegationId,
@@ -245,6 +261,15 @@ impl<'t> ApiServerStorageRead for ApiServerInMemoryStorageTransactionalRo<'t> {
         self.transaction.get_token_ids_by_ticker(len, offset, ticker)
     }
 
+    async fn get_data_deposits_by_prefix(
+        &self,
+        len: u32,
+        offset: u32,
+        prefix: &[u8],
+    ) -> Result<Vec<DataDepositInfo>, ApiServerStorageError> {
+        self.transaction.get_data_deposits_by_prefix(len, offset, prefix)
+    }
+
     async fn get_statistic(
         &self,
         statistic: CoinOrTokenStatistic,
@@ -259,4 +284,21 @@ impl<'t> ApiServerStorageRead for ApiServerInMemoryStorageTransactionalRo<'t> {
     ) -> Result<BTreeMap<CoinOrTokenStatistic, Amount>, ApiServerStorageError> {
         self.transaction.get_all_statistic(coin_or_token_id)
     }
+
+    async fn get_webhook_subscriptions(
+        &self,
+    ) -> Result<BTreeMap<u64, WebhookSubscription>, ApiServerStorageError> {
+        self.transaction.get_webhook_subscriptions()
+    }
+
+    async fn get_webhook_cursor(&self) -> Result<Option<BlockHeight>, ApiServerStorageError> {
+        self.transaction.get_webhook_cursor()
+    }
+
+    async fn get_address_token_allowances(
+        &self,
+        address: &str,
+    ) -> Result<Vec<TokenAllowance>, ApiServerStorageError> {
+        self.transaction.get_address_token_allowances(address)
+    }
 }
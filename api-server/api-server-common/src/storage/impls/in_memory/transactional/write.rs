@@ -28,8 +28,8 @@ use pos_accounting::PoolData;
 use crate::storage::storage_api::{
     block_aux_data::{BlockAuxData, BlockWithExtraData},
     ApiServerStorageError, ApiServerStorageRead, ApiServerStorageWrite, BlockInfo,
-    CoinOrTokenStatistic, Delegation, FungibleTokenData, LockedUtxo, PoolBlockStats,
-    TransactionInfo, Utxo, UtxoWithExtraInfo,
+    CoinOrTokenStatistic, DataDepositInfo, Delegation, FungibleTokenData, LockedUtxo,
+    PoolBlockStats, TokenAllowance, TransactionInfo, Utxo, UtxoWithExtraInfo, WebhookSubscription,
 };
 
 use super::ApiServerInMemoryStorageTransactionalRw;
@@ -64,6 +64,13 @@ impl<'t> ApiServerStorageWrite for ApiServerInMemoryStorageTransactionalRw<'t> {
         self.transaction.del_address_transactions_above_height(block_height)
     }
 
+    async fn del_token_transactions_above_height(
+        &mut self,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError> {
+        self.transaction.del_token_transactions_above_height(block_height)
+    }
+
     async fn set_address_balance_at_height(
         &mut self,
         address: &str,
@@ -104,6 +111,16 @@ impl<'t> ApiServerStorageWrite for ApiServerInMemoryStorageTransactionalRw<'t> {
             .set_address_transactions_at_height(address, transactions, block_height)
     }
 
+    async fn set_token_transactions_at_height(
+        &mut self,
+        token_id: TokenId,
+        transactions: BTreeMap<u32, Id<Transaction>>,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError> {
+        self.transaction
+            .set_token_transactions_at_height(token_id, transactions, block_height)
+    }
+
     async fn set_mainchain_block(
         &mut self,
         block_id: Id<Block>,
@@ -254,6 +271,33 @@ impl<'t> ApiServerStorageWrite for ApiServerInMemoryStorageTransactionalRw<'t> {
     ) -> Result<(), ApiServerStorageError> {
         self.transaction.del_statistics_above_height(block_height)
     }
+
+    async fn add_webhook_subscription(
+        &mut self,
+        subscription: &WebhookSubscription,
+        max_subscriptions: usize,
+    ) -> Result<Option<u64>, ApiServerStorageError> {
+        self.transaction.add_webhook_subscription(subscription, max_subscriptions)
+    }
+
+    async fn del_webhook_subscription(&mut self, id: u64) -> Result<bool, ApiServerStorageError> {
+        self.transaction.del_webhook_subscription(id)
+    }
+
+    async fn set_webhook_cursor(
+        &mut self,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError> {
+        self.transaction.set_webhook_cursor(block_height)
+    }
+
+    async fn set_address_token_allowance(
+        &mut self,
+        address: &str,
+        allowance: &TokenAllowance,
+    ) -> Result<(), ApiServerStorageError> {
+        self.transaction.set_address_token_allowance(address, allowance)
+    }
 }
 
 #[async_trait::async_trait]
@@ -289,6 +333,13 @@ impl<'t> ApiServerStorageRead for ApiServerInMemoryStorageTransactionalRw<'t> {
         self.transaction.get_address_transactions(address)
     }
 
+    async fn get_block_address_transactions(
+        &self,
+        block_height: BlockHeight,
+    ) -> Result<BTreeMap<String, BTreeSet<Id<Transaction>>>, ApiServerStorageError> {
+        self.transaction.get_block_address_transactions(block_height)
+    }
+
     async fn get_latest_blocktimestamps(
         &self,
     ) -> Result<Vec<BlockTimestamp>, ApiServerStorageError> {
@@ -364,6 +415,15 @@ impl<'t> ApiServerStorageRead for ApiServerInMemoryStorageTransactionalRw<'t> {
         self.transaction.get_transactions_with_block(len, offset)
     }
 
+    async fn get_token_transactions(
+        &self,
+        token_id: TokenId,
+        len: u32,
+        offset: u32,
+    ) -> Result<Vec<(BlockAuxData, TransactionInfo)>, ApiServerStorageError> {
+        self.transaction.get_token_transactions(token_id, len, offset)
+    }
+
     async fn get_pool_data(
         &self,
         pool_id: PoolId,
@@ -468,6 +528,15 @@ impl<'t> ApiServerStorageRead for ApiServerInMemoryStorageTransactionalRw<'t> {
         self.transaction.get_token_ids_by_ticker(len, offset, ticker)
     }
 
+    async fn get_data_deposits_by_prefix(
+        &self,
+        len: u32,
+        offset: u32,
+        prefix: &[u8],
+    ) -> Result<Vec<DataDepositInfo>, ApiServerStorageError> {
+        self.transaction.get_data_deposits_by_prefix(len, offset, prefix)
+    }
+
     async fn get_statistic(
         &self,
         statistic: CoinOrTokenStatistic,
@@ -482,4 +551,21 @@ impl<'t> ApiServerStorageRead for ApiServerInMemoryStorageTransactionalRw<'t> {
     ) -> Result<BTreeMap<CoinOrTokenStatistic, Amount>, ApiServerStorageError> {
         self.transaction.get_all_statistic(coin_or_token_id)
     }
+
+    async fn get_webhook_subscriptions(
+        &self,
+    ) -> Result<BTreeMap<u64, WebhookSubscription>, ApiServerStorageError> {
+        self.transaction.get_webhook_subscriptions()
+    }
+
+    async fn get_webhook_cursor(&self) -> Result<Option<BlockHeight>, ApiServerStorageError> {
+        self.transaction.get_webhook_cursor()
+    }
+
+    async fn get_address_token_allowances(
+        &self,
+        address: &str,
+    ) -> Result<Vec<TokenAllowance>, ApiServerStorageError> {
+        self.transaction.get_address_token_allowances(address)
+    }
 }
@@ -28,7 +28,7 @@ use common::{
         block::timestamp::BlockTimestamp,
         tokens::{NftIssuance, TokenId},
         AccountNonce, Block, ChainConfig, DelegationId, Destination, GenBlock, PoolId, Transaction,
-        UtxoOutPoint,
+        TxOutput, UtxoOutPoint,
     },
     primitives::{Amount, BlockHeight, CoinOrTokenId, Id},
 };
@@ -38,12 +38,27 @@ use crate::storage::{
     impls::CURRENT_STORAGE_VERSION,
     storage_api::{
         block_aux_data::{BlockAuxData, BlockWithExtraData},
-        ApiServerStorageError, BlockInfo, CoinOrTokenStatistic, Delegation, FungibleTokenData,
-        LockedUtxo, PoolBlockStats, TransactionInfo, Utxo, UtxoWithExtraInfo,
+        ApiServerStorageError, BlockInfo, CoinOrTokenStatistic, DataDepositInfo, Delegation,
+        FungibleTokenData, LockedUtxo, PoolBlockStats, TokenAllowance, TransactionInfo, Utxo,
+        UtxoWithExtraInfo, WebhookSubscription,
     },
 };
 
 const VERSION_STR: &str = "version";
+const WEBHOOK_CURSOR_STR: &str = "webhook_cursor";
+
+/// The smallest byte string that is greater than all the byte strings starting with `prefix`,
+/// or `None` if there is no such string (the prefix is empty or consists of 0xFF bytes only).
+fn prefix_upper_bound(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut upper_bound = prefix.to_vec();
+    while let Some(last) = upper_bound.pop() {
+        if last < u8::MAX {
+            upper_bound.push(last + 1);
+            return Some(upper_bound);
+        }
+    }
+    None
+}
 
 pub struct QueryFromConnection<'a, 'b> {
     tx: &'a PooledConnection<'b, PostgresConnectionManager<NoTls>>,
@@ -324,6 +339,44 @@ impl<'a, 'b> QueryFromConnection<'a, 'b> {
         Ok(transaction_ids)
     }
 
+    pub async fn get_block_address_transactions(
+        &self,
+        block_height: BlockHeight,
+    ) -> Result<BTreeMap<String, BTreeSet<Id<Transaction>>>, ApiServerStorageError> {
+        let height = Self::block_height_to_postgres_friendly(block_height);
+
+        let rows = self
+            .tx
+            .query(
+                r#"
+                    SELECT address, transaction_id
+                    FROM ml.address_transactions
+                    WHERE block_height = $1;
+                "#,
+                &[&height],
+            )
+            .await
+            .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+
+        let mut address_transactions = BTreeMap::<String, BTreeSet<Id<Transaction>>>::new();
+
+        for row in &rows {
+            let address: String = row.get(0);
+            let transaction_id: Vec<u8> = row.get(1);
+            let transaction_id = Id::<Transaction>::decode_all(&mut transaction_id.as_slice())
+                .map_err(|e| {
+                    ApiServerStorageError::DeserializationError(format!(
+                        "Transaction id deserialization failed: {}",
+                        e
+                    ))
+                })?;
+
+            address_transactions.entry(address).or_default().insert(transaction_id);
+        }
+
+        Ok(address_transactions)
+    }
+
     pub async fn del_address_transactions_above_height(
         &mut self,
         block_height: BlockHeight,
@@ -341,6 +394,55 @@ impl<'a, 'b> QueryFromConnection<'a, 'b> {
         Ok(())
     }
 
+    pub async fn del_token_transactions_above_height(
+        &mut self,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError> {
+        let height = Self::block_height_to_postgres_friendly(block_height);
+
+        self.tx
+            .execute(
+                "DELETE FROM ml.token_transactions WHERE block_height > $1;",
+                &[&height],
+            )
+            .await
+            .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    pub async fn set_token_transactions_at_height(
+        &mut self,
+        token_id: TokenId,
+        transaction_ids: BTreeMap<u32, Id<Transaction>>,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError> {
+        let height = Self::block_height_to_postgres_friendly(block_height);
+
+        for (tx_index, transaction_id) in transaction_ids {
+            let tx_index: i32 = tx_index.try_into().map_err(|_| {
+                ApiServerStorageError::LowLevelStorageError(format!(
+                    "Invalid transaction index {tx_index}"
+                ))
+            })?;
+
+            self.tx
+                .execute(
+                    r#"
+                        INSERT INTO ml.token_transactions (token_id, block_height, tx_index, transaction_id)
+                        VALUES ($1, $2, $3, $4)
+                        ON CONFLICT (token_id, block_height, tx_index)
+                        DO NOTHING;
+                    "#,
+                    &[&token_id.encode(), &height, &tx_index, &transaction_id.encode()],
+                )
+                .await
+                .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+
     pub async fn set_address_transactions_at_height(
         &mut self,
         address: &str,
@@ -512,6 +614,20 @@ impl<'a, 'b> QueryFromConnection<'a, 'b> {
         )
         .await?;
 
+        self.just_execute(
+            "CREATE TABLE ml.data_deposits (
+                    transaction_id bytea NOT NULL,
+                    output_index integer NOT NULL,
+                    data bytea NOT NULL,
+                    PRIMARY KEY (transaction_id, output_index)
+                );",
+        )
+        .await?;
+
+        // index when searching for data deposits by prefix
+        self.just_execute("CREATE INDEX data_deposits_data_index ON ml.data_deposits (data);")
+            .await?;
+
         self.just_execute(
             "CREATE TABLE ml.address_balance (
                     address TEXT NOT NULL,
@@ -544,6 +660,29 @@ impl<'a, 'b> QueryFromConnection<'a, 'b> {
         )
         .await?;
 
+        // index for the webhook notifications of new blocks
+        self.just_execute(
+            "CREATE INDEX address_transactions_block_height_index ON ml.address_transactions (block_height);",
+        )
+        .await?;
+
+        self.just_execute(
+            "CREATE TABLE ml.token_transactions (
+                    token_id bytea NOT NULL,
+                    block_height bigint NOT NULL,
+                    tx_index integer NOT NULL,
+                    transaction_id bytea NOT NULL,
+                    PRIMARY KEY (token_id, block_height, tx_index)
+                );",
+        )
+        .await?;
+
+        // index when disconnecting blocks
+        self.just_execute(
+            "CREATE INDEX token_transactions_block_height_index ON ml.token_transactions (block_height);",
+        )
+        .await?;
+
         self.just_execute(
             "CREATE TABLE ml.utxo (
                     outpoint bytea NOT NULL,
@@ -653,6 +792,25 @@ impl<'a, 'b> QueryFromConnection<'a, 'b> {
         )
         .await?;
 
+        self.just_execute(
+            "CREATE TABLE ml.webhook_subscriptions (
+                    webhook_id bigserial PRIMARY KEY,
+                    subscription bytea NOT NULL
+                );",
+        )
+        .await?;
+
+        self.just_execute(
+            "CREATE TABLE ml.address_token_allowances (
+                    address TEXT NOT NULL,
+                    token_id bytea NOT NULL,
+                    spender TEXT NOT NULL,
+                    allowance bytea NOT NULL,
+                    PRIMARY KEY (address, token_id, spender)
+                );",
+        )
+        .await?;
+
         logging::log::info!("Done creating database tables");
 
         Ok(())
@@ -1476,6 +1634,61 @@ impl<'a, 'b> QueryFromConnection<'a, 'b> {
             .collect()
     }
 
+    pub async fn get_token_transactions(
+        &self,
+        token_id: TokenId,
+        len: u32,
+        offset: u32,
+    ) -> Result<Vec<(BlockAuxData, TransactionInfo)>, ApiServerStorageError> {
+        let len = len as i64;
+        let offset = offset as i64;
+        let rows = self
+            .tx
+            .query(
+                r#"
+                SELECT
+                    t.transaction_data,
+                    b.aux_data
+                FROM
+                    ml.token_transactions tt
+                INNER JOIN
+                    ml.transactions t ON t.transaction_id = tt.transaction_id
+                INNER JOIN
+                    ml.block_aux_data b ON t.owning_block_id = b.block_id
+                WHERE tt.token_id = $1
+                ORDER BY tt.block_height DESC, tt.tx_index DESC
+                OFFSET $2
+                LIMIT $3;
+                "#,
+                &[&token_id.encode(), &offset, &len],
+            )
+            .await
+            .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+
+        rows.into_iter()
+            .map(|data| {
+                let transaction_data: Vec<u8> = data.get(0);
+                let block_data: Vec<u8> = data.get(1);
+
+                let block_data =
+                    BlockAuxData::decode_all(&mut block_data.as_slice()).map_err(|e| {
+                        ApiServerStorageError::DeserializationError(format!(
+                            "Block deserialization failed: {e}"
+                        ))
+                    })?;
+
+                let transaction = TransactionInfo::decode_all(&mut transaction_data.as_slice())
+                    .map_err(|e| {
+                        ApiServerStorageError::DeserializationError(format!(
+                            "Transaction deserialization failed: {e}"
+                        ))
+                    })?;
+
+                Ok((block_data, transaction))
+            })
+            .collect()
+    }
+
     pub async fn set_transaction(
         &mut self,
         transaction_id: Id<Transaction>,
@@ -1495,6 +1708,44 @@ impl<'a, 'b> QueryFromConnection<'a, 'b> {
             ).await
             .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
 
+        self.tx
+            .execute(
+                "DELETE FROM ml.data_deposits WHERE transaction_id = $1;",
+                &[&transaction_id.encode()],
+            )
+            .await
+            .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+
+        for (output_index, output) in transaction.tx.outputs().iter().enumerate() {
+            let data = match output {
+                TxOutput::DataDeposit(data) => data,
+                TxOutput::Transfer(_, _)
+                | TxOutput::LockThenTransfer(_, _, _)
+                | TxOutput::Burn(_)
+                | TxOutput::CreateStakePool(_, _)
+                | TxOutput::ProduceBlockFromStake(_, _)
+                | TxOutput::CreateDelegationId(_, _)
+                | TxOutput::DelegateStaking(_, _)
+                | TxOutput::IssueFungibleToken(_)
+                | TxOutput::IssueNft(_, _, _)
+                | TxOutput::Htlc(_, _)
+                | TxOutput::AnyoneCanTake(_) => continue,
+            };
+            let output_index: i32 = output_index.try_into().map_err(|_| {
+                ApiServerStorageError::LowLevelStorageError(format!(
+                    "Invalid output index {output_index}"
+                ))
+            })?;
+
+            self.tx
+                .execute(
+                    "INSERT INTO ml.data_deposits (transaction_id, output_index, data) VALUES ($1, $2, $3);",
+                    &[&transaction_id.encode(), &output_index, data],
+                )
+                .await
+                .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+        }
+
         Ok(())
     }
 
@@ -1892,6 +2143,62 @@ impl<'a, 'b> QueryFromConnection<'a, 'b> {
             .collect()
     }
 
+    pub async fn get_data_deposits_by_prefix(
+        &self,
+        len: u32,
+        offset: u32,
+        prefix: &[u8],
+    ) -> Result<Vec<DataDepositInfo>, ApiServerStorageError> {
+        let len = len as i64;
+        let offset = offset as i64;
+        // Search a range of the data index instead of matching the prefix on each row
+        let upper_bound = prefix_upper_bound(prefix);
+        self.tx
+            .query(
+                r#"
+                SELECT
+                    d.transaction_id,
+                    mb.block_height,
+                    d.data
+                FROM
+                    ml.data_deposits d
+                INNER JOIN
+                    ml.transactions t ON t.transaction_id = d.transaction_id
+                INNER JOIN
+                    ml.blocks mb ON t.owning_block_id = mb.block_id
+                WHERE mb.block_height IS NOT NULL
+                    AND d.data >= $3
+                    AND ($4::bytea IS NULL OR d.data < $4)
+                ORDER BY mb.block_height DESC, d.transaction_id, d.output_index
+                OFFSET $1
+                LIMIT $2;
+                "#,
+                &[&offset, &len, &prefix, &upper_bound],
+            )
+            .await
+            .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?
+            .into_iter()
+            .map(|row| -> Result<DataDepositInfo, ApiServerStorageError> {
+                let tx_id: Vec<u8> = row.get(0);
+                let block_height: i64 = row.get(1);
+                let data: Vec<u8> = row.get(2);
+
+                let tx_id = Id::<Transaction>::decode_all(&mut tx_id.as_slice()).map_err(|e| {
+                    ApiServerStorageError::DeserializationError(format!(
+                        "Transaction id deserialization failed: {e}"
+                    ))
+                })?;
+                let block_height = BlockHeight::new(block_height as u64);
+
+                Ok(DataDepositInfo {
+                    tx_id,
+                    block_height,
+                    data,
+                })
+            })
+            .collect()
+    }
+
     pub async fn get_statistic(
         &self,
         statistic: CoinOrTokenStatistic,
@@ -1987,6 +2294,184 @@ impl<'a, 'b> QueryFromConnection<'a, 'b> {
         Ok(())
     }
 
+    pub async fn get_webhook_subscriptions(
+        &self,
+    ) -> Result<BTreeMap<u64, WebhookSubscription>, ApiServerStorageError> {
+        let rows = self
+            .tx
+            .query(
+                "SELECT webhook_id, subscription FROM ml.webhook_subscriptions;",
+                &[],
+            )
+            .await
+            .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let id: i64 = row.get(0);
+                let subscription: Vec<u8> = row.get(1);
+
+                let subscription = WebhookSubscription::decode_all(&mut subscription.as_slice())
+                    .map_err(|e| {
+                        ApiServerStorageError::DeserializationError(format!(
+                            "Webhook subscription {id} deserialization failed: {e}"
+                        ))
+                    })?;
+
+                Ok((id as u64, subscription))
+            })
+            .collect()
+    }
+
+    pub async fn add_webhook_subscription(
+        &mut self,
+        subscription: &WebhookSubscription,
+        max_subscriptions: usize,
+    ) -> Result<Option<u64>, ApiServerStorageError> {
+        let max_subscriptions = i64::try_from(max_subscriptions).unwrap_or(i64::MAX);
+
+        let row = self
+            .tx
+            .query_opt(
+                "INSERT INTO ml.webhook_subscriptions (subscription)
+                    SELECT $1 WHERE (SELECT COUNT(*) FROM ml.webhook_subscriptions) < $2
+                    RETURNING webhook_id;",
+                &[&subscription.encode(), &max_subscriptions],
+            )
+            .await
+            .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+
+        Ok(row.map(|row| {
+            let id: i64 = row.get(0);
+            id as u64
+        }))
+    }
+
+    pub async fn get_webhook_cursor(&self) -> Result<Option<BlockHeight>, ApiServerStorageError> {
+        let row = self
+            .tx
+            .query_opt(
+                "SELECT value FROM ml.misc_data WHERE name = $1;",
+                &[&WEBHOOK_CURSOR_STR],
+            )
+            .await
+            .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+
+        row.map(|row| {
+            let data: Vec<u8> = row.get(0);
+            BlockHeight::decode_all(&mut data.as_slice()).map_err(|e| {
+                ApiServerStorageError::DeserializationError(format!(
+                    "Webhook cursor deserialization failed: {e}"
+                ))
+            })
+        })
+        .transpose()
+    }
+
+    pub async fn set_webhook_cursor(
+        &mut self,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError> {
+        self.tx
+            .execute(
+                "INSERT INTO ml.misc_data (name, value) VALUES ($1, $2)
+                    ON CONFLICT (name) DO UPDATE SET value = $2;",
+                &[&WEBHOOK_CURSOR_STR, &block_height.encode()],
+            )
+            .await
+            .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    pub async fn del_webhook_subscription(
+        &mut self,
+        id: u64,
+    ) -> Result<bool, ApiServerStorageError> {
+        let id = i64::try_from(id).unwrap_or(i64::MAX);
+
+        let deleted = self
+            .tx
+            .execute(
+                "DELETE FROM ml.webhook_subscriptions WHERE webhook_id = $1;",
+                &[&id],
+            )
+            .await
+            .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+
+        Ok(deleted > 0)
+    }
+
+    pub async fn get_address_token_allowances(
+        &self,
+        address: &str,
+    ) -> Result<Vec<TokenAllowance>, ApiServerStorageError> {
+        let rows = self
+            .tx
+            .query(
+                r#"
+                    SELECT token_id, spender, allowance
+                    FROM ml.address_token_allowances
+                    WHERE address = $1
+                    ORDER BY token_id, spender;
+                "#,
+                &[&address],
+            )
+            .await
+            .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let token_id: Vec<u8> = row.get(0);
+                let spender: String = row.get(1);
+                let allowance: Vec<u8> = row.get(2);
+
+                let token_id = TokenId::decode_all(&mut token_id.as_slice()).map_err(|e| {
+                    ApiServerStorageError::DeserializationError(format!(
+                        "Token id deserialization failed: {e}"
+                    ))
+                })?;
+                let allowance = Amount::decode_all(&mut allowance.as_slice()).map_err(|e| {
+                    ApiServerStorageError::DeserializationError(format!(
+                        "Amount deserialization failed: {e}"
+                    ))
+                })?;
+
+                Ok(TokenAllowance {
+                    token_id,
+                    spender,
+                    allowance,
+                })
+            })
+            .collect()
+    }
+
+    pub async fn set_address_token_allowance(
+        &mut self,
+        address: &str,
+        allowance: &TokenAllowance,
+    ) -> Result<(), ApiServerStorageError> {
+        self.tx
+            .execute(
+                r#"
+                    INSERT INTO ml.address_token_allowances (address, token_id, spender, allowance)
+                    VALUES ($1, $2, $3, $4)
+                    ON CONFLICT (address, token_id, spender) DO UPDATE
+                    SET allowance = $4;
+                "#,
+                &[
+                    &address,
+                    &allowance.token_id.encode(),
+                    &allowance.spender,
+                    &allowance.allowance.encode(),
+                ],
+            )
+            .await
+            .map_err(|e| ApiServerStorageError::LowLevelStorageError(e.to_string()))?;
+
+        Ok(())
+    }
+
     pub async fn del_statistics_above_height(
         &mut self,
         block_height: BlockHeight,
@@ -27,8 +27,9 @@ use pos_accounting::PoolData;
 use tokio_postgres::NoTls;
 
 use crate::storage::storage_api::{
-    block_aux_data::BlockAuxData, ApiServerStorage, ApiServerStorageError, ApiServerTransactionRo,
-    ApiServerTransactionRw, BlockInfo, TransactionInfo, Transactional,
+    block_aux_data::BlockAuxData, ApiServerStorage, ApiServerStorageError, ApiServerStorageWrite,
+    ApiServerTransactionRo, ApiServerTransactionRw, ApiServerWebhookStorage, BlockInfo,
+    TransactionInfo, Transactional, WebhookSubscription,
 };
 
 use super::{queries::QueryFromConnection, TransactionalApiServerPostgresStorage};
@@ -266,4 +267,36 @@ impl<'tx> Transactional<'tx> for TransactionalApiServerPostgresStorage {
     }
 }
 
+#[async_trait::async_trait]
+impl ApiServerWebhookStorage for TransactionalApiServerPostgresStorage {
+    async fn add_webhook_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+        max_subscriptions: usize,
+    ) -> Result<Option<u64>, ApiServerStorageError> {
+        let mut db_tx = self.begin_rw_transaction().await?;
+        let id = db_tx.add_webhook_subscription(subscription, max_subscriptions).await?;
+        db_tx.commit().await?;
+
+        Ok(id)
+    }
+
+    async fn del_webhook_subscription(&self, id: u64) -> Result<bool, ApiServerStorageError> {
+        let mut db_tx = self.begin_rw_transaction().await?;
+        let deleted = db_tx.del_webhook_subscription(id).await?;
+        db_tx.commit().await?;
+
+        Ok(deleted)
+    }
+
+    async fn set_webhook_cursor(
+        &self,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError> {
+        let mut db_tx = self.begin_rw_transaction().await?;
+        db_tx.set_webhook_cursor(block_height).await?;
+        db_tx.commit().await
+    }
+}
+
 impl ApiServerStorage for TransactionalApiServerPostgresStorage {}
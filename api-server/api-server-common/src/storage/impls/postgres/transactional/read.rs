@@ -26,11 +26,11 @@ use crate::storage::{
     impls::postgres::queries::QueryFromConnection,
     storage_api::{
         block_aux_data::BlockAuxData, ApiServerStorageError, ApiServerStorageRead, BlockInfo,
-        CoinOrTokenStatistic, Delegation, FungibleTokenData, PoolBlockStats, TransactionInfo, Utxo,
-        UtxoWithExtraInfo,
+        CoinOrTokenStatistic, DataDepositInfo, Delegation, FungibleTokenData, PoolBlockStats,
+        TokenAllowance, TransactionInfo, Utxo, UtxoWithExtraInfo, WebhookSubscription,
     },
 };
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use common::chain::UtxoOutPoint;
 use pos_accounting::PoolData;
@@ -85,6 +85,17 @@ impl<'a> ApiServerStorageRead for ApiServerPostgresTransactionalRo<'a> {
         Ok(res)
     }
 
+    async fn get_block_address_transactions(
+        &self,
+        block_height: BlockHeight,
+    ) -> Result<BTreeMap<String, BTreeSet<Id<common::chain::Transaction>>>, ApiServerStorageError>
+    {
+        let conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        let res = conn.get_block_address_transactions(block_height).await?;
+
+        Ok(res)
+    }
+
     async fn get_latest_blocktimestamps(
         &self,
     ) -> Result<Vec<BlockTimestamp>, ApiServerStorageError> {
@@ -196,6 +207,18 @@ impl<'a> ApiServerStorageRead for ApiServerPostgresTransactionalRo<'a> {
         Ok(res)
     }
 
+    async fn get_token_transactions(
+        &self,
+        token_id: TokenId,
+        len: u32,
+        offset: u32,
+    ) -> Result<Vec<(BlockAuxData, TransactionInfo)>, ApiServerStorageError> {
+        let conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        let res = conn.get_token_transactions(token_id, len, offset).await?;
+
+        Ok(res)
+    }
+
     async fn get_pool_data(
         &self,
         pool_id: PoolId,
@@ -345,6 +368,18 @@ impl<'a> ApiServerStorageRead for ApiServerPostgresTransactionalRo<'a> {
         Ok(res)
     }
 
+    async fn get_data_deposits_by_prefix(
+        &self,
+        len: u32,
+        offset: u32,
+        prefix: &[u8],
+    ) -> Result<Vec<DataDepositInfo>, ApiServerStorageError> {
+        let conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        let res = conn.get_data_deposits_by_prefix(len, offset, prefix).await?;
+
+        Ok(res)
+    }
+
     async fn get_statistic(
         &self,
         statistic: CoinOrTokenStatistic,
@@ -365,4 +400,30 @@ impl<'a> ApiServerStorageRead for ApiServerPostgresTransactionalRo<'a> {
 
         Ok(res)
     }
+
+    async fn get_webhook_subscriptions(
+        &self,
+    ) -> Result<BTreeMap<u64, WebhookSubscription>, ApiServerStorageError> {
+        let conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        let res = conn.get_webhook_subscriptions().await?;
+
+        Ok(res)
+    }
+
+    async fn get_webhook_cursor(&self) -> Result<Option<BlockHeight>, ApiServerStorageError> {
+        let conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        let res = conn.get_webhook_cursor().await?;
+
+        Ok(res)
+    }
+
+    async fn get_address_token_allowances(
+        &self,
+        address: &str,
+    ) -> Result<Vec<TokenAllowance>, ApiServerStorageError> {
+        let conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        let res = conn.get_address_token_allowances(address).await?;
+
+        Ok(res)
+    }
 }
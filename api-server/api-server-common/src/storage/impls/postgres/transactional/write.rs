@@ -30,8 +30,9 @@ use crate::storage::{
     storage_api::{
         block_aux_data::{BlockAuxData, BlockWithExtraData},
         ApiServerStorageError, ApiServerStorageRead, ApiServerStorageWrite, BlockInfo,
-        CoinOrTokenStatistic, Delegation, FungibleTokenData, LockedUtxo, PoolBlockStats,
-        TransactionInfo, Utxo, UtxoWithExtraInfo,
+        CoinOrTokenStatistic, DataDepositInfo, Delegation, FungibleTokenData, LockedUtxo,
+        PoolBlockStats, TokenAllowance, TransactionInfo, Utxo, UtxoWithExtraInfo,
+        WebhookSubscription,
     },
 };
 
@@ -79,6 +80,16 @@ impl<'a> ApiServerStorageWrite for ApiServerPostgresTransactionalRw<'a> {
         Ok(())
     }
 
+    async fn del_token_transactions_above_height(
+        &mut self,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError> {
+        let mut conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        conn.del_token_transactions_above_height(block_height).await?;
+
+        Ok(())
+    }
+
     async fn set_address_balance_at_height(
         &mut self,
         address: &str,
@@ -120,6 +131,19 @@ impl<'a> ApiServerStorageWrite for ApiServerPostgresTransactionalRw<'a> {
         Ok(())
     }
 
+    async fn set_token_transactions_at_height(
+        &mut self,
+        token_id: TokenId,
+        transaction_ids: BTreeMap<u32, Id<Transaction>>,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError> {
+        let mut conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        conn.set_token_transactions_at_height(token_id, transaction_ids, block_height)
+            .await?;
+
+        Ok(())
+    }
+
     async fn set_mainchain_block(
         &mut self,
         block_id: Id<Block>,
@@ -323,6 +347,45 @@ impl<'a> ApiServerStorageWrite for ApiServerPostgresTransactionalRw<'a> {
 
         Ok(())
     }
+
+    async fn add_webhook_subscription(
+        &mut self,
+        subscription: &WebhookSubscription,
+        max_subscriptions: usize,
+    ) -> Result<Option<u64>, ApiServerStorageError> {
+        let mut conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        let res = conn.add_webhook_subscription(subscription, max_subscriptions).await?;
+
+        Ok(res)
+    }
+
+    async fn del_webhook_subscription(&mut self, id: u64) -> Result<bool, ApiServerStorageError> {
+        let mut conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        let res = conn.del_webhook_subscription(id).await?;
+
+        Ok(res)
+    }
+
+    async fn set_webhook_cursor(
+        &mut self,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError> {
+        let mut conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        conn.set_webhook_cursor(block_height).await?;
+
+        Ok(())
+    }
+
+    async fn set_address_token_allowance(
+        &mut self,
+        address: &str,
+        allowance: &TokenAllowance,
+    ) -> Result<(), ApiServerStorageError> {
+        let mut conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        conn.set_address_token_allowance(address, allowance).await?;
+
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -373,6 +436,16 @@ impl<'a> ApiServerStorageRead for ApiServerPostgresTransactionalRw<'a> {
         Ok(res)
     }
 
+    async fn get_block_address_transactions(
+        &self,
+        block_height: BlockHeight,
+    ) -> Result<BTreeMap<String, BTreeSet<Id<Transaction>>>, ApiServerStorageError> {
+        let conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        let res = conn.get_block_address_transactions(block_height).await?;
+
+        Ok(res)
+    }
+
     async fn get_latest_blocktimestamps(
         &self,
     ) -> Result<Vec<BlockTimestamp>, ApiServerStorageError> {
@@ -460,6 +533,18 @@ impl<'a> ApiServerStorageRead for ApiServerPostgresTransactionalRw<'a> {
         Ok(res)
     }
 
+    async fn get_token_transactions(
+        &self,
+        token_id: TokenId,
+        len: u32,
+        offset: u32,
+    ) -> Result<Vec<(BlockAuxData, TransactionInfo)>, ApiServerStorageError> {
+        let conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        let res = conn.get_token_transactions(token_id, len, offset).await?;
+
+        Ok(res)
+    }
+
     async fn get_pool_data(
         &self,
         pool_id: PoolId,
@@ -629,6 +714,18 @@ impl<'a> ApiServerStorageRead for ApiServerPostgresTransactionalRw<'a> {
         Ok(res)
     }
 
+    async fn get_data_deposits_by_prefix(
+        &self,
+        len: u32,
+        offset: u32,
+        prefix: &[u8],
+    ) -> Result<Vec<DataDepositInfo>, ApiServerStorageError> {
+        let conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        let res = conn.get_data_deposits_by_prefix(len, offset, prefix).await?;
+
+        Ok(res)
+    }
+
     async fn get_statistic(
         &self,
         statistic: CoinOrTokenStatistic,
@@ -649,4 +746,30 @@ impl<'a> ApiServerStorageRead for ApiServerPostgresTransactionalRw<'a> {
 
         Ok(res)
     }
+
+    async fn get_webhook_subscriptions(
+        &self,
+    ) -> Result<BTreeMap<u64, WebhookSubscription>, ApiServerStorageError> {
+        let conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        let res = conn.get_webhook_subscriptions().await?;
+
+        Ok(res)
+    }
+
+    async fn get_webhook_cursor(&self) -> Result<Option<BlockHeight>, ApiServerStorageError> {
+        let conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        let res = conn.get_webhook_cursor().await?;
+
+        Ok(res)
+    }
+
+    async fn get_address_token_allowances(
+        &self,
+        address: &str,
+    ) -> Result<Vec<TokenAllowance>, ApiServerStorageError> {
+        let conn = QueryFromConnection::new(self.connection.as_ref().expect(CONN_ERR));
+        let res = conn.get_address_token_allowances(address).await?;
+
+        Ok(res)
+    }
 }
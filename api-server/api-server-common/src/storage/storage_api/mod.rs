@@ -379,12 +379,46 @@ pub struct PoolBlockStats {
     pub block_count: u64,
 }
 
+/// The data of a `DataDeposit` output of a transaction in the main chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataDepositInfo {
+    pub tx_id: Id<Transaction>,
+    pub block_height: BlockHeight,
+    pub data: Vec<u8>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BlockInfo {
     pub block: BlockWithExtraData,
     pub height: Option<BlockHeight>,
 }
 
+/// An event that webhook subscribers can be notified about
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Encode, Decode)]
+pub enum WebhookEvent {
+    /// A new block has been added to the main chain
+    Block,
+    /// A new main chain block contains transactions that involve the address
+    Address(String),
+}
+
+/// A request to POST the subscribed events to an external HTTP endpoint,
+/// signed with the secret
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct WebhookSubscription {
+    pub url: String,
+    pub events: BTreeSet<WebhookEvent>,
+    pub secret: String,
+}
+
+/// An amount of a token that the spender is allowed to spend on behalf of an address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenAllowance {
+    pub token_id: TokenId,
+    pub spender: String,
+    pub allowance: Amount,
+}
+
 #[async_trait::async_trait]
 pub trait ApiServerStorageRead: Sync {
     async fn is_initialized(&self) -> Result<bool, ApiServerStorageError>;
@@ -408,6 +442,13 @@ pub trait ApiServerStorageRead: Sync {
         address: &str,
     ) -> Result<Vec<Id<Transaction>>, ApiServerStorageError>;
 
+    /// The transactions of the main chain block at the height, grouped by the addresses
+    /// they involve
+    async fn get_block_address_transactions(
+        &self,
+        block_height: BlockHeight,
+    ) -> Result<BTreeMap<String, BTreeSet<Id<Transaction>>>, ApiServerStorageError>;
+
     async fn get_best_block(&self) -> Result<BlockAuxData, ApiServerStorageError>;
 
     async fn get_latest_blocktimestamps(
@@ -485,6 +526,16 @@ pub trait ApiServerStorageRead: Sync {
         offset: u32,
     ) -> Result<Vec<(BlockAuxData, TransactionInfo)>, ApiServerStorageError>;
 
+    /// Find the main chain transactions that reference the token in their inputs or outputs,
+    /// newest first, i.e. ordered by the block height and then by the index in the block,
+    /// both descending.
+    async fn get_token_transactions(
+        &self,
+        token_id: TokenId,
+        len: u32,
+        offset: u32,
+    ) -> Result<Vec<(BlockAuxData, TransactionInfo)>, ApiServerStorageError>;
+
     async fn get_utxo(&self, outpoint: UtxoOutPoint)
         -> Result<Option<Utxo>, ApiServerStorageError>;
 
@@ -537,6 +588,15 @@ pub trait ApiServerStorageRead: Sync {
         ticker: &[u8],
     ) -> Result<Vec<TokenId>, ApiServerStorageError>;
 
+    /// Find data deposits in the main chain whose data starts with the given prefix,
+    /// ordered from the highest block height to the lowest.
+    async fn get_data_deposits_by_prefix(
+        &self,
+        len: u32,
+        offset: u32,
+        prefix: &[u8],
+    ) -> Result<Vec<DataDepositInfo>, ApiServerStorageError>;
+
     async fn get_statistic(
         &self,
         statistic: CoinOrTokenStatistic,
@@ -547,6 +607,18 @@ pub trait ApiServerStorageRead: Sync {
         &self,
         coin_or_token_id: CoinOrTokenId,
     ) -> Result<BTreeMap<CoinOrTokenStatistic, Amount>, ApiServerStorageError>;
+
+    async fn get_webhook_subscriptions(
+        &self,
+    ) -> Result<BTreeMap<u64, WebhookSubscription>, ApiServerStorageError>;
+
+    /// The height of the last block whose webhook events have been delivered
+    async fn get_webhook_cursor(&self) -> Result<Option<BlockHeight>, ApiServerStorageError>;
+
+    async fn get_address_token_allowances(
+        &self,
+        address: &str,
+    ) -> Result<Vec<TokenAllowance>, ApiServerStorageError>;
 }
 
 #[async_trait::async_trait]
@@ -571,6 +643,11 @@ pub trait ApiServerStorageWrite: ApiServerStorageRead {
         block_height: BlockHeight,
     ) -> Result<(), ApiServerStorageError>;
 
+    async fn del_token_transactions_above_height(
+        &mut self,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError>;
+
     async fn set_address_balance_at_height(
         &mut self,
         address: &str,
@@ -594,6 +671,15 @@ pub trait ApiServerStorageWrite: ApiServerStorageRead {
         block_height: BlockHeight,
     ) -> Result<(), ApiServerStorageError>;
 
+    /// Store the transactions of the block at the given height that reference the token,
+    /// keyed by their index in the block
+    async fn set_token_transactions_at_height(
+        &mut self,
+        token_id: TokenId,
+        transaction_ids: BTreeMap<u32, Id<Transaction>>,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError>;
+
     async fn set_mainchain_block(
         &mut self,
         block_id: Id<Block>,
@@ -705,6 +791,30 @@ pub trait ApiServerStorageWrite: ApiServerStorageRead {
         &mut self,
         block_height: BlockHeight,
     ) -> Result<(), ApiServerStorageError>;
+
+    /// Store the subscription and return its newly assigned id,
+    /// returns None if there are already `max_subscriptions` subscriptions
+    async fn add_webhook_subscription(
+        &mut self,
+        subscription: &WebhookSubscription,
+        max_subscriptions: usize,
+    ) -> Result<Option<u64>, ApiServerStorageError>;
+
+    /// Remove the subscription, returns false if it doesn't exist
+    async fn del_webhook_subscription(&mut self, id: u64) -> Result<bool, ApiServerStorageError>;
+
+    async fn set_webhook_cursor(
+        &mut self,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError>;
+
+    /// Set the amount of the token that the spender can spend on behalf of the address,
+    /// replacing the previous allowance
+    async fn set_address_token_allowance(
+        &mut self,
+        address: &str,
+        allowance: &TokenAllowance,
+    ) -> Result<(), ApiServerStorageError>;
 }
 
 #[async_trait::async_trait]
@@ -737,4 +847,32 @@ pub trait Transactional<'tx> {
     ) -> Result<Self::TransactionRw, ApiServerStorageError>;
 }
 
-pub trait ApiServerStorage: for<'tx> Transactional<'tx> + Send + Sync {}
+/// Webhook management through a shared storage handle.
+///
+/// The scanner is the only user of [`Transactional::transaction_rw`], which needs exclusive access.
+/// The web server only shares its handle, so it manages the webhook subscriptions and their
+/// delivery cursor through this trait instead, where each call runs in its own read-write
+/// transaction.
+#[async_trait::async_trait]
+pub trait ApiServerWebhookStorage {
+    /// See [`ApiServerStorageWrite::add_webhook_subscription`]
+    async fn add_webhook_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+        max_subscriptions: usize,
+    ) -> Result<Option<u64>, ApiServerStorageError>;
+
+    /// See [`ApiServerStorageWrite::del_webhook_subscription`]
+    async fn del_webhook_subscription(&self, id: u64) -> Result<bool, ApiServerStorageError>;
+
+    /// See [`ApiServerStorageWrite::set_webhook_cursor`]
+    async fn set_webhook_cursor(
+        &self,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError>;
+}
+
+pub trait ApiServerStorage:
+    for<'tx> Transactional<'tx> + ApiServerWebhookStorage + Send + Sync
+{
+}
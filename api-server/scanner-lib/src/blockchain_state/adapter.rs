@@ -202,4 +202,12 @@ impl PoSAccountingOperations<()> for PoSAdapter {
     ) -> Result<(), pos_accounting::Error> {
         unimplemented!()
     }
+
+    fn slash_pool(
+        &mut self,
+        _pool_id: PoolId,
+        _slash_amount: Amount,
+    ) -> Result<(), pos_accounting::Error> {
+        unimplemented!()
+    }
 }
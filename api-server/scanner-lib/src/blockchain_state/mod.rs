@@ -163,8 +163,20 @@ impl<S: ApiServerStorage + Send + Sync> LocalBlockchainState for BlockchainState
                 tx_additional_infos,
             } = block_with_extras;
 
-            for (tx, additinal_info) in block.transactions().iter().zip(tx_additional_infos.iter())
+            let mut token_transactions: BTreeMap<TokenId, BTreeMap<u32, Id<Transaction>>> =
+                BTreeMap::new();
+
+            for (tx_index, (tx, additinal_info)) in
+                block.transactions().iter().zip(tx_additional_infos.iter()).enumerate()
             {
+                let tx_index = u32::try_from(tx_index).expect("block has too many transactions");
+                for token_id in get_tx_token_ids(tx.transaction(), additinal_info) {
+                    token_transactions
+                        .entry(token_id)
+                        .or_default()
+                        .insert(tx_index, tx.transaction().get_id());
+                }
+
                 update_tables_from_transaction(
                     Arc::clone(&self.chain_config),
                     &mut db_tx,
@@ -185,6 +197,13 @@ impl<S: ApiServerStorage + Send + Sync> LocalBlockchainState for BlockchainState
                     .expect("Unable to set transaction");
             }
 
+            for (token_id, transaction_ids) in token_transactions {
+                db_tx
+                    .set_token_transactions_at_height(token_id, transaction_ids, block_height)
+                    .await
+                    .expect("Unable to set token transactions");
+            }
+
             update_tables_from_block(
                 Arc::clone(&self.chain_config),
                 &mut db_tx,
@@ -308,6 +327,11 @@ async fn disconnect_tables_above_height<T: ApiServerStorageWrite>(
         .await
         .expect("Unable to disconnect address transactions");
 
+    db_tx
+        .del_token_transactions_above_height(block_height)
+        .await
+        .expect("Unable to disconnect token transactions");
+
     db_tx
         .del_utxo_above_height(block_height)
         .await
@@ -1920,6 +1944,36 @@ async fn set_utxo<T: ApiServerStorageWrite>(
     }
 }
 
+/// Collect the tokens that are referenced in the inputs or outputs of the transaction
+fn get_tx_token_ids(tx: &Transaction, additional_info: &TxAdditionalInfo) -> BTreeSet<TokenId> {
+    let value_token_id = |value: &OutputValue| match value {
+        OutputValue::TokenV1(token_id, _) => Some(*token_id),
+        OutputValue::Coin(_) | OutputValue::TokenV0(_) => None,
+    };
+
+    // the decimals are fetched for every token that is spent or referenced by an account command
+    let input_token_ids = additional_info.token_decimals.keys().copied();
+
+    let output_token_ids = tx.outputs().iter().flat_map(|output| match output {
+        TxOutput::Transfer(value, _)
+        | TxOutput::LockThenTransfer(value, _, _)
+        | TxOutput::Burn(value)
+        | TxOutput::Htlc(value, _) => vec![value_token_id(value)],
+        TxOutput::IssueNft(token_id, _, _) => vec![Some(*token_id)],
+        TxOutput::IssueFungibleToken(_) => vec![make_token_id(tx.inputs())],
+        TxOutput::AnyoneCanTake(order) => {
+            vec![value_token_id(order.ask()), value_token_id(order.give())]
+        }
+        TxOutput::CreateStakePool(_, _)
+        | TxOutput::ProduceBlockFromStake(_, _)
+        | TxOutput::CreateDelegationId(_, _)
+        | TxOutput::DelegateStaking(_, _)
+        | TxOutput::DataDeposit(_) => vec![],
+    });
+
+    input_token_ids.chain(output_token_ids.flatten()).collect()
+}
+
 fn get_tx_output_destination(txo: &TxOutput) -> Option<&Destination> {
     match txo {
         TxOutput::Transfer(_, d)
@@ -34,6 +34,8 @@ pub enum SyncError {
     NoNewBlocksFound,
     #[error("Invalid prev block id: {0}, expected: {1}")]
     InvalidPrevBlockId(Id<GenBlock>, Id<GenBlock>),
+    #[error("The remote node has pruned the blocks starting from height {0}, a node that keeps all the blocks is required")]
+    RemoteNodeBlocksPruned(BlockHeight),
     #[error("Attempted to sync the API server to a height that doesn't exist")]
     NotEnoughBlockHeight,
     #[error("Best block retrieval error {0}")]
@@ -141,10 +143,19 @@ async fn fetch_new_blocks(
     )
     .await?;
 
-    let blocks = rpc_client
-        .mainchain_blocks(common_block_height.next_height(), MAX_FETCH_BLOCK_COUNT)
-        .await
-        .map_err(|e| SyncError::RemoteNode(e.to_string()))?;
+    let from = common_block_height.next_height();
+    let blocks = match rpc_client.mainchain_blocks(from, MAX_FETCH_BLOCK_COUNT).await {
+        Ok(blocks) => blocks,
+        Err(err) => {
+            // Blocks are pruned from the bottom up, so it's enough to check the first one
+            let is_pruned = rpc_client.is_mainchain_block_pruned(from).await.unwrap_or(false);
+            return Err(if is_pruned {
+                SyncError::RemoteNodeBlocksPruned(from)
+            } else {
+                SyncError::RemoteNode(err.to_string())
+            });
+        }
+    };
     match blocks.first() {
         Some(block) => utils::ensure!(
             *block.header().prev_block_id() == common_block_id,
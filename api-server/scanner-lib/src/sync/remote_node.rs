@@ -40,6 +40,8 @@ pub trait RemoteNode {
         from: BlockHeight,
         max_count: usize,
     ) -> Result<Vec<Block>, Self::Error>;
+    /// Whether the node has the mainchain block at the given height, but not its body.
+    async fn is_mainchain_block_pruned(&self, height: BlockHeight) -> Result<bool, Self::Error>;
 
     async fn mempool_feerate_points(&self) -> Result<Vec<(usize, FeeRate)>, Self::Error>;
 }
@@ -67,6 +69,16 @@ impl RemoteNode for NodeRpcClient {
         self.get_mainchain_blocks(from, max_count).await
     }
 
+    async fn is_mainchain_block_pruned(&self, height: BlockHeight) -> Result<bool, Self::Error> {
+        let block_id = match self.get_block_id_at_height(height).await? {
+            Some(block_id) => block_id,
+            None => return Ok(false),
+        };
+        // Note: the genesis is never at a non-zero height
+        let block = self.get_block(Id::new(block_id.to_hash())).await?;
+        Ok(block.is_none())
+    }
+
     async fn mempool_feerate_points(&self) -> Result<Vec<(usize, FeeRate)>, Self::Error> {
         self.mempool_get_fee_rate_points().await
     }
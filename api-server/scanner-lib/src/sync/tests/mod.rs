@@ -174,6 +174,15 @@ impl RemoteNode for MockRemoteNode {
             .unwrap())
     }
 
+    async fn is_mainchain_block_pruned(&self, height: BlockHeight) -> Result<bool, Self::Error> {
+        let tf = self.tf.lock().unwrap();
+        let block_id = tf.chainstate.get_block_id_from_height(&height).unwrap();
+        Ok(block_id.is_some_and(|block_id| {
+            let block_id = tf.to_chain_block_id(&block_id);
+            tf.chainstate.get_block(block_id).unwrap().is_none()
+        }))
+    }
+
     async fn mempool_feerate_points(&self) -> Result<Vec<(usize, FeeRate)>, Self::Error> {
         Ok(vec![(
             1,
@@ -17,11 +17,12 @@ mod v2;
 
 use api_server_common::storage::impls::in_memory::transactional::TransactionalApiServerInMemoryStorage;
 use api_web_server::{api::web_server, ApiServerWebServerState, CachedValues, TxSubmitClient};
+use chainstate::ChainFork;
 use common::{
-    chain::{config::create_unit_test_config, SignedTransaction},
-    primitives::time::get_time,
+    chain::{config::create_unit_test_config, SignedTransaction, Transaction},
+    primitives::{time::get_time, Id},
 };
-use mempool::FeeRate;
+use mempool::{FeeRate, MempoolTxInfo};
 use node_comm::rpc_client::NodeRpcError;
 use std::sync::{Arc, RwLock};
 use tokio::net::TcpListener;
@@ -37,6 +38,21 @@ impl TxSubmitClient for DummyRPC {
     async fn get_feerate_points(&self) -> Result<Vec<(usize, FeeRate)>, NodeRpcError> {
         Ok(vec![])
     }
+
+    async fn get_chain_forks(&self) -> Result<Vec<ChainFork>, NodeRpcError> {
+        Ok(vec![])
+    }
+
+    async fn get_mempool_tx(
+        &self,
+        _: Id<Transaction>,
+    ) -> Result<Option<SignedTransaction>, NodeRpcError> {
+        Ok(None)
+    }
+
+    async fn get_mempool_tx_info(&self) -> Result<Vec<MempoolTxInfo>, NodeRpcError> {
+        Ok(vec![])
+    }
 }
 
 pub async fn spawn_webserver(url: &str) -> (tokio::task::JoinHandle<()>, reqwest::Response) {
@@ -54,8 +70,11 @@ pub async fn spawn_webserver(url: &str) -> (tokio::task::JoinHandle<()>, reqwest
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
@@ -87,7 +106,10 @@ async fn bad_request() {
     let (task, response) = spawn_webserver("/non-existent-url").await;
 
     assert_eq!(response.status(), 400);
-    assert_eq!(response.text().await.unwrap(), r#"{"error":"Bad request"}"#);
+    assert_eq!(
+        response.text().await.unwrap(),
+        r#"{"type":"https://mintlayer.org/errors/bad-request","title":"Bad Request","status":400,"detail":"Bad request"}"#
+    );
 
     task.abort();
 }
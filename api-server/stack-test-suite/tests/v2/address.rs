@@ -31,7 +31,33 @@ async fn invalid_address() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid address");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid address");
+
+    task.abort();
+}
+
+#[tokio::test]
+async fn invalid_address_problem_detail() {
+    let (task, response) = spawn_webserver("/api/v2/address/invalid-address").await;
+
+    assert_eq!(response.status(), 400);
+    assert_eq!(
+        response.headers().get("Content-Type").unwrap(),
+        "application/problem+json"
+    );
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(
+        body,
+        json!({
+            "type": "https://mintlayer.org/errors/invalid-address",
+            "title": "Bad Request",
+            "status": 400,
+            "detail": "Invalid address",
+        })
+    );
 
     task.abort();
 }
@@ -55,7 +81,7 @@ async fn address_not_found(#[case] seed: Seed) {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Address not found");
+    assert_eq!(body["detail"].as_str().unwrap(), "Address not found");
 
     task.abort();
 }
@@ -267,8 +293,11 @@ async fn multiple_outputs_to_single_address(#[case] seed: Seed) {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
@@ -516,8 +545,11 @@ async fn test_unlocking_for_locked_utxos(#[case] seed: Seed) {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
@@ -741,8 +773,11 @@ async fn ok(#[case] seed: Seed) {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
@@ -31,7 +31,7 @@ async fn invalid_address() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid address");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid address");
 
     task.abort();
 }
@@ -280,8 +280,11 @@ async fn multiple_utxos_to_single_address(#[case] seed: Seed) {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
@@ -525,8 +528,11 @@ async fn ok(#[case] seed: Seed) {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
@@ -0,0 +1,148 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use api_server_common::storage::storage_api::{FungibleTokenData, TokenAllowance};
+use api_web_server::api::json_helpers::amount_to_json;
+use common::{
+    chain::tokens::{IsTokenFreezable, IsTokenFrozen, TokenId, TokenTotalSupply},
+    primitives::H256,
+};
+use randomness::CryptoRng;
+
+use crate::DummyRPC;
+
+use super::*;
+
+fn random_address(
+    rng: &mut (impl Rng + CryptoRng),
+    chain_config: &common::chain::ChainConfig,
+) -> String {
+    let (_, public_key) = PrivateKey::new_from_rng(rng, KeyKind::Secp256k1Schnorr);
+    let destination = Destination::PublicKeyHash(PublicKeyHash::from(&public_key));
+    Address::<Destination>::new(chain_config, destination).unwrap().into_string()
+}
+
+#[tokio::test]
+async fn invalid_address() {
+    let (task, response) =
+        spawn_webserver("/api/v2/address/invalid-address/token-allowances").await;
+
+    assert_eq!(response.status(), 400);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid address");
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn ok(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_unit_test_config());
+    let mut db = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+    let owner = random_address(&mut rng, &chain_config);
+    let other = random_address(&mut rng, &chain_config);
+
+    let mut db_tx = db.transaction_rw().await.unwrap();
+    let mut tokens = vec![];
+    for _ in 0..2 {
+        let token_id = TokenId::new(H256::random_using(&mut rng));
+        let decimals = rng.gen_range(1..18);
+        let token_data = FungibleTokenData {
+            token_ticker: "XXXX".as_bytes().to_vec(),
+            number_of_decimals: decimals,
+            metadata_uri: "http://uri".as_bytes().to_vec(),
+            circulating_supply: Amount::ZERO,
+            total_supply: TokenTotalSupply::Unlimited,
+            is_locked: false,
+            frozen: IsTokenFrozen::No(IsTokenFreezable::Yes),
+            authority: Destination::AnyoneCanSpend,
+        };
+        db_tx
+            .set_fungible_token_issuance(token_id, BlockHeight::new(1), token_data)
+            .await
+            .unwrap();
+        tokens.push((token_id, decimals));
+    }
+
+    let mut allowances = tokens
+        .iter()
+        .map(|(token_id, decimals)| {
+            let allowance = TokenAllowance {
+                token_id: *token_id,
+                spender: random_address(&mut rng, &chain_config),
+                allowance: Amount::from_atoms(rng.gen_range(1..1_000_000)),
+            };
+            (allowance, *decimals)
+        })
+        .collect::<Vec<_>>();
+    for (allowance, _) in &allowances {
+        db_tx.set_address_token_allowance(&owner, allowance).await.unwrap();
+    }
+    db_tx.commit().await.unwrap();
+
+    allowances.sort_by(|(a, _), (b, _)| (a.token_id, &a.spender).cmp(&(b.token_id, &b.spender)));
+    let expected = allowances
+        .iter()
+        .map(|(allowance, decimals)| {
+            json!({
+                "token_id": Address::new(&chain_config, allowance.token_id).unwrap().as_str(),
+                "spender": allowance.spender,
+                "allowance": amount_to_json(allowance.allowance, *decimals),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let task = tokio::spawn(async move {
+        let web_server_state = ApiServerWebServerState {
+            db: Arc::new(db),
+            chain_config,
+            rpc: Arc::new(DummyRPC {}),
+            cached_values: Arc::new(CachedValues {
+                feerate_points: RwLock::new((get_time(), vec![])),
+                mempool_tx_info: RwLock::new((get_time(), vec![])),
+            }),
+            time_getter: Default::default(),
+            cors_allowed_origins: Default::default(),
+            page_size_limits: Default::default(),
+        };
+
+        web_server(listener, web_server_state, true).await.unwrap();
+    });
+
+    for (address, expected) in [(owner, json!(expected)), (other, json!([]))] {
+        let url = format!(
+            "http://{}:{}/api/v2/address/{address}/token-allowances",
+            addr.ip(),
+            addr.port()
+        );
+        let response = reqwest::get(url).await.unwrap();
+        assert_eq!(response.status(), 200);
+
+        let body = response.text().await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body, expected);
+    }
+
+    task.abort();
+}
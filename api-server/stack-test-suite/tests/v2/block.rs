@@ -39,7 +39,7 @@ async fn invalid_block_id() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid block Id");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid block Id");
 
     task.abort();
 }
@@ -56,7 +56,7 @@ async fn block_not_found() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Block not found");
+    assert_eq!(body["detail"].as_str().unwrap(), "Block not found");
 
     task.abort();
 }
@@ -227,8 +227,11 @@ async fn ok(#[case] seed: Seed) {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
@@ -247,17 +250,31 @@ async fn ok(#[case] seed: Seed) {
 
     assert_eq!(response.status(), 200);
 
+    let etag = response.headers().get(reqwest::header::ETAG).unwrap().clone();
+
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
     assert_eq!(body, new_expected_block);
 
+    // The block is on the main chain, so the client can keep using its copy
+    let response = reqwest::Client::new()
+        .get(format!("http://{}:{}{url}", addr.ip(), addr.port()))
+        .header(reqwest::header::IF_NONE_MATCH, etag)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 304);
+
     let url = format!("/api/v2/block/{old_block_id}");
     let response = reqwest::get(format!("http://{}:{}{url}", addr.ip(), addr.port()))
         .await
         .unwrap();
 
     assert_eq!(response.status(), 200);
+    // The block is not on the main chain anymore, but may get reconnected
+    assert!(response.headers().get(reqwest::header::ETAG).is_none());
 
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
@@ -34,7 +34,7 @@ async fn invalid_block_id() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid block Id");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid block Id");
 
     task.abort();
 }
@@ -51,7 +51,7 @@ async fn block_not_found() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Block not found");
+    assert_eq!(body["detail"].as_str().unwrap(), "Block not found");
 
     task.abort();
 }
@@ -135,8 +135,11 @@ async fn ok(#[case] seed: Seed) {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
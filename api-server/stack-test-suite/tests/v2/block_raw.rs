@@ -0,0 +1,78 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::str::FromStr;
+
+use common::chain::Block;
+use serialization::hex_encoded::HexEncoded;
+
+use super::{
+    helpers::{get_raw, spawn_webserver_with_random_chain},
+    *,
+};
+
+#[tokio::test]
+async fn invalid_block_id() {
+    let (task, response) = spawn_webserver("/api/v2/block/invalid-block-id/raw").await;
+
+    assert_eq!(response.status(), 400);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid block Id");
+
+    task.abort();
+}
+
+#[tokio::test]
+async fn block_not_found() {
+    let (task, response) = spawn_webserver(
+        "/api/v2/block/0000000000000000000000000000000000000000000000000000000000000001/raw",
+    )
+    .await;
+
+    assert_eq!(response.status(), 404);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body["detail"].as_str().unwrap(), "Block not found");
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn ok(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let (task, addr, expected_block) = spawn_webserver_with_random_chain(&mut rng).await;
+
+    let block_id = expected_block.get_id();
+    let url = format!(
+        "/api/v2/block/{}/raw",
+        block_id.to_hash().encode_hex::<String>()
+    );
+
+    let body = get_raw(addr, &url).await;
+    let block = HexEncoded::<Block>::from_str(&body).unwrap().take();
+
+    assert_eq!(block.get_id(), block_id);
+    assert_eq!(block, expected_block);
+
+    task.abort();
+}
@@ -31,7 +31,7 @@ async fn invalid_block_id() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid block Id");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid block Id");
 
     task.abort();
 }
@@ -48,7 +48,7 @@ async fn block_not_found() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Block not found");
+    assert_eq!(body["detail"].as_str().unwrap(), "Block not found");
 
     task.abort();
 }
@@ -113,8 +113,11 @@ async fn no_reward(#[case] seed: Seed) {
                     rpc: Arc::new(DummyRPC {}),
                     cached_values: Arc::new(CachedValues {
                         feerate_points: RwLock::new((get_time(), vec![])),
+                        mempool_tx_info: RwLock::new((get_time(), vec![])),
                     }),
                     time_getter: Default::default(),
+                    cors_allowed_origins: Default::default(),
+                    page_size_limits: Default::default(),
                 }
             };
 
@@ -217,8 +220,11 @@ async fn has_reward(#[case] seed: Seed) {
                     rpc: Arc::new(DummyRPC {}),
                     cached_values: Arc::new(CachedValues {
                         feerate_points: RwLock::new((get_time(), vec![])),
+                        mempool_tx_info: RwLock::new((get_time(), vec![])),
                     }),
                     time_getter: Default::default(),
+                    cors_allowed_origins: Default::default(),
+                    page_size_limits: Default::default(),
                 }
             };
 
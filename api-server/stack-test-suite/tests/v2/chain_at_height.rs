@@ -31,7 +31,7 @@ async fn invalid_height() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid block height");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid block height");
 
     task.abort();
 }
@@ -46,7 +46,7 @@ async fn height_zero() {
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
     assert_eq!(
-        body["error"].as_str().unwrap(),
+        body["detail"].as_str().unwrap(),
         "No block found at supplied height"
     );
 
@@ -63,7 +63,7 @@ async fn height_past_tip() {
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
     assert_eq!(
-        body["error"].as_str().unwrap(),
+        body["detail"].as_str().unwrap(),
         "No block found at supplied height"
     );
 
@@ -131,8 +131,11 @@ async fn height_n(#[case] seed: Seed) {
                     rpc: Arc::new(DummyRPC {}),
                     cached_values: Arc::new(CachedValues {
                         feerate_points: RwLock::new((get_time(), vec![])),
+                        mempool_tx_info: RwLock::new((get_time(), vec![])),
                     }),
                     time_getter: Default::default(),
+                    cors_allowed_origins: Default::default(),
+                    page_size_limits: Default::default(),
                 }
             };
 
@@ -0,0 +1,202 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::RwLock;
+
+use api_web_server::CachedValues;
+use common::{
+    chain::{block::timestamp::BlockTimestamp, Block},
+    primitives::time::get_time,
+};
+
+use crate::DummyRPC;
+
+use super::*;
+
+#[rstest]
+#[case("/api/v2/chain/blocks")]
+#[case("/api/v2/chain/blocks?from_time=10")]
+#[case("/api/v2/chain/blocks?from_time=invalid&to_time=10")]
+#[case("/api/v2/chain/blocks?from_time=10&to_time=9")]
+#[tokio::test]
+async fn invalid_time_range(#[case] url: &'static str) {
+    let (task, response) = spawn_webserver(url).await;
+
+    assert_eq!(response.status(), 400);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid time range");
+
+    task.abort();
+}
+
+#[tokio::test]
+async fn invalid_num_items() {
+    let (task, response) =
+        spawn_webserver("/api/v2/chain/blocks?from_time=0&to_time=10&items=101").await;
+
+    assert_eq!(response.status(), 400);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid number of items");
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn ok(#[case] seed: Seed) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    let task = tokio::spawn(async move {
+        let web_server_state = {
+            let mut rng = make_seedable_rng(seed);
+            let chain_config = create_unit_test_config();
+
+            let chainstate_blocks = {
+                let mut tf = TestFramework::builder(&mut rng)
+                    .with_chain_config(chain_config.clone())
+                    .build();
+
+                // Blocks at heights 1..=n_blocks, 10 seconds apart, except that every third one
+                // is timestamped 5 seconds before its parent, which is still above the median
+                // time past
+                let n_blocks = rng.gen_range(2..50);
+                let mut blocks: Vec<Block> = Vec::with_capacity(n_blocks);
+                for idx in 0..n_blocks {
+                    tf.progress_time_seconds_since_epoch(10);
+                    let block_builder = tf.make_block_builder();
+                    let block_builder = match blocks.last() {
+                        Some(parent) if idx >= 4 && idx % 3 == 0 => {
+                            block_builder.with_timestamp(BlockTimestamp::from_int_seconds(
+                                parent.timestamp().as_int_seconds() - 5,
+                            ))
+                        }
+                        Some(_) | None => block_builder,
+                    };
+                    let block = block_builder.build(&mut rng);
+                    tf.process_block(block.clone(), BlockSource::Local).unwrap();
+                    blocks.push(block);
+                }
+
+                let mut timestamps = blocks
+                    .iter()
+                    .map(|block| block.timestamp().as_int_seconds())
+                    .collect::<Vec<_>>();
+                timestamps.sort();
+                let from_idx = rng.gen_range(0..timestamps.len());
+                let to_idx = rng.gen_range(from_idx..timestamps.len());
+                let time_range = (timestamps[from_idx], timestamps[to_idx]);
+                let expected_blocks = blocks
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, block)| {
+                        (time_range.0..=time_range.1).contains(&block.timestamp().as_int_seconds())
+                    })
+                    .map(|(idx, block)| (idx as u64 + 1, block.get_id()))
+                    .collect::<Vec<_>>();
+
+                _ = tx.send((time_range, expected_blocks));
+
+                blocks
+            };
+
+            let storage = {
+                let mut storage = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+                let mut db_tx = storage.transaction_rw().await.unwrap();
+                db_tx.reinitialize_storage(&chain_config).await.unwrap();
+                db_tx.commit().await.unwrap();
+
+                storage
+            };
+
+            let chain_config = Arc::new(chain_config);
+            let mut local_node = BlockchainState::new(Arc::clone(&chain_config), storage);
+            local_node.scan_genesis(chain_config.genesis_block()).await.unwrap();
+            local_node.scan_blocks(BlockHeight::new(0), chainstate_blocks).await.unwrap();
+
+            ApiServerWebServerState {
+                db: Arc::new(local_node.storage().clone_storage().await),
+                chain_config: Arc::clone(&chain_config),
+                rpc: Arc::new(DummyRPC {}),
+                cached_values: Arc::new(CachedValues {
+                    feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
+                }),
+                time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
+            }
+        };
+
+        web_server(listener, web_server_state, true).await
+    });
+
+    let ((from, to), expected_blocks) = rx.await.unwrap();
+    let expected_blocks = expected_blocks
+        .into_iter()
+        .map(|(height, block_id)| {
+            json!({
+                "block_height": height,
+                "block_id": block_id.to_hash().encode_hex::<String>(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let get = |offset: usize, items: usize| async move {
+        let url = format!(
+            "/api/v2/chain/blocks?from_time={from}&to_time={to}&offset={offset}&items={items}"
+        );
+
+        // Given that the listener port is open, this will block until a
+        // response is made (by the web server, which takes the listener
+        // over)
+        let response = reqwest::get(format!("http://{}:{}{url}", addr.ip(), addr.port()))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200);
+
+        let body = response.text().await.unwrap();
+        serde_json::from_str::<serde_json::Value>(&body).unwrap()
+    };
+
+    // the whole range fits in a single page
+    let body = get(0, 100).await;
+    assert_eq!(body.as_array().unwrap(), &expected_blocks);
+
+    // the range is split into pages
+    let items = 1 + expected_blocks.len() / 2;
+    let first_page = get(0, items).await;
+    assert_eq!(first_page.as_array().unwrap(), &expected_blocks[..items]);
+    let second_page = get(items, items).await;
+    assert_eq!(second_page.as_array().unwrap(), &expected_blocks[items..]);
+
+    // nothing past the end of the range
+    let body = get(expected_blocks.len(), items).await;
+    assert!(body.as_array().unwrap().is_empty());
+
+    task.abort();
+}
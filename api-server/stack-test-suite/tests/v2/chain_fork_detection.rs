@@ -0,0 +1,177 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use api_web_server::TxSubmitClient;
+use chainstate::{ChainFork, ChainstateInterface};
+use mempool::{FeeRate, MempoolTxInfo};
+use node_comm::rpc_client::NodeRpcError;
+
+use super::*;
+
+#[tokio::test]
+async fn no_forks() {
+    let (task, response) = spawn_webserver("/api/v2/chain/fork-detection").await;
+
+    assert_eq!(response.status(), 200);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body, json!([]));
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn invalid_num_items_max(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let more_than_max = rng.gen_range(101..1000);
+    let (task, response) = spawn_webserver(&format!(
+        "/api/v2/chain/fork-detection?items={more_than_max}"
+    ))
+    .await;
+
+    assert_eq!(response.status(), 400);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid number of items");
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn orphan_block(#[case] seed: Seed) {
+    struct ForksRPC {
+        forks: Vec<ChainFork>,
+    }
+
+    #[async_trait::async_trait]
+    impl TxSubmitClient for ForksRPC {
+        async fn submit_tx(&self, _: SignedTransaction) -> Result<(), NodeRpcError> {
+            Ok(())
+        }
+
+        async fn get_feerate_points(&self) -> Result<Vec<(usize, FeeRate)>, NodeRpcError> {
+            Ok(vec![])
+        }
+
+        async fn get_chain_forks(&self) -> Result<Vec<ChainFork>, NodeRpcError> {
+            Ok(self.forks.clone())
+        }
+
+        async fn get_mempool_tx(
+            &self,
+            _: Id<Transaction>,
+        ) -> Result<Option<SignedTransaction>, NodeRpcError> {
+            Ok(None)
+        }
+
+        async fn get_mempool_tx_info(&self) -> Result<Vec<MempoolTxInfo>, NodeRpcError> {
+            Ok(vec![])
+        }
+    }
+
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = create_unit_test_config();
+
+    let (forks, orphan_tip, main_tip) = {
+        let mut tf =
+            TestFramework::builder(&mut rng).with_chain_config(chain_config.clone()).build();
+        let genesis_id = tf.genesis().get_id();
+
+        let main_len = rng.gen_range(2..10);
+        let main_tip = tf.create_chain(&genesis_id.into(), main_len, &mut rng).unwrap();
+
+        // Manually add a block that doesn't extend the best chain
+        let orphan_block = tf.make_block_builder().with_parent(genesis_id.into()).build(&mut rng);
+        let orphan_tip = orphan_block.get_id();
+        tf.process_block(orphan_block, BlockSource::Local).unwrap();
+        assert_eq!(tf.best_block_id(), main_tip);
+        assert_eq!(tf.chainstate.get_orphan_tips().unwrap(), vec![orphan_tip]);
+
+        let forks = chainstate::get_chain_forks(&tf.chainstate).unwrap();
+        (forks, orphan_tip, main_tip)
+    };
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let task = tokio::spawn(async move {
+        let web_server_state = {
+            let chain_config = Arc::new(chain_config);
+            let storage = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+            ApiServerWebServerState {
+                db: Arc::new(storage),
+                chain_config: Arc::clone(&chain_config),
+                rpc: Arc::new(ForksRPC { forks }),
+                cached_values: Arc::new(CachedValues {
+                    feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
+                }),
+                time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
+            }
+        };
+
+        web_server(listener, web_server_state, false).await.unwrap();
+    });
+
+    let response = reqwest::get(format!(
+        "http://{}:{}/api/v2/chain/fork-detection",
+        addr.ip(),
+        addr.port()
+    ))
+    .await
+    .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(
+        body,
+        json!([{
+            "fork_point_height": BlockHeight::new(0),
+            "orphan_tip": orphan_tip.to_hash().encode_hex::<String>(),
+            "main_tip": main_tip.to_hash().encode_hex::<String>(),
+        }])
+    );
+
+    let response = reqwest::get(format!(
+        "http://{}:{}/api/v2/chain/fork-detection?offset=1",
+        addr.ip(),
+        addr.port()
+    ))
+    .await
+    .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body, json!([]));
+
+    task.abort();
+}
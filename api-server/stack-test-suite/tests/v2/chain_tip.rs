@@ -51,8 +51,11 @@ async fn at_genesis() {
                     rpc: Arc::new(DummyRPC {}),
                     cached_values: Arc::new(CachedValues {
                         feerate_points: RwLock::new((get_time(), vec![])),
+                        mempool_tx_info: RwLock::new((get_time(), vec![])),
                     }),
                     time_getter: Default::default(),
+                    cors_allowed_origins: Default::default(),
+                    page_size_limits: Default::default(),
                 }
             };
 
@@ -68,6 +71,8 @@ async fn at_genesis() {
         .unwrap();
 
     assert_eq!(response.status(), 200);
+    // The tip changes with every new block, so it must not be cached
+    assert!(response.headers().get(reqwest::header::ETAG).is_none());
 
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
@@ -143,8 +148,11 @@ async fn height_n(#[case] seed: Seed) {
                     rpc: Arc::new(DummyRPC {}),
                     cached_values: Arc::new(CachedValues {
                         feerate_points: RwLock::new((get_time(), vec![])),
+                        mempool_tx_info: RwLock::new((get_time(), vec![])),
                     }),
                     time_getter: Default::default(),
+                    cors_allowed_origins: Default::default(),
+                    page_size_limits: Default::default(),
                 }
             };
 
@@ -160,6 +168,8 @@ async fn height_n(#[case] seed: Seed) {
         .unwrap();
 
     assert_eq!(response.status(), 200);
+    // The tip changes with every new block, so it must not be cached
+    assert!(response.headers().get(reqwest::header::ETAG).is_none());
 
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
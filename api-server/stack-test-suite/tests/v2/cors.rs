@@ -0,0 +1,117 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use api_web_server::config::CorsAllowedOrigins;
+
+use super::*;
+
+const ALLOWED_ORIGIN: &str = "https://explorer.mintlayer.org";
+const OTHER_ORIGIN: &str = "https://example.com";
+
+#[rstest]
+#[case("", ALLOWED_ORIGIN, None)]
+#[case("*", ALLOWED_ORIGIN, Some("*"))]
+#[case("*", OTHER_ORIGIN, Some("*"))]
+#[case(ALLOWED_ORIGIN, ALLOWED_ORIGIN, Some(ALLOWED_ORIGIN))]
+#[case(ALLOWED_ORIGIN, OTHER_ORIGIN, None)]
+#[case(
+    "https://a.example.com, https://explorer.mintlayer.org",
+    ALLOWED_ORIGIN,
+    Some(ALLOWED_ORIGIN)
+)]
+#[case(
+    "https://a.example.com, https://explorer.mintlayer.org",
+    OTHER_ORIGIN,
+    None
+)]
+#[tokio::test]
+async fn preflight(
+    #[case] allowed_origins: &'static str,
+    #[case] origin: &'static str,
+    #[case] expected_allow_origin: Option<&'static str>,
+) {
+    let allowed_origins = allowed_origins.parse::<CorsAllowedOrigins>().unwrap();
+    assert_eq!(
+        preflight_allow_origin(allowed_origins, origin).await.as_deref(),
+        expected_allow_origin
+    );
+}
+
+#[rstest]
+#[case(ALLOWED_ORIGIN)]
+#[case(OTHER_ORIGIN)]
+#[tokio::test]
+async fn preflight_default(#[case] origin: &'static str) {
+    assert_eq!(
+        preflight_allow_origin(Default::default(), origin).await.as_deref(),
+        Some("*")
+    );
+}
+
+/// Send a preflight request from the origin and return the allowed origin from the response
+async fn preflight_allow_origin(
+    cors_allowed_origins: CorsAllowedOrigins,
+    origin: &'static str,
+) -> Option<String> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let task = tokio::spawn(async move {
+        let web_server_state = {
+            let chain_config = Arc::new(create_unit_test_config());
+            let storage = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+            ApiServerWebServerState {
+                db: Arc::new(storage),
+                chain_config,
+                rpc: Arc::new(DummyRPC {}),
+                cached_values: Arc::new(CachedValues {
+                    feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
+                }),
+                time_getter: Default::default(),
+                cors_allowed_origins,
+                page_size_limits: Default::default(),
+            }
+        };
+
+        web_server(listener, web_server_state, false).await.unwrap();
+    });
+
+    // Given that the listener port is open, this will block until a
+    // response is made (by the web server, which takes the listener
+    // over)
+    let response = reqwest::Client::new()
+        .request(
+            reqwest::Method::OPTIONS,
+            format!("http://{}:{}/api/v2/chain/tip", addr.ip(), addr.port()),
+        )
+        .header("Origin", origin)
+        .header("Access-Control-Request-Method", "GET")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+
+    let allow_origin = response
+        .headers()
+        .get("Access-Control-Allow-Origin")
+        .map(|value| value.to_str().unwrap().to_owned());
+
+    task.abort();
+
+    allow_origin
+}
@@ -16,8 +16,9 @@
 use std::sync::RwLock;
 
 use api_web_server::{CachedValues, TxSubmitClient};
+use chainstate::ChainFork;
 use common::primitives::time::get_time;
-use mempool::FeeRate;
+use mempool::{FeeRate, MempoolTxInfo};
 use node_comm::rpc_client::NodeRpcError;
 use test_utils::mock_time_getter::mocked_time_getter_seconds;
 use utils::atomics::SeqCstAtomicU64;
@@ -36,7 +37,7 @@ async fn invalid_query_parameter() {
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
     assert_eq!(
-        body["error"].as_str().unwrap(),
+        body["detail"].as_str().unwrap(),
         "Invalid in top X MB query parameter"
     );
 
@@ -71,8 +72,11 @@ async fn ok(#[case] seed: Seed) {
                             (100, FeeRate::from_amount_per_kb(Amount::from_atoms(100))),
                         ],
                     )),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
@@ -113,6 +117,21 @@ async fn ok_reload_feerate(#[case] seed: Seed) {
                 (100, FeeRate::from_amount_per_kb(Amount::from_atoms(200))),
             ])
         }
+
+        async fn get_chain_forks(&self) -> Result<Vec<ChainFork>, NodeRpcError> {
+            Ok(vec![])
+        }
+
+        async fn get_mempool_tx(
+            &self,
+            _: Id<Transaction>,
+        ) -> Result<Option<SignedTransaction>, NodeRpcError> {
+            Ok(None)
+        }
+
+        async fn get_mempool_tx_info(&self) -> Result<Vec<MempoolTxInfo>, NodeRpcError> {
+            Ok(vec![])
+        }
     }
     let mut rng = make_seedable_rng(seed);
     let in_top_x_mb = rng.gen_range(1..100);
@@ -140,8 +159,11 @@ async fn ok_reload_feerate(#[case] seed: Seed) {
                             (100, FeeRate::from_amount_per_kb(Amount::from_atoms(100))),
                         ],
                     )),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter,
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
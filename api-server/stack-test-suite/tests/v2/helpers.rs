@@ -29,6 +29,8 @@ use crypto::{
 };
 use randomness::{CryptoRng, Rng};
 
+use super::*;
+
 pub fn prepare_stake_pool(
     stake_pool_outpoint: UtxoOutPoint,
     rng: &mut (impl Rng + CryptoRng),
@@ -137,3 +139,90 @@ pub fn stake_delegation(
 
     (amount_to_delegate, transfer_outpoint, block)
 }
+
+/// Spawn a web server over a random chain and return the server task, the address it listens on
+/// and a block chosen randomly from that chain.
+pub async fn spawn_webserver_with_random_chain(
+    rng: &mut (impl Rng + CryptoRng),
+) -> (tokio::task::JoinHandle<()>, std::net::SocketAddr, Block) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let block_height = rng.gen_range(1..50);
+    let n_blocks = rng.gen_range(block_height..100);
+
+    let chain_config = create_unit_test_config();
+
+    let (block, chainstate_blocks) = {
+        let mut tf = TestFramework::builder(rng).with_chain_config(chain_config.clone()).build();
+
+        let chainstate_block_ids = tf
+            .create_chain_return_ids(&tf.genesis().get_id().into(), n_blocks, rng)
+            .unwrap();
+
+        // Need the "- 1" to account for the genesis block not in the vec
+        let block_id = chainstate_block_ids[block_height - 1];
+        let block = tf.block(tf.to_chain_block_id(&block_id));
+
+        let chainstate_blocks = chainstate_block_ids
+            .iter()
+            .map(|id| tf.block(tf.to_chain_block_id(id)))
+            .collect::<Vec<_>>();
+
+        (block, chainstate_blocks)
+    };
+
+    let task = tokio::spawn(async move {
+        let web_server_state = {
+            let storage = {
+                let mut storage = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+                let mut db_tx = storage.transaction_rw().await.unwrap();
+                db_tx.reinitialize_storage(&chain_config).await.unwrap();
+                db_tx.commit().await.unwrap();
+
+                storage
+            };
+
+            let chain_config = Arc::new(chain_config);
+            let mut local_node = BlockchainState::new(Arc::clone(&chain_config), storage);
+            local_node.scan_genesis(chain_config.genesis_block()).await.unwrap();
+            local_node.scan_blocks(BlockHeight::new(0), chainstate_blocks).await.unwrap();
+
+            ApiServerWebServerState {
+                db: Arc::new(local_node.storage().clone_storage().await),
+                chain_config: Arc::clone(&chain_config),
+                rpc: Arc::new(DummyRPC {}),
+                cached_values: Arc::new(CachedValues {
+                    feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
+                }),
+                time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
+            }
+        };
+
+        web_server(listener, web_server_state, true).await.unwrap()
+    });
+
+    (task, addr, block)
+}
+
+/// Request a raw object from the web server, checking that it's returned as a hex string
+pub async fn get_raw(addr: std::net::SocketAddr, url: &str) -> String {
+    // Given that the listener port is open, this will block until a
+    // response is made (by the web server, which takes the listener
+    // over)
+    let response = reqwest::get(format!("http://{}:{}{url}", addr.ip(), addr.port()))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("Content-Type").unwrap(),
+        "text/plain"
+    );
+
+    response.text().await.unwrap()
+}
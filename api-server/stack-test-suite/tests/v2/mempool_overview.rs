@@ -0,0 +1,190 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use api_web_server::TxSubmitClient;
+use chainstate::ChainFork;
+use common::primitives::{time::Time, H256};
+use mempool::{FeeRate, MempoolTxInfo};
+use node_comm::rpc_client::NodeRpcError;
+use randomness::SliceRandom;
+use test_utils::mock_time_getter::mocked_time_getter_seconds;
+use utils::atomics::SeqCstAtomicU64;
+
+use super::*;
+
+async fn get_overview(url: &str) -> serde_json::Value {
+    let response = reqwest::get(url).await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    let body = response.text().await.unwrap();
+    serde_json::from_str(&body).unwrap()
+}
+
+#[tokio::test]
+async fn empty_mempool() {
+    let (task, response) = spawn_webserver("/api/v2/statistics/mempool-overview").await;
+
+    assert_eq!(response.status(), 200);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(
+        body,
+        json!({
+            "tx_count": 0,
+            "total_size_bytes": 0,
+            "min_feerate": null,
+            "median_feerate": null,
+            "max_feerate": null,
+            "age_histogram": {
+                "<1min": 0,
+                "1-10min": 0,
+                ">10min": 0,
+            },
+        })
+    );
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn ok(#[case] seed: Seed) {
+    struct MempoolOverviewRPC {
+        txs: Vec<MempoolTxInfo>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl TxSubmitClient for MempoolOverviewRPC {
+        async fn submit_tx(&self, _: SignedTransaction) -> Result<(), NodeRpcError> {
+            Ok(())
+        }
+
+        async fn get_feerate_points(&self) -> Result<Vec<(usize, FeeRate)>, NodeRpcError> {
+            Ok(vec![])
+        }
+
+        async fn get_chain_forks(&self) -> Result<Vec<ChainFork>, NodeRpcError> {
+            Ok(vec![])
+        }
+
+        async fn get_mempool_tx(
+            &self,
+            _: Id<Transaction>,
+        ) -> Result<Option<SignedTransaction>, NodeRpcError> {
+            Ok(None)
+        }
+
+        async fn get_mempool_tx_info(&self) -> Result<Vec<MempoolTxInfo>, NodeRpcError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(self.txs.clone())
+        }
+    }
+
+    let mut rng = make_seedable_rng(seed);
+
+    let now = 100_000;
+    let seconds = Arc::new(SeqCstAtomicU64::new(now));
+    let time_getter = mocked_time_getter_seconds(Arc::clone(&seconds));
+
+    // (size, fee rate, age in seconds)
+    let mut txs = [
+        (250, 300, 10),
+        (400, 100, 59),
+        (1000, 500, 60),
+        (150, 200, 599),
+        (600, 400, 600),
+    ];
+    txs.shuffle(&mut rng);
+    let txs = txs
+        .into_iter()
+        .map(|(size, fee_rate, age)| MempoolTxInfo {
+            tx_id: Id::new(H256::random_using(&mut rng)),
+            size,
+            fee_rate: FeeRate::from_atoms_per_kb(fee_rate),
+            creation_time: Time::from_secs_since_epoch(now - age),
+        })
+        .collect();
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let task = tokio::spawn({
+        let calls = Arc::clone(&calls);
+        async move {
+            let web_server_state = {
+                let chain_config = Arc::new(create_unit_test_config());
+                let storage = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+                ApiServerWebServerState {
+                    db: Arc::new(storage),
+                    chain_config: Arc::clone(&chain_config),
+                    rpc: Arc::new(MempoolOverviewRPC { txs, calls }),
+                    cached_values: Arc::new(CachedValues {
+                        feerate_points: RwLock::new((get_time(), vec![])),
+                        mempool_tx_info: RwLock::new((Time::from_secs_since_epoch(0), vec![])),
+                    }),
+                    time_getter,
+                    cors_allowed_origins: Default::default(),
+                    page_size_limits: Default::default(),
+                }
+            };
+
+            web_server(listener, web_server_state, false).await.unwrap();
+        }
+    });
+
+    let url = format!(
+        "http://{}:{}/api/v2/statistics/mempool-overview",
+        addr.ip(),
+        addr.port()
+    );
+    let body = get_overview(&url).await;
+    assert_eq!(
+        body,
+        json!({
+            "tx_count": 5,
+            "total_size_bytes": 2400,
+            "min_feerate": "100",
+            "median_feerate": "300",
+            "max_feerate": "500",
+            "age_histogram": {
+                "<1min": 2,
+                "1-10min": 2,
+                ">10min": 1,
+            },
+        })
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // The mempool data is cached for 10 seconds
+    seconds.fetch_add(10);
+    get_overview(&url).await;
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    seconds.fetch_add(1);
+    let body = get_overview(&url).await;
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!(body["tx_count"], 5);
+
+    task.abort();
+}
@@ -0,0 +1,255 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use api_web_server::{api::json_helpers::unconfirmed_tx_to_json, TxSubmitClient};
+use chainstate::ChainFork;
+use chainstate_test_framework::empty_witness;
+use common::{
+    chain::{tokens::TokenId, UtxoOutPoint},
+    primitives::H256,
+};
+use mempool::{FeeRate, MempoolTxInfo};
+use node_comm::rpc_client::NodeRpcError;
+
+use super::*;
+
+struct MempoolRPC {
+    tx: SignedTransaction,
+}
+
+#[async_trait::async_trait]
+impl TxSubmitClient for MempoolRPC {
+    async fn submit_tx(&self, _: SignedTransaction) -> Result<(), NodeRpcError> {
+        Ok(())
+    }
+
+    async fn get_feerate_points(&self) -> Result<Vec<(usize, FeeRate)>, NodeRpcError> {
+        Ok(vec![])
+    }
+
+    async fn get_chain_forks(&self) -> Result<Vec<ChainFork>, NodeRpcError> {
+        Ok(vec![])
+    }
+
+    async fn get_mempool_tx(
+        &self,
+        tx_id: Id<Transaction>,
+    ) -> Result<Option<SignedTransaction>, NodeRpcError> {
+        Ok((self.tx.transaction().get_id() == tx_id).then(|| self.tx.clone()))
+    }
+
+    async fn get_mempool_tx_info(&self) -> Result<Vec<MempoolTxInfo>, NodeRpcError> {
+        Ok(vec![])
+    }
+}
+
+#[tokio::test]
+async fn invalid_transaction_id() {
+    let (task, response) = spawn_webserver("/api/v2/mempool/transaction/invalid-txid").await;
+
+    assert_eq!(response.status(), 400);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid transaction Id");
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn ok(#[case] seed: Seed) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_unit_test_config());
+
+    let tx = TransactionBuilder::new()
+        .add_input(
+            TxInput::Utxo(UtxoOutPoint::new(
+                OutPointSourceId::Transaction(Id::<Transaction>::new(H256::random_using(&mut rng))),
+                0,
+            )),
+            empty_witness(&mut rng),
+        )
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(rng.gen_range(1..1000))),
+            Destination::AnyoneCanSpend,
+        ))
+        .build();
+    let tx_id = tx.transaction().get_id();
+    let expected_tx = unconfirmed_tx_to_json(tx.transaction(), &BTreeMap::new(), &chain_config);
+
+    let task = tokio::spawn({
+        let chain_config = Arc::clone(&chain_config);
+        async move {
+            let web_server_state = {
+                let storage = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+                ApiServerWebServerState {
+                    db: Arc::new(storage),
+                    chain_config,
+                    rpc: Arc::new(MempoolRPC { tx }),
+                    cached_values: Arc::new(CachedValues {
+                        feerate_points: RwLock::new((get_time(), vec![])),
+                        mempool_tx_info: RwLock::new((get_time(), vec![])),
+                    }),
+                    time_getter: Default::default(),
+                    cors_allowed_origins: Default::default(),
+                    page_size_limits: Default::default(),
+                }
+            };
+
+            web_server(listener, web_server_state, false).await.unwrap();
+        }
+    });
+
+    let get = |tx_id: Id<Transaction>| async move {
+        let url = format!(
+            "/api/v2/mempool/transaction/{}",
+            tx_id.to_hash().encode_hex::<String>()
+        );
+
+        // Given that the listener port is open, this will block until a
+        // response is made (by the web server, which takes the listener
+        // over)
+        let response = reqwest::get(format!("http://{}:{}{url}", addr.ip(), addr.port()))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200);
+
+        let body = response.text().await.unwrap();
+        serde_json::from_str::<serde_json::Value>(&body).unwrap()
+    };
+
+    // the transaction is in the mempool
+    let body = get(tx_id).await;
+    assert_eq!(
+        body,
+        json!({
+            "id": tx_id.to_hash().encode_hex::<String>(),
+            "status": "in_mempool",
+            "transaction": expected_tx,
+            "pending_token_ids": [],
+        })
+    );
+
+    // an unknown transaction is not found
+    let missing_tx_id = Id::<Transaction>::new(H256::random_using(&mut rng));
+    let body = get(missing_tx_id).await;
+    assert_eq!(
+        body,
+        json!({
+            "id": missing_tx_id.to_hash().encode_hex::<String>(),
+            "status": "not_found",
+        })
+    );
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn unconfirmed_token(#[case] seed: Seed) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_unit_test_config());
+
+    // The token is not in the db, as if its issuance was still in the mempool
+    let token_id = TokenId::new(H256::random_using(&mut rng));
+    let amount = Amount::from_atoms(rng.gen_range(1..1000));
+
+    let tx = TransactionBuilder::new()
+        .add_input(
+            TxInput::Utxo(UtxoOutPoint::new(
+                OutPointSourceId::Transaction(Id::<Transaction>::new(H256::random_using(&mut rng))),
+                0,
+            )),
+            empty_witness(&mut rng),
+        )
+        .add_output(TxOutput::Transfer(
+            OutputValue::TokenV1(token_id, amount),
+            Destination::AnyoneCanSpend,
+        ))
+        .build();
+    let tx_id = tx.transaction().get_id();
+
+    let task = tokio::spawn({
+        let chain_config = Arc::clone(&chain_config);
+        async move {
+            let web_server_state = {
+                let storage = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+                ApiServerWebServerState {
+                    db: Arc::new(storage),
+                    chain_config,
+                    rpc: Arc::new(MempoolRPC { tx }),
+                    cached_values: Arc::new(CachedValues {
+                        feerate_points: RwLock::new((get_time(), vec![])),
+                        mempool_tx_info: RwLock::new((get_time(), vec![])),
+                    }),
+                    time_getter: Default::default(),
+                    cors_allowed_origins: Default::default(),
+                    page_size_limits: Default::default(),
+                }
+            };
+
+            web_server(listener, web_server_state, false).await.unwrap();
+        }
+    });
+
+    let url = format!(
+        "/api/v2/mempool/transaction/{}",
+        tx_id.to_hash().encode_hex::<String>()
+    );
+
+    // Given that the listener port is open, this will block until a
+    // response is made (by the web server, which takes the listener
+    // over)
+    let response = reqwest::get(format!("http://{}:{}{url}", addr.ip(), addr.port()))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    let token_id = Address::new(&chain_config, token_id).unwrap();
+    assert_eq!(body["status"], "in_mempool");
+    assert_eq!(body["pending_token_ids"], json!([token_id.as_str()]));
+    assert_eq!(
+        body["transaction"]["outputs"][0]["value"],
+        json!({
+            "type": "TokenV1",
+            "token_id": token_id.as_str(),
+            "amount": {
+                "decimal": null,
+                "atoms": amount.into_atoms().to_string(),
+            },
+        })
+    );
+
+    task.abort();
+}
@@ -17,26 +17,38 @@ mod address;
 mod address_all_utxos;
 mod address_delegations;
 mod address_spendable_utxos;
+mod address_token_allowances;
 mod block;
 mod block_header;
+mod block_raw;
 mod block_reward;
 mod block_transaction_ids;
 mod chain_at_height;
+mod chain_blocks;
+mod chain_fork_detection;
 mod chain_tip;
+mod cors;
 mod feerate;
 mod helpers;
+mod mempool_overview;
+mod mempool_transaction;
 mod nft;
 mod pool;
 mod pool_block_stats;
 mod pools;
+mod search_data_deposit;
 mod statistics;
+mod stats_supply;
 mod token;
 mod token_ids;
 mod token_ticker;
+mod token_transactions;
 mod transaction;
 mod transaction_merkle_path;
+mod transaction_raw;
 mod transaction_submit;
 mod transactions;
+mod webhook;
 
 use crate::{spawn_webserver, DummyRPC};
 use api_blockchain_scanner_lib::{
@@ -112,8 +124,11 @@ async fn chain_genesis() {
                     rpc: Arc::new(DummyRPC {}),
                     cached_values: Arc::new(CachedValues {
                         feerate_points: RwLock::new((get_time(), vec![])),
+                        mempool_tx_info: RwLock::new((get_time(), vec![])),
                     }),
                     time_getter: Default::default(),
+                    cors_allowed_origins: Default::default(),
+                    page_size_limits: Default::default(),
                 }
             };
 
@@ -130,6 +145,8 @@ async fn chain_genesis() {
 
     assert_eq!(response.status(), 200);
 
+    let etag = response.headers().get(reqwest::header::ETAG).unwrap().clone();
+
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
@@ -137,5 +154,15 @@ async fn chain_genesis() {
 
     assert_eq!(body, expected_genesis);
 
+    // The genesis never changes, so the client can keep using its copy
+    let response = reqwest::Client::new()
+        .get(format!("http://{}:{}{url}", addr.ip(), addr.port()))
+        .header(reqwest::header::IF_NONE_MATCH, etag)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 304);
+
     task.abort();
 }
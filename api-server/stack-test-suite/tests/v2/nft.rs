@@ -32,7 +32,7 @@ async fn invalid_nft_id() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid NFT Id");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid NFT Id");
 
     task.abort();
 }
@@ -55,7 +55,7 @@ async fn nft_not_found(#[case] seed: Seed) {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "NFT not found");
+    assert_eq!(body["detail"].as_str().unwrap(), "NFT not found");
 
     task.abort();
 }
@@ -176,8 +176,11 @@ async fn ok(#[case] seed: Seed) {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
@@ -33,7 +33,7 @@ async fn invalid_pool_id() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid pool Id");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid pool Id");
 
     task.abort();
 }
@@ -50,7 +50,7 @@ async fn pool_id_not_fund() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Stake pool not found");
+    assert_eq!(body["detail"].as_str().unwrap(), "Stake pool not found");
 
     task.abort();
 }
@@ -177,8 +177,11 @@ async fn ok(#[case] seed: Seed) {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
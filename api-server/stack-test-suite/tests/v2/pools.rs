@@ -32,7 +32,7 @@ async fn invalid_offset() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid offset");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid offset");
 
     task.abort();
 }
@@ -46,7 +46,7 @@ async fn invalid_num_items() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid number of items");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid number of items");
 
     task.abort();
 }
@@ -65,7 +65,7 @@ async fn invalid_num_items_max(#[case] seed: Seed) {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid number of items");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid number of items");
 
     task.abort();
 }
@@ -79,7 +79,7 @@ async fn invalid_sort_order() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid pools sort order");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid pools sort order");
 
     task.abort();
 }
@@ -206,8 +206,11 @@ async fn ok(#[case] seed: Seed) {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
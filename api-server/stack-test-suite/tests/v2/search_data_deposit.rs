@@ -0,0 +1,244 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[tokio::test]
+async fn invalid_prefix() {
+    let (task, response) = spawn_webserver("/api/v2/search/data-deposit?q=xyzxyzxy").await;
+
+    assert_eq!(response.status(), 400);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(
+        body["detail"].as_str().unwrap(),
+        "Invalid data deposit search prefix"
+    );
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case("")]
+#[case("ab")]
+#[case("abcdef")]
+#[tokio::test]
+async fn prefix_too_short(#[case] prefix: &str) {
+    let (task, response) =
+        spawn_webserver(&format!("/api/v2/search/data-deposit?q={prefix}")).await;
+
+    assert_eq!(response.status(), 400);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(
+        body["detail"].as_str().unwrap(),
+        "Data deposit search prefix must be at least 4 bytes"
+    );
+
+    task.abort();
+}
+
+#[tokio::test]
+async fn missing_prefix() {
+    let (task, response) = spawn_webserver("/api/v2/search/data-deposit").await;
+
+    assert_eq!(response.status(), 400);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(
+        body["detail"].as_str().unwrap(),
+        "Data deposit search prefix must be at least 4 bytes"
+    );
+
+    task.abort();
+}
+
+#[tokio::test]
+async fn invalid_num_items() {
+    let (task, response) =
+        spawn_webserver("/api/v2/search/data-deposit?q=deadbeef&items=asd").await;
+
+    assert_eq!(response.status(), 400);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid number of items");
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn ok(#[case] seed: Seed) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    let shared_prefix = [0xde, 0xad, 0xbe, 0xef];
+    let unique_prefix = [0xca, 0xfe, 0xba, 0xbe];
+
+    let task = tokio::spawn(async move {
+        let web_server_state = {
+            let mut rng = make_seedable_rng(seed);
+            let chain_config = create_unit_test_config();
+
+            let chainstate_blocks = {
+                let mut tf = TestFramework::builder(&mut rng)
+                    .with_chain_config(chain_config.clone())
+                    .build();
+
+                let num_shared = rng.gen_range(2..10);
+
+                let mut remaining =
+                    (chain_config.data_deposit_fee(BlockHeight::new(1)) * 100).unwrap();
+                let mut input = TxInput::from_utxo(
+                    OutPointSourceId::BlockReward(tf.genesis().get_id().into()),
+                    0,
+                );
+
+                // (tx_id, block_height, data) of each deposit, the unique one being the last
+                let mut deposits = vec![];
+                for i in 0..=num_shared {
+                    let prefix = if i < num_shared {
+                        shared_prefix
+                    } else {
+                        unique_prefix
+                    };
+                    let suffix_len = rng.gen_range(1..32);
+                    let data: Vec<u8> = prefix
+                        .into_iter()
+                        .chain((0..suffix_len).map(|_| rng.gen::<u8>()))
+                        .collect();
+
+                    let block_height = BlockHeight::new(i as u64 + 1);
+                    let transaction = TransactionBuilder::new()
+                        .add_input(input, InputWitness::NoSignature(None))
+                        .add_output(TxOutput::Transfer(
+                            OutputValue::Coin(remaining),
+                            Destination::AnyoneCanSpend,
+                        ))
+                        .add_output(TxOutput::DataDeposit(data.clone()))
+                        .build();
+
+                    let tx_id = transaction.transaction().get_id();
+                    deposits.push((tx_id, block_height, data));
+                    input = TxInput::from_utxo(OutPointSourceId::Transaction(tx_id), 0);
+                    remaining = (remaining - chain_config.data_deposit_fee(block_height)).unwrap();
+
+                    tf.make_block_builder()
+                        .add_transaction(transaction)
+                        .build_and_process(&mut rng)
+                        .unwrap()
+                        .unwrap();
+                }
+
+                _ = tx.send(deposits);
+
+                tf.block_indexes
+                    .iter()
+                    .map(|idx| tf.block(tf.to_chain_block_id(idx.block_id().into())))
+                    .collect::<Vec<_>>()
+            };
+
+            let storage = {
+                let mut storage = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+                let mut db_tx = storage.transaction_rw().await.unwrap();
+                db_tx.reinitialize_storage(&chain_config).await.unwrap();
+                db_tx.commit().await.unwrap();
+
+                storage
+            };
+
+            let chain_config = Arc::new(chain_config);
+
+            let mut local_node = BlockchainState::new(Arc::clone(&chain_config), storage);
+            local_node.scan_genesis(chain_config.genesis_block()).await.unwrap();
+            local_node.scan_blocks(BlockHeight::new(0), chainstate_blocks).await.unwrap();
+
+            ApiServerWebServerState {
+                db: Arc::new(local_node.storage().clone_storage().await),
+                chain_config: Arc::clone(&chain_config),
+                rpc: Arc::new(DummyRPC {}),
+                cached_values: Arc::new(CachedValues {
+                    feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
+                }),
+                time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
+            }
+        };
+
+        web_server(listener, web_server_state, false).await
+    });
+
+    let mut deposits = rx.await.unwrap();
+    let to_json = |(tx_id, block_height, data): &(Id<Transaction>, BlockHeight, Vec<u8>)| {
+        json!({
+            "tx_id": tx_id.to_hash().encode_hex::<String>(),
+            "block_height": block_height,
+            "data": data.encode_hex::<String>(),
+        })
+    };
+
+    let search = |q: String| async move {
+        let url = format!("/api/v2/search/data-deposit?q={q}&offset=0&items=100");
+
+        // Given that the listener port is open, this will block until a
+        // response is made (by the web server, which takes the listener
+        // over)
+        let response = reqwest::get(format!("http://{}:{}{url}", addr.ip(), addr.port()))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200, "Failed searching data deposits");
+
+        let body = response.text().await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        body.as_array().unwrap().clone()
+    };
+
+    // exact match of the whole deposited data
+    let unique = deposits.pop().unwrap();
+    let body = search(unique.2.encode_hex::<String>()).await;
+    assert_eq!(body, vec![to_json(&unique)]);
+
+    // the unique prefix alone matches the same single deposit
+    let body = search(unique_prefix.encode_hex::<String>()).await;
+    assert_eq!(body, vec![to_json(&unique)]);
+
+    // the shared prefix matches all the other deposits, newest first
+    let body = search(shared_prefix.encode_hex::<String>()).await;
+    let expected = deposits.iter().rev().map(to_json).collect::<Vec<_>>();
+    assert_eq!(body, expected);
+
+    // nothing is deposited with this prefix
+    let body = search("01020304".to_owned()).await;
+    assert!(body.is_empty());
+
+    task.abort();
+}
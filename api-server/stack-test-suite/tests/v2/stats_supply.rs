@@ -0,0 +1,158 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use api_web_server::api::json_helpers::amount_to_json;
+use common::chain::{config::emission_schedule::DEFAULT_INITIAL_MINT, UtxoOutPoint};
+
+use crate::DummyRPC;
+
+use super::{helpers::prepare_stake_pool, *};
+
+#[tokio::test]
+async fn empty_chain() {
+    let (task, response) = spawn_webserver("/api/v2/stats/supply").await;
+
+    assert_eq!(response.status(), 200);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    let decimals = create_unit_test_config().coin_decimals();
+    assert_eq!(
+        body,
+        json!({
+            "height": 0,
+            "circulating_coins": amount_to_json(Amount::ZERO, decimals),
+            "total_burned": amount_to_json(Amount::ZERO, decimals),
+            "total_staked": amount_to_json(Amount::ZERO, decimals),
+        })
+    );
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn ok(#[case] seed: Seed) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    let task = tokio::spawn(async move {
+        let web_server_state = {
+            let mut rng = make_seedable_rng(seed);
+            let chain_config = create_unit_test_config();
+
+            let chainstate_blocks = {
+                let mut tf = TestFramework::builder(&mut rng)
+                    .with_chain_config(chain_config.clone())
+                    .build();
+
+                let stake_pool_outpoint = UtxoOutPoint::new(
+                    OutPointSourceId::BlockReward(tf.genesis().get_id().into()),
+                    0,
+                );
+
+                let mut available_amount = ((chain_config.min_stake_pool_pledge() * 10).unwrap()
+                    + Amount::from_atoms(10000))
+                .unwrap();
+
+                // Block 1 creates the pool
+                let (transfer_outpoint, stake_pool_data, _, _) = prepare_stake_pool(
+                    stake_pool_outpoint,
+                    &mut rng,
+                    &mut available_amount,
+                    &mut tf,
+                );
+
+                // Block 2 burns some of the remaining coins
+                let amount_to_burn =
+                    Amount::from_atoms(rng.gen_range(1..=available_amount.into_atoms()));
+                let burn_tx = TransactionBuilder::new()
+                    .add_input(transfer_outpoint.into(), InputWitness::NoSignature(None))
+                    .add_output(TxOutput::Burn(OutputValue::Coin(amount_to_burn)))
+                    .build();
+                let block = tf.make_block_builder().add_transaction(burn_tx).build(&mut rng);
+                tf.process_block(block, BlockSource::Local).unwrap();
+
+                let decimals = chain_config.coin_decimals();
+                _ = tx.send(json!({
+                    "height": 2,
+                    "circulating_coins": amount_to_json((DEFAULT_INITIAL_MINT - amount_to_burn).unwrap(), decimals),
+                    "total_burned": amount_to_json(amount_to_burn, decimals),
+                    "total_staked": amount_to_json(stake_pool_data.pledge(), decimals),
+                }));
+
+                tf.block_indexes
+                    .iter()
+                    .map(|idx| tf.block(tf.to_chain_block_id(idx.block_id().into())))
+                    .collect::<Vec<_>>()
+            };
+
+            let storage = {
+                let mut storage = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+                let mut db_tx = storage.transaction_rw().await.unwrap();
+                db_tx.reinitialize_storage(&chain_config).await.unwrap();
+                db_tx.commit().await.unwrap();
+
+                storage
+            };
+
+            let chain_config = Arc::new(chain_config);
+
+            let mut local_node = BlockchainState::new(Arc::clone(&chain_config), storage);
+            local_node.scan_genesis(chain_config.genesis_block()).await.unwrap();
+            local_node.scan_blocks(BlockHeight::new(0), chainstate_blocks).await.unwrap();
+
+            ApiServerWebServerState {
+                db: Arc::new(local_node.storage().clone_storage().await),
+                chain_config: Arc::clone(&chain_config),
+                rpc: Arc::new(DummyRPC {}),
+                cached_values: Arc::new(CachedValues {
+                    feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
+                }),
+                time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
+            }
+        };
+
+        web_server(listener, web_server_state, false).await
+    });
+
+    let expected_values = rx.await.unwrap();
+    let url = "/api/v2/stats/supply";
+
+    // Given that the listener port is open, this will block until a
+    // response is made (by the web server, which takes the listener
+    // over)
+    let response = reqwest::get(format!("http://{}:{}{url}", addr.ip(), addr.port()))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body, expected_values);
+
+    task.abort();
+}
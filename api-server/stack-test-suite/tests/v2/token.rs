@@ -36,7 +36,7 @@ async fn invalid_token_id() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid token Id");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid token Id");
 
     task.abort();
 }
@@ -59,7 +59,7 @@ async fn token_not_found(#[case] seed: Seed) {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Token not found");
+    assert_eq!(body["detail"].as_str().unwrap(), "Token not found");
 
     task.abort();
 }
@@ -179,8 +179,11 @@ async fn ok(#[case] seed: Seed) {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
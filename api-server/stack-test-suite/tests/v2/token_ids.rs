@@ -30,7 +30,7 @@ async fn invalid_offset() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid offset");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid offset");
 
     task.abort();
 }
@@ -44,7 +44,7 @@ async fn invalid_num_items() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid number of items");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid number of items");
 
     task.abort();
 }
@@ -63,7 +63,7 @@ async fn invalid_num_items_max(#[case] seed: Seed) {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid number of items");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid number of items");
 
     task.abort();
 }
@@ -204,8 +204,11 @@ async fn ok(#[case] seed: Seed) {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
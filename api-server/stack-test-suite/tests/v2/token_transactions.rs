@@ -0,0 +1,223 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common::{
+    chain::tokens::{make_token_id, NftIssuance, TokenId},
+    primitives::H256,
+};
+
+use super::*;
+
+#[tokio::test]
+async fn invalid_token_id() {
+    let (task, response) = spawn_webserver("/api/v2/token/invalid-token-id/transactions").await;
+
+    assert_eq!(response.status(), 400);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid token Id");
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn token_not_found(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = create_unit_test_config();
+
+    let token_id = TokenId::new(H256::random_using(&mut rng));
+    let token_id = Address::<TokenId>::new(&chain_config, token_id).unwrap();
+
+    let (task, response) =
+        spawn_webserver(&format!("/api/v2/token/{}/transactions", token_id.as_str())).await;
+
+    assert_eq!(response.status(), 404);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body["detail"].as_str().unwrap(), "Token not found");
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn ok(#[case] seed: Seed) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    let task = tokio::spawn(async move {
+        let web_server_state = {
+            let mut rng = make_seedable_rng(seed);
+            let chain_config = create_unit_test_config();
+
+            let chainstate_blocks = {
+                let mut tf = TestFramework::builder(&mut rng)
+                    .with_chain_config(chain_config.clone())
+                    .build();
+
+                let nft = test_utils::nft_utils::random_nft_issuance(&chain_config, &mut rng);
+
+                let input = TxInput::from_utxo(
+                    OutPointSourceId::BlockReward(tf.genesis().get_id().into()),
+                    0,
+                );
+                let token_id = make_token_id(&[input.clone()]).unwrap();
+
+                // mint the NFT, also keeping some coins that have nothing to do with it
+                let mint_tx = TransactionBuilder::new()
+                    .add_input(input, InputWitness::NoSignature(None))
+                    .add_output(TxOutput::IssueNft(
+                        token_id,
+                        Box::new(NftIssuance::V0(nft)),
+                        Destination::AnyoneCanSpend,
+                    ))
+                    .add_output(TxOutput::Transfer(
+                        OutputValue::Coin(Amount::from_atoms(rng.gen_range(1000..100_000))),
+                        Destination::AnyoneCanSpend,
+                    ))
+                    .build();
+                let mint_tx_id = mint_tx.transaction().get_id();
+
+                let transfer = |source_tx_id: Id<Transaction>| {
+                    TransactionBuilder::new()
+                        .add_input(
+                            TxInput::from_utxo(source_tx_id.into(), 0),
+                            InputWitness::NoSignature(None),
+                        )
+                        .add_output(TxOutput::Transfer(
+                            OutputValue::TokenV1(token_id, Amount::from_atoms(1)),
+                            Destination::AnyoneCanSpend,
+                        ))
+                        .build()
+                };
+                let transfer1_tx = transfer(mint_tx_id);
+                let transfer2_tx = transfer(transfer1_tx.transaction().get_id());
+                // in the same block as the previous one, so the index in the block decides
+                let transfer3_tx = transfer(transfer2_tx.transaction().get_id());
+
+                // a transaction that doesn't reference the NFT
+                let coins_tx = TransactionBuilder::new()
+                    .add_input(
+                        TxInput::from_utxo(mint_tx_id.into(), 1),
+                        InputWitness::NoSignature(None),
+                    )
+                    .add_output(TxOutput::Transfer(
+                        OutputValue::Coin(Amount::from_atoms(100)),
+                        Destination::AnyoneCanSpend,
+                    ))
+                    .build();
+
+                let expected_tx_ids = [&transfer3_tx, &transfer2_tx, &transfer1_tx, &mint_tx]
+                    .iter()
+                    .map(|tx| tx.transaction().get_id().to_hash().encode_hex::<String>())
+                    .collect::<Vec<_>>();
+
+                let blocks =
+                    [vec![mint_tx], vec![transfer1_tx], vec![transfer2_tx, coins_tx, transfer3_tx]]
+                        .into_iter()
+                        .map(|transactions| {
+                            let block = tf
+                                .make_block_builder()
+                                .with_transactions(transactions)
+                                .build(&mut rng);
+                            tf.process_block(block.clone(), BlockSource::Local).unwrap();
+                            block
+                        })
+                        .collect::<Vec<_>>();
+
+                _ = tx.send((token_id, expected_tx_ids));
+
+                blocks
+            };
+
+            let storage = {
+                let mut storage = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+                let mut db_tx = storage.transaction_rw().await.unwrap();
+                db_tx.reinitialize_storage(&chain_config).await.unwrap();
+                db_tx.commit().await.unwrap();
+
+                storage
+            };
+
+            let chain_config = Arc::new(chain_config);
+
+            let mut local_node = BlockchainState::new(Arc::clone(&chain_config), storage);
+            local_node.scan_genesis(chain_config.genesis_block()).await.unwrap();
+            local_node.scan_blocks(BlockHeight::new(0), chainstate_blocks).await.unwrap();
+
+            ApiServerWebServerState {
+                db: Arc::new(local_node.storage().clone_storage().await),
+                chain_config: Arc::clone(&chain_config),
+                rpc: Arc::new(DummyRPC {}),
+                cached_values: Arc::new(CachedValues {
+                    feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
+                }),
+                time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
+            }
+        };
+
+        web_server(listener, web_server_state, false).await
+    });
+
+    let chain_config = create_unit_test_config();
+    let (token_id, expected_tx_ids) = rx.await.unwrap();
+    let token_id = Address::new(&chain_config, token_id).unwrap();
+
+    let get = |url: String| async move {
+        // Given that the listener port is open, this will block until a
+        // response is made (by the web server, which takes the listener
+        // over)
+        let response = reqwest::get(format!("http://{}:{}{url}", addr.ip(), addr.port()))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200);
+
+        let body = response.text().await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        body.as_array()
+            .unwrap()
+            .iter()
+            .map(|tx| tx["id"].as_str().unwrap().to_owned())
+            .collect::<Vec<_>>()
+    };
+
+    // the newest transactions come first
+    let tx_ids = get(format!("/api/v2/token/{token_id}/transactions")).await;
+    assert_eq!(tx_ids, expected_tx_ids);
+
+    let tx_ids = get(format!(
+        "/api/v2/token/{token_id}/transactions?offset=1&items=2"
+    ))
+    .await;
+    assert_eq!(tx_ids, expected_tx_ids[1..3]);
+
+    task.abort();
+}
@@ -34,7 +34,7 @@ async fn get_transaction_failed() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid transaction Id");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid transaction Id");
 
     task.abort();
 }
@@ -146,8 +146,11 @@ async fn get_block_failed(#[case] seed: Seed) {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
@@ -170,7 +173,7 @@ async fn get_block_failed(#[case] seed: Seed) {
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
     let body = body.as_object().unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Block not found");
+    assert_eq!(body["detail"].as_str().unwrap(), "Block not found");
 
     task.abort();
 }
@@ -261,8 +264,11 @@ async fn transaction_not_part_of_block(#[case] seed: Seed) {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
@@ -286,7 +292,7 @@ async fn transaction_not_part_of_block(#[case] seed: Seed) {
     let body = body.as_object().unwrap();
 
     assert_eq!(
-        body["error"].as_str().unwrap(),
+        body["detail"].as_str().unwrap(),
         "Transaction not part of any block"
     );
 
@@ -391,8 +397,11 @@ async fn cannot_find_transaction_in_block(#[case] seed: Seed) {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
@@ -416,7 +425,7 @@ async fn cannot_find_transaction_in_block(#[case] seed: Seed) {
     let body = body.as_object().unwrap();
 
     assert_eq!(
-        body["error"].as_str().unwrap(),
+        body["detail"].as_str().unwrap(),
         "Cannot find transaction in block"
     );
 
@@ -505,8 +514,11 @@ async fn ok(#[case] seed: Seed) {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
@@ -0,0 +1,79 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::str::FromStr;
+
+use serialization::hex_encoded::HexEncoded;
+
+use super::{
+    helpers::{get_raw, spawn_webserver_with_random_chain},
+    *,
+};
+
+#[tokio::test]
+async fn invalid_transaction_id() {
+    let (task, response) = spawn_webserver("/api/v2/transaction/invalid-transaction-id/raw").await;
+
+    assert_eq!(response.status(), 400);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid transaction Id");
+
+    task.abort();
+}
+
+#[tokio::test]
+async fn transaction_not_found() {
+    let (task, response) = spawn_webserver(
+        "/api/v2/transaction/0000000000000000000000000000000000000000000000000000000000000001/raw",
+    )
+    .await;
+
+    assert_eq!(response.status(), 404);
+
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(body["detail"].as_str().unwrap(), "Transaction not found");
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn ok(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let (task, addr, block) = spawn_webserver_with_random_chain(&mut rng).await;
+
+    let transaction_index = rng.gen_range(0..block.transactions().len());
+    let expected_transaction = block.transactions()[transaction_index].clone();
+    let transaction_id = expected_transaction.transaction().get_id();
+    let url = format!(
+        "/api/v2/transaction/{}/raw",
+        transaction_id.to_hash().encode_hex::<String>()
+    );
+
+    let body = get_raw(addr, &url).await;
+    let transaction = HexEncoded::<SignedTransaction>::from_str(&body).unwrap().take();
+
+    assert_eq!(transaction.transaction().get_id(), transaction_id);
+    assert_eq!(transaction, expected_transaction);
+
+    task.abort();
+}
@@ -13,6 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use api_web_server::api::v2::TX_BODY_LIMIT;
 use chainstate_test_framework::empty_witness;
 use common::{chain::UtxoOutPoint, primitives::H256};
 use serialization::hex_encoded::HexEncoded;
@@ -38,8 +39,11 @@ async fn dissabled_post_route() {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
@@ -63,7 +67,7 @@ async fn dissabled_post_route() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Forbidden endpoint");
+    assert_eq!(body["detail"].as_str().unwrap(), "Forbidden endpoint");
 
     task.abort();
 }
@@ -87,8 +91,11 @@ async fn invalid_transaction() {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
@@ -113,13 +120,74 @@ async fn invalid_transaction() {
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
     assert_eq!(
-        body["error"].as_str().unwrap(),
+        body["detail"].as_str().unwrap(),
         "Invalid signed transaction"
     );
 
     task.abort();
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn transaction_too_large(#[case] seed: Seed) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let url = "/api/v2/transaction";
+
+    let mut rng = make_seedable_rng(seed);
+
+    let task = tokio::spawn(async move {
+        let web_server_state = {
+            let chain_config = Arc::new(create_unit_test_config());
+            let storage = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+            ApiServerWebServerState {
+                db: Arc::new(storage),
+                chain_config: Arc::clone(&chain_config),
+                rpc: Arc::new(DummyRPC {}),
+                cached_values: Arc::new(CachedValues {
+                    feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
+                }),
+                time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
+            }
+        };
+
+        web_server(listener, web_server_state, true).await.unwrap();
+    });
+
+    // Slightly over the limit and way over it
+    for body_len in [TX_BODY_LIMIT + rng.gen_range(1..10), TX_BODY_LIMIT * rng.gen_range(4..10)] {
+        let body = (0..body_len).map(|_| rng.gen_range('0'..='9')).collect::<String>();
+
+        // Given that the listener port is open, this will block until a
+        // response is made (by the web server, which takes the listener
+        // over)
+        let response = reqwest::Client::new()
+            .post(format!("http://{}:{}{url}", addr.ip(), addr.port()))
+            .body(body)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 413);
+
+        let body = response.text().await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(
+            body["detail"].as_str().unwrap(),
+            format!("Transaction is too large, the maximum is {TX_BODY_LIMIT} bytes")
+        );
+    }
+
+    task.abort();
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -142,8 +210,11 @@ async fn ok(#[case] seed: Seed) {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
@@ -16,7 +16,11 @@
 use api_server_common::storage::storage_api::{
     block_aux_data::BlockAuxData, TransactionInfo, TxAdditionalInfo,
 };
-use api_web_server::api::json_helpers::to_tx_json_with_block_info;
+use api_web_server::{
+    api::json_helpers::to_tx_json_with_block_info,
+    config::{EndpointPageSize, PaginatedEndpoint},
+    PageSizeLimit, PageSizeLimits,
+};
 
 use super::*;
 
@@ -29,7 +33,7 @@ async fn invalid_offset() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid offset");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid offset");
 
     task.abort();
 }
@@ -43,7 +47,7 @@ async fn invalid_num_items() {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid number of items");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid number of items");
 
     task.abort();
 }
@@ -63,7 +67,100 @@ async fn invalid_num_items_max(#[case] seed: Seed) {
     let body = response.text().await.unwrap();
     let body: serde_json::Value = serde_json::from_str(&body).unwrap();
 
-    assert_eq!(body["error"].as_str().unwrap(), "Invalid number of items");
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid number of items");
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn configured_max_num_items(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let max_num_items = rng.gen_range(101..1000);
+
+    let endpoint_page_size =
+        format!("transactions=10:{max_num_items}").parse::<EndpointPageSize>().unwrap();
+    assert_eq!(
+        endpoint_page_size,
+        EndpointPageSize {
+            endpoint: PaginatedEndpoint::Transactions,
+            default: 10,
+            max: max_num_items,
+        }
+    );
+    assert!("transactions=10".parse::<EndpointPageSize>().is_err());
+    assert!("unknown=10:100".parse::<EndpointPageSize>().is_err());
+
+    let mut page_size_limits = PageSizeLimits::default();
+    page_size_limits.set(
+        endpoint_page_size.endpoint,
+        PageSizeLimit {
+            default: endpoint_page_size.default,
+            max: endpoint_page_size.max,
+        },
+    );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let task = tokio::spawn(async move {
+        let web_server_state = {
+            let chain_config = Arc::new(create_unit_test_config());
+
+            let storage = {
+                let mut storage = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+                let mut db_tx = storage.transaction_rw().await.unwrap();
+                db_tx.reinitialize_storage(&chain_config).await.unwrap();
+                db_tx.commit().await.unwrap();
+
+                storage
+            };
+
+            ApiServerWebServerState {
+                db: Arc::new(storage),
+                chain_config,
+                rpc: Arc::new(DummyRPC {}),
+                cached_values: Arc::new(CachedValues {
+                    feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
+                }),
+                time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits,
+            }
+        };
+
+        web_server(listener, web_server_state, true).await
+    });
+
+    let get = |url: String| async move {
+        // Given that the listener port is open, this will block until a
+        // response is made (by the web server, which takes the listener
+        // over)
+        reqwest::get(format!("http://{}:{}{url}", addr.ip(), addr.port()))
+            .await
+            .unwrap()
+    };
+
+    // A page larger than the default max is allowed
+    let items = rng.gen_range(101..=max_num_items);
+    let response = get(format!("/api/v2/transaction?items={items}")).await;
+    assert_eq!(response.status(), 200);
+
+    // The configured max is still enforced
+    let items = rng.gen_range(max_num_items + 1..max_num_items * 2);
+    let response = get(format!("/api/v2/transaction?items={items}")).await;
+    assert_eq!(response.status(), 400);
+    let body = response.text().await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["detail"].as_str().unwrap(), "Invalid number of items");
+
+    // Other endpoints keep their own limits
+    let response = get("/api/v2/pool?items=101".to_owned()).await;
+    assert_eq!(response.status(), 400);
 
     task.abort();
 }
@@ -170,8 +267,11 @@ async fn ok(#[case] seed: Seed) {
                 rpc: Arc::new(DummyRPC {}),
                 cached_values: Arc::new(CachedValues {
                     feerate_points: RwLock::new((get_time(), vec![])),
+                    mempool_tx_info: RwLock::new((get_time(), vec![])),
                 }),
                 time_getter: Default::default(),
+                cors_allowed_origins: Default::default(),
+                page_size_limits: Default::default(),
             }
         };
 
@@ -0,0 +1,517 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{collections::BTreeSet, time::Duration};
+
+use api_server_common::storage::storage_api::{
+    block_aux_data::BlockWithExtraData, ApiServerStorageRead, ApiServerWebhookStorage,
+    WebhookEvent, WebhookSubscription,
+};
+use api_web_server::webhooks::{
+    sign_payload, WebhookNotifier, MAX_SUBSCRIPTIONS, SIGNATURE_HEADER,
+};
+use axum::http::HeaderMap;
+use common::{
+    chain::{Block, ChainConfig},
+    primitives::H256,
+};
+use hmac::{Hmac, Mac};
+use randomness::CryptoRng;
+use sha2::Sha256;
+use tokio::sync::mpsc;
+
+use crate::DummyRPC;
+
+use super::*;
+
+const SECRET: &str = "webhook secret of the subscriber";
+
+/// An HTTP endpoint that forwards the headers and the bodies of the POST requests to the channel
+async fn spawn_mock_endpoint() -> (
+    String,
+    mpsc::UnboundedReceiver<(HeaderMap, String)>,
+    tokio::task::JoinHandle<()>,
+) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (tx, rx) = mpsc::unbounded_channel();
+
+    let router = axum::Router::new().route(
+        "/hook",
+        axum::routing::post(move |headers: HeaderMap, body: String| {
+            let tx = tx.clone();
+            async move {
+                tx.send((headers, body)).unwrap();
+            }
+        }),
+    );
+    let task = tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+
+    (format!("http://{addr}/hook"), rx, task)
+}
+
+fn web_server_state(
+    db: Arc<TransactionalApiServerInMemoryStorage>,
+    chain_config: Arc<ChainConfig>,
+) -> ApiServerWebServerState<Arc<TransactionalApiServerInMemoryStorage>, Arc<DummyRPC>> {
+    ApiServerWebServerState {
+        db,
+        chain_config,
+        rpc: Arc::new(DummyRPC {}),
+        cached_values: Arc::new(CachedValues {
+            feerate_points: RwLock::new((get_time(), vec![])),
+            mempool_tx_info: RwLock::new((get_time(), vec![])),
+        }),
+        time_getter: Default::default(),
+        cors_allowed_origins: Default::default(),
+        page_size_limits: Default::default(),
+    }
+}
+
+/// Spawn the web server and return the URL of the webhook endpoint
+async fn spawn_web_server(
+    db: Arc<TransactionalApiServerInMemoryStorage>,
+    chain_config: Arc<ChainConfig>,
+) -> (String, tokio::task::JoinHandle<()>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let task = tokio::spawn(async move {
+        web_server(listener, web_server_state(db, chain_config), true).await.unwrap();
+    });
+
+    (
+        format!("http://{}:{}/api/v2/webhook", addr.ip(), addr.port()),
+        task,
+    )
+}
+
+fn random_address(rng: &mut (impl Rng + CryptoRng), chain_config: &ChainConfig) -> String {
+    let (_, public_key) = PrivateKey::new_from_rng(rng, KeyKind::Secp256k1Schnorr);
+    let destination = Destination::PublicKeyHash(PublicKeyHash::from(&public_key));
+    Address::<Destination>::new(chain_config, destination).unwrap().into_string()
+}
+
+/// Add a main chain block, as the scanner would, along with the transactions of the addresses
+async fn add_block(
+    db: &mut TransactionalApiServerInMemoryStorage,
+    block: Block,
+    height: u64,
+    address_transactions: &[(&str, Id<Transaction>)],
+) {
+    let mut db_tx = db.transaction_rw().await.unwrap();
+    for (address, tx_id) in address_transactions {
+        db_tx
+            .set_address_transactions_at_height(
+                address,
+                BTreeSet::from([*tx_id]),
+                BlockHeight::new(height),
+            )
+            .await
+            .unwrap();
+    }
+    db_tx
+        .set_mainchain_block(
+            block.get_id(),
+            BlockHeight::new(height),
+            &BlockWithExtraData {
+                block,
+                tx_additional_infos: vec![],
+            },
+        )
+        .await
+        .unwrap();
+    db_tx.commit().await.unwrap();
+}
+
+/// Poll with a new notifier every time, like after a restart of the web server,
+/// so the delivered blocks are only remembered through the stored cursor
+async fn poll(db: &Arc<TransactionalApiServerInMemoryStorage>) {
+    WebhookNotifier::new(Arc::clone(db))
+        .allow_non_public_destinations()
+        .poll()
+        .await
+        .unwrap();
+}
+
+async fn recv_delivery(rx: &mut mpsc::UnboundedReceiver<(HeaderMap, String)>) -> serde_json::Value {
+    let (headers, body) =
+        tokio::time::timeout(Duration::from_secs(10), rx.recv()).await.unwrap().unwrap();
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+    mac.update(body.as_bytes());
+    let expected_signature = mac.finalize().into_bytes().encode_hex::<String>();
+    assert_eq!(
+        headers.get(SIGNATURE_HEADER).unwrap().to_str().unwrap(),
+        expected_signature
+    );
+
+    serde_json::from_str(&body).unwrap()
+}
+
+#[rstest]
+#[case(r#"not json"#, "Invalid webhook subscription request")]
+#[case(
+    r#"{"url": "https://example.com", "events": [], "secret": "webhook secret of the subscriber"}"#,
+    "Invalid webhook subscription request"
+)]
+#[case(
+    r#"{"url": "https://example.com", "events": ["block"], "secret": ""}"#,
+    "Webhook secret must be at least 16 bytes"
+)]
+#[case(
+    r#"{"url": "https://example.com", "events": ["block"], "secret": "short secret"}"#,
+    "Webhook secret must be at least 16 bytes"
+)]
+#[case(
+    r#"{"url": "not a url", "events": ["block"], "secret": "webhook secret of the subscriber"}"#,
+    "Invalid webhook URL"
+)]
+#[case(
+    r#"{"url": "ftp://example.com", "events": ["block"], "secret": "webhook secret of the subscriber"}"#,
+    "Invalid webhook URL"
+)]
+#[case(
+    r#"{"url": "http://example.com", "events": ["block"], "secret": "webhook secret of the subscriber"}"#,
+    "Invalid webhook URL"
+)]
+#[case(
+    r#"{"url": "https://127.0.0.1/hook", "events": ["block"], "secret": "webhook secret of the subscriber"}"#,
+    "Invalid webhook URL"
+)]
+#[case(
+    r#"{"url": "https://10.1.2.3/hook", "events": ["block"], "secret": "webhook secret of the subscriber"}"#,
+    "Invalid webhook URL"
+)]
+#[case(
+    r#"{"url": "https://169.254.169.254/hook", "events": ["block"], "secret": "webhook secret of the subscriber"}"#,
+    "Invalid webhook URL"
+)]
+#[case(
+    r#"{"url": "https://[::1]/hook", "events": ["block"], "secret": "webhook secret of the subscriber"}"#,
+    "Invalid webhook URL"
+)]
+#[case(
+    r#"{"url": "https://[::ffff:192.168.0.1]/hook", "events": ["block"], "secret": "webhook secret of the subscriber"}"#,
+    "Invalid webhook URL"
+)]
+#[case(
+    r#"{"url": "https://localhost/hook", "events": ["block"], "secret": "webhook secret of the subscriber"}"#,
+    "Invalid webhook URL"
+)]
+#[case(
+    r#"{"url": "https://intranet/hook", "events": ["block"], "secret": "webhook secret of the subscriber"}"#,
+    "Invalid webhook URL"
+)]
+#[case(
+    r#"{"url": "https://example.com", "events": ["transaction"], "secret": "webhook secret of the subscriber"}"#,
+    "Invalid webhook event: transaction"
+)]
+#[case(
+    r#"{"url": "https://example.com", "events": ["address/invalid"], "secret": "webhook secret of the subscriber"}"#,
+    "Invalid webhook event: address/invalid"
+)]
+#[tokio::test]
+async fn invalid_subscription(#[case] body: &'static str, #[case] expected_detail: &str) {
+    let chain_config = Arc::new(create_unit_test_config());
+    let db = Arc::new(TransactionalApiServerInMemoryStorage::new(&chain_config));
+    let (webhook_url, task) = spawn_web_server(db, chain_config).await;
+
+    let response = reqwest::Client::new().post(webhook_url).body(body).send().await.unwrap();
+
+    assert_eq!(response.status(), 400);
+
+    let body: serde_json::Value = serde_json::from_str(&response.text().await.unwrap()).unwrap();
+    assert_eq!(body["detail"].as_str().unwrap(), expected_detail);
+
+    task.abort();
+}
+
+#[tokio::test]
+async fn too_many_subscriptions() {
+    let chain_config = Arc::new(create_unit_test_config());
+    let db = TransactionalApiServerInMemoryStorage::new(&chain_config);
+
+    let subscription = WebhookSubscription {
+        url: "https://example.com/hook".to_owned(),
+        events: BTreeSet::from([WebhookEvent::Block]),
+        secret: SECRET.to_owned(),
+    };
+    for _ in 0..MAX_SUBSCRIPTIONS {
+        db.add_webhook_subscription(&subscription, MAX_SUBSCRIPTIONS)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    let (webhook_url, task) = spawn_web_server(Arc::new(db), chain_config).await;
+
+    let response = reqwest::Client::new()
+        .post(webhook_url)
+        .body(
+            json!({
+                "url": "https://example.com/hook",
+                "events": ["block"],
+                "secret": SECRET,
+            })
+            .to_string(),
+        )
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 400);
+
+    let body: serde_json::Value = serde_json::from_str(&response.text().await.unwrap()).unwrap();
+    assert_eq!(
+        body["detail"].as_str().unwrap(),
+        "Too many webhook subscriptions"
+    );
+
+    task.abort();
+}
+
+#[tokio::test]
+async fn unsubscribe() {
+    let chain_config = Arc::new(create_unit_test_config());
+    let db = Arc::new(TransactionalApiServerInMemoryStorage::new(&chain_config));
+    let (webhook_url, task) = spawn_web_server(Arc::clone(&db), chain_config).await;
+
+    // Subscribe
+    let response = reqwest::Client::new()
+        .post(&webhook_url)
+        .body(
+            json!({
+                "url": "https://example.com/hook",
+                "events": ["block"],
+                "secret": SECRET,
+            })
+            .to_string(),
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = serde_json::from_str(&response.text().await.unwrap()).unwrap();
+    let webhook_id = body["id"].as_u64().unwrap();
+
+    let delete = |signature: Option<String>| {
+        let request = reqwest::Client::new().delete(format!("{webhook_url}/{webhook_id}"));
+        let request = match signature {
+            Some(signature) => request.header(SIGNATURE_HEADER, signature),
+            None => request,
+        };
+        request.send()
+    };
+
+    // Without the signature or with a wrong one the subscription stays
+    let response = delete(None).await.unwrap();
+    assert_eq!(response.status(), 403);
+
+    let response = delete(Some(sign_payload(
+        "some other secret",
+        webhook_id.to_string().as_bytes(),
+    )))
+    .await
+    .unwrap();
+    assert_eq!(response.status(), 403);
+
+    let response = delete(Some(sign_payload(SECRET, b"some other id"))).await.unwrap();
+    assert_eq!(response.status(), 403);
+
+    let db_tx = db.transaction_ro().await.unwrap();
+    assert!(db_tx.get_webhook_subscriptions().await.unwrap().contains_key(&webhook_id));
+    drop(db_tx);
+
+    // Signed with the secret of the subscription
+    let signature = sign_payload(SECRET, webhook_id.to_string().as_bytes());
+    let response = delete(Some(signature.clone())).await.unwrap();
+    assert_eq!(response.status(), 204);
+
+    let response = delete(Some(signature)).await.unwrap();
+    assert_eq!(response.status(), 404);
+
+    let db_tx = db.transaction_ro().await.unwrap();
+    assert!(db_tx.get_webhook_subscriptions().await.unwrap().is_empty());
+    drop(db_tx);
+
+    task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn deliver_events(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_unit_test_config());
+    let mut db = Arc::new(TransactionalApiServerInMemoryStorage::new(&chain_config));
+
+    let (endpoint_url, mut endpoint_rx, endpoint_task) = spawn_mock_endpoint().await;
+
+    let address = random_address(&mut rng, &chain_config);
+    let other_address = random_address(&mut rng, &chain_config);
+
+    // The first poll only stores the cursor
+    poll(&db).await;
+
+    let block_subscription_id = db
+        .add_webhook_subscription(
+            &WebhookSubscription {
+                url: endpoint_url.clone(),
+                events: BTreeSet::from([WebhookEvent::Block]),
+                secret: SECRET.to_owned(),
+            },
+            MAX_SUBSCRIPTIONS,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    db.add_webhook_subscription(
+        &WebhookSubscription {
+            url: endpoint_url,
+            events: BTreeSet::from([WebhookEvent::Address(address.clone())]),
+            secret: SECRET.to_owned(),
+        },
+        MAX_SUBSCRIPTIONS,
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    let mut tf = TestFramework::builder(&mut rng)
+        .with_chain_config(chain_config.as_ref().clone())
+        .build();
+
+    // A block that involves the address is delivered to both subscriptions
+    let block = tf.make_block_builder().build(&mut rng);
+    let block_id = block.get_id();
+    tf.process_block(block.clone(), BlockSource::Local).unwrap();
+    let tx_id = Id::<Transaction>::new(H256::random_using(&mut rng));
+    let other_tx_id = Id::<Transaction>::new(H256::random_using(&mut rng));
+    add_block(
+        Arc::get_mut(&mut db).unwrap(),
+        block,
+        1,
+        &[(&address, tx_id), (&other_address, other_tx_id)],
+    )
+    .await;
+
+    poll(&db).await;
+
+    let payloads = [recv_delivery(&mut endpoint_rx).await, recv_delivery(&mut endpoint_rx).await];
+    let expected_block_payload = json!({
+        "event": "block",
+        "block_id": block_id.to_hash().encode_hex::<String>(),
+        "block_height": 1,
+    });
+    let expected_address_payload = json!({
+        "event": format!("address/{address}"),
+        "block_id": block_id.to_hash().encode_hex::<String>(),
+        "block_height": 1,
+        "transaction_ids": [tx_id.to_hash().encode_hex::<String>()],
+    });
+    assert!(payloads.contains(&expected_block_payload));
+    assert!(payloads.contains(&expected_address_payload));
+
+    // Nothing more is delivered for the same block
+    poll(&db).await;
+    assert!(
+        tokio::time::timeout(Duration::from_millis(500), endpoint_rx.recv())
+            .await
+            .is_err()
+    );
+
+    // A block without the address is only delivered to the block subscription
+    let block = tf.make_block_builder().build(&mut rng);
+    let block_id = block.get_id();
+    tf.process_block(block.clone(), BlockSource::Local).unwrap();
+    add_block(Arc::get_mut(&mut db).unwrap(), block, 2, &[]).await;
+
+    poll(&db).await;
+
+    assert_eq!(
+        recv_delivery(&mut endpoint_rx).await,
+        json!({
+            "event": "block",
+            "block_id": block_id.to_hash().encode_hex::<String>(),
+            "block_height": 2,
+        })
+    );
+    assert!(
+        tokio::time::timeout(Duration::from_millis(500), endpoint_rx.recv())
+            .await
+            .is_err()
+    );
+
+    // Nothing is delivered after unsubscribing
+    assert!(db.del_webhook_subscription(block_subscription_id).await.unwrap());
+
+    let block = tf.make_block_builder().build(&mut rng);
+    add_block(Arc::get_mut(&mut db).unwrap(), block, 3, &[]).await;
+
+    poll(&db).await;
+    assert!(
+        tokio::time::timeout(Duration::from_millis(500), endpoint_rx.recv())
+            .await
+            .is_err()
+    );
+
+    endpoint_task.abort();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn non_public_destinations_are_not_delivered_to(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_unit_test_config());
+    let mut db = Arc::new(TransactionalApiServerInMemoryStorage::new(&chain_config));
+
+    let (endpoint_url, mut endpoint_rx, endpoint_task) = spawn_mock_endpoint().await;
+
+    WebhookNotifier::new(Arc::clone(&db)).poll().await.unwrap();
+
+    db.add_webhook_subscription(
+        &WebhookSubscription {
+            url: endpoint_url,
+            events: BTreeSet::from([WebhookEvent::Block]),
+            secret: SECRET.to_owned(),
+        },
+        MAX_SUBSCRIPTIONS,
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    let mut tf = TestFramework::builder(&mut rng)
+        .with_chain_config(chain_config.as_ref().clone())
+        .build();
+    let block = tf.make_block_builder().build(&mut rng);
+    add_block(Arc::get_mut(&mut db).unwrap(), block, 1, &[]).await;
+
+    WebhookNotifier::new(Arc::clone(&db)).poll().await.unwrap();
+
+    assert!(
+        tokio::time::timeout(Duration::from_millis(500), endpoint_rx.recv())
+            .await
+            .is_err()
+    );
+
+    endpoint_task.abort();
+}
@@ -14,7 +14,10 @@
 // limitations under the License.
 
 use serialization::extras::non_empty_vec::DataOrNoVec;
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
 
 use crate::helpers::make_trial;
 use crate::make_test;
@@ -25,8 +28,9 @@ use api_server_common::storage::{
     storage_api::{
         block_aux_data::{BlockAuxData, BlockWithExtraData},
         ApiServerStorage, ApiServerStorageRead, ApiServerStorageWrite, ApiServerTransactionRw,
-        BlockInfo, CoinOrTokenStatistic, Delegation, FungibleTokenData, LockedUtxo,
-        TransactionInfo, TxAdditionalInfo, Utxo, UtxoLock, UtxoWithExtraInfo,
+        ApiServerWebhookStorage, BlockInfo, CoinOrTokenStatistic, Delegation, FungibleTokenData,
+        LockedUtxo, TransactionInfo, TxAdditionalInfo, Utxo, UtxoLock, UtxoWithExtraInfo,
+        WebhookEvent, WebhookSubscription,
     },
 };
 use crypto::{
@@ -1363,6 +1367,136 @@ where
         db_tx.commit().await.unwrap();
     }
 
+    // webhook subscriptions
+    {
+        let mut db_tx = storage.transaction_rw().await.unwrap();
+        assert!(db_tx.get_webhook_subscriptions().await.unwrap().is_empty());
+
+        let subscription1 = WebhookSubscription {
+            url: "https://example.com/hook".to_owned(),
+            events: BTreeSet::from([WebhookEvent::Block]),
+            secret: "secret1".to_owned(),
+        };
+        let subscription2 = WebhookSubscription {
+            url: "https://example.org/hook".to_owned(),
+            events: BTreeSet::from([
+                WebhookEvent::Block,
+                WebhookEvent::Address("some address".to_owned()),
+            ]),
+            secret: "secret2".to_owned(),
+        };
+
+        let id1 = db_tx.add_webhook_subscription(&subscription1, 2).await.unwrap().unwrap();
+        let id2 = db_tx.add_webhook_subscription(&subscription2, 2).await.unwrap().unwrap();
+        assert_ne!(id1, id2);
+        // the limit is reached
+        assert_eq!(
+            db_tx.add_webhook_subscription(&subscription1, 2).await.unwrap(),
+            None
+        );
+        db_tx.commit().await.unwrap();
+
+        let mut db_tx = storage.transaction_rw().await.unwrap();
+        assert_eq!(
+            db_tx.get_webhook_subscriptions().await.unwrap(),
+            BTreeMap::from([(id1, subscription1), (id2, subscription2.clone())])
+        );
+
+        assert!(db_tx.del_webhook_subscription(id1).await.unwrap());
+        assert!(!db_tx.del_webhook_subscription(id1).await.unwrap());
+        assert_eq!(
+            db_tx.get_webhook_subscriptions().await.unwrap(),
+            BTreeMap::from([(id2, subscription2.clone())])
+        );
+
+        assert_eq!(db_tx.get_webhook_cursor().await.unwrap(), None);
+        db_tx.set_webhook_cursor(BlockHeight::new(5)).await.unwrap();
+        db_tx.set_webhook_cursor(BlockHeight::new(7)).await.unwrap();
+        assert_eq!(
+            db_tx.get_webhook_cursor().await.unwrap(),
+            Some(BlockHeight::new(7))
+        );
+
+        db_tx.commit().await.unwrap();
+
+        // through the shared handle
+        let id3 = storage.add_webhook_subscription(&subscription1, 2).await.unwrap().unwrap();
+        assert_eq!(
+            storage.add_webhook_subscription(&subscription1, 2).await.unwrap(),
+            None
+        );
+        storage.set_webhook_cursor(BlockHeight::new(8)).await.unwrap();
+
+        let db_tx = storage.transaction_ro().await.unwrap();
+        assert_eq!(
+            db_tx.get_webhook_subscriptions().await.unwrap(),
+            BTreeMap::from([(id2, subscription2), (id3, subscription1)])
+        );
+        assert_eq!(
+            db_tx.get_webhook_cursor().await.unwrap(),
+            Some(BlockHeight::new(8))
+        );
+        drop(db_tx);
+
+        assert!(storage.del_webhook_subscription(id3).await.unwrap());
+        assert!(!storage.del_webhook_subscription(id3).await.unwrap());
+    }
+
+    // address transactions of a block
+    {
+        let mut db_tx = storage.transaction_rw().await.unwrap();
+
+        let address1 = "address1";
+        let address2 = "address2";
+        let tx_id1 = Id::<Transaction>::new(H256::random_using(&mut rng));
+        let tx_id2 = Id::<Transaction>::new(H256::random_using(&mut rng));
+        let tx_id3 = Id::<Transaction>::new(H256::random_using(&mut rng));
+
+        db_tx
+            .set_address_transactions_at_height(
+                address1,
+                BTreeSet::from([tx_id1, tx_id2]),
+                BlockHeight::new(1),
+            )
+            .await
+            .unwrap();
+        db_tx
+            .set_address_transactions_at_height(
+                address2,
+                BTreeSet::from([tx_id2]),
+                BlockHeight::new(1),
+            )
+            .await
+            .unwrap();
+        db_tx
+            .set_address_transactions_at_height(
+                address1,
+                BTreeSet::from([tx_id3]),
+                BlockHeight::new(2),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            db_tx.get_block_address_transactions(BlockHeight::new(1)).await.unwrap(),
+            BTreeMap::from([
+                (address1.to_owned(), BTreeSet::from([tx_id1, tx_id2])),
+                (address2.to_owned(), BTreeSet::from([tx_id2])),
+            ])
+        );
+        assert_eq!(
+            db_tx.get_block_address_transactions(BlockHeight::new(2)).await.unwrap(),
+            BTreeMap::from([(address1.to_owned(), BTreeSet::from([tx_id3]))])
+        );
+        assert!(db_tx
+            .get_block_address_transactions(BlockHeight::new(3))
+            .await
+            .unwrap()
+            .is_empty());
+
+        db_tx.rollback().await.unwrap();
+    }
+
     Ok(())
 }
 
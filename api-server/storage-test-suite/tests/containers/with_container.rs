@@ -14,8 +14,10 @@
 // limitations under the License.
 
 use api_server_common::storage::storage_api::{
-    ApiServerStorage, ApiServerStorageError, Transactional,
+    ApiServerStorage, ApiServerStorageError, ApiServerWebhookStorage, Transactional,
+    WebhookSubscription,
 };
+use common::primitives::BlockHeight;
 
 use super::podman::Podman;
 
@@ -51,4 +53,26 @@ impl<'tx, S: ApiServerStorage> Transactional<'tx> for ApiServerStorageWithContai
     }
 }
 
+#[async_trait::async_trait]
+impl<S: ApiServerStorage> ApiServerWebhookStorage for ApiServerStorageWithContainer<S> {
+    async fn add_webhook_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+        max_subscriptions: usize,
+    ) -> Result<Option<u64>, ApiServerStorageError> {
+        self.storage.add_webhook_subscription(subscription, max_subscriptions).await
+    }
+
+    async fn del_webhook_subscription(&self, id: u64) -> Result<bool, ApiServerStorageError> {
+        self.storage.del_webhook_subscription(id).await
+    }
+
+    async fn set_webhook_cursor(
+        &self,
+        block_height: BlockHeight,
+    ) -> Result<(), ApiServerStorageError> {
+        self.storage.set_webhook_cursor(block_height).await
+    }
+}
+
 impl<S: ApiServerStorage> ApiServerStorage for ApiServerStorageWithContainer<S> {}
@@ -39,10 +39,11 @@ pub enum TokenDecimals<'a> {
 }
 
 impl<'a> TokenDecimals<'a> {
-    fn get(&self, token_id: &TokenId) -> u8 {
+    /// None if the token is not known yet, e.g. its issuance is still in the mempool
+    fn get(&self, token_id: &TokenId) -> Option<u8> {
         match self {
-            Self::Single(decimals) => decimals.expect("must exist"),
-            Self::Map(map) => *map.get(token_id).expect("must exist"),
+            Self::Single(decimals) => Some(decimals.expect("must exist")),
+            Self::Map(map) => map.get(token_id).copied(),
         }
     }
 }
@@ -81,7 +82,13 @@ pub fn outputvalue_to_json(
             json!({
                 "type": "TokenV1",
                 "token_id": Address::new(chain_config, *token_id).expect("no error").as_str(),
-                "amount": amount_to_json(*amount, token_decimals.get(token_id)),
+                "amount": match token_decimals.get(token_id) {
+                    Some(decimals) => amount_to_json(*amount, decimals),
+                    None => json!({
+                        "decimal": null,
+                        "atoms": amount.into_atoms().to_string(),
+                    }),
+                },
             })
         }
     }
@@ -395,6 +402,28 @@ pub fn tx_to_json(
     })
 }
 
+/// Same as `tx_to_json`, for transactions that aren't in the chain yet and so have
+/// no fee or input utxo information
+pub fn unconfirmed_tx_to_json(
+    tx: &Transaction,
+    token_decimals: &BTreeMap<TokenId, u8>,
+    chain_config: &ChainConfig,
+) -> serde_json::Value {
+    json!({
+    "id": tx.get_id().to_hash().encode_hex::<String>(),
+    "version_byte": tx.version_byte(),
+    "is_replaceable": tx.is_replaceable(),
+    "flags": tx.flags(),
+    "inputs": tx.inputs().iter().map(|inp| json!({
+        "input": tx_input_to_json(inp, chain_config, &token_decimals.into()),
+        })).collect::<Vec<_>>(),
+    "outputs": tx.outputs()
+            .iter()
+            .map(|out| txoutput_to_json(out, chain_config, &token_decimals.into()))
+            .collect::<Vec<_>>()
+    })
+}
+
 pub fn to_tx_json_with_block_info(
     tx: &TransactionInfo,
     chain_config: &ChainConfig,
@@ -18,6 +18,7 @@ pub mod v2;
 
 use crate::{
     api,
+    config::CorsAllowedOrigins,
     error::{ApiServerWebServerClientError, ApiServerWebServerError},
     ApiServerWebServerState, TxSubmitClient,
 };
@@ -27,7 +28,7 @@ use axum::{http::Method, response::IntoResponse, routing::get, Json, Router};
 use serde_json::json;
 use std::sync::Arc;
 use tokio::net::TcpListener;
-use tower_http_axum::cors::{AllowMethods, Any, CorsLayer};
+use tower_http_axum::cors::{AllowMethods, AllowOrigin, Any, CorsLayer};
 
 #[allow(clippy::unused_async)]
 async fn bad_request() -> Result<(), ApiServerWebServerError> {
@@ -42,6 +43,24 @@ async fn server_status() -> Result<impl IntoResponse, ApiServerWebServerError> {
     })))
 }
 
+fn cors_layer(allowed_origins: &CorsAllowedOrigins) -> CorsLayer {
+    let allow_origin = match allowed_origins {
+        // No origin matches an empty list, so no CORS headers are ever sent
+        CorsAllowedOrigins::SameOrigin => AllowOrigin::list([]),
+        CorsAllowedOrigins::Any => AllowOrigin::any(),
+        CorsAllowedOrigins::List(origins) => AllowOrigin::list(origins.iter().cloned()),
+    };
+
+    CorsLayer::new()
+        .allow_methods(AllowMethods::list([
+            Method::GET,
+            Method::POST,
+            Method::DELETE,
+        ]))
+        .allow_headers(Any)
+        .allow_origin(allow_origin)
+}
+
 #[allow(dead_code)]
 pub fn web_server<
     T: ApiServerStorage + Send + Sync + 'static,
@@ -51,10 +70,7 @@ pub fn web_server<
     state: ApiServerWebServerState<Arc<T>, Arc<R>>,
     enable_post_endpoints: bool,
 ) -> axum::serve::Serve<Router, Router> {
-    let cors_layer = CorsLayer::new()
-        .allow_methods(AllowMethods::list([Method::GET, Method::POST]))
-        .allow_headers(Any)
-        .allow_origin(Any);
+    let cors_layer = cors_layer(&state.cors_allowed_origins);
 
     let routes = Router::new()
         .route("/", get(server_status))
@@ -16,30 +16,35 @@
 use crate::{
     api::json_helpers::{
         amount_to_json, block_header_to_json, to_tx_json_with_block_info, tx_to_json,
-        txoutput_to_json, utxo_outpoint_to_json, TokenDecimals,
+        txoutput_to_json, unconfirmed_tx_to_json, utxo_outpoint_to_json, TokenDecimals,
     },
     error::{
         ApiServerWebServerClientError, ApiServerWebServerError, ApiServerWebServerForbiddenError,
         ApiServerWebServerNotFoundError, ApiServerWebServerServerError,
     },
-    TxSubmitClient,
+    webhooks, TxSubmitClient,
 };
 use api_server_common::storage::storage_api::{
-    block_aux_data::BlockAuxData, ApiServerStorage, ApiServerStorageRead, BlockInfo,
-    CoinOrTokenStatistic, TransactionInfo,
+    block_aux_data::BlockAuxData, ApiServerStorage, ApiServerStorageRead, ApiServerWebhookStorage,
+    BlockInfo, CoinOrTokenStatistic, TransactionInfo, WebhookSubscription,
 };
 use axum::{
-    extract::{DefaultBodyLimit, Path, Query, State},
-    response::IntoResponse,
-    routing::{get, post},
+    extract::{rejection::StringRejection, DefaultBodyLimit, Path, Query, State},
+    http::{
+        header::{CONTENT_TYPE, ETAG, IF_NONE_MATCH},
+        HeaderMap, HeaderValue, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
     Json, Router,
 };
 use common::{
     address::Address,
     chain::{
         block::timestamp::BlockTimestamp,
-        tokens::{IsTokenFreezable, IsTokenFrozen, IsTokenUnfreezable},
-        Block, Destination, SignedTransaction, Transaction,
+        output_value::OutputValue,
+        tokens::{IsTokenFreezable, IsTokenFrozen, IsTokenUnfreezable, TokenId},
+        AccountCommand, Block, Destination, SignedTransaction, Transaction, TxInput, TxOutput,
     },
     primitives::{Amount, BlockHeight, CoinOrTokenId, Id, Idable, H256},
 };
@@ -47,7 +52,13 @@ use hex::ToHex;
 use serde::Deserialize;
 use serde_json::json;
 use serialization::hex_encoded::HexEncoded;
-use std::{collections::BTreeMap, ops::Sub, str::FromStr, sync::Arc, time::Duration};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Sub,
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
 use utils::ensure;
 
 use crate::ApiServerWebServerState;
@@ -56,7 +67,9 @@ use super::json_helpers::{nft_issuance_data_to_json, to_json_string};
 
 pub const API_VERSION: &str = "2.0.0";
 
-const TX_BODY_LIMIT: usize = 10240;
+pub const TX_BODY_LIMIT: usize = 10240;
+
+const MIN_DATA_DEPOSIT_PREFIX_LEN: usize = 4;
 
 pub fn routes<
     T: ApiServerStorage + Send + Sync + 'static,
@@ -69,11 +82,14 @@ pub fn routes<
     let router = router
         .route("/chain/genesis", get(chain_genesis))
         .route("/chain/tip", get(chain_tip))
+        .route("/chain/fork-detection", get(chain_fork_detection))
+        .route("/chain/blocks", get(chain_blocks))
         .route("/chain/:height", get(chain_at_height));
 
     let router = router
         .route("/block/:id", get(block))
         .route("/block/:id/header", get(block_header))
+        .route("/block/:id/raw", get(block_raw))
         .route("/block/:id/reward", get(block_reward))
         .route("/block/:id/transaction-ids", get(block_transaction_ids));
 
@@ -86,18 +102,35 @@ pub fn routes<
         router.route("/transaction", post(forbidden_request))
     };
 
+    let router = if enable_post_routes {
+        router
+            .route("/webhook", post(add_webhook))
+            .route("/webhook/:id", delete(delete_webhook))
+    } else {
+        router
+            .route("/webhook", post(forbidden_request))
+            .route("/webhook/:id", delete(forbidden_request))
+    };
+
     let router = router.route("/feerate", get(feerate));
 
     let router = router
         .route("/transaction", get(transactions))
         .route("/transaction/:id", get(transaction))
+        .route("/transaction/:id/raw", get(transaction_raw))
         .route("/transaction/:id/merkle-path", get(transaction_merkle_path));
 
+    let router = router.route("/mempool/transaction/:id", get(mempool_transaction));
+
     let router = router
         .route("/address/:address", get(address))
         .route("/address/:address/all-utxos", get(all_address_utxos))
         .route("/address/:address/spendable-utxos", get(address_utxos))
-        .route("/address/:address/delegations", get(address_delegations));
+        .route("/address/:address/delegations", get(address_delegations))
+        .route(
+            "/address/:address/token-allowances",
+            get(address_token_allowances),
+        );
 
     let router = router
         .route("/pool", get(pools))
@@ -109,19 +142,55 @@ pub fn routes<
 
     let router = router
         .route("/statistics/coin", get(coin_statistics))
-        .route("/statistics/token/:id", get(token_statistics));
+        .route(
+            "/statistics/mempool-overview",
+            get(mempool_overview_statistics),
+        )
+        .route("/statistics/token/:id", get(token_statistics))
+        .route("/stats/supply", get(supply_statistics));
 
     router
         .route("/token", get(token_ids))
         .route("/token/:id", get(token))
+        .route("/token/:id/transactions", get(token_transactions))
         .route("/token/ticker/:ticker", get(token_ids_by_ticker))
         .route("/nft/:id", get(nft))
+        .route("/search/data-deposit", get(search_data_deposits))
 }
 
 async fn forbidden_request() -> Result<(), ApiServerWebServerError> {
     Err(ApiServerWebServerForbiddenError::Forbidden)?
 }
 
+/// Respond with the body tagged with the given entity tag, or with `304 Not Modified`
+/// if the client already has it according to the `If-None-Match` request header.
+///
+/// Only responses that can never change for the same tag should be tagged.
+fn response_with_etag(
+    etag: Option<String>,
+    headers: &HeaderMap,
+    body: serde_json::Value,
+) -> Response {
+    let etag = match etag.and_then(|etag| HeaderValue::from_str(&format!("\"{etag}\"")).ok()) {
+        Some(etag) => etag,
+        None => return Json(body).into_response(),
+    };
+
+    let not_modified = headers
+        .get_all(IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag);
+
+    if not_modified {
+        (StatusCode::NOT_MODIFIED, [(ETAG, etag)]).into_response()
+    } else {
+        ([(ETAG, etag)], Json(body)).into_response()
+    }
+}
+
 //
 // block/
 //
@@ -158,11 +227,15 @@ async fn get_block(
 #[allow(clippy::unused_async)]
 pub async fn block<T: ApiServerStorage>(
     Path(block_id): Path<String>,
+    headers: HeaderMap,
     State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
 ) -> Result<impl IntoResponse, ApiServerWebServerError> {
     let BlockInfo { block, height } = get_block(&block_id, &state).await?;
 
-    Ok(Json(json!({
+    // A block that is not on the main chain may still get connected, changing its height
+    let etag = height.map(|_| block.block.get_id().to_hash().encode_hex::<String>());
+
+    let body = json!({
     "height": height,
     "header": block_header_to_json(&block.block),
     "body": {
@@ -177,7 +250,9 @@ pub async fn block<T: ApiServerStorage>(
                             .map(|(tx, additinal_info)| tx_to_json(tx.transaction(), additinal_info, &state.chain_config))
                             .collect::<Vec<_>>(),
     },
-    })))
+    });
+
+    Ok(response_with_etag(etag, &headers, body))
 }
 
 #[allow(clippy::unused_async)]
@@ -190,6 +265,18 @@ pub async fn block_header<T: ApiServerStorage>(
     Ok(Json(block_header_to_json(&block.block)))
 }
 
+pub async fn block_raw<T: ApiServerStorage>(
+    Path(block_id): Path<String>,
+    State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
+) -> Result<impl IntoResponse, ApiServerWebServerError> {
+    let block = get_block(&block_id, &state).await?.block;
+
+    Ok((
+        [(CONTENT_TYPE, "text/plain")],
+        HexEncoded::new(block.block).to_string(),
+    ))
+}
+
 #[allow(clippy::unused_async)]
 pub async fn block_reward<T: ApiServerStorage>(
     Path(block_id): Path<String>,
@@ -228,11 +315,13 @@ pub async fn block_transaction_ids<T: ApiServerStorage>(
 
 #[allow(clippy::unused_async)]
 pub async fn chain_genesis<T: ApiServerStorage>(
+    headers: HeaderMap,
     State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
 ) -> Result<impl IntoResponse, ApiServerWebServerError> {
     let genesis = state.chain_config.genesis_block();
+    let etag = genesis.get_id().to_hash().encode_hex::<String>();
 
-    Ok(Json(json!({
+    let body = json!({
         "block_id": genesis.get_id(),
         "genesis_message": genesis.genesis_message(),
         "timestamp": genesis.timestamp(),
@@ -240,7 +329,9 @@ pub async fn chain_genesis<T: ApiServerStorage>(
                  .iter()
                  .map(|out| txoutput_to_json(out, &state.chain_config, &TokenDecimals::Single(None)))
                  .collect::<Vec<_>>(),
-    })))
+    });
+
+    Ok(response_with_etag(Some(etag), &headers, body))
 }
 
 pub async fn chain_at_height<T: ApiServerStorage>(
@@ -274,6 +365,115 @@ pub async fn chain_at_height<T: ApiServerStorage>(
     }
 }
 
+pub async fn chain_blocks<T: ApiServerStorage>(
+    Query(params): Query<BTreeMap<String, String>>,
+    State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
+) -> Result<impl IntoResponse, ApiServerWebServerError> {
+    const FROM_TIME: &str = "from_time";
+    const TO_TIME: &str = "to_time";
+    const OFFSET: &str = "offset";
+    const ITEMS: &str = "items";
+
+    let parse_time = |key: &str| {
+        params
+            .get(key)
+            .and_then(|time| u64::from_str(time).ok())
+            .map(BlockTimestamp::from_int_seconds)
+            .ok_or(ApiServerWebServerError::ClientError(
+                ApiServerWebServerClientError::InvalidTimeRange,
+            ))
+    };
+    let from_time = parse_time(FROM_TIME)?;
+    let to_time = parse_time(TO_TIME)?;
+    ensure!(
+        from_time <= to_time,
+        ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidTimeRange)
+    );
+
+    let offset = params
+        .get(OFFSET)
+        .map(|offset| u32::from_str(offset))
+        .transpose()
+        .map_err(|_| {
+            ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidOffset)
+        })?
+        .unwrap_or_default();
+
+    let items = params
+        .get(ITEMS)
+        .map(|items| u32::from_str(items))
+        .transpose()
+        .map_err(|_| {
+            ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidNumItems)
+        })?
+        .unwrap_or(state.page_size_limits.chain_blocks.default);
+    ensure!(
+        items <= state.page_size_limits.chain_blocks.max,
+        ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidNumItems)
+    );
+
+    let tx = state.db.transaction_ro().await.map_err(|e| {
+        logging::log::error!("internal error: {e}");
+        ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+    })?;
+
+    let (from_height, to_height) =
+        tx.get_block_range_from_time_range((from_time, to_time)).await.map_err(|e| {
+            logging::log::error!("internal error: {e}");
+            ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+        })?;
+
+    // Block timestamps are not monotonic, so the blocks in the height range are filtered
+    // by their timestamps and the pages are made of the remaining ones
+    let mut blocks_to_skip = offset;
+    let mut blocks = Vec::new();
+    for height in from_height.into_int()..=to_height.into_int() {
+        if blocks.len() >= items as usize {
+            break;
+        }
+
+        let block_height = BlockHeight::new(height);
+        let block_id = tx.get_main_chain_block_id(block_height).await.map_err(|e| {
+            logging::log::error!("internal error: {e}");
+            ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+        })?;
+        // The genesis is not stored as a main chain block
+        let Some(block_id) = block_id else {
+            continue;
+        };
+
+        let block_aux_data = tx
+            .get_block_aux_data(block_id)
+            .await
+            .map_err(|e| {
+                logging::log::error!("internal error: {e}");
+                ApiServerWebServerError::ServerError(
+                    ApiServerWebServerServerError::InternalServerError,
+                )
+            })?
+            .ok_or(ApiServerWebServerError::ServerError(
+                ApiServerWebServerServerError::InternalServerError,
+            ))?;
+
+        // The storage falls back to the whole chain if no block is in the time range
+        if !(from_time..=to_time).contains(&block_aux_data.block_timestamp()) {
+            continue;
+        }
+
+        if blocks_to_skip > 0 {
+            blocks_to_skip -= 1;
+            continue;
+        }
+
+        blocks.push(json!({
+            "block_height": block_height,
+            "block_id": block_id.to_hash().encode_hex::<String>(),
+        }));
+    }
+
+    Ok(Json(serde_json::Value::Array(blocks)))
+}
+
 pub async fn chain_tip<T: ApiServerStorage>(
     State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
 ) -> Result<impl IntoResponse, ApiServerWebServerError> {
@@ -285,6 +485,62 @@ pub async fn chain_tip<T: ApiServerStorage>(
     })))
 }
 
+pub async fn chain_fork_detection<T: ApiServerStorage>(
+    Query(params): Query<BTreeMap<String, String>>,
+    State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
+) -> Result<impl IntoResponse, ApiServerWebServerError> {
+    const OFFSET: &str = "offset";
+    const ITEMS: &str = "items";
+
+    let offset = params
+        .get(OFFSET)
+        .map(|offset| u32::from_str(offset))
+        .transpose()
+        .map_err(|_| {
+            ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidOffset)
+        })?
+        .unwrap_or_default();
+
+    let items = params
+        .get(ITEMS)
+        .map(|items| u32::from_str(items))
+        .transpose()
+        .map_err(|_| {
+            ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidNumItems)
+        })?
+        .unwrap_or(state.page_size_limits.chain_forks.default);
+    ensure!(
+        items <= state.page_size_limits.chain_forks.max,
+        ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidNumItems)
+    );
+
+    let mut forks = state.rpc.get_chain_forks().await.map_err(|e| {
+        logging::log::error!("internal error: {e}");
+        ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+    })?;
+    // The most recent forks first
+    forks.sort_by(|a, b| {
+        b.fork_point_height
+            .cmp(&a.fork_point_height)
+            .then_with(|| a.orphan_tip.cmp(&b.orphan_tip))
+    });
+
+    let forks = forks
+        .into_iter()
+        .skip(offset as usize)
+        .take(items as usize)
+        .map(|fork| {
+            json!({
+                "fork_point_height": fork.fork_point_height,
+                "orphan_tip": fork.orphan_tip.to_hash().encode_hex::<String>(),
+                "main_tip": fork.main_tip.to_hash().encode_hex::<String>(),
+            })
+        })
+        .collect();
+
+    Ok(Json(serde_json::Value::Array(forks)))
+}
+
 async fn best_block<T: ApiServerStorage>(
     state: &ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>,
 ) -> Result<BlockAuxData, ApiServerWebServerError> {
@@ -401,8 +657,22 @@ pub async fn feerate<T: ApiServerStorage>(
 
 pub async fn submit_transaction<T: ApiServerStorage>(
     State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
-    body: String,
+    body: Result<String, StringRejection>,
 ) -> Result<impl IntoResponse, ApiServerWebServerError> {
+    // Map the body rejections to the API errors, so that the client gets a JSON error
+    // instead of a plain text one from the framework
+    let body = body.map_err(|rejection| {
+        if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            ApiServerWebServerError::ClientError(
+                ApiServerWebServerClientError::TransactionTooLarge(TX_BODY_LIMIT),
+            )
+        } else {
+            ApiServerWebServerError::ClientError(
+                ApiServerWebServerClientError::InvalidSignedTransaction,
+            )
+        }
+    })?;
+
     let tx = HexEncoded::<SignedTransaction>::from_str(&body)
         .map_err(|_| {
             ApiServerWebServerError::ClientError(
@@ -428,8 +698,6 @@ pub async fn transactions<T: ApiServerStorage>(
 ) -> Result<impl IntoResponse, ApiServerWebServerError> {
     const OFFSET: &str = "offset";
     const ITEMS: &str = "items";
-    const DEFAULT_NUM_ITEMS: u32 = 10;
-    const MAX_NUM_ITEMS: u32 = 100;
 
     let offset = params
         .get(OFFSET)
@@ -447,9 +715,9 @@ pub async fn transactions<T: ApiServerStorage>(
         .map_err(|_| {
             ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidNumItems)
         })?
-        .unwrap_or(DEFAULT_NUM_ITEMS);
+        .unwrap_or(state.page_size_limits.transactions.default);
     ensure!(
-        items <= MAX_NUM_ITEMS,
+        items <= state.page_size_limits.transactions.max,
         ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidNumItems)
     );
 
@@ -479,6 +747,7 @@ pub async fn transactions<T: ApiServerStorage>(
 
 pub async fn transaction<T: ApiServerStorage>(
     Path(transaction_id): Path<String>,
+    headers: HeaderMap,
     State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
 ) -> Result<impl IntoResponse, ApiServerWebServerError> {
     let (block, TransactionInfo { tx, additinal_info }) =
@@ -514,7 +783,27 @@ pub async fn transaction<T: ApiServerStorage>(
         confirmations.map_or("".to_string(), |c| c.to_string()).into(),
     );
 
-    Ok(Json(json))
+    // Only the number of confirmations changes once the transaction is in a block
+    let etag = block.as_ref().zip(confirmations).map(|(block, confirmations)| {
+        format!(
+            "{}-{confirmations}",
+            block.block_id().to_hash().encode_hex::<String>()
+        )
+    });
+
+    Ok(response_with_etag(etag, &headers, json))
+}
+
+pub async fn transaction_raw<T: ApiServerStorage>(
+    Path(transaction_id): Path<String>,
+    State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
+) -> Result<impl IntoResponse, ApiServerWebServerError> {
+    let (_, TransactionInfo { tx, .. }) = get_transaction(&transaction_id, &state).await?;
+
+    Ok((
+        [(CONTENT_TYPE, "text/plain")],
+        HexEncoded::new(tx).to_string(),
+    ))
 }
 
 pub async fn transaction_merkle_path<T: ApiServerStorage>(
@@ -582,6 +871,98 @@ pub async fn transaction_merkle_path<T: ApiServerStorage>(
     })))
 }
 
+//
+// mempool/
+//
+
+pub async fn mempool_transaction<T: ApiServerStorage>(
+    Path(transaction_id): Path<String>,
+    State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
+) -> Result<impl IntoResponse, ApiServerWebServerError> {
+    let transaction_id: Id<Transaction> = H256::from_str(&transaction_id)
+        .map_err(|_| {
+            ApiServerWebServerError::ClientError(
+                ApiServerWebServerClientError::InvalidTransactionId,
+            )
+        })?
+        .into();
+
+    let tx = state.rpc.get_mempool_tx(transaction_id).await.map_err(|e| {
+        ApiServerWebServerError::ServerError(ApiServerWebServerServerError::RpcError(e.to_string()))
+    })?;
+
+    let tx = match tx {
+        Some(tx) => tx,
+        None => {
+            return Ok(Json(json!({
+                "id": transaction_id.to_hash().encode_hex::<String>(),
+                "status": "not_found",
+            })))
+        }
+    };
+
+    let db_tx = state.db.transaction_ro().await.map_err(|e| {
+        logging::log::error!("internal error: {e}");
+        ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+    })?;
+
+    let mut token_decimals = BTreeMap::new();
+    // Tokens whose issuance is not confirmed yet, e.g. it is still in the mempool too,
+    // so their amounts can't be shown with decimals
+    let mut pending_token_ids = Vec::new();
+    for token_id in tx_token_ids(tx.transaction()) {
+        let decimals = db_tx.get_token_num_decimals(token_id).await.map_err(|e| {
+            logging::log::error!("internal error: {e}");
+            ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+        })?;
+        match decimals {
+            Some(decimals) => {
+                token_decimals.insert(token_id, decimals);
+            }
+            None => pending_token_ids
+                .push(Address::new(&state.chain_config, token_id).expect("no error").into_string()),
+        }
+    }
+
+    Ok(Json(json!({
+        "id": transaction_id.to_hash().encode_hex::<String>(),
+        "status": "in_mempool",
+        "transaction": unconfirmed_tx_to_json(tx.transaction(), &token_decimals, &state.chain_config),
+        "pending_token_ids": pending_token_ids,
+    })))
+}
+
+/// Ids of the tokens whose amounts are shown when converting the transaction to json
+fn tx_token_ids(tx: &Transaction) -> BTreeSet<TokenId> {
+    let input_values = tx.inputs().iter().filter_map(|input| match input {
+        TxInput::AccountCommand(_, AccountCommand::FillOrder(_, fill, _)) => Some(fill),
+        TxInput::Utxo(_) | TxInput::Account(_) | TxInput::AccountCommand(_, _) => None,
+    });
+
+    let output_values = tx.outputs().iter().flat_map(|output| match output {
+        TxOutput::Transfer(value, _)
+        | TxOutput::LockThenTransfer(value, _, _)
+        | TxOutput::Burn(value)
+        | TxOutput::Htlc(value, _) => vec![value],
+        TxOutput::AnyoneCanTake(data) => vec![data.ask(), data.give()],
+        TxOutput::CreateStakePool(_, _)
+        | TxOutput::ProduceBlockFromStake(_, _)
+        | TxOutput::CreateDelegationId(_, _)
+        | TxOutput::DelegateStaking(_, _)
+        | TxOutput::IssueFungibleToken(_)
+        | TxOutput::IssueNft(_, _, _)
+        | TxOutput::DataDeposit(_) => vec![],
+    });
+
+    input_values
+        .chain(output_values)
+        .filter_map(|value| match value {
+            OutputValue::TokenV1(token_id, _) => Some(*token_id),
+            OutputValue::Coin(_) | OutputValue::TokenV0(_) => None,
+        })
+        .collect()
+}
+
 //
 // address/
 //
@@ -752,6 +1133,53 @@ pub async fn address_delegations<T: ApiServerStorage>(
     ))
 }
 
+/// The amounts of tokens that other parties are allowed to spend on behalf of the address
+pub async fn address_token_allowances<T: ApiServerStorage>(
+    Path(address): Path<String>,
+    State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
+) -> Result<impl IntoResponse, ApiServerWebServerError> {
+    let address =
+        Address::<Destination>::from_string(&state.chain_config, &address).map_err(|_| {
+            ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidAddress)
+        })?;
+
+    let db_tx = state.db.transaction_ro().await.map_err(|e| {
+        logging::log::error!("internal error: {e}");
+        ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+    })?;
+
+    let allowances = db_tx.get_address_token_allowances(address.as_str()).await.map_err(|e| {
+        logging::log::error!("internal error: {e}");
+        ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+    })?;
+
+    let mut result = Vec::with_capacity(allowances.len());
+    for allowance in allowances {
+        let decimals = db_tx
+            .get_token_num_decimals(allowance.token_id)
+            .await
+            .map_err(|e| {
+                logging::log::error!("internal error: {e}");
+                ApiServerWebServerError::ServerError(
+                    ApiServerWebServerServerError::InternalServerError,
+                )
+            })?
+            .ok_or(ApiServerWebServerError::NotFound(
+                ApiServerWebServerNotFoundError::TokenNotFound,
+            ))?;
+
+        result.push(json!({
+            "token_id": Address::new(&state.chain_config, allowance.token_id).expect(
+                "no error in encoding"
+            ).as_str(),
+            "spender": allowance.spender,
+            "allowance": amount_to_json(allowance.allowance, decimals),
+        }));
+    }
+
+    Ok(Json(result))
+}
+
 //
 // pool/
 //
@@ -779,8 +1207,6 @@ pub async fn pools<T: ApiServerStorage>(
 ) -> Result<impl IntoResponse, ApiServerWebServerError> {
     const OFFSET: &str = "offset";
     const ITEMS: &str = "items";
-    const DEFAULT_NUM_ITEMS: u32 = 10;
-    const MAX_NUM_ITEMS: u32 = 100;
     const SORT: &str = "sort";
 
     let offset = params
@@ -799,9 +1225,9 @@ pub async fn pools<T: ApiServerStorage>(
         .map_err(|_| {
             ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidNumItems)
         })?
-        .unwrap_or(DEFAULT_NUM_ITEMS);
+        .unwrap_or(state.page_size_limits.pools.default);
     ensure!(
-        items <= MAX_NUM_ITEMS,
+        items <= state.page_size_limits.pools.max,
         ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidNumItems)
     );
 
@@ -1089,6 +1515,74 @@ pub async fn token<T: ApiServerStorage>(
     })))
 }
 
+pub async fn token_transactions<T: ApiServerStorage>(
+    Path(token_id): Path<String>,
+    Query(params): Query<BTreeMap<String, String>>,
+    State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
+) -> Result<impl IntoResponse, ApiServerWebServerError> {
+    const OFFSET: &str = "offset";
+    const ITEMS: &str = "items";
+
+    let token_id = Address::from_string(&state.chain_config, &token_id)
+        .map_err(|_| {
+            ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidTokenId)
+        })?
+        .into_object();
+
+    let offset = params
+        .get(OFFSET)
+        .map(|offset| u32::from_str(offset))
+        .transpose()
+        .map_err(|_| {
+            ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidOffset)
+        })?
+        .unwrap_or_default();
+
+    let items = params
+        .get(ITEMS)
+        .map(|items| u32::from_str(items))
+        .transpose()
+        .map_err(|_| {
+            ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidNumItems)
+        })?
+        .unwrap_or(state.page_size_limits.transactions.default);
+    ensure!(
+        items <= state.page_size_limits.transactions.max,
+        ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidNumItems)
+    );
+
+    let db_tx = state.db.transaction_ro().await.map_err(|e| {
+        logging::log::error!("internal error: {e}");
+        ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+    })?;
+
+    let token_exists = db_tx
+        .get_token_num_decimals(token_id)
+        .await
+        .map_err(|e| {
+            logging::log::error!("internal error: {e}");
+            ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+        })?
+        .is_some();
+    ensure!(
+        token_exists,
+        ApiServerWebServerError::NotFound(ApiServerWebServerNotFoundError::TokenNotFound)
+    );
+
+    let txs = db_tx.get_token_transactions(token_id, items, offset).await.map_err(|e| {
+        logging::log::error!("internal error: {e}");
+        ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+    })?;
+
+    let tip_height = best_block(&state).await?.block_height();
+    let txs = txs
+        .into_iter()
+        .map(|(block, tx)| to_tx_json_with_block_info(&tx, &state.chain_config, tip_height, block))
+        .collect();
+
+    Ok(Json(serde_json::Value::Array(txs)))
+}
+
 pub async fn nft<T: ApiServerStorage>(
     Path(nft_id): Path<String>,
     State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
@@ -1145,6 +1639,73 @@ pub async fn coin_statistics<T: ApiServerStorage>(
     })))
 }
 
+pub async fn mempool_overview_statistics<T: ApiServerStorage>(
+    State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
+) -> Result<impl IntoResponse, ApiServerWebServerError> {
+    const REFRESH_INTERVAL_SEC: Duration = Duration::from_secs(10);
+    const ONE_MINUTE: Duration = Duration::from_secs(60);
+    const TEN_MINUTES: Duration = Duration::from_secs(600);
+
+    let mempool_tx_info = &state.cached_values.mempool_tx_info;
+    let current_time = state.time_getter.get_time();
+
+    let txs = {
+        let last_cache_time = mempool_tx_info.read().expect("should not fail normally").0;
+
+        if (last_cache_time + REFRESH_INTERVAL_SEC).expect("no overflow") < current_time {
+            let new_tx_info = state.rpc.get_mempool_tx_info().await.map_err(|e| {
+                logging::log::error!("internal error: {e}");
+                ApiServerWebServerError::ServerError(
+                    ApiServerWebServerServerError::InternalServerError,
+                )
+            })?;
+
+            let mut guard = mempool_tx_info.write().expect("should not fail normally");
+            guard.0 = current_time;
+            guard.1 = new_tx_info;
+            guard.1.clone()
+        } else {
+            mempool_tx_info.read().expect("should not fail normally").1.clone()
+        }
+    };
+
+    let total_size_bytes: usize = txs.iter().map(|tx| tx.size).sum();
+
+    let mut feerates = txs.iter().map(|tx| tx.fee_rate.atoms_per_kb()).collect::<Vec<_>>();
+    feerates.sort();
+    let median_feerate = match feerates.len() {
+        0 => None,
+        len if len % 2 == 1 => Some(feerates[len / 2]),
+        len => Some((feerates[len / 2 - 1] + feerates[len / 2]) / 2),
+    };
+    let feerate_to_json = |feerate: Option<&u128>| feerate.map(|feerate| feerate.to_string());
+
+    let (mut under_one_minute, mut one_to_ten_minutes, mut over_ten_minutes) = (0, 0, 0);
+    for tx in &txs {
+        let age = current_time.saturating_sub(tx.creation_time);
+        if age < ONE_MINUTE {
+            under_one_minute += 1;
+        } else if age < TEN_MINUTES {
+            one_to_ten_minutes += 1;
+        } else {
+            over_ten_minutes += 1;
+        }
+    }
+
+    Ok(Json(json!({
+        "tx_count": txs.len(),
+        "total_size_bytes": total_size_bytes,
+        "min_feerate": feerate_to_json(feerates.first()),
+        "median_feerate": feerate_to_json(median_feerate.as_ref()),
+        "max_feerate": feerate_to_json(feerates.last()),
+        "age_histogram": {
+            "<1min": under_one_minute,
+            "1-10min": one_to_ten_minutes,
+            ">10min": over_ten_minutes,
+        },
+    })))
+}
+
 pub async fn token_statistics<T: ApiServerStorage>(
     Path(delegation_id): Path<String>,
     State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
@@ -1185,14 +1746,40 @@ pub async fn token_statistics<T: ApiServerStorage>(
     })))
 }
 
+pub async fn supply_statistics<T: ApiServerStorage>(
+    State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
+) -> Result<impl IntoResponse, ApiServerWebServerError> {
+    let tx = state.db.transaction_ro().await.map_err(|e| {
+        logging::log::error!("internal error: {e}");
+        ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+    })?;
+
+    // Read the tip and the statistics in the same transaction so that they are consistent
+    let best_block = tx.get_best_block().await.map_err(|e| {
+        logging::log::error!("internal error: {e}");
+        ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+    })?;
+
+    let mut statistics = tx.get_all_statistic(CoinOrTokenId::Coin).await.map_err(|e| {
+        logging::log::error!("internal error: {e}");
+        ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+    })?;
+
+    let decimals = state.chain_config.coin_decimals();
+    Ok(Json(json!({
+        "height": best_block.block_height(),
+        "circulating_coins": amount_to_json(statistics.remove(&CoinOrTokenStatistic::CirculatingSupply).unwrap_or(Amount::ZERO), decimals),
+        "total_burned": amount_to_json(statistics.remove(&CoinOrTokenStatistic::Burned).unwrap_or(Amount::ZERO), decimals),
+        "total_staked": amount_to_json(statistics.remove(&CoinOrTokenStatistic::Staked).unwrap_or(Amount::ZERO), decimals),
+    })))
+}
+
 pub async fn token_ids<T: ApiServerStorage>(
     Query(params): Query<BTreeMap<String, String>>,
     State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
 ) -> Result<impl IntoResponse, ApiServerWebServerError> {
     const OFFSET: &str = "offset";
     const ITEMS: &str = "items";
-    const DEFAULT_NUM_ITEMS: u32 = 10;
-    const MAX_NUM_ITEMS: u32 = 100;
 
     let offset = params
         .get(OFFSET)
@@ -1210,9 +1797,9 @@ pub async fn token_ids<T: ApiServerStorage>(
         .map_err(|_| {
             ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidNumItems)
         })?
-        .unwrap_or(DEFAULT_NUM_ITEMS);
+        .unwrap_or(state.page_size_limits.token_ids.default);
     ensure!(
-        items <= MAX_NUM_ITEMS,
+        items <= state.page_size_limits.token_ids.max,
         ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidNumItems)
     );
     let token_ids: Vec<_> = state
@@ -1247,8 +1834,6 @@ pub async fn token_ids_by_ticker<T: ApiServerStorage>(
 ) -> Result<impl IntoResponse, ApiServerWebServerError> {
     const OFFSET: &str = "offset";
     const ITEMS: &str = "items";
-    const DEFAULT_NUM_ITEMS: u32 = 10;
-    const MAX_NUM_ITEMS: u32 = 100;
 
     let offset = params
         .get(OFFSET)
@@ -1266,9 +1851,9 @@ pub async fn token_ids_by_ticker<T: ApiServerStorage>(
         .map_err(|_| {
             ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidNumItems)
         })?
-        .unwrap_or(DEFAULT_NUM_ITEMS);
+        .unwrap_or(state.page_size_limits.token_ids_by_ticker.default);
     ensure!(
-        items <= MAX_NUM_ITEMS,
+        items <= state.page_size_limits.token_ids_by_ticker.max,
         ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidNumItems)
     );
     let token_ids: Vec<_> = state
@@ -1295,3 +1880,203 @@ pub async fn token_ids_by_ticker<T: ApiServerStorage>(
 
     Ok(Json(serde_json::Value::Array(token_ids)))
 }
+
+//
+// search/
+//
+
+pub async fn search_data_deposits<T: ApiServerStorage>(
+    Query(params): Query<BTreeMap<String, String>>,
+    State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
+) -> Result<impl IntoResponse, ApiServerWebServerError> {
+    const QUERY: &str = "q";
+    const OFFSET: &str = "offset";
+    const ITEMS: &str = "items";
+
+    let prefix = params
+        .get(QUERY)
+        .map(hex::decode)
+        .transpose()
+        .map_err(|_| {
+            ApiServerWebServerError::ClientError(
+                ApiServerWebServerClientError::InvalidDataDepositPrefix,
+            )
+        })?
+        .unwrap_or_default();
+    ensure!(
+        prefix.len() >= MIN_DATA_DEPOSIT_PREFIX_LEN,
+        ApiServerWebServerError::ClientError(
+            ApiServerWebServerClientError::DataDepositPrefixTooShort(MIN_DATA_DEPOSIT_PREFIX_LEN)
+        )
+    );
+
+    let offset = params
+        .get(OFFSET)
+        .map(|offset| u32::from_str(offset))
+        .transpose()
+        .map_err(|_| {
+            ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidOffset)
+        })?
+        .unwrap_or_default();
+
+    let items = params
+        .get(ITEMS)
+        .map(|items| u32::from_str(items))
+        .transpose()
+        .map_err(|_| {
+            ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidNumItems)
+        })?
+        .unwrap_or(state.page_size_limits.data_deposits.default);
+    ensure!(
+        items <= state.page_size_limits.data_deposits.max,
+        ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidNumItems)
+    );
+
+    let deposits: Vec<_> = state
+        .db
+        .transaction_ro()
+        .await
+        .map_err(|e| {
+            logging::log::error!("internal error: {e}");
+            ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+        })?
+        .get_data_deposits_by_prefix(items, offset, &prefix)
+        .await
+        .map_err(|e| {
+            logging::log::error!("internal error: {e}");
+            ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+        })?
+        .into_iter()
+        .map(|deposit| {
+            json!({
+                "tx_id": deposit.tx_id.to_hash().encode_hex::<String>(),
+                "block_height": deposit.block_height,
+                "data": deposit.data.encode_hex::<String>(),
+            })
+        })
+        .collect();
+
+    Ok(Json(serde_json::Value::Array(deposits)))
+}
+
+//
+// webhook/
+//
+
+#[derive(Deserialize)]
+struct WebhookRequest {
+    url: String,
+    events: Vec<String>,
+    secret: String,
+}
+
+/// Subscribe to events, the response contains the id of the new subscription.
+///
+/// The URL must use HTTPS and point to a public host. The secret is used to sign the deliveries
+/// and to authenticate the unsubscription.
+pub async fn add_webhook<T: ApiServerStorage>(
+    State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
+    body: String,
+) -> Result<impl IntoResponse, ApiServerWebServerError> {
+    let request: WebhookRequest = serde_json::from_str(&body).map_err(|_| {
+        ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidWebhookRequest)
+    })?;
+    ensure!(
+        !request.events.is_empty() && request.events.len() <= webhooks::MAX_SUBSCRIPTION_EVENTS,
+        ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidWebhookRequest)
+    );
+    ensure!(
+        request.secret.len() >= webhooks::MIN_SECRET_LEN,
+        ApiServerWebServerError::ClientError(ApiServerWebServerClientError::WebhookSecretTooShort(
+            webhooks::MIN_SECRET_LEN
+        ))
+    );
+
+    webhooks::parse_url(&request.url)?;
+
+    let events = request
+        .events
+        .iter()
+        .map(|event| webhooks::parse_event(&state.chain_config, event))
+        .collect::<Result<BTreeSet<_>, _>>()?;
+
+    let subscription = WebhookSubscription {
+        url: request.url,
+        events,
+        secret: request.secret,
+    };
+
+    let id = state
+        .db
+        .add_webhook_subscription(&subscription, webhooks::MAX_SUBSCRIPTIONS)
+        .await
+        .map_err(|e| {
+            logging::log::error!("internal error: {e}");
+            ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+        })?
+        .ok_or(ApiServerWebServerError::ClientError(
+            ApiServerWebServerClientError::TooManyWebhookSubscriptions,
+        ))?;
+
+    Ok(Json(json!({ "id": id })))
+}
+
+/// Unsubscribe, the request must be authenticated with the HMAC-SHA256 of the subscription id
+/// (as a decimal string), made with the subscription secret, in the `X-Mintlayer-Signature` header
+pub async fn delete_webhook<T: ApiServerStorage>(
+    Path(id): Path<String>,
+    State(state): State<ApiServerWebServerState<Arc<T>, Arc<impl TxSubmitClient>>>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiServerWebServerError> {
+    let id = u64::from_str(&id).map_err(|_| {
+        ApiServerWebServerError::ClientError(ApiServerWebServerClientError::InvalidWebhookId)
+    })?;
+
+    let subscription = {
+        let db_tx = state.db.transaction_ro().await.map_err(|e| {
+            logging::log::error!("internal error: {e}");
+            ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+        })?;
+
+        db_tx
+            .get_webhook_subscriptions()
+            .await
+            .map_err(|e| {
+                logging::log::error!("internal error: {e}");
+                ApiServerWebServerError::ServerError(
+                    ApiServerWebServerServerError::InternalServerError,
+                )
+            })?
+            .remove(&id)
+            .ok_or(ApiServerWebServerError::NotFound(
+                ApiServerWebServerNotFoundError::WebhookNotFound,
+            ))?
+    };
+
+    let signature = headers
+        .get(webhooks::SIGNATURE_HEADER)
+        .and_then(|signature| signature.to_str().ok())
+        .unwrap_or_default();
+    ensure!(
+        webhooks::verify_payload_signature(
+            &subscription.secret,
+            id.to_string().as_bytes(),
+            signature
+        ),
+        ApiServerWebServerError::Forbidden(
+            ApiServerWebServerForbiddenError::InvalidWebhookSignature
+        )
+    );
+
+    let deleted = state.db.del_webhook_subscription(id).await.map_err(|e| {
+        logging::log::error!("internal error: {e}");
+        ApiServerWebServerError::ServerError(ApiServerWebServerServerError::InternalServerError)
+    })?;
+
+    ensure!(
+        deleted,
+        ApiServerWebServerError::NotFound(ApiServerWebServerNotFoundError::WebhookNotFound)
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -13,9 +13,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{net::SocketAddr, ops::Deref};
+use std::{net::SocketAddr, ops::Deref, str::FromStr};
 
-use clap::Parser;
+use axum::http::{header::InvalidHeaderValue, HeaderValue};
+use clap::{Parser, ValueEnum};
 
 use api_server_common::{Network, PostgresConfig};
 use tokio::net::TcpListener;
@@ -63,6 +64,112 @@ pub struct ApiServerWebServerConfig {
     /// RPC password (either provide a username and password, or use a cookie file. You cannot use both)
     #[clap(long)]
     pub node_rpc_password: Option<String>,
+
+    /// Origins that browsers are allowed to make cross-origin requests from
+    ///
+    /// Format: a comma-separated list of origins, e.g. `https://explorer.mintlayer.org`, or `*` to allow any origin
+    ///
+    /// Default: `*`, requests from any origin are allowed
+    #[clap(long)]
+    pub cors_allowed_origins: Option<CorsAllowedOrigins>,
+
+    /// The number of items returned by paginated endpoints when the request doesn't specify it
+    ///
+    /// Default: `10`, or the max page size if it's lower
+    #[clap(long)]
+    pub default_page_size: Option<u32>,
+
+    /// The maximum number of items that paginated endpoints return in a single page
+    ///
+    /// Default: `100`
+    #[clap(long)]
+    pub max_page_size: Option<u32>,
+
+    /// The page size limits of individual paginated endpoints, overriding the ones above
+    ///
+    /// Format: `<endpoint>=<default>:<max>`, e.g. `transactions=20:500`, can be specified multiple times
+    ///
+    /// Endpoints: `chain-blocks`, `chain-forks`, `transactions`, `pools`, `token-ids`, `token-ids-by-ticker`, `data-deposits`
+    #[clap(long)]
+    pub endpoint_page_size: Vec<EndpointPageSize>,
+}
+
+/// The paginated endpoints whose page size limits can be configured separately
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum PaginatedEndpoint {
+    ChainBlocks,
+    ChainForks,
+    Transactions,
+    Pools,
+    TokenIds,
+    TokenIdsByTicker,
+    DataDeposits,
+}
+
+/// The page size limits of a single paginated endpoint
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EndpointPageSize {
+    pub endpoint: PaginatedEndpoint,
+    pub default: u32,
+    pub max: u32,
+}
+
+impl FromStr for EndpointPageSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || format!("Invalid endpoint page size `{s}`, expected `<endpoint>=<default>:<max>`");
+
+        let (endpoint, limits) = s.split_once('=').ok_or_else(invalid)?;
+        let endpoint = PaginatedEndpoint::from_str(endpoint.trim(), true)?;
+        let (default, max) = limits.split_once(':').ok_or_else(invalid)?;
+        let default = default.trim().parse().map_err(|_| invalid())?;
+        let max = max.trim().parse().map_err(|_| invalid())?;
+
+        Ok(Self {
+            endpoint,
+            default,
+            max,
+        })
+    }
+}
+
+/// The origins that are allowed to access the API server from a browser
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum CorsAllowedOrigins {
+    /// No cross-origin requests are allowed
+    SameOrigin,
+    /// Requests from any origin are allowed
+    #[default]
+    Any,
+    /// Requests from the listed origins are allowed
+    List(Vec<HeaderValue>),
+}
+
+impl FromStr for CorsAllowedOrigins {
+    type Err = InvalidHeaderValue;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if s == "*" {
+            return Ok(Self::Any);
+        }
+
+        let origins = s
+            .split(',')
+            .map(str::trim)
+            .filter(|origin| !origin.is_empty())
+            .map(HeaderValue::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if origins.is_empty() {
+            Ok(Self::SameOrigin)
+        } else {
+            Ok(Self::List(origins))
+        }
+    }
 }
 
 #[derive(Clone, Debug, Parser)]
@@ -15,12 +15,11 @@
 
 use api_server_common::storage::storage_api::ApiServerStorageError;
 use axum::{
-    http::StatusCode,
+    http::{header::CONTENT_TYPE, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use serde::Serialize;
-use serde_json::json;
 use thiserror::Error;
 
 #[derive(thiserror::Error, Debug)]
@@ -65,12 +64,16 @@ pub enum ApiServerWebServerNotFoundError {
     TokenNotFound,
     #[error("NFT not found")]
     NftNotFound,
+    #[error("Webhook not found")]
+    WebhookNotFound,
 }
 
 #[derive(Debug, Error, Serialize)]
 pub enum ApiServerWebServerForbiddenError {
     #[error("Forbidden endpoint")]
     Forbidden,
+    #[error("Invalid webhook signature")]
+    InvalidWebhookSignature,
 }
 
 #[derive(Debug, Error, Serialize)]
@@ -89,6 +92,8 @@ pub enum ApiServerWebServerClientError {
     InvalidPoolId,
     #[error("Invalid offset")]
     InvalidOffset,
+    #[error("Invalid time range")]
+    InvalidTimeRange,
     #[error("Invalid number of items")]
     InvalidNumItems,
     #[error("Invalid pools sort order")]
@@ -101,6 +106,24 @@ pub enum ApiServerWebServerClientError {
     InvalidNftId,
     #[error("Invalid in top X MB query parameter")]
     InvalidInTopX,
+    #[error("Invalid data deposit search prefix")]
+    InvalidDataDepositPrefix,
+    #[error("Data deposit search prefix must be at least {0} bytes")]
+    DataDepositPrefixTooShort(usize),
+    #[error("Transaction is too large, the maximum is {0} bytes")]
+    TransactionTooLarge(usize),
+    #[error("Invalid webhook subscription request")]
+    InvalidWebhookRequest,
+    #[error("Invalid webhook URL")]
+    InvalidWebhookUrl,
+    #[error("Invalid webhook event: {0}")]
+    InvalidWebhookEvent(String),
+    #[error("Invalid webhook Id")]
+    InvalidWebhookId,
+    #[error("Webhook secret must be at least {0} bytes")]
+    WebhookSecretTooShort(usize),
+    #[error("Too many webhook subscriptions")]
+    TooManyWebhookSubscriptions,
 }
 
 #[allow(dead_code)]
@@ -120,19 +143,196 @@ pub enum ApiServerWebServerServerError {
     RpcError(String),
 }
 
-impl IntoResponse for ApiServerWebServerError {
-    fn into_response(self) -> Response {
-        let (status, message) = match self {
+/// URIs identifying the types of the problems reported by the API server
+pub mod problem_type {
+    pub const BAD_REQUEST: &str = "https://mintlayer.org/errors/bad-request";
+    pub const INVALID_BLOCK_HEIGHT: &str = "https://mintlayer.org/errors/invalid-block-height";
+    pub const INVALID_BLOCK_ID: &str = "https://mintlayer.org/errors/invalid-block-id";
+    pub const INVALID_ADDRESS: &str = "https://mintlayer.org/errors/invalid-address";
+    pub const INVALID_TRANSACTION_ID: &str = "https://mintlayer.org/errors/invalid-transaction-id";
+    pub const INVALID_POOL_ID: &str = "https://mintlayer.org/errors/invalid-pool-id";
+    pub const INVALID_OFFSET: &str = "https://mintlayer.org/errors/invalid-offset";
+    pub const INVALID_TIME_RANGE: &str = "https://mintlayer.org/errors/invalid-time-range";
+    pub const INVALID_NUM_ITEMS: &str = "https://mintlayer.org/errors/invalid-num-items";
+    pub const INVALID_POOLS_SORT_ORDER: &str =
+        "https://mintlayer.org/errors/invalid-pools-sort-order";
+    pub const INVALID_SIGNED_TRANSACTION: &str =
+        "https://mintlayer.org/errors/invalid-signed-transaction";
+    pub const INVALID_TOKEN_ID: &str = "https://mintlayer.org/errors/invalid-token-id";
+    pub const INVALID_NFT_ID: &str = "https://mintlayer.org/errors/invalid-nft-id";
+    pub const INVALID_IN_TOP_X: &str = "https://mintlayer.org/errors/invalid-in-top-x";
+    pub const INVALID_DATA_DEPOSIT_PREFIX: &str =
+        "https://mintlayer.org/errors/invalid-data-deposit-prefix";
+    pub const DATA_DEPOSIT_PREFIX_TOO_SHORT: &str =
+        "https://mintlayer.org/errors/data-deposit-prefix-too-short";
+    pub const TRANSACTION_TOO_LARGE: &str = "https://mintlayer.org/errors/transaction-too-large";
+    pub const INVALID_WEBHOOK_REQUEST: &str =
+        "https://mintlayer.org/errors/invalid-webhook-request";
+    pub const INVALID_WEBHOOK_URL: &str = "https://mintlayer.org/errors/invalid-webhook-url";
+    pub const INVALID_WEBHOOK_EVENT: &str = "https://mintlayer.org/errors/invalid-webhook-event";
+    pub const INVALID_WEBHOOK_ID: &str = "https://mintlayer.org/errors/invalid-webhook-id";
+    pub const WEBHOOK_SECRET_TOO_SHORT: &str =
+        "https://mintlayer.org/errors/webhook-secret-too-short";
+    pub const TOO_MANY_WEBHOOK_SUBSCRIPTIONS: &str =
+        "https://mintlayer.org/errors/too-many-webhook-subscriptions";
+
+    pub const ADDRESS_NOT_FOUND: &str = "https://mintlayer.org/errors/address-not-found";
+    pub const BLOCK_NOT_FOUND: &str = "https://mintlayer.org/errors/block-not-found";
+    pub const NO_BLOCK_AT_HEIGHT: &str = "https://mintlayer.org/errors/no-block-at-height";
+    pub const TRANSACTION_NOT_FOUND: &str = "https://mintlayer.org/errors/transaction-not-found";
+    pub const TRANSACTION_NOT_PART_OF_BLOCK: &str =
+        "https://mintlayer.org/errors/transaction-not-part-of-block";
+    pub const POOL_NOT_FOUND: &str = "https://mintlayer.org/errors/pool-not-found";
+    pub const DELEGATION_NOT_FOUND: &str = "https://mintlayer.org/errors/delegation-not-found";
+    pub const TOKEN_NOT_FOUND: &str = "https://mintlayer.org/errors/token-not-found";
+    pub const NFT_NOT_FOUND: &str = "https://mintlayer.org/errors/nft-not-found";
+    pub const WEBHOOK_NOT_FOUND: &str = "https://mintlayer.org/errors/webhook-not-found";
+
+    pub const FORBIDDEN: &str = "https://mintlayer.org/errors/forbidden";
+    pub const INVALID_WEBHOOK_SIGNATURE: &str =
+        "https://mintlayer.org/errors/invalid-webhook-signature";
+
+    pub const CANNOT_FIND_TRANSACTION_IN_BLOCK: &str =
+        "https://mintlayer.org/errors/cannot-find-transaction-in-block";
+    pub const MERKLE_PATH_CALCULATION: &str =
+        "https://mintlayer.org/errors/merkle-path-calculation";
+    pub const MERKLE_TREE_CALCULATION: &str =
+        "https://mintlayer.org/errors/merkle-tree-calculation";
+    pub const INTERNAL_SERVER_ERROR: &str = "https://mintlayer.org/errors/internal-server-error";
+    pub const TRANSACTION_INDEX_OVERFLOW: &str =
+        "https://mintlayer.org/errors/transaction-index-overflow";
+    pub const RPC_ERROR: &str = "https://mintlayer.org/errors/rpc-error";
+}
+
+impl ApiServerWebServerClientError {
+    fn problem_type(&self) -> &'static str {
+        match self {
+            Self::BadRequest => problem_type::BAD_REQUEST,
+            Self::InvalidBlockHeight => problem_type::INVALID_BLOCK_HEIGHT,
+            Self::InvalidBlockId => problem_type::INVALID_BLOCK_ID,
+            Self::InvalidAddress => problem_type::INVALID_ADDRESS,
+            Self::InvalidTransactionId => problem_type::INVALID_TRANSACTION_ID,
+            Self::InvalidPoolId => problem_type::INVALID_POOL_ID,
+            Self::InvalidOffset => problem_type::INVALID_OFFSET,
+            Self::InvalidTimeRange => problem_type::INVALID_TIME_RANGE,
+            Self::InvalidNumItems => problem_type::INVALID_NUM_ITEMS,
+            Self::InvalidPoolsSortOrder => problem_type::INVALID_POOLS_SORT_ORDER,
+            Self::InvalidSignedTransaction => problem_type::INVALID_SIGNED_TRANSACTION,
+            Self::InvalidTokenId => problem_type::INVALID_TOKEN_ID,
+            Self::InvalidNftId => problem_type::INVALID_NFT_ID,
+            Self::InvalidInTopX => problem_type::INVALID_IN_TOP_X,
+            Self::InvalidDataDepositPrefix => problem_type::INVALID_DATA_DEPOSIT_PREFIX,
+            Self::DataDepositPrefixTooShort(_) => problem_type::DATA_DEPOSIT_PREFIX_TOO_SHORT,
+            Self::TransactionTooLarge(_) => problem_type::TRANSACTION_TOO_LARGE,
+            Self::InvalidWebhookRequest => problem_type::INVALID_WEBHOOK_REQUEST,
+            Self::InvalidWebhookUrl => problem_type::INVALID_WEBHOOK_URL,
+            Self::InvalidWebhookEvent(_) => problem_type::INVALID_WEBHOOK_EVENT,
+            Self::InvalidWebhookId => problem_type::INVALID_WEBHOOK_ID,
+            Self::WebhookSecretTooShort(_) => problem_type::WEBHOOK_SECRET_TOO_SHORT,
+            Self::TooManyWebhookSubscriptions => problem_type::TOO_MANY_WEBHOOK_SUBSCRIPTIONS,
+        }
+    }
+}
+
+impl ApiServerWebServerNotFoundError {
+    fn problem_type(&self) -> &'static str {
+        match self {
+            Self::AddressNotFound => problem_type::ADDRESS_NOT_FOUND,
+            Self::BlockNotFound => problem_type::BLOCK_NOT_FOUND,
+            Self::NoBlockAtHeight => problem_type::NO_BLOCK_AT_HEIGHT,
+            Self::TransactionNotFound => problem_type::TRANSACTION_NOT_FOUND,
+            Self::TransactionNotPartOfBlock => problem_type::TRANSACTION_NOT_PART_OF_BLOCK,
+            Self::PoolNotFound => problem_type::POOL_NOT_FOUND,
+            Self::DelegationNotFound => problem_type::DELEGATION_NOT_FOUND,
+            Self::TokenNotFound => problem_type::TOKEN_NOT_FOUND,
+            Self::NftNotFound => problem_type::NFT_NOT_FOUND,
+            Self::WebhookNotFound => problem_type::WEBHOOK_NOT_FOUND,
+        }
+    }
+}
+
+impl ApiServerWebServerForbiddenError {
+    fn problem_type(&self) -> &'static str {
+        match self {
+            Self::Forbidden => problem_type::FORBIDDEN,
+            Self::InvalidWebhookSignature => problem_type::INVALID_WEBHOOK_SIGNATURE,
+        }
+    }
+}
+
+impl ApiServerWebServerServerError {
+    fn problem_type(&self) -> &'static str {
+        match self {
+            Self::CannotFindTransactionInBlock => problem_type::CANNOT_FIND_TRANSACTION_IN_BLOCK,
+            Self::ErrorCalculatingMerklePath => problem_type::MERKLE_PATH_CALCULATION,
+            Self::ErrorCalculatingMerkleTree => problem_type::MERKLE_TREE_CALCULATION,
+            Self::InternalServerError => problem_type::INTERNAL_SERVER_ERROR,
+            Self::TransactionIndexOverflow => problem_type::TRANSACTION_INDEX_OVERFLOW,
+            Self::RpcError(_) => problem_type::RPC_ERROR,
+        }
+    }
+}
+
+/// Error response body, as defined in RFC 7807 "Problem Details for HTTP APIs"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProblemDetail {
+    #[serde(rename = "type")]
+    pub type_uri: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+}
+
+pub const PROBLEM_JSON_CONTENT_TYPE: &str = "application/problem+json";
+
+impl ApiServerWebServerError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            ApiServerWebServerError::ClientError(
+                ApiServerWebServerClientError::TransactionTooLarge(_),
+            ) => StatusCode::PAYLOAD_TOO_LARGE,
+            ApiServerWebServerError::ClientError(_) => StatusCode::BAD_REQUEST,
+            ApiServerWebServerError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiServerWebServerError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiServerWebServerError::ServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn problem_detail(&self) -> ProblemDetail {
+        let (type_uri, detail) = match self {
             ApiServerWebServerError::ClientError(error) => {
-                (StatusCode::BAD_REQUEST, error.to_string())
+                (error.problem_type(), error.to_string())
             }
-            ApiServerWebServerError::NotFound(error) => (StatusCode::NOT_FOUND, error.to_string()),
-            ApiServerWebServerError::Forbidden(error) => (StatusCode::FORBIDDEN, error.to_string()),
+            ApiServerWebServerError::NotFound(error) => (error.problem_type(), error.to_string()),
+            ApiServerWebServerError::Forbidden(error) => (error.problem_type(), error.to_string()),
             ApiServerWebServerError::ServerError(error) => {
-                (StatusCode::INTERNAL_SERVER_ERROR, error.to_string())
+                (error.problem_type(), error.to_string())
             }
         };
+        let status = self.status_code();
 
-        (status, Json(json!({ "error": message }))).into_response()
+        ProblemDetail {
+            type_uri: type_uri.to_owned(),
+            title: status.canonical_reason().unwrap_or_default().to_owned(),
+            status: status.as_u16(),
+            detail,
+            instance: None,
+        }
+    }
+}
+
+impl IntoResponse for ApiServerWebServerError {
+    fn into_response(self) -> Response {
+        (
+            self.status_code(),
+            [(
+                CONTENT_TYPE,
+                HeaderValue::from_static(PROBLEM_JSON_CONTENT_TYPE),
+            )],
+            Json(self.problem_detail()),
+        )
+            .into_response()
     }
 }
@@ -16,15 +16,18 @@
 pub mod api;
 pub mod config;
 pub mod error;
+pub mod webhooks;
 
 pub use error::ApiServerWebServerError;
 
+use chainstate::ChainFork;
 use common::{
-    chain::{ChainConfig, SignedTransaction},
-    primitives::time::Time,
+    chain::{ChainConfig, SignedTransaction, Transaction},
+    primitives::{time::Time, Id},
     time_getter::TimeGetter,
 };
-use mempool::FeeRate;
+use config::{CorsAllowedOrigins, PaginatedEndpoint};
+use mempool::{FeeRate, MempoolTxInfo};
 use node_comm::{
     node_traits::NodeInterface,
     rpc_client::{NodeRpcClient, NodeRpcError},
@@ -36,6 +39,15 @@ pub trait TxSubmitClient {
     async fn submit_tx(&self, tx: SignedTransaction) -> Result<(), NodeRpcError>;
 
     async fn get_feerate_points(&self) -> Result<Vec<(usize, FeeRate)>, NodeRpcError>;
+
+    async fn get_chain_forks(&self) -> Result<Vec<ChainFork>, NodeRpcError>;
+
+    async fn get_mempool_tx(
+        &self,
+        tx_id: Id<Transaction>,
+    ) -> Result<Option<SignedTransaction>, NodeRpcError>;
+
+    async fn get_mempool_tx_info(&self) -> Result<Vec<MempoolTxInfo>, NodeRpcError>;
 }
 
 #[async_trait::async_trait]
@@ -47,10 +59,88 @@ impl TxSubmitClient for NodeRpcClient {
     async fn get_feerate_points(&self) -> Result<Vec<(usize, FeeRate)>, NodeRpcError> {
         self.mempool_get_fee_rate_points().await
     }
+
+    async fn get_chain_forks(&self) -> Result<Vec<ChainFork>, NodeRpcError> {
+        NodeInterface::get_chain_forks(self).await
+    }
+
+    async fn get_mempool_tx(
+        &self,
+        tx_id: Id<Transaction>,
+    ) -> Result<Option<SignedTransaction>, NodeRpcError> {
+        self.mempool_get_transaction(tx_id).await
+    }
+
+    async fn get_mempool_tx_info(&self) -> Result<Vec<MempoolTxInfo>, NodeRpcError> {
+        self.mempool_get_all_tx_info().await
+    }
+}
+
+/// The default and the maximum number of items in a single page of a paginated endpoint
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PageSizeLimit {
+    pub default: u32,
+    pub max: u32,
+}
+
+impl PageSizeLimit {
+    pub const DEFAULT_PAGE_SIZE: u32 = 10;
+    pub const MAX_PAGE_SIZE: u32 = 100;
+}
+
+impl Default for PageSizeLimit {
+    fn default() -> Self {
+        Self {
+            default: Self::DEFAULT_PAGE_SIZE,
+            max: Self::MAX_PAGE_SIZE,
+        }
+    }
+}
+
+/// Page size limits for each of the paginated endpoints
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PageSizeLimits {
+    pub chain_blocks: PageSizeLimit,
+    pub transactions: PageSizeLimit,
+    pub pools: PageSizeLimit,
+    pub token_ids: PageSizeLimit,
+    pub token_ids_by_ticker: PageSizeLimit,
+    pub data_deposits: PageSizeLimit,
+    pub chain_forks: PageSizeLimit,
+}
+
+impl PageSizeLimits {
+    /// Use the same limit for all the endpoints
+    pub fn uniform(limit: PageSizeLimit) -> Self {
+        Self {
+            chain_blocks: limit,
+            transactions: limit,
+            pools: limit,
+            token_ids: limit,
+            token_ids_by_ticker: limit,
+            data_deposits: limit,
+            chain_forks: limit,
+        }
+    }
+
+    /// Use a separate limit for the endpoint
+    pub fn set(&mut self, endpoint: PaginatedEndpoint, limit: PageSizeLimit) {
+        let endpoint_limit = match endpoint {
+            PaginatedEndpoint::ChainBlocks => &mut self.chain_blocks,
+            PaginatedEndpoint::ChainForks => &mut self.chain_forks,
+            PaginatedEndpoint::Transactions => &mut self.transactions,
+            PaginatedEndpoint::Pools => &mut self.pools,
+            PaginatedEndpoint::TokenIds => &mut self.token_ids,
+            PaginatedEndpoint::TokenIdsByTicker => &mut self.token_ids_by_ticker,
+            PaginatedEndpoint::DataDeposits => &mut self.data_deposits,
+        };
+        *endpoint_limit = limit;
+    }
 }
 
 pub struct CachedValues {
     pub feerate_points: RwLock<(Time, Vec<(usize, FeeRate)>)>,
+    pub mempool_tx_info: RwLock<(Time, Vec<MempoolTxInfo>)>,
 }
 
 #[derive(Clone)]
@@ -60,4 +150,6 @@ pub struct ApiServerWebServerState<T, R> {
     pub rpc: R,
     pub cached_values: Arc<CachedValues>,
     pub time_getter: TimeGetter,
+    pub cors_allowed_origins: CorsAllowedOrigins,
+    pub page_size_limits: PageSizeLimits,
 }
@@ -19,8 +19,9 @@ mod error;
 
 use api_server_common::storage::impls::postgres::TransactionalApiServerPostgresStorage;
 use api_web_server::{
-    api::web_server, config::ApiServerWebServerConfig, ApiServerWebServerState, CachedValues,
-    TxSubmitClient,
+    api::web_server,
+    config::{ApiServerWebServerConfig, EndpointPageSize},
+    webhooks, ApiServerWebServerState, CachedValues, PageSizeLimit, PageSizeLimits, TxSubmitClient,
 };
 use clap::Parser;
 use common::{
@@ -93,16 +94,43 @@ async fn main() -> Result<(), ApiServerWebServerInitError> {
             .map_err(ApiServerWebServerInitError::RpcError)?
     };
 
+    let page_size_limits = {
+        let max = args.max_page_size.unwrap_or(PageSizeLimit::MAX_PAGE_SIZE);
+        let default = args
+            .default_page_size
+            .unwrap_or_else(|| std::cmp::min(PageSizeLimit::DEFAULT_PAGE_SIZE, max));
+
+        let mut page_size_limits = PageSizeLimits::uniform(check_page_size_limit(default, max)?);
+
+        for EndpointPageSize {
+            endpoint,
+            default,
+            max,
+        } in args.endpoint_page_size
+        {
+            page_size_limits.set(endpoint, check_page_size_limit(default, max)?);
+        }
+
+        page_size_limits
+    };
+
     let state = ApiServerWebServerState {
         db: Arc::new(storage),
         chain_config,
         rpc: Arc::new(rpc_client),
         cached_values: Arc::new(CachedValues {
             feerate_points: RwLock::new((Time::from_secs_since_epoch(0), vec![])),
+            mempool_tx_info: RwLock::new((Time::from_secs_since_epoch(0), vec![])),
         }),
         time_getter: Default::default(),
+        cors_allowed_origins: args.cors_allowed_origins.unwrap_or_default(),
+        page_size_limits,
     };
 
+    tokio::spawn(
+        webhooks::WebhookNotifier::new(Arc::clone(&state.db)).run(webhooks::POLL_INTERVAL),
+    );
+
     web_server(
         args.bind_address.unwrap_or_default().tcp_listener().await,
         state,
@@ -113,3 +141,16 @@ async fn main() -> Result<(), ApiServerWebServerInitError> {
 
     Ok(())
 }
+
+fn check_page_size_limit(
+    default: u32,
+    max: u32,
+) -> Result<PageSizeLimit, ApiServerWebServerInitError> {
+    if max == 0 || default > max {
+        return Err(ApiServerWebServerInitError::InvalidConfig(format!(
+            "Invalid page size limits: default {default}, max {max}"
+        )));
+    }
+
+    Ok(PageSizeLimit { default, max })
+}
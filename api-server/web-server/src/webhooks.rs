@@ -0,0 +1,405 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Delivery of the events that were subscribed to with `POST /webhook`.
+//!
+//! The storage is polled for new main chain blocks, and for each block the matching events are
+//! POSTed as JSON to the subscribed URLs. The body is signed with HMAC-SHA256 using the secret
+//! of the subscription, the hex-encoded signature is sent in the `X-Mintlayer-Signature` header.
+//! Failed deliveries are logged and not retried.
+//!
+//! Only HTTPS endpoints on public hosts can be subscribed to, and the host is resolved again
+//! before each delivery, so that the server can't be used to reach its own network.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+
+use api_server_common::storage::storage_api::{
+    ApiServerStorage, ApiServerStorageError, ApiServerStorageRead, ApiServerWebhookStorage,
+    WebhookEvent, WebhookSubscription,
+};
+use common::{
+    address::Address,
+    chain::{Block, ChainConfig, Destination, Transaction},
+    primitives::{BlockHeight, Id},
+};
+use hex::ToHex;
+use hmac::{Hmac, Mac};
+use logging::log;
+use p2p_types::IsGlobalIp;
+use serde_json::json;
+use sha2::Sha256;
+use utils::ensure;
+
+use crate::error::ApiServerWebServerClientError;
+
+pub const SIGNATURE_HEADER: &str = "X-Mintlayer-Signature";
+
+/// How often the storage is checked for new blocks
+pub const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The maximum number of subscriptions the server keeps
+pub const MAX_SUBSCRIPTIONS: usize = 1000;
+
+/// The maximum number of events in a single subscription
+pub const MAX_SUBSCRIPTION_EVENTS: usize = 100;
+
+pub const MAX_URL_LEN: usize = 2048;
+
+pub const MIN_SECRET_LEN: usize = 16;
+
+/// The maximum number of blocks whose events are delivered in a single poll,
+/// so that catching up after a downtime doesn't flood the subscribers
+const MAX_BLOCKS_PER_POLL: u64 = 100;
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+const BLOCK_EVENT: &str = "block";
+const ADDRESS_EVENT_PREFIX: &str = "address/";
+
+pub fn parse_event(
+    chain_config: &ChainConfig,
+    event: &str,
+) -> Result<WebhookEvent, ApiServerWebServerClientError> {
+    if event == BLOCK_EVENT {
+        return Ok(WebhookEvent::Block);
+    }
+
+    let address = event
+        .strip_prefix(ADDRESS_EVENT_PREFIX)
+        .ok_or_else(|| ApiServerWebServerClientError::InvalidWebhookEvent(event.to_owned()))?;
+    let address = Address::<Destination>::from_string(chain_config, address)
+        .map_err(|_| ApiServerWebServerClientError::InvalidWebhookEvent(event.to_owned()))?;
+
+    Ok(WebhookEvent::Address(address.into_string()))
+}
+
+pub fn event_to_string(event: &WebhookEvent) -> String {
+    match event {
+        WebhookEvent::Block => BLOCK_EVENT.to_owned(),
+        WebhookEvent::Address(address) => format!("{ADDRESS_EVENT_PREFIX}{address}"),
+    }
+}
+
+/// Check that the URL can be subscribed to: it must use HTTPS and point to a public host
+pub fn parse_url(url: &str) -> Result<reqwest::Url, ApiServerWebServerClientError> {
+    ensure!(
+        url.len() <= MAX_URL_LEN,
+        ApiServerWebServerClientError::InvalidWebhookUrl
+    );
+
+    let url =
+        reqwest::Url::parse(url).map_err(|_| ApiServerWebServerClientError::InvalidWebhookUrl)?;
+    ensure!(
+        url.scheme() == "https" && url.username().is_empty() && url.password().is_none(),
+        ApiServerWebServerClientError::InvalidWebhookUrl
+    );
+
+    let is_public_host = match url_host(&url) {
+        Some(UrlHost::Ip(ip)) => is_public_ip(ip),
+        Some(UrlHost::Domain(domain)) => is_public_domain(domain),
+        None => false,
+    };
+    ensure!(
+        is_public_host,
+        ApiServerWebServerClientError::InvalidWebhookUrl
+    );
+
+    Ok(url)
+}
+
+/// The hex-encoded HMAC-SHA256 of the payload
+pub fn sign_payload(secret: &str, payload: &[u8]) -> String {
+    make_mac(secret, payload).finalize().into_bytes().encode_hex::<String>()
+}
+
+/// Check the hex-encoded HMAC-SHA256 of the payload in constant time
+pub fn verify_payload_signature(secret: &str, payload: &[u8], signature: &str) -> bool {
+    let signature = match hex::decode(signature) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    make_mac(secret, payload).verify_slice(&signature).is_ok()
+}
+
+fn make_mac(secret: &str, payload: &[u8]) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(payload);
+    mac
+}
+
+/// Delivers the events of the main chain blocks that were added since the last delivery.
+///
+/// The height of the last processed block is kept in the storage, so that no blocks are missed
+/// when the server is restarted.
+pub struct WebhookNotifier<T> {
+    db: Arc<T>,
+    allow_non_public_destinations: bool,
+}
+
+impl<T: ApiServerStorage> WebhookNotifier<T> {
+    pub fn new(db: Arc<T>) -> Self {
+        Self {
+            db,
+            allow_non_public_destinations: false,
+        }
+    }
+
+    /// Deliver to any HTTP(S) endpoint, including the ones on local networks.
+    ///
+    /// Only meant for tests, where the endpoints run locally.
+    pub fn allow_non_public_destinations(mut self) -> Self {
+        self.allow_non_public_destinations = true;
+        self
+    }
+
+    /// Poll the storage until the task is aborted
+    pub async fn run(self, poll_interval: Duration) {
+        loop {
+            if let Err(e) = self.poll().await {
+                log::error!("Webhook notification failed: {e}");
+            }
+
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
+    /// Deliver the events of the main chain blocks added after the stored cursor.
+    ///
+    /// If there is no cursor yet, it is set to the current tip and nothing is delivered. At most
+    /// `MAX_BLOCKS_PER_POLL` blocks are processed in one call. Blocks that replace already
+    /// notified ones in a reorg are not notified about.
+    pub async fn poll(&self) -> Result<(), ApiServerStorageError> {
+        let db_tx = self.db.transaction_ro().await?;
+        let best_height = db_tx.get_best_block().await?.block_height();
+
+        let last_height = match db_tx.get_webhook_cursor().await? {
+            Some(cursor) if cursor < best_height => cursor,
+            Some(cursor) if cursor == best_height => return Ok(()),
+            _ => {
+                drop(db_tx);
+                return self.db.set_webhook_cursor(best_height).await;
+            }
+        };
+        let end_height = std::cmp::min(
+            best_height,
+            BlockHeight::new(last_height.into_int().saturating_add(MAX_BLOCKS_PER_POLL)),
+        );
+
+        let subscriptions = db_tx.get_webhook_subscriptions().await?;
+        let subscribed_addresses = subscriptions
+            .values()
+            .flat_map(|subscription| subscription.events.iter())
+            .filter_map(|event| match event {
+                WebhookEvent::Block => None,
+                WebhookEvent::Address(address) => Some(address),
+            })
+            .collect::<BTreeSet<_>>();
+
+        let mut deliveries = Vec::new();
+        if !subscriptions.is_empty() {
+            let mut height = last_height.next_height();
+            while height <= end_height {
+                if let Some(block_id) = db_tx.get_main_chain_block_id(height).await? {
+                    let mut address_transactions = if subscribed_addresses.is_empty() {
+                        BTreeMap::new()
+                    } else {
+                        db_tx.get_block_address_transactions(height).await?
+                    };
+                    address_transactions
+                        .retain(|address, _| subscribed_addresses.contains(address));
+
+                    for (event, payload) in block_events(block_id, height, address_transactions) {
+                        for (id, subscription) in &subscriptions {
+                            if subscription.events.contains(&event) {
+                                deliveries.push((*id, subscription.clone(), payload.clone()));
+                            }
+                        }
+                    }
+                }
+
+                height = height.next_height();
+            }
+        }
+        drop(db_tx);
+
+        self.db.set_webhook_cursor(end_height).await?;
+
+        for (id, subscription, payload) in deliveries {
+            deliver(
+                self.allow_non_public_destinations,
+                id,
+                subscription,
+                payload,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// The events that happened in the block, along with their payloads
+fn block_events(
+    block_id: Id<Block>,
+    height: BlockHeight,
+    address_transactions: BTreeMap<String, BTreeSet<Id<Transaction>>>,
+) -> Vec<(WebhookEvent, serde_json::Value)> {
+    let block_id = block_id.to_hash().encode_hex::<String>();
+
+    let block_event = (
+        WebhookEvent::Block,
+        json!({
+            "event": BLOCK_EVENT,
+            "block_id": block_id,
+            "block_height": height.into_int(),
+        }),
+    );
+
+    let address_events = address_transactions.into_iter().map(|(address, tx_ids)| {
+        let event = WebhookEvent::Address(address);
+        let payload = json!({
+            "event": event_to_string(&event),
+            "block_id": block_id,
+            "block_height": height.into_int(),
+            "transaction_ids": tx_ids
+                .into_iter()
+                .map(|id| id.to_hash().encode_hex::<String>())
+                .collect::<Vec<_>>(),
+        });
+        (event, payload)
+    });
+
+    std::iter::once(block_event).chain(address_events).collect()
+}
+
+#[derive(thiserror::Error, Debug)]
+enum DeliveryError {
+    #[error("Invalid URL")]
+    InvalidUrl,
+    #[error("Host resolution failed: {0}")]
+    HostResolution(std::io::Error),
+    #[error("The host resolves to the non-public address {0}")]
+    NonPublicAddress(IpAddr),
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+}
+
+enum UrlHost<'a> {
+    Ip(IpAddr),
+    Domain(&'a str),
+}
+
+fn url_host(url: &reqwest::Url) -> Option<UrlHost<'_>> {
+    let host = url.host_str()?;
+    // IPv6 addresses are enclosed in brackets
+    let ip = host.strip_prefix('[').and_then(|host| host.strip_suffix(']')).unwrap_or(host);
+
+    match ip.parse::<IpAddr>() {
+        Ok(ip) => Some(UrlHost::Ip(ip)),
+        Err(_) => Some(UrlHost::Domain(host)),
+    }
+}
+
+fn is_public_ip(ip: IpAddr) -> bool {
+    ip.to_canonical().is_global_unicast_ip()
+}
+
+fn is_public_domain(domain: &str) -> bool {
+    let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+    // Single label names are only resolvable on local networks
+    domain.contains('.') && domain != "localhost" && !domain.ends_with(".localhost")
+}
+
+/// Make a client that can only connect to public addresses of the URL's host.
+///
+/// The host is resolved here and the client is pinned to the checked address, so that
+/// the name can't be re-resolved to a private address when connecting.
+async fn make_client(
+    url: &reqwest::Url,
+    allow_non_public_destinations: bool,
+) -> Result<reqwest::Client, DeliveryError> {
+    let builder = reqwest::Client::builder()
+        .timeout(DELIVERY_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none());
+
+    if allow_non_public_destinations {
+        return Ok(builder.build()?);
+    }
+
+    ensure!(url.scheme() == "https", DeliveryError::InvalidUrl);
+    let port = url.port_or_known_default().ok_or(DeliveryError::InvalidUrl)?;
+    let builder = match url_host(url).ok_or(DeliveryError::InvalidUrl)? {
+        UrlHost::Ip(ip) => {
+            ensure!(is_public_ip(ip), DeliveryError::NonPublicAddress(ip));
+            builder
+        }
+        UrlHost::Domain(domain) => {
+            let addresses = tokio::net::lookup_host((domain, port))
+                .await
+                .map_err(DeliveryError::HostResolution)?
+                .collect::<Vec<SocketAddr>>();
+
+            if let Some(address) = addresses.iter().find(|address| !is_public_ip(address.ip())) {
+                return Err(DeliveryError::NonPublicAddress(address.ip()));
+            }
+            let address = addresses.first().ok_or_else(|| {
+                DeliveryError::HostResolution(std::io::ErrorKind::NotFound.into())
+            })?;
+
+            builder.resolve(domain, *address)
+        }
+    };
+
+    Ok(builder.build()?)
+}
+
+fn deliver(
+    allow_non_public_destinations: bool,
+    id: u64,
+    subscription: WebhookSubscription,
+    payload: serde_json::Value,
+) {
+    tokio::spawn(async move {
+        let body = payload.to_string();
+        let signature = sign_payload(&subscription.secret, body.as_bytes());
+
+        let result = async {
+            let url =
+                reqwest::Url::parse(&subscription.url).map_err(|_| DeliveryError::InvalidUrl)?;
+            let client = make_client(&url, allow_non_public_destinations).await?;
+
+            client
+                .post(url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(SIGNATURE_HEADER, signature)
+                .body(body)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())?;
+
+            Ok::<_, DeliveryError>(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            log::warn!("Webhook {id} delivery to {} failed: {e}", subscription.url);
+        }
+    });
+}
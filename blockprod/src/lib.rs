@@ -274,6 +274,8 @@ mod tests {
             max_db_commit_attempts: Default::default(),
             max_orphan_blocks: Default::default(),
             min_max_bootstrap_import_buffer_sizes: Default::default(),
+            max_transactions_per_block: None,
+            pruning_depth: None,
         };
 
         let mempool_config = MempoolConfig::new();
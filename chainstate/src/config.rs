@@ -49,6 +49,12 @@ pub struct ChainstateConfig {
     /// If true, additional computationally-expensive consistency checks will be performed by
     /// the chainstate. The default value depends on the chain type.
     pub enable_heavy_checks: Option<bool>,
+    /// The maximum number of transactions a block may contain to be accepted by this node.
+    /// If not set, only the consensus block size limits apply.
+    pub max_transactions_per_block: Option<usize>,
+    /// If set, the bodies of the mainchain blocks that are this many blocks below the tip are
+    /// deleted from the storage. Block headers are always kept.
+    pub pruning_depth: Option<u64>,
 }
 
 impl ChainstateConfig {
@@ -80,6 +86,16 @@ impl ChainstateConfig {
         self
     }
 
+    pub fn with_max_transactions_per_block(mut self, max_transactions_per_block: usize) -> Self {
+        self.max_transactions_per_block = Some(max_transactions_per_block);
+        self
+    }
+
+    pub fn with_pruning_depth(mut self, pruning_depth: u64) -> Self {
+        self.pruning_depth = Some(pruning_depth);
+        self
+    }
+
     pub fn heavy_checks_enabled(&self, chain_config: &ChainConfig) -> bool {
         if let Some(enable_heavy_checks) = self.enable_heavy_checks {
             return enable_heavy_checks;
@@ -50,6 +50,7 @@ impl BanScore for BlockError {
             BlockError::OrphanCheckFailed(err) => err.ban_score(),
             BlockError::CheckBlockFailed(err) => err.ban_score(),
             BlockError::StateUpdateFailed(err) => err.ban_score(),
+            BlockError::OutputAlreadySpent { .. } => 100,
             // Even though this should've been caught by orphans check, its mere presence means
             // a peer sent a block they're not supposed to send.
             BlockError::PrevBlockNotFoundForNewBlock(_) => 100,
@@ -82,6 +83,7 @@ impl BanScore for BlockError {
             BlockError::InvariantErrorPoolDataPresentBalanceMissing(_, _) => 0,
 
             BlockError::UnexpectedHeightRange(_, _) => 0,
+            BlockError::HeightTooDeepToReprocess(_) => 0,
 
             BlockError::TokensAccountingError(err) => err.ban_score(),
             BlockError::OrdersAccountingError(err) => err.ban_score(),
@@ -286,6 +288,8 @@ impl BanScore for CheckBlockError {
             CheckBlockError::BlockTimeOrderInvalid(_, _) => 100,
             CheckBlockError::BlockFromTheFuture(_) => 100,
             CheckBlockError::BlockSizeError(err) => err.ban_score(),
+            // The limit is a local policy, so the peer shouldn't be punished for it
+            CheckBlockError::TooManyTransactions(_, _) => 0,
             CheckBlockError::CheckTransactionFailed(err) => err.ban_score(),
             CheckBlockError::ConsensusVerificationFailed(err) => err.ban_score(),
             CheckBlockError::InvalidBlockRewardOutputType(_) => 100,
@@ -450,6 +454,7 @@ impl BanScore for ConsensusPoSError {
             ConsensusPoSError::EffectivePoolBalanceError(_) => 100,
             ConsensusPoSError::EmptyTimespan => 100,
             ConsensusPoSError::FailedToCalculateCappedBalance => 100,
+            ConsensusPoSError::PoolBalanceUnderflow => 100,
         }
     }
 }
@@ -504,6 +509,7 @@ impl BanScore for pos_accounting::Error {
             E::DelegateToNonexistingId => 100,
             E::DelegateToNonexistingPool => 100,
             E::SpendingShareOfNonexistingDelegation(_) => 100,
+            E::UnauthorizedDelegationSpend(_) => 100,
             E::AdditionError => 100,
             E::SubError => 100,
             E::DelegationBalanceAdditionError => 100,
@@ -536,6 +542,9 @@ impl BanScore for pos_accounting::Error {
             E::DelegationDeletionFailedPoolsShareNonZero => 100,
             E::DelegationDeletionFailedPoolStillExists => 100,
             E::InvariantErrorNonZeroBalanceForNonExistingDelegation => 100,
+            E::AttemptedSlashNonexistingPool => 100,
+            E::SlashExceedsPoolBalance => 100,
+            E::InvariantErrorSlashUndoFailedPoolDataNotFound => 100,
         }
     }
 }
@@ -548,6 +557,8 @@ impl BanScore for ChainstateError {
             ChainstateError::FailedToReadProperty(_) => 0,
             ChainstateError::BootstrapError(_) => 0,
             ChainstateError::BlockInvalidatorError(_) => 0,
+            ChainstateError::CannotPruneToDepth(_, _) => 0,
+            ChainstateError::UtxoSnapshotError(_) => 0,
         }
     }
 }
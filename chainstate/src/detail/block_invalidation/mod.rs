@@ -176,6 +176,9 @@ impl<'a, S: BlockchainStorage, V: TransactionVerificationStrategy> BlockInvalida
             log::warn!("No better chain was found after invalidating block {block_id}");
         }
 
+        // The mainchain blocks have been disconnected even if no better chain was found
+        self.chainstate.record_reorg_from(&best_block_index);
+
         Ok(())
     }
 
@@ -274,13 +277,17 @@ impl<'a, S: BlockchainStorage, V: TransactionVerificationStrategy> BlockInvalida
         &mut self,
         block_id: &Id<Block>,
     ) -> Result<(), BlockInvalidatorError> {
-        let block_indices_to_clear = {
+        let (block_indices_to_clear, old_tip_index) = {
             let chainstate_ref =
                 self.chainstate.make_db_tx_ro().map_err(BlockInvalidatorError::from)?;
 
-            chainstate_ref
-                .collect_block_indices_in_branch(block_id)
-                .map_err(BlockInvalidatorError::BlockIndicesForBranchQueryError)?
+            let block_indices_to_clear =
+                chainstate_ref
+                    .collect_block_indices_in_branch(block_id)
+                    .map_err(BlockInvalidatorError::BlockIndicesForBranchQueryError)?;
+            let old_tip_index = get_best_block_index(&chainstate_ref)?;
+
+            (block_indices_to_clear, old_tip_index)
         };
 
         self.chainstate.with_rw_tx(
@@ -318,7 +325,12 @@ impl<'a, S: BlockchainStorage, V: TransactionVerificationStrategy> BlockInvalida
             },
         )?;
 
-        self.find_and_activate_best_chain()?;
+        // Non-persisted block indices have been deleted above, so some leaves may be gone.
+        self.chainstate.reload_block_tree_leaves();
+
+        if self.find_and_activate_best_chain()? {
+            self.chainstate.record_reorg_from(&old_tip_index);
+        }
 
         Ok(())
     }
@@ -0,0 +1,84 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+
+use common::{
+    chain::{Block, GenBlock},
+    primitives::Id,
+};
+
+/// In-memory set of the blocks that have no children in the block tree, kept up to date
+/// as block indices are added, so that the fork tips can be found without scanning the
+/// whole block tree.
+pub struct BlockTreeLeaves {
+    /// None if the leaves are unknown and have to be recomputed from the db
+    leaves: Option<BTreeSet<Id<Block>>>,
+}
+
+impl BlockTreeLeaves {
+    pub fn new_unknown() -> Self {
+        Self { leaves: None }
+    }
+
+    pub fn get(&self) -> Option<&BTreeSet<Id<Block>>> {
+        self.leaves.as_ref()
+    }
+
+    pub fn reset(&mut self, leaves: Option<BTreeSet<Id<Block>>>) {
+        self.leaves = leaves;
+    }
+
+    /// Account for a newly saved block index
+    pub fn add_block(&mut self, block_id: Id<Block>, prev_block_id: &Id<GenBlock>) {
+        if let Some(leaves) = &mut self.leaves {
+            // Genesis is never in the set, so its id won't match anything here
+            leaves.remove(&Id::new(prev_block_id.to_hash()));
+            leaves.insert(block_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use common::primitives::H256;
+    use test_utils::random::{make_seedable_rng, Seed};
+
+    use super::*;
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn parent_is_replaced_by_child(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let root = Id::<GenBlock>::new(H256::random_using(&mut rng));
+        let a = Id::<Block>::new(H256::random_using(&mut rng));
+        let b = Id::<Block>::new(H256::random_using(&mut rng));
+        let c = Id::<Block>::new(H256::random_using(&mut rng));
+
+        let mut leaves = BlockTreeLeaves::new_unknown();
+        leaves.add_block(a, &root);
+        assert_eq!(leaves.get(), None);
+
+        leaves.reset(Some(BTreeSet::new()));
+        leaves.add_block(a, &root);
+        leaves.add_block(b, &a.into());
+        leaves.add_block(c, &root);
+        assert_eq!(leaves.get(), Some(&BTreeSet::from([b, c])));
+    }
+}
@@ -92,11 +92,23 @@ impl<'a, DbTx: BlockchainStorageRead> ConsistencyChecker<'a, DbTx> {
                 Ord::cmp(id1, id2)
             })
         {
-            let (block_id, block_index) = match merged {
+            let (block_id, block_index, has_body) = match merged {
                 EitherOrBoth::Left(block_id) => {
                     // The block object is present, the index object is not.
                     panic!("{PANIC_MSG}: block index data missing for block {block_id}");
                 }
+                EitherOrBoth::Right((block_id, block_index))
+                    if block_index.status().is_body_pruned() =>
+                {
+                    // The block body was pruned or covered by a snapshot, only the index object
+                    // is left.
+                    assert!(
+                        self.is_pruned_block(block_index),
+                        "{PANIC_MSG}: block {block_id} can't have its body pruned"
+                    );
+
+                    (block_id, block_index, false)
+                }
                 EitherOrBoth::Right((block_id, block_index)) => {
                     // The block index object is present, the block object is not;
                     // The persistence flag must be unset and the status must not be "ok".
@@ -109,18 +121,18 @@ impl<'a, DbTx: BlockchainStorageRead> ConsistencyChecker<'a, DbTx> {
                         "{PANIC_MSG}: block {block_id} must not be ok"
                     );
 
-                    (block_id, block_index)
+                    (block_id, block_index, false)
                 }
                 EitherOrBoth::Both(_, (block_id, block_index)) => {
                     // Both the block and block index objects are present.
 
-                    // The persistence flag must be set.
+                    // The persistence flag must be set and the body must not be marked as pruned.
                     assert!(
-                        block_index.is_persisted(),
+                        block_index.has_body(),
                         "{PANIC_MSG}: block {block_id} must be persisted"
                     );
 
-                    (block_id, block_index)
+                    (block_id, block_index, true)
                 }
             };
 
@@ -132,10 +144,10 @@ impl<'a, DbTx: BlockchainStorageRead> ConsistencyChecker<'a, DbTx> {
                 "{PANIC_MSG}: block id from BlockIndex {block_id_in_block_index} doesn't match {block_id}"
             );
 
-            // If the block is persisted, calculate its id and check that it matches the id
+            // If the block body is present, calculate its id and check that it matches the id
             // that was used as the key. Also compare the block header stored in the index vs the one
             // in the block itself.
-            if block_index.is_persisted() {
+            if has_body {
                 let block =
                     self.db_tx.get_block(*block_id)?.expect("The block is known to be present");
                 let calculated_block_id = block.get_id();
@@ -187,6 +199,16 @@ impl<'a, DbTx: BlockchainStorageRead> ConsistencyChecker<'a, DbTx> {
         Ok(())
     }
 
+    /// Whether the block can have its body pruned or covered by a snapshot. Only persisted
+    /// finalized mainchain blocks can be like that.
+    fn is_pruned_block(&self, block_index: &BlockIndex) -> bool {
+        block_index.is_persisted()
+            && block_index.status().is_fully_valid()
+            && block_index.block_height() <= self.min_height_with_allowed_reorg
+            && self.block_by_height_map.get(&block_index.block_height())
+                == Some(&(*block_index.block_id()).into())
+    }
+
     /// Check consistency of the block-by-height map.
     fn check_block_height_map_consistency(&self) {
         // The block at zero height must be the genesis.
@@ -58,7 +58,7 @@ use tx_verifier::transaction_verifier::TransactionVerifier;
 use utils::{debug_assert_or_log, ensure, log_error, tap_log::TapLog};
 use utxo::{UtxosCache, UtxosDB, UtxosStorageRead, UtxosView};
 
-use crate::{BlockError, ChainstateConfig};
+use crate::{BlockError, ChainstateConfig, MainchainChange};
 
 use self::{
     block_info::BlockInfo, consistency_checker::ConsistencyChecker,
@@ -66,7 +66,8 @@ use self::{
 };
 
 use super::{
-    median_time::calculate_median_time_past, transaction_verifier::flush::flush_to_storage,
+    median_time::calculate_median_time_past, transaction_verifier::error::ConnectTransactionError,
+    transaction_verifier::flush::flush_to_storage,
     tx_verification_strategy::TransactionVerificationStrategy, BlockSizeError, CheckBlockError,
     CheckBlockTransactionsError,
 };
@@ -74,12 +75,18 @@ use super::{
 pub use epoch_seal::EpochSealError;
 pub use in_memory_reorg::InMemoryReorgError;
 
+/// How many mainchain blocks below the tip are searched for the block that has spent an output
+/// which a new block attempts to spend again.
+const DOUBLE_SPEND_LOOKUP_DEPTH: u64 = 100;
+
 pub struct ChainstateRef<'a, S, V> {
     chain_config: &'a ChainConfig,
-    _chainstate_config: &'a ChainstateConfig,
+    chainstate_config: &'a ChainstateConfig,
     tx_verification_strategy: &'a V,
     db_tx: S,
     time_getter: &'a TimeGetter,
+    // The blocks connected and disconnected via this db tx
+    mainchain_changes: Vec<MainchainChange>,
 }
 
 impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> BlockIndexHandle
@@ -129,6 +136,16 @@ impl<'a, S: TransactionRw, V> ChainstateRef<'a, S, V> {
     pub fn check_storage_error(&self) -> chainstate_storage::Result<()> {
         self.db_tx.check_error()
     }
+
+    pub fn abort_db_tx(self) {
+        self.db_tx.abort()
+    }
+
+    /// Take the blocks that have been disconnected from and connected to the main chain so far,
+    /// in the order in which that happened.
+    pub fn take_mainchain_changes(&mut self) -> Vec<MainchainChange> {
+        std::mem::take(&mut self.mainchain_changes)
+    }
 }
 
 impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> ChainstateRef<'a, S, V> {
@@ -141,10 +158,11 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
     ) -> Self {
         ChainstateRef {
             chain_config,
-            _chainstate_config: chainstate_config,
+            chainstate_config,
             db_tx,
             tx_verification_strategy,
             time_getter,
+            mainchain_changes: Vec::new(),
         }
     }
 
@@ -157,10 +175,11 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
     ) -> Self {
         ChainstateRef {
             chain_config,
-            _chainstate_config: chainstate_config,
+            chainstate_config,
             db_tx,
             tx_verification_strategy,
             time_getter,
+            mainchain_changes: Vec::new(),
         }
     }
 
@@ -179,6 +198,10 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         self.chain_config
     }
 
+    pub fn db_tx(&self) -> &S {
+        &self.db_tx
+    }
+
     pub fn current_time(&self) -> Time {
         self.time_getter.get_time()
     }
@@ -291,6 +314,71 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         self.get_block_height_in_main_chain(block_id).map(|ht| ht.is_some())
     }
 
+    /// Find the mainchain block that spends the specified outpoint, looking at most at `max_depth`
+    /// blocks, starting from the tip.
+    #[log_error]
+    pub fn find_mainchain_block_spending(
+        &self,
+        outpoint: &UtxoOutPoint,
+        max_depth: u64,
+    ) -> Result<Option<Id<Block>>, PropertyQueryError> {
+        let best_block_height = self.get_best_block_index()?.block_height().into_int();
+        let min_height = std::cmp::max(best_block_height.saturating_sub(max_depth), 1);
+
+        for height in (min_height..=best_block_height).rev() {
+            let block_id = match self
+                .get_existing_block_id_by_height(&BlockHeight::new(height))?
+                .classify(self.chain_config)
+            {
+                GenBlockId::Genesis(_) => break,
+                GenBlockId::Block(id) => id,
+            };
+            // Block bodies below this one may have been pruned too
+            let block = match self.get_block(block_id)? {
+                Some(block) => block,
+                None => break,
+            };
+
+            let spends_outpoint = block
+                .transactions()
+                .iter()
+                .flat_map(|tx| tx.inputs())
+                .any(|input| input.utxo_outpoint() == Some(outpoint));
+            if spends_outpoint {
+                return Ok(Some(block_id));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// If the block connection failed because an input spends an output that has already been
+    /// spent on the mainchain, report the block that has spent it.
+    ///
+    /// Note: this scans the recent mainchain blocks, so it's only meant to be used for
+    /// locally submitted blocks.
+    pub fn make_output_already_spent_error(&self, err: BlockError) -> BlockError {
+        if let BlockError::StateUpdateFailed(ConnectTransactionError::MissingOutputOrSpent(
+            outpoint,
+        )) = &err
+        {
+            match self.find_mainchain_block_spending(outpoint, DOUBLE_SPEND_LOOKUP_DEPTH) {
+                Ok(Some(spent_in_block)) => {
+                    return BlockError::OutputAlreadySpent {
+                        outpoint: outpoint.clone(),
+                        spent_in_block,
+                    };
+                }
+                Ok(None) => {}
+                Err(lookup_err) => {
+                    log::warn!("Failed to look up the block spending {outpoint:?}: {lookup_err}");
+                }
+            }
+        }
+
+        err
+    }
+
     #[log_error]
     pub fn get_min_height_with_allowed_reorg(&self) -> Result<BlockHeight, PropertyQueryError> {
         Ok(self.db_tx.get_min_height_with_allowed_reorg()?.unwrap_or(0.into()))
@@ -817,6 +905,14 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
 
         self.check_block_size(block).map_err(CheckBlockError::BlockSizeError)?;
 
+        if let Some(max_txs) = self.chainstate_config.max_transactions_per_block {
+            let tx_count = block.transactions().len();
+            ensure!(
+                tx_count <= max_txs,
+                CheckBlockError::TooManyTransactions(tx_count, max_txs)
+            );
+        }
+
         self.check_block_reward_maturity_settings(block)?;
 
         let merkle_proxy = block
@@ -898,6 +994,25 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         self.get_higher_block_ids_sorted_by_height(0.into())
     }
 
+    /// Return ids of the blocks that have no children.
+    /// Note: this iterates over all block indices in the DB.
+    #[log_error]
+    pub fn get_block_tree_leaves(&self) -> Result<BTreeSet<Id<Block>>, PropertyQueryError> {
+        let block_ids = self.get_block_id_tree_as_list()?;
+
+        let mut parents = BTreeSet::new();
+        for block_id in &block_ids {
+            let block_index = self.get_existing_block_index(block_id)?;
+            parents.insert(*block_index.prev_block_id());
+        }
+
+        let result = block_ids
+            .into_iter()
+            .filter(|block_id| !parents.contains(&Id::<GenBlock>::from(*block_id)))
+            .collect();
+        Ok(result)
+    }
+
     /// Return ids of all blocks with height bigger or equal to the specified one,
     /// sorted by height (lower first).
     // TODO: this function iterates over all block indices in the DB, which is too expensive
@@ -1133,6 +1248,62 @@ impl<'a, S: BlockchainStorageWrite, V: TransactionVerificationStrategy> Chainsta
         Ok(())
     }
 
+    /// Disconnect the mainchain blocks starting from the specified height and connect them again,
+    /// re-running all the checks for each of them.
+    #[log_error]
+    pub fn reprocess_mainchain_from(&mut self, height: BlockHeight) -> Result<(), BlockError> {
+        let best_block_index =
+            self.get_best_block_index().map_err(BlockError::BestBlockIndexQueryError)?;
+        let best_block_id = match best_block_index.block_id().classify(self.chain_config) {
+            GenBlockId::Genesis(_) => return Ok(()),
+            GenBlockId::Block(id) => id,
+        };
+
+        // Genesis is never disconnected
+        let height = std::cmp::max(height, BlockHeight::new(1));
+        if height > best_block_index.block_height() {
+            return Ok(());
+        }
+
+        let min_height_with_allowed_reorg = self
+            .get_min_height_with_allowed_reorg()
+            .map_err(BlockError::MinHeightForReorgQueryError)?;
+        ensure!(
+            height > min_height_with_allowed_reorg,
+            BlockError::HeightTooDeepToReprocess(height)
+        );
+
+        let block_indices = height
+            .iter_up_to_including(best_block_index.block_height())
+            .map(|height| {
+                let block_id = self
+                    .get_existing_block_id_by_height(&height)?
+                    .classify(self.chain_config)
+                    .chain_block_id()
+                    .expect("Genesis is not at a non-zero height");
+                self.get_existing_block_index(&block_id)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(BlockError::PropertyQueryError)?;
+
+        let first_block_index = block_indices.first().expect("The tip is always reprocessed");
+        self.disconnect_until(&best_block_id, first_block_index.prev_block_id())?;
+
+        for block_index in &block_indices {
+            // Note: connect_tip skips the block checks for blocks that have already passed them.
+            let block: WithId<Block> = self
+                .get_block_from_index(block_index)?
+                .ok_or(BlockError::BlockDataMissingForValidBlockIndex(
+                    *block_index.block_id(),
+                ))?
+                .into();
+            self.check_block(&block)?;
+            self.connect_tip(block_index)?;
+        }
+
+        Ok(())
+    }
+
     #[log_error]
     fn reorganize(
         &mut self,
@@ -1268,7 +1439,13 @@ impl<'a, S: BlockchainStorageWrite, V: TransactionVerificationStrategy> Chainsta
             self.set_block_index(&new_block_index)?;
         }
 
-        self.post_connect_tip(block_index, block.as_ref())
+        self.post_connect_tip(block_index, block.as_ref())?;
+
+        self.mainchain_changes.push(MainchainChange::Connected(
+            *block_index.block_id(),
+            block_index.block_height(),
+        ));
+        Ok(())
     }
 
     /// Does a read-modify-write operation on the database and disconnects a block
@@ -1307,6 +1484,11 @@ impl<'a, S: BlockchainStorageWrite, V: TransactionVerificationStrategy> Chainsta
             .expect("Previous block index retrieval failed");
 
         self.post_disconnect_tip(prev_block_index.block_height())?;
+
+        self.mainchain_changes.push(MainchainChange::Disconnected(
+            best_block_id,
+            block_index.block_height(),
+        ));
         Ok(prev_block_index)
     }
 
@@ -1422,6 +1604,52 @@ impl<'a, S: BlockchainStorageWrite, V: TransactionVerificationStrategy> Chainsta
 
         let consumed_epoch_data = epoch_data_cache.consume();
         consumed_epoch_data.flush(&mut self.db_tx)?;
+
+        if let Some(pruning_depth) = self.chainstate_config.pruning_depth {
+            self.prune_block_bodies(tip_height, pruning_depth)?;
+        }
+
+        Ok(())
+    }
+
+    /// Delete the bodies of the mainchain blocks that are `pruning_depth` or more blocks below
+    /// the tip. The block indices, which contain the headers, are kept and marked as having
+    /// their bodies pruned.
+    #[log_error]
+    fn prune_block_bodies(
+        &mut self,
+        tip_height: BlockHeight,
+        pruning_depth: u64,
+    ) -> Result<(), BlockError> {
+        let prune_height = match tip_height.into_int().checked_sub(pruning_depth) {
+            // The genesis block is a part of the chain config and is never stored
+            Some(0) | None => return Ok(()),
+            Some(height) => BlockHeight::new(height),
+        };
+
+        // Blocks are pruned from the bottom up, so going down from the prune height stops
+        // at the first block that was already pruned (or covered by a snapshot).
+        let mut height = prune_height;
+        while height > BlockHeight::zero() {
+            let block_id = self
+                .get_existing_block_id_by_height(&height)
+                .map_err(BlockError::PropertyQueryError)?
+                .classify(self.chain_config)
+                .chain_block_id()
+                .expect("Genesis is not at a non-zero height");
+            let block_index = self
+                .get_existing_block_index(&block_id)
+                .map_err(BlockError::PropertyQueryError)?;
+            if block_index.status().is_body_pruned() {
+                break;
+            }
+
+            self.set_block_index(&block_index.with_body_pruned())?;
+            self.db_tx.del_block(block_id)?;
+
+            height = height.prev_height().expect("Height is not zero");
+        }
+
         Ok(())
     }
 
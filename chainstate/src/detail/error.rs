@@ -30,7 +30,7 @@ use common::{
     chain::{
         block::{block_body::BlockMerkleTreeError, timestamp::BlockTimestamp},
         config::MagicBytes,
-        Block, GenBlock, PoolId,
+        Block, GenBlock, PoolId, UtxoOutPoint,
     },
     primitives::{Amount, BlockHeight, Id},
 };
@@ -46,6 +46,11 @@ pub enum BlockError {
     CheckBlockFailed(#[from] CheckBlockError),
     #[error("Failed to update the internal blockchain state: {0}")]
     StateUpdateFailed(#[from] ConnectTransactionError),
+    #[error("Output {outpoint:?} has already been spent in block {spent_in_block}")]
+    OutputAlreadySpent {
+        outpoint: UtxoOutPoint,
+        spent_in_block: Id<Block>,
+    },
     #[error("The previous block not found when adding new block {0}")]
     PrevBlockNotFoundForNewBlock(Id<Block>),
     #[error("Block {0} already exists")]
@@ -113,6 +118,9 @@ pub enum BlockError {
 
     #[error("Unexpected block height range: first = {0}, second = {1}")]
     UnexpectedHeightRange(BlockHeight, BlockHeight),
+
+    #[error("The blocks starting at height {0} are too deep to reprocess")]
+    HeightTooDeepToReprocess(BlockHeight),
 }
 
 // Note: this enum isn't supposed to represent a complete error; this is why its elements
@@ -124,6 +132,8 @@ pub enum DbCommittingContext {
     Block(Id<Block>),
     #[display(fmt = "committing block status for block {}", _0)]
     BlockStatus(Id<Block>),
+    #[display(fmt = "committing blocks reprocessed from height {}", _0)]
+    ReprocessedBlocks(BlockHeight),
 }
 
 #[derive(Error, Debug, PartialEq, Eq, Clone)]
@@ -147,6 +157,8 @@ pub enum CheckBlockError {
     BlockFromTheFuture(Id<Block>),
     #[error("Block size is too large: {0}")]
     BlockSizeError(#[from] BlockSizeError),
+    #[error("Too many transactions in block (current: {0}, limit: {1})")]
+    TooManyTransactions(usize, usize),
     #[error("Check transaction failed: {0}")]
     CheckTransactionFailed(CheckBlockTransactionsError),
     #[error("Consensus verification failed: {0}")]
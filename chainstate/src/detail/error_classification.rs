@@ -99,6 +99,7 @@ impl BlockProcessingErrorClassification for BlockError {
             | BlockError::InvariantErrorPoolBalancePresentDataMissing(_, _)
             | BlockError::InvariantErrorPoolDataPresentBalanceMissing(_, _)
             | BlockError::UnexpectedHeightRange(_, _)
+            | BlockError::HeightTooDeepToReprocess(_)
             | BlockError::DbCommitError(_, _, _)
             | BlockError::BlockAlreadyExists(_)
             | BlockError::BlockIndexAlreadyExists(_)
@@ -123,6 +124,7 @@ impl BlockProcessingErrorClassification for BlockError {
             BlockError::InvalidBlockAlreadyProcessed(_) => BlockProcessingErrorClass::BadBlock,
 
             BlockError::BlockProofCalculationError(_) => BlockProcessingErrorClass::BadBlock,
+            BlockError::OutputAlreadySpent { .. } => BlockProcessingErrorClass::BadBlock,
 
             BlockError::TransactionVerifierError(err) => err.classify(),
             BlockError::PoSAccountingError(err) => err.classify(),
@@ -172,7 +174,9 @@ impl BlockProcessingErrorClassification for CheckBlockError {
             | CheckBlockError::AttemptedToAddBlockBeforeReorgLimit(_, _, _)
             | CheckBlockError::InvalidParent { .. } => BlockProcessingErrorClass::BadBlock,
 
-            CheckBlockError::BlockFromTheFuture(_) => {
+            // The transaction count limit is a local policy and the block may become acceptable
+            // once it's changed, so the block must not be invalidated.
+            CheckBlockError::BlockFromTheFuture(_) | CheckBlockError::TooManyTransactions(_, _) => {
                 BlockProcessingErrorClass::TemporarilyBadBlock
             }
 
@@ -426,6 +430,7 @@ impl BlockProcessingErrorClassification for PropertyQueryError {
             | PropertyQueryError::BlockForHeightNotFound(_)
             | PropertyQueryError::GenesisHeaderRequested
             | PropertyQueryError::InvalidStartingBlockHeightForMainchainBlocks(_)
+            | PropertyQueryError::BlockBodyPruned(_, _)
             | PropertyQueryError::InvalidBlockHeightRange { .. } => {
                 BlockProcessingErrorClass::General
             }
@@ -640,9 +645,8 @@ impl BlockProcessingErrorClassification for ConsensusPoSError {
             | ConsensusPoSError::PoSBlockTimeStrictOrderInvalid(_)
             | ConsensusPoSError::FiniteTotalSupplyIsRequired
             | ConsensusPoSError::UnsupportedConsensusVersion
-            | ConsensusPoSError::FailedToCalculateCappedBalance => {
-                BlockProcessingErrorClass::BadBlock
-            }
+            | ConsensusPoSError::FailedToCalculateCappedBalance
+            | ConsensusPoSError::PoolBalanceUnderflow => BlockProcessingErrorClass::BadBlock,
 
             ConsensusPoSError::TargetConversionError(err) => match err {
                 UintConversionError::ConversionOverflow => BlockProcessingErrorClass::BadBlock,
@@ -831,6 +835,7 @@ impl BlockProcessingErrorClassification for pos_accounting::Error {
             | Error::DelegateToNonexistingId
             | Error::DelegateToNonexistingPool
             | Error::SpendingShareOfNonexistingDelegation(_)
+            | Error::UnauthorizedDelegationSpend(_)
             | Error::AdditionError
             | Error::SubError
             | Error::DelegationBalanceAdditionError
@@ -853,6 +858,9 @@ impl BlockProcessingErrorClassification for pos_accounting::Error {
             | Error::InvariantErrorDelegationUndoFailedDataNotFound(_)
             | Error::DuplicatesInDeltaAndUndo
             | Error::IncreaseStakerRewardsOfNonexistingPool
+            | Error::AttemptedSlashNonexistingPool
+            | Error::SlashExceedsPoolBalance
+            | Error::InvariantErrorSlashUndoFailedPoolDataNotFound
             | Error::StakerBalanceOverflow
             | Error::InvariantErrorIncreasePledgeUndoFailedPoolBalanceNotFound
             | Error::InvariantErrorIncreaseStakerRewardUndoFailedPoolBalanceNotFound
@@ -16,7 +16,7 @@
 use serde::{Deserialize, Serialize};
 
 use common::{
-    chain::{block::timestamp::BlockTimestamp, GenBlock},
+    chain::{block::timestamp::BlockTimestamp, Block, GenBlock},
     primitives::{BlockHeight, Id},
 };
 
@@ -28,3 +28,12 @@ pub struct ChainInfo {
     pub median_time: BlockTimestamp,
     pub is_initial_block_download: bool,
 }
+
+/// A branch of the block tree that forked off the main chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, rpc::description::HasValueHint)]
+pub struct ChainFork {
+    /// The height of the last block shared by the branch and the main chain
+    pub fork_point_height: BlockHeight,
+    pub orphan_tip: Id<Block>,
+    pub main_tip: Id<GenBlock>,
+}
@@ -13,12 +13,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod block_tree_leaves;
 mod chainstateref;
 mod error;
 mod error_classification;
 mod info;
 mod median_time;
 mod orphan_blocks;
+mod reorg_tracker;
 
 pub mod ban_score;
 pub mod block_checking;
@@ -26,6 +28,8 @@ pub mod block_invalidation;
 pub mod bootstrap;
 pub mod query;
 pub mod tx_verification_strategy;
+pub mod utxo_commitment;
+pub mod utxo_snapshot;
 
 use std::{collections::VecDeque, sync::Arc};
 
@@ -35,22 +39,31 @@ use utils_networking::broadcaster;
 
 use self::{
     block_invalidation::BlockInvalidator,
+    block_tree_leaves::BlockTreeLeaves,
     orphan_blocks::{OrphanBlocksMut, OrphansProxy},
     query::ChainstateQuery,
+    reorg_tracker::ReorgTracker,
     tx_verification_strategy::TransactionVerificationStrategy,
+    utxo_snapshot::UtxoSnapshotError,
+};
+use crate::{
+    BlockInvalidatorError, ChainstateConfig, ChainstateEvent, MainchainChange,
+    MainchainChangesHandler,
 };
-use crate::{BlockInvalidatorError, ChainstateConfig, ChainstateEvent};
 use chainstate_storage::{
     BlockchainStorage, BlockchainStorageRead, BlockchainStorageWrite, TransactionRw, Transactional,
 };
 use chainstate_types::{
     pos_randomness::PoSRandomness, BlockIndex, BlockStatus, BlockValidationStage, EpochData,
-    EpochStorageWrite, PropertyQueryError, SealedStorageTag, TipStorageTag,
+    EpochStorageWrite, GenBlockIndex, PropertyQueryError, SealedStorageTag, TipStorageTag,
 };
 use chainstateref::{ChainstateRef, ReorgError};
 use common::{
-    chain::{block::timestamp::BlockTimestamp, config::ChainConfig, Block, GenBlock, TxOutput},
-    primitives::{id::WithId, BlockHeight, Compact, Id, Idable},
+    chain::{
+        block::timestamp::BlockTimestamp, config::ChainConfig, Block, GenBlock, GenBlockId,
+        TxOutput,
+    },
+    primitives::{id::WithId, BlockHeight, Compact, Id, Idable, H256},
     time_getter::TimeGetter,
     Uint256,
 };
@@ -71,7 +84,7 @@ use utils::{
 use utxo::UtxosDB;
 
 pub use self::{
-    error::*, info::ChainInfo, median_time::calculate_median_time_past,
+    error::*, info::ChainFork, info::ChainInfo, median_time::calculate_median_time_past,
     median_time::calculate_median_time_past_from_blocktimestamps, median_time::MEDIAN_TIME_SPAN,
 };
 pub use chainstate_types::Locator;
@@ -82,6 +95,7 @@ pub use error::{
 };
 pub use error_classification::{BlockProcessingErrorClass, BlockProcessingErrorClassification};
 pub use orphan_blocks::OrphanBlocksRef;
+pub use reorg_tracker::ReorgEvent;
 pub use transaction_verifier::{
     error::{ConnectTransactionError, SpendStakeError, TokenIssuanceError, TokensError},
     storage::TransactionVerifierStorageError,
@@ -104,8 +118,11 @@ pub struct Chainstate<S, V> {
     custom_orphan_error_hook: Option<Arc<OrphanErrorHandler>>,
     subsystem_events: EventsController<ChainstateEvent>,
     rpc_events: broadcaster::Broadcaster<ChainstateEvent>,
+    mainchain_changes_handlers: Vec<MainchainChangesHandler>,
     time_getter: TimeGetter,
     is_initial_block_download_finished: SetFlag,
+    reorg_tracker: ReorgTracker,
+    block_tree_leaves: BlockTreeLeaves,
 }
 
 #[derive(Copy, Clone, Eq, Debug, PartialEq)]
@@ -162,6 +179,12 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         self.rpc_events.subscribe()
     }
 
+    /// Unlike the events, the handler is called synchronously, right after the db tx that has
+    /// changed the main chain is committed.
+    pub fn subscribe_to_mainchain_changes(&mut self, handler: MainchainChangesHandler) {
+        self.mainchain_changes_handlers.push(handler);
+    }
+
     #[log_error]
     pub fn new(
         chain_config: Arc<ChainConfig>,
@@ -173,6 +196,17 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
     ) -> Result<Self, crate::ChainstateError> {
         use crate::ChainstateError;
 
+        if let Some(pruning_depth) = chainstate_config.pruning_depth {
+            // Blocks that may still be disconnected during a reorg must keep their bodies
+            let min_depth = chain_config.max_depth_for_reorg();
+            ensure!(
+                pruning_depth > 0
+                    && i64::try_from(pruning_depth)
+                        .map_or(true, |depth| depth >= min_depth.to_int()),
+                ChainstateError::CannotPruneToDepth(pruning_depth, min_depth)
+            );
+        }
+
         let best_block_id = {
             let db_tx = chainstate_storage
                 .transaction_ro()
@@ -203,6 +237,8 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
             .check_consistency()
             .map_err(|e| ChainstateError::FailedToInitializeChainstate(e.into()))?;
 
+        chainstate.reload_block_tree_leaves();
+
         Ok(chainstate)
     }
 
@@ -226,8 +262,11 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
             custom_orphan_error_hook,
             subsystem_events,
             rpc_events,
+            mainchain_changes_handlers: Vec::new(),
             time_getter,
             is_initial_block_download_finished: SetFlag::new(),
+            reorg_tracker: ReorgTracker::new(),
+            block_tree_leaves: BlockTreeLeaves::new_unknown(),
         }
     }
 
@@ -273,12 +312,24 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         }
     }
 
+    fn notify_mainchain_changes(&self, mainchain_changes: Vec<MainchainChange>) {
+        if mainchain_changes.is_empty() {
+            return;
+        }
+
+        for handler in &self.mainchain_changes_handlers {
+            handler(mainchain_changes.clone());
+        }
+    }
+
     /// Create a read-write transaction, call `main_action` on it and commit.
     ///
     /// If a storage failure occurs during execution or committing fails, repeat the whole process
     /// again until it succeeds or the maximum number of commit attempts is reached. If the maximum
     /// number of attempts is reached, use `on_db_err` to create a BlockError and return it. On each
     /// iteration, before doing anything else, call `on_new_attempt` (this can be used for logging).
+    /// Once committed, the main chain changes made by the transaction are passed to the handlers
+    /// subscribed to them.
     #[log_error]
     fn with_rw_tx<MainAction, OnNewAttempt, OnDbCommitErr, Res, Err>(
         &mut self,
@@ -321,10 +372,14 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
                 }
             };
 
+            let mainchain_changes = chainstate_ref.take_mainchain_changes();
             let db_commit_result = chainstate_ref.commit_db_tx();
 
             match db_commit_result {
-                Ok(()) => return Ok(result),
+                Ok(()) => {
+                    self.notify_mainchain_changes(mainchain_changes);
+                    return Ok(result);
+                }
                 Err(dbtx_err) => {
                     if is_last_attempt || !dbtx_err.is_intermittent() {
                         return Err(on_db_commit_err(attempts_count, dbtx_err));
@@ -374,6 +429,10 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
 
         result.map_err(|err| match err {
             ReorgError::ConnectTipFailed(block_id, block_err) => {
+                // The db tx still has the partially connected chain at this point, so the block
+                // that has spent the output can be found, if it's on that chain.
+                // The lookup is bounded, so it's done for peer blocks too.
+                let block_err = chainstate_ref.make_output_already_spent_error(block_err);
                 BlockIntegrationError::ConnectBlockErrorDuringReorg(
                     block_err,
                     block_status,
@@ -386,6 +445,126 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         })
     }
 
+    /// Describe the switch of the best chain from `old_tip_index` to `new_tip_index`, unless it
+    /// has only extended the old chain without disconnecting any of its blocks.
+    /// Switches from or to the genesis are not described, as the event refers to actual blocks.
+    fn make_reorg_event(
+        chainstate_ref: &ChainstateRef<TxRo<'_, S>, V>,
+        old_tip_index: &GenBlockIndex,
+        new_tip_index: &GenBlockIndex,
+        timestamp: BlockTimestamp,
+    ) -> Result<Option<ReorgEvent>, BlockError> {
+        let chain_config = chainstate_ref.chain_config();
+        let (old_tip, new_tip) = match (
+            old_tip_index.block_id().classify(chain_config),
+            new_tip_index.block_id().classify(chain_config),
+        ) {
+            (GenBlockId::Block(old_tip), GenBlockId::Block(new_tip)) => (old_tip, new_tip),
+            (GenBlockId::Genesis(_), _) | (_, GenBlockId::Genesis(_)) => return Ok(None),
+        };
+
+        // The common case of the chain being extended by one block
+        if new_tip_index.prev_block_id() == Some(old_tip_index.block_id()) {
+            return Ok(None);
+        }
+
+        let common_ancestor = chainstate_ref
+            .last_common_ancestor(old_tip_index, new_tip_index)
+            .map_err(BlockError::PropertyQueryError)?;
+        let reorg_depth =
+            old_tip_index.block_height().into_int() - common_ancestor.block_height().into_int();
+
+        Ok((reorg_depth > 0).then_some(ReorgEvent {
+            reorg_depth,
+            old_tip,
+            new_tip,
+            timestamp,
+        }))
+    }
+
+    /// Record the switch of the best chain from `old_tip_index` to the current best block,
+    /// if it has disconnected any blocks.
+    /// The new best chain has already been committed, so a failure to describe the switch
+    /// is only logged.
+    fn record_reorg_from(&mut self, old_tip_index: &GenBlockIndex) {
+        let timestamp = BlockTimestamp::from_time(self.time_getter.get_time());
+        let reorg_event =
+            self.make_db_tx_ro().map_err(BlockError::from).and_then(|chainstate_ref| {
+                let new_tip_index = chainstate_ref
+                    .get_best_block_index()
+                    .map_err(BlockError::BestBlockIndexQueryError)?;
+                Self::make_reorg_event(&chainstate_ref, old_tip_index, &new_tip_index, timestamp)
+            });
+
+        match reorg_event {
+            Ok(Some(reorg_event)) => {
+                log::info!(
+                    "Reorg of depth {} from {} to {}",
+                    reorg_event.reorg_depth,
+                    reorg_event.old_tip,
+                    reorg_event.new_tip
+                );
+                self.reorg_tracker.record(reorg_event);
+            }
+            Ok(None) => {}
+            Err(err) => {
+                log::error!(
+                    "Failed to describe the reorg from block {}: {err}",
+                    old_tip_index.block_id()
+                );
+            }
+        }
+    }
+
+    pub fn recent_reorgs(&self) -> Vec<ReorgEvent> {
+        self.reorg_tracker.recent_reorgs()
+    }
+
+    /// Rescan the block tree to find its leaves. On failure, they will be recomputed
+    /// on each query until the next successful reload.
+    fn reload_block_tree_leaves(&mut self) {
+        let leaves = self
+            .make_db_tx_ro()
+            .map_err(PropertyQueryError::from)
+            .and_then(|chainstate_ref| chainstate_ref.get_block_tree_leaves());
+
+        match leaves {
+            Ok(leaves) => self.block_tree_leaves.reset(Some(leaves)),
+            Err(err) => {
+                log::warn!("Failed to load the block tree leaves: {err}");
+                self.block_tree_leaves.reset(None);
+            }
+        }
+    }
+
+    /// Return ids of the blocks that are not part of the main chain and have no children,
+    /// i.e. the tips of the branches that were forked off the main chain.
+    /// Blocks that have failed validation are not reported, as their branches can't become
+    /// the main chain.
+    #[log_error]
+    pub fn get_orphan_tips(&self) -> Result<Vec<Id<Block>>, PropertyQueryError> {
+        let chainstate_ref = self.make_db_tx_ro()?;
+
+        let scanned_leaves;
+        let leaves = match self.block_tree_leaves.get() {
+            Some(leaves) => leaves,
+            None => {
+                scanned_leaves = chainstate_ref.get_block_tree_leaves()?;
+                &scanned_leaves
+            }
+        };
+
+        let mut result = Vec::new();
+        for block_id in leaves {
+            if !chainstate_ref.is_block_in_main_chain(&(*block_id).into())?
+                && chainstate_ref.get_existing_block_index(block_id)?.status().is_ok()
+            {
+                result.push(*block_id);
+            }
+        }
+        Ok(result)
+    }
+
     /// Attempt to process the block. On success, return Some(block_index_of_the_passed_block)
     /// if a reorg has occurred and the passed block is now the best block, otherwise return None.
     #[log_error]
@@ -399,7 +578,7 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
 
         // Ensure that the block being submitted is new to us. If not, bail out immediately,
         // otherwise create a new block index and continue.
-        let block_index = {
+        let (block_index, old_tip_index) = {
             let chainstate_ref = self.make_db_tx_ro().map_err(BlockError::from)?;
             let existing_block_index = get_block_index(&chainstate_ref, &block_id)?;
 
@@ -411,7 +590,12 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
                 };
             }
 
-            chainstate_ref.create_block_index_for_new_block(&block, BlockStatus::new())?
+            let old_tip_index = chainstate_ref
+                .get_best_block_index()
+                .map_err(BlockError::BestBlockIndexQueryError)?;
+            let block_index =
+                chainstate_ref.create_block_index_for_new_block(&block, BlockStatus::new())?;
+            (block_index, old_tip_index)
         };
 
         // Perform block checks; `integrate_block_result` is `Result<bool>`, where the bool
@@ -428,6 +612,10 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
 
         match integrate_block_result {
             Ok(reorg_occurred) => {
+                // The block index has been committed, so the leaves must be updated before
+                // anything else can fail.
+                self.block_tree_leaves.add_block(block_id, block_index.prev_block_id());
+
                 // If the above code has succeeded, then the block_index must be present in the DB.
                 // Note that we can't return the initially obtained block_index, because its
                 // block status is outdated.
@@ -435,6 +623,12 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
                 let saved_block_index = get_existing_block_index(&chainstate_ref, &block_id)?;
 
                 assert!(saved_block_index.status().is_ok());
+                drop(chainstate_ref);
+
+                if reorg_occurred {
+                    self.record_reorg_from(&old_tip_index);
+                }
+
                 return Ok(reorg_occurred.then_some(saved_block_index));
             }
             Err(BlockIntegrationError::BlockCommitError(block_id, attempts_count, db_err)) => {
@@ -532,7 +726,7 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
 
     #[log_error]
     fn set_new_block_index(&mut self, block_index: &BlockIndex) -> Result<(), BlockError> {
-        self.with_rw_tx(
+        let result = self.with_rw_tx(
             |chainstate_ref| chainstate_ref.set_new_block_index(block_index),
             |attempt_number| {
                 log::info!(
@@ -548,7 +742,13 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
                     DbCommittingContext::BlockStatus(*block_index.block_id()),
                 )
             },
-        )
+        );
+
+        if result.is_ok() {
+            self.block_tree_leaves
+                .add_block(*block_index.block_id(), block_index.prev_block_id());
+        }
+        result
     }
 
     /// process orphan blocks that depend on the given block, recursively
@@ -696,6 +896,79 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         Ok(())
     }
 
+    /// Disconnect the mainchain blocks starting from the specified height and connect them again,
+    /// checking that each of them is still valid.
+    /// On success the tip stays the same; on failure nothing is changed.
+    #[log_error]
+    pub fn reprocess_blocks_from(&mut self, height: BlockHeight) -> Result<(), BlockError> {
+        let result = self.with_rw_tx(
+            |chainstate_ref| chainstate_ref.reprocess_mainchain_from(height),
+            |attempt_number| {
+                log::info!(
+                    "Reprocessing blocks starting at height {height}, attempt #{attempt_number}"
+                );
+            },
+            |attempts_count, db_err| {
+                BlockError::DbCommitError(
+                    attempts_count,
+                    db_err,
+                    DbCommittingContext::ReprocessedBlocks(height),
+                )
+            },
+        );
+        // Note: we don't ignore the result of check_consistency even though we may already have
+        // an error to return (if the checks are enabled but couldn't be done for some reason,
+        // we don't want to miss this).
+        self.check_consistency()?;
+        result
+    }
+
+    /// Write a snapshot of the state as of the mainchain block at the given height and return
+    /// its commitment.
+    #[log_error]
+    pub fn export_utxo_snapshot(
+        &mut self,
+        height: BlockHeight,
+        writer: &mut impl std::io::Write,
+    ) -> Result<H256, UtxoSnapshotError> {
+        let chain_config = Arc::clone(&self.chain_config);
+        let mut chainstate_ref = self.make_db_tx()?;
+        let result =
+            utxo_snapshot::export_utxo_snapshot(&mut chainstate_ref, &chain_config, height, writer);
+
+        // The blocks above the snapshot height were only disconnected to obtain the state
+        // at that height
+        chainstate_ref.abort_db_tx();
+        result
+    }
+
+    /// Compute the commitment to the current UTXO set.
+    #[log_error]
+    pub fn utxo_set_commitment(&self) -> Result<H256, PropertyQueryError> {
+        let db_tx = self.chainstate_storage.transaction_ro()?;
+        let utxos = db_tx.get_utxo_set()?;
+        Ok(utxo_commitment::compute_utxo_commitment(
+            utxos.into_iter().map(|(outpoint, utxo)| (outpoint, utxo.take_output())),
+        ))
+    }
+
+    /// Initialize the chainstate, which must contain only the genesis, from a snapshot.
+    #[log_error]
+    pub fn import_utxo_snapshot(
+        &mut self,
+        reader: &mut impl std::io::Read,
+    ) -> Result<(), UtxoSnapshotError> {
+        let mut db_tx = self.chainstate_storage.transaction_rw(None)?;
+        utxo_snapshot::import_utxo_snapshot(&mut db_tx, &self.chain_config, reader)?;
+        db_tx.commit()?;
+
+        self.reload_block_tree_leaves();
+
+        self.check_consistency()?;
+        self.update_initial_block_download_flag()?;
+        Ok(())
+    }
+
     #[log_error]
     pub fn invalidate_block(&mut self, block_id: &Id<Block>) -> Result<(), BlockInvalidatorError> {
         let result = BlockInvalidator::new(self)
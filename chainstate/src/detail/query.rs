@@ -110,9 +110,10 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
                             panic!("genesis block received at non-zero height {from}")
                         }
                         common::chain::GenBlockId::Block(block_id) => {
-                            let block = self.get_block(block_id)?.unwrap_or_else(|| {
-                                panic!("can't find block {block_id} at height {from}")
-                            });
+                            let block = self
+                                .chainstate_ref
+                                .get_block(block_id)?
+                                .ok_or(PropertyQueryError::BlockBodyPruned(block_id, from))?;
                             res.push(block);
                         }
                     }
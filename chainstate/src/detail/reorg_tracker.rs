@@ -0,0 +1,100 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use common::{
+    chain::{block::timestamp::BlockTimestamp, Block},
+    primitives::Id,
+};
+
+/// The maximum number of reorgs kept by the tracker
+pub const MAX_TRACKED_REORGS: usize = 100;
+
+/// A change of the best chain that disconnected some of its blocks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, rpc::description::HasValueHint)]
+pub struct ReorgEvent {
+    /// The number of blocks of the old best chain that were disconnected
+    pub reorg_depth: u64,
+    pub old_tip: Id<Block>,
+    pub new_tip: Id<Block>,
+    /// When the reorg happened
+    pub timestamp: BlockTimestamp,
+}
+
+/// In-memory history of the most recent reorgs, the oldest ones are dropped first
+pub struct ReorgTracker {
+    events: VecDeque<ReorgEvent>,
+}
+
+impl ReorgTracker {
+    pub fn new() -> Self {
+        Self {
+            events: VecDeque::with_capacity(MAX_TRACKED_REORGS),
+        }
+    }
+
+    pub fn record(&mut self, event: ReorgEvent) {
+        if self.events.len() == MAX_TRACKED_REORGS {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+
+    /// The recorded reorgs, from the oldest to the most recent one
+    pub fn recent_reorgs(&self) -> Vec<ReorgEvent> {
+        self.events.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use common::primitives::H256;
+    use randomness::Rng;
+    use test_utils::random::{make_seedable_rng, Seed};
+
+    use super::*;
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn oldest_events_are_dropped(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let events = (0..MAX_TRACKED_REORGS + rng.gen_range(1..10))
+            .map(|i| ReorgEvent {
+                reorg_depth: rng.gen_range(1..10),
+                old_tip: Id::new(H256::random_using(&mut rng)),
+                new_tip: Id::new(H256::random_using(&mut rng)),
+                timestamp: BlockTimestamp::from_int_seconds(i as u64),
+            })
+            .collect::<Vec<_>>();
+
+        let mut tracker = ReorgTracker::new();
+        assert!(tracker.recent_reorgs().is_empty());
+        for event in &events {
+            tracker.record(event.clone());
+        }
+
+        assert_eq!(
+            tracker.recent_reorgs(),
+            events[events.len() - MAX_TRACKED_REORGS..]
+        );
+    }
+}
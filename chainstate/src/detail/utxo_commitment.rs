@@ -0,0 +1,123 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Commitment to the UTXO set, which allows checking that a UTXO is part of the set with
+//! a merkle proof instead of the whole set.
+
+use std::collections::BTreeMap;
+
+use common::{
+    chain::{block::block_body::merkle_tools::MerkleHasher, TxOutput, UtxoOutPoint},
+    primitives::{id, H256},
+};
+use merkletree_mintlayer::tree::MerkleTree;
+
+/// Compute the merkle root of the UTXO set.
+///
+/// The leaves are the hashes of the encoded `(outpoint, output)` pairs sorted by the outpoint,
+/// so the result doesn't depend on the order of `utxos`. The commitment of an empty set is zero.
+pub fn compute_utxo_commitment(utxos: impl Iterator<Item = (UtxoOutPoint, TxOutput)>) -> H256 {
+    let leaves = utxos
+        .collect::<BTreeMap<_, _>>()
+        .iter()
+        .map(|utxo| id::hash_encoded(&utxo))
+        .collect::<Vec<H256>>();
+
+    if leaves.is_empty() {
+        return H256::zero();
+    }
+
+    MerkleTree::<H256, MerkleHasher>::from_leaves(leaves)
+        .expect("The leaves are not empty")
+        .root()
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{
+        chain::{output_value::OutputValue, Destination, OutPointSourceId},
+        primitives::Amount,
+    };
+    use randomness::{Rng, SliceRandom};
+    use rstest::rstest;
+    use test_utils::random::{make_seedable_rng, Seed};
+
+    use super::*;
+
+    fn random_utxo(rng: &mut impl Rng) -> (UtxoOutPoint, TxOutput) {
+        let outpoint = UtxoOutPoint::new(
+            OutPointSourceId::Transaction(H256::random_using(rng).into()),
+            rng.gen_range(0..10),
+        );
+        let output = TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(rng.gen_range(1..1_000_000))),
+            Destination::AnyoneCanSpend,
+        );
+        (outpoint, output)
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn order_independent(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let mut utxos =
+            (0..rng.gen_range(1..50)).map(|_| random_utxo(&mut rng)).collect::<Vec<_>>();
+        let commitment = compute_utxo_commitment(utxos.clone().into_iter());
+
+        utxos.reverse();
+        assert_eq!(
+            compute_utxo_commitment(utxos.clone().into_iter()),
+            commitment
+        );
+
+        utxos.shuffle(&mut rng);
+        assert_eq!(compute_utxo_commitment(utxos.into_iter()), commitment);
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn changes_after_spending(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let mut utxos =
+            (0..rng.gen_range(1..50)).map(|_| random_utxo(&mut rng)).collect::<Vec<_>>();
+        let commitment = compute_utxo_commitment(utxos.clone().into_iter());
+
+        // Spend a UTXO
+        let spent = utxos.remove(rng.gen_range(0..utxos.len()));
+        let commitment_after_spend = compute_utxo_commitment(utxos.clone().into_iter());
+        assert_ne!(commitment_after_spend, commitment);
+
+        // Create a new one
+        utxos.push(random_utxo(&mut rng));
+        assert_ne!(
+            compute_utxo_commitment(utxos.clone().into_iter()),
+            commitment_after_spend
+        );
+
+        // The set of the remaining UTXOs and the spent one again has the original commitment
+        utxos.pop();
+        utxos.push(spent);
+        assert_eq!(compute_utxo_commitment(utxos.into_iter()), commitment);
+    }
+
+    #[test]
+    fn empty_set() {
+        assert_eq!(compute_utxo_commitment(std::iter::empty()), H256::zero());
+    }
+}
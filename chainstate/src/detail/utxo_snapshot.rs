@@ -0,0 +1,498 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Chainstate snapshots, which allow a new node to start from a finalized mainchain block
+//! instead of downloading and connecting all blocks since genesis.
+//!
+//! A snapshot contains the indices of the mainchain blocks up to the snapshot block and the whole
+//! state as of that block: the utxo set, PoS accounting, tokens and orders data, epoch data and
+//! account nonces. It is written as a stream, so that the utxo set never has to be kept in memory:
+//! - the magic bytes of the chain;
+//! - the encoded `SnapshotHeader`;
+//! - the encoded indices of the mainchain blocks at heights `1..=block_height`;
+//! - the encoded `SnapshotStateData`;
+//! - pages of utxos, each encoded as a `Vec`, terminated by an empty page.
+//!
+//! The commitment to a snapshot is the hash of everything after the magic bytes. The imported
+//! state is not validated, so a snapshot is only imported if its commitment matches the trusted
+//! one from the chain config.
+
+use std::io::{Read, Write};
+
+use chainstate_storage::{BlockchainStorageWrite, SnapshotStateData};
+use chainstate_types::{BlockIndex, PropertyQueryError, SealedStorageTag, TipStorageTag};
+use common::{
+    chain::{config::MagicBytes, Block, ChainConfig, GenBlock, UtxoOutPoint},
+    primitives::{
+        id::{hash_encoded_to, DefaultHashAlgoStream},
+        BlockHeight, Id, H256,
+    },
+};
+use crypto::hash::StreamHasher;
+use pos_accounting::{
+    PoSAccountingData, PoSAccountingStorageRead, PoSAccountingStorageWrite, StorageTag,
+};
+use serialization::{Decode, Encode};
+use utils::ensure;
+use utxo::Utxo;
+
+use crate::BlockError;
+
+use super::{
+    chainstateref::ChainstateRef, tx_verification_strategy::TransactionVerificationStrategy,
+};
+
+/// The number of utxos that are read from the storage and written to the snapshot at once
+const UTXOS_PAGE_SIZE: usize = 10_000;
+
+#[derive(thiserror::Error, Debug, Clone, Eq, PartialEq)]
+pub enum UtxoSnapshotError {
+    #[error("Storage error: {0}")]
+    StorageError(#[from] chainstate_storage::Error),
+    #[error("Property query error: {0}")]
+    PropertyQueryError(#[from] PropertyQueryError),
+    #[error("Failed to disconnect the blocks above the snapshot height: {0}")]
+    BlockError(#[from] BlockError),
+    #[error("File error: {0}")]
+    File(String),
+    #[error("Deserialization error: {0}")]
+    Deserialization(#[from] serialization::Error),
+    #[error("Snapshot height {0} is above the best block height {1}")]
+    HeightAboveTip(BlockHeight, BlockHeight),
+    #[error("Snapshot height {0} is not finalized, the min height with allowed reorg is {1}")]
+    HeightNotFinalized(BlockHeight, BlockHeight),
+    #[error("Mainchain block at height {0} not found")]
+    MainchainBlockNotFound(BlockHeight),
+    #[error("Block {0} not found")]
+    BlockNotFound(Id<Block>),
+    #[error("Snapshot can only be imported into a chainstate that contains only the genesis")]
+    ChainstateNotEmpty,
+    #[error("The snapshot was created for a different chain")]
+    WrongMagicBytes,
+    #[error("There is no trusted commitment for a snapshot at height {0}")]
+    UntrustedSnapshot(BlockHeight),
+    #[error("Snapshot commitment {actual} doesn't match the trusted commitment {expected}")]
+    CommitmentMismatch { expected: H256, actual: H256 },
+    #[error("Invalid block index for block {0} in the snapshot")]
+    InvalidBlockIndex(Id<Block>),
+    #[error("Snapshot block at height {0} is {1}, but the chain has {2}")]
+    BlockIdMismatch(BlockHeight, Id<GenBlock>, Id<GenBlock>),
+}
+
+impl From<std::io::Error> for UtxoSnapshotError {
+    fn from(error: std::io::Error) -> Self {
+        Self::File(error.to_string())
+    }
+}
+
+/// The mainchain block the snapshot was taken at
+#[derive(Debug, Clone, Encode, Decode)]
+struct SnapshotHeader {
+    block_id: Id<GenBlock>,
+    block_height: BlockHeight,
+}
+
+/// Writes the encoded snapshot items and computes the commitment
+struct SnapshotWriter<'w, W> {
+    writer: &'w mut W,
+    hasher: DefaultHashAlgoStream,
+}
+
+impl<'w, W: Write> SnapshotWriter<'w, W> {
+    fn new(writer: &'w mut W, magic_bytes: &MagicBytes) -> Result<Self, UtxoSnapshotError> {
+        writer.write_all(&magic_bytes.bytes())?;
+        Ok(Self {
+            writer,
+            hasher: DefaultHashAlgoStream::new(),
+        })
+    }
+
+    fn write<T: Encode>(&mut self, item: &T) -> Result<(), UtxoSnapshotError> {
+        let encoded = item.encode();
+        self.hasher.write(&encoded);
+        self.writer.write_all(&encoded)?;
+        Ok(())
+    }
+
+    fn finalize(mut self) -> H256 {
+        self.hasher.finalize().into()
+    }
+}
+
+/// Reads the encoded snapshot items and computes the commitment
+struct SnapshotReader<'r, R> {
+    reader: &'r mut R,
+    hasher: DefaultHashAlgoStream,
+}
+
+impl<'r, R: Read> SnapshotReader<'r, R> {
+    fn new(reader: &'r mut R, magic_bytes: &MagicBytes) -> Result<Self, UtxoSnapshotError> {
+        let mut file_magic_bytes = [0; 4];
+        reader.read_exact(&mut file_magic_bytes)?;
+        ensure!(
+            file_magic_bytes == magic_bytes.bytes(),
+            UtxoSnapshotError::WrongMagicBytes
+        );
+
+        Ok(Self {
+            reader,
+            hasher: DefaultHashAlgoStream::new(),
+        })
+    }
+
+    fn read<T: Encode + Decode>(&mut self) -> Result<T, UtxoSnapshotError> {
+        let item = T::decode(&mut parity_scale_codec::IoReader(&mut *self.reader))?;
+        hash_encoded_to(&item, &mut self.hasher);
+        Ok(item)
+    }
+
+    fn finalize(mut self) -> H256 {
+        self.hasher.finalize().into()
+    }
+}
+
+fn mainchain_block_id_at_height<S, V>(
+    chainstate_ref: &ChainstateRef<'_, S, V>,
+    chain_config: &ChainConfig,
+    height: BlockHeight,
+) -> Result<Id<Block>, UtxoSnapshotError>
+where
+    S: BlockchainStorageWrite,
+    V: TransactionVerificationStrategy,
+{
+    chainstate_ref
+        .get_existing_block_id_by_height(&height)?
+        .classify(chain_config)
+        .chain_block_id()
+        .ok_or(UtxoSnapshotError::MainchainBlockNotFound(height))
+}
+
+/// Write a snapshot of the state as of the mainchain block at the given height and return
+/// its commitment.
+///
+/// The block must be deep enough not to be reorged anymore. The state at its height is obtained
+/// by disconnecting the blocks above it in the db transaction of `chainstate_ref`, so the caller
+/// must abort the transaction afterwards.
+pub fn export_utxo_snapshot<S, V>(
+    chainstate_ref: &mut ChainstateRef<'_, S, V>,
+    chain_config: &ChainConfig,
+    height: BlockHeight,
+    writer: &mut impl Write,
+) -> Result<H256, UtxoSnapshotError>
+where
+    S: BlockchainStorageWrite,
+    V: TransactionVerificationStrategy,
+{
+    let best_block_index = chainstate_ref.get_best_block_index()?;
+    let best_block_height = best_block_index.block_height();
+    ensure!(
+        height <= best_block_height,
+        UtxoSnapshotError::HeightAboveTip(height, best_block_height)
+    );
+
+    let min_height_with_allowed_reorg = chainstate_ref.get_min_height_with_allowed_reorg()?;
+    ensure!(
+        height <= min_height_with_allowed_reorg,
+        UtxoSnapshotError::HeightNotFinalized(height, min_height_with_allowed_reorg)
+    );
+
+    // The blocks above the snapshot height must be present to be disconnected
+    for cur_height in height.next_height().iter_up_to_including(best_block_height) {
+        let block_id = mainchain_block_id_at_height(chainstate_ref, chain_config, cur_height)?;
+        ensure!(
+            chainstate_ref.block_exists(block_id)?,
+            UtxoSnapshotError::BlockNotFound(block_id)
+        );
+    }
+
+    let block_id = chainstate_ref.get_existing_block_id_by_height(&height)?;
+    if let Some(best_block_id) = best_block_index.block_id().classify(chain_config).chain_block_id()
+    {
+        chainstate_ref.disconnect_until(&best_block_id, &block_id)?;
+    }
+
+    let mut writer = SnapshotWriter::new(writer, chain_config.magic_bytes())?;
+    writer.write(&SnapshotHeader {
+        block_id,
+        block_height: height,
+    })?;
+
+    for cur_height in BlockHeight::new(1).iter_up_to_including(height) {
+        let block_id = mainchain_block_id_at_height(chainstate_ref, chain_config, cur_height)?;
+        // The block bodies are not a part of the snapshot, and whether the exporting node
+        // has pruned them must not affect the commitment
+        let block_index = chainstate_ref.get_existing_block_index(&block_id)?;
+        writer.write(&block_index.with_body_pruned())?;
+    }
+
+    writer.write(&chainstate_ref.db_tx().get_snapshot_state_data()?)?;
+
+    let mut start_after = None;
+    loop {
+        let utxos = chainstate_ref.db_tx().get_utxos_page(start_after, UTXOS_PAGE_SIZE)?;
+        writer.write(&utxos)?;
+        match utxos.last() {
+            Some((outpoint, _)) => start_after = Some(outpoint.clone()),
+            None => break,
+        }
+    }
+
+    Ok(writer.finalize())
+}
+
+/// Initialize a chainstate that contains only the genesis from a snapshot.
+///
+/// After the import the snapshot block becomes the tip, blocks on top of it can be processed
+/// as usual; reorgs below it are not possible. On error, the db transaction must be aborted.
+pub fn import_utxo_snapshot<Tx: BlockchainStorageWrite>(
+    db_tx: &mut Tx,
+    chain_config: &ChainConfig,
+    reader: &mut impl Read,
+) -> Result<(), UtxoSnapshotError> {
+    let genesis_id = chain_config.genesis_block_id();
+    ensure!(
+        db_tx.get_best_block_id()? == Some(genesis_id),
+        UtxoSnapshotError::ChainstateNotEmpty
+    );
+
+    let mut reader = SnapshotReader::new(reader, chain_config.magic_bytes())?;
+    let header: SnapshotHeader = reader.read()?;
+    let expected_commitment = *chain_config
+        .snapshot_commitment_at_height(&header.block_height)
+        .ok_or(UtxoSnapshotError::UntrustedSnapshot(header.block_height))?;
+
+    // The block indices must form a chain from the genesis to the snapshot block
+    let mut prev_block_id = genesis_id;
+    let mut prev_height = BlockHeight::zero();
+    while prev_height < header.block_height {
+        let block_index: BlockIndex = reader.read()?;
+        let block_id = *block_index.block_id();
+        ensure!(
+            block_index.block_header().block_id() == block_id
+                && *block_index.prev_block_id() == prev_block_id
+                && block_index.block_height() == prev_height.next_height()
+                && block_index.status().is_fully_valid()
+                && block_index.status().is_body_pruned()
+                && block_index.is_persisted(),
+            UtxoSnapshotError::InvalidBlockIndex(block_id)
+        );
+
+        prev_block_id = block_id.into();
+        prev_height = block_index.block_height();
+
+        if let Some(checkpoint) =
+            chain_config.height_checkpoints().checkpoint_at_height(&prev_height)
+        {
+            ensure!(
+                *checkpoint == prev_block_id,
+                UtxoSnapshotError::BlockIdMismatch(prev_height, prev_block_id, *checkpoint)
+            );
+        }
+
+        db_tx.set_block_index(&block_index)?;
+        db_tx.set_block_id_at_height(&prev_height, &prev_block_id)?;
+    }
+    ensure!(
+        prev_block_id == header.block_id,
+        UtxoSnapshotError::BlockIdMismatch(header.block_height, header.block_id, prev_block_id)
+    );
+
+    // Replace the state created from the genesis with the snapshot one
+    del_genesis_state(db_tx, chain_config)?;
+
+    let state: SnapshotStateData = reader.read()?;
+    set_state_data(db_tx, &state)?;
+
+    loop {
+        let utxos: Vec<(UtxoOutPoint, Utxo)> = reader.read()?;
+        if utxos.is_empty() {
+            break;
+        }
+        for (outpoint, utxo) in utxos {
+            db_tx.set_utxo(&outpoint, utxo)?;
+        }
+    }
+
+    let commitment = reader.finalize();
+    ensure!(
+        commitment == expected_commitment,
+        UtxoSnapshotError::CommitmentMismatch {
+            expected: expected_commitment,
+            actual: commitment,
+        }
+    );
+
+    db_tx.set_best_block_id(&header.block_id)?;
+    db_tx.set_best_block_for_utxos(&header.block_id)?;
+    db_tx.set_min_height_with_allowed_reorg(header.block_height)?;
+    db_tx.set_snapshot_height(header.block_height)?;
+
+    Ok(())
+}
+
+fn del_genesis_state<Tx: BlockchainStorageWrite>(
+    db_tx: &mut Tx,
+    chain_config: &ChainConfig,
+) -> Result<(), chainstate_storage::Error> {
+    let genesis_id = chain_config.genesis_block_id();
+    for idx in 0..chain_config.genesis_block().utxos().len() {
+        db_tx.del_utxo(&UtxoOutPoint::new(genesis_id.into(), idx as u32))?;
+    }
+
+    let state = db_tx.get_snapshot_state_data()?;
+
+    for epoch_index in state.epoch_data.keys() {
+        db_tx.del_epoch_data(*epoch_index)?;
+    }
+    for epoch_index in state.accounting_epoch_deltas.keys() {
+        db_tx.del_accounting_epoch_delta(*epoch_index)?;
+    }
+    for epoch_index in state.accounting_epoch_undo_deltas.keys() {
+        db_tx.del_accounting_epoch_undo_delta(*epoch_index)?;
+    }
+
+    del_pos_accounting_data::<_, TipStorageTag>(db_tx, &state.pos_accounting_data_tip)?;
+    del_pos_accounting_data::<_, SealedStorageTag>(db_tx, &state.pos_accounting_data_sealed)?;
+
+    for token_id in state.tokens_accounting_data.token_data.keys() {
+        db_tx.del_token_data(token_id)?;
+    }
+    for token_id in state.tokens_accounting_data.circulating_supply.keys() {
+        db_tx.del_circulating_supply(token_id)?;
+    }
+    for token_id in state.token_aux_data.keys() {
+        db_tx.del_token_aux_data(token_id)?;
+    }
+    for tx_id in state.token_ids.keys() {
+        db_tx.del_token_id(tx_id)?;
+    }
+
+    for order_id in state.orders_accounting_data.order_data.keys() {
+        db_tx.del_order_data(order_id)?;
+    }
+    for order_id in state.orders_accounting_data.ask_balances.keys() {
+        db_tx.del_ask_balance(order_id)?;
+    }
+    for order_id in state.orders_accounting_data.give_balances.keys() {
+        db_tx.del_give_balance(order_id)?;
+    }
+
+    for account in state.account_nonces.keys() {
+        db_tx.del_account_nonce_count(*account)?;
+    }
+
+    Ok(())
+}
+
+fn set_state_data<Tx: BlockchainStorageWrite>(
+    db_tx: &mut Tx,
+    state: &SnapshotStateData,
+) -> Result<(), chainstate_storage::Error> {
+    for (epoch_index, epoch_data) in &state.epoch_data {
+        db_tx.set_epoch_data(*epoch_index, epoch_data)?;
+    }
+    for (epoch_index, delta) in &state.accounting_epoch_deltas {
+        db_tx.set_accounting_epoch_delta(*epoch_index, delta)?;
+    }
+    for (epoch_index, undo) in &state.accounting_epoch_undo_deltas {
+        db_tx.set_accounting_epoch_undo_delta(*epoch_index, undo)?;
+    }
+
+    set_pos_accounting_data::<_, TipStorageTag>(db_tx, &state.pos_accounting_data_tip)?;
+    set_pos_accounting_data::<_, SealedStorageTag>(db_tx, &state.pos_accounting_data_sealed)?;
+
+    for (token_id, data) in &state.tokens_accounting_data.token_data {
+        db_tx.set_token_data(token_id, data)?;
+    }
+    for (token_id, supply) in &state.tokens_accounting_data.circulating_supply {
+        db_tx.set_circulating_supply(token_id, supply)?;
+    }
+    for (token_id, data) in &state.token_aux_data {
+        db_tx.set_token_aux_data(token_id, data)?;
+    }
+    for (tx_id, token_id) in &state.token_ids {
+        db_tx.set_token_id(tx_id, token_id)?;
+    }
+
+    for (order_id, data) in &state.orders_accounting_data.order_data {
+        db_tx.set_order_data(order_id, data)?;
+    }
+    for (order_id, balance) in &state.orders_accounting_data.ask_balances {
+        db_tx.set_ask_balance(order_id, balance)?;
+    }
+    for (order_id, balance) in &state.orders_accounting_data.give_balances {
+        db_tx.set_give_balance(order_id, balance)?;
+    }
+
+    for (account, nonce) in &state.account_nonces {
+        db_tx.set_account_nonce_count(*account, *nonce)?;
+    }
+
+    Ok(())
+}
+
+fn del_pos_accounting_data<Tx, Tag>(
+    db_tx: &mut Tx,
+    data: &PoSAccountingData,
+) -> Result<(), chainstate_storage::Error>
+where
+    Tx: PoSAccountingStorageWrite<Tag>
+        + PoSAccountingStorageRead<Tag, Error = chainstate_storage::Error>,
+    Tag: StorageTag,
+{
+    for pool_id in data.pool_data.keys() {
+        db_tx.del_pool_data(*pool_id)?;
+    }
+    for pool_id in data.pool_balances.keys() {
+        db_tx.del_pool_balance(*pool_id)?;
+    }
+    for (pool_id, delegation_id) in data.pool_delegation_shares.keys() {
+        db_tx.del_pool_delegation_share(*pool_id, *delegation_id)?;
+    }
+    for delegation_id in data.delegation_balances.keys() {
+        db_tx.del_delegation_balance(*delegation_id)?;
+    }
+    for delegation_id in data.delegation_data.keys() {
+        db_tx.del_delegation_data(*delegation_id)?;
+    }
+    Ok(())
+}
+
+fn set_pos_accounting_data<Tx, Tag>(
+    db_tx: &mut Tx,
+    data: &PoSAccountingData,
+) -> Result<(), chainstate_storage::Error>
+where
+    Tx: PoSAccountingStorageWrite<Tag>
+        + PoSAccountingStorageRead<Tag, Error = chainstate_storage::Error>,
+    Tag: StorageTag,
+{
+    for (pool_id, pool_data) in &data.pool_data {
+        db_tx.set_pool_data(*pool_id, pool_data)?;
+    }
+    for (pool_id, balance) in &data.pool_balances {
+        db_tx.set_pool_balance(*pool_id, *balance)?;
+    }
+    for ((pool_id, delegation_id), balance) in &data.pool_delegation_shares {
+        db_tx.set_pool_delegation_share(*pool_id, *delegation_id, *balance)?;
+    }
+    for (delegation_id, balance) in &data.delegation_balances {
+        db_tx.set_delegation_balance(*delegation_id, *balance)?;
+    }
+    for (delegation_id, delegation_data) in &data.delegation_data {
+        db_tx.set_delegation_data(*delegation_id, delegation_data)?;
+    }
+    Ok(())
+}
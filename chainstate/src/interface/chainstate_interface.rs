@@ -17,7 +17,7 @@ use std::{collections::BTreeMap, num::NonZeroUsize, sync::Arc};
 
 use crate::{
     detail::BlockSource, ChainInfo, ChainstateConfig, ChainstateError, ChainstateEvent,
-    NonZeroPoolBalances,
+    MainchainChangesHandler, NonZeroPoolBalances, ReorgEvent,
 };
 use chainstate_types::{BlockIndex, EpochData, GenBlockIndex, Locator};
 use common::{
@@ -30,7 +30,7 @@ use common::{
         AccountNonce, AccountType, ChainConfig, DelegationId, OrderData, OrderId, PoolId,
         Transaction, TxInput, UtxoOutPoint,
     },
-    primitives::{Amount, BlockHeight, Id},
+    primitives::{Amount, BlockHeight, Id, H256},
 };
 use pos_accounting::{DelegationData, PoolData};
 use utils::eventhandler::EventHandler;
@@ -43,6 +43,9 @@ pub trait ChainstateInterface: Send + Sync {
         handler: Arc<dyn Fn(ChainstateEvent) + Send + Sync>,
     );
     fn subscribe_to_rpc_events(&mut self) -> broadcaster::Receiver<ChainstateEvent>;
+    /// Subscribe to the blocks being disconnected from and connected to the main chain.
+    /// The handler is called synchronously, once per committed change of the main chain.
+    fn subscribe_to_mainchain_changes(&mut self, handler: MainchainChangesHandler);
     /// Process the given block. If a reorg occurs, return the block index of the new tip.
     /// Otherwise return None.
     fn process_block(
@@ -52,6 +55,11 @@ pub trait ChainstateInterface: Send + Sync {
     ) -> Result<Option<BlockIndex>, ChainstateError>;
     fn invalidate_block(&mut self, block_id: &Id<Block>) -> Result<(), ChainstateError>;
     fn reset_block_failure_flags(&mut self, block_id: &Id<Block>) -> Result<(), ChainstateError>;
+
+    /// Disconnect the mainchain blocks starting from the given height and connect them again,
+    /// re-validating each one (e.g. after a consensus rule change).
+    /// The tip is restored on success; the state is left unchanged on failure.
+    fn reprocess_blocks_from(&mut self, height: BlockHeight) -> Result<(), ChainstateError>;
     fn preliminary_block_check(&self, block: Block) -> Result<Block, ChainstateError>;
 
     /// Check the headers. The first header's parent block must be known.
@@ -237,6 +245,14 @@ pub trait ChainstateInterface: Send + Sync {
     /// Returns a list of all blocks in the block tree, including orphans. The length cannot be predicted before the call.
     fn get_block_id_tree_as_list(&self) -> Result<Vec<Id<Block>>, ChainstateError>;
 
+    /// Returns the tips of all the branches of the block tree that are not part of the mainchain.
+    /// The blocks that have failed validation are not included.
+    fn get_orphan_tips(&self) -> Result<Vec<Id<Block>>, ChainstateError>;
+
+    /// Returns the most recent reorgs since the node was started, from the oldest to the newest.
+    /// At most 100 of them are kept.
+    fn get_recent_reorgs(&self) -> Vec<ReorgEvent>;
+
     /// Imports a bootstrap file exported with `export_bootstrap_stream`.
     fn import_bootstrap_stream<'a>(
         &mut self,
@@ -253,6 +269,26 @@ pub trait ChainstateInterface: Send + Sync {
         include_orphans: bool,
     ) -> Result<(), ChainstateError>;
 
+    /// Writes a snapshot of the chainstate as of the mainchain block at the given height,
+    /// which must be below the reorg limit, and returns the snapshot commitment.
+    fn export_utxo_snapshot<'a>(
+        &mut self,
+        height: BlockHeight,
+        writer: std::io::BufWriter<Box<dyn std::io::Write + Send + 'a>>,
+    ) -> Result<H256, ChainstateError>;
+
+    /// Initializes a chainstate that contains only the genesis from a snapshot created with
+    /// `export_utxo_snapshot`, so that blocks below the snapshot height don't have to be processed.
+    /// The snapshot commitment must match the one trusted by the chain config at that height.
+    fn import_utxo_snapshot<'a>(
+        &mut self,
+        reader: std::io::BufReader<Box<dyn std::io::Read + Send + 'a>>,
+    ) -> Result<(), ChainstateError>;
+
+    /// Returns the merkle root of the current UTXO set, see `compute_utxo_commitment`.
+    /// This goes over the whole UTXO set, so it's expensive.
+    fn get_utxo_set_commitment(&self) -> Result<H256, ChainstateError>;
+
     /// Returns the UTXO for a specified OutPoint.
     fn utxo(&self, outpoint: &UtxoOutPoint) -> Result<Option<Utxo>, ChainstateError>;
 
@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{collections::BTreeMap, num::NonZeroUsize, sync::Arc};
+use std::{collections::BTreeMap, io::Write, num::NonZeroUsize, sync::Arc};
 
 use crate::{
     detail::{
@@ -26,7 +26,7 @@ use crate::{
         BlockSource, OrphanBlocksRef,
     },
     ChainInfo, ChainstateConfig, ChainstateError, ChainstateEvent, ChainstateInterface, Locator,
-    NonZeroPoolBalances,
+    MainchainChangesHandler, NonZeroPoolBalances, ReorgEvent, UtxoSnapshotError,
 };
 use chainstate_storage::BlockchainStorage;
 use chainstate_types::{BlockIndex, EpochData, GenBlockIndex, PropertyQueryError};
@@ -38,7 +38,7 @@ use common::{
         AccountNonce, AccountType, DelegationId, OrderData, OrderId, PoolId, Transaction, TxInput,
         TxOutput, UtxoOutPoint,
     },
-    primitives::{id::WithId, Amount, BlockHeight, Id, Idable},
+    primitives::{id::WithId, Amount, BlockHeight, Id, Idable, H256},
 };
 use pos_accounting::{DelegationData, PoSAccountingStorageRead, PoolData};
 use utils::{displayable_option::DisplayableOption, eventhandler::EventHandler};
@@ -70,6 +70,11 @@ where
         self.chainstate.subscribe_to_event_broadcast()
     }
 
+    #[tracing::instrument(skip_all)]
+    fn subscribe_to_mainchain_changes(&mut self, handler: MainchainChangesHandler) {
+        self.chainstate.subscribe_to_mainchain_changes(handler)
+    }
+
     #[tracing::instrument(skip_all, fields(block_id = %block.get_id()))]
     fn process_block(
         &mut self,
@@ -95,6 +100,13 @@ where
             .map_err(ChainstateError::BlockInvalidatorError)
     }
 
+    #[tracing::instrument(skip_all, fields(height = %height))]
+    fn reprocess_blocks_from(&mut self, height: BlockHeight) -> Result<(), ChainstateError> {
+        self.chainstate
+            .reprocess_blocks_from(height)
+            .map_err(ChainstateError::ProcessBlockError)
+    }
+
     #[tracing::instrument(
         skip_all,
         fields(first_block_id = %headers.first().map(|header| header.get_id()).as_displayable())
@@ -564,6 +576,16 @@ where
             .map_err(ChainstateError::FailedToReadProperty)
     }
 
+    #[tracing::instrument(skip_all)]
+    fn get_orphan_tips(&self) -> Result<Vec<Id<Block>>, ChainstateError> {
+        self.chainstate.get_orphan_tips().map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    #[tracing::instrument(skip_all)]
+    fn get_recent_reorgs(&self) -> Vec<ReorgEvent> {
+        self.chainstate.recent_reorgs()
+    }
+
     #[tracing::instrument(skip_all)]
     fn import_bootstrap_stream<'a>(
         &mut self,
@@ -606,6 +628,35 @@ where
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(height = %height))]
+    fn export_utxo_snapshot<'a>(
+        &mut self,
+        height: BlockHeight,
+        writer: std::io::BufWriter<Box<dyn std::io::Write + Send + 'a>>,
+    ) -> Result<H256, ChainstateError> {
+        let mut writer = writer;
+        let commitment = self.chainstate.export_utxo_snapshot(height, &mut writer)?;
+        writer.flush().map_err(UtxoSnapshotError::from)?;
+        Ok(commitment)
+    }
+
+    #[tracing::instrument(skip_all)]
+    fn import_utxo_snapshot<'a>(
+        &mut self,
+        reader: std::io::BufReader<Box<dyn std::io::Read + Send + 'a>>,
+    ) -> Result<(), ChainstateError> {
+        let mut reader = reader;
+        self.chainstate.import_utxo_snapshot(&mut reader)?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all)]
+    fn get_utxo_set_commitment(&self) -> Result<H256, ChainstateError> {
+        self.chainstate
+            .utxo_set_commitment()
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
     #[tracing::instrument(skip_all)]
     fn utxo(&self, outpoint: &UtxoOutPoint) -> Result<Option<Utxo>, ChainstateError> {
         let chainstate_ref = self
@@ -29,7 +29,7 @@ use common::{
         AccountNonce, AccountType, Block, DelegationId, GenBlock, OrderData, OrderId, PoolId,
         Transaction, TxInput, UtxoOutPoint,
     },
-    primitives::{Amount, BlockHeight, Id},
+    primitives::{Amount, BlockHeight, Id, H256},
 };
 use pos_accounting::{DelegationData, PoolData};
 use utils::eventhandler::EventHandler;
@@ -38,7 +38,7 @@ use utxo::Utxo;
 
 use crate::{
     chainstate_interface::ChainstateInterface, BlockSource, ChainInfo, ChainstateConfig,
-    ChainstateError, ChainstateEvent, NonZeroPoolBalances,
+    ChainstateError, ChainstateEvent, MainchainChangesHandler, NonZeroPoolBalances, ReorgEvent,
 };
 
 impl<T: Deref + DerefMut + Send + Sync> ChainstateInterface for T
@@ -56,6 +56,10 @@ where
         self.deref_mut().subscribe_to_rpc_events()
     }
 
+    fn subscribe_to_mainchain_changes(&mut self, handler: MainchainChangesHandler) {
+        self.deref_mut().subscribe_to_mainchain_changes(handler)
+    }
+
     fn process_block(
         &mut self,
         block: Block,
@@ -72,6 +76,10 @@ where
         self.deref_mut().reset_block_failure_flags(block_id)
     }
 
+    fn reprocess_blocks_from(&mut self, height: BlockHeight) -> Result<(), ChainstateError> {
+        self.deref_mut().reprocess_blocks_from(height)
+    }
+
     fn preliminary_block_check(&self, block: Block) -> Result<Block, ChainstateError> {
         self.deref().preliminary_block_check(block)
     }
@@ -304,6 +312,14 @@ where
         self.deref().get_block_id_tree_as_list()
     }
 
+    fn get_orphan_tips(&self) -> Result<Vec<Id<Block>>, ChainstateError> {
+        self.deref().get_orphan_tips()
+    }
+
+    fn get_recent_reorgs(&self) -> Vec<ReorgEvent> {
+        self.deref().get_recent_reorgs()
+    }
+
     fn import_bootstrap_stream<'a>(
         &mut self,
         reader: std::io::BufReader<Box<dyn std::io::Read + Send + 'a>>,
@@ -319,6 +335,25 @@ where
         self.deref().export_bootstrap_stream(writer, include_orphans)
     }
 
+    fn export_utxo_snapshot<'a>(
+        &mut self,
+        height: BlockHeight,
+        writer: std::io::BufWriter<Box<dyn std::io::Write + Send + 'a>>,
+    ) -> Result<H256, ChainstateError> {
+        self.deref_mut().export_utxo_snapshot(height, writer)
+    }
+
+    fn import_utxo_snapshot<'a>(
+        &mut self,
+        reader: std::io::BufReader<Box<dyn std::io::Read + Send + 'a>>,
+    ) -> Result<(), ChainstateError> {
+        self.deref_mut().import_utxo_snapshot(reader)
+    }
+
+    fn get_utxo_set_commitment(&self) -> Result<H256, ChainstateError> {
+        self.deref().get_utxo_set_commitment()
+    }
+
     fn utxo(&self, outpoint: &UtxoOutPoint) -> Result<Option<Utxo>, ChainstateError> {
         self.deref().utxo(outpoint)
     }
@@ -472,6 +507,8 @@ mod tests {
                 min_max_bootstrap_import_buffer_sizes: Default::default(),
                 max_tip_age: Default::default(),
                 enable_heavy_checks: Some(true),
+                max_transactions_per_block: None,
+                pruning_depth: None,
             };
             let chainstate_storage = Store::new_empty().unwrap();
 
@@ -16,3 +16,35 @@
 pub mod chainstate_interface;
 pub mod chainstate_interface_impl;
 pub mod chainstate_interface_impl_delegation;
+
+use chainstate_types::PropertyQueryError;
+use common::{chain::GenBlock, primitives::Id};
+
+use crate::{ChainFork, ChainstateError};
+
+use self::chainstate_interface::ChainstateInterface;
+
+/// Collect the known forks of the main chain, one per orphan tip.
+pub fn get_chain_forks<C: ChainstateInterface + ?Sized>(
+    chainstate: &C,
+) -> Result<Vec<ChainFork>, ChainstateError> {
+    let main_tip = chainstate.get_best_block_id()?;
+
+    chainstate
+        .get_orphan_tips()?
+        .into_iter()
+        .map(|orphan_tip| {
+            let orphan_tip_id = Id::<GenBlock>::from(orphan_tip);
+            let (_, fork_point_height) = chainstate
+                .last_common_ancestor_by_id(&orphan_tip_id, &main_tip)?
+                .ok_or(ChainstateError::FailedToReadProperty(
+                    PropertyQueryError::BlockIndexNotFound(orphan_tip_id),
+                ))?;
+            Ok(ChainFork {
+                fork_point_height,
+                orphan_tip,
+                main_tip,
+            })
+        })
+        .collect()
+}
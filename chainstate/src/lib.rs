@@ -25,7 +25,7 @@ use chainstate_interface::ChainstateInterface;
 use chainstate_interface_impl::ChainstateInterfaceImpl;
 use common::{
     chain::{Block, ChainConfig, GenBlock},
-    primitives::{BlockHeight, Id},
+    primitives::{BlockDistance, BlockHeight, Id},
     time_getter::TimeGetter,
 };
 use detail::{bootstrap::BootstrapError, Chainstate};
@@ -33,12 +33,14 @@ use interface::chainstate_interface_impl;
 
 pub use crate::{
     config::{ChainstateConfig, MaxTipAge},
+    detail::utxo_commitment::compute_utxo_commitment,
+    detail::utxo_snapshot::UtxoSnapshotError,
     detail::{
         ban_score, block_invalidation::BlockInvalidatorError, calculate_median_time_past,
         calculate_median_time_past_from_blocktimestamps, BlockError, BlockProcessingErrorClass,
-        BlockProcessingErrorClassification, BlockSource, ChainInfo, CheckBlockError,
+        BlockProcessingErrorClassification, BlockSource, ChainFork, ChainInfo, CheckBlockError,
         CheckBlockTransactionsError, ConnectTransactionError, IOPolicyError, InitializationError,
-        Locator, NonZeroPoolBalances, OrphanCheckError, SpendStakeError,
+        Locator, NonZeroPoolBalances, OrphanCheckError, ReorgEvent, SpendStakeError,
         StorageCompatibilityCheckError, TokenIssuanceError, TokensError,
         TransactionVerifierStorageError, MEDIAN_TIME_SPAN,
     },
@@ -46,7 +48,7 @@ pub use crate::{
 pub use chainstate_types::{BlockIndex, GenBlockIndex, PropertyQueryError};
 pub use constraints_value_accumulator;
 pub use detail::tx_verification_strategy::*;
-pub use interface::{chainstate_interface, chainstate_interface_impl_delegation};
+pub use interface::{chainstate_interface, chainstate_interface_impl_delegation, get_chain_forks};
 pub use tx_verifier;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -54,6 +56,17 @@ pub enum ChainstateEvent {
     NewTip(Id<Block>, BlockHeight),
 }
 
+/// A block that was removed from or added to the main chain
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MainchainChange {
+    Disconnected(Id<Block>, BlockHeight),
+    Connected(Id<Block>, BlockHeight),
+}
+
+/// Called with the blocks disconnected from and connected to the main chain by a committed
+/// db transaction, in the order in which that happened.
+pub type MainchainChangesHandler = Arc<dyn Fn(Vec<MainchainChange>) + Send + Sync>;
+
 /// A struct that will be used to print ChainstateEvent when it becomes a part of tracing's span.
 /// Here we favor compactness of the info over its precision, so ids are printed in their
 /// shortened form.
@@ -81,6 +94,10 @@ pub enum ChainstateError {
     BootstrapError(#[from] BootstrapError),
     #[error("Error invoking block invalidator: {0}")]
     BlockInvalidatorError(#[from] BlockInvalidatorError),
+    #[error("Cannot prune block bodies to depth {0}, the minimum is {1}")]
+    CannotPruneToDepth(u64, BlockDistance),
+    #[error("UTXO snapshot error: {0}")]
+    UtxoSnapshotError(#[from] UtxoSnapshotError),
 }
 
 pub type ChainstateSubsystem = Box<dyn ChainstateInterface>;
@@ -25,7 +25,7 @@ use std::{
 };
 
 use self::types::{block::RpcBlock, event::RpcEvent};
-use crate::{Block, BlockSource, ChainInfo, GenBlock};
+use crate::{Block, BlockSource, ChainFork, ChainInfo, GenBlock, ReorgEvent};
 use chainstate_types::BlockIndex;
 use common::{
     address::{dehexify::to_dehexified_json, Address},
@@ -33,12 +33,14 @@ use common::{
         tokens::{RPCTokenInfo, TokenId},
         ChainConfig, DelegationId, PoolId, TxOutput,
     },
-    primitives::{Amount, BlockHeight, Id},
+    primitives::{Amount, BlockHeight, Id, H256},
 };
 use rpc::{subscription, RpcResult};
 use serialization::hex_encoded::HexEncoded;
 pub use types::{
-    input::RpcUtxoOutpoint, output::RpcTxOutput, signed_transaction::RpcSignedTransaction,
+    input::{RpcOutPointSourceId, RpcUtxoOutpoint},
+    output::RpcTxOutput,
+    signed_transaction::RpcSignedTransaction,
 };
 
 #[rpc::describe]
@@ -90,6 +92,13 @@ trait ChainstateRpc {
     #[method(name = "get_utxo")]
     async fn get_utxo(&self, outpoint: RpcUtxoOutpoint) -> RpcResult<Option<TxOutput>>;
 
+    /// Returns the merkle root of the current UTXO set, which can be compared with the one
+    /// of another node, e.g. to verify a UTXO snapshot.
+    ///
+    /// This goes over the whole UTXO set, so it's expensive.
+    #[method(name = "utxo_set_commitment")]
+    async fn utxo_set_commitment(&self) -> RpcResult<H256>;
+
     /// Submit a block to be included in the blockchain.
     ///
     /// Note that the submission does not circumvent any validation process.
@@ -128,6 +137,20 @@ trait ChainstateRpc {
         second_block: Id<GenBlock>,
     ) -> RpcResult<Option<(Id<GenBlock>, BlockHeight)>>;
 
+    /// Returns the known forks of the mainchain.
+    ///
+    /// For every tip of a branch that is not part of the mainchain, the height at which the branch
+    /// split off the mainchain is returned, along with the current mainchain tip.
+    #[method(name = "fork_detection")]
+    async fn fork_detection(&self) -> RpcResult<Vec<ChainFork>>;
+
+    /// Returns the most recent reorgs since the node was started, from the oldest to the newest.
+    ///
+    /// For every reorg, the number of disconnected blocks of the old mainchain is returned,
+    /// along with the old and the new tips and the time of the reorg. At most 100 reorgs are kept.
+    #[method(name = "recent_reorgs")]
+    async fn recent_reorgs(&self) -> RpcResult<Vec<ReorgEvent>>;
+
     /// Returns the balance of the pool associated with the given pool id.
     ///
     /// The balance contains both delegated balance and staker balance.
@@ -142,6 +165,12 @@ trait ChainstateRpc {
     #[method(name = "staker_balance")]
     async fn staker_balance(&self, pool_address: String) -> RpcResult<Option<Amount>>;
 
+    /// Returns the number of delegations in the pool associated with the given pool address.
+    ///
+    /// Returns `None` (null) if the pool is not found.
+    #[method(name = "stake_pool_delegations_count")]
+    async fn stake_pool_delegations_count(&self, pool_address: String) -> RpcResult<Option<usize>>;
+
     /// Given a pool defined by a pool address, and a delegation address,
     /// returns the amount of coins owned by that delegation in that pool.
     #[method(name = "delegation_share")]
@@ -262,6 +291,10 @@ impl ChainstateRpcServer for super::ChainstateHandle {
         )
     }
 
+    async fn utxo_set_commitment(&self) -> RpcResult<H256> {
+        rpc::handle_result(self.call(move |this| this.get_utxo_set_commitment()).await)
+    }
+
     async fn submit_block(&self, block: HexEncoded<Block>) -> RpcResult<()> {
         let res = self
             .call_mut(move |this| this.process_block(block.take(), BlockSource::Local))
@@ -303,6 +336,14 @@ impl ChainstateRpcServer for super::ChainstateHandle {
         )
     }
 
+    async fn fork_detection(&self) -> RpcResult<Vec<ChainFork>> {
+        rpc::handle_result(self.call(move |this| crate::get_chain_forks(this)).await)
+    }
+
+    async fn recent_reorgs(&self) -> RpcResult<Vec<ReorgEvent>> {
+        rpc::handle_result(self.call(move |this| this.get_recent_reorgs()).await)
+    }
+
     async fn stake_pool_balance(&self, pool_address: String) -> RpcResult<Option<Amount>> {
         rpc::handle_result(
             self.call(move |this| {
@@ -332,6 +373,25 @@ impl ChainstateRpcServer for super::ChainstateHandle {
         )
     }
 
+    async fn stake_pool_delegations_count(&self, pool_address: String) -> RpcResult<Option<usize>> {
+        rpc::handle_result(
+            self.call(move |this| {
+                let chain_config = this.get_chain_config();
+                let pool_id =
+                    dynamize_err(Address::<PoolId>::from_string(chain_config, pool_address))?
+                        .into_object();
+
+                if dynamize_err(this.get_stake_pool_data(pool_id))?.is_none() {
+                    return Ok(None);
+                }
+
+                dynamize_err(this.get_stake_pool_delegations_shares(pool_id))
+                    .map(|shares| Some(shares.map_or(0, |shares| shares.len())))
+            })
+            .await,
+        )
+    }
+
     async fn delegation_share(
         &self,
         pool_address: String,
@@ -482,6 +542,10 @@ mod test {
 
             let res: RpcCallResult<Value> = rpc.call("chainstate_block_id_at_height", [1u32]).await;
             assert!(matches!(res, Ok(Value::Null)));
+
+            let res: RpcCallResult<Value> =
+                rpc.call("chainstate_utxo_set_commitment", [(); 0]).await;
+            assert!(matches!(res, Ok(Value::String(hash)) if hash.len() == 64));
         })
         .await
     }
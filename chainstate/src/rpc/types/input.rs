@@ -71,6 +71,15 @@ pub enum RpcOutPointSourceId {
     BlockReward { block_id: Id<GenBlock> },
 }
 
+impl From<OutPointSourceId> for RpcOutPointSourceId {
+    fn from(source_id: OutPointSourceId) -> Self {
+        match source_id {
+            OutPointSourceId::Transaction(tx_id) => Self::Transaction { tx_id },
+            OutPointSourceId::BlockReward(block_id) => Self::BlockReward { block_id },
+        }
+    }
+}
+
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, rpc_description::HasValueHint)]
 pub struct RpcUtxoOutpoint {
     source_id: RpcOutPointSourceId,
@@ -79,14 +88,8 @@ pub struct RpcUtxoOutpoint {
 
 impl RpcUtxoOutpoint {
     pub fn new(outpoint: UtxoOutPoint) -> Self {
-        let source_id = match outpoint.source_id() {
-            OutPointSourceId::Transaction(tx_id) => RpcOutPointSourceId::Transaction { tx_id },
-            OutPointSourceId::BlockReward(block_id) => {
-                RpcOutPointSourceId::BlockReward { block_id }
-            }
-        };
         Self {
-            source_id,
+            source_id: outpoint.source_id().into(),
             index: outpoint.output_index(),
         }
     }
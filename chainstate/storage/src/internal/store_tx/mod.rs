@@ -54,6 +54,7 @@ mod well_known {
     declare_entry!(MagicBytes: chain::config::MagicBytes);
     declare_entry!(ChainType: String);
     declare_entry!(MinHeightForReorg: BlockHeight);
+    declare_entry!(SnapshotHeight: BlockHeight);
 }
 
 /// Read-only chainstate storage transaction
@@ -27,18 +27,18 @@ use common::{
     },
     primitives::{Amount, BlockHeight, Id, H256},
 };
-use orders_accounting::{OrdersAccountingStorageRead, OrdersAccountingUndo};
+use orders_accounting::{OrdersAccountingData, OrdersAccountingStorageRead, OrdersAccountingUndo};
 use pos_accounting::{
-    DelegationData, DeltaMergeUndo, PoSAccountingDeltaData, PoSAccountingStorageRead,
-    PoSAccountingUndo, PoolData,
+    DelegationData, DeltaMergeUndo, PoSAccountingData, PoSAccountingDeltaData,
+    PoSAccountingStorageRead, PoSAccountingUndo, PoolData,
 };
 use serialization::{Decode, Encode};
 use storage::MakeMapRef;
-use tokens_accounting::{TokenAccountingUndo, TokensAccountingStorageRead};
+use tokens_accounting::{TokenAccountingUndo, TokensAccountingData, TokensAccountingStorageRead};
 use utils::log_error;
 use utxo::{Utxo, UtxosBlockUndo, UtxosStorageRead};
 
-use crate::{BlockchainStorageRead, ChainstateStorageVersion};
+use crate::{BlockchainStorageRead, ChainstateStorageVersion, SnapshotStateData};
 
 use super::well_known;
 
@@ -85,6 +85,13 @@ mod private {
     }
 }
 
+// Read a whole db map into a BTreeMap
+macro_rules! read_map {
+    ($map:expr) => {
+        $map.prefix_iter_decoded(&())?.collect::<BTreeMap<_, _>>()
+    };
+}
+
 /// Blockchain data storage transaction
 impl<'st, B: storage::Backend> BlockchainStorageRead for super::StoreTxRo<'st, B> {
     #[log_error]
@@ -141,6 +148,11 @@ impl<'st, B: storage::Backend> BlockchainStorageRead for super::StoreTxRo<'st, B
         self.read_value::<well_known::MinHeightForReorg>()
     }
 
+    #[log_error]
+    fn get_snapshot_height(&self) -> crate::Result<Option<BlockHeight>> {
+        self.read_value::<well_known::SnapshotHeight>()
+    }
+
     #[log_error]
     fn get_block_id_by_height(&self, height: &BlockHeight) -> crate::Result<Option<Id<GenBlock>>> {
         self.read::<db::DBBlockByHeight, _, _>(height)
@@ -244,6 +256,76 @@ impl<'st, B: storage::Backend> BlockchainStorageRead for super::StoreTxRo<'st, B
         let items = map.prefix_iter_decoded(&())?;
         Ok(items.collect::<BTreeMap<_, _>>())
     }
+
+    #[log_error]
+    fn get_utxo_set(&self) -> crate::Result<BTreeMap<UtxoOutPoint, Utxo>> {
+        let map = self.0.get::<db::DBUtxo, _>();
+        let items = map.prefix_iter_decoded(&())?;
+        Ok(items.collect::<BTreeMap<_, _>>())
+    }
+
+    #[log_error]
+    fn get_utxos_page(
+        &self,
+        start_after: Option<UtxoOutPoint>,
+        max_count: usize,
+    ) -> crate::Result<Vec<(UtxoOutPoint, Utxo)>> {
+        let map = self.0.get::<db::DBUtxo, _>();
+        let items = match start_after {
+            Some(start_after) => map
+                .greater_equal_iter_decoded(&start_after)?
+                .skip_while(|(outpoint, _)| *outpoint == start_after)
+                .take(max_count)
+                .collect(),
+            None => map.prefix_iter_decoded(&())?.take(max_count).collect(),
+        };
+        Ok(items)
+    }
+
+    #[log_error]
+    fn get_snapshot_state_data(&self) -> crate::Result<SnapshotStateData> {
+        Ok(SnapshotStateData {
+            epoch_data: read_map!(self.0.get::<db::DBEpochData, _>()),
+            accounting_epoch_deltas: read_map!(self.0.get::<db::DBAccountingEpochDelta, _>()),
+            accounting_epoch_undo_deltas: read_map!(self
+                .0
+                .get::<db::DBAccountingEpochDeltaUndo, _>()),
+            pos_accounting_data_tip: PoSAccountingData {
+                pool_data: read_map!(self.0.get::<db::DBAccountingPoolDataTip, _>()),
+                pool_balances: read_map!(self.0.get::<db::DBAccountingPoolBalancesTip, _>()),
+                pool_delegation_shares: read_map!(self
+                    .0
+                    .get::<db::DBAccountingPoolDelegationSharesTip, _>()),
+                delegation_balances: read_map!(self
+                    .0
+                    .get::<db::DBAccountingDelegationBalancesTip, _>()),
+                delegation_data: read_map!(self.0.get::<db::DBAccountingDelegationDataTip, _>()),
+            },
+            pos_accounting_data_sealed: PoSAccountingData {
+                pool_data: read_map!(self.0.get::<db::DBAccountingPoolDataSealed, _>()),
+                pool_balances: read_map!(self.0.get::<db::DBAccountingPoolBalancesSealed, _>()),
+                pool_delegation_shares: read_map!(self
+                    .0
+                    .get::<db::DBAccountingPoolDelegationSharesSealed, _>()),
+                delegation_balances: read_map!(self
+                    .0
+                    .get::<db::DBAccountingDelegationBalancesSealed, _>()),
+                delegation_data: read_map!(self.0.get::<db::DBAccountingDelegationDataSealed, _>()),
+            },
+            tokens_accounting_data: TokensAccountingData {
+                token_data: read_map!(self.0.get::<db::DBTokensData, _>()),
+                circulating_supply: read_map!(self.0.get::<db::DBTokensCirculatingSupply, _>()),
+            },
+            token_aux_data: read_map!(self.0.get::<db::DBTokensAuxData, _>()),
+            token_ids: read_map!(self.0.get::<db::DBIssuanceTxVsTokenId, _>()),
+            orders_accounting_data: OrdersAccountingData {
+                order_data: read_map!(self.0.get::<db::DBOrdersData, _>()),
+                ask_balances: read_map!(self.0.get::<db::DBOrdersAskBalances, _>()),
+                give_balances: read_map!(self.0.get::<db::DBOrdersGiveBalances, _>()),
+            },
+            account_nonces: read_map!(self.0.get::<db::DBAccountNonceCount, _>()),
+        })
+    }
 }
 
 impl<'st, B: storage::Backend> EpochStorageRead for super::StoreTxRo<'st, B> {
@@ -453,6 +535,11 @@ impl<'st, B: storage::Backend> BlockchainStorageRead for super::StoreTxRw<'st, B
         self.read_value::<well_known::MinHeightForReorg>()
     }
 
+    #[log_error]
+    fn get_snapshot_height(&self) -> crate::Result<Option<BlockHeight>> {
+        self.read_value::<well_known::SnapshotHeight>()
+    }
+
     #[log_error]
     fn get_block_id_by_height(&self, height: &BlockHeight) -> crate::Result<Option<Id<GenBlock>>> {
         self.read::<db::DBBlockByHeight, _, _>(height)
@@ -561,6 +648,81 @@ impl<'st, B: storage::Backend> BlockchainStorageRead for super::StoreTxRw<'st, B
         let items = map.prefix_iter_decoded(&())?;
         Ok(items.collect::<BTreeMap<_, _>>())
     }
+
+    // TODO: same as above.
+    #[log_error]
+    fn get_utxo_set(&self) -> crate::Result<BTreeMap<UtxoOutPoint, Utxo>> {
+        let map = self.get_map::<db::DBUtxo, _>()?;
+        let items = map.prefix_iter_decoded(&())?;
+        Ok(items.collect::<BTreeMap<_, _>>())
+    }
+
+    // TODO: same as above.
+    #[log_error]
+    fn get_utxos_page(
+        &self,
+        start_after: Option<UtxoOutPoint>,
+        max_count: usize,
+    ) -> crate::Result<Vec<(UtxoOutPoint, Utxo)>> {
+        let map = self.get_map::<db::DBUtxo, _>()?;
+        let items = match start_after {
+            Some(start_after) => map
+                .greater_equal_iter_decoded(&start_after)?
+                .skip_while(|(outpoint, _)| *outpoint == start_after)
+                .take(max_count)
+                .collect(),
+            None => map.prefix_iter_decoded(&())?.take(max_count).collect(),
+        };
+        Ok(items)
+    }
+
+    // TODO: same as above.
+    #[log_error]
+    fn get_snapshot_state_data(&self) -> crate::Result<SnapshotStateData> {
+        Ok(SnapshotStateData {
+            epoch_data: read_map!(self.get_map::<db::DBEpochData, _>()?),
+            accounting_epoch_deltas: read_map!(self.get_map::<db::DBAccountingEpochDelta, _>()?),
+            accounting_epoch_undo_deltas: read_map!(
+                self.get_map::<db::DBAccountingEpochDeltaUndo, _>()?
+            ),
+            pos_accounting_data_tip: PoSAccountingData {
+                pool_data: read_map!(self.get_map::<db::DBAccountingPoolDataTip, _>()?),
+                pool_balances: read_map!(self.get_map::<db::DBAccountingPoolBalancesTip, _>()?),
+                pool_delegation_shares: read_map!(
+                    self.get_map::<db::DBAccountingPoolDelegationSharesTip, _>()?
+                ),
+                delegation_balances: read_map!(
+                    self.get_map::<db::DBAccountingDelegationBalancesTip, _>()?
+                ),
+                delegation_data: read_map!(self.get_map::<db::DBAccountingDelegationDataTip, _>()?),
+            },
+            pos_accounting_data_sealed: PoSAccountingData {
+                pool_data: read_map!(self.get_map::<db::DBAccountingPoolDataSealed, _>()?),
+                pool_balances: read_map!(self.get_map::<db::DBAccountingPoolBalancesSealed, _>()?),
+                pool_delegation_shares: read_map!(self
+                    .get_map::<db::DBAccountingPoolDelegationSharesSealed, _>(
+                )?),
+                delegation_balances: read_map!(
+                    self.get_map::<db::DBAccountingDelegationBalancesSealed, _>()?
+                ),
+                delegation_data: read_map!(
+                    self.get_map::<db::DBAccountingDelegationDataSealed, _>()?
+                ),
+            },
+            tokens_accounting_data: TokensAccountingData {
+                token_data: read_map!(self.get_map::<db::DBTokensData, _>()?),
+                circulating_supply: read_map!(self.get_map::<db::DBTokensCirculatingSupply, _>()?),
+            },
+            token_aux_data: read_map!(self.get_map::<db::DBTokensAuxData, _>()?),
+            token_ids: read_map!(self.get_map::<db::DBIssuanceTxVsTokenId, _>()?),
+            orders_accounting_data: OrdersAccountingData {
+                order_data: read_map!(self.get_map::<db::DBOrdersData, _>()?),
+                ask_balances: read_map!(self.get_map::<db::DBOrdersAskBalances, _>()?),
+                give_balances: read_map!(self.get_map::<db::DBOrdersGiveBalances, _>()?),
+            },
+            account_nonces: read_map!(self.get_map::<db::DBAccountNonceCount, _>()?),
+        })
+    }
 }
 
 impl<'st, B: storage::Backend> EpochStorageRead for super::StoreTxRw<'st, B> {
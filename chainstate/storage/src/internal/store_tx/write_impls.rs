@@ -82,6 +82,11 @@ impl<'st, B: storage::Backend> BlockchainStorageWrite for StoreTxRw<'st, B> {
         self.write_value::<well_known::MinHeightForReorg>(&height)
     }
 
+    #[log_error]
+    fn set_snapshot_height(&mut self, height: BlockHeight) -> crate::Result<()> {
+        self.write_value::<well_known::SnapshotHeight>(&height)
+    }
+
     #[log_error]
     fn set_block_id_at_height(
         &mut self,
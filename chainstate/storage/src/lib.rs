@@ -24,7 +24,7 @@ pub mod schema;
 use std::collections::{BTreeMap, BTreeSet};
 
 use chainstate_types::{
-    BlockIndex, EpochStorageRead, EpochStorageWrite, SealedStorageTag, TipStorageTag,
+    BlockIndex, EpochData, EpochStorageRead, EpochStorageWrite, SealedStorageTag, TipStorageTag,
 };
 use common::{
     chain::{
@@ -32,21 +32,24 @@ use common::{
         config::{EpochIndex, MagicBytes},
         tokens::{TokenAuxiliaryData, TokenId},
         transaction::Transaction,
-        AccountNonce, AccountType, Block, GenBlock,
+        AccountNonce, AccountType, Block, GenBlock, UtxoOutPoint,
     },
     primitives::{BlockHeight, Id},
 };
 use orders_accounting::{
-    OrdersAccountingStorageRead, OrdersAccountingStorageWrite, OrdersAccountingUndo,
+    OrdersAccountingData, OrdersAccountingStorageRead, OrdersAccountingStorageWrite,
+    OrdersAccountingUndo,
 };
 use pos_accounting::{
-    DeltaMergeUndo, PoSAccountingDeltaData, PoSAccountingStorageRead, PoSAccountingStorageWrite,
-    PoSAccountingUndo,
+    DeltaMergeUndo, PoSAccountingData, PoSAccountingDeltaData, PoSAccountingStorageRead,
+    PoSAccountingStorageWrite, PoSAccountingUndo,
 };
+use serialization::{Decode, Encode};
 use tokens_accounting::{
-    TokenAccountingUndo, TokensAccountingStorageRead, TokensAccountingStorageWrite,
+    TokenAccountingUndo, TokensAccountingData, TokensAccountingStorageRead,
+    TokensAccountingStorageWrite,
 };
-use utxo::{UtxosBlockUndo, UtxosStorageRead, UtxosStorageWrite};
+use utxo::{Utxo, UtxosBlockUndo, UtxosStorageRead, UtxosStorageWrite};
 
 pub use internal::{ChainstateStorageVersion, Store};
 
@@ -100,6 +103,10 @@ pub trait BlockchainStorageRead:
     /// Get the height below which reorgs should not be allowed.
     fn get_min_height_with_allowed_reorg(&self) -> crate::Result<Option<BlockHeight>>;
 
+    /// Get the height of the UTXO snapshot the chainstate was initialized from, if any.
+    /// Mainchain blocks up to this height have no bodies.
+    fn get_snapshot_height(&self) -> crate::Result<Option<BlockHeight>>;
+
     /// Get mainchain block by its height
     fn get_block_id_by_height(&self, height: &BlockHeight) -> crate::Result<Option<Id<GenBlock>>>;
 
@@ -159,6 +166,33 @@ pub trait BlockchainStorageRead:
     /// Get the entire mainchain-block-by-height map as BTreeMap. This is used in the chainstate's
     /// "heavy" consistency checks.
     fn get_block_by_height_map(&self) -> crate::Result<BTreeMap<BlockHeight, Id<GenBlock>>>;
+    /// Get the entire utxo set as BTreeMap. This is used to compute the utxo set commitment.
+    fn get_utxo_set(&self) -> crate::Result<BTreeMap<UtxoOutPoint, Utxo>>;
+    /// Get at most `max_count` utxos that follow `start_after` in the storage order (or the first
+    /// ones if it's `None`). This is used when exporting a snapshot, so that the utxo set doesn't
+    /// have to be kept in memory.
+    fn get_utxos_page(
+        &self,
+        start_after: Option<UtxoOutPoint>,
+        max_count: usize,
+    ) -> crate::Result<Vec<(UtxoOutPoint, Utxo)>>;
+    /// Get all the state data except for the utxo set. This is used when exporting a snapshot.
+    fn get_snapshot_state_data(&self) -> crate::Result<SnapshotStateData>;
+}
+
+/// The chainstate state, except for the blocks, the undo data and the utxo set.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct SnapshotStateData {
+    pub epoch_data: BTreeMap<EpochIndex, EpochData>,
+    pub accounting_epoch_deltas: BTreeMap<EpochIndex, PoSAccountingDeltaData>,
+    pub accounting_epoch_undo_deltas: BTreeMap<EpochIndex, DeltaMergeUndo>,
+    pub pos_accounting_data_tip: PoSAccountingData,
+    pub pos_accounting_data_sealed: PoSAccountingData,
+    pub tokens_accounting_data: TokensAccountingData,
+    pub token_aux_data: BTreeMap<TokenId, TokenAuxiliaryData>,
+    pub token_ids: BTreeMap<Id<Transaction>, TokenId>,
+    pub orders_accounting_data: OrdersAccountingData,
+    pub account_nonces: BTreeMap<AccountType, AccountNonce>,
 }
 
 /// Modifying operations on persistent blockchain data
@@ -198,6 +232,9 @@ pub trait BlockchainStorageWrite:
     /// Set the height below which reorgs should not be allowed.
     fn set_min_height_with_allowed_reorg(&mut self, height: BlockHeight) -> crate::Result<()>;
 
+    /// Set the height of the UTXO snapshot the chainstate was initialized from.
+    fn set_snapshot_height(&mut self, height: BlockHeight) -> crate::Result<()>;
+
     /// Set the mainchain block at given height to be given block.
     fn set_block_id_at_height(
         &mut self,
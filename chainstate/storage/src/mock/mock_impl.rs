@@ -45,7 +45,7 @@ use super::mock_impl_accounting::{
     PoSAccountingStorageWriteTip,
 };
 
-use crate::ChainstateStorageVersion;
+use crate::{ChainstateStorageVersion, SnapshotStateData};
 
 mockall::mock! {
     /// A mock object for blockchain storage
@@ -63,6 +63,7 @@ mockall::mock! {
         fn get_block_header(&self, id: Id<Block>) -> crate::Result<Option<SignedBlockHeader>>;
 
         fn get_min_height_with_allowed_reorg(&self) -> crate::Result<Option<BlockHeight>>;
+        fn get_snapshot_height(&self) -> crate::Result<Option<BlockHeight>>;
 
         fn get_block_id_by_height(
             &self,
@@ -107,6 +108,13 @@ mockall::mock! {
         fn get_block_map_keys(&self) -> crate::Result<BTreeSet<Id<Block>>>;
         fn get_block_index_map(&self) -> crate::Result<BTreeMap<Id<Block>, BlockIndex>>;
         fn get_block_by_height_map(&self) -> crate::Result<BTreeMap<BlockHeight, Id<GenBlock>>>;
+        fn get_utxo_set(&self) -> crate::Result<BTreeMap<UtxoOutPoint, Utxo>>;
+        fn get_utxos_page(
+            &self,
+            start_after: Option<UtxoOutPoint>,
+            max_count: usize,
+        ) -> crate::Result<Vec<(UtxoOutPoint, Utxo)>>;
+        fn get_snapshot_state_data(&self) -> crate::Result<SnapshotStateData>;
     }
 
     impl EpochStorageRead for Store {
@@ -187,6 +195,7 @@ mockall::mock! {
         fn del_block(&mut self, id: Id<Block>) -> crate::Result<()>;
 
         fn set_min_height_with_allowed_reorg(&mut self, height: BlockHeight) -> crate::Result<()>;
+        fn set_snapshot_height(&mut self, height: BlockHeight) -> crate::Result<()>;
 
         fn set_block_id_at_height(
             &mut self,
@@ -368,6 +377,7 @@ mockall::mock! {
         fn get_block_header(&self, id: Id<Block>) -> crate::Result<Option<SignedBlockHeader>>;
 
         fn get_min_height_with_allowed_reorg(&self) -> crate::Result<Option<BlockHeight>>;
+        fn get_snapshot_height(&self) -> crate::Result<Option<BlockHeight>>;
 
         fn get_block_id_by_height(
             &self,
@@ -404,6 +414,13 @@ mockall::mock! {
         fn get_block_map_keys(&self) -> crate::Result<BTreeSet<Id<Block>>>;
         fn get_block_index_map(&self) -> crate::Result<BTreeMap<Id<Block>, BlockIndex>>;
         fn get_block_by_height_map(&self) -> crate::Result<BTreeMap<BlockHeight, Id<GenBlock>>>;
+        fn get_utxo_set(&self) -> crate::Result<BTreeMap<UtxoOutPoint, Utxo>>;
+        fn get_utxos_page(
+            &self,
+            start_after: Option<UtxoOutPoint>,
+            max_count: usize,
+        ) -> crate::Result<Vec<(UtxoOutPoint, Utxo)>>;
+        fn get_snapshot_state_data(&self) -> crate::Result<SnapshotStateData>;
     }
 
     impl EpochStorageRead for StoreTxRo {
@@ -495,6 +512,7 @@ mockall::mock! {
         fn get_block_header(&self, id: Id<Block>) -> crate::Result<Option<SignedBlockHeader>>;
 
         fn get_min_height_with_allowed_reorg(&self) -> crate::Result<Option<BlockHeight>>;
+        fn get_snapshot_height(&self) -> crate::Result<Option<BlockHeight>>;
 
         fn get_block_id_by_height(
             &self,
@@ -530,6 +548,13 @@ mockall::mock! {
         fn get_block_map_keys(&self) -> crate::Result<BTreeSet<Id<Block>>>;
         fn get_block_index_map(&self) -> crate::Result<BTreeMap<Id<Block>, BlockIndex>>;
         fn get_block_by_height_map(&self) -> crate::Result<BTreeMap<BlockHeight, Id<GenBlock>>>;
+        fn get_utxo_set(&self) -> crate::Result<BTreeMap<UtxoOutPoint, Utxo>>;
+        fn get_utxos_page(
+            &self,
+            start_after: Option<UtxoOutPoint>,
+            max_count: usize,
+        ) -> crate::Result<Vec<(UtxoOutPoint, Utxo)>>;
+        fn get_snapshot_state_data(&self) -> crate::Result<SnapshotStateData>;
     }
 
     impl EpochStorageRead for StoreTxRw {
@@ -610,6 +635,7 @@ mockall::mock! {
         fn del_block(&mut self, id: Id<Block>) -> crate::Result<()>;
 
         fn set_min_height_with_allowed_reorg(&mut self, height: BlockHeight) -> crate::Result<()>;
+        fn set_snapshot_height(&mut self, height: BlockHeight) -> crate::Result<()>;
 
         fn set_block_id_at_height(
             &mut self,
@@ -13,7 +13,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
 
 use chainstate_storage::{
     BlockchainStorageRead, BlockchainStorageWrite, TransactionRw, Transactional,
@@ -32,7 +35,10 @@ use crate::{
     },
     BlockBuilder, TestChainstate, TestFrameworkBuilder, TestStore,
 };
-use chainstate::{chainstate_interface::ChainstateInterface, BlockSource, ChainstateError};
+use chainstate::{
+    chainstate_interface::ChainstateInterface, BlockSource, ChainstateError, ChainstateEvent,
+    MainchainChange,
+};
 use chainstate_types::{BlockIndex, BlockStatus, GenBlockIndex};
 use common::{
     chain::{
@@ -60,10 +66,41 @@ pub struct TestFramework {
     // All pools from the tip that can be used for staking
     pub staking_pools: StakingPools,
     pub key_manager: KeyManager,
+
+    // The `NewTip` events emitted by the chainstate
+    pub(crate) new_tip_events: Arc<Mutex<Vec<(Id<Block>, BlockHeight)>>>,
+    // The main chain changes reported by the chainstate that haven't been taken yet
+    pub(crate) mainchain_changes: Arc<Mutex<Vec<MainchainChange>>>,
 }
 
 pub type BlockOutputs = BTreeMap<OutPointSourceId, Vec<TxOutput>>;
 
+/// Subscribe to the chainstate events, collecting the new tips into the returned list.
+pub(crate) fn subscribe_to_new_tip_events(
+    chainstate: &mut TestChainstate,
+) -> Arc<Mutex<Vec<(Id<Block>, BlockHeight)>>> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_clone = Arc::clone(&events);
+    chainstate.subscribe_to_subsystem_events(Arc::new(move |event| match event {
+        ChainstateEvent::NewTip(block_id, block_height) => {
+            events_clone.lock().unwrap().push((block_id, block_height));
+        }
+    }));
+    events
+}
+
+/// Subscribe to the main chain changes, collecting them into the returned list.
+pub(crate) fn subscribe_to_mainchain_changes(
+    chainstate: &mut TestChainstate,
+) -> Arc<Mutex<Vec<MainchainChange>>> {
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let changes_clone = Arc::clone(&changes);
+    chainstate.subscribe_to_mainchain_changes(Arc::new(move |new_changes| {
+        changes_clone.lock().unwrap().extend(new_changes);
+    }));
+    changes
+}
+
 impl TestFramework {
     /// Creates a new test framework instance using a builder api.
     pub fn builder(rng: &mut (impl Rng + CryptoRng)) -> TestFrameworkBuilder {
@@ -208,6 +245,31 @@ impl TestFramework {
         result
     }
 
+    /// Processes the given block and returns the blocks that the chainstate has disconnected
+    /// from and connected to the main chain while doing so, in the order in which that happened.
+    pub fn process_block_with_mainchain_changes(
+        &mut self,
+        block: Block,
+        source: BlockSource,
+    ) -> Result<Vec<MainchainChange>, ChainstateError> {
+        self.take_mainchain_changes();
+        self.process_block(block, source)?;
+        Ok(self.take_mainchain_changes())
+    }
+
+    /// Take the main chain changes reported by the chainstate since the last call.
+    pub fn take_mainchain_changes(&self) -> Vec<MainchainChange> {
+        std::mem::take(&mut *self.mainchain_changes.lock().unwrap())
+    }
+
+    /// Check that the chainstate has emitted exactly the specified `NewTip` events
+    /// since the framework was created.
+    #[track_caller]
+    pub fn assert_events(&self, expected: &[(Id<Block>, BlockHeight)]) {
+        self.chainstate.wait_for_all_events();
+        assert_eq!(self.new_tip_events.lock().unwrap().as_slice(), expected);
+    }
+
     /// Create and process a given amount of blocks. Return the ids of the produced blocks.
     ///
     /// Each block contains a single transaction that spends a random amount from the previous
@@ -398,8 +460,8 @@ impl TestFramework {
             GenBlockId::Block(ref id) => {
                 // Now check consistency of get_block_index_for_any_block and get_block_index_for_persisted_block
                 // as well as get_block_index_for_any_block and get_gen_block_index_for_any_block.
-                // Also check that a block index has the persistence flag set iff the corresponding block data
-                // is in the db (note that this part is somewhat redundant, because the chainstate consistency
+                // Also check that a block index has the persistence flag set and its body isn't pruned
+                // iff the corresponding block data is in the db (note that this part is somewhat redundant, because the chainstate consistency
                 // checks also verify this; but this function will be called more often, so at least
                 // it has a chance to catch a problem earlier).
 
@@ -413,7 +475,8 @@ impl TestFramework {
                         persisted_block_index_opt.as_ref(),
                         any_block_index_opt.as_ref(),
                     );
-                    assert!(self.chainstate.get_block(*id).unwrap().is_some());
+                    let has_body = any_block_index_opt.as_ref().is_some_and(|idx| idx.has_body());
+                    assert_eq!(self.chainstate.get_block(*id).unwrap().is_some(), has_body);
                 } else {
                     assert_block_index_opt_identical_to(persisted_block_index_opt.as_ref(), None);
                     assert!(self.chainstate.get_block(*id).unwrap().is_none());
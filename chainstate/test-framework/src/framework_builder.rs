@@ -16,6 +16,7 @@
 use std::{collections::BTreeMap, sync::Arc};
 
 use crate::{
+    framework::{subscribe_to_mainchain_changes, subscribe_to_new_tip_events},
     key_manager::KeyManager,
     staking_pools::StakingPools,
     tx_verification_strategy::{
@@ -198,7 +199,7 @@ impl TestFrameworkBuilder {
     pub fn try_build(self) -> Result<TestFramework, chainstate::ChainstateError> {
         let (time_getter, time_value) = self.create_time_getter_and_value();
 
-        let chainstate = match self.tx_verification_strategy {
+        let mut chainstate = match self.tx_verification_strategy {
             TxVerificationStrategy::Default => chainstate::make_chainstate(
                 Arc::new(self.chain_config),
                 self.chainstate_config,
@@ -224,6 +225,8 @@ impl TestFrameworkBuilder {
                 time_getter.clone(),
             ),
         }?;
+        let new_tip_events = subscribe_to_new_tip_events(&mut chainstate);
+        let mainchain_changes = subscribe_to_mainchain_changes(&mut chainstate);
 
         let key_manager =
             KeyManager::new(self.staking_pools.staking_pools().values().map(|(pk, _, _)| pk));
@@ -236,6 +239,8 @@ impl TestFrameworkBuilder {
             time_value,
             staking_pools: self.staking_pools,
             key_manager,
+            new_tip_events,
+            mainchain_changes,
         })
     }
 
@@ -38,9 +38,10 @@ pub type TestChainstate = Box<dyn chainstate::chainstate_interface::ChainstateIn
 pub use {
     crate::utils::{
         anyonecanspend_address, create_chain_config_with_default_staking_pool,
+        create_chain_config_with_default_staking_pool_after_pow,
         create_chain_config_with_staking_pool, create_custom_genesis_with_stake_pool,
         create_stake_pool_data_with_all_reward_to_staker, empty_witness, get_output_value,
-        output_value_amount, pos_mine, produce_kernel_signature,
+        mine_n_blocks, output_value_amount, pos_mine, produce_kernel_signature,
     },
     block_builder::BlockBuilder,
     framework::TestFramework,
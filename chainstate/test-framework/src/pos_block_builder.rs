@@ -294,7 +294,22 @@ impl<'f> PoSBlockBuilder<'f> {
                 chainstate_types::GenBlockIndex::Block(block_index) => {
                     match block_index.block_header().header().consensus_data() {
                         ConsensusData::None | ConsensusData::PoW(_) => {
-                            unimplemented!()
+                            // The kernel can't be deduced from a non-PoS parent, so use the last
+                            // known kernel of the pool; if the pool hasn't staked yet, it must
+                            // come from genesis.
+                            let pool_id = self.staking_pool.unwrap();
+                            self.framework
+                                .staking_pools
+                                .staking_pools()
+                                .get(&pool_id)
+                                .map(|(_, _, kernel_outpoint)| kernel_outpoint.clone())
+                                .or_else(|| {
+                                    find_create_pool_tx_in_genesis(
+                                        &self.framework.genesis(),
+                                        &pool_id,
+                                    )
+                                })
+                                .unwrap_or_else(|| panic!("No kernel input for pool {pool_id}"))
                         }
                         ConsensusData::PoS(_) => {
                             UtxoOutPoint::new(parent_block_index.block_id().into(), 0)
@@ -13,11 +13,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use crate::{
     framework::BlockOutputs, key_manager::KeyManager,
     signature_destination_getter::SignatureDestinationGetter, TestFramework,
 };
-use chainstate::{BlockIndex, GenBlockIndex};
+use chainstate::{BlockIndex, BlockSource, GenBlockIndex};
 use chainstate_storage::BlockchainStorageRead;
 use chainstate_types::{pos_randomness::PoSRandomness, TipStorageTag};
 use common::{
@@ -30,6 +32,7 @@ use common::{
             sighash::sighashtype::SigHashType,
         },
         stakelock::StakePoolData,
+        timelock::OutputTimeLock,
         Block, ChainConfig, CoinUnit, ConsensusUpgrade, Destination, GenBlock, Genesis,
         NetUpgrades, OutPointSourceId, PoSChainConfig, PoSChainConfigBuilder, PoolId, TxInput,
         TxOutput, UtxoOutPoint,
@@ -201,6 +204,44 @@ pub fn create_chain_config_with_default_staking_pool(
     (chain_config, genesis_pool_id)
 }
 
+/// Same as `create_chain_config_with_default_staking_pool`, but the chain starts with PoW blocks
+/// of the specified difficulty and switches to PoS at `pos_height`.
+pub fn create_chain_config_with_default_staking_pool_after_pow(
+    rng: &mut impl Rng,
+    staking_pk: PublicKey,
+    vrf_pk: VRFPublicKey,
+    pow_difficulty: Uint256,
+    pos_height: BlockHeight,
+) -> (ConfigBuilder, PoolId) {
+    assert!(pos_height > BlockHeight::new(1));
+
+    let (chain_config, genesis_pool_id) =
+        create_chain_config_with_default_staking_pool(rng, staking_pk, vrf_pk);
+
+    let upgrades = vec![
+        (BlockHeight::new(0), ConsensusUpgrade::IgnoreConsensus),
+        (
+            BlockHeight::new(1),
+            ConsensusUpgrade::PoW {
+                initial_difficulty: pow_difficulty.into(),
+            },
+        ),
+        (
+            pos_height,
+            ConsensusUpgrade::PoS {
+                initial_difficulty: Some(Uint256::MAX.into()),
+                config: PoSChainConfigBuilder::new_for_unit_test().build(),
+            },
+        ),
+    ];
+    let net_upgrades = NetUpgrades::initialize(upgrades).unwrap();
+
+    (
+        chain_config.consensus_upgrades(net_upgrades),
+        genesis_pool_id,
+    )
+}
+
 pub fn create_chain_config_with_staking_pool(
     rng: &mut impl Rng,
     mint_amount: Amount,
@@ -321,6 +362,50 @@ pub fn pos_mine(
     })
 }
 
+/// Mine `n` PoW blocks on top of the current tip with the specified difficulty and process them.
+///
+/// Each block has a locked reward and a test transaction spending from its parent.
+pub fn mine_n_blocks(
+    tf: &mut TestFramework,
+    n: usize,
+    difficulty: Uint256,
+    rng: &mut (impl Rng + CryptoRng),
+) -> Vec<Block> {
+    let reward_lock_distance =
+        tf.chain_config().get_proof_of_work_config().reward_maturity_distance();
+
+    let mut blocks = Vec::with_capacity(n);
+    for _ in 0..n {
+        let (_, pub_key) = PrivateKey::new_from_rng(rng, KeyKind::Secp256k1Schnorr);
+        let block = tf
+            .make_block_builder()
+            .with_reward(vec![TxOutput::LockThenTransfer(
+                OutputValue::Coin(Amount::from_atoms(10)),
+                Destination::PublicKey(pub_key),
+                OutputTimeLock::ForBlockCount(reward_lock_distance.to_int()),
+            )])
+            .add_test_transaction_from_best_block(rng)
+            .build(rng);
+
+        let mut block_header = block.header().clone();
+        assert_eq!(
+            consensus::mine(
+                block_header.header_mut().unwrap(),
+                u128::MAX,
+                difficulty.into(),
+                Arc::new(false.into())
+            )
+            .expect("Unexpected conversion error"),
+            consensus::MiningResult::Success
+        );
+        let block = Block::new_from_header(block_header, block.body().clone()).unwrap();
+
+        tf.process_block(block.clone(), BlockSource::Local).unwrap();
+        blocks.push(block);
+    }
+    blocks
+}
+
 #[allow(unused)]
 pub fn assert_block_index_identical_to(bi1: &BlockIndex, bi2: &BlockIndex) {
     assert!(
@@ -191,17 +191,82 @@ fn double_spend_tx_in_another_block(#[case] seed: Seed) {
         let second_block = tf.make_block_builder().add_transaction(second_tx).build(&mut rng);
         assert_eq!(
             tf.process_block(second_block, BlockSource::Local).unwrap_err(),
-            ChainstateError::ProcessBlockError(BlockError::StateUpdateFailed(
-                ConnectTransactionError::MissingOutputOrSpent(UtxoOutPoint::new(
-                    tf.genesis().get_id().into(),
-                    0
-                ))
-            ))
+            ChainstateError::ProcessBlockError(BlockError::OutputAlreadySpent {
+                outpoint: UtxoOutPoint::new(tf.genesis().get_id().into(), 0),
+                spent_in_block: first_block_id,
+            })
         );
         assert_eq!(tf.best_block_id(), first_block_id);
     });
 }
 
+// Same as above, but the second block comes from a peer, which doesn't change the error.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn double_spend_tx_in_another_block_from_peer(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+
+        let tx1_output_value = rng.gen_range(100_000..200_000);
+        let first_tx = tx_from_genesis(&tf.genesis(), &mut rng, tx1_output_value);
+        let first_block = tf.make_block_builder().add_transaction(first_tx).build(&mut rng);
+        let first_block_id = first_block.get_id();
+        tf.process_block(first_block, BlockSource::Local).unwrap();
+        assert_eq!(tf.best_block_id(), first_block_id);
+
+        let tx2_output_value = rng.gen_range(100_000..200_000);
+        let second_tx = tx_from_genesis(&tf.genesis(), &mut rng, tx2_output_value);
+        let second_block = tf.make_block_builder().add_transaction(second_tx).build(&mut rng);
+        assert_eq!(
+            tf.process_block(second_block, BlockSource::Peer).unwrap_err(),
+            ChainstateError::ProcessBlockError(BlockError::OutputAlreadySpent {
+                outpoint: UtxoOutPoint::new(tf.genesis().get_id().into(), 0),
+                spent_in_block: first_block_id,
+            })
+        );
+        assert_eq!(tf.best_block_id(), first_block_id);
+    });
+}
+
+// Try to use an output that has been spent several blocks below the tip.
+//
+// Genesis -> b1 -> ... -> bN.
+//
+// The error must point at b1, which has spent the output first.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn double_spend_tx_in_earlier_block(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+
+        let tx1_output_value = rng.gen_range(100_000..200_000);
+        let first_tx = tx_from_genesis(&tf.genesis(), &mut rng, tx1_output_value);
+        let first_block = tf.make_block_builder().add_transaction(first_tx).build(&mut rng);
+        let first_block_id = first_block.get_id();
+        tf.process_block(first_block, BlockSource::Local).unwrap();
+
+        let blocks_on_top = rng.gen_range(1..20);
+        let tip_id = tf.create_chain(&first_block_id.into(), blocks_on_top, &mut rng).unwrap();
+        assert_eq!(tf.best_block_id(), tip_id);
+
+        let tx2_output_value = rng.gen_range(100_000..200_000);
+        let second_tx = tx_from_genesis(&tf.genesis(), &mut rng, tx2_output_value);
+        let second_block = tf.make_block_builder().add_transaction(second_tx).build(&mut rng);
+        assert_eq!(
+            tf.process_block(second_block, BlockSource::Local).unwrap_err(),
+            ChainstateError::ProcessBlockError(BlockError::OutputAlreadySpent {
+                outpoint: UtxoOutPoint::new(tf.genesis().get_id().into(), 0),
+                spent_in_block: first_block_id,
+            })
+        );
+        assert_eq!(tf.best_block_id(), tip_id);
+    });
+}
+
 // Try to process a block where the second transaction's input is more then first output.
 //
 // +--Block----------------+
@@ -24,13 +24,14 @@ use common::{
         self,
         block::timestamp::BlockTimestamp,
         config::{create_regtest, ChainType},
-        Destination, NetUpgrades,
+        ConsensusUpgrade, Destination, GenBlock, NetUpgrades,
     },
-    primitives::Idable,
+    primitives::{BlockHeight, Id, Idable},
+    Uint256,
 };
 use randomness::{CryptoRng, Rng};
 
-use chainstate_test_framework::TestFramework;
+use chainstate_test_framework::{mine_n_blocks, TestFramework};
 use chainstate_types::{BlockStatus, BlockValidationStage};
 use test_utils::random::{make_seedable_rng, Seed};
 
@@ -126,6 +127,45 @@ fn test_consistency_check_explicitly_enabled_for_testnet(#[case] seed: Seed) {
     tf.create_chain(&tf.genesis().get_id().into(), 1, &mut rng).unwrap();
 }
 
+// Check that `mine_n_blocks` produces a valid PoW chain on top of the current tip.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn test_mine_n_blocks(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let difficulty =
+        Uint256([0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF, 0x0FFFFFFFFFFFFFFF]);
+
+    let net_upgrades = NetUpgrades::initialize(vec![
+        (BlockHeight::new(0), ConsensusUpgrade::IgnoreConsensus),
+        (
+            BlockHeight::new(1),
+            ConsensusUpgrade::PoW {
+                initial_difficulty: difficulty.into(),
+            },
+        ),
+    ])
+    .unwrap();
+    let chain_config =
+        chain::config::Builder::test_chain().consensus_upgrades(net_upgrades).build();
+    let mut tf = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+
+    let blocks = mine_n_blocks(&mut tf, 3, difficulty, &mut rng);
+    assert_eq!(blocks.len(), 3);
+
+    let mut parent_id: Id<GenBlock> = tf.genesis().get_id().into();
+    for (height, block) in (1..).zip(&blocks) {
+        assert_eq!(block.prev_block_id(), parent_id);
+        assert!(tf.is_block_in_main_chain(&block.get_id()));
+        assert_eq!(
+            tf.block_index(&block.get_id()).block_height(),
+            BlockHeight::new(height)
+        );
+        parent_id = block.get_id().into();
+    }
+    assert_eq!(tf.best_block_id(), parent_id);
+}
+
 // Store a block index with the persistence flag set, but without storing the corresponding block.
 fn make_chainstate_inconsistent(tf: &mut TestFramework, rng: &mut (impl Rng + CryptoRng)) {
     let genesis_id = tf.chain_config().genesis_block_id();
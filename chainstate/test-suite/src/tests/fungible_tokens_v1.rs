@@ -347,6 +347,32 @@ fn token_issue_test(#[case] seed: Seed) {
             );
         }
 
+        // URI contains a NUL byte
+        {
+            let issuance = TokenIssuance::V1(TokenIssuanceV1 {
+                token_ticker: random_ascii_alphanumeric_string(&mut rng, 1..5).as_bytes().to_vec(),
+                number_of_decimals: rng.gen_range(1..18),
+                metadata_uri: b"https://example.com/\0token.json".to_vec(),
+                total_supply: TokenTotalSupply::Unlimited,
+                authority: Destination::AnyoneCanSpend,
+                is_freezable: IsTokenFreezable::No,
+            });
+            let (result, tx_id, _) = process_block_with_issuance(issuance);
+            assert_eq!(
+                result.unwrap_err(),
+                ChainstateError::ProcessBlockError(BlockError::CheckBlockFailed(
+                    CheckBlockError::CheckTransactionFailed(
+                        CheckBlockTransactionsError::CheckTransactionError(
+                            CheckTransactionError::TokensError(TokensError::IssueError(
+                                TokenIssuanceError::IssueErrorIncorrectMetadataURI,
+                                tx_id,
+                            ))
+                        )
+                    )
+                ))
+            );
+        }
+
         // URI contain non alpha-numeric char
         let issuance = TokenIssuance::V1(TokenIssuanceV1 {
             token_ticker: random_ascii_alphanumeric_string(&mut rng, 1..5).as_bytes().to_vec(),
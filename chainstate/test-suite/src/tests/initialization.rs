@@ -18,11 +18,13 @@ use chainstate::ChainstateError;
 use chainstate_test_framework::TestFramework;
 use common::{
     chain::{
-        config::{Builder as ChainConfigBuilder, ChainType},
-        NetUpgrades,
+        config::{create_unit_test_config_builder, Builder as ChainConfigBuilder, ChainType},
+        output_value::OutputValue,
+        Destination, NetUpgrades, TxOutput, UtxoOutPoint,
     },
-    primitives::Id,
+    primitives::{Amount, Id},
 };
+use crypto::key::{KeyKind, PrivateKey};
 use randomness::CryptoRng;
 
 #[cfg(not(loom))]
@@ -144,3 +146,38 @@ fn genesis_check_err_nonempty_chain(#[case] seed: Seed) {
     let mut rng = make_seedable_rng(seed);
     genesis_check_err(rng.gen_range(2..100), &mut rng);
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn genesis_with_custom_utxos(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let utxos = [
+        (
+            Destination::PublicKey(PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr).1),
+            Amount::from_atoms(rng.gen_range(1..1_000_000)),
+        ),
+        (
+            Destination::AnyoneCanSpend,
+            Amount::from_atoms(rng.gen_range(1..1_000_000)),
+        ),
+    ];
+    let chain_config = create_unit_test_config_builder()
+        .genesis_unittest_with_utxos(utxos.clone())
+        .build();
+    let genesis_id = chain_config.genesis_block_id();
+    let tf = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+
+    for (index, (destination, amount)) in utxos.into_iter().enumerate() {
+        let outpoint = UtxoOutPoint::new(genesis_id.into(), index as u32);
+        let utxo = tf.chainstate.utxo(&outpoint).unwrap().unwrap();
+        assert_eq!(
+            utxo.output(),
+            &TxOutput::Transfer(OutputValue::Coin(amount), destination)
+        );
+    }
+
+    let outpoint = UtxoOutPoint::new(genesis_id.into(), 2);
+    assert!(tf.chainstate.utxo(&outpoint).unwrap().is_none());
+}
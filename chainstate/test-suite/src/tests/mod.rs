@@ -64,6 +64,7 @@ mod tx_fee;
 mod tx_verification_simulation;
 mod tx_verifier_among_threads;
 mod tx_verifier_disconnect;
+mod utxo_snapshot;
 
 mod helpers;
 
@@ -23,8 +23,9 @@ use chainstate::{
 };
 use chainstate_storage::Transactional;
 use chainstate_test_framework::{
-    anyonecanspend_address, create_stake_pool_data_with_all_reward_to_staker, empty_witness,
-    TestFramework, TransactionBuilder,
+    anyonecanspend_address, create_chain_config_with_default_staking_pool_after_pow,
+    create_stake_pool_data_with_all_reward_to_staker, empty_witness, mine_n_blocks, TestFramework,
+    TransactionBuilder,
 };
 use chainstate_types::{
     pos_randomness::{PoSRandomness, PoSRandomnessError},
@@ -45,9 +46,10 @@ use common::{
         },
         stakelock::StakePoolData,
         timelock::OutputTimeLock,
-        AccountNonce, AccountOutPoint, AccountSpending, ChainConfig, ConsensusUpgrade, Destination,
-        GenBlock, NetUpgrades, OutPointSourceId, PoSChainConfig, PoSChainConfigBuilder, PoolId,
-        RequiredConsensus, SignedTransaction, TxInput, TxOutput, UtxoOutPoint,
+        AccountNonce, AccountOutPoint, AccountSpending, Block, ChainConfig, ConsensusUpgrade,
+        Destination, GenBlock, NetUpgrades, OutPointSourceId, PoSChainConfig,
+        PoSChainConfigBuilder, PoolId, RequiredConsensus, SignedTransaction, TxInput, TxOutput,
+        UtxoOutPoint,
     },
     primitives::{per_thousand::PerThousand, Amount, BlockCount, BlockHeight, Id, Idable, H256},
     Uint256,
@@ -819,6 +821,78 @@ fn pos_invalid_vrf(#[case] seed: Seed) {
     }
 }
 
+// Create a chain genesis <- PoW block_1 <- PoW block_2 <- PoS block_3, where block_3 stakes
+// the pool from genesis.
+// Then try to create block_4 with the VRF data taken from block_3 and check that it's rejected.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn pos_after_pow_with_tampered_vrf(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let (vrf_sk, vrf_pk) = VRFPrivateKey::new_from_rng(&mut rng, VRFKeyKind::Schnorrkel);
+    let (staking_sk, staking_pk) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+    let pow_difficulty =
+        Uint256([0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF, 0x0FFFFFFFFFFFFFFF]);
+
+    let (chain_config_builder, pool_id) = create_chain_config_with_default_staking_pool_after_pow(
+        &mut rng,
+        staking_pk,
+        vrf_pk,
+        pow_difficulty,
+        BlockHeight::new(3),
+    );
+    let chain_config = chain_config_builder.build();
+    let target_block_time = chain_config.target_block_spacing();
+    let mut tf = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+
+    mine_n_blocks(&mut tf, 2, pow_difficulty, &mut rng);
+    tf.progress_time_seconds_since_epoch(target_block_time.as_secs());
+
+    let block_3 = tf
+        .make_pos_block_builder()
+        .with_stake_pool_id(pool_id)
+        .with_stake_spending_key(staking_sk.clone())
+        .with_vrf_key(vrf_sk.clone())
+        .build(&mut rng);
+    let block_3_id = block_3.get_id();
+    tf.process_block(block_3.clone(), BlockSource::Local).unwrap();
+    assert_eq!(tf.best_block_id(), block_3_id);
+
+    let pos_data = |block: &Block| match block.header().header().consensus_data() {
+        ConsensusData::PoS(pos_data) => pos_data.as_ref().clone(),
+        ConsensusData::None | ConsensusData::PoW(_) => panic!("Unexpected consensus data"),
+    };
+
+    let valid_block_4 = tf
+        .make_pos_block_builder()
+        .with_stake_pool_id(pool_id)
+        .with_stake_spending_key(staking_sk.clone())
+        .with_vrf_key(vrf_sk.clone())
+        .build(&mut rng);
+    let mut tampered_pos_data = pos_data(&valid_block_4);
+    tampered_pos_data.update_vrf_data(pos_data(&block_3).vrf_data().clone());
+
+    let res = tf
+        .make_pos_block_builder()
+        .with_stake_pool_id(pool_id)
+        .with_stake_spending_key(staking_sk.clone())
+        .with_vrf_key(vrf_sk.clone())
+        .with_consensus_data(tampered_pos_data)
+        .build_and_process(&mut rng)
+        .unwrap_err();
+    assert_eq!(
+        res,
+        ChainstateError::ProcessBlockError(BlockError::CheckBlockFailed(
+            CheckBlockError::ConsensusVerificationFailed(ConsensusVerificationError::PoSError(
+                ConsensusPoSError::RandomnessError(PoSRandomnessError::VRFDataVerificationFailed(
+                    ProofOfStakeVRFError::VRFDataVerificationFailed(VRFError::VerificationError),
+                )),
+            )),
+        ))
+    );
+    assert_eq!(tf.best_block_id(), block_3_id);
+}
+
 // Create a chain genesis <- block_1, where block_1 has valid StakePool output.
 // PoS consensus activates on height 2.
 // Try to crete block_2 with PoS data that has refer to invalid pool id.
@@ -22,8 +22,8 @@ use chainstate::{
     DefaultTransactionVerificationStrategy, OrphanCheckError,
 };
 use chainstate_test_framework::{
-    anyonecanspend_address, empty_witness, get_output_value, TestFramework, TestStore,
-    TransactionBuilder,
+    anyonecanspend_address, empty_witness, get_output_value, mine_n_blocks, TestFramework,
+    TestStore, TransactionBuilder,
 };
 use chainstate_types::{
     BlockStatus, BlockValidationStage, GenBlockIndex, GetAncestorError, PropertyQueryError,
@@ -46,7 +46,8 @@ use common::{
         TxOutput, UtxoOutPoint,
     },
     primitives::{
-        per_thousand::PerThousand, Amount, BlockCount, BlockHeight, Compact, Id, Idable, H256,
+        per_thousand::PerThousand, Amount, BlockCount, BlockDistance, BlockHeight, Compact, Id,
+        Idable, H256,
     },
     Uint256,
 };
@@ -708,12 +709,6 @@ fn consensus_type(#[case] seed: Seed) {
     let chain_config = ConfigBuilder::test_chain().consensus_upgrades(net_upgrades).build();
     let mut tf = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
 
-    let reward_lock_distance = tf
-        .chainstate
-        .get_chain_config()
-        .get_proof_of_work_config()
-        .reward_maturity_distance();
-
     // The next block will have height 1. At this height, we are still under IgnoreConsensus, so
     // processing a block with PoWData will fail
     assert!(matches!(
@@ -748,34 +743,7 @@ fn consensus_type(#[case] seed: Seed) {
     ));
 
     // Mine blocks 5-9 with minimal difficulty, as expected by net upgrades
-    for i in 5..10 {
-        let (_, pub_key) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
-        let prev_block = tf.block(*tf.index_at(i - 1).block_id());
-        let mined_block = tf
-            .make_block_builder()
-            .with_parent(prev_block.get_id().into())
-            .with_reward(vec![TxOutput::LockThenTransfer(
-                OutputValue::Coin(Amount::from_atoms(10)),
-                Destination::PublicKey(pub_key),
-                OutputTimeLock::ForBlockCount(reward_lock_distance.to_int()),
-            )])
-            .add_test_transaction_from_block(&prev_block, &mut rng)
-            .build(&mut rng);
-        let mut block_header = mined_block.header().clone();
-        let bits = min_difficulty.into();
-        assert_eq!(
-            consensus::mine(
-                block_header.header_mut().unwrap(),
-                u128::MAX,
-                bits,
-                Arc::new(false.into())
-            )
-            .expect("Unexpected conversion error"),
-            consensus::MiningResult::Success
-        );
-        let mined_block = Block::new_from_header(block_header, mined_block.body().clone()).unwrap();
-        tf.process_block(mined_block, BlockSource::Local).unwrap();
-    }
+    mine_n_blocks(&mut tf, 5, min_difficulty, &mut rng);
 
     // Block 10 should ignore consensus according to net upgrades. The following Pow block should
     // fail.
@@ -827,41 +795,8 @@ fn consensus_type(#[case] seed: Seed) {
         ))
     ));
 
-    let reward_lock_distance = tf
-        .chainstate
-        .get_chain_config()
-        .get_proof_of_work_config()
-        .reward_maturity_distance();
-
     // Mining should work
-    for i in 15..20 {
-        let (_, pub_key) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
-        let prev_block = tf.block(*tf.index_at(i - 1).block_id());
-        let mined_block = tf
-            .make_block_builder()
-            .with_parent(prev_block.get_id().into())
-            .with_reward(vec![TxOutput::LockThenTransfer(
-                OutputValue::Coin(Amount::from_atoms(10)),
-                Destination::PublicKey(pub_key),
-                OutputTimeLock::ForBlockCount(reward_lock_distance.to_int()),
-            )])
-            .add_test_transaction_from_block(&prev_block, &mut rng)
-            .build(&mut rng);
-        let bits = min_difficulty.into();
-        let mut block_header = mined_block.header().clone();
-        assert_eq!(
-            consensus::mine(
-                block_header.header_mut().unwrap(),
-                u128::MAX,
-                bits,
-                Arc::new(false.into())
-            )
-            .expect("Unexpected conversion error"),
-            consensus::MiningResult::Success
-        );
-        let mined_block = Block::new_from_header(block_header, mined_block.body().clone()).unwrap();
-        tf.process_block(mined_block, BlockSource::Local).unwrap();
-    }
+    mine_n_blocks(&mut tf, 5, min_difficulty, &mut rng);
 }
 
 #[rstest]
@@ -1535,3 +1470,196 @@ fn spend_timelocked_signed_output(#[case] seed: Seed) {
         tf.make_block_builder().add_transaction(tx).build_and_process(&mut rng).unwrap();
     });
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn max_transactions_per_block(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let max_txs = rng.gen_range(1..10);
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chainstate_config(
+                ChainstateConfig::new().with_max_transactions_per_block(max_txs),
+            )
+            .build();
+        let genesis_id = tf.genesis().get_id();
+        let genesis_amount = get_output_value(&tf.genesis().utxos()[0]).unwrap();
+
+        // A chain of transactions where each one spends the output of the previous one
+        let make_txs = |tx_count: usize, rng: &mut _| {
+            let mut prev_source = genesis_id.into();
+            (0..tx_count)
+                .map(|_| {
+                    let tx = TransactionBuilder::new()
+                        .add_input(TxInput::from_utxo(prev_source, 0), empty_witness(rng))
+                        .add_output(TxOutput::Transfer(
+                            genesis_amount.clone(),
+                            anyonecanspend_address(),
+                        ))
+                        .build();
+                    prev_source = tx.transaction().get_id().into();
+                    tx
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let txs = make_txs(max_txs + 1, &mut rng);
+        let block = tf.make_block_builder().with_transactions(txs).build(&mut rng);
+        let block_id = block.get_id();
+        let expected_error = BlockError::CheckBlockFailed(CheckBlockError::TooManyTransactions(
+            max_txs + 1,
+            max_txs,
+        ));
+        assert_eq!(
+            expected_error.classify(),
+            BlockProcessingErrorClass::TemporarilyBadBlock
+        );
+        assert_eq!(
+            tf.process_block(block, BlockSource::Local).unwrap_err(),
+            ChainstateError::ProcessBlockError(expected_error)
+        );
+        assert_eq!(tf.best_block_id(), genesis_id);
+        // The block is not marked as invalid
+        assert_no_block_indices(&tf, &[block_id]);
+
+        let txs = make_txs(max_txs, &mut rng);
+        let block = tf.make_block_builder().with_transactions(txs).build(&mut rng);
+        let block_id = block.get_id();
+        tf.process_block(block, BlockSource::Local).unwrap();
+        assert_eq!(tf.best_block_id(), block_id);
+    });
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn pruning_old_block_bodies(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let max_reorg_depth = rng.gen_range(1..20);
+        let pruning_depth = rng.gen_range(max_reorg_depth..50);
+        let blocks_count = 100;
+        let chain_config = ConfigBuilder::test_chain()
+            .max_depth_for_reorg(BlockDistance::new(max_reorg_depth as i64))
+            .build();
+
+        // Pruning less deep than the reorg limit isn't allowed
+        let result = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config.clone())
+            .with_chainstate_config(
+                ChainstateConfig::new().with_pruning_depth(rng.gen_range(0..max_reorg_depth)),
+            )
+            .try_build();
+        assert!(matches!(
+            result,
+            Err(ChainstateError::CannotPruneToDepth(_, _))
+        ));
+
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config)
+            .with_chainstate_config(ChainstateConfig::new().with_pruning_depth(pruning_depth))
+            .build();
+        let block_ids = tf
+            .create_chain_return_ids(&tf.genesis().get_id().into(), blocks_count, &mut rng)
+            .unwrap();
+
+        assert_eq!(tf.best_block_id(), *block_ids.last().unwrap());
+        assert_eq!(
+            tf.best_block_index().block_height(),
+            BlockHeight::new(blocks_count as u64)
+        );
+
+        for (idx, block_id) in block_ids.iter().enumerate() {
+            let height = idx as u64 + 1;
+            let block_id = tf.to_chain_block_id(block_id);
+            let block = tf.chainstate.get_block(block_id).unwrap();
+            if height + pruning_depth <= blocks_count as u64 {
+                assert_eq!(block, None, "block at height {height} is not pruned");
+            } else {
+                assert!(block.is_some(), "block at height {height} is pruned");
+            }
+
+            let header = tf.chainstate.get_block_header(block_id).unwrap().unwrap();
+            assert_eq!(header.block_id(), block_id);
+
+            // Pruned blocks stay persisted and valid, only their bodies are marked as pruned
+            let block_index =
+                tf.chainstate.get_block_index_for_persisted_block(&block_id).unwrap().unwrap();
+            assert!(block_index.status().is_fully_valid());
+            assert_eq!(block_index.status().is_body_pruned(), block.is_none());
+            assert_eq!(block_index.has_body(), block.is_some());
+        }
+
+        // Pruned blocks can't be returned, which is reported explicitly
+        let first_unpruned_height = blocks_count as u64 - pruning_depth + 1;
+        let pruned_block_id = tf.to_chain_block_id(&block_ids[0]);
+        assert_eq!(
+            tf.chainstate.get_mainchain_blocks(BlockHeight::new(1), 1).unwrap_err(),
+            ChainstateError::FailedToReadProperty(PropertyQueryError::BlockBodyPruned(
+                pruned_block_id,
+                BlockHeight::new(1)
+            ))
+        );
+        let blocks = tf
+            .chainstate
+            .get_mainchain_blocks(BlockHeight::new(first_unpruned_height), blocks_count)
+            .unwrap();
+        assert_eq!(blocks.len(), pruning_depth as usize);
+
+        // Resetting the failure flags must not delete the indices of the pruned blocks
+        tf.chainstate.reset_block_failure_flags(&pruned_block_id).unwrap();
+        let block_index = tf
+            .chainstate
+            .get_block_index_for_persisted_block(&pruned_block_id)
+            .unwrap()
+            .unwrap();
+        assert!(block_index.status().is_fully_valid());
+        assert!(block_index.status().is_body_pruned());
+        assert_eq!(tf.best_block_id(), *block_ids.last().unwrap());
+    });
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn enabling_pruning_prunes_all_old_blocks(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let max_reorg_depth = rng.gen_range(1..20);
+        let pruning_depth = rng.gen_range(max_reorg_depth..50);
+        let blocks_count = rng.gen_range(50..100);
+        let chain_config = ConfigBuilder::test_chain()
+            .max_depth_for_reorg(BlockDistance::new(max_reorg_depth as i64))
+            .build();
+        let storage = TestStore::new_empty().unwrap();
+
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config.clone())
+            .with_storage(storage.clone())
+            .build();
+        let mut block_ids = tf
+            .create_chain_return_ids(&tf.genesis().get_id().into(), blocks_count, &mut rng)
+            .unwrap();
+        drop(tf);
+
+        // Restart the node with pruning, the next block prunes everything deep enough
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config)
+            .with_storage(storage)
+            .with_chainstate_config(ChainstateConfig::new().with_pruning_depth(pruning_depth))
+            .build();
+        block_ids.push(tf.create_chain(&tf.best_block_id(), 1, &mut rng).unwrap());
+        let tip_height = blocks_count as u64 + 1;
+
+        for (idx, block_id) in block_ids.iter().enumerate() {
+            let height = idx as u64 + 1;
+            let block = tf.chainstate.get_block(tf.to_chain_block_id(block_id)).unwrap();
+            assert_eq!(
+                block.is_none(),
+                height + pruning_depth <= tip_height,
+                "unexpected pruning state of block at height {height}"
+            );
+        }
+    });
+}
@@ -19,10 +19,16 @@ use std::sync::Mutex;
 use crate::tests::EventList;
 use chainstate::BlockError;
 use chainstate::BlockSource;
+use chainstate::ChainFork;
 use chainstate::ChainstateError;
 use chainstate::ChainstateEvent;
 use chainstate::ConnectTransactionError;
+use chainstate::MainchainChange;
+use chainstate_storage::BlockchainStorageWrite;
+use chainstate_storage::TransactionRw;
+use chainstate_storage::Transactional;
 use chainstate_test_framework::TestFramework;
+use common::chain::block::block_body::BlockBody;
 use common::chain::Block;
 use common::chain::GenBlock;
 use common::chain::Transaction;
@@ -33,6 +39,8 @@ use common::primitives::Idable;
 use randomness::CryptoRng;
 use randomness::Rng;
 use rstest::rstest;
+use serialization::DecodeAll;
+use serialization::Encode;
 use test_utils::random::make_seedable_rng;
 use test_utils::random::Seed;
 
@@ -76,6 +84,234 @@ fn reorg_simple(#[case] seed: Seed) {
     });
 }
 
+// Produce `genesis -> a1 -> a2`, then a parallel `genesis -> b1 -> b2 -> b3`; the last block
+// triggers a reorg that disconnects both `a` blocks before connecting the `b` ones.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn reorg_mainchain_changes(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+        let genesis_id: Id<GenBlock> = tf.genesis().get_id().into();
+
+        let a_ids = tf.create_chain_return_ids(&genesis_id, 2, &mut rng).unwrap();
+        let b_ids = tf.create_chain_return_ids(&genesis_id, 2, &mut rng).unwrap();
+        let a_ids = a_ids.iter().map(|id| tf.to_chain_block_id(id)).collect::<Vec<_>>();
+        let b_ids = b_ids.iter().map(|id| tf.to_chain_block_id(id)).collect::<Vec<_>>();
+        assert_eq!(tf.best_block_id(), Id::<GenBlock>::from(a_ids[1]));
+
+        let block_b3 = tf
+            .make_block_builder()
+            .add_test_transaction_with_parent(b_ids[1].into(), &mut rng)
+            .with_parent(b_ids[1].into())
+            .build(&mut rng);
+        let b3_id = block_b3.get_id();
+        let changes =
+            tf.process_block_with_mainchain_changes(block_b3, BlockSource::Local).unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                MainchainChange::Disconnected(a_ids[1], BlockHeight::new(2)),
+                MainchainChange::Disconnected(a_ids[0], BlockHeight::new(1)),
+                MainchainChange::Connected(b_ids[0], BlockHeight::new(1)),
+                MainchainChange::Connected(b_ids[1], BlockHeight::new(2)),
+                MainchainChange::Connected(b3_id, BlockHeight::new(3)),
+            ]
+        );
+
+        // Only the tips are reported by the chainstate
+        tf.assert_events(&[
+            (a_ids[0], BlockHeight::new(1)),
+            (a_ids[1], BlockHeight::new(2)),
+            (b3_id, BlockHeight::new(3)),
+        ]);
+    });
+}
+
+// Produce `genesis -> a1 -> a2 -> a3` and forks `a1 -> b2`, `a2 -> c3 -> c4 -> c5`, then check
+// the orphan tips and the forks reported for them, also after b2 has been invalidated.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn orphan_tips(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+        let genesis_id = tf.genesis().get_id();
+
+        assert_eq!(tf.chainstate.get_orphan_tips().unwrap(), vec![]);
+
+        let main_ids = tf.create_chain_return_ids(&genesis_id.into(), 3, &mut rng).unwrap();
+        let main_tip = tf.best_block_id();
+        assert_eq!(main_tip, main_ids[2]);
+        assert_eq!(tf.chainstate.get_orphan_tips().unwrap(), vec![]);
+        assert_eq!(chainstate::get_chain_forks(&tf.chainstate).unwrap(), vec![]);
+
+        // a shorter fork doesn't cause a reorg
+        let fork_b_tip = tf.create_chain(&main_ids[0], 1, &mut rng).unwrap();
+        let fork_b_tip = tf.to_chain_block_id(&fork_b_tip);
+        assert_eq!(tf.best_block_id(), main_tip);
+        assert_eq!(tf.chainstate.get_orphan_tips().unwrap(), vec![fork_b_tip]);
+
+        let forks = chainstate::get_chain_forks(&tf.chainstate).unwrap();
+        assert_eq!(
+            forks,
+            vec![ChainFork {
+                fork_point_height: BlockHeight::new(1),
+                orphan_tip: fork_b_tip,
+                main_tip,
+            }]
+        );
+
+        // a longer fork causes a reorg, after which the old mainchain becomes a fork
+        let fork_c_tip = tf.create_chain(&main_ids[1], 3, &mut rng).unwrap();
+        assert_eq!(tf.best_block_id(), fork_c_tip);
+
+        let old_main_tip = tf.to_chain_block_id(&main_tip);
+        let orphan_tips = tf.chainstate.get_orphan_tips().unwrap();
+        assert_eq!(orphan_tips.len(), 2);
+        assert!(orphan_tips.contains(&fork_b_tip));
+        assert!(orphan_tips.contains(&old_main_tip));
+
+        let mut forks = chainstate::get_chain_forks(&tf.chainstate).unwrap();
+        forks.sort_by_key(|fork| fork.fork_point_height);
+        assert_eq!(
+            forks,
+            vec![
+                ChainFork {
+                    fork_point_height: BlockHeight::new(1),
+                    orphan_tip: fork_b_tip,
+                    main_tip: fork_c_tip,
+                },
+                ChainFork {
+                    fork_point_height: BlockHeight::new(2),
+                    orphan_tip: old_main_tip,
+                    main_tip: fork_c_tip,
+                },
+            ]
+        );
+
+        // an invalidated branch is no longer reported
+        tf.chainstate.invalidate_block(&fork_b_tip).unwrap();
+        assert_eq!(tf.chainstate.get_orphan_tips().unwrap(), vec![old_main_tip]);
+    });
+}
+
+// Produce `genesis -> a1 -> a2 -> a3 -> a4` and forks `a1 -> b2 -> b3 -> b4`,
+// `a1 -> c2 -> c3 -> c4 -> c5`, then check that only the reorg of depth 3 is recorded.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn recent_reorgs(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+        let genesis_id = tf.genesis().get_id();
+
+        let main_ids = tf.create_chain_return_ids(&genesis_id.into(), 4, &mut rng).unwrap();
+        let old_tip = tf.to_chain_block_id(&tf.best_block_id());
+        assert_eq!(tf.chainstate.get_recent_reorgs(), vec![]);
+
+        // a fork of the same length doesn't cause a reorg
+        tf.create_chain(&main_ids[0], 3, &mut rng).unwrap();
+        assert_eq!(tf.to_chain_block_id(&tf.best_block_id()), old_tip);
+        assert_eq!(tf.chainstate.get_recent_reorgs(), vec![]);
+
+        let new_tip = tf.create_chain(&main_ids[0], 4, &mut rng).unwrap();
+        assert_eq!(tf.best_block_id(), new_tip);
+        let new_tip = tf.to_chain_block_id(&new_tip);
+
+        let reorgs = tf.chainstate.get_recent_reorgs();
+        assert_eq!(reorgs.len(), 1);
+        assert_eq!(reorgs[0].reorg_depth, 3);
+        assert_eq!(reorgs[0].old_tip, old_tip);
+        assert_eq!(reorgs[0].new_tip, new_tip);
+    });
+}
+
+// Produce `genesis -> a1 -> a2 -> a3 -> a4` and a fork `a1 -> b2 -> b3`, then check that
+// both invalidating a3 and resetting its failure flags record the resulting reorgs.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn recent_reorgs_after_invalidation(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+        let genesis_id = tf.genesis().get_id();
+
+        let main_ids = tf.create_chain_return_ids(&genesis_id.into(), 4, &mut rng).unwrap();
+        let main_tip = tf.to_chain_block_id(&main_ids[3]);
+        let fork_tip = tf.create_chain(&main_ids[0], 2, &mut rng).unwrap();
+        let fork_tip = tf.to_chain_block_id(&fork_tip);
+        assert_eq!(tf.chainstate.get_recent_reorgs(), vec![]);
+
+        let a3_id = tf.to_chain_block_id(&main_ids[2]);
+        tf.chainstate.invalidate_block(&a3_id).unwrap();
+        assert_eq!(tf.to_chain_block_id(&tf.best_block_id()), fork_tip);
+
+        let reorgs = tf.chainstate.get_recent_reorgs();
+        assert_eq!(reorgs.len(), 1);
+        assert_eq!(reorgs[0].reorg_depth, 3);
+        assert_eq!(reorgs[0].old_tip, main_tip);
+        assert_eq!(reorgs[0].new_tip, fork_tip);
+
+        tf.chainstate.reset_block_failure_flags(&a3_id).unwrap();
+        assert_eq!(tf.to_chain_block_id(&tf.best_block_id()), main_tip);
+
+        let reorgs = tf.chainstate.get_recent_reorgs();
+        assert_eq!(reorgs.len(), 2);
+        assert_eq!(reorgs[1].reorg_depth, 2);
+        assert_eq!(reorgs[1].old_tip, fork_tip);
+        assert_eq!(reorgs[1].new_tip, main_tip);
+    });
+}
+
+// Reprocessing a range of valid mainchain blocks keeps the tip, while a corrupted block
+// in the range results in an error that leaves the chainstate intact.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn reprocess_blocks(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+        let genesis_id: Id<GenBlock> = tf.genesis().get_id().into();
+
+        let block_ids = tf.create_chain_return_ids(&genesis_id, 5, &mut rng).unwrap();
+        let tip_id = tf.best_block_id();
+
+        // Heights outside of the mainchain are fine too
+        for height in [0, 1, 3, 5, 6] {
+            tf.chainstate.reprocess_blocks_from(BlockHeight::new(height)).unwrap();
+            assert_eq!(tf.best_block_id(), tip_id);
+        }
+
+        // Replace the block at height 3 with one that has the same header, but an empty body
+        let block = tf.block(tf.to_chain_block_id(&block_ids[2]));
+        let corrupted_block = {
+            let body = BlockBody::new(block.block_reward().clone(), Vec::new());
+            Block::decode_all(&mut (block.header(), &body).encode().as_slice()).unwrap()
+        };
+        assert_eq!(corrupted_block.get_id(), block.get_id());
+        {
+            let mut db_tx = tf.storage.transaction_rw(None).unwrap();
+            db_tx.add_block(&corrupted_block).unwrap();
+            db_tx.commit().unwrap();
+        }
+
+        let height = BlockHeight::new(rng.gen_range(1..=3));
+        let result = tf.chainstate.reprocess_blocks_from(height);
+        assert!(matches!(result, Err(ChainstateError::ProcessBlockError(_))));
+        assert_eq!(tf.best_block_id(), tip_id);
+
+        // The blocks above the corrupted one can still be reprocessed
+        tf.chainstate.reprocess_blocks_from(BlockHeight::new(4)).unwrap();
+        assert_eq!(tf.best_block_id(), tip_id);
+    });
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -209,12 +445,14 @@ fn check_fork_that_double_spends(tf: &mut TestFramework, rng: &mut (impl Rng + C
     let block_id = double_spend_block.get_id();
     tf.process_block(double_spend_block, BlockSource::Local).unwrap();
 
-    // Cause reorg on a bad block
+    // Cause reorg on a bad block; the output has already been spent by the parent block
+    let err = tf.create_chain(&block_id.into(), 10, rng).unwrap_err();
     assert!(matches!(
-        tf.create_chain(&block_id.into(), 10, rng).unwrap_err(),
-        ChainstateError::ProcessBlockError(BlockError::StateUpdateFailed(
-            ConnectTransactionError::MissingOutputOrSpent(_)
-        ))
+        err,
+        ChainstateError::ProcessBlockError(BlockError::OutputAlreadySpent {
+            spent_in_block,
+            ..
+        }) if spent_in_block == *parent_id
     ));
 }
 
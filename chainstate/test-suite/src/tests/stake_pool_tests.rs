@@ -344,9 +344,11 @@ fn stake_pool_twice_two_blocks(#[case] seed: Seed) {
                 Box::new(stake_pool_data.clone()),
             ))
             .build();
-        tf.make_block_builder()
+        let tx1_block_index = tf
+            .make_block_builder()
             .add_transaction(tx1)
             .build_and_process(&mut rng)
+            .unwrap()
             .unwrap();
 
         let tx2 = TransactionBuilder::new()
@@ -360,9 +362,10 @@ fn stake_pool_twice_two_blocks(#[case] seed: Seed) {
 
         assert_eq!(
             result.unwrap_err(),
-            ChainstateError::ProcessBlockError(BlockError::StateUpdateFailed(
-                ConnectTransactionError::MissingOutputOrSpent(genesis_outpoint)
-            ))
+            ChainstateError::ProcessBlockError(BlockError::OutputAlreadySpent {
+                outpoint: genesis_outpoint,
+                spent_in_block: *tx1_block_index.block_id(),
+            })
         );
     });
 }
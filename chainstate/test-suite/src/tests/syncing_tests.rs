@@ -640,6 +640,8 @@ fn initial_block_download(#[case] seed: Seed) {
                 min_max_bootstrap_import_buffer_sizes: Default::default(),
                 max_tip_age: Duration::from_secs(1).into(),
                 enable_heavy_checks: Some(true),
+                max_transactions_per_block: None,
+                pruning_depth: None,
             })
             .with_initial_time_since_genesis(2)
             .build();
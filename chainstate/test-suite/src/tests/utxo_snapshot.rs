@@ -0,0 +1,316 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    collections::BTreeMap,
+    io::{BufReader, BufWriter},
+};
+
+use super::*;
+use chainstate::{ChainstateError, UtxoSnapshotError};
+use chainstate_storage::{BlockchainStorageRead, Transactional};
+use chainstate_test_framework::{
+    anyonecanspend_address, create_stake_pool_data_with_all_reward_to_staker, empty_witness,
+    get_output_value, TransactionBuilder,
+};
+use common::{
+    chain::{
+        config::Builder as ConfigBuilder, output_value::OutputValue, OutPointSourceId, TxOutput,
+        UtxoOutPoint,
+    },
+    primitives::{BlockDistance, Idable, H256},
+};
+use crypto::vrf::{VRFKeyKind, VRFPrivateKey};
+use randomness::CryptoRng;
+use serialization::Encode;
+
+fn utxo_set(tf: &TestFramework) -> BTreeMap<UtxoOutPoint, utxo::Utxo> {
+    tf.storage.transaction_ro().unwrap().read_utxo_set().unwrap()
+}
+
+// The encoded state, because not all of its parts can be compared directly
+fn state_data(tf: &TestFramework) -> Vec<u8> {
+    tf.storage.transaction_ro().unwrap().get_snapshot_state_data().unwrap().encode()
+}
+
+fn export_snapshot(
+    tf: &mut TestFramework,
+    height: u64,
+) -> Result<(Vec<u8>, H256), ChainstateError> {
+    let mut snapshot = Vec::new();
+    let commitment = tf.chainstate.export_utxo_snapshot(
+        BlockHeight::new(height),
+        BufWriter::new(Box::new(&mut snapshot)),
+    )?;
+    Ok((snapshot, commitment))
+}
+
+fn import_snapshot(tf: &mut TestFramework, snapshot: &[u8]) -> Result<(), ChainstateError> {
+    tf.chainstate.import_utxo_snapshot(BufReader::new(Box::new(snapshot)))
+}
+
+// Create a stake pool from the genesis output, so that the snapshot has some PoS accounting data
+fn create_pool(tf: &mut TestFramework, rng: &mut (impl Rng + CryptoRng)) {
+    let genesis_outpoint = UtxoOutPoint::new(
+        OutPointSourceId::BlockReward(tf.genesis().get_id().into()),
+        0,
+    );
+    let genesis_amount = get_output_value(&tf.genesis().utxos()[0]).unwrap().coin_amount().unwrap();
+    let pledge = tf.chainstate.get_chain_config().min_stake_pool_pledge();
+    let (_, vrf_pk) = VRFPrivateKey::new_from_rng(rng, VRFKeyKind::Schnorrkel);
+    let (stake_pool_data, _) =
+        create_stake_pool_data_with_all_reward_to_staker(rng, pledge, vrf_pk);
+    let pool_id = pos_accounting::make_pool_id(&genesis_outpoint);
+
+    let tx = TransactionBuilder::new()
+        .add_input(genesis_outpoint.into(), empty_witness(rng))
+        .add_output(TxOutput::CreateStakePool(
+            pool_id,
+            Box::new(stake_pool_data),
+        ))
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin((genesis_amount - pledge).unwrap()),
+            anyonecanspend_address(),
+        ))
+        .build();
+    let block = tf.make_block_builder().add_transaction(tx).build(rng);
+    tf.process_block(block, BlockSource::Local).unwrap();
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn export_import_round_trip(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let max_reorg_depth = rng.gen_range(1..10);
+        let blocks_count = rng.gen_range(20..50);
+        let max_snapshot_height = (blocks_count - max_reorg_depth) as u64;
+        let snapshot_height = rng.gen_range(1..=max_snapshot_height);
+        let chain_config_builder = || {
+            ConfigBuilder::test_chain()
+                .max_depth_for_reorg(BlockDistance::new(max_reorg_depth as i64))
+        };
+
+        let mut tf1 = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config_builder().build())
+            .build();
+        create_pool(&mut tf1, &mut rng);
+        tf1.create_chain(&tf1.best_block_id(), snapshot_height as usize - 1, &mut rng)
+            .unwrap();
+        let snapshot_utxos = utxo_set(&tf1);
+        let snapshot_state = state_data(&tf1);
+
+        let block_ids = tf1
+            .create_chain_return_ids(
+                &tf1.best_block_id(),
+                blocks_count - snapshot_height as usize,
+                &mut rng,
+            )
+            .unwrap();
+
+        // Blocks that can still be reorged can't be snapshotted
+        assert_eq!(
+            export_snapshot(&mut tf1, max_snapshot_height + 1).unwrap_err(),
+            ChainstateError::UtxoSnapshotError(UtxoSnapshotError::HeightNotFinalized(
+                BlockHeight::new(max_snapshot_height + 1),
+                BlockHeight::new(max_snapshot_height)
+            ))
+        );
+
+        // Exporting doesn't change the chainstate
+        let tip_utxos = utxo_set(&tf1);
+        let tip_state = state_data(&tf1);
+        let (snapshot, commitment) = export_snapshot(&mut tf1, snapshot_height).unwrap();
+        assert_eq!(tf1.best_block_id(), *block_ids.last().unwrap());
+        assert_eq!(utxo_set(&tf1), tip_utxos);
+        assert_eq!(state_data(&tf1), tip_state);
+
+        // The commitment only depends on the state at the snapshot height
+        assert_eq!(
+            export_snapshot(&mut tf1, snapshot_height).unwrap(),
+            (snapshot.clone(), commitment)
+        );
+
+        // Import into a fresh chainstate, the snapshot block becomes the tip
+        let chain_config = chain_config_builder()
+            .snapshot_commitments([(BlockHeight::new(snapshot_height), commitment)].into())
+            .build();
+        let mut tf2 = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+        import_snapshot(&mut tf2, &snapshot).unwrap();
+        assert_eq!(tf2.best_block_id(), tf1.block_id(snapshot_height));
+        assert_eq!(utxo_set(&tf2), snapshot_utxos);
+        assert_eq!(state_data(&tf2), snapshot_state);
+
+        // A snapshot can't be imported twice
+        assert_eq!(
+            import_snapshot(&mut tf2, &snapshot).unwrap_err(),
+            ChainstateError::UtxoSnapshotError(UtxoSnapshotError::ChainstateNotEmpty)
+        );
+
+        // The blocks above the snapshot can be processed, ending up in the same state
+        for block_id in &block_ids {
+            let block = tf1.block(tf1.to_chain_block_id(block_id));
+            tf2.process_block(block, BlockSource::Local).unwrap();
+        }
+        assert_eq!(tf2.best_block_id(), tf1.best_block_id());
+        assert_eq!(utxo_set(&tf2), utxo_set(&tf1));
+        assert_eq!(state_data(&tf2), state_data(&tf1));
+
+        // And the chain can be extended further
+        let new_blocks_count = rng.gen_range(1..5);
+        let new_tip = tf2.create_chain(&tf2.best_block_id(), new_blocks_count, &mut rng).unwrap();
+        assert_eq!(tf2.best_block_id(), new_tip);
+        assert_eq!(
+            tf2.best_block_index().block_height(),
+            BlockHeight::new((blocks_count + new_blocks_count) as u64)
+        );
+    });
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn import_untrusted_snapshot(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let blocks_count = rng.gen_range(10..20);
+        let chain_config =
+            ConfigBuilder::test_chain().max_depth_for_reorg(BlockDistance::new(1)).build();
+
+        let mut tf1 = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+        create_pool(&mut tf1, &mut rng);
+        tf1.create_chain(&tf1.best_block_id(), blocks_count - 1, &mut rng).unwrap();
+
+        let snapshot_height = rng.gen_range(1..blocks_count) as u64;
+        let (snapshot, _) = export_snapshot(&mut tf1, snapshot_height).unwrap();
+
+        // No commitment is trusted at the snapshot height
+        let other_height = BlockHeight::new(snapshot_height + 1);
+        let chain_config = ConfigBuilder::test_chain()
+            .snapshot_commitments([(other_height, H256::random_using(&mut rng))].into())
+            .build();
+        let mut tf2 = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+        assert_eq!(
+            import_snapshot(&mut tf2, &snapshot).unwrap_err(),
+            ChainstateError::UtxoSnapshotError(UtxoSnapshotError::UntrustedSnapshot(
+                BlockHeight::new(snapshot_height)
+            ))
+        );
+        assert_eq!(tf2.best_block_id(), tf2.genesis().get_id());
+
+        // The trusted commitment is different
+        let expected = H256::random_using(&mut rng);
+        let chain_config = ConfigBuilder::test_chain()
+            .snapshot_commitments([(BlockHeight::new(snapshot_height), expected)].into())
+            .build();
+        let mut tf2 = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+        let genesis_utxos = utxo_set(&tf2);
+        let genesis_state = state_data(&tf2);
+        assert!(matches!(
+            import_snapshot(&mut tf2, &snapshot).unwrap_err(),
+            ChainstateError::UtxoSnapshotError(UtxoSnapshotError::CommitmentMismatch {
+                expected: e,
+                actual: _,
+            }) if e == expected
+        ));
+        assert_eq!(tf2.best_block_id(), tf2.genesis().get_id());
+        assert_eq!(utxo_set(&tf2), genesis_utxos);
+        assert_eq!(state_data(&tf2), genesis_state);
+    });
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn import_checkpoint_mismatch(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let blocks_count = rng.gen_range(10..20);
+        let chain_config =
+            ConfigBuilder::test_chain().max_depth_for_reorg(BlockDistance::new(1)).build();
+
+        let mut tf1 = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+        tf1.create_chain(&tf1.genesis().get_id().into(), blocks_count, &mut rng)
+            .unwrap();
+
+        let snapshot_height = rng.gen_range(1..blocks_count) as u64;
+        let (snapshot, commitment) = export_snapshot(&mut tf1, snapshot_height).unwrap();
+
+        // The node expects a different block at some height covered by the snapshot
+        let checkpoint_height = BlockHeight::new(rng.gen_range(1..=snapshot_height));
+        let checkpoint_id: Id<GenBlock> = Id::new(H256::random_using(&mut rng));
+        let chain_config = ConfigBuilder::test_chain()
+            .checkpoints([(checkpoint_height, checkpoint_id)].into())
+            .snapshot_commitments([(BlockHeight::new(snapshot_height), commitment)].into())
+            .build();
+        let mut tf2 = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+
+        assert_eq!(
+            import_snapshot(&mut tf2, &snapshot).unwrap_err(),
+            ChainstateError::UtxoSnapshotError(UtxoSnapshotError::BlockIdMismatch(
+                checkpoint_height,
+                tf1.block_id(checkpoint_height.into_int()),
+                checkpoint_id
+            ))
+        );
+        assert_eq!(tf2.best_block_id(), tf2.genesis().get_id());
+    });
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn utxo_set_commitment(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf1 = TestFramework::builder(&mut rng).build();
+        let mut tf2 = TestFramework::builder(&mut rng).build();
+
+        let commitment_of = |tf: &TestFramework| {
+            chainstate::compute_utxo_commitment(
+                utxo_set(tf).into_iter().map(|(outpoint, utxo)| (outpoint, utxo.take_output())),
+            )
+        };
+
+        let mut commitment = tf1.chainstate.get_utxo_set_commitment().unwrap();
+        assert_eq!(commitment, commitment_of(&tf1));
+        assert_eq!(
+            tf2.chainstate.get_utxo_set_commitment().unwrap(),
+            commitment
+        );
+
+        for _ in 0..rng.gen_range(1..10) {
+            // Each block spends a UTXO and creates new ones
+            let block = tf1
+                .make_block_builder()
+                .add_test_transaction_from_best_block(&mut rng)
+                .build(&mut rng);
+            tf1.process_block(block.clone(), BlockSource::Local).unwrap();
+            tf2.process_block(block, BlockSource::Local).unwrap();
+
+            let new_commitment = tf1.chainstate.get_utxo_set_commitment().unwrap();
+            assert_ne!(new_commitment, commitment);
+            assert_eq!(new_commitment, commitment_of(&tf1));
+
+            // The commitment only depends on the UTXO set
+            assert_eq!(
+                tf2.chainstate.get_utxo_set_commitment().unwrap(),
+                new_commitment
+            );
+            commitment = new_commitment;
+        }
+    });
+}
@@ -25,6 +25,6 @@ pub use transaction_verifier::{
         TransactionVerifierStorageRef,
     },
     timelock_check,
-    tokens_check::{check_nft_issuance_data, check_tokens_issuance},
+    tokens_check::{check_nft_issuance_data, check_tokens_issuance, MetadataExt},
     TransactionSource, TransactionVerifier,
 };
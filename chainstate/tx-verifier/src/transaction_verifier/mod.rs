@@ -63,7 +63,9 @@ use self::{
     utxos_undo_cache::{CachedUtxoBlockUndoOp, UtxosBlockUndoCache},
 };
 use ::utils::{ensure, shallow_clone::ShallowClone};
-pub use reward_distribution::{distribute_pos_reward, RewardDistributionError};
+pub use reward_distribution::{
+    calculate_pool_reward_shares, distribute_pos_reward, PoolRewardShares, RewardDistributionError,
+};
 
 use chainstate_types::{BlockIndex, TipStorageTag};
 use common::{
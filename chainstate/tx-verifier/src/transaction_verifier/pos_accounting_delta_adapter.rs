@@ -201,6 +201,22 @@ impl<'a, P: PoSAccountingView> PoSAccountingOperations<PoSAccountingUndo>
         Ok(undo)
     }
 
+    fn slash_pool(
+        &mut self,
+        pool_id: PoolId,
+        slash_amount: Amount,
+    ) -> Result<PoSAccountingUndo, pos_accounting::Error> {
+        log::debug!("Slashing {:?} coins from pool: {}", slash_amount, pool_id);
+
+        let mut delta = PoSAccountingDelta::new(&self.adapter.accounting_delta);
+
+        let undo = delta.slash_pool(pool_id, slash_amount)?;
+
+        self.merge_delta(delta.consume())?;
+
+        Ok(undo)
+    }
+
     fn create_delegation_id(
         &mut self,
         target_pool: PoolId,
@@ -60,21 +60,36 @@ pub enum RewardDistributionError {
     StakerRewardOverflow(PoolId, Amount, Amount, Amount),
 }
 
-/// Distribute reward among the staker and delegations
-pub fn distribute_pos_reward<
-    U,
-    P: PoSAccountingView<Error = pos_accounting::Error> + PoSAccountingOperations<U>,
->(
-    accounting_adapter: &mut P,
+/// The parts of a pool reward that go to the staker and to each of the pool's delegations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolRewardShares {
+    staker_reward: Amount,
+    delegation_rewards: BTreeMap<DelegationId, Amount>,
+}
+
+impl PoolRewardShares {
+    pub fn staker_reward(&self) -> Amount {
+        self.staker_reward
+    }
+
+    pub fn delegation_rewards(&self) -> &BTreeMap<DelegationId, Amount> {
+        &self.delegation_rewards
+    }
+}
+
+/// Calculate how the reward of a pool is split among the staker and delegations,
+/// without modifying the accounting state
+pub fn calculate_pool_reward_shares<P: PoSAccountingView<Error = pos_accounting::Error>>(
+    accounting_view: &P,
     block_id: Id<Block>,
     pool_id: PoolId,
     total_reward: Amount,
     reward_distribution_version: RewardDistributionVersion,
-) -> Result<Vec<U>, RewardDistributionError> {
-    let pool_data = accounting_adapter
+) -> Result<PoolRewardShares, RewardDistributionError> {
+    let pool_data = accounting_view
         .get_pool_data(pool_id)?
         .ok_or(RewardDistributionError::PoolDataNotFound(pool_id))?;
-    let pool_balance = accounting_adapter.get_pool_balance(pool_id)?;
+    let pool_balance = accounting_view.get_pool_balance(pool_id)?;
 
     let staker_reward = match reward_distribution_version {
         RewardDistributionVersion::V0 => calculate_staker_reward_v0(
@@ -106,8 +121,8 @@ pub fn distribute_pos_reward<
 
     // Distribute reward among delegators.
     // In some cases this process can yield reward unallocated to delegators. This reward goes to the staker.
-    let (delegation_undos, unallocated_reward) = if total_delegations_reward > Amount::ZERO {
-        match accounting_adapter.get_pool_delegations_shares(pool_id)? {
+    let (delegation_rewards, unallocated_reward) = if total_delegations_reward > Amount::ZERO {
+        match accounting_view.get_pool_delegations_shares(pool_id)? {
             Some(delegation_shares) => {
                 let total_delegations_balance =
                     delegation_shares.values().copied().sum::<Option<Amount>>().ok_or(
@@ -115,8 +130,7 @@ pub fn distribute_pos_reward<
                     )?;
 
                 if total_delegations_balance > Amount::ZERO {
-                    distribute_delegations_pos_reward(
-                        accounting_adapter,
+                    calculate_delegations_pos_reward(
                         &delegation_shares,
                         block_id,
                         pool_id,
@@ -125,21 +139,59 @@ pub fn distribute_pos_reward<
                     )?
                 } else {
                     // If total balance of all delegations is 0 then give the reward to the staker
-                    (Vec::new(), total_delegations_reward)
+                    (BTreeMap::new(), total_delegations_reward)
                 }
             }
             // If no delegations then give the reward to the staker
-            None => (Vec::new(), total_delegations_reward),
+            None => (BTreeMap::new(), total_delegations_reward),
         }
     } else {
         // Do nothing if no delegations reward
-        (Vec::new(), Amount::ZERO)
+        (BTreeMap::new(), Amount::ZERO)
     };
 
-    let total_staker_reward = (staker_reward + unallocated_reward)
+    let staker_reward = (staker_reward + unallocated_reward)
         .ok_or(RewardDistributionError::RewardAdditionError(block_id))?;
+
+    Ok(PoolRewardShares {
+        staker_reward,
+        delegation_rewards,
+    })
+}
+
+/// Distribute reward among the staker and delegations
+pub fn distribute_pos_reward<
+    U,
+    P: PoSAccountingView<Error = pos_accounting::Error> + PoSAccountingOperations<U>,
+>(
+    accounting_adapter: &mut P,
+    block_id: Id<Block>,
+    pool_id: PoolId,
+    total_reward: Amount,
+    reward_distribution_version: RewardDistributionVersion,
+) -> Result<Vec<U>, RewardDistributionError> {
+    let shares = calculate_pool_reward_shares(
+        accounting_adapter,
+        block_id,
+        pool_id,
+        total_reward,
+        reward_distribution_version,
+    )?;
+
+    // increase the delegation balances
+    let delegation_undos = shares
+        .delegation_rewards
+        .iter()
+        .filter(|(_, reward)| **reward > Amount::ZERO)
+        .map(|(delegation_id, reward)| {
+            accounting_adapter
+                .delegate_staking(*delegation_id, *reward)
+                .map_err(RewardDistributionError::PoSAccountingError)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
     let increase_pool_balance_undo =
-        accounting_adapter.increase_staker_rewards(pool_id, total_staker_reward)?;
+        accounting_adapter.increase_staker_rewards(pool_id, shares.staker_reward)?;
 
     let undos = delegation_undos
         .into_iter()
@@ -230,14 +282,13 @@ fn calculate_staker_reward_v1(
 }
 
 /// The reward is distributed among delegations proportionally to their balance
-fn distribute_delegations_pos_reward<U, P: PoSAccountingView + PoSAccountingOperations<U>>(
-    accounting_adapter: &mut P,
+fn calculate_delegations_pos_reward(
     delegation_shares: &BTreeMap<DelegationId, Amount>,
     block_id: Id<Block>,
     pool_id: PoolId,
     total_delegations_balance: Amount,
     total_delegations_reward: Amount,
-) -> Result<(Vec<U>, Amount), RewardDistributionError> {
+) -> Result<(BTreeMap<DelegationId, Amount>, Amount), RewardDistributionError> {
     let rewards_per_delegation = calculate_rewards_per_delegation(
         delegation_shares.iter(),
         pool_id,
@@ -245,17 +296,6 @@ fn distribute_delegations_pos_reward<U, P: PoSAccountingView + PoSAccountingOper
         total_delegations_reward,
     )?;
 
-    // increase the delegation balances
-    let delegation_undos = rewards_per_delegation
-        .iter()
-        .filter(|(_, reward)| *reward > Amount::ZERO)
-        .map(|(delegation_id, reward)| {
-            accounting_adapter
-                .delegate_staking(*delegation_id, *reward)
-                .map_err(RewardDistributionError::PoSAccountingError)
-        })
-        .collect::<Result<Vec<_>, _>>()?;
-
     // Due to integer arithmetics there can be a small remainder after all the delegations distributed.
     // This remainder goes to the staker
     let total_delegations_reward_distributed =
@@ -272,7 +312,10 @@ fn distribute_delegations_pos_reward<U, P: PoSAccountingView + PoSAccountingOper
                 total_delegations_reward,
             ),
         )?;
-    Ok((delegation_undos, delegations_reward_remainder))
+    Ok((
+        rewards_per_delegation.into_iter().collect(),
+        delegations_reward_remainder,
+    ))
 }
 
 fn calculate_rewards_per_delegation<'a, I: Iterator<Item = (&'a DelegationId, &'a Amount)>>(
@@ -332,7 +375,7 @@ mod tests {
         DelegationData, FlushablePoSAccountingView, InMemoryPoSAccounting, PoSAccountingDB,
         PoolData,
     };
-    use randomness::Rng;
+    use randomness::{CryptoRng, Rng};
     use rstest::rstest;
     use std::collections::BTreeMap;
     use test_utils::random::{make_seedable_rng, Seed};
@@ -1164,4 +1207,164 @@ mod tests {
 
         assert_eq!(store, expected_store);
     }
+
+    fn make_store_with_delegations(
+        rng: &mut (impl Rng + CryptoRng),
+        pledged_amount: Amount,
+        delegated_amounts: &[Amount],
+        mpt: PerThousand,
+        cost_per_block: Amount,
+    ) -> (PoolId, Vec<DelegationId>, InMemoryPoSAccounting) {
+        let pool_id = new_pool_id(1);
+        let delegation_ids = (0..delegated_amounts.len())
+            .map(|i| new_delegation_id(i as u64 + 1))
+            .collect::<Vec<_>>();
+        let pool_balance = delegated_amounts
+            .iter()
+            .fold(pledged_amount, |acc, amount| (acc + *amount).unwrap());
+
+        let pool_data = PoolData::new(
+            Destination::AnyoneCanSpend,
+            pledged_amount,
+            Amount::ZERO,
+            VRFPrivateKey::new_from_rng(rng, VRFKeyKind::Schnorrkel).1,
+            mpt,
+            cost_per_block,
+        );
+        let delegation_data = DelegationData::new(pool_id, Destination::AnyoneCanSpend);
+
+        let store = InMemoryPoSAccounting::from_values(
+            BTreeMap::from([(pool_id, pool_data)]),
+            BTreeMap::from([(pool_id, pool_balance)]),
+            delegation_ids
+                .iter()
+                .zip(delegated_amounts)
+                .map(|(id, amount)| ((pool_id, *id), *amount))
+                .collect(),
+            delegation_ids.iter().copied().zip(delegated_amounts.iter().copied()).collect(),
+            delegation_ids.iter().map(|id| (*id, delegation_data.clone())).collect(),
+        );
+        (pool_id, delegation_ids, store)
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy(), RewardDistributionVersion::V0)]
+    #[case(Seed::from_entropy(), RewardDistributionVersion::V1)]
+    fn shares_of_nonexisting_pool(#[case] seed: Seed, #[case] version: RewardDistributionVersion) {
+        let mut rng = make_seedable_rng(seed);
+        let block_id = Id::new(H256::random_using(&mut rng));
+        let pool_id = new_pool_id(rng.gen());
+
+        let store = InMemoryPoSAccounting::new();
+        let db = PoSAccountingDB::new(&store);
+
+        assert_eq!(
+            calculate_pool_reward_shares(&db, block_id, pool_id, Amount::from_atoms(1000), version),
+            Err(RewardDistributionError::PoolDataNotFound(pool_id))
+        );
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn shares_uneven_split(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let block_id = Id::new(H256::random_using(&mut rng));
+
+        let (pool_id, delegation_ids, store) = make_store_with_delegations(
+            &mut rng,
+            Amount::from_atoms(1000),
+            &[Amount::from_atoms(100), Amount::from_atoms(200), Amount::from_atoms(400)],
+            PerThousand::new(100).unwrap(),
+            Amount::from_atoms(100),
+        );
+        let db = PoSAccountingDB::new(&store);
+
+        // 900 is left after the cost per block; the staker gets 529 of it pro rata and 37 as
+        // the margin of the remaining 371. The other 334 are split in the 1:2:4 ratio with
+        // a remainder of 2, which goes to the staker as well.
+        let shares = calculate_pool_reward_shares(
+            &db,
+            block_id,
+            pool_id,
+            Amount::from_atoms(1000),
+            RewardDistributionVersion::V1,
+        )
+        .unwrap();
+
+        assert_eq!(shares.staker_reward(), Amount::from_atoms(668));
+        assert_eq!(
+            shares.delegation_rewards(),
+            &BTreeMap::from([
+                (delegation_ids[0], Amount::from_atoms(47)),
+                (delegation_ids[1], Amount::from_atoms(95)),
+                (delegation_ids[2], Amount::from_atoms(190)),
+            ])
+        );
+    }
+
+    // The shares must be exactly what the distribution applies to the accounting state
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy(), RewardDistributionVersion::V0)]
+    #[case(Seed::from_entropy(), RewardDistributionVersion::V1)]
+    fn shares_match_distribution(#[case] seed: Seed, #[case] version: RewardDistributionVersion) {
+        let mut rng = make_seedable_rng(seed);
+        let block_id = Id::new(H256::random_using(&mut rng));
+
+        let delegated_amounts = (0..rng.gen_range(0..20))
+            .map(|_| Amount::from_atoms(rng.gen_range(0..1_000_000)))
+            .collect::<Vec<_>>();
+        let pledged_amount = Amount::from_atoms(rng.gen_range(1..1_000_000));
+        let reward = Amount::from_atoms(rng.gen_range(0..1_000_000));
+        let cost_per_block = Amount::from_atoms(rng.gen_range(0..1000));
+        let mpt = PerThousand::new_from_rng(&mut rng);
+        let (pool_id, delegation_ids, mut store) = make_store_with_delegations(
+            &mut rng,
+            pledged_amount,
+            &delegated_amounts,
+            mpt,
+            cost_per_block,
+        );
+
+        let shares = {
+            let db = PoSAccountingDB::new(&store);
+            calculate_pool_reward_shares(&db, block_id, pool_id, reward, version).unwrap()
+        };
+
+        // Nothing is lost to rounding
+        let total_shares = shares
+            .delegation_rewards()
+            .values()
+            .fold(shares.staker_reward(), |acc, reward| {
+                (acc + *reward).unwrap()
+            });
+        assert_eq!(total_shares, reward);
+
+        let mut db = PoSAccountingDB::new(&mut store);
+        let mut accounting_adapter = PoSAccountingDeltaAdapter::new(&mut db);
+        {
+            let mut accounting_adapter =
+                accounting_adapter.operations(TransactionSource::Chain(block_id));
+            distribute_pos_reward(&mut accounting_adapter, block_id, pool_id, reward, version)
+                .unwrap();
+        }
+        let (consumed, _) = accounting_adapter.consume();
+        db.batch_write_delta(consumed).unwrap();
+
+        let db = PoSAccountingDB::new(&store);
+        assert_eq!(
+            db.get_pool_data(pool_id).unwrap().unwrap().staker_rewards(),
+            shares.staker_reward()
+        );
+        for (delegation_id, delegated_amount) in delegation_ids.iter().zip(&delegated_amounts) {
+            let reward =
+                shares.delegation_rewards().get(delegation_id).copied().unwrap_or(Amount::ZERO);
+            assert_eq!(
+                db.get_delegation_balance(*delegation_id).unwrap(),
+                (*delegated_amount + reward).unwrap()
+            );
+        }
+    }
 }
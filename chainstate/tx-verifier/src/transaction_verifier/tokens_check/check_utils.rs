@@ -97,3 +97,17 @@ pub fn check_nft_description(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uri_validity() {
+        assert!(is_uri_valid(b"https://example.com/token.json"));
+        assert!(is_uri_valid(b""));
+
+        assert!(!is_uri_valid(b"https://example.com/\0token.json"));
+        assert!(!is_uri_valid(b"https://example.com/\xfftoken.json"));
+    }
+}
@@ -18,7 +18,7 @@ use self::check_utils::check_media_hash;
 use crate::error::TokenIssuanceError;
 
 use common::chain::{
-    tokens::{NftIssuanceV0, TokenIssuance},
+    tokens::{Metadata, NftIssuanceV0, TokenIssuance},
     ChainConfig,
 };
 use serialization::{DecodeAll, Encode};
@@ -30,51 +30,63 @@ pub fn check_nft_issuance_data(
     chain_config: &ChainConfig,
     issuance: &NftIssuanceV0,
 ) -> Result<(), TokenIssuanceError> {
-    check_utils::check_token_ticker(chain_config, &issuance.metadata.ticker)?;
-    check_utils::check_nft_name(chain_config, &issuance.metadata.name)?;
-    check_utils::check_nft_description(chain_config, &issuance.metadata.description)?;
+    issuance.metadata.validate_sizes(chain_config)
+}
 
-    let icon_uri = Vec::<u8>::decode_all(&mut issuance.metadata.icon_uri.encode().as_slice())
-        .map_err(|_| TokenIssuanceError::IssueErrorIncorrectIconURI)?;
-    if !icon_uri.is_empty() {
-        ensure!(
-            icon_uri.len() <= chain_config.token_max_uri_len(),
-            TokenIssuanceError::IssueErrorIncorrectIconURI
-        );
-        ensure!(
-            check_utils::is_uri_valid(&icon_uri),
-            TokenIssuanceError::IssueErrorIncorrectIconURI
-        );
-    }
+/// Checks of the NFT metadata on its own, so that an invalid field can be reported
+/// before the issuance transaction is even constructed
+pub trait MetadataExt {
+    fn validate_sizes(&self, chain_config: &ChainConfig) -> Result<(), TokenIssuanceError>;
+}
 
-    let additional_metadata_uri =
-        Vec::<u8>::decode_all(&mut issuance.metadata.additional_metadata_uri.encode().as_slice())
-            .map_err(|_| TokenIssuanceError::IssueErrorIncorrectMetadataURI)?;
-    if !additional_metadata_uri.is_empty() {
-        ensure!(
-            additional_metadata_uri.len() <= chain_config.token_max_uri_len(),
-            TokenIssuanceError::IssueErrorIncorrectMetadataURI
-        );
-        ensure!(
-            check_utils::is_uri_valid(&additional_metadata_uri),
-            TokenIssuanceError::IssueErrorIncorrectMetadataURI
-        );
-    }
+impl MetadataExt for Metadata {
+    fn validate_sizes(&self, chain_config: &ChainConfig) -> Result<(), TokenIssuanceError> {
+        check_utils::check_token_ticker(chain_config, &self.ticker)?;
+        check_utils::check_nft_name(chain_config, &self.name)?;
+        check_utils::check_nft_description(chain_config, &self.description)?;
+
+        let icon_uri = Vec::<u8>::decode_all(&mut self.icon_uri.encode().as_slice())
+            .map_err(|_| TokenIssuanceError::IssueErrorIncorrectIconURI)?;
+        if !icon_uri.is_empty() {
+            ensure!(
+                icon_uri.len() <= chain_config.token_max_uri_len(),
+                TokenIssuanceError::IssueErrorIncorrectIconURI
+            );
+            ensure!(
+                check_utils::is_uri_valid(&icon_uri),
+                TokenIssuanceError::IssueErrorIncorrectIconURI
+            );
+        }
+
+        let additional_metadata_uri =
+            Vec::<u8>::decode_all(&mut self.additional_metadata_uri.encode().as_slice())
+                .map_err(|_| TokenIssuanceError::IssueErrorIncorrectMetadataURI)?;
+        if !additional_metadata_uri.is_empty() {
+            ensure!(
+                additional_metadata_uri.len() <= chain_config.token_max_uri_len(),
+                TokenIssuanceError::IssueErrorIncorrectMetadataURI
+            );
+            ensure!(
+                check_utils::is_uri_valid(&additional_metadata_uri),
+                TokenIssuanceError::IssueErrorIncorrectMetadataURI
+            );
+        }
 
-    let media_uri = Vec::<u8>::decode_all(&mut issuance.metadata.media_uri.encode().as_slice())
-        .map_err(|_| TokenIssuanceError::IssueErrorIncorrectMediaURI)?;
-    if !media_uri.is_empty() {
-        ensure!(
-            media_uri.len() <= chain_config.token_max_uri_len(),
-            TokenIssuanceError::IssueErrorIncorrectMediaURI
-        );
-        ensure!(
-            check_utils::is_uri_valid(&media_uri),
-            TokenIssuanceError::IssueErrorIncorrectMediaURI
-        );
+        let media_uri = Vec::<u8>::decode_all(&mut self.media_uri.encode().as_slice())
+            .map_err(|_| TokenIssuanceError::IssueErrorIncorrectMediaURI)?;
+        if !media_uri.is_empty() {
+            ensure!(
+                media_uri.len() <= chain_config.token_max_uri_len(),
+                TokenIssuanceError::IssueErrorIncorrectMediaURI
+            );
+            ensure!(
+                check_utils::is_uri_valid(&media_uri),
+                TokenIssuanceError::IssueErrorIncorrectMediaURI
+            );
+        }
+        check_media_hash(chain_config, &self.media_hash)?;
+        Ok(())
     }
-    check_media_hash(chain_config, &issuance.metadata.media_hash)?;
-    Ok(())
 }
 
 pub fn check_tokens_issuance(
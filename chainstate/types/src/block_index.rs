@@ -127,6 +127,18 @@ impl BlockIndex {
         self
     }
 
+    /// Whether the block body can be obtained from the db, i.e. the block has been persisted
+    /// and its body hasn't been pruned since.
+    pub fn has_body(&self) -> bool {
+        self.is_persisted && !self.status.is_body_pruned()
+    }
+
+    /// Mark the body of the block as pruned, the block itself stays persisted and valid.
+    pub fn with_body_pruned(mut self) -> Self {
+        self.status.set_body_pruned();
+        self
+    }
+
     pub fn into_block_header(self) -> SignedBlockHeader {
         self.block_header
     }
@@ -97,6 +97,16 @@ impl BlockStatus {
         self.get_field(BlockStatusBitArea::ExplicitlyInvalidatedBit) != 0
     }
 
+    /// Mark the block as one whose body has been deleted from the db (or was never downloaded,
+    /// because it's covered by a UTXO snapshot). This is not a failure, the block stays valid.
+    pub fn set_body_pruned(&mut self) {
+        self.set_field(BlockStatusBitArea::BodyPrunedBit, 1)
+    }
+
+    pub fn is_body_pruned(&self) -> bool {
+        self.get_field(BlockStatusBitArea::BodyPrunedBit) != 0
+    }
+
     pub fn with_cleared_fail_bits(&self) -> BlockStatus {
         let mut result = *self;
         result.set_bits(Self::bit_range_of_range(FAIL_BITS_RANGE), 0);
@@ -164,13 +174,14 @@ enum BlockStatusBitArea {
     ValidationFailedBit = 8,
     InvalidParentBit,
     ExplicitlyInvalidatedBit,
+    BodyPrunedBit,
     ReservedArea,
     End = 64,
 }
 
 const FAIL_BITS_RANGE: Range<BlockStatusBitArea> = Range::<BlockStatusBitArea> {
     start: BlockStatusBitArea::ValidationFailedBit,
-    end: BlockStatusBitArea::ReservedArea,
+    end: BlockStatusBitArea::BodyPrunedBit,
 };
 
 #[cfg(test)]
@@ -196,9 +207,13 @@ mod tests {
             BlockStatus::bit_range_of(BlockStatusBitArea::ExplicitlyInvalidatedBit),
             10..11
         );
+        assert_eq!(
+            BlockStatus::bit_range_of(BlockStatusBitArea::BodyPrunedBit),
+            11..12
+        );
         assert_eq!(
             BlockStatus::bit_range_of(BlockStatusBitArea::ReservedArea),
-            11..64
+            12..64
         );
 
         assert!(catch_unwind(|| BlockStatus::bit_range_of(BlockStatusBitArea::End)).is_err());
@@ -208,7 +223,7 @@ mod tests {
     fn test_bit_range_of_range() {
         let small_range = Range::<BlockStatusBitArea> {
             start: BlockStatusBitArea::ValidationFailedBit,
-            end: BlockStatusBitArea::ReservedArea,
+            end: BlockStatusBitArea::BodyPrunedBit,
         };
 
         assert_eq!(BlockStatus::bit_range_of_range(small_range), 8..11);
@@ -305,9 +320,10 @@ mod tests {
             status.get_field(BlockStatusBitArea::ExplicitlyInvalidatedBit),
             0
         );
+        assert_eq!(status.get_field(BlockStatusBitArea::BodyPrunedBit), 1);
         assert_eq!(
             status.get_field(BlockStatusBitArea::ReservedArea),
-            0b11110000_10101
+            0b11110000_1010
         );
 
         let status = BlockStatus(pattern << 1);
@@ -322,9 +338,10 @@ mod tests {
             status.get_field(BlockStatusBitArea::ExplicitlyInvalidatedBit),
             1
         );
+        assert_eq!(status.get_field(BlockStatusBitArea::BodyPrunedBit), 0);
         assert_eq!(
             status.get_field(BlockStatusBitArea::ReservedArea),
-            0b11110000_101010
+            0b11110000_10101
         );
     }
 
@@ -346,13 +363,16 @@ mod tests {
         status.set_field(BlockStatusBitArea::ExplicitlyInvalidatedBit, 1);
         assert_eq!(status.0, 0b111_11001100);
 
+        status.set_field(BlockStatusBitArea::BodyPrunedBit, 1);
+        assert_eq!(status.0, 0b1111_11001100);
+
         status.set_field(
             BlockStatusBitArea::ReservedArea,
-            0b10101010_10101010_10101010_10101010_10101010_10101010_10101,
+            0b10101010_10101010_10101010_10101010_10101010_10101010_1010,
         );
         assert_eq!(
             status.0,
-            0b10101010_10101010_10101010_10101010_10101010_10101010_10101_111_11001100
+            0b10101010_10101010_10101010_10101010_10101010_10101010_1010_1111_11001100
         );
     }
 
@@ -383,12 +403,18 @@ mod tests {
         })
         .is_err());
 
+        assert!(catch_unwind(|| {
+            let mut status = BlockStatus(0);
+            status.set_field(BlockStatusBitArea::BodyPrunedBit, 2);
+        })
+        .is_err());
+
         assert!(catch_unwind(|| {
             let mut status = BlockStatus(0);
             status.set_field(
                 BlockStatusBitArea::ReservedArea,
                 // This is the value from test_set_field but with an additional 1 an the end.
-                0b10101010_10101010_10101010_10101010_10101010_10101010_101011,
+                0b10101010_10101010_10101010_10101010_10101010_10101010_10101,
             );
         })
         .is_err());
@@ -534,4 +560,21 @@ mod tests {
         assert!(!new_status.has_invalid_parent());
         assert!(!new_status.is_explicitly_invalidated());
     }
+
+    #[test]
+    fn test_set_body_pruned() {
+        let mut status = BlockStatus::new_fully_checked();
+        assert!(!status.is_body_pruned());
+
+        status.set_body_pruned();
+        assert!(status.is_body_pruned());
+        assert!(status.is_ok());
+        assert!(status.is_fully_valid());
+        assert_eq!(status.reserved_bits(), 0);
+
+        status.set_validation_failed();
+        let new_status = status.with_cleared_fail_bits();
+        assert!(new_status.is_fully_valid());
+        assert!(new_status.is_body_pruned());
+    }
 }
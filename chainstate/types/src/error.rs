@@ -51,6 +51,8 @@ pub enum PropertyQueryError {
     PoolBalanceNotFound(PoolId),
     #[error("Invalid starting block height: {0}")]
     InvalidStartingBlockHeightForMainchainBlocks(BlockHeight),
+    #[error("The body of block {0} at height {1} has been pruned")]
+    BlockBodyPruned(Id<Block>, BlockHeight),
     #[error("Invalid block height range: {start}..{end}")]
     InvalidBlockHeightRange {
         start: BlockHeight,
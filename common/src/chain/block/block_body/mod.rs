@@ -14,7 +14,7 @@
 // limitations under the License.
 
 mod block_merkle;
-mod merkle_tools;
+pub mod merkle_tools;
 
 pub mod merkle_proxy;
 
@@ -19,7 +19,8 @@ use crate::{
     chain::{
         config::{
             create_mainnet_genesis, create_testnet_genesis, create_unit_test_genesis,
-            emission_schedule, ChainConfig, ChainType, EmissionScheduleTabular,
+            create_unit_test_genesis_with_utxos, emission_schedule, ChainConfig, ChainType,
+            EmissionScheduleTabular,
         },
         get_initial_randomness,
         pos::{
@@ -309,6 +310,7 @@ enum EmissionScheduleInit {
 #[derive(Clone)]
 enum GenesisBlockInit {
     UnitTest { premine_destination: Destination },
+    UnitTestWithUtxos { utxos: Vec<(Destination, Amount)> },
     Mainnet,
     Testnet,
     Custom(Genesis),
@@ -326,6 +328,7 @@ pub struct Builder {
     chain_type: ChainType,
     bip44_coin_type: ChildNumber,
     checkpoints: Option<BTreeMap<BlockHeight, Id<GenBlock>>>,
+    snapshot_commitments: BTreeMap<BlockHeight, H256>,
     magic_bytes: MagicBytes,
     p2p_port: u16,
     dns_seeds: Vec<&'static str>,
@@ -359,6 +362,7 @@ pub struct Builder {
     empty_consensus_reward_maturity_block_count: BlockCount,
     max_classic_multisig_public_keys_count: usize,
     min_stake_pool_pledge: Amount,
+    token_fee_multiplier: f64,
 }
 
 impl Builder {
@@ -371,6 +375,7 @@ impl Builder {
             chain_type,
             bip44_coin_type: chain_type.default_bip44_coin_type(),
             checkpoints: None,
+            snapshot_commitments: BTreeMap::new(),
             coin_decimals: CoinUnit::DECIMALS,
             coin_ticker: chain_type.coin_ticker(),
             magic_bytes: chain_type.magic_bytes(),
@@ -406,6 +411,7 @@ impl Builder {
             empty_consensus_reward_maturity_block_count: BlockCount::new(0),
             max_classic_multisig_public_keys_count: super::MAX_CLASSIC_MULTISIG_PUBLIC_KEYS_COUNT,
             min_stake_pool_pledge: super::MIN_STAKE_POOL_PLEDGE,
+            token_fee_multiplier: super::DEFAULT_TOKEN_FEE_MULTIPLIER,
         }
     }
 
@@ -425,6 +431,7 @@ impl Builder {
             chain_type,
             bip44_coin_type,
             checkpoints,
+            snapshot_commitments,
             coin_decimals,
             coin_ticker,
             magic_bytes,
@@ -458,6 +465,7 @@ impl Builder {
             empty_consensus_reward_maturity_block_count,
             max_classic_multisig_public_keys_count,
             min_stake_pool_pledge,
+            token_fee_multiplier,
         } = self;
 
         let emission_table = match emission_schedule {
@@ -476,6 +484,9 @@ impl Builder {
             GenesisBlockInit::UnitTest {
                 premine_destination,
             } => create_unit_test_genesis(premine_destination),
+            GenesisBlockInit::UnitTestWithUtxos { utxos } => {
+                create_unit_test_genesis_with_utxos(utxos)
+            }
         };
         let genesis_block = Arc::new(WithId::new(genesis_block));
 
@@ -536,6 +547,7 @@ impl Builder {
             target_block_spacing,
             genesis_block,
             height_checkpoint_data,
+            snapshot_commitments,
             emission_schedule,
             final_supply,
             consensus_upgrades,
@@ -550,6 +562,7 @@ impl Builder {
             token_max_hash_len,
             max_classic_multisig_public_keys_count,
             min_stake_pool_pledge,
+            token_fee_multiplier,
         }
     }
 }
@@ -589,6 +602,18 @@ impl Builder {
     builder_method!(sealed_epoch_distance_from_tip: usize);
     builder_method!(data_deposit_max_size: Option<usize>);
     builder_method!(min_stake_pool_pledge: Amount);
+    builder_method!(snapshot_commitments: BTreeMap<BlockHeight, H256>);
+
+    /// Set the factor by which the fee rate of transactions that transfer tokens is multiplied.
+    /// It must be a finite number not less than 1, so that such transactions never pay less.
+    pub fn token_fee_multiplier(mut self, token_fee_multiplier: f64) -> Self {
+        assert!(
+            token_fee_multiplier.is_finite() && token_fee_multiplier >= 1.0,
+            "Invalid token fee multiplier: {token_fee_multiplier}"
+        );
+        self.token_fee_multiplier = token_fee_multiplier;
+        self
+    }
 
     pub fn checkpoints(mut self, checkpoints: BTreeMap<BlockHeight, Id<GenBlock>>) -> Self {
         self.checkpoints = Some(checkpoints);
@@ -603,6 +628,18 @@ impl Builder {
         self
     }
 
+    /// Set the genesis block to be the unit test version that sends the specified amounts
+    /// of coins to the specified destinations instead of the premine
+    pub fn genesis_unittest_with_utxos(
+        mut self,
+        utxos: impl IntoIterator<Item = (Destination, Amount)>,
+    ) -> Self {
+        self.genesis_block = GenesisBlockInit::UnitTestWithUtxos {
+            utxos: utxos.into_iter().collect(),
+        };
+        self
+    }
+
     /// Set genesis block to be the mainnet genesis
     pub fn genesis_mainnet(mut self) -> Self {
         self.genesis_block = GenesisBlockInit::Mainnet;
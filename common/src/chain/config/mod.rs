@@ -28,7 +28,7 @@ pub use emission_schedule::{EmissionSchedule, EmissionScheduleFn, EmissionSchedu
 use utils::const_nz_u64;
 
 use std::fmt::{Debug, Display};
-use std::{net::SocketAddr, num::NonZeroU64, sync::Arc, time::Duration};
+use std::{collections::BTreeMap, net::SocketAddr, num::NonZeroU64, sync::Arc, time::Duration};
 
 use hex::FromHex;
 
@@ -258,6 +258,7 @@ pub struct ChainConfig {
     chain_type: ChainType,
     bip44_coin_type: ChildNumber,
     height_checkpoint_data: Checkpoints,
+    snapshot_commitments: BTreeMap<BlockHeight, H256>,
     consensus_upgrades: NetUpgrades<ConsensusUpgrade>,
     chainstate_upgrades: NetUpgrades<ChainstateUpgrade>,
     magic_bytes: MagicBytes,
@@ -293,6 +294,7 @@ pub struct ChainConfig {
     empty_consensus_reward_maturity_block_count: BlockCount,
     max_classic_multisig_public_keys_count: usize,
     min_stake_pool_pledge: Amount,
+    token_fee_multiplier: f64,
 }
 
 impl ChainConfig {
@@ -430,6 +432,13 @@ impl ChainConfig {
         &self.height_checkpoint_data
     }
 
+    /// The trusted commitment to the chainstate snapshot taken at the given height, if any.
+    /// Only snapshots with a trusted commitment can be imported.
+    #[must_use]
+    pub fn snapshot_commitment_at_height(&self, height: &BlockHeight) -> Option<&H256> {
+        self.snapshot_commitments.get(height)
+    }
+
     /// The target time-distance between blocks
     #[must_use]
     pub fn target_block_spacing(&self) -> Duration {
@@ -716,6 +725,12 @@ impl ChainConfig {
         self.min_stake_pool_pledge
     }
 
+    /// The factor by which the fee rate is multiplied for transactions that transfer tokens,
+    /// a finite number not less than 1
+    pub fn token_fee_multiplier(&self) -> f64 {
+        self.token_fee_multiplier
+    }
+
     pub fn final_supply(&self) -> Option<CoinUnit> {
         self.final_supply
     }
@@ -762,6 +777,7 @@ const TOKEN_MAX_DESCRIPTION_LEN: usize = 100;
 const TOKEN_MAX_URI_LEN: usize = 1024;
 const MAX_CLASSIC_MULTISIG_PUBLIC_KEYS_COUNT: usize = 32;
 const MIN_STAKE_POOL_PLEDGE: Amount = Amount::from_atoms(40_000 * CoinUnit::ATOMS_PER_COIN);
+const DEFAULT_TOKEN_FEE_MULTIPLIER: f64 = 1.0;
 
 fn decode_hex<T: serialization::DecodeAll>(hex: &str) -> T {
     let bytes = Vec::from_hex(hex).expect("Hex decoding shouldn't fail");
@@ -867,14 +883,21 @@ fn create_testnet_genesis() -> Genesis {
 }
 
 fn create_unit_test_genesis(premine_destination: Destination) -> Genesis {
+    create_unit_test_genesis_with_utxos(vec![(premine_destination, DEFAULT_INITIAL_MINT)])
+}
+
+fn create_unit_test_genesis_with_utxos(utxos: Vec<(Destination, Amount)>) -> Genesis {
     let genesis_message = String::new();
 
-    let output = TxOutput::Transfer(OutputValue::Coin(DEFAULT_INITIAL_MINT), premine_destination);
+    let outputs = utxos
+        .into_iter()
+        .map(|(destination, amount)| TxOutput::Transfer(OutputValue::Coin(amount), destination))
+        .collect();
 
     Genesis::new(
         genesis_message,
         BlockTimestamp::from_int_seconds(1639975460),
-        vec![output],
+        outputs,
     )
 }
 
@@ -13,15 +13,42 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use thiserror::Error;
+
 use super::{
     htlc::HtlcSecret,
-    signature::{inputsig::InputWitness, Signable, Transactable},
+    output::classic_multisig::ClassicMultisigChallengeError,
+    signature::{
+        inputsig::{
+            authorize_hashed_timelock_contract_spend::AuthorizedHashedTimelockContractSpend,
+            authorize_pubkey_spend::AuthorizedPublicKeySpend,
+            authorize_pubkeyhash_spend::AuthorizedPublicKeyHashSpend,
+            classical_multisig::authorize_classical_multisig::AuthorizedClassicalMultisigSpend,
+            InputWitness,
+        },
+        Signable, Transactable,
+    },
     Destination, Transaction, TxOutput,
 };
-use crate::chain::{SignedTransaction, TransactionCreationError, TxInput};
+use crate::{
+    address::pubkeyhash::PublicKeyHash,
+    chain::{ChainConfig, SignedTransaction, TransactionCreationError, TxInput},
+};
 use serialization::{Decode, Encode};
 use utils::ensure;
 
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ScriptVerificationError {
+    #[error("Witness of input {0} does not match the spending destination")]
+    WitnessMismatch(usize),
+    #[error("Witness of input {0} cannot be decoded")]
+    InvalidScriptBytes(usize),
+    #[error("Unknown destination type for input {0}")]
+    UnknownDestinationType(usize),
+    #[error("Invalid multisig challenge in the witness of input {0}: {1}")]
+    InvalidMultisigChallenge(usize, ClassicMultisigChallengeError),
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Encode, Decode)]
 pub struct PartiallySignedTransaction {
     tx: Transaction,
@@ -128,6 +155,93 @@ impl PartiallySignedTransaction {
             ))
         }
     }
+
+    /// Check that the witness of every signed input can be decoded and matches the destination
+    /// it spends from. Signatures themselves are not verified, so partially signed multisig
+    /// inputs are accepted.
+    pub fn verify_scripts(
+        &self,
+        chain_config: &ChainConfig,
+    ) -> Result<(), ScriptVerificationError> {
+        for (input_index, ((witness, destination), input_utxo)) in
+            self.witnesses.iter().zip(&self.destinations).zip(&self.input_utxos).enumerate()
+        {
+            let sig = match witness {
+                Some(InputWitness::Standard(sig)) => sig,
+                Some(InputWitness::NoSignature(_)) | None => continue,
+            };
+
+            let destination = destination
+                .as_ref()
+                .ok_or(ScriptVerificationError::WitnessMismatch(input_index))?;
+
+            let raw_signature = match input_utxo {
+                Some(TxOutput::Htlc(_, _)) => {
+                    match AuthorizedHashedTimelockContractSpend::from_data(sig.raw_signature())
+                        .map_err(|_| ScriptVerificationError::InvalidScriptBytes(input_index))?
+                    {
+                        AuthorizedHashedTimelockContractSpend::Secret(_, raw_signature)
+                        | AuthorizedHashedTimelockContractSpend::Multisig(raw_signature) => {
+                            raw_signature
+                        }
+                    }
+                }
+                _ => sig.raw_signature().to_vec(),
+            };
+
+            verify_witness_script(chain_config, input_index, destination, &raw_signature)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn verify_witness_script(
+    chain_config: &ChainConfig,
+    input_index: usize,
+    destination: &Destination,
+    raw_signature: &[u8],
+) -> Result<(), ScriptVerificationError> {
+    match destination {
+        Destination::PublicKeyHash(pkh) => {
+            let spend = AuthorizedPublicKeyHashSpend::from_data(raw_signature)
+                .map_err(|_| ScriptVerificationError::InvalidScriptBytes(input_index))?;
+            ensure!(
+                PublicKeyHash::from(spend.public_key()) == *pkh,
+                ScriptVerificationError::WitnessMismatch(input_index)
+            );
+        }
+        Destination::PublicKey(_) => {
+            AuthorizedPublicKeySpend::from_data(raw_signature)
+                .map_err(|_| ScriptVerificationError::InvalidScriptBytes(input_index))?;
+        }
+        Destination::ClassicMultisig(challenge_hash) => {
+            let spend = AuthorizedClassicalMultisigSpend::from_data(raw_signature)
+                .map_err(|_| ScriptVerificationError::InvalidScriptBytes(input_index))?;
+            let challenge = spend.challenge();
+            ensure!(
+                PublicKeyHash::from(challenge) == *challenge_hash,
+                ScriptVerificationError::WitnessMismatch(input_index)
+            );
+            challenge
+                .is_valid(chain_config)
+                .map_err(|e| ScriptVerificationError::InvalidMultisigChallenge(input_index, e))?;
+            ensure!(
+                spend
+                    .public_key_indices()
+                    .all(|idx| (idx as usize) < challenge.public_keys().len()),
+                ScriptVerificationError::WitnessMismatch(input_index)
+            );
+        }
+        Destination::ScriptHash(_) => {
+            return Err(ScriptVerificationError::UnknownDestinationType(input_index))
+        }
+        Destination::AnyoneCanSpend => {
+            return Err(ScriptVerificationError::WitnessMismatch(input_index))
+        }
+    }
+
+    Ok(())
 }
 
 impl Signable for PartiallySignedTransaction {
@@ -153,3 +267,155 @@ impl Transactable for PartiallySignedTransaction {
         self.witnesses.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crypto::key::{KeyKind, PrivateKey};
+    use randomness::{CryptoRng, Rng};
+    use rstest::rstest;
+    use test_utils::random::{make_seedable_rng, Seed};
+
+    use super::*;
+    use crate::{
+        chain::{
+            config::create_unit_test_config,
+            output_value::OutputValue,
+            signature::{
+                inputsig::standard_signature::StandardInputSignature,
+                sighash::sighashtype::SigHashType,
+            },
+        },
+        primitives::{Amount, Id, H256},
+    };
+
+    fn random_destination(rng: &mut (impl Rng + CryptoRng)) -> Destination {
+        let (_, public_key) = PrivateKey::new_from_rng(rng, KeyKind::Secp256k1Schnorr);
+        match rng.gen_range(0..4) {
+            0 => Destination::PublicKeyHash(PublicKeyHash::from(&public_key)),
+            1 => Destination::PublicKey(public_key),
+            2 => Destination::ClassicMultisig(PublicKeyHash::from(&public_key)),
+            _ => Destination::ScriptHash(Id::new(H256::random_using(rng))),
+        }
+    }
+
+    fn make_ptx(
+        rng: &mut (impl Rng + CryptoRng),
+        witness: InputWitness,
+        destination: Option<Destination>,
+    ) -> PartiallySignedTransaction {
+        let utxo = TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(rng.gen_range(1..1_000_000))),
+            Destination::AnyoneCanSpend,
+        );
+        let input_utxos = vec![Some(utxo.clone())];
+        let input = TxInput::from_utxo(Id::<Transaction>::new(H256::random_using(rng)).into(), 0);
+        let tx = Transaction::new(0, vec![input], vec![utxo]).unwrap();
+        PartiallySignedTransaction::new(
+            tx,
+            vec![Some(witness)],
+            input_utxos,
+            vec![destination],
+            None,
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn random_witness_bytes(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = create_unit_test_config();
+
+        for _ in 0..1000 {
+            let destination = random_destination(&mut rng);
+            let raw_signature: Vec<u8> =
+                (0..rng.gen_range(0..200)).map(|_| rng.gen::<u8>()).collect();
+            let witness = InputWitness::Standard(StandardInputSignature::new(
+                SigHashType::try_from(SigHashType::ALL).unwrap(),
+                raw_signature,
+            ));
+            let ptx = make_ptx(&mut rng, witness, Some(destination.clone()));
+
+            let result = ptx.verify_scripts(&chain_config);
+            match destination {
+                // random bytes may happen to be a well formed signature
+                Destination::PublicKey(_) => assert!(matches!(
+                    result,
+                    Ok(()) | Err(ScriptVerificationError::InvalidScriptBytes(0))
+                )),
+                Destination::PublicKeyHash(_) | Destination::ClassicMultisig(_) => assert!(
+                    matches!(
+                        result,
+                        Err(ScriptVerificationError::InvalidScriptBytes(0)
+                            | ScriptVerificationError::WitnessMismatch(0))
+                    ),
+                    "{result:?}"
+                ),
+                Destination::ScriptHash(_) => assert_eq!(
+                    result,
+                    Err(ScriptVerificationError::UnknownDestinationType(0))
+                ),
+                Destination::AnyoneCanSpend => unreachable!(),
+            }
+        }
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn signed_witness(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = create_unit_test_config();
+
+        let (private_key, public_key) =
+            PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+        let destination = Destination::PublicKeyHash(PublicKeyHash::from(&public_key));
+
+        let unsigned_ptx = make_ptx(
+            &mut rng,
+            InputWitness::NoSignature(None),
+            Some(destination.clone()),
+        );
+        assert_eq!(unsigned_ptx.verify_scripts(&chain_config), Ok(()));
+
+        let input_utxos = unsigned_ptx.input_utxos().iter().map(Option::as_ref).collect::<Vec<_>>();
+        let sig = StandardInputSignature::produce_uniparty_signature_for_input(
+            &private_key,
+            SigHashType::try_from(SigHashType::ALL).unwrap(),
+            destination.clone(),
+            unsigned_ptx.tx(),
+            &input_utxos,
+            0,
+            &mut rng,
+        )
+        .unwrap();
+        let witnesses = vec![Some(InputWitness::Standard(sig))];
+
+        let ptx = unsigned_ptx.clone().with_witnesses(witnesses.clone());
+        assert_eq!(ptx.verify_scripts(&chain_config), Ok(()));
+
+        // the same witness spending from other destinations
+        let (_, other_public_key) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+        for other_destination in [
+            None,
+            Some(Destination::AnyoneCanSpend),
+            Some(Destination::PublicKeyHash(PublicKeyHash::from(
+                &other_public_key,
+            ))),
+        ] {
+            let ptx = PartiallySignedTransaction::new(
+                unsigned_ptx.tx().clone(),
+                witnesses.clone(),
+                unsigned_ptx.input_utxos().to_vec(),
+                vec![other_destination],
+                None,
+            )
+            .unwrap();
+            assert_eq!(
+                ptx.verify_scripts(&chain_config),
+                Err(ScriptVerificationError::WitnessMismatch(0))
+            );
+        }
+    }
+}
@@ -108,4 +108,6 @@ pub enum ConsensusPoSError {
     EffectivePoolBalanceError(#[from] EffectivePoolBalanceError),
     #[error("Failed to calculate capped balance")]
     FailedToCalculateCappedBalance,
+    #[error("Pool balance must be greater than zero to stake")]
+    PoolBalanceUnderflow,
 }
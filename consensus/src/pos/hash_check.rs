@@ -73,6 +73,12 @@ pub fn check_pos_hash(
     pool_balance: Amount,
     final_supply: Amount,
 ) -> Result<(), ConsensusPoSError> {
+    // With zero balance the target collapses to zero and only a zero hash could pass the check
+    ensure!(
+        pool_balance > Amount::ZERO,
+        ConsensusPoSError::PoolBalanceUnderflow
+    );
+
     match consensus_version {
         PoSConsensusVersion::V0 => check_pos_hash_v0(hash, target, pool_balance),
         PoSConsensusVersion::V1 => {
@@ -114,3 +120,50 @@ pub fn calc_and_check_pos_hash(
         final_supply,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use randomness::Rng;
+    use rstest::rstest;
+    use test_utils::random::{make_seedable_rng, Seed};
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy(), PoSConsensusVersion::V0)]
+    #[case(Seed::from_entropy(), PoSConsensusVersion::V1)]
+    fn zero_pool_balance(#[case] seed: Seed, #[case] consensus_version: PoSConsensusVersion) {
+        let mut rng = make_seedable_rng(seed);
+
+        let final_supply = Amount::from_atoms(600_000_000);
+        let target = Uint256::from_u64(rng.gen());
+
+        // A zero hash would meet the zero target, but the block must be rejected anyway
+        assert_eq!(
+            check_pos_hash(
+                consensus_version,
+                &Uint256::ZERO,
+                &target,
+                Amount::ZERO,
+                Amount::ZERO,
+                final_supply,
+            ),
+            Err(ConsensusPoSError::PoolBalanceUnderflow)
+        );
+
+        let pool_balance = Amount::from_atoms(rng.gen_range(1..final_supply.into_atoms()));
+        let pledge_amount = Amount::from_atoms(rng.gen_range(1..=pool_balance.into_atoms()));
+        assert_eq!(
+            check_pos_hash(
+                consensus_version,
+                &Uint256::ZERO,
+                &target,
+                pledge_amount,
+                pool_balance,
+                final_supply,
+            ),
+            Ok(())
+        );
+    }
+}
@@ -268,6 +268,8 @@ where
                 Ok(())
             }
             PeerManagerMessage::PingResponse(_) => Ok(()),
+            // The crawler doesn't track reputation and is never a trusted peer.
+            PeerManagerMessage::PeerReputation(_) => Ok(()),
 
             PeerManagerMessage::WillDisconnect(_) => {
                 // Since the peer is going to disconnect us anyway, do it ourselves right away.
@@ -70,6 +70,8 @@ async fn run(options: DnsServerRunOptions) -> anyhow::Result<Never> {
         boot_nodes: Vec::new(),
         reserved_nodes: Vec::new(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         // Note: this ban config (as well as any other settings related to the peer or sync manager)
         // won't have any effect on the dns server.
         ban_config: Default::default(),
@@ -78,6 +80,7 @@ async fn run(options: DnsServerRunOptions) -> anyhow::Result<Never> {
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: NodeType::DnsServer.into(),
         allow_discover_private_ips: Default::default(),
         user_agent,
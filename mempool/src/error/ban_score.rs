@@ -109,6 +109,8 @@ impl MempoolBanScore for ChainstateError {
             ChainstateError::FailedToReadProperty(_) => 0,
             ChainstateError::BootstrapError(_) => 0,
             ChainstateError::BlockInvalidatorError(_) => 0,
+            ChainstateError::CannotPruneToDepth(_, _) => 0,
+            ChainstateError::UtxoSnapshotError(_) => 0,
         }
     }
 }
@@ -326,6 +328,7 @@ impl MempoolBanScore for pos_accounting::Error {
             E::DelegateToNonexistingPool => 0,
             E::SpendingShareOfNonexistingDelegation(_) => 0,
             E::IncreaseStakerRewardsOfNonexistingPool => 0,
+            E::AttemptedSlashNonexistingPool => 0,
 
             // Accounting error has to be inspected further
             E::AccountingError(err) => err.mempool_ban_score(),
@@ -350,6 +353,8 @@ impl MempoolBanScore for pos_accounting::Error {
             E::DelegationSharesSubtractionError => 100,
             E::PledgeValueToSignedError => 100,
             E::StakerBalanceOverflow => 100,
+            E::UnauthorizedDelegationSpend(_) => 100,
+            E::SlashExceedsPoolBalance => 100,
 
             // Not undo-ing in mempool
             E::InvariantErrorDecommissionUndoFailedPoolBalanceAlreadyExists => 0,
@@ -363,6 +368,7 @@ impl MempoolBanScore for pos_accounting::Error {
             E::DuplicatesInDeltaAndUndo => 0,
             E::InvariantErrorIncreasePledgeUndoFailedPoolBalanceNotFound => 0,
             E::InvariantErrorIncreaseStakerRewardUndoFailedPoolBalanceNotFound => 0,
+            E::InvariantErrorSlashUndoFailedPoolDataNotFound => 0,
 
             // Internal errors
             E::ViewFail => 0,
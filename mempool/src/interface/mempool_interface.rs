@@ -18,7 +18,7 @@ use crate::{
     event::MempoolEvent,
     tx_accumulator::{PackingStrategy, TransactionAccumulator},
     tx_origin::{LocalTxOrigin, RemoteTxOrigin},
-    FeeRate, MempoolMaxSize, TxOptions, TxStatus,
+    FeeRate, MempoolMaxSize, MempoolTxInfo, TxOptions, TxStatus,
 };
 use common::{
     chain::{GenBlock, SignedTransaction, Transaction},
@@ -46,6 +46,9 @@ pub trait MempoolInterface: Send + Sync {
     /// Get all transactions from mempool
     fn get_all(&self) -> Vec<SignedTransaction>;
 
+    /// Get the size, fee rate and creation time of all transactions in the main mempool
+    fn get_all_tx_info(&self) -> Vec<MempoolTxInfo>;
+
     /// Get a specific transaction from the main mempool (non-orphan)
     fn transaction(&self, id: &Id<Transaction>) -> Option<SignedTransaction>;
 
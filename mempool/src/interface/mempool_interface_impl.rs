@@ -20,7 +20,7 @@ use crate::{
     pool::memory_usage_estimator::StoreMemoryUsageEstimator,
     tx_accumulator::{PackingStrategy, TransactionAccumulator},
     tx_origin::{LocalTxOrigin, RemoteTxOrigin},
-    FeeRate, MempoolInterface, MempoolMaxSize, TxOptions, TxStatus,
+    FeeRate, MempoolInterface, MempoolMaxSize, MempoolTxInfo, TxOptions, TxStatus,
 };
 use chainstate::ChainstateEventTracingWrapper;
 use common::{
@@ -120,6 +120,10 @@ impl MempoolInterface for Mempool {
         self.get_all()
     }
 
+    fn get_all_tx_info(&self) -> Vec<MempoolTxInfo> {
+        self.get_all_tx_info()
+    }
+
     fn contains_transaction(&self, tx_id: &Id<Transaction>) -> bool {
         self.contains_transaction(tx_id)
     }
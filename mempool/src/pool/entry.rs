@@ -23,7 +23,7 @@ use common::{
     primitives::{Id, Idable},
 };
 
-use super::{Fee, Time, TxOptions, TxOrigin};
+use super::{Fee, FeeRate, Time, TxOptions, TxOrigin};
 use crate::tx_origin::IsOrigin;
 
 /// A dependency of a transaction on a previous account state.
@@ -103,6 +103,23 @@ impl TxDependency {
     }
 }
 
+/// Size, fee rate and creation time of a transaction in the mempool
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    rpc::description::HasValueHint,
+)]
+pub struct MempoolTxInfo {
+    pub tx_id: Id<Transaction>,
+    pub size: usize,
+    pub fee_rate: FeeRate,
+    pub creation_time: Time,
+}
+
 /// A transaction together with its creation time
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TxEntry<O = TxOrigin> {
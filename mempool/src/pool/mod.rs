@@ -25,7 +25,7 @@ use logging::log;
 use utils::{const_value::ConstValue, ensure, eventhandler::EventsController};
 use utils_networking::broadcaster;
 
-pub use self::{feerate::FeeRate, tx_pool::feerate_points};
+pub use self::{entry::MempoolTxInfo, feerate::FeeRate, tx_pool::feerate_points};
 
 use self::{
     entry::{TxDependency, TxEntry},
@@ -112,6 +112,10 @@ impl<M> Mempool<M> {
         self.tx_pool.get_all()
     }
 
+    pub fn get_all_tx_info(&self) -> Vec<MempoolTxInfo> {
+        self.tx_pool.get_all_tx_info()
+    }
+
     pub fn contains_transaction(&self, tx_id: &Id<Transaction>) -> bool {
         self.tx_pool.contains_transaction(tx_id)
     }
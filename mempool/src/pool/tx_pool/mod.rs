@@ -61,7 +61,7 @@ use crate::{
         ReorgError, TxValidationError,
     },
     pool::{
-        entry::{TxEntry, TxEntryWithFee},
+        entry::{MempoolTxInfo, TxEntry, TxEntryWithFee},
         fee::Fee,
         feerate::FeeRate,
     },
@@ -160,6 +160,20 @@ impl<M> TxPool<M> {
             .map(|(_score, id)| self.store.get_entry(id).expect("entry").transaction().clone())
             .collect()
     }
+
+    pub fn get_all_tx_info(&self) -> Vec<MempoolTxInfo> {
+        self.store
+            .txs_by_id
+            .values()
+            .map(|entry| MempoolTxInfo {
+                tx_id: *entry.tx_id(),
+                size: entry.size().get(),
+                fee_rate: FeeRate::from_total_tx_fee(entry.fee(), entry.size())
+                    .expect("cannot overflow due to max supply"),
+                creation_time: entry.creation_time(),
+            })
+            .collect()
+    }
 }
 
 // Rolling-fee-related methods
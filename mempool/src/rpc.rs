@@ -25,7 +25,7 @@ use mempool_types::{tx_options::TxOptionsOverrides, tx_origin::LocalTxOrigin, Tx
 use serialization::hex_encoded::HexEncoded;
 use utils::tap_log::TapLog;
 
-use crate::{rpc_event::RpcEvent, FeeRate, MempoolMaxSize, TxStatus};
+use crate::{rpc_event::RpcEvent, FeeRate, MempoolMaxSize, MempoolTxInfo, TxStatus};
 
 use rpc::RpcResult;
 
@@ -36,6 +36,16 @@ pub struct GetTxResponse {
     transaction: HexEncoded<SignedTransaction>,
 }
 
+impl GetTxResponse {
+    pub fn status(&self) -> TxStatus {
+        self.status
+    }
+
+    pub fn into_transaction(self) -> SignedTransaction {
+        self.transaction.take()
+    }
+}
+
 #[rpc::describe]
 #[rpc::rpc(server, client, namespace = "mempool")]
 trait MempoolRpc {
@@ -63,6 +73,12 @@ trait MempoolRpc {
     #[method(name = "transactions")]
     async fn get_all_transactions(&self) -> RpcResult<Vec<HexEncoded<SignedTransaction>>>;
 
+    /// Get the size, fee rate and creation time of all mempool transactions.
+    ///
+    /// Notice that this call may be expensive. Use it with caution.
+    #[method(name = "transactions_info")]
+    async fn get_all_transactions_info(&self) -> RpcResult<Vec<MempoolTxInfo>>;
+
     /// Submit a transaction to the mempool.
     ///
     /// Note that submitting a transaction to the mempool does not guarantee broadcasting it.
@@ -129,6 +145,10 @@ impl MempoolRpcServer for super::MempoolHandle {
         )
     }
 
+    async fn get_all_transactions_info(&self) -> rpc::RpcResult<Vec<MempoolTxInfo>> {
+        rpc::handle_result(self.call(move |this| this.get_all_tx_info()).await)
+    }
+
     async fn get_transaction(
         &self,
         tx_id: Id<Transaction>,
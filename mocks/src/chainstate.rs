@@ -17,6 +17,7 @@ use std::{collections::BTreeMap, num::NonZeroUsize, sync::Arc};
 
 use chainstate::{
     BlockSource, ChainInfo, ChainstateConfig, ChainstateError, ChainstateEvent, Locator,
+    MainchainChangesHandler, ReorgEvent,
 };
 use chainstate_types::{BlockIndex, EpochData, GenBlockIndex};
 use common::{
@@ -29,7 +30,7 @@ use common::{
         AccountNonce, AccountType, ChainConfig, DelegationId, OrderData, OrderId, PoolId, TxInput,
         UtxoOutPoint,
     },
-    primitives::{Amount, BlockHeight, Id},
+    primitives::{Amount, BlockHeight, Id, H256},
 };
 use pos_accounting::PoolData;
 use utils::eventhandler::EventHandler;
@@ -43,9 +44,11 @@ mockall::mock! {
     impl ChainstateInterface for ChainstateInterface {
         fn subscribe_to_subsystem_events(&mut self, handler: Arc<dyn Fn(ChainstateEvent) + Send + Sync>);
         fn subscribe_to_rpc_events(&mut self) -> utils_networking::broadcaster::Receiver<ChainstateEvent>;
+        fn subscribe_to_mainchain_changes(&mut self, handler: MainchainChangesHandler);
         fn process_block(&mut self, block: Block, source: BlockSource) -> Result<Option<BlockIndex>, ChainstateError>;
         fn invalidate_block(&mut self, block_id: &Id<Block>) -> Result<(), ChainstateError>;
         fn reset_block_failure_flags(&mut self, block_id: &Id<Block>) -> Result<(), ChainstateError>;
+        fn reprocess_blocks_from(&mut self, height: BlockHeight) -> Result<(), ChainstateError>;
         fn preliminary_block_check(&self, block: Block) -> Result<Block, ChainstateError>;
         fn preliminary_headers_check(
             &self,
@@ -157,6 +160,8 @@ mockall::mock! {
         ) -> Result<Vec<Option<Amount>>, ChainstateError>;
         fn get_mainchain_blocks_list(&self) -> Result<Vec<Id<Block>>, ChainstateError>;
         fn get_block_id_tree_as_list(&self) -> Result<Vec<Id<Block>>, ChainstateError>;
+        fn get_orphan_tips(&self) -> Result<Vec<Id<Block>>, ChainstateError>;
+        fn get_recent_reorgs(&self) -> Vec<ReorgEvent>;
         fn import_bootstrap_stream<'a>(
             &'a mut self,
             reader: std::io::BufReader<Box<dyn std::io::Read + Send + 'a>>,
@@ -166,6 +171,16 @@ mockall::mock! {
             writer: std::io::BufWriter<Box<dyn std::io::Write + Send + 'a>>,
             include_orphans: bool,
         ) -> Result<(), ChainstateError>;
+        fn export_utxo_snapshot<'a>(
+            &'a mut self,
+            height: BlockHeight,
+            writer: std::io::BufWriter<Box<dyn std::io::Write + Send + 'a>>,
+        ) -> Result<H256, ChainstateError>;
+        fn import_utxo_snapshot<'a>(
+            &'a mut self,
+            reader: std::io::BufReader<Box<dyn std::io::Read + Send + 'a>>,
+        ) -> Result<(), ChainstateError>;
+        fn get_utxo_set_commitment(&self) -> Result<H256, ChainstateError>;
         fn utxo(&self, outpoint: &UtxoOutPoint) -> Result<Option<Utxo>, ChainstateError>;
         fn is_initial_block_download(&self) -> bool;
         fn stake_pool_exists(&self, pool_id: PoolId) -> Result<bool, ChainstateError>;
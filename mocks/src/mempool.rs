@@ -26,7 +26,7 @@ use mempool::{
     event::MempoolEvent,
     tx_accumulator::{PackingStrategy, TransactionAccumulator},
     tx_origin::{LocalTxOrigin, RemoteTxOrigin},
-    FeeRate, MempoolInterface, MempoolMaxSize, TxOptions, TxStatus,
+    FeeRate, MempoolInterface, MempoolMaxSize, MempoolTxInfo, TxOptions, TxStatus,
 };
 
 mockall::mock! {
@@ -48,6 +48,7 @@ mockall::mock! {
         ) -> Result<TxStatus, Error>;
 
         fn get_all(&self) -> Vec<SignedTransaction>;
+        fn get_all_tx_info(&self) -> Vec<MempoolTxInfo>;
         fn transaction(&self, id: &Id<Transaction>) -> Option<SignedTransaction>;
         fn orphan_transaction(&self, id: &Id<Transaction>) -> Option<SignedTransaction>;
         fn contains_transaction(&self, tx: &Id<Transaction>) -> bool;
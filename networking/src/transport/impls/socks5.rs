@@ -26,9 +26,6 @@ use crate::{
     Result,
 };
 
-// TODO: Add tests. A typical way to test this is to create a forwarding proxy with a socks interface
-// that can test that things sent through the proxy are delivered to the other end.
-
 #[derive(Debug)]
 pub struct Socks5TransportSocket {
     proxy: Arc<String>,
@@ -105,3 +102,108 @@ impl ConnectedSocketInfo for Socks5TransportStream {
         Ok(TcpStream::peer_addr(self)?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use serialization::Encode;
+    use test_utils::random::{gen_random_bytes, Seed};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        sync::oneshot,
+    };
+
+    use crate::{
+        test_helpers::{TestTransportMaker, TestTransportTcp},
+        transport::{BufferedTranscoder, TcpTransportSocket},
+    };
+
+    use super::*;
+
+    /// Accept a single SOCKS5 client that doesn't use authentication, report the destination
+    /// it asks for and forward the traffic to it
+    async fn run_stub_proxy(
+        listener: TcpListener,
+        destination_sender: oneshot::Sender<SocketAddr>,
+    ) {
+        let (mut client, _) = listener.accept().await.unwrap();
+
+        // Greeting: the version, the number of authentication methods and the methods
+        let mut greeting = [0; 2];
+        client.read_exact(&mut greeting).await.unwrap();
+        assert_eq!(greeting[0], 5);
+        let mut methods = vec![0; greeting[1] as usize];
+        client.read_exact(&mut methods).await.unwrap();
+        assert!(methods.contains(&0));
+        client.write_all(&[5, 0]).await.unwrap();
+
+        // Request: the version, the CONNECT command, a reserved byte, an IPv4 address and a port
+        let mut request = [0; 10];
+        client.read_exact(&mut request).await.unwrap();
+        assert_eq!(request[..4], [5, 1, 0, 1]);
+        let ip = Ipv4Addr::new(request[4], request[5], request[6], request[7]);
+        let port = u16::from_be_bytes([request[8], request[9]]);
+        let destination = SocketAddr::from((ip, port));
+        destination_sender.send(destination).unwrap();
+
+        let mut server = TcpStream::connect(destination).await.unwrap();
+        client.write_all(&[5, 0, 0, 1, 0, 0, 0, 0, 0, 0]).await.unwrap();
+        let _ = tokio::io::copy_bidirectional(&mut client, &mut server).await;
+    }
+
+    #[tracing::instrument(skip(seed))]
+    #[rstest::rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    #[tokio::test]
+    async fn connect_through_proxy(#[case] seed: Seed) {
+        let mut rng = test_utils::random::make_seedable_rng(seed);
+
+        let mut server = TcpTransportSocket::new()
+            .bind(vec![TestTransportTcp::make_address()])
+            .await
+            .unwrap();
+        let server_address = server.local_addresses().unwrap()[0];
+
+        let proxy_listener = TcpListener::bind(TestTransportTcp::make_address()).await.unwrap();
+        let proxy_address = proxy_listener.local_addr().unwrap();
+        let (destination_sender, destination_receiver) = oneshot::channel();
+        let proxy_task = tokio::spawn(run_stub_proxy(proxy_listener, destination_sender));
+
+        let transport = Socks5TransportSocket::new(&proxy_address.to_string());
+        let (server_res, peer_res) =
+            tokio::join!(server.accept(), transport.connect(server_address));
+        let server_stream = server_res.unwrap().0;
+        let peer_stream = peer_res.unwrap();
+
+        // The connection goes to the proxy, which is asked to connect to the server
+        assert_eq!(peer_stream.remote_address().unwrap(), proxy_address);
+        assert_eq!(destination_receiver.await.unwrap(), server_address);
+
+        let message = gen_random_bytes(&mut rng, 0, 1000);
+        let mut peer_stream = BufferedTranscoder::new(peer_stream, Some(message.encoded_size()));
+        peer_stream.send(message.clone()).await.unwrap();
+
+        let mut server_stream =
+            BufferedTranscoder::<_, Vec<u8>>::new(server_stream, Some(message.encoded_size()));
+        assert_eq!(server_stream.recv().await.unwrap(), message);
+
+        proxy_task.abort();
+    }
+
+    #[tokio::test]
+    async fn proxy_unreachable() {
+        // Get a free port that nothing is listening on
+        let proxy_address = TcpListener::bind(TestTransportTcp::make_address())
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+
+        let transport = Socks5TransportSocket::new(&proxy_address.to_string());
+        let result = transport.connect("127.0.0.1:1".parse().unwrap()).await;
+        assert!(matches!(result, Err(NetworkingError::ProxyError(_))));
+    }
+}
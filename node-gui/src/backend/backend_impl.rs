@@ -40,7 +40,7 @@ use wallet_controller::{
     WalletHandlesClient,
 };
 use wallet_rpc_client::handles_client::WalletRpcHandlesClient;
-use wallet_rpc_lib::{EventStream, WalletRpc, WalletService};
+use wallet_rpc_lib::{config::WalletTxConfig, EventStream, WalletRpc, WalletService};
 use wallet_types::{
     seed_phrase::StoreSeedPhrase, wallet_type::WalletType, with_locked::WithLocked,
 };
@@ -361,7 +361,12 @@ impl Backend {
         let wallet_handle = wallet_service.handle();
         let node_rpc = wallet_service.node_rpc().clone();
         let chain_config = wallet_service.chain_config().clone();
-        let wallet_rpc = WalletRpc::new(wallet_handle, node_rpc.clone(), chain_config.clone());
+        let wallet_rpc = WalletRpc::new(
+            wallet_handle,
+            node_rpc.clone(),
+            chain_config.clone(),
+            WalletTxConfig::default(),
+        );
         wallet_rpc
             .create_wallet(
                 file_path,
@@ -381,10 +386,7 @@ impl Backend {
                 .map_err(|e| BackendError::WalletError(e.to_string()))?,
         ));
         let command_handler = CommandHandler::new(
-            ControllerConfig {
-                in_top_x_mb: IN_TOP_X_MB,
-                broadcast_to_mempool: true,
-            },
+            wallet_rpc.controller_config(IN_TOP_X_MB),
             WalletRpcHandlesClient::new(wallet_rpc.clone(), None),
         )
         .await;
@@ -525,7 +527,12 @@ impl Backend {
         let wallet_handle = wallet_service.handle();
         let node_rpc = wallet_service.node_rpc().clone();
         let chain_config = wallet_service.chain_config().clone();
-        let wallet_rpc = WalletRpc::new(wallet_handle, node_rpc.clone(), chain_config.clone());
+        let wallet_rpc = WalletRpc::new(
+            wallet_handle,
+            node_rpc.clone(),
+            chain_config.clone(),
+            WalletTxConfig::default(),
+        );
         wallet_rpc
             .open_wallet(file_path, None, false)
             .await
@@ -539,10 +546,7 @@ impl Backend {
                 .map_err(|e| BackendError::WalletError(e.to_string()))?,
         ));
         let command_handler = CommandHandler::new(
-            ControllerConfig {
-                in_top_x_mb: IN_TOP_X_MB,
-                broadcast_to_mempool: true,
-            },
+            wallet_rpc.controller_config(IN_TOP_X_MB),
             WalletRpcHandlesClient::new(wallet_rpc.clone(), None),
         )
         .await;
@@ -716,6 +720,9 @@ impl Backend {
                     in_top_x_mb: IN_TOP_X_MB,
                     // don't broadcast_to_mempool before confirmation dialog
                     broadcast_to_mempool: false,
+                    min_relay_fee_rate: None,
+                    max_sync_lag: None,
+                    broadcast_retry: Default::default(),
                 },
             )
             .await
@@ -760,10 +767,14 @@ impl Backend {
                 cost_per_block.into(),
                 mpt.to_string(),
                 decommission_address,
+                None,
                 ControllerConfig {
                     in_top_x_mb: IN_TOP_X_MB,
                     // don't broadcast_to_mempool before confirmation dialog
                     broadcast_to_mempool: false,
+                    min_relay_fee_rate: None,
+                    max_sync_lag: None,
+                    broadcast_retry: Default::default(),
                 },
             )
             .await
@@ -793,6 +804,9 @@ impl Backend {
                     in_top_x_mb: IN_TOP_X_MB,
                     // don't broadcast_to_mempool before confirmation dialog
                     broadcast_to_mempool: false,
+                    min_relay_fee_rate: None,
+                    max_sync_lag: None,
+                    broadcast_retry: Default::default(),
                 },
             )
             .await
@@ -822,6 +836,9 @@ impl Backend {
                     in_top_x_mb: IN_TOP_X_MB,
                     // don't broadcast_to_mempool before confirmation dialog
                     broadcast_to_mempool: false,
+                    min_relay_fee_rate: None,
+                    max_sync_lag: None,
+                    broadcast_retry: Default::default(),
                 },
             )
             .await
@@ -858,6 +875,9 @@ impl Backend {
                     in_top_x_mb: IN_TOP_X_MB,
                     // don't broadcast_to_mempool before confirmation dialog
                     broadcast_to_mempool: false,
+                    min_relay_fee_rate: None,
+                    max_sync_lag: None,
+                    broadcast_retry: Default::default(),
                 },
             )
             .await
@@ -898,6 +918,9 @@ impl Backend {
                     in_top_x_mb: IN_TOP_X_MB,
                     // don't broadcast_to_mempool before confirmation dialog
                     broadcast_to_mempool: false,
+                    min_relay_fee_rate: None,
+                    max_sync_lag: None,
+                    broadcast_retry: Default::default(),
                 },
             )
             .await
@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use common::chain::OutPointSourceId;
+use common::{chain::OutPointSourceId, primitives::BlockHeight};
 use crypto::key::hdkd::u31::U31;
 use tokio::sync::mpsc::UnboundedSender;
 use wallet::wallet_events::WalletEvents;
@@ -51,4 +51,11 @@ impl WalletEvents for GuiWalletEvents {
     fn del_transaction(&self, _id: U31, _source: OutPointSourceId) {
         self.notify();
     }
+
+    fn reorg(&self, id: U31, common_height: BlockHeight, disconnected: usize, connected: usize) {
+        logging::log::warn!(
+            "Chain reorg detected in account {id}: {disconnected} blocks after height {common_height} were replaced by {connected} new blocks, confirmed transactions may have been rolled back"
+        );
+        self.notify();
+    }
 }
@@ -36,6 +36,10 @@ pub struct ChainstateConfigFile {
     pub max_tip_age: Option<u64>,
     /// If true, additional computationally-expensive consistency checks will be performed by the chainstate.
     pub enable_heavy_checks: Option<bool>,
+    /// The maximum number of transactions a block may contain to be accepted by the node.
+    pub max_transactions_per_block: Option<usize>,
+    /// If set, the bodies of blocks that are this many blocks below the tip are deleted to save disk space.
+    pub pruning_depth: Option<u64>,
 }
 
 impl From<ChainstateConfigFile> for ChainstateConfig {
@@ -46,6 +50,8 @@ impl From<ChainstateConfigFile> for ChainstateConfig {
             min_max_bootstrap_import_buffer_sizes,
             max_tip_age,
             enable_heavy_checks,
+            max_transactions_per_block,
+            pruning_depth,
         } = config_file;
 
         ChainstateConfig {
@@ -54,6 +60,8 @@ impl From<ChainstateConfigFile> for ChainstateConfig {
             min_max_bootstrap_import_buffer_sizes: min_max_bootstrap_import_buffer_sizes.into(),
             max_tip_age: max_tip_age.map(Duration::from_secs).into(),
             enable_heavy_checks,
+            max_transactions_per_block,
+            pruning_depth,
         }
     }
 }
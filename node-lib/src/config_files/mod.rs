@@ -152,6 +152,8 @@ fn chainstate_config(
         min_max_bootstrap_import_buffer_sizes,
         max_tip_age,
         enable_heavy_checks,
+        max_transactions_per_block,
+        pruning_depth,
     } = chainstate_config;
 
     let storage_backend = options.storage_backend.clone().unwrap_or(storage_backend);
@@ -166,6 +168,8 @@ fn chainstate_config(
         min_max_bootstrap_import_buffer_sizes,
         max_tip_age,
         enable_heavy_checks,
+        max_transactions_per_block,
+        pruning_depth,
     };
     ChainstateLauncherConfigFile {
         storage_backend,
@@ -182,6 +186,9 @@ fn p2p_config(config: P2pConfigFile, options: &RunOptions) -> P2pConfigFile {
         boot_nodes,
         reserved_nodes,
         whitelisted_addresses,
+        trusted_peers,
+        trusted_peer_keys,
+        min_peer_protocol_version,
         max_inbound_connections,
         discouragement_threshold,
         discouragement_duration,
@@ -192,6 +199,7 @@ fn p2p_config(config: P2pConfigFile, options: &RunOptions) -> P2pConfigFile {
         sync_stalling_timeout,
         node_type,
         force_dns_query_if_no_global_addresses_known,
+        asn_map_file,
     } = config;
 
     let networking_enabled = options.p2p_networking_enabled.or(networking_enabled);
@@ -201,6 +209,8 @@ fn p2p_config(config: P2pConfigFile, options: &RunOptions) -> P2pConfigFile {
     let boot_nodes = options.p2p_boot_nodes.clone().or(boot_nodes);
     let reserved_nodes = options.p2p_reserved_nodes.clone().or(reserved_nodes);
     let whitelisted_addresses = options.p2p_whitelist_addr.clone().or(whitelisted_addresses);
+    let min_peer_protocol_version =
+        options.p2p_min_peer_protocol_version.or(min_peer_protocol_version);
     let max_inbound_connections = options.p2p_max_inbound_connections.or(max_inbound_connections);
     let discouragement_threshold =
         options.p2p_discouragement_threshold.or(discouragement_threshold);
@@ -215,6 +225,7 @@ fn p2p_config(config: P2pConfigFile, options: &RunOptions) -> P2pConfigFile {
     let force_dns_query_if_no_global_addresses_known = options
         .p2p_force_dns_query_if_no_global_addresses_known
         .or(force_dns_query_if_no_global_addresses_known);
+    let asn_map_file = options.p2p_asn_map_file.clone().or(asn_map_file);
 
     P2pConfigFile {
         networking_enabled,
@@ -224,6 +235,9 @@ fn p2p_config(config: P2pConfigFile, options: &RunOptions) -> P2pConfigFile {
         boot_nodes,
         reserved_nodes,
         whitelisted_addresses,
+        trusted_peers,
+        trusted_peer_keys,
+        min_peer_protocol_version,
         max_inbound_connections,
         discouragement_threshold,
         discouragement_duration,
@@ -234,6 +248,7 @@ fn p2p_config(config: P2pConfigFile, options: &RunOptions) -> P2pConfigFile {
         sync_stalling_timeout,
         node_type,
         force_dns_query_if_no_global_addresses_known,
+        asn_map_file,
     }
 }
 
@@ -16,17 +16,26 @@
 use std::{
     net::{IpAddr, SocketAddr},
     num::NonZeroU64,
+    path::PathBuf,
     str::FromStr,
     time::Duration,
 };
 
+use anyhow::{anyhow, Context};
 use common::primitives::user_agent::mintlayer_core_user_agent;
+use crypto::key::PublicKey;
 use serde::{Deserialize, Serialize};
+use serialization::hex_encoded::HexEncoded;
 
 use p2p::{
     ban_config::BanConfig,
-    config::{NodeType, P2pConfig},
-    peer_manager::config::PeerManagerConfig,
+    config::{NodeType, P2pConfig, TrustedPeersConfig},
+    peer_manager::{
+        config::PeerManagerConfig,
+        peerdb::{asn_map::AsnMap, config::PeerDbConfig},
+    },
+    protocol::{ProtocolVersion, SupportedProtocolVersion},
+    types::socket_address::SocketAddress,
 };
 use utils_networking::IpOrSocketAddress;
 
@@ -78,6 +87,13 @@ pub struct P2pConfigFile {
     pub reserved_nodes: Option<Vec<IpOrSocketAddress>>,
     /// Optional list of whitelisted addresses.
     pub whitelisted_addresses: Option<Vec<IpAddr>>,
+    /// Optional list of trusted peer addresses to share peer reputation updates with.
+    pub trusted_peers: Option<Vec<SocketAddr>>,
+    /// Hex encoded identity public keys of the trusted nodes; peer reputation updates are only
+    /// accepted if they are signed by one of them. A node logs its own key on startup.
+    pub trusted_peer_keys: Option<Vec<HexEncoded<PublicKey>>>,
+    /// Peers whose protocol version is lower than this one are rejected during the handshake.
+    pub min_peer_protocol_version: Option<u32>,
     /// Maximum allowed number of inbound connections.
     pub max_inbound_connections: Option<usize>,
     /// The score threshold after which a peer becomes discouraged.
@@ -100,10 +116,16 @@ pub struct P2pConfigFile {
     /// If true, the node will perform an early dns query if the peer db doesn't contain
     /// any global addresses at startup.
     pub force_dns_query_if_no_global_addresses_known: Option<bool>,
+    /// A file mapping IP prefixes to the numbers of the autonomous systems that announce them,
+    /// with lines of the form `<ip prefix>/<prefix length> <asn>`. If it's set, outbound
+    /// connections are spread across the ASNs.
+    pub asn_map_file: Option<PathBuf>,
 }
 
-impl From<P2pConfigFile> for P2pConfig {
-    fn from(config_file: P2pConfigFile) -> Self {
+impl TryFrom<P2pConfigFile> for P2pConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(config_file: P2pConfigFile) -> Result<Self, Self::Error> {
         let P2pConfigFile {
             networking_enabled: _,
             bind_addresses,
@@ -112,6 +134,9 @@ impl From<P2pConfigFile> for P2pConfig {
             boot_nodes,
             reserved_nodes,
             whitelisted_addresses,
+            trusted_peers,
+            trusted_peer_keys,
+            min_peer_protocol_version,
             max_inbound_connections,
             discouragement_threshold,
             discouragement_duration,
@@ -122,20 +147,52 @@ impl From<P2pConfigFile> for P2pConfig {
             sync_stalling_timeout,
             node_type,
             force_dns_query_if_no_global_addresses_known,
+            asn_map_file,
         } = config_file;
 
-        P2pConfig {
+        let asn_map = asn_map_file
+            .map(|path| {
+                let text = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read the ASN map file {path:?}"))?;
+                AsnMap::parse(&text)
+                    .with_context(|| format!("Failed to parse the ASN map file {path:?}"))
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        let min_peer_protocol_version = min_peer_protocol_version
+            .map(|version| {
+                Option::<SupportedProtocolVersion>::from(ProtocolVersion::new(version))
+                    .ok_or_else(|| anyhow!("Unsupported minimum peer protocol version {version}"))
+            })
+            .transpose()?;
+
+        Ok(P2pConfig {
             bind_addresses: bind_addresses.unwrap_or_default(),
             socks5_proxy,
             disable_noise,
             boot_nodes: boot_nodes.unwrap_or_default(),
             reserved_nodes: reserved_nodes.unwrap_or_default(),
             whitelisted_addresses: whitelisted_addresses.unwrap_or_default(),
+            trusted_peers: TrustedPeersConfig {
+                addresses: trusted_peers
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(SocketAddress::new)
+                    .collect(),
+                public_keys: trusted_peer_keys
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(HexEncoded::take)
+                    .collect(),
+            },
+            min_peer_protocol_version: min_peer_protocol_version.into(),
             ban_config: BanConfig {
                 discouragement_threshold: discouragement_threshold.into(),
                 discouragement_duration: discouragement_duration.map(Duration::from_secs).into(),
             },
             max_clock_diff: max_clock_diff.map(Duration::from_secs).into(),
+            max_block_future_offset: Default::default(),
             outbound_connection_timeout: outbound_connection_timeout
                 .map(|t| Duration::from_secs(t.into()))
                 .into(),
@@ -175,10 +232,13 @@ impl From<P2pConfigFile> for P2pConfig {
 
                 allow_same_ip_connections: Default::default(),
 
-                peerdb_config: Default::default(),
+                peerdb_config: PeerDbConfig {
+                    asn_map,
+                    ..Default::default()
+                },
             },
             protocol_config: Default::default(),
             peer_handshake_timeout: Default::default(),
-        }
+        })
     }
 }
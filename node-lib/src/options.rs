@@ -159,6 +159,10 @@ pub struct RunOptions {
     #[clap(long, value_name = "ADDR", value_delimiter(','))]
     pub p2p_whitelist_addr: Option<Vec<IpAddr>>,
 
+    /// Peers whose protocol version is lower than this one are rejected during the handshake.
+    #[clap(long, value_name = "VERSION")]
+    pub p2p_min_peer_protocol_version: Option<u32>,
+
     /// Maximum allowed number of inbound connections.
     #[clap(long, value_name = "COUNT")]
     pub p2p_max_inbound_connections: Option<usize>,
@@ -193,6 +197,12 @@ pub struct RunOptions {
     #[clap(long, value_name = "DIFF")]
     pub p2p_max_clock_diff: Option<u64>,
 
+    /// A file mapping IP prefixes to the numbers of the autonomous systems that announce them,
+    /// with lines of the form `<ip prefix>/<prefix length> <asn>`. If it's set, outbound
+    /// connections are spread across the ASNs.
+    #[clap(long, value_name = "PATH")]
+    pub p2p_asn_map_file: Option<PathBuf>,
+
     // TODO: this option and the corresponding field of PeerManagerConfig are no longer used,
     // remove them.
     /// If true, the node will perform an early dns query if the peer db doesn't contain
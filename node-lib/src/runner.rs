@@ -148,15 +148,20 @@ async fn initialize(
                 | P2pError::InvalidStorageState(_)
                 | P2pError::MempoolError(_)
                 | P2pError::ConnectionValidationFailed(_)
-                | P2pError::SyncError(_) => Err(err),
+                | P2pError::SyncError(_)
+                | P2pError::SignatureError(_)
+                | P2pError::BlockTimestampTooFarInFuture { .. } => Err(err),
             },
         }
     }?;
     let p2p_config_file = node_config.p2p.unwrap_or_default();
+    let p2p_networking_enabled =
+        p2p_config_file.networking_enabled.unwrap_or(DEFAULT_P2P_NETWORKING_ENABLED);
+    let p2p_config: p2p::config::P2pConfig = p2p_config_file.try_into()?;
     let p2p = p2p::make_p2p(
-        p2p_config_file.networking_enabled.unwrap_or(DEFAULT_P2P_NETWORKING_ENABLED),
+        p2p_networking_enabled,
         Arc::clone(&chain_config),
-        Arc::new(p2p_config_file.into()),
+        Arc::new(p2p_config),
         subsystem::Handle::clone(&chainstate),
         subsystem::Handle::clone(&mempool),
         Default::default(),
@@ -16,6 +16,10 @@
 use std::{net::SocketAddr, num::NonZeroU64, path::Path, str::FromStr};
 
 use common::chain::config::create_testnet;
+use p2p::{
+    config::P2pConfig,
+    protocol::{SupportedProtocolVersion, MIN_SUPPORTED_PROTOCOL_VERSION},
+};
 use tempfile::TempDir;
 
 use node_lib::{NodeConfigFile, NodeTypeConfigFile, RunOptions, StorageBackendConfigFile};
@@ -105,6 +109,7 @@ fn read_config_override_values() {
     let p2p_disable_noise = false;
     let p2p_boot_node: IpOrSocketAddress = "127.0.0.1".parse().unwrap();
     let p2p_reserved_node: IpOrSocketAddress = "127.0.0.1".parse().unwrap();
+    let p2p_min_peer_protocol_version = 3;
     let p2p_max_inbound_connections = 123;
     let p2p_discouragement_threshold = 3;
     let p2p_discouragement_duration = 234;
@@ -114,6 +119,7 @@ fn read_config_override_values() {
     let p2p_sync_stalling_timeout = NonZeroU64::new(37).unwrap();
     let p2p_max_clock_diff = 15;
     let p2p_force_dns_query_if_no_global_addresses_known = true;
+    let p2p_asn_map_file = data_dir.path().join("asn_map.txt");
     let rpc_bind_address = "127.0.0.1:5432".parse().unwrap();
     let backend_type = StorageBackendConfigFile::InMemory;
     let node_type = NodeTypeConfigFile::FullNode;
@@ -139,6 +145,7 @@ fn read_config_override_values() {
         p2p_disable_noise: Some(p2p_disable_noise),
         p2p_boot_nodes: Some(vec![p2p_boot_node.clone()]),
         p2p_reserved_nodes: Some(vec![p2p_reserved_node.clone()]),
+        p2p_min_peer_protocol_version: Some(p2p_min_peer_protocol_version),
         p2p_max_inbound_connections: Some(p2p_max_inbound_connections),
         p2p_discouragement_threshold: Some(p2p_discouragement_threshold),
         p2p_discouragement_duration: Some(p2p_discouragement_duration),
@@ -151,6 +158,7 @@ fn read_config_override_values() {
         p2p_force_dns_query_if_no_global_addresses_known: Some(
             p2p_force_dns_query_if_no_global_addresses_known,
         ),
+        p2p_asn_map_file: Some(p2p_asn_map_file.clone()),
         max_tip_age: Some(max_tip_age),
         rpc_bind_address: Some(rpc_bind_address),
         rpc_enabled: Some(true),
@@ -226,6 +234,10 @@ fn read_config_override_values() {
         config.p2p.clone().unwrap().reserved_nodes,
         Some(vec!(p2p_reserved_node))
     );
+    assert_eq!(
+        config.p2p.clone().unwrap().min_peer_protocol_version,
+        Some(p2p_min_peer_protocol_version)
+    );
     assert_eq!(
         config.p2p.clone().unwrap().max_inbound_connections,
         Some(p2p_max_inbound_connections)
@@ -263,6 +275,10 @@ fn read_config_override_values() {
         config.p2p.clone().unwrap().force_dns_query_if_no_global_addresses_known,
         Some(p2p_force_dns_query_if_no_global_addresses_known)
     );
+    assert_eq!(
+        config.p2p.clone().unwrap().asn_map_file,
+        Some(p2p_asn_map_file)
+    );
 
     assert_eq!(
         config.rpc.clone().unwrap().bind_address,
@@ -285,3 +301,92 @@ fn read_config_override_values() {
 
     assert_eq!(config.chainstate.unwrap().storage_backend, backend_type);
 }
+
+// Check that the ASN map file from the config reaches the peer db config.
+#[test]
+fn read_asn_map_file() {
+    let data_dir = TempDir::new().unwrap();
+    let chain_config = create_testnet();
+
+    let asn_map_path = data_dir.path().join("asn_map.txt");
+    std::fs::write(
+        &asn_map_path,
+        "# prefix asn\n1.2.0.0/16 100\n1.2.3.0/24 200\n",
+    )
+    .unwrap();
+
+    let config_path = data_dir.path().join(CONFIG_NAME);
+    std::fs::write(
+        &config_path,
+        format!(
+            "[p2p]\nasn_map_file = {:?}\n",
+            asn_map_path.to_str().unwrap()
+        ),
+    )
+    .unwrap();
+
+    let config = NodeConfigFile::read(&chain_config, &config_path, &RunOptions::default()).unwrap();
+    let p2p_config = P2pConfig::try_from(config.p2p.unwrap()).unwrap();
+    let asn_map = &p2p_config.peer_manager_config.peerdb_config.asn_map;
+    assert_eq!(asn_map.lookup(&"1.2.3.4".parse().unwrap()), Some(200));
+    assert_eq!(asn_map.lookup(&"1.2.4.4".parse().unwrap()), Some(100));
+    assert_eq!(asn_map.lookup(&"5.6.7.8".parse().unwrap()), None);
+
+    // Without the file, ASNs are not taken into account
+    create_empty_file(&config_path);
+    let config = NodeConfigFile::read(&chain_config, &config_path, &RunOptions::default()).unwrap();
+    let p2p_config = P2pConfig::try_from(config.p2p.unwrap()).unwrap();
+    assert!(p2p_config.peer_manager_config.peerdb_config.asn_map.is_empty());
+
+    // An invalid or missing file is an error
+    std::fs::write(&asn_map_path, "1.2.0.0 100\n").unwrap();
+    let options = RunOptions {
+        p2p_asn_map_file: Some(asn_map_path),
+        ..Default::default()
+    };
+    let config = NodeConfigFile::read(&chain_config, &config_path, &options).unwrap();
+    assert!(P2pConfig::try_from(config.p2p.unwrap()).is_err());
+
+    let options = RunOptions {
+        p2p_asn_map_file: Some(data_dir.path().join("missing.txt")),
+        ..Default::default()
+    };
+    let config = NodeConfigFile::read(&chain_config, &config_path, &options).unwrap();
+    assert!(P2pConfig::try_from(config.p2p.unwrap()).is_err());
+}
+
+// Check that the minimum peer protocol version from the config reaches the p2p config.
+#[test]
+fn read_min_peer_protocol_version() {
+    let data_dir = TempDir::new().unwrap();
+    let chain_config = create_testnet();
+
+    let config_path = data_dir.path().join(CONFIG_NAME);
+    std::fs::write(&config_path, "[p2p]\nmin_peer_protocol_version = 3\n").unwrap();
+
+    let config = NodeConfigFile::read(&chain_config, &config_path, &RunOptions::default()).unwrap();
+    let p2p_config = P2pConfig::try_from(config.p2p.unwrap()).unwrap();
+    assert_eq!(
+        *p2p_config.min_peer_protocol_version,
+        SupportedProtocolVersion::V3
+    );
+
+    // The command line option overrides the config, unsupported versions are rejected
+    for version in [0, 1, u32::MAX] {
+        let options = RunOptions {
+            p2p_min_peer_protocol_version: Some(version),
+            ..Default::default()
+        };
+        let config = NodeConfigFile::read(&chain_config, &config_path, &options).unwrap();
+        assert!(P2pConfig::try_from(config.p2p.unwrap()).is_err());
+    }
+
+    // Without the setting, all the supported versions are accepted
+    create_empty_file(&config_path);
+    let config = NodeConfigFile::read(&chain_config, &config_path, &RunOptions::default()).unwrap();
+    let p2p_config = P2pConfig::try_from(config.p2p.unwrap()).unwrap();
+    assert_eq!(
+        *p2p_config.min_peer_protocol_version,
+        *MIN_SUPPORTED_PROTOCOL_VERSION
+    );
+}
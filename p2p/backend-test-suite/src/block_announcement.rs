@@ -182,12 +182,15 @@ where
         boot_nodes: Vec::new(),
         reserved_nodes: Vec::new(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         allow_discover_private_ips: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
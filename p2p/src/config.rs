@@ -17,6 +17,7 @@ use std::net::{IpAddr, SocketAddr};
 use std::time::Duration;
 
 use common::primitives::user_agent::UserAgent;
+use crypto::key::PublicKey;
 use utils::make_config_setting;
 use utils_networking::IpOrSocketAddress;
 
@@ -24,7 +25,8 @@ use crate::{
     ban_config::BanConfig,
     net::types::services::{Service, Services},
     peer_manager::config::PeerManagerConfig,
-    protocol::ProtocolConfig,
+    protocol::{ProtocolConfig, SupportedProtocolVersion, MIN_SUPPORTED_PROTOCOL_VERSION},
+    types::socket_address::SocketAddress,
 };
 
 make_config_setting!(OutboundConnectionTimeout, Duration, Duration::from_secs(10));
@@ -33,8 +35,18 @@ make_config_setting!(AllowDiscoverPrivateIps, bool, false);
 make_config_setting!(PingCheckPeriod, Duration, Duration::from_secs(60));
 make_config_setting!(PingTimeout, Duration, Duration::from_secs(150));
 make_config_setting!(MaxClockDiff, Duration, Duration::from_secs(10));
+make_config_setting!(
+    MaxBlockFutureOffset,
+    Duration,
+    Duration::from_secs(2 * 60 * 60)
+);
 make_config_setting!(SyncStallingTimeout, Duration, Duration::from_secs(25));
 make_config_setting!(PeerHandshakeTimeout, Duration, Duration::from_secs(10));
+make_config_setting!(
+    MinPeerProtocolVersion,
+    SupportedProtocolVersion,
+    *MIN_SUPPORTED_PROTOCOL_VERSION
+);
 
 /// A node type.
 #[derive(Debug, Copy, Clone)]
@@ -64,6 +76,17 @@ impl From<NodeType> for Services {
     }
 }
 
+/// The peers that peer reputation updates are exchanged with.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedPeersConfig {
+    /// Addresses of the trusted peers. Reputation updates are relayed to these peers only and
+    /// only accepted when received from them.
+    pub addresses: Vec<SocketAddress>,
+    /// Identity keys of the trusted nodes. Only the reputation updates signed with one of these
+    /// keys are accepted.
+    pub public_keys: Vec<PublicKey>,
+}
+
 /// The p2p subsystem configuration.
 #[derive(Debug)]
 pub struct P2pConfig {
@@ -82,6 +105,10 @@ pub struct P2pConfig {
     pub reserved_nodes: Vec<IpOrSocketAddress>,
     /// Optional list of whitelisted addresses. Such addresses cannot be automatically banned.
     pub whitelisted_addresses: Vec<IpAddr>,
+    /// The trusted peers that peer reputation updates are shared with.
+    pub trusted_peers: TrustedPeersConfig,
+    /// Peers whose protocol version is lower than this one are rejected during the handshake.
+    pub min_peer_protocol_version: MinPeerProtocolVersion,
     /// Settings related to banning and discouragement.
     pub ban_config: BanConfig,
     /// The outbound connection timeout value in seconds.
@@ -95,6 +122,9 @@ pub struct P2pConfig {
     /// Maximum acceptable time difference between this node and the remote peer.
     /// If a large difference is detected, the peer will be disconnected.
     pub max_clock_diff: MaxClockDiff,
+    /// Maximum acceptable offset of an announced block's timestamp from the local time.
+    /// Headers of blocks further in the future are rejected.
+    pub max_block_future_offset: MaxBlockFutureOffset,
     /// A node type.
     pub node_type: NodeTypeSetting,
     /// Allow announcing and discovering local and private IPs. Should be used for testing only.
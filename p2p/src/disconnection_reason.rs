@@ -52,6 +52,8 @@ pub enum DisconnectionReason {
     TooManyInboundPeersAndCannotEvictAnyone,
     #[error("Unsupported protocol version, our min version is {}", *MIN_SUPPORTED_PROTOCOL_VERSION as u32)]
     UnsupportedProtocol,
+    #[error("Your protocol version is too old, our min version is {min_protocol_version}")]
+    ProtocolVersionTooOld { min_protocol_version: u32 },
     #[error("Your time {remote_time:?} is out of the acceptable range {accepted_peer_time:?}")]
     TimeDiff {
         remote_time: Time,
@@ -90,11 +92,19 @@ impl DisconnectionReason {
             | P2pError::InvalidStorageState(_)
             | P2pError::PeerDbStorageVersionMismatch { .. }
             | P2pError::MempoolError(_)
-            | P2pError::SyncError(_) => None,
+            | P2pError::SyncError(_)
+            | P2pError::SignatureError(_)
+            | P2pError::BlockTimestampTooFarInFuture { .. } => None,
             P2pError::ConnectionValidationFailed(err) => match err {
                 ConnectionValidationError::UnsupportedProtocol {
                     peer_protocol_version: _,
                 } => Some(Self::UnsupportedProtocol),
+                ConnectionValidationError::ProtocolVersionTooOld {
+                    peer_protocol_version: _,
+                    min_protocol_version,
+                } => Some(Self::ProtocolVersionTooOld {
+                    min_protocol_version: *min_protocol_version as u32,
+                }),
                 ConnectionValidationError::TimeDiff {
                     remote_time,
                     accepted_peer_time,
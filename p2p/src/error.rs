@@ -13,6 +13,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::Duration;
+
 use thiserror::Error;
 
 use chainstate::{ban_score::BanScore, ChainstateError};
@@ -20,12 +22,17 @@ use common::{
     chain::{config::MagicBytes, Block, Transaction},
     primitives::{time::Time, Id},
 };
+use crypto::key::SignatureError;
 use mempool::error::{Error as MempoolError, MempoolBanScore};
 use networking::error::NetworkingError;
 use p2p_types::{services::Services, socket_address::SocketAddress, PeerId};
 use utils::try_as::TryAsRef;
 
-use crate::{net::types::PeerRole, peer_manager::peerdb_common, protocol::ProtocolVersion};
+use crate::{
+    net::types::PeerRole,
+    peer_manager::peerdb_common,
+    protocol::{ProtocolVersion, SupportedProtocolVersion},
+};
 
 /// Errors related to invalid data/peer information that results in connection getting closed
 /// and the peer getting banned.
@@ -108,6 +115,11 @@ pub enum ConnectionValidationError {
     UnsupportedProtocol {
         peer_protocol_version: ProtocolVersion,
     },
+    #[error("Peer protocol version {peer_protocol_version:?} is lower than the configured minimum {min_protocol_version:?}")]
+    ProtocolVersionTooOld {
+        peer_protocol_version: ProtocolVersion,
+        min_protocol_version: SupportedProtocolVersion,
+    },
     #[error("Peer time {remote_time:?} out of the acceptable range {accepted_peer_time:?}")]
     TimeDiff {
         remote_time: Time,
@@ -147,6 +159,8 @@ pub enum SyncError {
     BlockDataMissingInSendBlock(Id<Block>),
     #[error("Block index missing when trying to send block {0}")]
     BlockIndexMissingInSendBlock(Id<Block>),
+    #[error("A peer requested block {0}, whose body has been pruned")]
+    PrunedBlockRequested(Id<Block>),
 }
 
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
@@ -184,6 +198,13 @@ pub enum P2pError {
     ConnectionValidationFailed(#[from] ConnectionValidationError),
     #[error("Synchronization error: {0}")]
     SyncError(#[from] SyncError),
+    #[error("Signature error: {0}")]
+    SignatureError(#[from] SignatureError),
+    #[error("Block {block_id} timestamp is {} seconds ahead of the local time", offset.as_secs())]
+    BlockTimestampTooFarInFuture {
+        block_id: Id<Block>,
+        offset: Duration,
+    },
 }
 
 impl From<DialError> for P2pError {
@@ -238,6 +259,9 @@ impl BanScore for P2pError {
             P2pError::MempoolError(err) => err.mempool_ban_score(),
             P2pError::ConnectionValidationFailed(_) => 0,
             P2pError::SyncError(err) => err.ban_score(),
+            P2pError::SignatureError(_) => 0,
+            // The peer's clock may be off, so don't punish it
+            P2pError::BlockTimestampTooFarInFuture { .. } => 0,
         }
     }
 }
@@ -272,6 +296,7 @@ impl BanScore for SyncError {
         match self {
             SyncError::BlockDataMissingInSendBlock(_) => 0,
             SyncError::BlockIndexMissingInSendBlock(_) => 0,
+            SyncError::PrunedBlockRequested(_) => 0,
         }
     }
 }
@@ -295,7 +320,9 @@ impl TryAsRef<storage::Error> for P2pError {
             }
             | P2pError::MempoolError(_)
             | P2pError::ConnectionValidationFailed(_)
-            | P2pError::SyncError(_) => None,
+            | P2pError::SyncError(_)
+            | P2pError::SignatureError(_)
+            | P2pError::BlockTimestampTooFarInFuture { .. } => None,
             P2pError::StorageFailure(err) => Some(err),
         }
     }
@@ -22,7 +22,12 @@ use p2p_types::{
 };
 use utils_networking::IpOrSocketAddress;
 
-use crate::{interface::types::ConnectedPeer, types::peer_id::PeerId};
+use crate::{
+    interface::types::{ConnectedPeer, PeerDetails, PeerLimits},
+    message::PeerReputationReason,
+    protocol::SupportedProtocolVersion,
+    types::peer_id::PeerId,
+};
 
 #[async_trait::async_trait]
 pub trait P2pInterface: Send + Sync {
@@ -37,14 +42,30 @@ pub trait P2pInterface: Send + Sync {
 
     async fn list_discouraged(&self) -> crate::Result<Vec<(BannableAddress, Time)>>;
 
+    async fn broadcast_reputation(
+        &mut self,
+        addr: SocketAddress,
+        score: i16,
+        reason: PeerReputationReason,
+    ) -> crate::Result<()>;
+
     async fn get_peer_count(&self) -> crate::Result<usize>;
     async fn get_bind_addresses(&self) -> crate::Result<Vec<SocketAddress>>;
     async fn get_connected_peers(&self) -> crate::Result<Vec<ConnectedPeer>>;
+    async fn peer_info(&self, peer_id: PeerId) -> crate::Result<Option<PeerDetails>>;
+    async fn list_peers(&self) -> crate::Result<Vec<PeerDetails>>;
+    async fn peer_protocol_version(
+        &self,
+        peer_id: PeerId,
+    ) -> crate::Result<Option<SupportedProtocolVersion>>;
 
     async fn get_reserved_nodes(&self) -> crate::Result<Vec<SocketAddress>>;
     async fn add_reserved_node(&mut self, addr: IpOrSocketAddress) -> crate::Result<()>;
     async fn remove_reserved_node(&mut self, addr: IpOrSocketAddress) -> crate::Result<()>;
 
+    async fn get_peer_limits(&self) -> crate::Result<PeerLimits>;
+    async fn set_peer_limits(&mut self, limits: PeerLimits) -> crate::Result<()>;
+
     async fn submit_transaction(
         &mut self,
         tx: SignedTransaction,
@@ -26,9 +26,14 @@ use utils_networking::IpOrSocketAddress;
 use crate::{
     disconnection_reason::DisconnectionReason,
     error::P2pError,
-    interface::{p2p_interface::P2pInterface, types::ConnectedPeer},
+    interface::{
+        p2p_interface::P2pInterface,
+        types::{ConnectedPeer, PeerDetails, PeerLimits},
+    },
+    message::PeerReputationReason,
     net::NetworkingService,
     peer_manager_event::PeerDisconnectionDbAction,
+    protocol::SupportedProtocolVersion,
     types::peer_id::PeerId,
     utils::oneshot_nofail,
     MessagingService, P2p, P2pEvent, PeerManagerEvent,
@@ -106,6 +111,24 @@ where
         Ok(list)
     }
 
+    async fn broadcast_reputation(
+        &mut self,
+        address: SocketAddress,
+        score: i16,
+        reason: PeerReputationReason,
+    ) -> crate::Result<()> {
+        let (response_sender, response_receiver) = oneshot_nofail::channel();
+        self.peer_mgr_event_sender
+            .send(PeerManagerEvent::BroadcastReputation {
+                address,
+                score,
+                reason,
+                response_sender,
+            })
+            .map_err(|_| P2pError::ChannelClosed)?;
+        response_receiver.await?
+    }
+
     async fn get_peer_count(&self) -> crate::Result<usize> {
         let (response_sender, response_receiver) = oneshot_nofail::channel();
         self.peer_mgr_event_sender
@@ -127,6 +150,32 @@ where
         Ok(response_receiver.await?)
     }
 
+    async fn peer_info(&self, peer_id: PeerId) -> crate::Result<Option<PeerDetails>> {
+        let (response_sender, response_receiver) = oneshot_nofail::channel();
+        self.peer_mgr_event_sender
+            .send(PeerManagerEvent::GetPeerDetails(peer_id, response_sender))?;
+        Ok(response_receiver.await?)
+    }
+
+    async fn list_peers(&self) -> crate::Result<Vec<PeerDetails>> {
+        let (response_sender, response_receiver) = oneshot_nofail::channel();
+        self.peer_mgr_event_sender
+            .send(PeerManagerEvent::ListPeerDetails(response_sender))?;
+        Ok(response_receiver.await?)
+    }
+
+    async fn peer_protocol_version(
+        &self,
+        peer_id: PeerId,
+    ) -> crate::Result<Option<SupportedProtocolVersion>> {
+        let (response_sender, response_receiver) = oneshot_nofail::channel();
+        self.peer_mgr_event_sender.send(PeerManagerEvent::GetPeerProtocolVersion(
+            peer_id,
+            response_sender,
+        ))?;
+        Ok(response_receiver.await?)
+    }
+
     async fn get_reserved_nodes(&self) -> crate::Result<Vec<SocketAddress>> {
         let (response_sender, response_receiver) = oneshot_nofail::channel();
         self.peer_mgr_event_sender
@@ -152,6 +201,22 @@ where
         Ok(response_receiver.await??)
     }
 
+    async fn get_peer_limits(&self) -> crate::Result<PeerLimits> {
+        let (response_sender, response_receiver) = oneshot_nofail::channel();
+        self.peer_mgr_event_sender
+            .send(PeerManagerEvent::GetPeerLimits(response_sender))
+            .map_err(|_| P2pError::ChannelClosed)?;
+        Ok(response_receiver.await?)
+    }
+
+    async fn set_peer_limits(&mut self, limits: PeerLimits) -> crate::Result<()> {
+        let (response_sender, response_receiver) = oneshot_nofail::channel();
+        self.peer_mgr_event_sender
+            .send(PeerManagerEvent::SetPeerLimits(limits, response_sender))
+            .map_err(|_| P2pError::ChannelClosed)?;
+        Ok(response_receiver.await??)
+    }
+
     async fn submit_transaction(
         &mut self,
         tx: SignedTransaction,
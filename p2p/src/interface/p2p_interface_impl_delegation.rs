@@ -24,9 +24,15 @@ use mempool::tx_options::TxOptionsOverrides;
 use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress};
 use utils_networking::IpOrSocketAddress;
 
-use crate::{types::peer_id::PeerId, P2pEvent};
+use crate::{
+    message::PeerReputationReason, protocol::SupportedProtocolVersion, types::peer_id::PeerId,
+    P2pEvent,
+};
 
-use super::{p2p_interface::P2pInterface, types::ConnectedPeer};
+use super::{
+    p2p_interface::P2pInterface,
+    types::{ConnectedPeer, PeerDetails, PeerLimits},
+};
 
 #[async_trait::async_trait]
 impl<T: Deref<Target = dyn P2pInterface> + DerefMut<Target = dyn P2pInterface> + Send + Sync>
@@ -60,6 +66,15 @@ impl<T: Deref<Target = dyn P2pInterface> + DerefMut<Target = dyn P2pInterface> +
         self.deref().list_discouraged().await
     }
 
+    async fn broadcast_reputation(
+        &mut self,
+        addr: SocketAddress,
+        score: i16,
+        reason: PeerReputationReason,
+    ) -> crate::Result<()> {
+        self.deref_mut().broadcast_reputation(addr, score, reason).await
+    }
+
     async fn get_peer_count(&self) -> crate::Result<usize> {
         self.deref().get_peer_count().await
     }
@@ -72,6 +87,21 @@ impl<T: Deref<Target = dyn P2pInterface> + DerefMut<Target = dyn P2pInterface> +
         self.deref().get_connected_peers().await
     }
 
+    async fn peer_info(&self, peer_id: PeerId) -> crate::Result<Option<PeerDetails>> {
+        self.deref().peer_info(peer_id).await
+    }
+
+    async fn list_peers(&self) -> crate::Result<Vec<PeerDetails>> {
+        self.deref().list_peers().await
+    }
+
+    async fn peer_protocol_version(
+        &self,
+        peer_id: PeerId,
+    ) -> crate::Result<Option<SupportedProtocolVersion>> {
+        self.deref().peer_protocol_version(peer_id).await
+    }
+
     async fn get_reserved_nodes(&self) -> crate::Result<Vec<SocketAddress>> {
         self.deref().get_reserved_nodes().await
     }
@@ -84,6 +114,14 @@ impl<T: Deref<Target = dyn P2pInterface> + DerefMut<Target = dyn P2pInterface> +
         self.deref_mut().remove_reserved_node(addr).await
     }
 
+    async fn get_peer_limits(&self) -> crate::Result<PeerLimits> {
+        self.deref().get_peer_limits().await
+    }
+
+    async fn set_peer_limits(&mut self, limits: PeerLimits) -> crate::Result<()> {
+        self.deref_mut().set_peer_limits(limits).await
+    }
+
     async fn submit_transaction(
         &mut self,
         tx: SignedTransaction,
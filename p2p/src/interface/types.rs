@@ -43,4 +43,44 @@ pub struct ConnectedPeer {
 
     /// Min time for a ping roundtrip, in milliseconds
     pub ping_min: Option<u64>,
+
+    /// Last time a peer manager message (e.g. a ping) was received from the peer,
+    /// in seconds since the Unix epoch
+    pub last_message_time: Option<u64>,
+}
+
+/// Connection limits of the peer manager that can be changed at runtime.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, rpc_description::HasValueHint,
+)]
+pub struct PeerLimits {
+    /// Maximum allowed number of inbound connections
+    pub max_inbound: usize,
+
+    /// The desired maximum number of automatic full relay outbound connections
+    pub max_outbound: usize,
+}
+
+/// Block sync state of a connected peer.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, rpc_description::HasValueHint,
+)]
+pub enum PeerState {
+    /// No blocks are being requested from the peer
+    Idle,
+
+    /// Blocks have been requested from the peer and are still being waited for
+    ExpectingBlocks,
+}
+
+/// Helper type used to return the state of a connected peer from RPC.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, rpc_description::HasValueHint)]
+pub struct PeerDetails {
+    pub peer_id: PeerId,
+
+    pub address: SocketAddress,
+
+    pub peer_role: PeerRole,
+
+    pub state: PeerState,
 }
@@ -19,11 +19,13 @@ use common::{
         block::{signed_block_header::SignedBlockHeader, Block},
         SignedTransaction, Transaction,
     },
-    primitives::Id,
+    primitives::{time::Time, Id},
 };
+use crypto::key::{PrivateKey, PublicKey, Signature, SignatureError};
+use randomness::{make_true_rng, Rng};
 use serialization::{Decode, Encode};
 
-use crate::types::peer_address::PeerAddress;
+use crate::types::{peer_address::PeerAddress, socket_address::SocketAddress};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BlockSyncMessage {
@@ -55,6 +57,7 @@ pub enum PeerManagerMessage {
     AddrListResponse(AddrListResponse),
     PingResponse(PingResponse),
     WillDisconnect(WillDisconnectMessage),
+    PeerReputation(PeerReputation),
 }
 
 #[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
@@ -176,3 +179,105 @@ pub struct PingResponse {
 pub struct WillDisconnectMessage {
     pub reason: String,
 }
+
+#[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+pub enum PeerReputationReason {
+    #[codec(index = 0)]
+    Spamming,
+    #[codec(index = 1)]
+    InvalidBlock,
+    #[codec(index = 2)]
+    InvalidTransaction,
+}
+
+/// A reputation score adjustment for some address, shared between trusted peers.
+///
+/// The message is signed with the persistent identity key of the node that issued it, so that
+/// it can be relayed further without being altered. The timestamp and the random nonce are
+/// signed too; they let the receivers drop stale updates and recognize replayed ones.
+#[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
+pub struct PeerReputation {
+    address: PeerAddress,
+    score: i16,
+    reason: PeerReputationReason,
+    timestamp: u64,
+    nonce: u64,
+    public_key: PublicKey,
+    signature: Signature,
+}
+
+impl PeerReputation {
+    pub fn new_signed(
+        address: SocketAddress,
+        score: i16,
+        reason: PeerReputationReason,
+        timestamp: Time,
+        private_key: &PrivateKey,
+    ) -> Result<Self, SignatureError> {
+        let mut rng = make_true_rng();
+        let address = address.as_peer_address();
+        let timestamp = timestamp.as_secs_since_epoch();
+        let nonce = rng.gen();
+        let signature = private_key.sign_message(
+            &Self::signed_data(&address, score, reason, timestamp, nonce),
+            &mut rng,
+        )?;
+
+        Ok(Self {
+            address,
+            score,
+            reason,
+            timestamp,
+            nonce,
+            public_key: PublicKey::from_private_key(private_key),
+            signature,
+        })
+    }
+
+    pub fn address(&self) -> SocketAddress {
+        SocketAddress::new((&self.address).into())
+    }
+
+    pub fn score(&self) -> i16 {
+        self.score
+    }
+
+    pub fn reason(&self) -> PeerReputationReason {
+        self.reason
+    }
+
+    pub fn timestamp(&self) -> Time {
+        Time::from_secs_since_epoch(self.timestamp)
+    }
+
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    pub fn verify_signature(&self) -> bool {
+        self.public_key.verify_message(
+            &self.signature,
+            &Self::signed_data(
+                &self.address,
+                self.score,
+                self.reason,
+                self.timestamp,
+                self.nonce,
+            ),
+        )
+    }
+
+    fn signed_data(
+        address: &PeerAddress,
+        score: i16,
+        reason: PeerReputationReason,
+        timestamp: u64,
+        nonce: u64,
+    ) -> Vec<u8> {
+        (address, score, reason, timestamp, nonce).encode()
+    }
+}
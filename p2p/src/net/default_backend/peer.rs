@@ -199,13 +199,28 @@ where
                 remote_time,
             )?;
 
-            choose_common_protocol_version(peer_protocol_version, self.node_protocol_version).ok_or(
-                P2pError::ConnectionValidationFailed(
+            let Some(common_protocol_version) =
+                choose_common_protocol_version(peer_protocol_version, self.node_protocol_version)
+            else {
+                return Err(P2pError::ConnectionValidationFailed(
                     ConnectionValidationError::UnsupportedProtocol {
                         peer_protocol_version,
                     },
-                ),
-            )
+                ));
+            };
+
+            let min_protocol_version = *self.p2p_config.min_peer_protocol_version;
+            utils::ensure!(
+                peer_protocol_version >= min_protocol_version.into_raw_version(),
+                P2pError::ConnectionValidationFailed(
+                    ConnectionValidationError::ProtocolVersionTooOld {
+                        peer_protocol_version,
+                        min_protocol_version,
+                    }
+                )
+            );
+
+            Ok(common_protocol_version)
         })();
 
         self.maybe_send_will_disconnect(
@@ -27,8 +27,8 @@ use common::time_getter::TimeGetter;
 use utils::atomics::SeqCstAtomicBool;
 
 use crate::{
-    config::NodeType,
-    error::{DialError, P2pError},
+    config::{NodeType, P2pConfig},
+    error::{ConnectionValidationError, DialError, P2pError},
     net::{
         default_backend::default_networking_service::get_preferred_protocol_version_for_tests,
         types::ConnectivityEvent, ConnectivityService, NetworkingService,
@@ -404,6 +404,115 @@ where
     }
 }
 
+// A peer whose protocol version is below the configured minimum is rejected during
+// the handshake, while a peer with the minimum version is accepted.
+async fn min_peer_protocol_version<A, T>()
+where
+    A: TestTransportMaker<Transport = T>,
+    T: TransportSocket + Debug,
+{
+    let config = Arc::new(common::chain::config::create_unit_test_config());
+    let min_protocol_version = get_preferred_protocol_version_for_tests();
+    let p2p_config = Arc::new(P2pConfig {
+        min_peer_protocol_version: min_protocol_version.into(),
+        ..test_p2p_config()
+    });
+    let shutdown = Arc::new(SeqCstAtomicBool::new(false));
+    let time_getter = TimeGetter::default();
+
+    let (_shutdown_sender, shutdown_receiver) = oneshot::channel();
+    let (_subscribers_sender, subscribers_receiver) = mpsc::unbounded_channel();
+    let (mut local_srv, _, _, _) = DefaultNetworkingService::<T>::start(
+        true,
+        A::make_transport(),
+        vec![A::make_address().into()],
+        Arc::clone(&config),
+        Arc::clone(&p2p_config),
+        time_getter.clone(),
+        Arc::clone(&shutdown),
+        shutdown_receiver,
+        subscribers_receiver,
+    )
+    .await
+    .unwrap();
+
+    let too_old_protocol_version =
+        ProtocolVersion::new(min_protocol_version.into_raw_version().inner() - 1);
+    for remote_protocol_version in [too_old_protocol_version, CURRENT_PROTOCOL_VERSION] {
+        let (_shutdown_sender, shutdown_receiver) = oneshot::channel();
+        let (_subscribers_sender, subscribers_receiver) = mpsc::unbounded_channel();
+        let (remote_srv, _, _, _) = DefaultNetworkingService::<T>::start_with_version(
+            true,
+            A::make_transport(),
+            vec![A::make_address().into()],
+            Arc::clone(&config),
+            Arc::new(test_p2p_config()),
+            time_getter.clone(),
+            Arc::clone(&shutdown),
+            shutdown_receiver,
+            subscribers_receiver,
+            remote_protocol_version,
+        )
+        .await
+        .unwrap();
+
+        let addr = remote_srv.local_addresses()[0];
+        local_srv.connect(addr, None).unwrap();
+
+        let poll_result = local_srv.poll_next().await;
+        if remote_protocol_version == too_old_protocol_version {
+            if let Ok(ConnectivityEvent::ConnectionError {
+                peer_address,
+                error,
+            }) = poll_result
+            {
+                assert_eq!(peer_address, addr);
+                assert_eq!(
+                    error,
+                    P2pError::ConnectionValidationFailed(
+                        ConnectionValidationError::ProtocolVersionTooOld {
+                            peer_protocol_version: too_old_protocol_version,
+                            min_protocol_version,
+                        }
+                    )
+                );
+            } else {
+                panic!("Invalid event received: {poll_result:?}");
+            }
+        } else if let Ok(ConnectivityEvent::OutboundAccepted {
+            peer_address,
+            bind_address: _,
+            peer_info,
+            node_address_as_seen_by_peer: _,
+        }) = poll_result
+        {
+            assert_eq!(peer_address, addr);
+            let protocol_version: ProtocolVersion = peer_info.protocol_version.into();
+            assert_eq!(protocol_version, CURRENT_PROTOCOL_VERSION);
+        } else {
+            panic!("Invalid event received: {poll_result:?}");
+        }
+    }
+}
+
+#[tracing::instrument]
+#[tokio::test]
+async fn min_peer_protocol_version_tcp() {
+    min_peer_protocol_version::<TestTransportTcp, TcpTransportSocket>().await;
+}
+
+#[tracing::instrument]
+#[tokio::test]
+async fn min_peer_protocol_version_channels() {
+    min_peer_protocol_version::<TestTransportChannel, MpscChannelTransport>().await;
+}
+
+#[tracing::instrument]
+#[tokio::test]
+async fn min_peer_protocol_version_noise() {
+    min_peer_protocol_version::<TestTransportNoise, NoiseTcpTransport>().await;
+}
+
 // Note: it's important for the "self_connect" tests to be multi-threaded to be able to detect
 // all possible problems of the implementation (specifically, we had a race condition between
 // sending a `PeerEvent` to backend and sending `HelloAck` to the peer, which could break
@@ -29,8 +29,9 @@ use crate::{
     error::P2pError,
     message::{
         AddrListRequest, AddrListResponse, AnnounceAddrRequest, BlockListRequest, BlockResponse,
-        BlockSyncMessage, HeaderList, HeaderListRequest, PeerManagerMessage, PingRequest,
-        PingResponse, TransactionResponse, TransactionSyncMessage, WillDisconnectMessage,
+        BlockSyncMessage, HeaderList, HeaderListRequest, PeerManagerMessage, PeerReputation,
+        PingRequest, PingResponse, TransactionResponse, TransactionSyncMessage,
+        WillDisconnectMessage,
     },
     net::types::services::Services,
     protocol::{ProtocolVersion, SupportedProtocolVersion},
@@ -218,6 +219,10 @@ pub enum Message {
     #[codec(index = 13)]
     WillDisconnect(WillDisconnectMessage),
 
+    /// A signed reputation update, exchanged between trusted peers only.
+    #[codec(index = 14)]
+    PeerReputation(PeerReputation),
+
     // A message that corresponds to BlockSyncMessage::TestSentinel.
     #[cfg(test)]
     #[codec(index = 255)]
@@ -233,6 +238,7 @@ impl From<PeerManagerMessage> for Message {
             PeerManagerMessage::AddrListResponse(r) => Message::AddrListResponse(r),
             PeerManagerMessage::PingResponse(r) => Message::PingResponse(r),
             PeerManagerMessage::WillDisconnect(r) => Message::WillDisconnect(r),
+            PeerManagerMessage::PeerReputation(r) => Message::PeerReputation(r),
         }
     }
 }
@@ -293,6 +299,9 @@ impl Message {
             Message::WillDisconnect(msg) => {
                 CategorizedMessage::PeerManagerMessage(PeerManagerMessage::WillDisconnect(msg))
             }
+            Message::PeerReputation(msg) => {
+                CategorizedMessage::PeerManagerMessage(PeerManagerMessage::PeerReputation(msg))
+            }
 
             Message::HeaderListRequest(msg) => {
                 CategorizedMessage::BlockSyncMessage(BlockSyncMessage::HeaderListRequest(msg))
@@ -340,6 +349,7 @@ mod tests {
         chain::config::MagicBytes,
         primitives::{semver::SemVer, Id},
     };
+    use crypto::key::{KeyKind, PrivateKey};
     use networking::test_helpers::{get_two_connected_sockets, TestTransportChannel};
     use networking::transport::{BufferedTranscoder, MpscChannelTransport};
     use p2p_types::services::Service;
@@ -349,8 +359,8 @@ mod tests {
     use crate::{
         message::{
             AddrListRequest, AddrListResponse, AnnounceAddrRequest, BlockListRequest,
-            BlockResponse, HeaderList, HeaderListRequest, PingRequest, PingResponse,
-            TransactionResponse,
+            BlockResponse, HeaderList, HeaderListRequest, PeerReputationReason, PingRequest,
+            PingResponse, TransactionResponse,
         },
         net::default_backend::types::{HandshakeMessage, P2pTimestamp},
         protocol::ProtocolVersion,
@@ -448,6 +458,20 @@ mod tests {
                 )
                 .into()],
             }),
+            Message::PeerReputation(
+                PeerReputation::new_signed(
+                    SocketAddr::new(
+                        IpAddr::V4(Ipv4Addr::new(rng.gen(), rng.gen(), rng.gen(), rng.gen())),
+                        rng.gen(),
+                    )
+                    .into(),
+                    rng.gen(),
+                    PeerReputationReason::InvalidBlock,
+                    Time::from_secs_since_epoch(rng.gen::<u32>().into()),
+                    &PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr).0,
+                )
+                .unwrap(),
+            ),
         ];
 
         let (socket1, socket2) =
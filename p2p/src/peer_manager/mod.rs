@@ -40,10 +40,12 @@ use common::{
     primitives::time::{duration_to_int, Time},
     time_getter::TimeGetter,
 };
+use crypto::key::PublicKey;
 use logging::log;
 use networking::types::ConnectionDirection;
 use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress, IsGlobalIp};
 use randomness::{make_pseudo_rng, seq::IteratorRandom, Rng};
+use serialization::hex::HexEncode;
 use utils::{bloom_filters::rolling_bloom_filter::RollingBloomFilter, ensure, set_flag::SetFlag};
 use utils_networking::IpOrSocketAddress;
 
@@ -51,10 +53,10 @@ use crate::{
     config::P2pConfig,
     disconnection_reason::DisconnectionReason,
     error::{ConnectionValidationError, P2pError, PeerError, ProtocolError},
-    interface::types::ConnectedPeer,
+    interface::types::{ConnectedPeer, PeerDetails, PeerLimits, PeerState},
     message::{
-        AddrListRequest, AddrListResponse, AnnounceAddrRequest, PeerManagerMessage, PingRequest,
-        PingResponse, WillDisconnectMessage,
+        AddrListRequest, AddrListResponse, AnnounceAddrRequest, PeerManagerMessage, PeerReputation,
+        PeerReputationReason, PingRequest, PingResponse, WillDisconnectMessage,
     },
     net::{
         types::{
@@ -107,6 +109,10 @@ const PEER_ADDRESS_RESEND_COUNT: usize = 2;
 const PEER_ADDRESSES_ROLLING_BLOOM_FILTER_SIZE: usize = 5000;
 const PEER_ADDRESSES_ROLLING_BLOOM_FPP: f64 = 0.001;
 
+/// Reputation updates issued earlier than this are ignored; the newer ones are remembered
+/// until they become this old, so that they can't be replayed.
+const REPUTATION_UPDATE_MAX_AGE: Duration = Duration::from_secs(10 * 60);
+
 enum OutboundConnectType {
     Automatic {
         block_relay_only: bool,
@@ -168,6 +174,9 @@ where
     /// P2P configuration.
     p2p_config: Arc<P2pConfig>,
 
+    /// Connection limits, initialized from the config and adjustable at runtime.
+    peer_limits: PeerLimits,
+
     time_getter: TimeGetter,
 
     /// Handle for sending/receiving connectivity events
@@ -215,6 +224,10 @@ where
     last_ping_check_time: Option<Time>,
     /// The time after which a new feeler connection can be established.
     next_feeler_connection_time: Time,
+
+    /// Issuer keys and nonces of the recent reputation updates that have already been applied
+    /// and relayed, along with the update timestamps.
+    seen_reputation_updates: BTreeMap<(PublicKey, u64), Time>,
 }
 
 /// Takes IP or socket address and converts it to socket address (adding the default peer port if IP address is used)
@@ -278,11 +291,21 @@ where
             Self::choose_next_feeler_connection_time(&p2p_config, now);
         assert!(!p2p_config.outbound_connection_timeout.is_zero());
         assert!(!p2p_config.ping_timeout.is_zero());
+        log::info!(
+            "P2P identity public key: {}",
+            PublicKey::from_private_key(peerdb.identity_key()).hex_encode()
+        );
+
+        let peer_limits = PeerLimits {
+            max_inbound: *p2p_config.peer_manager_config.max_inbound_connections,
+            max_outbound: *p2p_config.peer_manager_config.outbound_full_relay_count,
+        };
 
         Ok(PeerManager {
             networking_enabled,
             chain_config,
             p2p_config,
+            peer_limits,
             time_getter,
             peer_connectivity_handle: handle,
             peer_mgr_event_receiver,
@@ -301,6 +324,7 @@ where
             last_dns_query_time: None,
             last_ping_check_time: None,
             next_feeler_connection_time,
+            seen_reputation_updates: BTreeMap::new(),
         })
     }
 
@@ -571,6 +595,119 @@ where
         }
     }
 
+    /// Check whether the address belongs to one of the trusted peers.
+    ///
+    /// Only the IP address is compared, so that inbound connections from trusted peers
+    /// are recognized too.
+    fn is_trusted_peer(&self, address: &SocketAddress) -> bool {
+        self.p2p_config
+            .trusted_peers
+            .addresses
+            .iter()
+            .any(|trusted_peer| trusted_peer.as_bannable() == address.as_bannable())
+    }
+
+    fn oldest_allowed_reputation_update_time(&self) -> Time {
+        (self.time_getter.get_time() - REPUTATION_UPDATE_MAX_AGE)
+            .unwrap_or(Time::from_secs_since_epoch(0))
+    }
+
+    fn broadcast_reputation(
+        &mut self,
+        address: SocketAddress,
+        score: i16,
+        reason: PeerReputationReason,
+    ) -> crate::Result<()> {
+        let update = PeerReputation::new_signed(
+            address,
+            score,
+            reason,
+            self.time_getter.get_time(),
+            self.peerdb.identity_key(),
+        )?;
+        self.apply_reputation_update(update, None);
+        Ok(())
+    }
+
+    /// Apply the reputation update to the peer db and relay it to all connected trusted peers,
+    /// except for the one it was received from.
+    ///
+    /// The address is discouraged if its reputation score reaches the discouragement threshold.
+    fn apply_reputation_update(&mut self, update: PeerReputation, sender: Option<PeerId>) {
+        let oldest_allowed = self.oldest_allowed_reputation_update_time();
+        self.seen_reputation_updates.retain(|_, timestamp| *timestamp >= oldest_allowed);
+
+        let update_key = (update.public_key().clone(), update.nonce());
+        if self.seen_reputation_updates.contains_key(&update_key) {
+            return;
+        }
+        self.seen_reputation_updates.insert(update_key, update.timestamp());
+
+        let address = update.address().as_bannable();
+        let new_score = self.peerdb.adjust_reputation(address, update.score());
+
+        log::info!(
+            "Adjusting reputation of {address:?}, adjustment {}, reason {:?}, new score {new_score}",
+            update.score(),
+            update.reason(),
+        );
+
+        if i64::from(new_score) >= i64::from(*self.p2p_config.ban_config.discouragement_threshold)
+            && !self.p2p_config.whitelisted_addresses.contains(&update.address().ip_addr())
+            && !self.peerdb.is_address_discouraged(&address)
+        {
+            self.discourage(address);
+        }
+
+        let trusted_peer_ids = self
+            .peers
+            .values()
+            .filter(|peer| {
+                Some(peer.info.peer_id) != sender && self.is_trusted_peer(&peer.peer_address)
+            })
+            .map(|peer| peer.info.peer_id)
+            .collect::<Vec<_>>();
+        for peer_id in trusted_peer_ids {
+            Self::send_peer_message(
+                &mut self.peer_connectivity_handle,
+                peer_id,
+                PeerManagerMessage::PeerReputation(update.clone()),
+            );
+        }
+    }
+
+    fn handle_peer_reputation(&mut self, peer_id: PeerId, update: PeerReputation) {
+        let peer = self.peers.get(&peer_id).expect("peer sending PeerReputation must be known");
+
+        if !self.is_trusted_peer(&peer.peer_address) {
+            log::debug!("Ignoring reputation update from untrusted peer {peer_id}");
+            return;
+        }
+
+        if !self.p2p_config.trusted_peers.public_keys.contains(update.public_key()) {
+            log::debug!("Ignoring reputation update issued by an unknown node from peer {peer_id}");
+            return;
+        }
+
+        if !update.verify_signature() {
+            log::warn!("Ignoring reputation update with invalid signature from peer {peer_id}");
+            return;
+        }
+
+        let now = self.time_getter.get_time();
+        let timestamp = update.timestamp();
+        if timestamp < self.oldest_allowed_reputation_update_time()
+            || timestamp > (now + self.p2p_config.effective_max_clock_diff()).unwrap_or(now)
+        {
+            log::debug!(
+                "Ignoring reputation update with timestamp {timestamp} from peer {peer_id}"
+            );
+            return;
+        }
+
+        self.apply_reputation_update(update, Some(peer_id));
+    }
+
     /// Try to initiate a new outbound connection
     ///
     /// This function doesn't block on the call but sends a command to the
@@ -767,9 +904,7 @@ where
                 // Outbound peer count is not checked because the node initiates new connections
                 // only when needed or from RPC requests.
                 // TODO: Always allow connections from the whitelisted IPs
-                if self.inbound_peer_count()
-                    >= *self.p2p_config.peer_manager_config.max_inbound_connections
-                {
+                if self.inbound_peer_count() >= self.peer_limits.max_inbound {
                     if self.peerdb.is_address_discouraged(&address.as_bannable()) {
                         log::info!("Rejecting inbound connection from a discouraged address - too many peers");
                         return Err(P2pError::ConnectionValidationFailed(
@@ -880,8 +1015,15 @@ where
 
     /// If there are too many outbound full relay peers, find and disconnect the "worst" one.
     fn evict_full_relay_peer(&mut self) {
+        // The limit may have been raised at runtime above the configured one, in which case
+        // the additional connections must be kept.
+        let candidates = self.eviction_candidates(PeerRole::OutboundFullRelay);
+        if candidates.len() <= self.peer_limits.max_outbound {
+            return;
+        }
+
         if let Some(peer_id) = peers_eviction::select_for_eviction_full_relay(
-            self.eviction_candidates(PeerRole::OutboundFullRelay),
+            candidates,
             &self.p2p_config.peer_manager_config,
             self.time_getter.get_time(),
             &mut make_pseudo_rng(),
@@ -994,6 +1136,7 @@ where
             discovered_own_address,
             last_tip_block_time: None,
             last_tx_time: None,
+            last_message_time: None,
             block_sync_status: PeerBlockSyncStatus::new(),
         };
 
@@ -1246,6 +1389,68 @@ where
         }
     }
 
+    /// Select addresses for new automatic outbound connections, spreading them across ASNs.
+    ///
+    /// Addresses from ASNs that have fewer than `max_connections_per_asn` outbound connections
+    /// are preferred; the limit is only raised when no other candidates remain, so a single
+    /// autonomous system can't easily take over all outbound slots.
+    /// Addresses with an unknown ASN are not limited.
+    fn select_non_reserved_outbound_addresses(
+        &self,
+        cur_outbound_conn_addr_groups: &BTreeSet<AddressGroup>,
+        cur_outbound_conn_asn_counts: &BTreeMap<u32, usize>,
+        additional_filter: &impl Fn(&SocketAddress) -> bool,
+        count: usize,
+    ) -> Vec<SocketAddress> {
+        let max_conns_per_asn = std::cmp::max(
+            *self.p2p_config.peer_manager_config.peerdb_config.max_connections_per_asn,
+            1,
+        ) as usize;
+
+        let mut addr_groups = cur_outbound_conn_addr_groups.clone();
+        let mut asn_counts = cur_outbound_conn_asn_counts.clone();
+        let mut asn_limit = max_conns_per_asn;
+        let mut selected = Vec::new();
+
+        while selected.len() < count {
+            let candidates = self.peerdb.select_non_reserved_outbound_addresses(
+                &addr_groups,
+                &|addr| {
+                    self.peerdb.address_asn(addr).map_or(true, |asn| {
+                        asn_counts.get(&asn).copied().unwrap_or(0) < asn_limit
+                    }) && additional_filter(addr)
+                },
+                count - selected.len(),
+            );
+
+            if candidates.is_empty() {
+                let have_more_candidates = !self
+                    .peerdb
+                    .select_non_reserved_outbound_addresses(&addr_groups, additional_filter, 1)
+                    .is_empty();
+                if !have_more_candidates {
+                    break;
+                }
+                asn_limit += max_conns_per_asn;
+                continue;
+            }
+
+            for address in candidates {
+                if let Some(asn) = self.peerdb.address_asn(&address) {
+                    let asn_count = asn_counts.entry(asn).or_default();
+                    if *asn_count >= asn_limit {
+                        continue;
+                    }
+                    *asn_count += 1;
+                }
+                addr_groups.insert(AddressGroup::from_peer_address(&address.as_peer_address()));
+                selected.push(address);
+            }
+        }
+
+        selected
+    }
+
     fn establish_new_connections(&mut self) {
         let mut cur_outbound_full_relay_conn_count = 0;
         let mut cur_outbound_block_relay_conn_count = 0;
@@ -1278,6 +1483,19 @@ where
             cur_conn_ip_port_to_role_map.insert((socket_addr.ip(), socket_addr.port()), role);
         }
 
+        let mut cur_outbound_conn_asn_counts = self.peerdb.count_connections_by_asn();
+        for (addr, pending_conn) in &self.pending_outbound_connects {
+            if matches!(
+                pending_conn.outbound_connect_type,
+                OutboundConnectType::Feeler
+            ) {
+                continue;
+            }
+            if let Some(asn) = self.peerdb.address_asn(addr) {
+                *cur_outbound_conn_asn_counts.entry(asn).or_default() += 1;
+            }
+        }
+
         let needed_outbound_full_relay_conn_count = {
             let extra_conn_count = if self.tip_is_stale() {
                 *self.p2p_config.peer_manager_config.outbound_full_relay_extra_count
@@ -1285,12 +1503,13 @@ where
                 0
             };
 
-            (*self.p2p_config.peer_manager_config.outbound_full_relay_count + extra_conn_count)
+            (self.peer_limits.max_outbound + extra_conn_count)
                 .saturating_sub(cur_outbound_full_relay_conn_count)
         };
 
-        let new_full_relay_conn_addresses = self.peerdb.select_non_reserved_outbound_addresses(
+        let new_full_relay_conn_addresses = self.select_non_reserved_outbound_addresses(
             &cur_outbound_conn_addr_groups,
+            &cur_outbound_conn_asn_counts,
             &|addr| {
                 self.allow_new_outbound_connection(
                     &cur_conn_ip_port_to_role_map,
@@ -1315,6 +1534,9 @@ where
         for address in &new_full_relay_conn_addresses {
             let addr_group = AddressGroup::from_peer_address(&address.as_peer_address());
             cur_outbound_conn_addr_groups.insert(addr_group);
+            if let Some(asn) = self.peerdb.address_asn(address) {
+                *cur_outbound_conn_asn_counts.entry(asn).or_default() += 1;
+            }
 
             self.connect(
                 *address,
@@ -1329,8 +1551,9 @@ where
                 + *self.p2p_config.peer_manager_config.outbound_block_relay_extra_count)
                 .saturating_sub(cur_outbound_block_relay_conn_count);
 
-        let new_block_relay_conn_addresses = self.peerdb.select_non_reserved_outbound_addresses(
+        let new_block_relay_conn_addresses = self.select_non_reserved_outbound_addresses(
             &cur_outbound_conn_addr_groups,
+            &cur_outbound_conn_asn_counts,
             &|addr| {
                 self.allow_new_outbound_connection(
                     &cur_conn_ip_port_to_role_map,
@@ -1393,6 +1616,10 @@ where
     }
 
     fn handle_incoming_message(&mut self, peer: PeerId, message: PeerManagerMessage) {
+        if let Some(peer_context) = self.peers.get_mut(&peer) {
+            peer_context.last_message_time = Some(self.time_getter.get_time());
+        }
+
         match message {
             PeerManagerMessage::AddrListRequest(_) => self.handle_addr_list_request(peer),
             PeerManagerMessage::AnnounceAddrRequest(r) => {
@@ -1406,6 +1633,7 @@ where
             PeerManagerMessage::WillDisconnect(msg) => {
                 self.handle_will_disconnect_messgae(peer, msg)
             }
+            PeerManagerMessage::PeerReputation(update) => self.handle_peer_reputation(peer, update),
         }
     }
 
@@ -1633,6 +1861,18 @@ where
                 let peers = self.get_connected_peers();
                 response_sender.send(peers);
             }
+            PeerManagerEvent::GetPeerDetails(peer_id, response_sender) => {
+                let details = self.peers.get(&peer_id).map(Self::peer_details);
+                response_sender.send(details);
+            }
+            PeerManagerEvent::GetPeerProtocolVersion(peer_id, response_sender) => {
+                let version = self.peers.get(&peer_id).map(|peer| peer.info.protocol_version);
+                response_sender.send(version);
+            }
+            PeerManagerEvent::ListPeerDetails(response_sender) => {
+                let peers = self.peers.values().map(Self::peer_details).collect();
+                response_sender.send(peers);
+            }
             PeerManagerEvent::GetReserved(response_sender) => {
                 response_sender.send(self.peerdb.get_reserved_nodes().collect())
             }
@@ -1664,6 +1904,20 @@ where
             PeerManagerEvent::ListDiscouraged(response_sender) => {
                 response_sender.send(self.peerdb.list_discouraged().collect())
             }
+            PeerManagerEvent::BroadcastReputation {
+                address,
+                score,
+                reason,
+                response_sender,
+            } => {
+                response_sender.send(self.broadcast_reputation(address, score, reason));
+            }
+            PeerManagerEvent::GetPeerLimits(response_sender) => {
+                response_sender.send(self.peer_limits)
+            }
+            PeerManagerEvent::SetPeerLimits(limits, response_sender) => {
+                response_sender.send(self.set_peer_limits(limits));
+            }
             PeerManagerEvent::EnableNetworking {
                 enable,
                 response_sender,
@@ -1763,10 +2017,26 @@ where
                 ping_min: context.ping_min.map(|time| {
                     duration_to_int(&time).expect("valid timestamp expected (ping_min)")
                 }),
+                last_message_time: context.last_message_time.map(|time| time.as_secs_since_epoch()),
             })
             .collect()
     }
 
+    fn peer_details(context: &PeerContext) -> PeerDetails {
+        let state = if context.block_sync_status.expecting_blocks_since.is_some() {
+            PeerState::ExpectingBlocks
+        } else {
+            PeerState::Idle
+        };
+
+        PeerDetails {
+            peer_id: context.info.peer_id,
+            address: context.peer_address,
+            peer_role: context.peer_role,
+            state,
+        }
+    }
+
     /// Checks if the peer is in active state
     fn is_peer_connected(&self, peer_id: PeerId) -> bool {
         self.peers.contains_key(&peer_id)
@@ -2047,6 +2317,24 @@ where
         }
     }
 
+    fn set_peer_limits(&mut self, limits: PeerLimits) -> crate::Result<()> {
+        utils::ensure!(
+            limits.max_outbound != 0,
+            P2pError::InvalidConfigurationValue(
+                "The maximum number of outbound connections must not be zero".to_owned()
+            )
+        );
+
+        log::info!(
+            "Peer limits changed: max inbound {}, max outbound {}",
+            limits.max_inbound,
+            limits.max_outbound
+        );
+        self.peer_limits = limits;
+
+        Ok(())
+    }
+
     fn enable_networking(&mut self, enable: bool) -> crate::Result<()> {
         if self.networking_enabled == enable {
             return Ok(());
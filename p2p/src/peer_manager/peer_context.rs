@@ -77,6 +77,9 @@ pub struct PeerContext {
 
     pub last_tx_time: Option<Time>,
 
+    /// Last time a peer manager message was received from the peer
+    pub last_message_time: Option<Time>,
+
     /// Certain information from the block sync manager that the peer manager may be interested in.
     pub block_sync_status: PeerBlockSyncStatus,
 }
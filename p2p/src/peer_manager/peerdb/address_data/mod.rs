@@ -94,10 +94,21 @@ pub struct AddressData {
 
 impl AddressData {
     pub fn new(was_reachable: bool, reserved: bool, now: Time) -> Self {
+        Self::new_with_fail_count(was_reachable, reserved, 0, now)
+    }
+
+    /// Create the address data with the given number of consecutive failed connection attempts,
+    /// e.g. the one that was loaded from the db.
+    pub fn new_with_fail_count(
+        was_reachable: bool,
+        reserved: bool,
+        fail_count: u32,
+        now: Time,
+    ) -> Self {
         AddressData {
             state: AddressState::Disconnected {
                 was_reachable,
-                fail_count: 0,
+                fail_count,
                 next_connect_after: now,
             },
             reserved,
@@ -147,6 +158,35 @@ impl AddressData {
         matches!(self.state, AddressState::Unreachable { .. })
     }
 
+    /// The number of consecutive failed connection attempts; None if the address is unreachable.
+    pub fn fail_count(&self) -> Option<u32> {
+        match self.state {
+            AddressState::Connected {} => Some(0),
+            AddressState::Disconnected {
+                was_reachable: _,
+                fail_count,
+                next_connect_after: _,
+            } => Some(fail_count),
+            AddressState::Unreachable { erase_after: _ } => None,
+        }
+    }
+
+    /// A simple reliability score of the address.
+    ///
+    /// Connected addresses have the score of 0, disconnected ones lose a point for each
+    /// consecutive failed connection attempt and unreachable ones get the lowest possible score.
+    pub fn score(&self) -> i32 {
+        match self.state {
+            AddressState::Connected {} => 0,
+            AddressState::Disconnected {
+                was_reachable: _,
+                fail_count,
+                next_connect_after: _,
+            } => 0i32.saturating_sub_unsigned(fail_count),
+            AddressState::Unreachable { erase_after: _ } => i32::MIN,
+        }
+    }
+
     fn next_connect_delay(fail_count: u32, reserved: bool) -> Duration {
         let max_delay = if reserved {
             MAX_DELAY_RESERVED
@@ -91,6 +91,62 @@ fn reachable_reconnects(#[case] seed: Seed) {
     );
 }
 
+#[tracing::instrument(skip(seed))]
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn reserved_reconnects(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let started_at = Time::from_secs_since_epoch(1600000000);
+    let mut now = started_at;
+    let mut address = AddressData::new(true, true, started_at);
+    address.transition_to(AddressStateTransitionTo::Connected, now, &mut rng);
+    address.transition_to(AddressStateTransitionTo::Disconnected, now, &mut rng);
+    assert_eq!(address.score(), 0);
+
+    // Reserved addresses are never purged, no matter how many connection attempts fail
+    let mut connection_attempts = 0;
+    while connection_attempts < 2 * PURGE_REACHABLE_FAIL_COUNT {
+        assert!(!address.is_unreachable());
+        if address.connect_now(now) {
+            address.transition_to(AddressStateTransitionTo::ConnectionFailed, now, &mut rng);
+            connection_attempts += 1;
+            assert_eq!(address.score(), -(connection_attempts as i32));
+        }
+        now = (now + Duration::from_secs(60)).unwrap();
+    }
+
+    // The backoff is reset after a successful connection
+    while !address.connect_now(now) {
+        now = (now + Duration::from_secs(60)).unwrap();
+    }
+    address.transition_to(AddressStateTransitionTo::Connected, now, &mut rng);
+    address.transition_to(AddressStateTransitionTo::Disconnected, now, &mut rng);
+    assert_eq!(address.score(), 0);
+}
+
+#[test]
+fn reserved_next_connect_delay() {
+    // The delay doubles after each failed attempt until it reaches the limit
+    assert_eq!(
+        AddressData::next_connect_delay(0, true),
+        Duration::from_secs(10)
+    );
+    assert_eq!(
+        AddressData::next_connect_delay(1, true),
+        Duration::from_secs(20)
+    );
+    assert_eq!(
+        AddressData::next_connect_delay(4, true),
+        Duration::from_secs(160)
+    );
+    assert_eq!(AddressData::next_connect_delay(6, true), MAX_DELAY_RESERVED);
+    assert_eq!(
+        AddressData::next_connect_delay(u32::MAX, true),
+        MAX_DELAY_RESERVED
+    );
+}
+
 fn next_connect_time_test_impl(rng: &mut impl Rng) {
     let limit_reserved = MAX_DELAY_RESERVED * MAX_DELAY_FACTOR;
     let limit_reachable = MAX_DELAY_REACHABLE * MAX_DELAY_FACTOR;
@@ -0,0 +1,188 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{collections::BTreeMap, net::IpAddr};
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum AsnMapParseError {
+    #[error("Invalid ASN map entry at line {line}: {entry}")]
+    InvalidEntry { line: usize, entry: String },
+}
+
+/// Maps IP prefixes to the numbers of the autonomous systems that announce them.
+///
+/// Lookups return the ASN of the longest matching prefix.
+#[derive(Default, Debug, Clone)]
+pub struct AsnMap {
+    /// Prefix length -> masked IPv4 prefix -> ASN
+    v4: BTreeMap<u8, BTreeMap<u32, u32>>,
+    /// Prefix length -> masked IPv6 prefix -> ASN
+    v6: BTreeMap<u8, BTreeMap<u128, u32>>,
+}
+
+fn mask_v4(addr: u32, prefix_len: u8) -> u32 {
+    match prefix_len {
+        0 => 0,
+        _ => addr & (u32::MAX << (32 - prefix_len as u32)),
+    }
+}
+
+fn mask_v6(addr: u128, prefix_len: u8) -> u128 {
+    match prefix_len {
+        0 => 0,
+        _ => addr & (u128::MAX << (128 - prefix_len as u32)),
+    }
+}
+
+impl AsnMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Assign the ASN to the prefix; prefix lengths that are too big are clamped.
+    pub fn insert(&mut self, prefix: IpAddr, prefix_len: u8, asn: u32) {
+        match prefix {
+            IpAddr::V4(addr) => {
+                let prefix_len = prefix_len.min(32);
+                self.v4
+                    .entry(prefix_len)
+                    .or_default()
+                    .insert(mask_v4(addr.into(), prefix_len), asn);
+            }
+            IpAddr::V6(addr) => {
+                let prefix_len = prefix_len.min(128);
+                self.v6
+                    .entry(prefix_len)
+                    .or_default()
+                    .insert(mask_v6(addr.into(), prefix_len), asn);
+            }
+        }
+    }
+
+    pub fn lookup(&self, ip: &IpAddr) -> Option<u32> {
+        match ip {
+            IpAddr::V4(addr) => self.v4.iter().rev().find_map(|(prefix_len, prefixes)| {
+                prefixes.get(&mask_v4((*addr).into(), *prefix_len)).copied()
+            }),
+            IpAddr::V6(addr) => self.v6.iter().rev().find_map(|(prefix_len, prefixes)| {
+                prefixes.get(&mask_v6((*addr).into(), *prefix_len)).copied()
+            }),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.v4.is_empty() && self.v6.is_empty()
+    }
+
+    /// Parse the map from lines of the form `<ip prefix>/<prefix length> <asn>`,
+    /// e.g. `1.2.0.0/16 100`; empty lines and lines starting with `#` are ignored.
+    pub fn parse(text: &str) -> Result<Self, AsnMapParseError> {
+        let mut map = Self::new();
+
+        for (line_idx, line) in text.lines().enumerate() {
+            let entry = line.trim();
+            if entry.is_empty() || entry.starts_with('#') {
+                continue;
+            }
+
+            let parse_entry = || -> Option<(IpAddr, u8, u32)> {
+                let (prefix, asn) = entry.split_once(char::is_whitespace)?;
+                let (prefix, prefix_len) = prefix.split_once('/')?;
+                Some((
+                    prefix.parse().ok()?,
+                    prefix_len.parse().ok()?,
+                    asn.trim().parse().ok()?,
+                ))
+            };
+            let (prefix, prefix_len, asn) =
+                parse_entry().ok_or_else(|| AsnMapParseError::InvalidEntry {
+                    line: line_idx + 1,
+                    entry: entry.to_owned(),
+                })?;
+
+            map.insert(prefix, prefix_len, asn);
+        }
+
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[tracing::instrument]
+    #[test]
+    fn lookup() {
+        let mut map = AsnMap::new();
+        assert!(map.is_empty());
+
+        map.insert(ip("1.2.0.0"), 16, 100);
+        map.insert(ip("1.2.3.0"), 24, 200);
+        map.insert(ip("2a00:1450::"), 32, 300);
+        map.insert(ip("0.0.0.0"), 0, 1);
+        assert!(!map.is_empty());
+
+        // The longest prefix wins
+        assert_eq!(map.lookup(&ip("1.2.3.4")), Some(200));
+        assert_eq!(map.lookup(&ip("1.2.4.4")), Some(100));
+        // The zero-length prefix matches every IPv4 address, but not IPv6 ones
+        assert_eq!(map.lookup(&ip("5.6.7.8")), Some(1));
+        assert_eq!(map.lookup(&ip("2a00:1450:4017:815::200e")), Some(300));
+        assert_eq!(map.lookup(&ip("2a00:1451::1")), None);
+
+        // Host bits of the inserted prefix are ignored, too long prefixes are clamped
+        map.insert(ip("10.20.30.40"), 8, 400);
+        map.insert(ip("11.0.0.1"), 64, 500);
+        assert_eq!(map.lookup(&ip("10.1.1.1")), Some(400));
+        assert_eq!(map.lookup(&ip("11.0.0.1")), Some(500));
+        assert_eq!(map.lookup(&ip("11.0.0.2")), Some(1));
+    }
+
+    #[tracing::instrument]
+    #[test]
+    fn parse() {
+        let map = AsnMap::parse(
+            "# prefix asn\n\n1.2.0.0/16 100\n  1.2.3.0/24\t200  \n2a00:1450::/32 300\n",
+        )
+        .unwrap();
+        assert_eq!(map.lookup(&ip("1.2.3.4")), Some(200));
+        assert_eq!(map.lookup(&ip("1.2.4.4")), Some(100));
+        assert_eq!(map.lookup(&ip("2a00:1450:4017:815::200e")), Some(300));
+        assert_eq!(map.lookup(&ip("5.6.7.8")), None);
+
+        assert!(AsnMap::parse("").unwrap().is_empty());
+
+        for (text, line, entry) in [
+            ("1.2.0.0/16 100\n1.2.0.0 100", 2, "1.2.0.0 100"),
+            ("1.2.0.0/16", 1, "1.2.0.0/16"),
+            ("1.2.0.0/x 100", 1, "1.2.0.0/x 100"),
+            ("1.2.0/16 100", 1, "1.2.0/16 100"),
+            ("\n1.2.0.0/16 AS100", 2, "1.2.0.0/16 AS100"),
+        ] {
+            assert_eq!(
+                AsnMap::parse(text).unwrap_err(),
+                AsnMapParseError::InvalidEntry {
+                    line,
+                    entry: entry.to_owned()
+                }
+            );
+        }
+    }
+}
@@ -13,14 +13,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crypto::key::PrivateKey;
 use utils::make_config_setting;
 
-use super::salt::Salt;
+use super::{asn_map::AsnMap, salt::Salt};
 
 // TODO: do we need the tables to be this big?
 make_config_setting!(NewAddrTableBucketCount, usize, 1024);
 make_config_setting!(TriedAddrTableBucketCount, usize, 256);
 make_config_setting!(AddrTablesBucketSize, usize, 64);
+make_config_setting!(MaxConnectionsPerAsn, u8, 2);
 
 #[derive(Default, Debug, Clone)]
 pub struct PeerDbConfig {
@@ -32,4 +34,12 @@ pub struct PeerDbConfig {
     pub addr_tables_bucket_size: AddrTablesBucketSize,
     /// The initial value for the peer db's salt.
     pub salt: Option<Salt>,
+    /// The initial value for the node's identity key, which signs the peer reputation updates
+    /// issued by the node.
+    pub identity_key: Option<PrivateKey>,
+    /// Outbound connections to peers from an ASN that already has this many of them are only
+    /// made if there are no candidates from other ASNs.
+    pub max_connections_per_asn: MaxConnectionsPerAsn,
+    /// The IP prefix to ASN mapping; if it's empty, ASNs are not taken into account.
+    pub asn_map: AsnMap,
 }
@@ -26,6 +26,7 @@
 
 pub mod address_data;
 pub mod address_tables;
+pub mod asn_map;
 pub mod config;
 pub mod salt;
 pub mod storage;
@@ -34,11 +35,13 @@ mod storage_load;
 
 use std::{
     collections::{btree_map::Entry, BTreeMap, BTreeSet},
+    net::{IpAddr, SocketAddr},
     sync::Arc,
     time::Duration,
 };
 
 use common::{chain::ChainConfig, primitives::time::Time, time_getter::TimeGetter};
+use crypto::key::PrivateKey;
 use logging::log;
 use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress};
 use randomness::{make_pseudo_rng, seq::IteratorRandom, Rng, SliceRandom};
@@ -60,6 +63,32 @@ use super::{
 
 pub use storage_load::{open_storage, CURRENT_STORAGE_VERSION};
 
+/// Convert an IPv4-mapped IPv6 address (e.g. `::ffff:1.2.3.4`) to the plain IPv4 one,
+/// so that the same host can't occupy two address slots.
+fn normalise_address(addr: SocketAddress) -> SocketAddress {
+    let socket_addr = addr.socket_addr();
+    SocketAddress::new(SocketAddr::new(
+        socket_addr.ip().to_canonical(),
+        socket_addr.port(),
+    ))
+}
+
+/// Format the addresses as A/AAAA resource records suitable for inclusion in a BIND zone file.
+///
+/// DNS records can't hold port numbers, so they are dropped; duplicate IPs are only listed once.
+pub fn format_as_dns_seed_entries(addrs: &[SocketAddress]) -> Vec<String> {
+    addrs
+        .iter()
+        .map(|addr| addr.ip_addr())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|ip| match ip {
+            IpAddr::V4(ip) => format!("@\tIN\tA\t{ip}"),
+            IpAddr::V6(ip) => format!("@\tIN\tAAAA\t{ip}"),
+        })
+        .collect()
+}
+
 pub struct PeerDb<S> {
     /// P2P configuration
     p2p_config: Arc<P2pConfig>,
@@ -85,6 +114,9 @@ pub struct PeerDb<S> {
     /// Discouraged addresses along with the discouragement expiration time.
     discouraged_addresses: BTreeMap<BannableAddress, Time>,
 
+    /// Reputation scores received from trusted peers (not persisted).
+    reputation_scores: BTreeMap<BannableAddress, i32>,
+
     /// Anchor addresses
     anchor_addresses: BTreeSet<SocketAddress>,
 
@@ -93,6 +125,9 @@ pub struct PeerDb<S> {
     storage: S,
 
     salt: Salt,
+
+    /// The key signing the peer reputation updates issued by this node.
+    identity_key: PrivateKey,
 }
 
 impl<S: PeerDbStorage> PeerDb<S> {
@@ -108,7 +143,9 @@ impl<S: PeerDbStorage> PeerDb<S> {
             banned_addresses,
             discouraged_addresses,
             anchor_addresses,
+            address_fail_counts,
             salt,
+            identity_key,
         } = LoadedStorage::load_storage(&storage, &p2p_config.peer_manager_config.peerdb_config)?;
 
         let reserved_nodes = p2p_config
@@ -149,9 +186,10 @@ impl<S: PeerDbStorage> PeerDb<S> {
                 }
             }
 
-            let addr_data = AddressData::new(
+            let addr_data = AddressData::new_with_fail_count(
                 *state == KnownAddressState::Tried,
                 reserved_nodes.contains(addr),
+                address_fail_counts.get(addr).copied().unwrap_or(0),
                 now,
             );
             addresses.insert(*addr, addr_data);
@@ -182,11 +220,13 @@ impl<S: PeerDbStorage> PeerDb<S> {
             address_tables,
             banned_addresses,
             discouraged_addresses,
+            reputation_scores: BTreeMap::new(),
             anchor_addresses,
             p2p_config,
             time_getter,
             storage,
             salt,
+            identity_key,
         })
     }
 
@@ -194,6 +234,10 @@ impl<S: PeerDbStorage> PeerDb<S> {
         self.salt
     }
 
+    pub fn identity_key(&self) -> &PrivateKey {
+        &self.identity_key
+    }
+
     /// Iterator of all known addresses.
     ///
     /// Result could be shared with remote peers over network.
@@ -376,6 +420,8 @@ impl<S: PeerDbStorage> PeerDb<S> {
 
     /// Add a new peer address
     pub fn peer_discovered(&mut self, address: SocketAddress) {
+        let address = normalise_address(address);
+
         #[allow(clippy::map_entry)]
         if !self.addresses.contains_key(&address) {
             log::debug!("New address discovered: {}", address.to_string());
@@ -399,6 +445,7 @@ impl<S: PeerDbStorage> PeerDb<S> {
     /// When [`crate::peer_manager::PeerManager::heartbeat()`] has initiated an outbound connection
     /// and the connection is refused, it's reported back to the `PeerDb` so it marks the address as unreachable.
     pub fn report_outbound_failure(&mut self, address: SocketAddress) {
+        let address = normalise_address(address);
         self.change_address_state(address, AddressStateTransitionTo::ConnectionFailed);
 
         // Note: if the failed connection is a manual one, the address won't be in the addr tables,
@@ -412,15 +459,32 @@ impl<S: PeerDbStorage> PeerDb<S> {
     /// After `PeerManager` has established either an inbound or an outbound connection,
     /// it informs the `PeerDb` about it.
     pub fn outbound_peer_connected(&mut self, address: SocketAddress) {
+        let address = normalise_address(address);
         self.change_address_state(address, AddressStateTransitionTo::Connected);
         self.move_addr_to_tried(&address);
     }
 
     /// Handle peer disconnect event with unspecified reason
     pub fn outbound_peer_disconnected(&mut self, address: SocketAddress) {
+        let address = normalise_address(address);
         self.change_address_state(address, AddressStateTransitionTo::Disconnected);
     }
 
+    /// Return addresses from the "tried" table whose score is above `score_threshold`.
+    ///
+    /// This is intended for producing DNS seed records, see [`format_as_dns_seed_entries`].
+    pub fn export_trusted_addresses(&self, score_threshold: i32) -> Vec<SocketAddress> {
+        self.address_tables
+            .tried_addresses()
+            .filter(|addr| {
+                self.addresses
+                    .get(addr)
+                    .is_some_and(|addr_data| addr_data.score() > score_threshold)
+            })
+            .copied()
+            .collect()
+    }
+
     pub fn remove_address(&mut self, address: &SocketAddress) {
         if !self.reserved_nodes.contains(address) {
             self.addresses.remove(address);
@@ -531,7 +595,24 @@ impl<S: PeerDbStorage> PeerDb<S> {
             transition,
         );
 
+        let old_fail_count = address_data.fail_count();
         address_data.transition_to(transition, now, &mut make_pseudo_rng());
+        let new_fail_count = address_data.fail_count();
+
+        // Persist the fail count of the addresses that are stored in the db, so that their
+        // scores survive restarts. Unreachable addresses will be deleted from the db soon anyway.
+        if let Some(new_fail_count) = new_fail_count {
+            if old_fail_count != Some(new_fail_count) && self.address_tables.have_addr(&address) {
+                update_db(&self.storage, |tx| {
+                    if new_fail_count == 0 {
+                        tx.del_address_fail_count(&address)
+                    } else {
+                        tx.set_address_fail_count(&address, new_fail_count)
+                    }
+                })
+                .expect("DB failure when updating the fail count of address {address}");
+            }
+        }
     }
 
     pub fn is_reserved_node(&self, address: &SocketAddress) -> bool {
@@ -604,6 +685,17 @@ impl<S: PeerDbStorage> PeerDb<S> {
         self.discouraged_addresses.insert(address, discourage_till);
     }
 
+    /// Apply a reputation score delta to the address and return the new score
+    pub fn adjust_reputation(&mut self, address: BannableAddress, delta: i16) -> i32 {
+        let score = self.reputation_scores.entry(address).or_insert(0);
+        *score = score.saturating_add(delta.into());
+        *score
+    }
+
+    pub fn reputation(&self, address: &BannableAddress) -> i32 {
+        self.reputation_scores.get(address).copied().unwrap_or(0)
+    }
+
     pub fn is_address_banned_or_discouraged(&self, address: &BannableAddress) -> bool {
         self.is_address_banned(address) || self.is_address_discouraged(address)
     }
@@ -635,6 +727,29 @@ impl<S: PeerDbStorage> PeerDb<S> {
         self.addresses.len()
     }
 
+    /// Return the number of the autonomous system the address belongs to, if it's known.
+    pub fn address_asn(&self, address: &SocketAddress) -> Option<u32> {
+        self.p2p_config
+            .peer_manager_config
+            .peerdb_config
+            .asn_map
+            .lookup(&address.ip_addr())
+    }
+
+    /// Count the connected outbound addresses per ASN; addresses with an unknown ASN are skipped.
+    pub fn count_connections_by_asn(&self) -> BTreeMap<u32, usize> {
+        let mut counts = BTreeMap::new();
+        for (address, address_data) in &self.addresses {
+            if !address_data.is_connected() {
+                continue;
+            }
+            if let Some(asn) = self.address_asn(address) {
+                *counts.entry(asn).or_default() += 1;
+            }
+        }
+        counts
+    }
+
     #[cfg(test)]
     pub fn address_tables(&self) -> &AddressTables {
         &self.address_tables
@@ -649,6 +764,7 @@ impl<S: PeerDbStorage> PeerDb<S> {
 pub trait PeerDbInterface {
     fn is_address_banned(&self, address: &BannableAddress) -> bool;
     fn is_address_discouraged(&self, address: &BannableAddress) -> bool;
+    fn reputation(&self, address: &BannableAddress) -> i32;
 
     fn peer_discovered(&mut self, address: SocketAddress);
 }
@@ -662,6 +778,10 @@ impl<S: PeerDbStorage> PeerDbInterface for PeerDb<S> {
         self.is_address_discouraged(address)
     }
 
+    fn reputation(&self, address: &BannableAddress) -> i32 {
+        self.reputation(address)
+    }
+
     fn peer_discovered(&mut self, address: SocketAddress) {
         self.peer_discovered(address)
     }
@@ -14,6 +14,7 @@
 // limitations under the License.
 
 use common::primitives::time::Time;
+use crypto::key::PrivateKey;
 use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress};
 use serialization::{Decode, Encode};
 
@@ -36,6 +37,8 @@ pub trait PeerDbStorageRead {
 
     fn get_salt(&self) -> crate::Result<Option<Salt>>;
 
+    fn get_identity_key(&self) -> crate::Result<Option<PrivateKey>>;
+
     fn get_known_addresses(&self) -> crate::Result<Vec<(SocketAddress, KnownAddressState)>>;
 
     fn get_banned_addresses(&self) -> crate::Result<Vec<(BannableAddress, Time)>>;
@@ -43,6 +46,8 @@ pub trait PeerDbStorageRead {
     fn get_discouraged_addresses(&self) -> crate::Result<Vec<(BannableAddress, Time)>>;
 
     fn get_anchor_addresses(&self) -> crate::Result<Vec<SocketAddress>>;
+
+    fn get_address_fail_counts(&self) -> crate::Result<Vec<(SocketAddress, u32)>>;
 }
 
 pub trait PeerDbStorageWrite {
@@ -50,6 +55,8 @@ pub trait PeerDbStorageWrite {
 
     fn set_salt(&mut self, salt: Salt) -> crate::Result<()>;
 
+    fn set_identity_key(&mut self, key: &PrivateKey) -> crate::Result<()>;
+
     // Note: the "add" methods below will overwrite the existing value if it's present.
 
     fn add_known_address(
@@ -57,8 +64,16 @@ pub trait PeerDbStorageWrite {
         address: &SocketAddress,
         state: KnownAddressState,
     ) -> crate::Result<()>;
+    // Note: this also deletes the address' fail count.
     fn del_known_address(&mut self, address: &SocketAddress) -> crate::Result<()>;
 
+    fn set_address_fail_count(
+        &mut self,
+        address: &SocketAddress,
+        fail_count: u32,
+    ) -> crate::Result<()>;
+    fn del_address_fail_count(&mut self, address: &SocketAddress) -> crate::Result<()>;
+
     fn add_banned_address(&mut self, address: &BannableAddress, time: Time) -> crate::Result<()>;
     fn del_banned_address(&mut self, address: &BannableAddress) -> crate::Result<()>;
 
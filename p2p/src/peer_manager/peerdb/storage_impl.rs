@@ -16,6 +16,7 @@
 use std::time::Duration;
 
 use common::primitives::time::Time;
+use crypto::key::PrivateKey;
 use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress};
 use serialization::{encoded::Encoded, DecodeAll, Encode};
 use storage::MakeMapRef;
@@ -54,11 +55,16 @@ storage::decl_schema! {
 
         /// Table for anchor peers addresses
         pub DBAnchorAddresses: Map<String, ()>,
+
+        /// Table for the numbers of consecutive failed connection attempts to known addresses
+        /// (zero counts are not stored)
+        pub DBAddressFailCounts: Map<String, u32>,
     }
 }
 
 const VALUE_ID_VERSION: ValueId = 1;
 const VALUE_ID_SALT: ValueId = 2;
+const VALUE_ID_IDENTITY_KEY: ValueId = 3;
 
 type PeerDbStoreTxRo<'st, B> = StorageTxRo<'st, B, Schema>;
 type PeerDbStoreTxRw<'st, B> = StorageTxRw<'st, B, Schema>;
@@ -76,6 +82,13 @@ impl<'st, B: storage::Backend> PeerDbStorageWrite for PeerDbStoreTxRw<'st, B> {
         Ok(self.storage().get_mut::<DBValue, _>().put(VALUE_ID_SALT, salt.encode())?)
     }
 
+    fn set_identity_key(&mut self, key: &PrivateKey) -> crate::Result<()> {
+        Ok(self
+            .storage()
+            .get_mut::<DBValue, _>()
+            .put(VALUE_ID_IDENTITY_KEY, key.encode())?)
+    }
+
     fn add_known_address(
         &mut self,
         address: &SocketAddress,
@@ -88,7 +101,23 @@ impl<'st, B: storage::Backend> PeerDbStorageWrite for PeerDbStoreTxRw<'st, B> {
     }
 
     fn del_known_address(&mut self, address: &SocketAddress) -> crate::Result<()> {
-        Ok(self.storage().get_mut::<DBKnownAddresses, _>().del(address.to_string())?)
+        self.storage().get_mut::<DBKnownAddresses, _>().del(address.to_string())?;
+        self.del_address_fail_count(address)
+    }
+
+    fn set_address_fail_count(
+        &mut self,
+        address: &SocketAddress,
+        fail_count: u32,
+    ) -> crate::Result<()> {
+        Ok(self
+            .storage()
+            .get_mut::<DBAddressFailCounts, _>()
+            .put(address.to_string(), fail_count)?)
+    }
+
+    fn del_address_fail_count(&mut self, address: &SocketAddress) -> crate::Result<()> {
+        Ok(self.storage().get_mut::<DBAddressFailCounts, _>().del(address.to_string())?)
     }
 
     fn add_banned_address(&mut self, address: &BannableAddress, time: Time) -> crate::Result<()> {
@@ -155,6 +184,18 @@ impl<'st, B: storage::Backend> PeerDbStorageRead for PeerDbStoreTxRo<'st, B> {
             .transpose()
     }
 
+    fn get_identity_key(&self) -> crate::Result<Option<PrivateKey>> {
+        let map = self.storage().get::<DBValue, _>();
+        let vec_opt = map.get(VALUE_ID_IDENTITY_KEY)?.as_ref().map(Encoded::decode);
+        vec_opt
+            .map(|vec| {
+                PrivateKey::decode_all(&mut vec.as_ref()).map_err(|err| {
+                    P2pError::InvalidStorageState(format!("Error decoding identity key: {err}"))
+                })
+            })
+            .transpose()
+    }
+
     fn get_known_addresses(&self) -> crate::Result<Vec<(SocketAddress, KnownAddressState)>> {
         let map = self.storage().get::<DBKnownAddresses, _>();
         let iter = map.prefix_iter_decoded(&())?.map(|(addr_str, state)| {
@@ -205,4 +246,17 @@ impl<'st, B: storage::Backend> PeerDbStorageRead for PeerDbStoreTxRo<'st, B> {
         });
         itertools::process_results(iter, |iter| iter.collect::<Vec<_>>())
     }
+
+    fn get_address_fail_counts(&self) -> crate::Result<Vec<(SocketAddress, u32)>> {
+        let map = self.storage().get::<DBAddressFailCounts, _>();
+        let iter = map.prefix_iter_decoded(&())?.map(|(addr_str, fail_count)| {
+            let addr = addr_str.parse::<SocketAddress>().map_err(|err| {
+                P2pError::InvalidStorageState(format!(
+                    "Error parsing address from {addr_str:?}: {err}"
+                ))
+            })?;
+            crate::Result::Ok((addr, fail_count))
+        });
+        itertools::process_results(iter, |iter| iter.collect::<Vec<_>>())
+    }
 }
@@ -16,6 +16,7 @@
 use std::collections::{BTreeMap, BTreeSet};
 
 use common::primitives::time::Time;
+use crypto::key::{KeyKind, PrivateKey};
 use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress};
 
 use crate::{
@@ -25,6 +26,7 @@ use crate::{
 
 use super::{
     config::PeerDbConfig,
+    normalise_address,
     salt::Salt,
     storage::{KnownAddressState, PeerDbStorage, PeerDbStorageRead, PeerDbStorageWrite},
     storage_impl::PeerDbStorageImpl,
@@ -37,7 +39,9 @@ pub struct LoadedStorage {
     pub banned_addresses: BTreeMap<BannableAddress, Time>,
     pub discouraged_addresses: BTreeMap<BannableAddress, Time>,
     pub anchor_addresses: BTreeSet<SocketAddress>,
+    pub address_fail_counts: BTreeMap<SocketAddress, u32>,
     pub salt: Salt,
+    pub identity_key: PrivateKey,
 }
 
 impl LoadedStorage {
@@ -51,7 +55,7 @@ impl LoadedStorage {
 
         match version {
             None => Self::init_storage(storage, peerdb_config),
-            Some(CURRENT_STORAGE_VERSION) => Self::load_storage_v3(storage),
+            Some(CURRENT_STORAGE_VERSION) => Self::load_storage_v3(storage, peerdb_config),
             Some(version) => Err(P2pError::PeerDbStorageVersionMismatch {
                 expected_version: CURRENT_STORAGE_VERSION,
                 actual_version: version,
@@ -64,10 +68,12 @@ impl LoadedStorage {
         peerdb_config: &PeerDbConfig,
     ) -> crate::Result<LoadedStorage> {
         let salt = peerdb_config.salt.unwrap_or_else(Salt::new_random);
+        let identity_key = peerdb_config.identity_key.clone().unwrap_or_else(new_identity_key);
 
         let mut tx = storage.transaction_rw()?;
         tx.set_version(CURRENT_STORAGE_VERSION)?;
         tx.set_salt(salt)?;
+        tx.set_identity_key(&identity_key)?;
         tx.commit()?;
 
         Ok(LoadedStorage {
@@ -75,34 +81,130 @@ impl LoadedStorage {
             banned_addresses: BTreeMap::new(),
             discouraged_addresses: BTreeMap::new(),
             anchor_addresses: BTreeSet::new(),
+            address_fail_counts: BTreeMap::new(),
             salt,
+            identity_key,
         })
     }
 
-    fn load_storage_v3<S: PeerDbStorage>(storage: &S) -> crate::Result<LoadedStorage> {
+    fn load_storage_v3<S: PeerDbStorage>(
+        storage: &S,
+        peerdb_config: &PeerDbConfig,
+    ) -> crate::Result<LoadedStorage> {
         let tx = storage.transaction_ro()?;
 
-        let known_addresses = tx.get_known_addresses()?.into_iter().collect::<BTreeMap<_, _>>();
+        let mut known_addresses = tx.get_known_addresses()?.into_iter().collect::<BTreeMap<_, _>>();
 
         let banned_addresses = tx.get_banned_addresses()?.into_iter().collect::<BTreeMap<_, _>>();
 
         let discouraged_addresses =
             tx.get_discouraged_addresses()?.into_iter().collect::<BTreeMap<_, _>>();
 
-        let anchor_addresses = tx.get_anchor_addresses()?.into_iter().collect::<BTreeSet<_>>();
+        let mut anchor_addresses = tx.get_anchor_addresses()?.into_iter().collect::<BTreeSet<_>>();
+
+        let mut address_fail_counts =
+            tx.get_address_fail_counts()?.into_iter().collect::<BTreeMap<_, _>>();
 
         let salt = tx
             .get_salt()?
             .ok_or_else(|| P2pError::InvalidStorageState("Missing salt".to_owned()))?;
 
+        let identity_key = tx.get_identity_key()?;
+        tx.close();
+
+        Self::normalise_addresses(
+            storage,
+            &mut known_addresses,
+            &mut anchor_addresses,
+            &mut address_fail_counts,
+        )?;
+
+        // The identity key is missing in the databases created before it was introduced
+        let identity_key = match identity_key {
+            Some(identity_key) => identity_key,
+            None => {
+                let identity_key =
+                    peerdb_config.identity_key.clone().unwrap_or_else(new_identity_key);
+                let mut tx = storage.transaction_rw()?;
+                tx.set_identity_key(&identity_key)?;
+                tx.commit()?;
+                identity_key
+            }
+        };
+
         Ok(LoadedStorage {
             known_addresses,
             banned_addresses,
             discouraged_addresses,
             anchor_addresses,
+            address_fail_counts,
             salt,
+            identity_key,
         })
     }
+
+    /// Replace the IPv4-mapped IPv6 addresses, which could be stored before the addresses
+    /// were normalised, with the plain IPv4 ones, merging the entries of the same host.
+    fn normalise_addresses<S: PeerDbStorage>(
+        storage: &S,
+        known_addresses: &mut BTreeMap<SocketAddress, KnownAddressState>,
+        anchor_addresses: &mut BTreeSet<SocketAddress>,
+        address_fail_counts: &mut BTreeMap<SocketAddress, u32>,
+    ) -> crate::Result<()> {
+        let is_not_normalised = |addr: &&SocketAddress| normalise_address(**addr) != **addr;
+        let known_to_normalise =
+            known_addresses.keys().filter(is_not_normalised).copied().collect::<Vec<_>>();
+        let anchors_to_normalise =
+            anchor_addresses.iter().filter(is_not_normalised).copied().collect::<Vec<_>>();
+
+        if known_to_normalise.is_empty() && anchors_to_normalise.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = storage.transaction_rw()?;
+
+        for addr in known_to_normalise {
+            let normalised_addr = normalise_address(addr);
+            let state = known_addresses.remove(&addr).expect("must be present");
+            // The address stays "tried" if either entry is, and the lower fail count is kept
+            let state = match known_addresses.get(&normalised_addr) {
+                Some(KnownAddressState::Tried) => KnownAddressState::Tried,
+                Some(KnownAddressState::New) | None => state,
+            };
+            let fail_count = [
+                address_fail_counts.remove(&addr),
+                address_fail_counts.get(&normalised_addr).copied(),
+            ]
+            .into_iter()
+            .flatten()
+            .min();
+
+            // Note: this also deletes the fail count
+            tx.del_known_address(&addr)?;
+            tx.add_known_address(&normalised_addr, state)?;
+            known_addresses.insert(normalised_addr, state);
+            if let Some(fail_count) = fail_count {
+                tx.set_address_fail_count(&normalised_addr, fail_count)?;
+                address_fail_counts.insert(normalised_addr, fail_count);
+            }
+        }
+
+        for addr in anchors_to_normalise {
+            let normalised_addr = normalise_address(addr);
+            tx.del_anchor_address(&addr)?;
+            tx.add_anchor_address(&normalised_addr)?;
+            anchor_addresses.remove(&addr);
+            anchor_addresses.insert(normalised_addr);
+        }
+
+        tx.commit()?;
+
+        Ok(())
+    }
+}
+
+fn new_identity_key() -> PrivateKey {
+    PrivateKey::new_from_entropy(KeyKind::Secp256k1Schnorr).0
 }
 
 pub fn open_storage<Backend>(backend: Backend) -> crate::Result<PeerDbStorageImpl<Backend>>
@@ -16,6 +16,8 @@
 use std::{
     collections::{BTreeMap, BTreeSet},
     fmt::Debug,
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
     sync::Arc,
     time::Duration,
 };
@@ -29,8 +31,11 @@ use ::test_utils::{
 };
 use common::{chain::config::create_unit_test_config, primitives::time::Time};
 use networking::test_helpers::TestAddressMaker;
-use p2p_types::socket_addr_ext::SocketAddrExt;
-use randomness::Rng;
+use p2p_types::{
+    bannable_address::BannableAddress, socket_addr_ext::SocketAddrExt,
+    socket_address::SocketAddress,
+};
+use randomness::{Rng, SliceRandom};
 
 use crate::{
     ban_config::BanConfig,
@@ -38,9 +43,9 @@ use crate::{
         peerdb::{
             address_data::{self, PURGE_REACHABLE_FAIL_COUNT, PURGE_UNREACHABLE_TIME},
             salt::Salt,
-            storage::{KnownAddressState, PeerDbStorageRead},
+            storage::{KnownAddressState, PeerDbStorageRead, PeerDbStorageWrite},
         },
-        peerdb_common::Transactional,
+        peerdb_common::{TransactionRw, Transactional},
     },
     test_helpers::{
         peerdb_inmemory_store, test_p2p_config, test_p2p_config_with_ban_config,
@@ -517,6 +522,9 @@ fn remove_addr(#[case] seed: Seed) {
         new_addr_table_bucket_count: 10.into(),
         tried_addr_table_bucket_count: 10.into(),
         salt: Some(Salt::new_random_with_rng(&mut rng)),
+        identity_key: None,
+        max_connections_per_asn: Default::default(),
+        asn_map: Default::default(),
     }));
 
     let mut peerdb = PeerDb::new(
@@ -578,6 +586,9 @@ fn remove_unreachable(#[case] seed: Seed) {
         new_addr_table_bucket_count: 10.into(),
         tried_addr_table_bucket_count: 10.into(),
         salt: Some(Salt::new_random_with_rng(&mut rng)),
+        identity_key: None,
+        max_connections_per_asn: Default::default(),
+        asn_map: Default::default(),
     }));
 
     let mut peerdb = PeerDb::new(
@@ -674,6 +685,9 @@ fn new_addr_count_limit(#[case] seed: Seed, #[values(true, false)] use_reserved_
         new_addr_table_bucket_count: bucket_count.into(),
         tried_addr_table_bucket_count: bucket_count.into(),
         salt: Some(Salt::new_random_with_rng(&mut rng)),
+        identity_key: None,
+        max_connections_per_asn: Default::default(),
+        asn_map: Default::default(),
     }));
 
     let mut peerdb = PeerDb::new(
@@ -733,6 +747,9 @@ fn tried_addr_count_limit(#[case] seed: Seed, #[values(true, false)] use_reserve
         new_addr_table_bucket_count: bucket_count.into(),
         tried_addr_table_bucket_count: bucket_count.into(),
         salt: Some(Salt::new_random_with_rng(&mut rng)),
+        identity_key: None,
+        max_connections_per_asn: Default::default(),
+        asn_map: Default::default(),
     }));
 
     let mut peerdb = PeerDb::new(
@@ -797,6 +814,9 @@ fn new_tried_addr_selection_frequency() {
                 new_addr_table_bucket_count: bucket_count.into(),
                 tried_addr_table_bucket_count: bucket_count.into(),
                 salt: Some(Salt::new_random_with_rng(&mut rng)),
+                identity_key: None,
+                max_connections_per_asn: Default::default(),
+                asn_map: Default::default(),
             }));
 
             let mut peerdb = PeerDb::new(
@@ -873,6 +893,266 @@ fn new_tried_addr_selection_frequency() {
     }
 }
 
+// Check that `export_trusted_addresses` only returns addresses from the tried table whose
+// score is above the threshold, and that the scores survive reloading the db.
+#[tracing::instrument(skip(seed))]
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn export_trusted_addresses(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let db_store = peerdb_inmemory_store();
+    let time_getter = BasicTestTimeGetter::new();
+    let chain_config = create_unit_test_config();
+    let p2p_config = Arc::new(test_p2p_config());
+    let mut peerdb = PeerDb::new(
+        &chain_config,
+        Arc::clone(&p2p_config),
+        time_getter.get_time_getter(),
+        db_store,
+    )
+    .unwrap();
+
+    let addrs = make_non_colliding_addresses(
+        &[new_addr_table(&peerdb), tried_addr_table(&peerdb)],
+        3,
+        &mut rng,
+    );
+    let (good_addr, failing_addr, new_addr) = (addrs[0], addrs[1], addrs[2]);
+
+    // This one is in the tried table and has the score of 0.
+    peerdb.outbound_peer_connected(good_addr);
+    peerdb.outbound_peer_disconnected(good_addr);
+    assert_eq!(peerdb.addresses.get(&good_addr).unwrap().score(), 0);
+
+    // This one is in the tried table too, but it has failed to connect twice.
+    peerdb.outbound_peer_connected(failing_addr);
+    peerdb.outbound_peer_disconnected(failing_addr);
+    peerdb.report_outbound_failure(failing_addr);
+    peerdb.report_outbound_failure(failing_addr);
+    assert_eq!(peerdb.addresses.get(&failing_addr).unwrap().score(), -2);
+
+    // This one is only in the new table.
+    peerdb.peer_discovered(new_addr);
+
+    let check_exported = |peerdb: &PeerDb<_>| {
+        assert_eq!(peerdb.export_trusted_addresses(0), vec![]);
+        assert_eq!(peerdb.export_trusted_addresses(-1), vec![good_addr]);
+        assert_eq!(peerdb.export_trusted_addresses(-2), vec![good_addr]);
+        assert_eq!(
+            peerdb.export_trusted_addresses(-3).into_iter().collect::<BTreeSet<_>>(),
+            BTreeSet::from([good_addr, failing_addr])
+        );
+    };
+    check_exported(&peerdb);
+    assert_addr_consistency(&peerdb);
+
+    let peerdb = PeerDb::new(
+        &chain_config,
+        p2p_config,
+        time_getter.get_time_getter(),
+        peerdb.storage,
+    )
+    .unwrap();
+    assert_eq!(peerdb.addresses.get(&good_addr).unwrap().score(), 0);
+    assert_eq!(peerdb.addresses.get(&failing_addr).unwrap().score(), -2);
+    check_exported(&peerdb);
+    assert_addr_consistency(&peerdb);
+}
+
+// Check that an IPv4-mapped IPv6 address and the plain IPv4 one of the same host
+// only occupy one entry.
+#[tracing::instrument(skip(seed))]
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn ipv4_mapped_addresses(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let db_store = peerdb_inmemory_store();
+    let time_getter = BasicTestTimeGetter::new();
+    let chain_config = create_unit_test_config();
+    let p2p_config = Arc::new(test_p2p_config());
+    let mut peerdb = PeerDb::new(
+        &chain_config,
+        p2p_config,
+        time_getter.get_time_getter(),
+        db_store,
+    )
+    .unwrap();
+
+    let ipv4_addr: SocketAddress = "1.2.3.4:8080".parse().unwrap();
+    let mapped_addr: SocketAddress = "[::ffff:1.2.3.4]:8080".parse().unwrap();
+    let mut addrs = [ipv4_addr, mapped_addr];
+    addrs.shuffle(&mut rng);
+
+    for addr in addrs {
+        peerdb.peer_discovered(addr);
+    }
+    assert_eq!(
+        peerdb.addresses.keys().collect::<Vec<_>>(),
+        vec![&ipv4_addr]
+    );
+    assert_eq!(new_addr_table(&peerdb).addr_count(), 1);
+
+    // A connection to the mapped address moves the IPv4 one to the tried table
+    peerdb.outbound_peer_connected(mapped_addr);
+    assert!(peerdb.addresses.get(&ipv4_addr).unwrap().is_connected());
+    peerdb.outbound_peer_disconnected(mapped_addr);
+    assert_eq!(
+        peerdb.addresses.keys().collect::<Vec<_>>(),
+        vec![&ipv4_addr]
+    );
+    assert_eq!(new_addr_table(&peerdb).addr_count(), 0);
+    assert_eq!(tried_addr_table(&peerdb).addr_count(), 1);
+
+    // Other IPv6 addresses are kept as is
+    let ipv6_addr: SocketAddress = "[2001:db8::1]:8080".parse().unwrap();
+    peerdb.peer_discovered(ipv6_addr);
+    assert!(peerdb.addresses.contains_key(&ipv6_addr));
+
+    assert_addr_consistency(&peerdb);
+}
+
+// Check that IPv4-mapped IPv6 addresses stored before the addresses were normalised are
+// converted when the db is loaded, and merged with the IPv4 entries of the same hosts.
+#[tracing::instrument]
+#[test]
+fn ipv4_mapped_addresses_in_db() {
+    let time_getter = BasicTestTimeGetter::new();
+    let chain_config = create_unit_test_config();
+    let p2p_config = Arc::new(test_p2p_config());
+    let peerdb = PeerDb::new(
+        &chain_config,
+        Arc::clone(&p2p_config),
+        time_getter.get_time_getter(),
+        peerdb_inmemory_store(),
+    )
+    .unwrap();
+
+    let ipv4_addr1: SocketAddress = "1.2.3.4:8080".parse().unwrap();
+    let mapped_addr1: SocketAddress = "[::ffff:1.2.3.4]:8080".parse().unwrap();
+    let ipv4_addr2: SocketAddress = "5.6.7.8:8080".parse().unwrap();
+    let mapped_addr2: SocketAddress = "[::ffff:5.6.7.8]:8080".parse().unwrap();
+
+    let db_store = peerdb.storage;
+    {
+        let mut tx = db_store.transaction_rw().unwrap();
+        tx.add_known_address(&ipv4_addr1, KnownAddressState::New).unwrap();
+        tx.set_address_fail_count(&ipv4_addr1, 3).unwrap();
+        tx.add_known_address(&mapped_addr1, KnownAddressState::Tried).unwrap();
+        tx.set_address_fail_count(&mapped_addr1, 1).unwrap();
+        tx.add_known_address(&mapped_addr2, KnownAddressState::New).unwrap();
+        tx.add_anchor_address(&mapped_addr2).unwrap();
+        tx.commit().unwrap();
+    }
+
+    let peerdb = PeerDb::new(
+        &chain_config,
+        p2p_config,
+        time_getter.get_time_getter(),
+        db_store,
+    )
+    .unwrap();
+
+    assert_eq!(
+        peerdb.addresses.keys().collect::<Vec<_>>(),
+        vec![&ipv4_addr1, &ipv4_addr2]
+    );
+    assert_eq!(
+        tried_addr_table(&peerdb).addr_iter().collect::<Vec<_>>(),
+        vec![&ipv4_addr1]
+    );
+    assert_eq!(
+        new_addr_table(&peerdb).addr_iter().collect::<Vec<_>>(),
+        vec![&ipv4_addr2]
+    );
+    assert_eq!(
+        peerdb.anchors().iter().collect::<Vec<_>>(),
+        vec![&ipv4_addr2]
+    );
+
+    {
+        let tx = peerdb.storage.transaction_ro().unwrap();
+        assert_eq!(
+            tx.get_known_addresses().unwrap().into_iter().collect::<BTreeMap<_, _>>(),
+            BTreeMap::from([
+                (ipv4_addr1, KnownAddressState::Tried),
+                (ipv4_addr2, KnownAddressState::New)
+            ])
+        );
+        assert_eq!(tx.get_address_fail_counts().unwrap(), vec![(ipv4_addr1, 1)]);
+        assert_eq!(tx.get_anchor_addresses().unwrap(), vec![ipv4_addr2]);
+    }
+
+    assert_addr_consistency(&peerdb);
+}
+
+// Check that banning or discouraging the IPv4 form of an address also affects its IPv4-mapped
+// IPv6 form and vice versa.
+#[tracing::instrument(skip(seed))]
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn ban_ipv4_mapped_address(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let db_store = peerdb_inmemory_store();
+    let time_getter = BasicTestTimeGetter::new();
+    let chain_config = create_unit_test_config();
+    let p2p_config = Arc::new(test_p2p_config());
+    let mut peerdb = PeerDb::new(
+        &chain_config,
+        p2p_config,
+        time_getter.get_time_getter(),
+        db_store,
+    )
+    .unwrap();
+
+    let ipv4 = TestAddressMaker::new_random_ipv4_addr(&mut rng);
+    let ipv4_addr = SocketAddr::new(IpAddr::V4(ipv4), rng.gen());
+    let mapped_addr = SocketAddr::new(IpAddr::V6(ipv4.to_ipv6_mapped()), rng.gen());
+    assert_eq!(ipv4_addr.as_bannable(), mapped_addr.as_bannable());
+    assert_eq!(
+        BannableAddress::from_str(&format!("::ffff:{ipv4}")).unwrap(),
+        ipv4_addr.as_bannable()
+    );
+
+    peerdb.ban(ipv4_addr.as_bannable(), Duration::from_secs(60));
+    assert!(peerdb.is_address_banned(&mapped_addr.as_bannable()));
+    let banned_addresses = peerdb.storage.transaction_ro().unwrap().get_banned_addresses().unwrap();
+    assert_eq!(banned_addresses.len(), 1);
+    assert_eq!(banned_addresses[0].0.to_string(), ipv4.to_string());
+
+    peerdb.unban(&mapped_addr.as_bannable());
+    assert!(!peerdb.is_address_banned(&ipv4_addr.as_bannable()));
+
+    peerdb.discourage(mapped_addr.as_bannable());
+    assert!(peerdb.is_address_discouraged(&ipv4_addr.as_bannable()));
+
+    // Other IPv6 addresses are kept as is
+    let ipv6_addr = TestAddressMaker::new_random_address(&mut rng);
+    assert_eq!(
+        ipv6_addr.as_bannable().to_string(),
+        ipv6_addr.ip().to_string()
+    );
+    assert!(!peerdb.is_address_discouraged(&ipv6_addr.as_bannable()));
+
+    assert_addr_consistency(&peerdb);
+}
+
+#[test]
+fn format_as_dns_seed_entries() {
+    let addrs = ["1.2.3.4:3031", "[2001:db8::1]:3031", "1.2.3.4:4041", "5.6.7.8:3031"]
+        .map(|addr| SocketAddress::new(addr.parse().unwrap()));
+
+    assert_eq!(
+        super::format_as_dns_seed_entries(&addrs),
+        vec!["@\tIN\tA\t1.2.3.4", "@\tIN\tA\t5.6.7.8", "@\tIN\tAAAA\t2001:db8::1",]
+    );
+}
+
 fn assert_eq_sets<T, I1, I2>(iter1: I1, iter2: I2)
 where
     I1: Iterator<Item = T>,
@@ -710,11 +710,14 @@ async fn dont_use_dns_seed_if_connections_exist(#[case] seed: Seed) {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         allow_discover_private_ips: Default::default(),
         user_agent: mintlayer_core_user_agent(),
@@ -391,12 +391,15 @@ async fn banned_address_is_not_announced(#[case] seed: Seed) {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
@@ -512,12 +515,15 @@ async fn banned_address_not_in_addr_response(#[case] seed: Seed) {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
@@ -15,6 +15,7 @@
 
 use std::{
     collections::{BTreeMap, BTreeSet},
+    net::Ipv4Addr,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -64,7 +65,7 @@ use crate::{
         self,
         config::{MaxInboundConnections, PeerManagerConfig},
         peerdb::{
-            self, config::PeerDbConfig,
+            self, asn_map::AsnMap, config::PeerDbConfig,
             test_utils::make_non_colliding_addresses_for_peer_db_in_distinct_addr_groups,
         },
         tests::{
@@ -845,11 +846,14 @@ async fn connection_timeout_rpc_notified<T>(
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         allow_discover_private_ips: Default::default(),
         user_agent: mintlayer_core_user_agent(),
@@ -959,12 +963,15 @@ where
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         allow_discover_private_ips: Default::default(),
         user_agent: mintlayer_core_user_agent(),
@@ -1003,12 +1010,15 @@ where
         disable_noise: Default::default(),
         boot_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         allow_discover_private_ips: Default::default(),
         user_agent: mintlayer_core_user_agent(),
@@ -1114,12 +1124,15 @@ where
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
@@ -1158,12 +1171,15 @@ where
         disable_noise: Default::default(),
         boot_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
@@ -1189,12 +1205,15 @@ where
         disable_noise: Default::default(),
         boot_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
@@ -1318,12 +1337,15 @@ async fn discovered_node_2_groups() {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
@@ -1363,12 +1385,15 @@ async fn discovered_node_2_groups() {
         disable_noise: Default::default(),
         boot_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
@@ -1395,12 +1420,15 @@ async fn discovered_node_2_groups() {
         disable_noise: Default::default(),
         boot_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
@@ -1485,12 +1513,15 @@ async fn discovered_node_separate_groups() {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
@@ -1530,12 +1561,15 @@ async fn discovered_node_separate_groups() {
         disable_noise: Default::default(),
         boot_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
@@ -1562,12 +1596,15 @@ async fn discovered_node_separate_groups() {
         disable_noise: Default::default(),
         boot_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
@@ -1847,10 +1884,13 @@ mod feeler_connections_test_utils {
 
                 peerdb_config: PeerDbConfig {
                     salt: Some(Salt::new_random_with_rng(rng)),
+                    identity_key: None,
 
                     new_addr_table_bucket_count: Default::default(),
                     tried_addr_table_bucket_count: Default::default(),
                     addr_tables_bucket_size: Default::default(),
+                    max_connections_per_asn: Default::default(),
+                    asn_map: Default::default(),
                 },
 
                 preserved_inbound_count_address_group: Default::default(),
@@ -1876,11 +1916,14 @@ mod feeler_connections_test_utils {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            trusted_peers: Default::default(),
+            min_peer_protocol_version: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_timeout: Default::default(),
             peer_handshake_timeout: Default::default(),
             max_clock_diff: Default::default(),
+            max_block_future_offset: Default::default(),
             node_type: Default::default(),
             allow_discover_private_ips: Default::default(),
             user_agent: mintlayer_core_user_agent(),
@@ -1910,6 +1953,85 @@ mod feeler_connections_test_utils {
     }
 }
 
+// Simulate addresses from two autonomous systems, 10 from each, and check that the 8 automatic
+// outbound connections are split between them equally.
+#[tracing::instrument(skip(seed))]
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn outbound_connections_span_asns(#[case] seed: Seed) {
+    type TestNetworkingService = DefaultNetworkingService<TcpTransportSocket>;
+
+    let mut rng = make_seedable_rng(seed);
+
+    let chain_config = Arc::new(config::create_unit_test_config());
+
+    let mut asn_map = AsnMap::new();
+    asn_map.insert(Ipv4Addr::new(0, 0, 0, 0).into(), 1, 1);
+    asn_map.insert(Ipv4Addr::new(128, 0, 0, 0).into(), 1, 2);
+
+    let mut p2p_config =
+        feeler_connections_test_utils::make_p2p_config(Duration::from_secs(1), &mut rng);
+    p2p_config.peer_manager_config.outbound_full_relay_count = 8.into();
+    p2p_config.peer_manager_config.enable_feeler_connections = false.into();
+    p2p_config.peer_manager_config.peerdb_config.asn_map = asn_map;
+    let p2p_config = Arc::new(p2p_config);
+
+    let (cmd_sender, mut cmd_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let (_conn_event_sender, conn_event_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let (_peer_mgr_event_sender, peer_mgr_event_receiver) =
+        tokio::sync::mpsc::unbounded_channel::<PeerManagerEvent>();
+    let time_getter = BasicTestTimeGetter::new();
+    let connectivity_handle =
+        ConnectivityHandle::<TestNetworkingService>::new(vec![], cmd_sender, conn_event_receiver);
+
+    let mut peer_mgr = PeerManager::<TestNetworkingService, _>::new(
+        true,
+        Arc::clone(&chain_config),
+        Arc::clone(&p2p_config),
+        connectivity_handle,
+        peer_mgr_event_receiver,
+        time_getter.get_time_getter(),
+        peerdb_inmemory_store(),
+    )
+    .unwrap();
+
+    let addresses = make_non_colliding_addresses_for_peer_db_in_distinct_addr_groups(
+        &peer_mgr.peerdb,
+        100,
+        &mut rng,
+    );
+    let (asn1_addresses, asn2_addresses): (Vec<_>, Vec<_>) = addresses
+        .into_iter()
+        .partition(|addr| peer_mgr.peerdb.address_asn(addr) == Some(1));
+    let mut addresses = asn1_addresses
+        .into_iter()
+        .take(10)
+        .chain(asn2_addresses.into_iter().take(10))
+        .collect::<BTreeSet<_>>();
+    assert_eq!(addresses.len(), 20);
+    for addr in &addresses {
+        peer_mgr.peerdb.peer_discovered(*addr);
+    }
+
+    peer_mgr.establish_new_connections();
+
+    let mut conn_addresses = Vec::new();
+    while let Ok(cmd) = cmd_receiver.try_recv() {
+        conn_addresses.push(expect_cmd_connect_to_one_of(&cmd, &mut addresses));
+    }
+    assert_eq!(conn_addresses.len(), 8);
+
+    for addr in &conn_addresses {
+        peer_mgr.peerdb.outbound_peer_connected(*addr);
+    }
+    assert_eq!(
+        peer_mgr.peerdb.count_connections_by_asn(),
+        BTreeMap::from([(1, 4), (2, 4)])
+    );
+}
+
 // Check that an automatic outbound connection won't be attempted if an inbound connection to
 // the same ip address already exists.
 // Test scenario:
@@ -1962,11 +2084,14 @@ async fn reject_connection_to_existing_ip(#[case] seed: Seed) {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         allow_discover_private_ips: Default::default(),
         user_agent: mintlayer_core_user_agent(),
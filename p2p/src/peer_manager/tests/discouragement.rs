@@ -17,6 +17,7 @@ use std::{sync::Arc, time::Duration};
 
 use rstest::rstest;
 
+use chainstate::ban_score::BanScore;
 use common::{chain::config, primitives::user_agent::mintlayer_core_user_agent};
 use networking::test_helpers::{TestAddressMaker, TestTransportMaker, TestTransportTcp};
 use p2p_test_utils::{expect_no_recv, expect_recv, wait_for_no_recv};
@@ -29,6 +30,7 @@ use crate::{
     ban_config::BanConfig,
     config::P2pConfig,
     disconnection_reason::DisconnectionReason,
+    error::{P2pError, ProtocolError},
     message::{AddrListRequest, AddrListResponse, AnnounceAddrRequest, PeerManagerMessage},
     net::{
         default_backend::types::{Command, Message},
@@ -166,6 +168,105 @@ async fn discourage_connected_peer(#[case] seed: Seed) {
     let _peer_mgr = peer_mgr_join_handle.await.unwrap();
 }
 
+// Check that the ban scores of the protocol violations reported by the backend accumulate
+// and that the peer is discouraged and disconnected once the threshold is reached.
+#[tracing::instrument(skip(seed))]
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn discourage_misbehaving_peer(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let chain_config = Arc::new(config::create_unit_test_config());
+    let ban_config = BanConfig {
+        discouragement_threshold: 100.into(),
+        discouragement_duration: Duration::from_secs(60 * 60).into(),
+    };
+    let p2p_config = Arc::new(test_p2p_config_with_ban_config(ban_config.clone()));
+
+    let time_getter = BasicTestTimeGetter::new();
+    let bind_addr = TestTransportTcp::make_address().into();
+
+    let (
+        peer_mgr,
+        conn_event_sender,
+        peer_mgr_event_sender,
+        mut cmd_receiver,
+        _peer_mgr_notification_receiver,
+    ) = make_standalone_peer_manager(
+        Arc::clone(&chain_config),
+        Arc::clone(&p2p_config),
+        vec![bind_addr],
+        time_getter.get_time_getter(),
+    );
+
+    let peer_mgr_join_handle = logging::spawn_in_current_span(async move {
+        let mut peer_mgr = peer_mgr;
+        let _ = peer_mgr.run_internal(None).await;
+        peer_mgr
+    });
+
+    let peer_addr = TestAddressMaker::new_random_address(&mut rng).into();
+    let peer_id = inbound_full_relay_peer_accepted_by_backend(
+        &conn_event_sender,
+        peer_addr,
+        bind_addr,
+        &chain_config,
+    );
+
+    let cmd = expect_recv!(cmd_receiver);
+    assert_eq!(cmd, Command::Accept { peer_id });
+
+    let violation = || P2pError::ProtocolError(ProtocolError::UnexpectedMessage("test".into()));
+    let violations_to_discourage = *ban_config.discouragement_threshold / violation().ban_score();
+
+    // One violation short of the threshold, the peer stays connected.
+    for _ in 0..violations_to_discourage - 1 {
+        conn_event_sender
+            .send(ConnectivityEvent::Misbehaved {
+                peer_id,
+                error: violation(),
+            })
+            .unwrap();
+    }
+    expect_no_recv!(cmd_receiver);
+
+    let is_discouraged = query_peer_manager(&peer_mgr_event_sender, move |peer_mgr| {
+        peer_mgr.peer_db().is_address_discouraged(&peer_addr.as_bannable())
+    })
+    .await;
+    assert!(!is_discouraged);
+
+    // The last violation reaches the threshold.
+    conn_event_sender
+        .send(ConnectivityEvent::Misbehaved {
+            peer_id,
+            error: violation(),
+        })
+        .unwrap();
+
+    let cmd = expect_recv!(cmd_receiver);
+    assert_eq!(
+        cmd,
+        Command::Disconnect {
+            peer_id,
+            reason: Some(DisconnectionReason::AddressDiscouraged)
+        }
+    );
+
+    let is_discouraged = query_peer_manager(&peer_mgr_event_sender, move |peer_mgr| {
+        peer_mgr.peer_db().is_address_discouraged(&peer_addr.as_bannable())
+    })
+    .await;
+    assert!(is_discouraged);
+
+    drop(conn_event_sender);
+    drop(peer_mgr_event_sender);
+
+    let _peer_mgr = peer_mgr_join_handle.await.unwrap();
+}
+
 // Check that an incoming connection from a discouraged peer is NOT rejected if
 // max_inbound_connections is not reached yet.
 #[tracing::instrument(skip(seed))]
@@ -462,12 +563,15 @@ async fn discouraged_address_is_not_announced(#[case] seed: Seed) {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
@@ -582,12 +686,15 @@ async fn discouraged_address_not_in_addr_response(#[case] seed: Seed) {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
@@ -101,10 +101,13 @@ mod dont_evict_if_blocks_in_flight {
 
                 peerdb_config: PeerDbConfig {
                     salt: Some(Salt::new_random_with_rng(&mut rng)),
+                    identity_key: None,
 
                     new_addr_table_bucket_count: Default::default(),
                     tried_addr_table_bucket_count: Default::default(),
                     addr_tables_bucket_size: Default::default(),
+                    max_connections_per_asn: Default::default(),
+                    asn_map: Default::default(),
                 },
 
                 preserved_inbound_count_address_group: Default::default(),
@@ -130,11 +133,14 @@ mod dont_evict_if_blocks_in_flight {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            trusted_peers: Default::default(),
+            min_peer_protocol_version: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_timeout: Default::default(),
             peer_handshake_timeout: Default::default(),
             max_clock_diff: Default::default(),
+            max_block_future_offset: Default::default(),
             node_type: Default::default(),
             allow_discover_private_ips: Default::default(),
             user_agent: mintlayer_core_user_agent(),
@@ -19,6 +19,8 @@ mod ban;
 mod connections;
 mod discouragement;
 mod eviction;
+mod peer_details;
+mod peer_limits;
 mod peer_types;
 mod ping;
 pub mod utils;
@@ -0,0 +1,218 @@
+// Copyright (c) 2022 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use rstest::rstest;
+
+use common::chain::config;
+use networking::test_helpers::{TestAddressMaker, TestTransportMaker, TestTransportTcp};
+use p2p_test_utils::expect_recv;
+use test_utils::{
+    random::{make_seedable_rng, Seed},
+    BasicTestTimeGetter,
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    interface::types::{PeerDetails, PeerState},
+    net::{default_backend::types::Command, types::PeerRole},
+    peer_manager::tests::{
+        make_standalone_peer_manager,
+        utils::{
+            expect_cmd_connect_to, inbound_full_relay_peer_accepted_by_backend,
+            outbound_block_relay_peer_accepted_by_backend, start_manually_connecting,
+        },
+    },
+    protocol::SupportedProtocolVersion,
+    sync::sync_status::PeerBlockSyncStatus,
+    test_helpers::{test_p2p_config, TEST_PROTOCOL_VERSION},
+    types::peer_id::PeerId,
+    utils::oneshot_nofail,
+    PeerManagerEvent,
+};
+
+async fn get_peer_details(
+    peer_mgr_event_sender: &mpsc::UnboundedSender<PeerManagerEvent>,
+    peer_id: PeerId,
+) -> Option<PeerDetails> {
+    let (response_sender, response_receiver) = oneshot_nofail::channel();
+    peer_mgr_event_sender
+        .send(PeerManagerEvent::GetPeerDetails(peer_id, response_sender))
+        .unwrap();
+    response_receiver.await.unwrap()
+}
+
+async fn list_peer_details(
+    peer_mgr_event_sender: &mpsc::UnboundedSender<PeerManagerEvent>,
+) -> Vec<PeerDetails> {
+    let (response_sender, response_receiver) = oneshot_nofail::channel();
+    peer_mgr_event_sender
+        .send(PeerManagerEvent::ListPeerDetails(response_sender))
+        .unwrap();
+    response_receiver.await.unwrap()
+}
+
+async fn get_peer_protocol_version(
+    peer_mgr_event_sender: &mpsc::UnboundedSender<PeerManagerEvent>,
+    peer_id: PeerId,
+) -> Option<SupportedProtocolVersion> {
+    let (response_sender, response_receiver) = oneshot_nofail::channel();
+    peer_mgr_event_sender
+        .send(PeerManagerEvent::GetPeerProtocolVersion(
+            peer_id,
+            response_sender,
+        ))
+        .unwrap();
+    response_receiver.await.unwrap()
+}
+
+// Check that the details of connected peers reflect their role and block sync state.
+#[tracing::instrument(skip(seed))]
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn peer_details(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let chain_config = Arc::new(config::create_unit_test_config());
+    let p2p_config = Arc::new(test_p2p_config());
+    let time_getter = BasicTestTimeGetter::new();
+    let bind_addr = TestTransportTcp::make_address().into();
+
+    let (
+        peer_mgr,
+        conn_event_sender,
+        peer_mgr_event_sender,
+        mut cmd_receiver,
+        _peer_mgr_notification_receiver,
+    ) = make_standalone_peer_manager(
+        Arc::clone(&chain_config),
+        Arc::clone(&p2p_config),
+        vec![bind_addr],
+        time_getter.get_time_getter(),
+    );
+
+    let peer_mgr_join_handle = logging::spawn_in_current_span(async move {
+        let mut peer_mgr = peer_mgr;
+        let _ = peer_mgr.run_internal(None).await;
+        peer_mgr
+    });
+
+    let inbound_peer_addr = TestAddressMaker::new_random_address(&mut rng).into();
+    let inbound_peer_id = inbound_full_relay_peer_accepted_by_backend(
+        &conn_event_sender,
+        inbound_peer_addr,
+        bind_addr,
+        &chain_config,
+    );
+    let cmd = expect_recv!(cmd_receiver);
+    assert_eq!(
+        cmd,
+        Command::Accept {
+            peer_id: inbound_peer_id
+        }
+    );
+
+    let outbound_peer_addr = TestAddressMaker::new_random_address(&mut rng).into();
+    let _connect_result_receiver =
+        start_manually_connecting(&peer_mgr_event_sender, outbound_peer_addr);
+    let cmd = expect_recv!(cmd_receiver);
+    expect_cmd_connect_to(&cmd, &outbound_peer_addr);
+    let outbound_peer_id = outbound_block_relay_peer_accepted_by_backend(
+        &conn_event_sender,
+        outbound_peer_addr,
+        bind_addr,
+        &chain_config,
+    );
+    let cmd = expect_recv!(cmd_receiver);
+    assert_eq!(
+        cmd,
+        Command::Accept {
+            peer_id: outbound_peer_id
+        }
+    );
+
+    // Both peers are idle initially
+    let inbound_peer_details = PeerDetails {
+        peer_id: inbound_peer_id,
+        address: inbound_peer_addr,
+        peer_role: PeerRole::Inbound,
+        state: PeerState::Idle,
+    };
+    let mut outbound_peer_details = PeerDetails {
+        peer_id: outbound_peer_id,
+        address: outbound_peer_addr,
+        peer_role: PeerRole::OutboundManual,
+        state: PeerState::Idle,
+    };
+    assert_eq!(
+        get_peer_details(&peer_mgr_event_sender, inbound_peer_id).await,
+        Some(inbound_peer_details.clone())
+    );
+    assert_eq!(
+        get_peer_details(&peer_mgr_event_sender, outbound_peer_id).await,
+        Some(outbound_peer_details.clone())
+    );
+
+    // Blocks are requested from the outbound peer
+    peer_mgr_event_sender
+        .send(PeerManagerEvent::PeerBlockSyncStatusUpdate {
+            peer_id: outbound_peer_id,
+            new_status: PeerBlockSyncStatus {
+                expecting_blocks_since: Some(time_getter.get_time_getter().get_time()),
+            },
+        })
+        .unwrap();
+    outbound_peer_details.state = PeerState::ExpectingBlocks;
+    assert_eq!(
+        get_peer_details(&peer_mgr_event_sender, inbound_peer_id).await,
+        Some(inbound_peer_details.clone())
+    );
+    assert_eq!(
+        get_peer_details(&peer_mgr_event_sender, outbound_peer_id).await,
+        Some(outbound_peer_details.clone())
+    );
+
+    let mut peers = list_peer_details(&peer_mgr_event_sender).await;
+    peers.sort_by_key(|details| details.peer_id);
+    let mut expected_peers = vec![inbound_peer_details, outbound_peer_details];
+    expected_peers.sort_by_key(|details| details.peer_id);
+    assert_eq!(peers, expected_peers);
+
+    // The negotiated protocol version is recorded for both peers
+    for peer_id in [inbound_peer_id, outbound_peer_id] {
+        assert_eq!(
+            get_peer_protocol_version(&peer_mgr_event_sender, peer_id).await,
+            Some(TEST_PROTOCOL_VERSION)
+        );
+    }
+
+    // Unknown peers have no details
+    assert_eq!(
+        get_peer_details(&peer_mgr_event_sender, PeerId::new()).await,
+        None
+    );
+    assert_eq!(
+        get_peer_protocol_version(&peer_mgr_event_sender, PeerId::new()).await,
+        None
+    );
+
+    drop(conn_event_sender);
+    drop(peer_mgr_event_sender);
+
+    let _peer_mgr = peer_mgr_join_handle.await.unwrap();
+}
@@ -0,0 +1,170 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use rstest::rstest;
+
+use common::chain::config;
+use networking::test_helpers::{TestAddressMaker, TestTransportMaker, TestTransportTcp};
+use p2p_test_utils::expect_recv;
+use test_utils::{
+    random::{make_seedable_rng, Seed},
+    BasicTestTimeGetter,
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    disconnection_reason::DisconnectionReason,
+    error::P2pError,
+    interface::types::PeerLimits,
+    net::default_backend::types::Command,
+    peer_manager::tests::{
+        make_standalone_peer_manager, utils::inbound_full_relay_peer_accepted_by_backend,
+    },
+    test_helpers::test_p2p_config,
+    utils::oneshot_nofail,
+    PeerManagerEvent,
+};
+
+async fn get_peer_limits(
+    peer_mgr_event_sender: &mpsc::UnboundedSender<PeerManagerEvent>,
+) -> PeerLimits {
+    let (response_sender, response_receiver) = oneshot_nofail::channel();
+    peer_mgr_event_sender
+        .send(PeerManagerEvent::GetPeerLimits(response_sender))
+        .unwrap();
+    response_receiver.await.unwrap()
+}
+
+async fn set_peer_limits(
+    peer_mgr_event_sender: &mpsc::UnboundedSender<PeerManagerEvent>,
+    limits: PeerLimits,
+) -> crate::Result<()> {
+    let (response_sender, response_receiver) = oneshot_nofail::channel();
+    peer_mgr_event_sender
+        .send(PeerManagerEvent::SetPeerLimits(limits, response_sender))
+        .unwrap();
+    response_receiver.await.unwrap()
+}
+
+// Check that the peer limits can be changed at runtime, that a zero outbound limit is rejected
+// and that the new inbound limit applies to new connections while keeping the existing ones.
+#[tracing::instrument(skip(seed))]
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn set_get_peer_limits(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let chain_config = Arc::new(config::create_unit_test_config());
+    let p2p_config = Arc::new(test_p2p_config());
+    let time_getter = BasicTestTimeGetter::new();
+    let bind_addr = TestTransportTcp::make_address().into();
+
+    let (
+        peer_mgr,
+        conn_event_sender,
+        peer_mgr_event_sender,
+        mut cmd_receiver,
+        _peer_mgr_notification_receiver,
+    ) = make_standalone_peer_manager(
+        Arc::clone(&chain_config),
+        Arc::clone(&p2p_config),
+        vec![bind_addr],
+        time_getter.get_time_getter(),
+    );
+
+    let peer_mgr_join_handle = logging::spawn_in_current_span(async move {
+        let mut peer_mgr = peer_mgr;
+        let _ = peer_mgr.run_internal(None).await;
+        peer_mgr
+    });
+
+    // The initial limits come from the config
+    let initial_limits = PeerLimits {
+        max_inbound: *p2p_config.peer_manager_config.max_inbound_connections,
+        max_outbound: *p2p_config.peer_manager_config.outbound_full_relay_count,
+    };
+    assert_eq!(
+        get_peer_limits(&peer_mgr_event_sender).await,
+        initial_limits
+    );
+
+    // Two inbound peers are accepted
+    let mut inbound_peer_ids = Vec::new();
+    for _ in 0..2 {
+        let peer_id = inbound_full_relay_peer_accepted_by_backend(
+            &conn_event_sender,
+            TestAddressMaker::new_random_address(&mut rng).into(),
+            bind_addr,
+            &chain_config,
+        );
+        let cmd = expect_recv!(cmd_receiver);
+        assert_eq!(cmd, Command::Accept { peer_id });
+        inbound_peer_ids.push(peer_id);
+    }
+
+    // A zero outbound limit is rejected
+    let result = set_peer_limits(
+        &peer_mgr_event_sender,
+        PeerLimits {
+            max_inbound: 1,
+            max_outbound: 0,
+        },
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(P2pError::InvalidConfigurationValue(_))
+    ));
+    assert_eq!(
+        get_peer_limits(&peer_mgr_event_sender).await,
+        initial_limits
+    );
+
+    // Lower the inbound limit below the current number of inbound peers
+    let new_limits = PeerLimits {
+        max_inbound: 1,
+        max_outbound: 1,
+    };
+    set_peer_limits(&peer_mgr_event_sender, new_limits).await.unwrap();
+    assert_eq!(get_peer_limits(&peer_mgr_event_sender).await, new_limits);
+
+    // The existing peers are kept, but a new one is rejected
+    let peer_id = inbound_full_relay_peer_accepted_by_backend(
+        &conn_event_sender,
+        TestAddressMaker::new_random_address(&mut rng).into(),
+        bind_addr,
+        &chain_config,
+    );
+    let cmd = expect_recv!(cmd_receiver);
+    assert_eq!(
+        cmd,
+        Command::Disconnect {
+            peer_id,
+            reason: Some(DisconnectionReason::TooManyInboundPeersAndCannotEvictAnyone)
+        }
+    );
+
+    drop(conn_event_sender);
+    drop(peer_mgr_event_sender);
+
+    let peer_mgr = peer_mgr_join_handle.await.unwrap();
+    for peer_id in inbound_peer_ids {
+        assert!(peer_mgr.peers.contains_key(&peer_id));
+    }
+}
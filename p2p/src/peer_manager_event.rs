@@ -23,9 +23,14 @@ use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress
 use utils_networking::IpOrSocketAddress;
 
 use crate::{
-    disconnection_reason::DisconnectionReason, interface::types::ConnectedPeer,
-    peer_manager::PeerManagerInterface, sync::sync_status::PeerBlockSyncStatus,
-    types::peer_id::PeerId, utils::oneshot_nofail,
+    disconnection_reason::DisconnectionReason,
+    interface::types::{ConnectedPeer, PeerDetails, PeerLimits},
+    message::PeerReputationReason,
+    peer_manager::PeerManagerInterface,
+    protocol::SupportedProtocolVersion,
+    sync::sync_status::PeerBlockSyncStatus,
+    types::peer_id::PeerId,
+    utils::oneshot_nofail,
 };
 
 #[derive(Debug)]
@@ -60,6 +65,18 @@ pub enum PeerManagerEvent {
     /// Get peer IDs and addresses of connected peers
     GetConnectedPeers(oneshot_nofail::Sender<Vec<ConnectedPeer>>),
 
+    /// Get the state of a connected peer, `None` if the peer is not connected
+    GetPeerDetails(PeerId, oneshot_nofail::Sender<Option<PeerDetails>>),
+
+    /// Get the state of all connected peers
+    ListPeerDetails(oneshot_nofail::Sender<Vec<PeerDetails>>),
+
+    /// Get the protocol version negotiated with the peer, if it's connected
+    GetPeerProtocolVersion(
+        PeerId,
+        oneshot_nofail::Sender<Option<SupportedProtocolVersion>>,
+    ),
+
     /// Increases the ban score of a peer by the given amount.
     ///
     /// The peer is discouraged if the new score exceeds the corresponding threshold.
@@ -110,6 +127,22 @@ pub enum PeerManagerEvent {
 
     ListDiscouraged(oneshot_nofail::Sender<Vec<(BannableAddress, Time)>>),
 
+    /// Get the current connection limits
+    GetPeerLimits(oneshot_nofail::Sender<PeerLimits>),
+
+    /// Change the connection limits; existing connections are kept, the new limits are only
+    /// taken into account when establishing or accepting new ones
+    SetPeerLimits(PeerLimits, oneshot_nofail::Sender<crate::Result<()>>),
+
+    /// Sign a reputation update for the address with the node's identity key
+    /// and send it to all connected trusted peers.
+    BroadcastReputation {
+        address: SocketAddress,
+        score: i16,
+        reason: PeerReputationReason,
+        response_sender: oneshot_nofail::Sender<crate::Result<()>>,
+    },
+
     EnableNetworking {
         enable: bool,
         response_sender: oneshot_nofail::Sender<crate::Result<()>>,
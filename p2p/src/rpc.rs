@@ -21,7 +21,10 @@ use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress
 use serialization::hex_encoded::HexEncoded;
 use utils_networking::IpOrSocketAddress;
 
-use crate::{interface::types::ConnectedPeer, types::peer_id::PeerId};
+use crate::{
+    interface::types::{ConnectedPeer, PeerDetails, PeerLimits},
+    types::peer_id::PeerId,
+};
 use rpc::RpcResult;
 
 #[rpc::describe]
@@ -72,6 +75,16 @@ trait P2pRpc {
     #[method(name = "get_connected_peers")]
     async fn get_connected_peers(&self) -> RpcResult<Vec<ConnectedPeer>>;
 
+    /// Get the state of a connected peer, given its id.
+    ///
+    /// Returns `None` (null) if the peer is not connected.
+    #[method(name = "peer_info")]
+    async fn peer_info(&self, peer_id: PeerId) -> RpcResult<Option<PeerDetails>>;
+
+    /// Get the state of all connected peers.
+    #[method(name = "list_peers")]
+    async fn list_peers(&self) -> RpcResult<Vec<PeerDetails>>;
+
     /// Get addresses of reserved nodes.
     #[method(name = "get_reserved_nodes")]
     async fn get_reserved_nodes(&self) -> RpcResult<Vec<SocketAddress>>;
@@ -89,6 +102,17 @@ trait P2pRpc {
     #[method(name = "remove_reserved_node")]
     async fn remove_reserved_node(&self, addr: IpOrSocketAddress) -> RpcResult<()>;
 
+    /// Get the maximum numbers of inbound and automatic outbound connections.
+    #[method(name = "get_peer_limits")]
+    async fn get_peer_limits(&self) -> RpcResult<PeerLimits>;
+
+    /// Change the maximum numbers of inbound and automatic outbound connections.
+    ///
+    /// Existing connections are not closed, the new limits only apply to new connections.
+    /// The outbound limit must not be zero.
+    #[method(name = "set_peer_limits")]
+    async fn set_peer_limits(&self, limits: PeerLimits) -> RpcResult<()>;
+
     /// Submits a transaction to mempool, and if it is valid, broadcasts it to the network as well.
     #[method(name = "submit_transaction")]
     async fn submit_transaction(
@@ -150,6 +174,16 @@ impl P2pRpcServer for super::P2pHandle {
         rpc::handle_result(res)
     }
 
+    async fn peer_info(&self, peer_id: PeerId) -> RpcResult<Option<PeerDetails>> {
+        let res = self.call_async(move |this| this.peer_info(peer_id)).await;
+        rpc::handle_result(res)
+    }
+
+    async fn list_peers(&self) -> RpcResult<Vec<PeerDetails>> {
+        let res = self.call_async(|this| this.list_peers()).await;
+        rpc::handle_result(res)
+    }
+
     async fn get_reserved_nodes(&self) -> RpcResult<Vec<SocketAddress>> {
         let res = self.call_async(|this| this.get_reserved_nodes()).await;
         rpc::handle_result(res)
@@ -165,6 +199,16 @@ impl P2pRpcServer for super::P2pHandle {
         rpc::handle_result(res)
     }
 
+    async fn get_peer_limits(&self) -> RpcResult<PeerLimits> {
+        let res = self.call_async(|this| this.get_peer_limits()).await;
+        rpc::handle_result(res)
+    }
+
+    async fn set_peer_limits(&self, limits: PeerLimits) -> RpcResult<()> {
+        let res = self.call_async_mut(move |this| this.set_peer_limits(limits)).await;
+        rpc::handle_result(res)
+    }
+
     async fn submit_transaction(
         &self,
         tx: HexEncoded<SignedTransaction>,
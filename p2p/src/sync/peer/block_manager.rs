@@ -46,6 +46,7 @@ use crate::{
     peer_manager_event::PeerDisconnectionDbAction,
     sync::{
         chainstate_handle::ChainstateHandle,
+        peer::timestamp_drift::check_block_timestamp_drift,
         peer_activity::PeerActivity,
         peer_common::{choose_peers_best_block, handle_message_processing_result},
         sync_status::PeerBlockSyncStatus,
@@ -428,6 +429,20 @@ where
                     Some(c.get_best_block_id()?)
                 };
 
+                // The blocks whose bodies have been pruned can't be served, so their headers
+                // aren't sent either. Since pruning goes from the bottom up, it's enough to
+                // check the first one.
+                let headers = match headers.first() {
+                    Some(header)
+                        if c.get_block_index_for_persisted_block(&header.block_id())?
+                            .is_some_and(|index| index.status().is_body_pruned()) =>
+                    {
+                        log::debug!("Not sending headers of pruned blocks");
+                        Vec::new()
+                    }
+                    _ => headers,
+                };
+
                 Ok((headers, peers_best_block_that_we_have))
             })
             .await?;
@@ -497,15 +512,16 @@ where
         self.chainstate_handle
             .call(move |c| {
                 for id in ids {
-                    // Note: in the future, when/if we implement block purging, it may be possible for a previously
-                    // existing block (and therefore its BlockIndex) not to exist anymore; if this happens, the
-                    // following check will fail without peer's fault. (But this situation should be rare, so we
-                    // probably won't care about it anyway, because its impact - erroneously discourage/or be discouraged
-                    // by a peer - is low.)
+                    // Note: the body of a block may have been pruned after its header was sent
+                    // to the peer, so this is not necessarily the peer's fault.
                     // Also see a similar note in send_block.
                     let index = c.get_block_index_for_persisted_block(&id)?.ok_or(
                         P2pError::ProtocolError(ProtocolError::UnknownBlockRequested(id)),
                     )?;
+                    utils::ensure!(
+                        !index.status().is_body_pruned(),
+                        P2pError::SyncError(SyncError::PrunedBlockRequested(id))
+                    );
 
                     if let Some(ref best_sent_block) = best_sent_block {
                         if index.block_height() <= best_sent_block.block_height() {
@@ -642,6 +658,17 @@ where
             .ok_or(P2pError::ProtocolError(ProtocolError::DisconnectedHeaders))?
             .block_height();
 
+        // Reject the headers if any of them is too far in the future.
+        let latest_header = headers
+            .iter()
+            .max_by_key(|header| header.timestamp())
+            .expect("Headers shouldn't be empty");
+        check_block_timestamp_drift(
+            latest_header.header(),
+            self.time_getter.get_time(),
+            *self.p2p_config.max_block_future_offset,
+        )?;
+
         let last_header = headers.last().expect("Headers shouldn't be empty");
         let last_header_height = first_header_prev_block_height
             .checked_add(headers.len() as u64)
@@ -849,9 +876,8 @@ where
             })
             .await?;
         // Note: all requested blocks have already been checked for existence in handle_block_request.
-        // But in the future, when/if we implement block purging, it will still be possible for a
-        // block to become missing by this point. This should be a rare and low impact situation,
-        // but at least we should fail gracefully here and not panic.
+        // But the body of a block may still be pruned by this point. This should be a rare and
+        // low impact situation, but at least we should fail gracefully here and not panic.
         // Also see a similar note in handle_block_request.
         let block = block?.ok_or(SyncError::BlockDataMissingInSendBlock(id))?;
         let block_index = block_index?.ok_or(SyncError::BlockIndexMissingInSendBlock(id))?;
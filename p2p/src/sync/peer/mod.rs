@@ -18,3 +18,4 @@ pub mod requested_transactions;
 pub mod transaction_manager;
 
 mod pending_transactions;
+mod timestamp_drift;
@@ -0,0 +1,174 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use common::{
+    chain::block::BlockHeader,
+    primitives::{time::Time, Idable},
+};
+use logging::log;
+
+use crate::error::P2pError;
+
+/// Blocks that are further in the future than this are accepted, but a warning is logged.
+pub const BLOCK_TIMESTAMP_DRIFT_WARNING_THRESHOLD: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug, PartialEq, Eq)]
+enum TimestampDrift {
+    Acceptable,
+    Suspicious(Duration),
+    TooFarInFuture(Duration),
+}
+
+fn timestamp_drift(
+    header: &BlockHeader,
+    local_time: Time,
+    max_future_offset: Duration,
+) -> TimestampDrift {
+    let offset = header.timestamp().into_time().saturating_sub(local_time);
+
+    if offset > max_future_offset {
+        TimestampDrift::TooFarInFuture(offset)
+    } else if offset > BLOCK_TIMESTAMP_DRIFT_WARNING_THRESHOLD {
+        TimestampDrift::Suspicious(offset)
+    } else {
+        TimestampDrift::Acceptable
+    }
+}
+
+/// Check that the timestamp of an announced block is not too far ahead of the local time.
+///
+/// The peer's clock may be slightly ahead of ours, so blocks that are only somewhat in the future
+/// are accepted with a warning.
+pub fn check_block_timestamp_drift(
+    header: &BlockHeader,
+    local_time: Time,
+    max_future_offset: Duration,
+) -> Result<(), P2pError> {
+    match timestamp_drift(header, local_time, max_future_offset) {
+        TimestampDrift::Acceptable => Ok(()),
+        TimestampDrift::Suspicious(offset) => {
+            log::warn!(
+                "Block {} timestamp is {} seconds ahead of the local time",
+                header.get_id(),
+                offset.as_secs()
+            );
+            Ok(())
+        }
+        TimestampDrift::TooFarInFuture(offset) => Err(P2pError::BlockTimestampTooFarInFuture {
+            block_id: header.get_id(),
+            offset,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{
+        chain::block::{timestamp::BlockTimestamp, ConsensusData},
+        primitives::{Id, H256},
+    };
+    use randomness::Rng;
+    use rstest::rstest;
+    use test_utils::random::{make_seedable_rng, Seed};
+
+    use super::*;
+
+    fn header_at(local_time: Time, offset_secs: i64) -> BlockHeader {
+        let timestamp = local_time.as_secs_since_epoch().checked_add_signed(offset_secs).unwrap();
+        BlockHeader::new(
+            Id::new(H256::zero()),
+            H256::zero(),
+            H256::zero(),
+            BlockTimestamp::from_int_seconds(timestamp),
+            ConsensusData::None,
+        )
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn drift_categories(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let local_time = Time::from_secs_since_epoch(rng.gen_range(1_000_000..1_000_000_000));
+        let max_future_offset = Duration::from_secs(2 * 60 * 60);
+
+        let warning_threshold = BLOCK_TIMESTAMP_DRIFT_WARNING_THRESHOLD.as_secs() as i64;
+        let max_offset = max_future_offset.as_secs() as i64;
+
+        let drift = |offset_secs| {
+            timestamp_drift(
+                &header_at(local_time, offset_secs),
+                local_time,
+                max_future_offset,
+            )
+        };
+
+        // Blocks from the past and the near future
+        assert_eq!(
+            drift(-rng.gen_range(1..1_000_000)),
+            TimestampDrift::Acceptable
+        );
+        assert_eq!(drift(0), TimestampDrift::Acceptable);
+        assert_eq!(drift(warning_threshold), TimestampDrift::Acceptable);
+
+        // Blocks ahead of the local time, but still within the limit
+        assert_eq!(
+            drift(warning_threshold + 1),
+            TimestampDrift::Suspicious(Duration::from_secs(warning_threshold as u64 + 1))
+        );
+        assert_eq!(
+            drift(max_offset),
+            TimestampDrift::Suspicious(max_future_offset)
+        );
+
+        // Blocks too far in the future
+        let offset = rng.gen_range(max_offset + 1..max_offset * 10);
+        assert_eq!(
+            drift(max_offset + 1),
+            TimestampDrift::TooFarInFuture(max_future_offset + Duration::from_secs(1))
+        );
+        assert_eq!(
+            drift(offset),
+            TimestampDrift::TooFarInFuture(Duration::from_secs(offset as u64))
+        );
+    }
+
+    #[test]
+    fn check_result() {
+        let local_time = Time::from_secs_since_epoch(1_000_000);
+        let max_future_offset = Duration::from_secs(2 * 60 * 60);
+        let max_offset = max_future_offset.as_secs() as i64;
+
+        check_block_timestamp_drift(&header_at(local_time, 0), local_time, max_future_offset)
+            .unwrap();
+        check_block_timestamp_drift(
+            &header_at(local_time, max_offset),
+            local_time,
+            max_future_offset,
+        )
+        .unwrap();
+
+        let header = header_at(local_time, max_offset + 1);
+        assert_eq!(
+            check_block_timestamp_drift(&header, local_time, max_future_offset),
+            Err(P2pError::BlockTimestampTooFarInFuture {
+                block_id: header.get_id(),
+                offset: max_future_offset + Duration::from_secs(1),
+            })
+        );
+    }
+}
@@ -60,7 +60,8 @@ pub async fn handle_message_processing_result(
         | P2pError::ProtocolError(_)
         | P2pError::MempoolError(_)
         | P2pError::ChainstateError(_)
-        | P2pError::SyncError(_)) => {
+        | P2pError::SyncError(_)
+        | P2pError::BlockTimestampTooFarInFuture { .. }) => {
             let ban_score = e.ban_score();
             if ban_score > 0 {
                 log::info!(
@@ -94,7 +95,8 @@ pub async fn handle_message_processing_result(
         | P2pError::PeerError(_)
         | P2pError::NoiseHandshakeError(_)
         | P2pError::InvalidConfigurationValue(_)
-        | P2pError::ConnectionValidationFailed(_)) => panic!("Unexpected error {e:?}"),
+        | P2pError::ConnectionValidationFailed(_)
+        | P2pError::SignatureError(_)) => panic!("Unexpected error {e:?}"),
 
         // Fatal errors, simply propagate them to stop the sync manager.
         // Note: due to how error types are currently organized, a storage error can
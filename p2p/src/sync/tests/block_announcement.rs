@@ -531,12 +531,15 @@ async fn send_headers_connected_to_previously_sent_headers(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            trusted_peers: Default::default(),
+            min_peer_protocol_version: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
             ping_timeout: Default::default(),
             peer_handshake_timeout: Default::default(),
             max_clock_diff: Default::default(),
+            max_block_future_offset: Default::default(),
             node_type: Default::default(),
             allow_discover_private_ips: Default::default(),
             user_agent: mintlayer_core_user_agent(),
@@ -634,12 +637,15 @@ async fn send_headers_connected_to_block_which_is_being_downloaded(#[case] seed:
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            trusted_peers: Default::default(),
+            min_peer_protocol_version: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
             ping_timeout: Default::default(),
             peer_handshake_timeout: Default::default(),
             max_clock_diff: Default::default(),
+            max_block_future_offset: Default::default(),
             node_type: Default::default(),
             allow_discover_private_ips: Default::default(),
             user_agent: mintlayer_core_user_agent(),
@@ -734,12 +740,15 @@ async fn correct_pending_headers_update(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            trusted_peers: Default::default(),
+            min_peer_protocol_version: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
             ping_timeout: Default::default(),
             peer_handshake_timeout: Default::default(),
             max_clock_diff: Default::default(),
+            max_block_future_offset: Default::default(),
             node_type: Default::default(),
             allow_discover_private_ips: Default::default(),
             user_agent: mintlayer_core_user_agent(),
@@ -22,11 +22,11 @@ use std::{
 use chainstate::ban_score::BanScore;
 use chainstate_test_framework::TestFramework;
 use common::{
-    chain::config::create_unit_test_config,
-    primitives::{user_agent::mintlayer_core_user_agent, Idable},
+    chain::{config::create_unit_test_config, GenBlock},
+    primitives::{user_agent::mintlayer_core_user_agent, Id, Idable},
 };
 use logging::log;
-use p2p_test_utils::create_n_blocks;
+use p2p_test_utils::{create_fork, create_n_blocks};
 use randomness::Rng;
 use test_utils::{
     random::{shuffle_until_different, Seed},
@@ -141,6 +141,75 @@ async fn valid_response(#[case] seed: Seed) {
     .await;
 }
 
+// The peer sends the headers and then the blocks of a chain and then of a longer competing one,
+// the node switches to the longer chain.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn reorg_to_longer_chain(#[case] seed: Seed) {
+    for_each_protocol_version(|protocol_version| async move {
+        let mut rng = test_utils::random::make_seedable_rng(seed);
+
+        let chain_config = Arc::new(create_unit_test_config());
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config.as_ref().clone())
+            .build();
+        let main_chain_length = rng.gen_range(1..5);
+        let fork_length = rng.gen_range(main_chain_length + 1..10);
+        let (main_chain, fork) = create_fork(
+            &mut rng,
+            &mut tf,
+            chain_config.genesis_block_id(),
+            main_chain_length,
+            fork_length,
+        );
+
+        let mut node = TestNode::builder(protocol_version)
+            .with_chain_config(chain_config)
+            .with_chainstate(tf.into_chainstate())
+            .build()
+            .await;
+
+        let peer = node.connect_peer(PeerId::new(), protocol_version).await;
+
+        for blocks in [main_chain, fork] {
+            peer.send_headers(blocks.iter().map(|b| b.header().clone()).collect()).await;
+
+            let (sent_to, message) = node.get_sent_block_sync_message().await;
+            assert_eq!(peer.get_id(), sent_to);
+            let ids = blocks.iter().map(|b| b.get_id()).collect();
+            assert_eq!(
+                message,
+                BlockSyncMessage::BlockListRequest(BlockListRequest::new(ids))
+            );
+
+            let top_block_id: Id<GenBlock> = blocks.last().unwrap().get_id().into();
+            for block in blocks {
+                peer.send_block_sync_message(BlockSyncMessage::BlockResponse(BlockResponse::new(
+                    block,
+                )))
+                .await;
+            }
+
+            assert!(matches!(
+                node.get_sent_block_sync_message().await.1,
+                BlockSyncMessage::HeaderListRequest(HeaderListRequest { .. })
+            ));
+
+            let best_block_id =
+                node.chainstate().call(|cs| cs.get_best_block_id()).await.unwrap().unwrap();
+            assert_eq!(best_block_id, top_block_id);
+        }
+
+        node.assert_no_error().await;
+
+        node.join_subsystem_manager().await;
+    })
+    .await;
+}
+
 #[tracing::instrument(skip(seed))]
 #[rstest::rstest]
 #[trace]
@@ -165,11 +234,14 @@ async fn block_responses_in_wrong_order(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            trusted_peers: Default::default(),
+            min_peer_protocol_version: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
             ping_timeout: Default::default(),
             peer_handshake_timeout: Default::default(),
             max_clock_diff: Default::default(),
+            max_block_future_offset: Default::default(),
             node_type: Default::default(),
             allow_discover_private_ips: Default::default(),
             user_agent: "test".try_into().unwrap(),
@@ -293,12 +365,15 @@ async fn disconnect(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            trusted_peers: Default::default(),
+            min_peer_protocol_version: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
             ping_timeout: Default::default(),
             peer_handshake_timeout: Default::default(),
             max_clock_diff: Default::default(),
+            max_block_future_offset: Default::default(),
             node_type: Default::default(),
             allow_discover_private_ips: Default::default(),
             user_agent: "test".try_into().unwrap(),
@@ -360,12 +435,15 @@ async fn slow_response(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            trusted_peers: Default::default(),
+            min_peer_protocol_version: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
             ping_timeout: Default::default(),
             peer_handshake_timeout: Default::default(),
             max_clock_diff: Default::default(),
+            max_block_future_offset: Default::default(),
             node_type: Default::default(),
             allow_discover_private_ips: Default::default(),
             user_agent: mintlayer_core_user_agent(),
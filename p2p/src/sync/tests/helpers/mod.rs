@@ -350,6 +350,9 @@ impl TestNode {
                     | PeerManagerEvent::GetPeerCount(_)
                     | PeerManagerEvent::GetBindAddresses(_)
                     | PeerManagerEvent::GetConnectedPeers(_)
+                    | PeerManagerEvent::GetPeerDetails(_, _)
+                    | PeerManagerEvent::ListPeerDetails(_)
+                    | PeerManagerEvent::GetPeerProtocolVersion(_, _)
                     | PeerManagerEvent::AdjustPeerScore(_, _, _)
                     | PeerManagerEvent::GetReserved(_)
                     | PeerManagerEvent::AddReserved(_, _)
@@ -358,6 +361,9 @@ impl TestNode {
                     | PeerManagerEvent::Ban(_, _, _)
                     | PeerManagerEvent::Unban(_, _)
                     | PeerManagerEvent::ListDiscouraged(_)
+                    | PeerManagerEvent::GetPeerLimits(_)
+                    | PeerManagerEvent::SetPeerLimits(_, _)
+                    | PeerManagerEvent::BroadcastReputation { .. }
                     | PeerManagerEvent::EnableNetworking { .. }
                     | PeerManagerEvent::GenericQuery(_)
                     | PeerManagerEvent::GenericMut(_) => {
@@ -642,6 +648,15 @@ pub enum PeerManagerEventDesc {
     Ban(BannableAddress, Duration),
     Unban(BannableAddress),
     ListDiscouraged,
+    GetPeerLimits,
+    SetPeerLimits {
+        max_inbound: usize,
+        max_outbound: usize,
+    },
+    BroadcastReputation {
+        address: SocketAddress,
+        score: i16,
+    },
     EnableNetworking {
         enable: bool,
     },
@@ -695,6 +710,20 @@ impl From<&PeerManagerEvent> for PeerManagerEventDesc {
             PeerManagerEvent::Ban(addr, duration, _) => PeerManagerEventDesc::Ban(*addr, *duration),
             PeerManagerEvent::Unban(addr, _) => PeerManagerEventDesc::Unban(*addr),
             PeerManagerEvent::ListDiscouraged(_) => PeerManagerEventDesc::ListDiscouraged,
+            PeerManagerEvent::GetPeerLimits(_) => PeerManagerEventDesc::GetPeerLimits,
+            PeerManagerEvent::SetPeerLimits(limits, _) => PeerManagerEventDesc::SetPeerLimits {
+                max_inbound: limits.max_inbound,
+                max_outbound: limits.max_outbound,
+            },
+            PeerManagerEvent::BroadcastReputation {
+                address,
+                score,
+                reason: _,
+                response_sender: _,
+            } => PeerManagerEventDesc::BroadcastReputation {
+                address: *address,
+                score: *score,
+            },
             PeerManagerEvent::EnableNetworking {
                 enable,
                 response_sender: _,
@@ -324,6 +324,9 @@ impl TestNodeGroup {
                         | PeerManagerEvent::Ban(_, _, _)
                         | PeerManagerEvent::Unban(_, _)
                         | PeerManagerEvent::ListDiscouraged(_)
+                        | PeerManagerEvent::GetPeerLimits(_)
+                        | PeerManagerEvent::SetPeerLimits(_, _)
+                        | PeerManagerEvent::BroadcastReputation { .. }
                         | PeerManagerEvent::EnableNetworking { .. }
                         | PeerManagerEvent::GenericQuery(_)
                         | PeerManagerEvent::GenericMut(_) => {
@@ -69,12 +69,15 @@ async fn basic(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            trusted_peers: Default::default(),
+            min_peer_protocol_version: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
             ping_timeout: Default::default(),
             peer_handshake_timeout: Default::default(),
             max_clock_diff: Default::default(),
+            max_block_future_offset: Default::default(),
             node_type: Default::default(),
             allow_discover_private_ips: Default::default(),
             user_agent: mintlayer_core_user_agent(),
@@ -311,12 +314,15 @@ async fn block_announcement_disconnected_headers(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            trusted_peers: Default::default(),
+            min_peer_protocol_version: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
             ping_timeout: Default::default(),
             peer_handshake_timeout: Default::default(),
             max_clock_diff: Default::default(),
+            max_block_future_offset: Default::default(),
             node_type: Default::default(),
             allow_discover_private_ips: Default::default(),
             user_agent: mintlayer_core_user_agent(),
@@ -422,6 +428,7 @@ async fn send_block_from_the_future_again(#[case] seed: Seed) {
         let p2p_config = Arc::new(P2pConfig {
             // Minimize the time block sync manager spends in wait_for_clock_diff.
             max_clock_diff: Duration::from_secs(1).into(),
+            max_block_future_offset: Default::default(),
             peer_handshake_timeout: Duration::from_secs(1).into(),
 
             bind_addresses: Default::default(),
@@ -430,6 +437,8 @@ async fn send_block_from_the_future_again(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            trusted_peers: Default::default(),
+            min_peer_protocol_version: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
@@ -22,18 +22,23 @@
 //! with each other, producing errors like "`XXX` and `XXX` have similar names, but are actually
 //! distinct types ... the crate `YYY` is compiled multiple times, possibly with different configurations".
 
-use std::{fmt::Debug, net::Ipv4Addr, time::Duration};
+use std::{
+    fmt::Debug,
+    net::{IpAddr, Ipv4Addr},
+    time::Duration,
+};
 
 use futures::Future;
 use tokio::time::timeout;
 
 use common::primitives::user_agent::mintlayer_core_user_agent;
+use crypto::key::PrivateKey;
 use logging::log;
 use networking::transport::MpscChannelTransport;
 
 use crate::{
     ban_config::BanConfig,
-    config::P2pConfig,
+    config::{P2pConfig, TrustedPeersConfig},
     net::{
         types::{ConnectivityEvent, PeerInfo},
         ConnectivityService, NetworkingService,
@@ -55,12 +60,20 @@ pub const TEST_PROTOCOL_VERSION: SupportedProtocolVersion = SupportedProtocolVer
 /// Create a new MpscChannelTransport with a local address in the specified "group", which is
 /// represented by an integer.
 ///
+/// See `make_local_addr_in_group` for details.
+pub fn make_transport_with_local_addr_in_group(addr_group_idx: u32) -> MpscChannelTransport {
+    MpscChannelTransport::new_with_local_address(make_local_addr_in_group(addr_group_idx))
+}
+
+/// Make a new unique local address for MpscChannelTransport in the specified "group", which is
+/// represented by an integer.
+///
 /// Internally, the address group is represented by a specific number of most significant bits
 /// in the ip address; this function basically puts the passed addr_group_idx into that bit range.
 ///
 /// The function will also set the resulting address' highest bit to ensure that it doesn't end up
 /// in AddressGroup::Private (to which all 0.x.x.x addresses are mapped).
-pub fn make_transport_with_local_addr_in_group(addr_group_idx: u32) -> MpscChannelTransport {
+pub fn make_local_addr_in_group(addr_group_idx: u32) -> IpAddr {
     let addr_group_bits = peer_manager::address_groups::IPV4_GROUP_BYTES * 8;
     let addr_group_bit_offset = 32 - addr_group_bits;
     // Set the highest bit.
@@ -73,7 +86,7 @@ pub fn make_transport_with_local_addr_in_group(addr_group_idx: u32) -> MpscChann
     assert!(addr_group <= u32::MAX as u64);
 
     let local_address: Ipv4Addr = (next_addr_as_u32 + addr_group as u32).into();
-    MpscChannelTransport::new_with_local_address(local_address.into())
+    local_address.into()
 }
 
 /// Connect the node represented by conn1 to the first listening address of the node represented
@@ -185,12 +198,15 @@ pub fn test_p2p_config() -> P2pConfig {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         allow_discover_private_ips: Default::default(),
         user_agent: mintlayer_core_user_agent(),
@@ -210,12 +226,15 @@ pub fn test_p2p_config_with_peer_mgr_config(peer_manager_config: PeerManagerConf
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         allow_discover_private_ips: Default::default(),
         user_agent: mintlayer_core_user_agent(),
@@ -258,11 +277,14 @@ pub fn test_p2p_config_with_ban_config(ban_config: BanConfig) -> P2pConfig {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
         peer_handshake_timeout: Default::default(),
         max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
         node_type: Default::default(),
         allow_discover_private_ips: Default::default(),
         user_agent: mintlayer_core_user_agent(),
@@ -272,6 +294,42 @@ pub fn test_p2p_config_with_ban_config(ban_config: BanConfig) -> P2pConfig {
     }
 }
 
+pub fn test_p2p_config_with_trusted_peers(
+    trusted_peers: TrustedPeersConfig,
+    identity_key: PrivateKey,
+) -> P2pConfig {
+    P2pConfig {
+        trusted_peers,
+        peer_manager_config: PeerManagerConfig {
+            peerdb_config: PeerDbConfig {
+                identity_key: Some(identity_key),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+
+        bind_addresses: Default::default(),
+        socks5_proxy: Default::default(),
+        disable_noise: Default::default(),
+        boot_nodes: Default::default(),
+        reserved_nodes: Default::default(),
+        whitelisted_addresses: Default::default(),
+        min_peer_protocol_version: Default::default(),
+        ban_config: Default::default(),
+        outbound_connection_timeout: Default::default(),
+        ping_check_period: Default::default(),
+        ping_timeout: Default::default(),
+        peer_handshake_timeout: Default::default(),
+        max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
+        node_type: Default::default(),
+        allow_discover_private_ips: Default::default(),
+        user_agent: mintlayer_core_user_agent(),
+        sync_stalling_timeout: Default::default(),
+        protocol_config: Default::default(),
+    }
+}
+
 pub fn test_peer_mgr_config_with_no_auto_outbound_connections() -> PeerManagerConfig {
     PeerManagerConfig {
         outbound_block_relay_count: 0.into(),
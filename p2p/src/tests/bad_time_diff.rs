@@ -47,6 +47,7 @@ where
         let max_clock_diff = Duration::from_secs(1);
         let p2p_config = Arc::new(P2pConfig {
             max_clock_diff: max_clock_diff.into(),
+            max_block_future_offset: Default::default(),
 
             bind_addresses: Default::default(),
             socks5_proxy: Default::default(),
@@ -54,6 +55,8 @@ where
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            trusted_peers: Default::default(),
+            min_peer_protocol_version: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
@@ -171,6 +174,7 @@ where
         let max_clock_diff = Duration::from_secs(1);
         let p2p_config = Arc::new(P2pConfig {
             max_clock_diff: max_clock_diff.into(),
+            max_block_future_offset: Default::default(),
 
             bind_addresses: Default::default(),
             socks5_proxy: Default::default(),
@@ -178,6 +182,8 @@ where
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            trusted_peers: Default::default(),
+            min_peer_protocol_version: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
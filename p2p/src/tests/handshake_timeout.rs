@@ -0,0 +1,201 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{sync::Arc, time::Duration};
+
+use chainstate::ChainstateConfig;
+use common::primitives::user_agent::mintlayer_core_user_agent;
+use networking::test_helpers::{
+    TestTransportChannel, TestTransportMaker, TestTransportNoise, TestTransportTcp,
+};
+use networking::transport::{BufferedTranscoder, TransportListener, TransportSocket};
+use p2p_test_utils::run_with_timeout;
+use test_utils::{assert_matches, BasicTestTimeGetter};
+
+use crate::{
+    config::P2pConfig,
+    net::default_backend::types::{HandshakeMessage, Message},
+    peer_manager::PeerManagerInterface,
+    test_helpers::TEST_PROTOCOL_VERSION,
+    tests::helpers::TestNode,
+};
+
+// Check that a connection whose handshake isn't completed in time is closed and cleaned up,
+// without punishing the peer.
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(1);
+
+fn make_p2p_config() -> P2pConfig {
+    P2pConfig {
+        peer_handshake_timeout: HANDSHAKE_TIMEOUT.into(),
+
+        bind_addresses: Default::default(),
+        socks5_proxy: Default::default(),
+        disable_noise: Default::default(),
+        boot_nodes: Default::default(),
+        reserved_nodes: Default::default(),
+        whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
+        ban_config: Default::default(),
+        outbound_connection_timeout: Default::default(),
+        ping_check_period: Default::default(),
+        ping_timeout: Default::default(),
+        max_clock_diff: Default::default(),
+        node_type: Default::default(),
+        allow_discover_private_ips: Default::default(),
+        user_agent: mintlayer_core_user_agent(),
+        sync_stalling_timeout: Default::default(),
+        max_block_future_offset: Default::default(),
+        peer_manager_config: Default::default(),
+        protocol_config: Default::default(),
+    }
+}
+
+async fn handshake_timeout_outgoing<TTM>()
+where
+    TTM: TestTransportMaker,
+    TTM::Transport: TransportSocket,
+{
+    let time_getter = BasicTestTimeGetter::new();
+    let chain_config = Arc::new(common::chain::config::create_unit_test_config());
+    let p2p_config = Arc::new(make_p2p_config());
+
+    let test_node = TestNode::<TTM::Transport>::start(
+        true,
+        time_getter.clone(),
+        Arc::clone(&chain_config),
+        ChainstateConfig::new(),
+        Arc::clone(&p2p_config),
+        TTM::make_transport(),
+        TTM::make_address().into(),
+        TEST_PROTOCOL_VERSION.into(),
+        None,
+    )
+    .await;
+
+    let transport = TTM::make_transport();
+    let mut listener = transport.bind(vec![TTM::make_address()]).await.unwrap();
+
+    let address = listener.local_addresses().unwrap()[0].into();
+    let connect_result_receiver = test_node.start_connecting(address);
+
+    let (stream, _) = listener.accept().await.unwrap();
+    let start_time = tokio::time::Instant::now();
+
+    let mut msg_stream =
+        BufferedTranscoder::new(stream, Some(*p2p_config.protocol_config.max_message_size));
+
+    let msg = msg_stream.recv().await.unwrap();
+    assert_matches!(msg, Message::Handshake(HandshakeMessage::Hello { .. }));
+
+    // Never respond with HelloAck.
+
+    // connect_result should indicate a failed connection
+    let connect_result = connect_result_receiver.await.unwrap();
+    assert!(connect_result.is_err());
+    assert!(start_time.elapsed() >= HANDSHAKE_TIMEOUT);
+
+    // The connection should be closed.
+    msg_stream.recv().await.unwrap_err();
+
+    let test_node_remnants = test_node.join().await;
+    assert!(test_node_remnants.peer_mgr.peers().is_empty());
+    assert!(test_node_remnants.peer_mgr.pending_outbound_conn_addrs().is_empty());
+    assert_eq!(
+        test_node_remnants.peer_mgr.peerdb().list_discouraged().count(),
+        0
+    );
+}
+
+#[tracing::instrument]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn handshake_timeout_outgoing_tcp() {
+    run_with_timeout(handshake_timeout_outgoing::<TestTransportTcp>()).await;
+}
+
+#[tracing::instrument]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn handshake_timeout_outgoing_channels() {
+    run_with_timeout(handshake_timeout_outgoing::<TestTransportChannel>()).await;
+}
+
+#[tracing::instrument]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn handshake_timeout_outgoing_noise() {
+    run_with_timeout(handshake_timeout_outgoing::<TestTransportNoise>()).await;
+}
+
+async fn handshake_timeout_incoming<TTM>()
+where
+    TTM: TestTransportMaker,
+    TTM::Transport: TransportSocket,
+{
+    let time_getter = BasicTestTimeGetter::new();
+    let chain_config = Arc::new(common::chain::config::create_unit_test_config());
+    let p2p_config = Arc::new(make_p2p_config());
+
+    let test_node = TestNode::<TTM::Transport>::start(
+        true,
+        time_getter.clone(),
+        Arc::clone(&chain_config),
+        ChainstateConfig::new(),
+        Arc::clone(&p2p_config),
+        TTM::make_transport(),
+        TTM::make_address().into(),
+        TEST_PROTOCOL_VERSION.into(),
+        None,
+    )
+    .await;
+
+    let transport = TTM::make_transport();
+
+    let stream = transport.connect(test_node.local_address().socket_addr()).await.unwrap();
+    let start_time = tokio::time::Instant::now();
+
+    let mut msg_stream = BufferedTranscoder::<_, Message>::new(
+        stream,
+        Some(*p2p_config.protocol_config.max_message_size),
+    );
+
+    // Never send Hello; the connection should be closed after the timeout.
+    msg_stream.recv().await.unwrap_err();
+    assert!(start_time.elapsed() >= HANDSHAKE_TIMEOUT);
+
+    let test_node_remnants = test_node.join().await;
+    assert!(test_node_remnants.peer_mgr.peers().is_empty());
+    assert_eq!(
+        test_node_remnants.peer_mgr.peerdb().list_discouraged().count(),
+        0
+    );
+}
+
+#[tracing::instrument]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn handshake_timeout_incoming_tcp() {
+    run_with_timeout(handshake_timeout_incoming::<TestTransportTcp>()).await;
+}
+
+#[tracing::instrument]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn handshake_timeout_incoming_channels() {
+    run_with_timeout(handshake_timeout_incoming::<TestTransportChannel>()).await;
+}
+
+#[tracing::instrument]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn handshake_timeout_incoming_noise() {
+    run_with_timeout(handshake_timeout_incoming::<TestTransportNoise>()).await;
+}
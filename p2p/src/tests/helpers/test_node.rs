@@ -36,7 +36,9 @@ use common::chain::ChainConfig;
 use mempool::MempoolConfig;
 use networking::transport::{TransportListener, TransportSocket};
 use p2p_test_utils::SHORT_TIMEOUT;
-use p2p_types::{p2p_event::P2pEventHandler, socket_address::SocketAddress};
+use p2p_types::{
+    bannable_address::BannableAddress, p2p_event::P2pEventHandler, socket_address::SocketAddress,
+};
 use storage_inmemory::InMemory;
 use subsystem::ShutdownTrigger;
 use utils::atomics::SeqCstAtomicBool;
@@ -45,6 +47,7 @@ use utils_networking::IpOrSocketAddress;
 use crate::{
     config::P2pConfig,
     error::P2pError,
+    message::PeerReputationReason,
     net::{default_backend::DefaultNetworkingService, types::PeerRole, ConnectivityService},
     peer_manager::{
         peerdb::storage_impl::PeerDbStorageImpl,
@@ -347,6 +350,38 @@ where
         .await;
     }
 
+    pub async fn broadcast_reputation(
+        &self,
+        address: SocketAddress,
+        score: i16,
+        reason: PeerReputationReason,
+    ) {
+        let (response_sender, response_receiver) = oneshot_nofail::channel();
+        self.peer_mgr_event_sender
+            .send(PeerManagerEvent::BroadcastReputation {
+                address,
+                score,
+                reason,
+                response_sender,
+            })
+            .unwrap();
+        response_receiver.await.unwrap().unwrap();
+    }
+
+    pub async fn get_reputation(&self, address: BannableAddress) -> i32 {
+        query_peer_manager(&self.peer_mgr_event_sender, move |peer_mgr| {
+            peer_mgr.peer_db().reputation(&address)
+        })
+        .await
+    }
+
+    pub async fn is_address_discouraged(&self, address: BannableAddress) -> bool {
+        query_peer_manager(&self.peer_mgr_event_sender, move |peer_mgr| {
+            peer_mgr.peer_db().is_address_discouraged(&address)
+        })
+        .await
+    }
+
     pub async fn enable_networking(&mut self, enable: bool) {
         let (response_sender, response_receiver) = oneshot_nofail::channel();
         self.peer_mgr_event_sender
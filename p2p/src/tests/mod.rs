@@ -20,9 +20,11 @@ mod bad_time_diff;
 mod correct_handshake;
 mod disable_networking;
 mod disconnect_on_will_disconnect_msg;
+mod handshake_timeout;
 mod incorrect_handshake;
 mod misbehavior;
 mod peer_discovery_on_stale_tip;
+mod peer_reputation;
 mod same_handshake_nonce;
 mod unsupported_version;
 
@@ -485,6 +485,7 @@ pub fn make_p2p_config(peer_manager_config: PeerManagerConfig) -> P2pConfig {
         // each other (but we can't use MAX, because it'll cause overflow during calculations).
         ping_timeout: millenium.into(),
         max_clock_diff: millenium.into(),
+        max_block_future_offset: Default::default(),
         sync_stalling_timeout: millenium.into(),
 
         peer_manager_config,
@@ -494,6 +495,8 @@ pub fn make_p2p_config(peer_manager_config: PeerManagerConfig) -> P2pConfig {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         // Note: peer_handshake_timeout specifies real time rather than mocked time (it's passed
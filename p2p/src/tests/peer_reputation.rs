@@ -0,0 +1,189 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+
+use chainstate::ChainstateConfig;
+use crypto::key::{KeyKind, PrivateKey, PublicKey};
+use networking::{
+    test_helpers::{TestTransportChannel, TestTransportMaker},
+    transport::MpscChannelTransport,
+};
+use p2p_test_utils::run_with_timeout;
+use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress};
+use test_utils::BasicTestTimeGetter;
+
+use crate::{
+    ban_config::BanConfig,
+    config::TrustedPeersConfig,
+    message::PeerReputationReason,
+    test_helpers::{
+        make_local_addr_in_group, test_p2p_config_with_trusted_peers, TEST_PROTOCOL_VERSION,
+    },
+    tests::helpers::{node_wait_for_connection_to_sock_addr, TestNode},
+};
+
+type Transport = <TestTransportChannel as TestTransportMaker>::Transport;
+
+async fn start_node(
+    name: &str,
+    local_ip: IpAddr,
+    trusted_peers: &TrustedPeersConfig,
+    identity_key: PrivateKey,
+    time_getter: &BasicTestTimeGetter,
+) -> TestNode<Transport> {
+    let p2p_config = Arc::new(test_p2p_config_with_trusted_peers(
+        trusted_peers.clone(),
+        identity_key,
+    ));
+    TestNode::<Transport>::start(
+        true,
+        time_getter.clone(),
+        Arc::new(common::chain::config::create_unit_test_config()),
+        ChainstateConfig::new(),
+        p2p_config,
+        MpscChannelTransport::new_with_local_address(local_ip),
+        TestTransportChannel::make_address().into(),
+        TEST_PROTOCOL_VERSION.into(),
+        Some(name),
+    )
+    .await
+}
+
+async fn connect(from: &TestNode<Transport>, to: &TestNode<Transport>) {
+    from.start_connecting(*to.local_address()).await.unwrap().unwrap();
+    node_wait_for_connection_to_sock_addr(from, *to.local_address(), None, None).await;
+}
+
+async fn wait_for_reputation(node: &TestNode<Transport>, address: BannableAddress, score: i32) {
+    while node.get_reputation(address).await != score {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+// Three trusted nodes are connected in a chain: node1 <-> node2 <-> node3.
+// An untrusted node4 is connected to node1; it considers the other nodes trusted,
+// but they don't trust it back.
+// Node5 is connected to node3; its address is trusted by everyone, but its identity key isn't.
+// Check that the reputation updates reach every trusted node, including the ones
+// that aren't connected to the issuer directly, never reach the untrusted node and that
+// the updates signed by an unknown key are ignored.
+#[tracing::instrument]
+#[tokio::test]
+async fn reputation_propagates_through_trusted_cluster() {
+    run_with_timeout(reputation_propagates_through_trusted_cluster_impl()).await;
+}
+
+async fn reputation_propagates_through_trusted_cluster_impl() {
+    let time_getter = BasicTestTimeGetter::new();
+
+    let trusted_ips = [0, 1, 2, 4].map(make_local_addr_in_group);
+    let untrusted_ip = make_local_addr_in_group(3);
+
+    let new_key = || PrivateKey::new_from_entropy(KeyKind::Secp256k1Schnorr).0;
+    let trusted_keys = [new_key(), new_key(), new_key()];
+    let trusted_peers = TrustedPeersConfig {
+        addresses: trusted_ips
+            .iter()
+            .map(|ip| SocketAddress::new(SocketAddr::new(*ip, 0)))
+            .collect(),
+        public_keys: trusted_keys.iter().map(PublicKey::from_private_key).collect(),
+    };
+
+    let [key1, key2, key3] = trusted_keys;
+    let node1 = start_node("node1", trusted_ips[0], &trusted_peers, key1, &time_getter).await;
+    let node2 = start_node("node2", trusted_ips[1], &trusted_peers, key2, &time_getter).await;
+    let node3 = start_node("node3", trusted_ips[2], &trusted_peers, key3, &time_getter).await;
+    let node4 = start_node(
+        "node4",
+        untrusted_ip,
+        &trusted_peers,
+        new_key(),
+        &time_getter,
+    )
+    .await;
+    let node5 = start_node(
+        "node5",
+        trusted_ips[3],
+        &trusted_peers,
+        new_key(),
+        &time_getter,
+    )
+    .await;
+
+    connect(&node1, &node2).await;
+    connect(&node2, &node3).await;
+    connect(&node4, &node1).await;
+    connect(&node5, &node3).await;
+
+    let spammer_address: SocketAddress = "1.2.3.4:3031".parse().unwrap();
+    let spammer = spammer_address.as_bannable();
+    let threshold = *BanConfig::default().discouragement_threshold as i32;
+
+    // The update signed by the unknown key only changes the score on its issuer
+    let unknown_key_score = threshold / 4;
+    node5
+        .broadcast_reputation(
+            spammer_address,
+            unknown_key_score as i16,
+            PeerReputationReason::InvalidTransaction,
+        )
+        .await;
+    wait_for_reputation(&node5, spammer, unknown_key_score).await;
+
+    // The update issued by node1 is relayed to node3 by node2
+    let score = threshold / 2;
+    node1
+        .broadcast_reputation(
+            spammer_address,
+            score as i16,
+            PeerReputationReason::Spamming,
+        )
+        .await;
+    for node in [&node1, &node2, &node3] {
+        wait_for_reputation(node, spammer, score).await;
+        assert!(!node.is_address_discouraged(spammer).await);
+    }
+
+    // The update issued by node2 makes the score reach the threshold everywhere
+    node2
+        .broadcast_reputation(
+            spammer_address,
+            (threshold - score) as i16,
+            PeerReputationReason::InvalidBlock,
+        )
+        .await;
+    for node in [&node1, &node2, &node3] {
+        wait_for_reputation(node, spammer, threshold).await;
+        assert!(node.is_address_discouraged(spammer).await);
+    }
+
+    // The untrusted node has received nothing
+    assert_eq!(node4.get_reputation(spammer).await, 0);
+    assert!(!node4.is_address_discouraged(spammer).await);
+
+    // Node5 itself trusts the other nodes' keys, so it accepts their updates relayed by node3
+    wait_for_reputation(&node5, spammer, threshold + unknown_key_score).await;
+
+    node1.join().await;
+    node2.join().await;
+    node3.join().await;
+    node4.join().await;
+    node5.join().await;
+}
@@ -23,13 +23,13 @@ use std::{future::Future, sync::Arc, time::Duration};
 use tokio::sync::mpsc;
 
 use chainstate::{
-    make_chainstate, ChainstateConfig, ChainstateHandle, ChainstateSubsystem,
+    make_chainstate, ChainstateConfig, ChainstateEvent, ChainstateHandle, ChainstateSubsystem,
     DefaultTransactionVerificationStrategy,
 };
 use chainstate_test_framework::TestFramework;
 use common::{
-    chain::{config::ChainConfig, Block},
-    primitives::Idable,
+    chain::{block::timestamp::BlockTimestamp, config::ChainConfig, Block, GenBlock},
+    primitives::{Id, Idable},
     time_getter::TimeGetter,
 };
 use logging::log;
@@ -69,6 +69,38 @@ pub fn start_subsystems(
     )
 }
 
+/// Start the chainstate subsystem alone; the events it emits are sent to the returned receiver.
+pub fn start_chainstate_with_events(
+    chain_config: Arc<ChainConfig>,
+) -> (
+    ChainstateHandle,
+    mpsc::UnboundedReceiver<ChainstateEvent>,
+    ShutdownTrigger,
+    ManagerJoinHandle,
+) {
+    let mut chainstate = make_chainstate(
+        chain_config,
+        ChainstateConfig::new(),
+        chainstate_storage::inmemory::Store::new_empty().unwrap(),
+        DefaultTransactionVerificationStrategy::new(),
+        None,
+        Default::default(),
+    )
+    .unwrap();
+
+    let (event_sender, event_receiver) = mpsc::unbounded_channel();
+    chainstate.subscribe_to_subsystem_events(Arc::new(move |event| {
+        let _ = event_sender.send(event);
+    }));
+
+    let mut manager = subsystem::Manager::new("p2p-test-manager");
+    let shutdown_trigger = manager.make_shutdown_trigger();
+    let chainstate = manager.add_subsystem("p2p-test-chainstate", chainstate);
+    let manager_handle = manager.main_in_task_in_span(tracing::Span::current());
+
+    (chainstate, event_receiver, shutdown_trigger, manager_handle)
+}
+
 pub fn start_subsystems_generic(
     chainstate: ChainstateSubsystem,
     chain_config: Arc<ChainConfig>,
@@ -119,6 +151,104 @@ pub fn create_n_blocks(
     blocks
 }
 
+/// Build a chain of `n` blocks on top of the specified parent; the timestamps of the blocks
+/// start at `parent_timestamp` and grow by `time_step` seconds per block.
+fn create_branch(
+    rng: &mut (impl Rng + CryptoRng),
+    tf: &mut TestFramework,
+    parent_id: Id<GenBlock>,
+    parent_timestamp: BlockTimestamp,
+    n: usize,
+    time_step: u64,
+) -> Vec<Block> {
+    let mut blocks: Vec<Block> = Vec::with_capacity(n);
+
+    for _ in 0..n {
+        let (prev_id, prev_timestamp) =
+            blocks.last().map_or((parent_id, parent_timestamp), |block| {
+                (block.get_id().into(), block.timestamp())
+            });
+        let timestamp = prev_timestamp.add_int_seconds(time_step).unwrap();
+        blocks.push(
+            tf.make_block_builder()
+                .with_parent(prev_id)
+                .with_timestamp(timestamp)
+                .build(&mut *rng),
+        );
+    }
+
+    blocks
+}
+
+/// Build two competing chains of `main_chain_length` and `fork_length` blocks on top of
+/// `fork_point`, which must be known to the framework's chainstate.
+///
+/// Returns the main and the fork chains, the blocks are not processed. The blocks of the main
+/// chain are a second apart and the ones of the fork chain two seconds apart, so the chains
+/// diverge right after the fork point and don't depend on the framework's current time.
+///
+/// Note that the test framework doesn't use PoW by default, every block adds the same amount
+/// of chain trust; so the longer chain wins and, if the lengths are equal, the one processed
+/// first is kept.
+pub fn create_fork(
+    rng: &mut (impl Rng + CryptoRng),
+    tf: &mut TestFramework,
+    fork_point: Id<GenBlock>,
+    main_chain_length: usize,
+    fork_length: usize,
+) -> (Vec<Block>, Vec<Block>) {
+    assert!(main_chain_length > 0 && fork_length > 0);
+
+    let fork_point_timestamp = tf.gen_block_index(&fork_point).block_timestamp();
+    let main_chain = create_branch(
+        rng,
+        tf,
+        fork_point,
+        fork_point_timestamp,
+        main_chain_length,
+        1,
+    );
+    let fork = create_branch(rng, tf, fork_point, fork_point_timestamp, fork_length, 2);
+
+    (main_chain, fork)
+}
+
+/// Build a chain of `common` blocks and two competing branches of `a` and `b` blocks on top of it.
+///
+/// Returns the shared prefix and the two branches. The blocks are not processed.
+pub fn create_two_chains(
+    rng: &mut (impl Rng + CryptoRng),
+    tf: &mut TestFramework,
+    common: usize,
+    a: usize,
+    b: usize,
+) -> (Vec<Block>, Vec<Block>, Vec<Block>) {
+    assert!(common > 0 && a > 0 && b > 0);
+
+    let common_blocks = create_n_blocks(rng, tf, common);
+    let fork_point = common_blocks.last().unwrap();
+
+    let branch_a = create_branch(
+        rng,
+        tf,
+        fork_point.get_id().into(),
+        fork_point.timestamp(),
+        a,
+        1,
+    );
+    // A different time step makes the branches diverge right after the fork point.
+    let branch_b = create_branch(
+        rng,
+        tf,
+        fork_point.get_id().into(),
+        fork_point.timestamp(),
+        b,
+        2,
+    );
+
+    (common_blocks, branch_a, branch_b)
+}
+
 /// A timeout for blocking calls.
 pub const LONG_TIMEOUT: Duration = Duration::from_secs(600);
 /// A short timeout for events that shouldn't occur.
@@ -226,3 +356,160 @@ pub async fn wait_for_no_recv<T>(receiver: &mut mpsc::UnboundedReceiver<T>) {
 
     expect_future_val!(wait_loop);
 }
+
+#[cfg(test)]
+mod tests {
+    use chainstate::BlockSource;
+    use common::primitives::BlockHeight;
+    use rstest::rstest;
+    use test_utils::random::{make_seedable_rng, Seed};
+
+    use super::*;
+
+    fn assert_linked(parent: &Block, blocks: &[Block]) {
+        assert_eq!(
+            blocks[0].prev_block_id(),
+            Id::<GenBlock>::from(parent.get_id())
+        );
+        for pair in blocks.windows(2) {
+            assert_eq!(
+                pair[1].prev_block_id(),
+                Id::<GenBlock>::from(pair[0].get_id())
+            );
+        }
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    #[tokio::test]
+    async fn chainstate_events(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = Arc::new(common::chain::config::create_unit_test_config());
+
+        let (chainstate, mut event_receiver, shutdown_trigger, manager_handle) =
+            start_chainstate_with_events(Arc::clone(&chain_config));
+
+        let block = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config.as_ref().clone())
+            .build()
+            .make_block_builder()
+            .build(&mut rng);
+        let block_id = block.get_id();
+        chainstate
+            .call_mut(move |this| this.process_block(block, BlockSource::Local))
+            .await
+            .unwrap()
+            .unwrap();
+
+        let event = tokio::time::timeout(Duration::from_millis(100), event_receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            event,
+            ChainstateEvent::NewTip(block_id, BlockHeight::new(1))
+        );
+
+        shutdown_trigger.initiate();
+        manager_handle.join().await;
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn two_chains(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+
+        let common = rng.gen_range(1..5);
+        let a = rng.gen_range(1..5);
+        let b = rng.gen_range(a + 1..10);
+        let (common_blocks, branch_a, branch_b) =
+            create_two_chains(&mut rng, &mut tf, common, a, b);
+        assert_eq!(common_blocks.len(), common);
+        assert_eq!(branch_a.len(), a);
+        assert_eq!(branch_b.len(), b);
+
+        // Both branches continue the common prefix and diverge right after it
+        let fork_point = common_blocks.last().unwrap();
+        assert_linked(fork_point, &branch_a);
+        assert_linked(fork_point, &branch_b);
+        assert_ne!(branch_a[0].get_id(), branch_b[0].get_id());
+
+        // The branches are deterministic
+        assert_eq!(
+            create_branch(
+                &mut rng,
+                &mut tf,
+                fork_point.get_id().into(),
+                fork_point.timestamp(),
+                a,
+                1
+            ),
+            branch_a
+        );
+
+        for block in common_blocks.iter().chain(&branch_a) {
+            tf.process_block(block.clone(), BlockSource::Local).unwrap();
+        }
+        assert_eq!(
+            tf.best_block_id(),
+            Id::<GenBlock>::from(branch_a.last().unwrap().get_id())
+        );
+
+        // The longer branch wins
+        for block in &branch_b {
+            tf.process_block(block.clone(), BlockSource::Local).unwrap();
+        }
+        assert_eq!(
+            tf.best_block_id(),
+            Id::<GenBlock>::from(branch_b.last().unwrap().get_id())
+        );
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn fork(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+
+        let fork_point = tf
+            .create_chain(&tf.genesis().get_id().into(), rng.gen_range(1..5), &mut rng)
+            .unwrap();
+        let main_chain_length = rng.gen_range(1..5);
+        let fork_length = rng.gen_range(main_chain_length + 1..10);
+        let (main_chain, fork) = create_fork(
+            &mut rng,
+            &mut tf,
+            fork_point,
+            main_chain_length,
+            fork_length,
+        );
+        assert_eq!(main_chain.len(), main_chain_length);
+        assert_eq!(fork.len(), fork_length);
+
+        // Both chains start at the fork point and diverge right after it
+        assert_eq!(main_chain[0].prev_block_id(), fork_point);
+        assert_eq!(fork[0].prev_block_id(), fork_point);
+        assert_ne!(main_chain[0].get_id(), fork[0].get_id());
+
+        for block in &main_chain {
+            tf.process_block(block.clone(), BlockSource::Local).unwrap();
+        }
+        assert_eq!(
+            tf.best_block_id(),
+            Id::<GenBlock>::from(main_chain.last().unwrap().get_id())
+        );
+
+        // The longer fork wins
+        for block in &fork {
+            tf.process_block(block.clone(), BlockSource::Local).unwrap();
+        }
+        assert_eq!(
+            tf.best_block_id(),
+            Id::<GenBlock>::from(fork.last().unwrap().get_id())
+        );
+    }
+}
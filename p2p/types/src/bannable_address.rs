@@ -33,11 +33,20 @@ use serde::{Deserialize, Serialize};
     Deserialize,
     rpc_description::HasValueHint,
 )]
+#[serde(from = "IpAddr")]
 pub struct BannableAddress(IpAddr);
 
 impl BannableAddress {
+    /// An IPv4-mapped IPv6 address (e.g. `::ffff:1.2.3.4`) is converted to the plain IPv4 one,
+    /// so that a host can't evade a ban by switching between the two forms.
     pub fn new(ip: IpAddr) -> Self {
-        Self(ip)
+        Self(ip.to_canonical())
+    }
+}
+
+impl From<IpAddr> for BannableAddress {
+    fn from(ip: IpAddr) -> Self {
+        Self::new(ip)
     }
 }
 
@@ -51,6 +60,6 @@ impl FromStr for BannableAddress {
     type Err = AddrParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        IpAddr::from_str(s).map(BannableAddress)
+        IpAddr::from_str(s).map(BannableAddress::new)
     }
 }
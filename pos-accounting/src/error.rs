@@ -43,6 +43,8 @@ pub enum Error {
     DelegateToNonexistingPool,
     #[error("Spending share of non-existing pool")]
     SpendingShareOfNonexistingDelegation(DelegationId),
+    #[error("Spender is not authorized to spend from delegation {0}")]
+    UnauthorizedDelegationSpend(DelegationId),
     #[error("Addition error")]
     AdditionError,
     #[error("Subtraction error")]
@@ -87,6 +89,12 @@ pub enum Error {
     DuplicatesInDeltaAndUndo,
     #[error("Increase staker rewards of nonexisting pool")]
     IncreaseStakerRewardsOfNonexistingPool,
+    #[error("Attempted to slash a non-existing pool")]
+    AttemptedSlashNonexistingPool,
+    #[error("Slash amount exceeds the pool balance")]
+    SlashExceedsPoolBalance,
+    #[error("Undo of pool slashing failed as pool data is not in storage")]
+    InvariantErrorSlashUndoFailedPoolDataNotFound,
     #[error("Staker balance addition error")]
     StakerBalanceOverflow,
     #[error("Undo of increase pledge amount failed as pool balance is not in storage")]
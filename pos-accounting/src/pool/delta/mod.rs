@@ -17,12 +17,13 @@ use std::collections::BTreeMap;
 
 use accounting::{DeltaAmountCollection, DeltaDataUndoCollection};
 use common::{
-    chain::{DelegationId, PoolId},
+    chain::{DelegationId, Destination, PoolId},
     primitives::{amount::SignedAmount, Amount, H256},
 };
 use serialization::{Decode, Encode};
+use utils::ensure;
 
-use crate::error::Error;
+use crate::{error::Error, PoSAccountingOperations, PoSAccountingUndo};
 
 use self::data::PoSAccountingDeltaData;
 
@@ -105,6 +106,30 @@ impl<P: PoSAccountingView> PoSAccountingDelta<P> {
         self.data.undo_delta_merge(undo_data)
     }
 
+    /// Spend `amount` from the delegation on behalf of `spender`, which must be the spend
+    /// destination of the delegation. Unlike `spend_share_from_delegation_id`, spending more
+    /// than the delegation balance fails right away instead of on flush.
+    pub fn withdraw_from_delegation(
+        &mut self,
+        delegation_id: DelegationId,
+        amount: Amount,
+        spender: &Destination,
+    ) -> Result<PoSAccountingUndo, Error> {
+        let delegation_data = self
+            .get_delegation_data(delegation_id)?
+            .ok_or(Error::SpendingShareOfNonexistingDelegation(delegation_id))?;
+        ensure!(
+            delegation_data.spend_destination() == spender,
+            Error::UnauthorizedDelegationSpend(delegation_id)
+        );
+        ensure!(
+            amount <= self.get_delegation_balance(delegation_id)?,
+            Error::DelegationBalanceSubtractionError
+        );
+
+        self.spend_share_from_delegation_id(delegation_id, amount)
+    }
+
     fn add_to_delegation_balance(
         &mut self,
         delegation_target: DelegationId,
@@ -13,10 +13,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
+
 use accounting::DataDelta;
 use common::{
     chain::{DelegationId, Destination, PoolId, UtxoOutPoint},
-    primitives::Amount,
+    primitives::{amount::UnsignedIntType as AmountUIntType, Amount},
+    Uint256,
 };
 use utils::ensure;
 
@@ -28,7 +31,7 @@ use crate::{
         operations::{
             CreateDelegationIdUndo, CreatePoolUndo, DecommissionPoolUndo, DelegateStakingUndo,
             DeleteDelegationIdUndo, IncreaseStakerRewardsUndo, PoSAccountingOperations,
-            PoSAccountingUndo, SpendFromShareUndo,
+            PoSAccountingUndo, SlashPoolUndo, SpendFromShareUndo,
         },
         pool_data::PoolData,
         view::PoSAccountingView,
@@ -115,6 +118,75 @@ impl<P: PoSAccountingView> PoSAccountingOperations<PoSAccountingUndo> for PoSAcc
         ))
     }
 
+    fn slash_pool(
+        &mut self,
+        pool_id: PoolId,
+        slash_amount: Amount,
+    ) -> Result<PoSAccountingUndo, Error> {
+        let pool_data = self.get_pool_data(pool_id)?.ok_or(Error::AttemptedSlashNonexistingPool)?;
+
+        let pool_balance = self.get_pool_balance(pool_id)?;
+        ensure!(slash_amount <= pool_balance, Error::SlashExceedsPoolBalance);
+
+        let delegation_shares = self.get_pool_delegations_shares(pool_id)?.unwrap_or_default();
+
+        let mut delegations_slashed = BTreeMap::new();
+        for (delegation_id, share) in &delegation_shares {
+            let amount = proportional_slash(*share, slash_amount, pool_balance)?;
+            if amount > Amount::ZERO {
+                delegations_slashed.insert(*delegation_id, amount);
+            }
+        }
+
+        // Rounding down is in favor of the delegators, so the staker covers the remainder.
+        // If the staker balance isn't enough for that, the rest is taken from the delegations
+        // one atom at a time.
+        let delegations_total = delegations_slashed
+            .values()
+            .try_fold(Amount::ZERO, |total, amount| total + *amount)
+            .ok_or(Error::SlashExceedsPoolBalance)?;
+        let remainder = (slash_amount - delegations_total).ok_or(Error::SlashExceedsPoolBalance)?;
+        let staker_slashed = std::cmp::min(remainder, pool_data.staker_balance()?);
+
+        let mut excess = (remainder - staker_slashed).ok_or(Error::SlashExceedsPoolBalance)?;
+        for (delegation_id, share) in &delegation_shares {
+            if excess == Amount::ZERO {
+                break;
+            }
+            let slashed = delegations_slashed.entry(*delegation_id).or_insert(Amount::ZERO);
+            if *slashed < *share {
+                *slashed = (*slashed + Amount::from_atoms(1)).ok_or(Error::AdditionError)?;
+                excess = (excess - Amount::from_atoms(1)).ok_or(Error::SubError)?;
+            }
+        }
+        ensure!(excess == Amount::ZERO, Error::SlashExceedsPoolBalance);
+
+        let new_pool_data = if slash_amount == pool_balance {
+            None
+        } else {
+            Some(pool_data.clone().slash_staker_balance(staker_slashed)?)
+        };
+
+        for (delegation_id, amount) in &delegations_slashed {
+            self.sub_delegation_from_pool_share(pool_id, *delegation_id, *amount)?;
+            self.sub_from_delegation_balance(*delegation_id, *amount)?;
+        }
+
+        self.sub_balance_from_pool(pool_id, slash_amount)?;
+
+        let data_undo = self
+            .data
+            .pool_data
+            .merge_delta_data_element(pool_id, DataDelta::new(Some(pool_data), new_pool_data))?;
+
+        Ok(PoSAccountingUndo::SlashPool(SlashPoolUndo {
+            pool_id,
+            slash_amount,
+            delegations_slashed,
+            data_undo,
+        }))
+    }
+
     fn create_delegation_id(
         &mut self,
         target_pool: PoolId,
@@ -243,6 +315,7 @@ impl<P: PoSAccountingView> PoSAccountingOperations<PoSAccountingUndo> for PoSAcc
             PoSAccountingUndo::IncreaseStakerRewards(undo) => {
                 self.undo_increase_staker_balance(undo)
             }
+            PoSAccountingUndo::SlashPool(undo) => self.undo_slash_pool(undo),
         }
     }
 }
@@ -362,4 +435,36 @@ impl<P: PoSAccountingView> PoSAccountingDelta<P> {
 
         Ok(())
     }
+
+    fn undo_slash_pool(&mut self, undo: SlashPoolUndo) -> Result<(), Error> {
+        self.data
+            .pool_data
+            .undo_merge_delta_data_element(undo.pool_id, undo.data_undo)?;
+
+        self.get_pool_data(undo.pool_id)?
+            .ok_or(Error::InvariantErrorSlashUndoFailedPoolDataNotFound)?;
+
+        self.add_balance_to_pool(undo.pool_id, undo.slash_amount)?;
+
+        for (delegation_id, amount) in undo.delegations_slashed {
+            self.add_to_delegation_balance(delegation_id, amount)?;
+            self.add_delegation_to_pool_share(undo.pool_id, delegation_id, amount)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The part of `slash_amount` that falls on `share` of `pool_balance`, rounded down
+fn proportional_slash(
+    share: Amount,
+    slash_amount: Amount,
+    pool_balance: Amount,
+) -> Result<Amount, Error> {
+    let numer = (Uint256::from_amount(share) * Uint256::from_amount(slash_amount))
+        .expect("Source types are smaller");
+    let slashed =
+        (numer / Uint256::from_amount(pool_balance)).ok_or(Error::SlashExceedsPoolBalance)?;
+    let slashed: AmountUIntType = slashed.try_into().map_err(|_| Error::SlashExceedsPoolBalance)?;
+    Ok(Amount::from_atoms(slashed))
 }
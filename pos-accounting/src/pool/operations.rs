@@ -13,6 +13,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
+
 use accounting::DataDeltaUndo;
 use common::{
     chain::{DelegationId, Destination, PoolId, UtxoOutPoint},
@@ -68,6 +70,14 @@ pub struct IncreaseStakerRewardsUndo {
     pub(crate) data_undo: DataDeltaUndo<PoolData>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct SlashPoolUndo {
+    pub(crate) pool_id: PoolId,
+    pub(crate) slash_amount: Amount,
+    pub(crate) delegations_slashed: BTreeMap<DelegationId, Amount>,
+    pub(crate) data_undo: DataDeltaUndo<PoolData>,
+}
+
 #[must_use]
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, VariantCount)]
 pub enum PoSAccountingUndo {
@@ -85,6 +95,8 @@ pub enum PoSAccountingUndo {
     SpendFromShare(SpendFromShareUndo),
     #[codec(index = 6)]
     IncreaseStakerRewards(IncreaseStakerRewardsUndo),
+    #[codec(index = 7)]
+    SlashPool(SlashPoolUndo),
 }
 
 use super::{delegation::DelegationData, pool_data::PoolData};
@@ -100,6 +112,13 @@ pub trait PoSAccountingOperations<U> {
         amount_to_add: Amount,
     ) -> Result<U, Error>;
 
+    /// Take `slash_amount` away from the pool balance.
+    ///
+    /// The delegations lose a part of their shares proportional to their stake in the pool,
+    /// the rest is taken from the staker. If the whole pool balance is slashed,
+    /// the pool is decommissioned.
+    fn slash_pool(&mut self, pool_id: PoolId, slash_amount: Amount) -> Result<U, Error>;
+
     fn create_delegation_id(
         &mut self,
         target_pool: PoolId,
@@ -90,6 +90,18 @@ impl PoolData {
         Ok(self)
     }
 
+    /// Reduce the staker balance, taking the rewards first and then the pledge
+    pub fn slash_staker_balance(mut self, amount: Amount) -> Result<Self, Error> {
+        let from_rewards = std::cmp::min(self.staker_rewards, amount);
+        let from_pledge = (amount - from_rewards).ok_or(Error::SlashExceedsPoolBalance)?;
+
+        self.staker_rewards =
+            (self.staker_rewards - from_rewards).ok_or(Error::SlashExceedsPoolBalance)?;
+        self.pledge_amount =
+            (self.pledge_amount - from_pledge).ok_or(Error::SlashExceedsPoolBalance)?;
+        Ok(self)
+    }
+
     pub fn is_decommissioned(&self) -> bool {
         self.pledge_amount == Amount::ZERO && self.staker_rewards == Amount::ZERO
     }
@@ -19,20 +19,24 @@ use common::{
     chain::{OutPointSourceId, UtxoOutPoint},
     primitives::{Amount, Id, H256},
 };
-use randomness::RngCore;
+use randomness::{Rng, RngCore};
 use rstest::rstest;
 use test_utils::random::{make_seedable_rng, Seed};
 
 use super::{
-    create_pool, create_pool_data, create_storage_with_pool,
+    create_delegation_id, create_pool, create_pool_data, create_storage_with_pool,
     create_storage_with_pool_and_delegation, new_delegation_id, new_pool_id,
     new_pub_key_destination,
 };
 
 use crate::{
-    pool::{delta::PoSAccountingDelta, storage::PoSAccountingDB, view::FlushablePoSAccountingView},
+    pool::{
+        delta::PoSAccountingDelta,
+        storage::PoSAccountingDB,
+        view::{FlushablePoSAccountingView, PoSAccountingView},
+    },
     storage::in_memory::InMemoryPoSAccounting,
-    Error, PoSAccountingOperations,
+    DelegationData, Error, PoSAccountingDeltaData, PoSAccountingOperations,
 };
 
 #[rstest]
@@ -355,3 +359,316 @@ fn delta_delete_delegation_existing_pool(#[case] seed: Seed) {
     assert!(storage.all_delegation_data().is_empty());
     assert!(storage.all_delegation_balances().is_empty());
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn slash_unknown_pool(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let storage = InMemoryPoSAccounting::new();
+    let pool_id = new_pool_id(rng.next_u64());
+
+    let db = PoSAccountingDB::new(&storage);
+    let mut delta = PoSAccountingDelta::new(&db);
+    assert_eq!(
+        delta.slash_pool(pool_id, Amount::from_atoms(1)).unwrap_err(),
+        Error::AttemptedSlashNonexistingPool
+    );
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn slash_more_than_pool_balance(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let pledge_amount = Amount::from_atoms(100);
+    let delegated_amount = Amount::from_atoms(300);
+    let (pool_id, _, _, _, storage) =
+        create_storage_with_pool_and_delegation(&mut rng, pledge_amount, delegated_amount);
+
+    let db = PoSAccountingDB::new(&storage);
+    let mut delta = PoSAccountingDelta::new(&db);
+    assert_eq!(
+        delta.slash_pool(pool_id, Amount::from_atoms(401)).unwrap_err(),
+        Error::SlashExceedsPoolBalance
+    );
+}
+
+// The delegation loses 300 * 40 / 400 = 30 coins, the staker loses the remaining 10
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn slash_pool_partially(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let pledge_amount = Amount::from_atoms(100);
+    let delegated_amount = Amount::from_atoms(300);
+    let (pool_id, pool_data, delegation_id, del_destination, mut storage) =
+        create_storage_with_pool_and_delegation(&mut rng, pledge_amount, delegated_amount);
+
+    let mut db = PoSAccountingDB::new(&mut storage);
+    let mut delta = PoSAccountingDelta::new(&db);
+    let _ = delta.slash_pool(pool_id, Amount::from_atoms(40)).unwrap();
+    db.batch_write_delta(delta.consume()).unwrap();
+
+    let expected_pool_data = pool_data.slash_staker_balance(Amount::from_atoms(10)).unwrap();
+    assert_eq!(expected_pool_data.pledge_amount(), Amount::from_atoms(90));
+
+    let expected_storage = InMemoryPoSAccounting::from_values(
+        BTreeMap::from([(pool_id, expected_pool_data)]),
+        BTreeMap::from([(pool_id, Amount::from_atoms(360))]),
+        BTreeMap::from([((pool_id, delegation_id), Amount::from_atoms(270))]),
+        BTreeMap::from([(delegation_id, Amount::from_atoms(270))]),
+        BTreeMap::from([(delegation_id, DelegationData::new(pool_id, del_destination))]),
+    );
+    assert_eq!(storage, expected_storage);
+}
+
+// Slashing the whole pool balance decommissions the pool and empties the delegations
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn slash_pool_fully(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let pledge_amount = Amount::from_atoms(100);
+    let delegated_amount = Amount::from_atoms(300);
+    let (pool_id, _, delegation_id, del_destination, mut storage) =
+        create_storage_with_pool_and_delegation(&mut rng, pledge_amount, delegated_amount);
+
+    let mut db = PoSAccountingDB::new(&mut storage);
+    let mut delta = PoSAccountingDelta::new(&db);
+    let _ = delta.slash_pool(pool_id, Amount::from_atoms(400)).unwrap();
+
+    assert!(!delta.pool_exists(pool_id).unwrap());
+    assert_eq!(
+        delta.decommission_pool(pool_id).unwrap_err(),
+        Error::AttemptedDecommissionNonexistingPoolData
+    );
+
+    db.batch_write_delta(delta.consume()).unwrap();
+
+    let expected_storage = InMemoryPoSAccounting::from_values(
+        BTreeMap::new(),
+        BTreeMap::new(),
+        BTreeMap::new(),
+        BTreeMap::new(),
+        BTreeMap::from([(delegation_id, DelegationData::new(pool_id, del_destination))]),
+    );
+    assert_eq!(storage, expected_storage);
+}
+
+// Each delegation owes 2/3 of a coin, which is rounded down. The staker can't cover
+// the remainder, so the first delegation pays the missing coin.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn slash_pool_rounding(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let pool_id = new_pool_id(rng.next_u64());
+    let pool_data = create_pool_data(
+        &mut rng,
+        new_pub_key_destination(&mut rng),
+        Amount::from_atoms(1),
+    );
+    let delegation_id_1 = new_delegation_id(1);
+    let delegation_id_2 = new_delegation_id(2);
+    let delegation_data_1 = DelegationData::new(pool_id, new_pub_key_destination(&mut rng));
+    let delegation_data_2 = DelegationData::new(pool_id, new_pub_key_destination(&mut rng));
+
+    let mut storage = InMemoryPoSAccounting::from_values(
+        BTreeMap::from([(pool_id, pool_data.clone())]),
+        BTreeMap::from([(pool_id, Amount::from_atoms(3))]),
+        BTreeMap::from([
+            ((pool_id, delegation_id_1), Amount::from_atoms(1)),
+            ((pool_id, delegation_id_2), Amount::from_atoms(1)),
+        ]),
+        BTreeMap::from([
+            (delegation_id_1, Amount::from_atoms(1)),
+            (delegation_id_2, Amount::from_atoms(1)),
+        ]),
+        BTreeMap::from([
+            (delegation_id_1, delegation_data_1.clone()),
+            (delegation_id_2, delegation_data_2.clone()),
+        ]),
+    );
+
+    let mut db = PoSAccountingDB::new(&mut storage);
+    let mut delta = PoSAccountingDelta::new(&db);
+    let _ = delta.slash_pool(pool_id, Amount::from_atoms(2)).unwrap();
+    db.batch_write_delta(delta.consume()).unwrap();
+
+    let expected_storage = InMemoryPoSAccounting::from_values(
+        BTreeMap::from([(
+            pool_id,
+            pool_data.slash_staker_balance(Amount::from_atoms(1)).unwrap(),
+        )]),
+        BTreeMap::from([(pool_id, Amount::from_atoms(1))]),
+        BTreeMap::from([((pool_id, delegation_id_2), Amount::from_atoms(1))]),
+        BTreeMap::from([(delegation_id_2, Amount::from_atoms(1))]),
+        BTreeMap::from([
+            (delegation_id_1, delegation_data_1),
+            (delegation_id_2, delegation_data_2),
+        ]),
+    );
+    assert_eq!(storage, expected_storage);
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn balance_accessors(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let pledge_amount = Amount::from_atoms(rng.gen_range(1..100_000));
+    let delegated_amount = Amount::from_atoms(rng.gen_range(1..100_000));
+
+    let mut storage = InMemoryPoSAccounting::new();
+    let mut db = PoSAccountingDB::new(&mut storage);
+    let mut delta = PoSAccountingDelta::new(&mut db);
+
+    let (pool_id, _, _) = create_pool(&mut rng, &mut delta, pledge_amount).unwrap();
+    let (delegation_id, _, _) = create_delegation_id(&mut rng, &mut delta, pool_id).unwrap();
+    assert_eq!(
+        delta.delegation_balance(delegation_id),
+        Ok(Some(Amount::ZERO))
+    );
+
+    let _ = delta.delegate_staking(delegation_id, delegated_amount).unwrap();
+    let expected_pool_balance = (pledge_amount + delegated_amount).unwrap();
+
+    // before flush
+    assert_eq!(delta.pool_balance(pool_id), Ok(Some(expected_pool_balance)));
+    assert_eq!(
+        delta.delegation_balance(delegation_id),
+        Ok(Some(delegated_amount))
+    );
+
+    let unknown_pool_id = new_pool_id(rng.next_u64());
+    let unknown_delegation_id = new_delegation_id(rng.next_u64());
+    assert_eq!(delta.pool_balance(unknown_pool_id), Ok(None));
+    assert_eq!(delta.delegation_balance(unknown_delegation_id), Ok(None));
+
+    let consumed = delta.consume();
+    db.batch_write_delta(consumed).unwrap();
+
+    // after flush
+    assert_eq!(db.pool_balance(pool_id), Ok(Some(expected_pool_balance)));
+    assert_eq!(
+        db.delegation_balance(delegation_id),
+        Ok(Some(delegated_amount))
+    );
+    assert_eq!(db.pool_balance(unknown_pool_id), Ok(None));
+    assert_eq!(db.delegation_balance(unknown_delegation_id), Ok(None));
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn withdraw_from_delegation_authorized(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let pledge_amount = Amount::from_atoms(rng.gen_range(1..100_000));
+    let delegated_amount = Amount::from_atoms(rng.gen_range(1..100_000));
+    let (pool_id, _, delegation_id, spend_destination, mut storage) =
+        create_storage_with_pool_and_delegation(&mut rng, pledge_amount, delegated_amount);
+
+    let amount_to_withdraw = Amount::from_atoms(rng.gen_range(1..=delegated_amount.into_atoms()));
+
+    let mut db = PoSAccountingDB::new(&mut storage);
+    let mut delta = PoSAccountingDelta::new(&mut db);
+    let _ = delta
+        .withdraw_from_delegation(delegation_id, amount_to_withdraw, &spend_destination)
+        .unwrap();
+    db.batch_write_delta(delta.consume()).unwrap();
+
+    let delegation_balance = (delegated_amount - amount_to_withdraw).unwrap();
+    assert_eq!(
+        db.get_pool_balance(pool_id),
+        Ok((pledge_amount + delegation_balance).unwrap())
+    );
+    assert_eq!(
+        db.get_delegation_balance(delegation_id),
+        Ok(delegation_balance)
+    );
+    assert_eq!(
+        db.get_pool_delegation_share(pool_id, delegation_id),
+        Ok(delegation_balance)
+    );
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn withdraw_from_delegation_wrong_key(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let pledge_amount = Amount::from_atoms(100);
+    let delegated_amount = Amount::from_atoms(200);
+    let (_, _, delegation_id, _, mut storage) =
+        create_storage_with_pool_and_delegation(&mut rng, pledge_amount, delegated_amount);
+
+    let mut db = PoSAccountingDB::new(&mut storage);
+    let mut delta = PoSAccountingDelta::new(&mut db);
+    assert_eq!(
+        delta.withdraw_from_delegation(
+            delegation_id,
+            Amount::from_atoms(100),
+            &new_pub_key_destination(&mut rng)
+        ),
+        Err(Error::UnauthorizedDelegationSpend(delegation_id))
+    );
+    assert_eq!(delta.consume(), PoSAccountingDeltaData::new());
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn withdraw_from_delegation_more_than_delegated(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let pledge_amount = Amount::from_atoms(100);
+    let delegated_amount = Amount::from_atoms(200);
+    let (_, _, delegation_id, spend_destination, mut storage) =
+        create_storage_with_pool_and_delegation(&mut rng, pledge_amount, delegated_amount);
+
+    let mut db = PoSAccountingDB::new(&mut storage);
+    let mut delta = PoSAccountingDelta::new(&mut db);
+    assert_eq!(
+        delta.withdraw_from_delegation(
+            delegation_id,
+            Amount::from_atoms(rng.gen_range(201..1000)),
+            &spend_destination
+        ),
+        Err(Error::DelegationBalanceSubtractionError)
+    );
+    assert_eq!(delta.consume(), PoSAccountingDeltaData::new());
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn pools_iter(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let mut storage = InMemoryPoSAccounting::new();
+    let mut db = PoSAccountingDB::new(&mut storage);
+    let mut delta = PoSAccountingDelta::new(&mut db);
+
+    let mut expected_balances = BTreeMap::new();
+    for _ in 0..rng.gen_range(1..10) {
+        let pledge_amount = Amount::from_atoms(rng.gen_range(1..100_000));
+        let (pool_id, _, _) = create_pool(&mut rng, &mut delta, pledge_amount).unwrap();
+
+        let mut pool_balance = pledge_amount;
+        for _ in 0..rng.gen_range(0..5) {
+            let (delegation_id, _, _) =
+                create_delegation_id(&mut rng, &mut delta, pool_id).unwrap();
+            let delegated_amount = Amount::from_atoms(rng.gen_range(1..100_000));
+            let _ = delta.delegate_staking(delegation_id, delegated_amount).unwrap();
+            pool_balance = (pool_balance + delegated_amount).unwrap();
+        }
+
+        expected_balances.insert(pool_id, pool_balance);
+    }
+    db.batch_write_delta(delta.consume()).unwrap();
+
+    assert_eq!(
+        storage.pools_iter().collect::<BTreeMap<_, _>>(),
+        expected_balances
+    );
+}
@@ -112,9 +112,9 @@ fn perform_random_operation(
 ) {
     // If it fires it means that number of actions in PoSAccountingOperations has changed
     // and the following match needs to be updated
-    assert_eq!(PoSAccountingUndo::VARIANT_COUNT, 7);
+    assert_eq!(PoSAccountingUndo::VARIANT_COUNT, 8);
 
-    match rng.gen_range(0..11) {
+    match rng.gen_range(0..13) {
         // create new pool
         0..=1 => {
             let input0_outpoint = random_outpoint0(rng);
@@ -185,8 +185,18 @@ fn perform_random_operation(
                 }
             }
         }
-        // undo
+        // slash pool
         11 => {
+            if let Some(pool_id) = random_pool {
+                let pool_balance = op.get_pool_balance(pool_id).unwrap();
+                let slash_amount = Amount::from_atoms(rng.gen_range(0..=pool_balance.into_atoms()));
+
+                let undo = op.slash_pool(pool_id, slash_amount).unwrap();
+                undos.push(undo);
+            }
+        }
+        // undo
+        12 => {
             if let Some(undo) = undos.pop() {
                 op.undo(undo).unwrap();
             }
@@ -564,3 +564,32 @@ fn delete_delegation_id_delta_check_undo_check(#[case] seed: Seed) {
         assert_eq!(storage, original_storage);
     }
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy(), 40)]
+#[case(Seed::from_entropy(), 400)]
+fn slash_pool_flush_undo(#[case] seed: Seed, #[case] slash_amount: u128) {
+    let mut rng = make_seedable_rng(seed);
+    let pledge_amount = Amount::from_atoms(100);
+    let delegated_amount = Amount::from_atoms(300);
+    let (pool_id, _, _, _, original_storage) =
+        create_storage_with_pool_and_delegation(&mut rng, pledge_amount, delegated_amount);
+    let mut storage = original_storage.clone();
+
+    let undo = {
+        let mut db = PoSAccountingDB::new(&mut storage);
+        let mut delta = PoSAccountingDelta::new(&db);
+        let undo = delta.slash_pool(pool_id, Amount::from_atoms(slash_amount)).unwrap();
+        db.batch_write_delta(delta.consume()).unwrap();
+        undo
+    };
+    assert_ne!(storage, original_storage);
+
+    let mut db = PoSAccountingDB::new(&mut storage);
+    let mut delta = PoSAccountingDelta::new(&db);
+    delta.undo(undo).unwrap();
+    db.batch_write_delta(delta.consume()).unwrap();
+
+    assert_eq!(storage, original_storage);
+}
@@ -50,6 +50,27 @@ pub trait PoSAccountingView {
         pool_id: PoolId,
         delegation_id: DelegationId,
     ) -> Result<Amount, Self::Error>;
+
+    /// Balance of the pool, `None` if the pool doesn't exist
+    fn pool_balance(&self, pool_id: PoolId) -> Result<Option<Amount>, Self::Error> {
+        if self.pool_exists(pool_id)? {
+            self.get_pool_balance(pool_id).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Balance of the delegation, `None` if the delegation doesn't exist
+    fn delegation_balance(
+        &self,
+        delegation_id: DelegationId,
+    ) -> Result<Option<Amount>, Self::Error> {
+        if self.get_delegation_data(delegation_id)?.is_some() {
+            self.get_delegation_balance(delegation_id).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
 }
 
 pub trait FlushablePoSAccountingView {
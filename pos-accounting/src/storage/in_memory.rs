@@ -85,6 +85,11 @@ impl InMemoryPoSAccounting {
         &self.delegation_balances
     }
 
+    /// All the pools with their total balances, which include the delegated amounts
+    pub fn pools_iter(&self) -> impl Iterator<Item = (PoolId, Amount)> + '_ {
+        self.pool_balances.iter().map(|(pool_id, balance)| (*pool_id, *balance))
+    }
+
     #[cfg(test)]
     pub(crate) fn check_consistency(&self) {
         // pool_balance and pool_data must contain the same keys
@@ -238,6 +238,7 @@ enum SqliteStorageMode {
 pub struct Sqlite {
     backend: SqliteStorageMode,
     options: Options,
+    read_only: bool,
 }
 
 impl Sqlite {
@@ -245,6 +246,7 @@ impl Sqlite {
         Self {
             backend: SqliteStorageMode::InMemory,
             options: Default::default(),
+            read_only: false,
         }
     }
 
@@ -253,6 +255,20 @@ impl Sqlite {
         Self {
             backend: SqliteStorageMode::File(path.as_ref().to_path_buf()),
             options: Default::default(),
+            read_only: false,
+        }
+    }
+
+    /// New Sqlite database backend that opens an existing database file for reading only.
+    ///
+    /// The file and its tables are never created and no exclusive lock is taken, so the database
+    /// can be read while it's not in use by a read-write backend (which holds an exclusive lock).
+    /// Read-write transactions can still be started, but any write to them fails.
+    pub fn new_read_only<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            backend: SqliteStorageMode::File(path.as_ref().to_path_buf()),
+            options: Default::default(),
+            read_only: true,
         }
     }
 
@@ -260,10 +276,15 @@ impl Sqlite {
         Self {
             backend: self.backend,
             options,
+            read_only: self.read_only,
         }
     }
 
     fn open_db(self, desc: DbDesc) -> rusqlite::Result<Connection> {
+        if self.read_only {
+            return Self::open_db_read_only(self.backend, desc);
+        }
+
         let flags = OpenFlags::from_iter([
             OpenFlags::SQLITE_OPEN_FULL_MUTEX,
             OpenFlags::SQLITE_OPEN_READ_WRITE,
@@ -310,11 +331,52 @@ impl Sqlite {
         }
         drop(exists_stmt);
 
+        Self::set_statement_cache_capacity(&connection, &desc);
+
+        Ok(connection)
+    }
+
+    fn open_db_read_only(backend: SqliteStorageMode, desc: DbDesc) -> rusqlite::Result<Connection> {
+        let flags = OpenFlags::from_iter([
+            OpenFlags::SQLITE_OPEN_FULL_MUTEX,
+            OpenFlags::SQLITE_OPEN_READ_ONLY,
+        ]);
+
+        let connection = match backend {
+            SqliteStorageMode::InMemory => Connection::open_in_memory_with_flags(flags)?,
+            SqliteStorageMode::File(path) => Connection::open_with_flags(path, flags)?,
+        };
+
+        // The tables can't be created, so all of them must already exist
+        let mut exists_stmt = connection
+            .prepare_cached("SELECT name FROM sqlite_master WHERE type='table' AND name=?")?;
+
+        for idx in desc.db_map_count().indices() {
+            let table_name = &desc.db_maps()[idx].name();
+            let is_missing = exists_stmt
+                .query_row([&table_name], |row| row.get::<usize, String>(0))
+                .optional()?
+                .is_none();
+            if is_missing {
+                return Err(rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_READONLY),
+                    Some(format!(
+                        "Table {table_name} is missing in a database opened read-only"
+                    )),
+                ));
+            }
+        }
+        drop(exists_stmt);
+
+        Self::set_statement_cache_capacity(&connection, &desc);
+
+        Ok(connection)
+    }
+
+    fn set_statement_cache_capacity(connection: &Connection, desc: &DbDesc) {
         // Set statement cache to fit all the prepared statements we use
         let statement_cap = max(desc.db_map_count().as_usize() * 4, 16);
         connection.set_prepared_statement_cache_capacity(statement_cap);
-
-        Ok(connection)
     }
 }
 
@@ -322,9 +384,10 @@ impl backend::Backend for Sqlite {
     type Impl = SqliteImpl;
 
     fn open(self, desc: DbDesc) -> storage_core::Result<Self::Impl> {
-        // Attempt to create the parent storage directory if using a file
+        // Attempt to create the parent storage directory if using a file,
+        // unless the database is opened read-only and so must already exist
 
-        if let SqliteStorageMode::File(ref path) = self.backend {
+        if let (SqliteStorageMode::File(path), false) = (&self.backend, self.read_only) {
             if let Some(parent) = path.parent() {
                 std::fs::create_dir_all(parent).map_err(error::process_io_error)?;
             } else {
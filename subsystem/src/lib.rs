@@ -28,7 +28,8 @@
 //!
 //! The shutdown proceeds in three phases:
 //!
-//! 1. As soon as any subsystem terminates, the main task is notified.
+//! 1. As soon as any subsystem terminates, the main task is notified. Subsystems added with a
+//!    [RestartPolicy] are first restarted, until they run out of restarts.
 //! 2. The main task broadcasts the shutdown request to all subsystems. The subsystems react to the
 //!    request by shutting themselves down.
 //! 3. The main task waits for all subsystems to terminate.
@@ -43,6 +44,6 @@ pub mod error;
 
 pub use crate::{
     calls::{blocking, CallResponse, CallResult, Handle, SubmitOnlyHandle},
-    manager::{Manager, ManagerConfig, ManagerJoinHandle, ShutdownTrigger},
+    manager::{Manager, ManagerConfig, ManagerJoinHandle, RestartPolicy, ShutdownTrigger},
     subsystem::Subsystem,
 };
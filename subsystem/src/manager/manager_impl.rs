@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{panic, time::Duration};
+use std::{collections::BTreeMap, panic, time::Duration};
 
 use futures::future::BoxFuture;
 use tokio::{
@@ -22,9 +22,16 @@ use tokio::{
 };
 
 use logging::log;
-use utils::{const_value::ConstValue, shallow_clone::ShallowClone};
+use utils::{
+    const_value::ConstValue,
+    shallow_clone::ShallowClone,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
 
-use crate::{task, Handle, ManagerConfig, SubmitOnlyHandle, Subsystem};
+use crate::{task, Handle, ManagerConfig, RestartPolicy, SubmitOnlyHandle, Subsystem};
 
 use super::shutdown_signal::shutdown_signal;
 
@@ -46,6 +53,9 @@ pub struct Manager {
 
     // List of subsystem tasks
     subsystems: Vec<SubsystemData<BoxFuture<'static, ()>>>,
+
+    // Number of restarts of each subsystem that has a restart policy
+    restart_counts: RestartCounts,
 }
 
 impl Manager {
@@ -59,12 +69,14 @@ impl Manager {
         log::info!("Initializing subsystem manager {}", config.name);
         let (shutting_down_tx, shutting_down_rx) = mpsc::unbounded_channel();
         let subsystems = Vec::new();
+        let restart_counts = RestartCounts::new();
 
         Self {
             config: config.into(),
             shutting_down_tx,
             shutting_down_rx,
             subsystems,
+            restart_counts,
         }
     }
 
@@ -116,6 +128,60 @@ impl Manager {
         Handle::new(submit_handle)
     }
 
+    /// Add a subsystem that is restarted if it panics or fails to initialize.
+    ///
+    /// This works like [Manager::add_custom_subsystem], except the initialization routine is
+    /// invoked again each time the subsystem is restarted. The subsystem is restarted at most
+    /// `restart_policy.max_restarts` times, after that its termination shuts down the manager as
+    /// usual. The handle stays valid across restarts, calls in progress at the time of the crash
+    /// are lost.
+    pub fn add_subsystem_with_restart_policy<S, IF, SF, E>(
+        &mut self,
+        subsys_name: &'static str,
+        restart_policy: RestartPolicy,
+        subsys_init: IF,
+    ) -> Handle<S::Interface>
+    where
+        IF: Fn(SubmitOnlyHandle<S::Interface>) -> SF + Send + Sync + 'static,
+        SF: std::future::IntoFuture<Output = Result<S, E>> + Send + 'static,
+        SF::IntoFuture: Send,
+        S: Subsystem,
+        E: std::error::Error + 'static,
+    {
+        let full_name = self.config.full_name_of(subsys_name);
+
+        // Shutdown-related channels
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+
+        // Call related channels
+        let (action_tx, action_rx) = mpsc::unbounded_channel();
+        let submit_handle = SubmitOnlyHandle::new(action_tx);
+
+        let restart_count = Arc::new(AtomicU32::new(0));
+        self.restart_counts.0.insert(subsys_name, Arc::clone(&restart_count));
+
+        log::info!("Registering subsystem {full_name} with {restart_policy:?}");
+
+        let task = Box::pin(task::restartable_subsystem(
+            full_name.clone(),
+            subsys_init,
+            submit_handle.shallow_clone(),
+            action_rx,
+            shutdown_rx,
+            self.shutting_down_tx.clone(),
+            restart_policy,
+            restart_count,
+        ));
+
+        self.subsystems.push(SubsystemData {
+            full_name,
+            task,
+            shutdown_tx,
+        });
+
+        Handle::new(submit_handle)
+    }
+
     /// Add a subsystem that does not require custom initialization code.
     pub fn add_subsystem<S>(&mut self, name: &'static str, subsys: S) -> Handle<S::Interface>
     where
@@ -176,10 +242,14 @@ impl Manager {
     /// an incorrect usage. The returned handle must be joined to ensure a proper subsystems
     /// shutdown.
     pub fn main_in_task(self) -> ManagerJoinHandle {
+        let restart_counts = self.restart_counts.clone();
         let handle = Some(logging::spawn_in_current_span(
             async move { self.main().await },
         ));
-        ManagerJoinHandle { handle }
+        ManagerJoinHandle {
+            handle,
+            restart_counts,
+        }
     }
 
     /// Runs the application in a separate task.
@@ -187,11 +257,36 @@ impl Manager {
     /// This does the same as `main_in_task` but uses the specified tracing span instead of
     /// the current one.
     pub fn main_in_task_in_span(self, tracing_span: tracing::Span) -> ManagerJoinHandle {
+        let restart_counts = self.restart_counts.clone();
         let handle = Some(logging::spawn_in_span(
             async move { self.main().await },
             tracing_span,
         ));
-        ManagerJoinHandle { handle }
+        ManagerJoinHandle {
+            handle,
+            restart_counts,
+        }
+    }
+
+    /// Number of times the given subsystem has been restarted so far.
+    ///
+    /// Returns 0 for subsystems without a restart policy and for unknown subsystems.
+    pub fn subsystem_restart_count(&self, name: &str) -> u32 {
+        self.restart_counts.get(name)
+    }
+}
+
+/// Restart counters shared between the subsystem tasks and the manager handles
+#[derive(Clone)]
+struct RestartCounts(BTreeMap<&'static str, Arc<AtomicU32>>);
+
+impl RestartCounts {
+    fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    fn get(&self, name: &str) -> u32 {
+        self.0.get(name).map_or(0, |count| count.load(Ordering::Acquire))
     }
 }
 
@@ -275,9 +370,17 @@ impl ShutdownTrigger {
 /// Join handle for the top-level subsystem manager task
 pub struct ManagerJoinHandle {
     handle: Option<JoinHandle<()>>,
+    restart_counts: RestartCounts,
 }
 
 impl ManagerJoinHandle {
+    /// Number of times the given subsystem has been restarted so far.
+    ///
+    /// Returns 0 for subsystems without a restart policy and for unknown subsystems.
+    pub fn subsystem_restart_count(&self, name: &str) -> u32 {
+        self.restart_counts.get(name)
+    }
+
     pub async fn join(mut self) {
         if let Err(err) = self.handle.take().expect("The join handle is missing").await {
             log::error!("Failed to join subsystem manager handle: {err:?}");
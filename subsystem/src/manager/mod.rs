@@ -22,6 +22,16 @@ pub use manager_impl::{Manager, ManagerJoinHandle, ShutdownTrigger};
 
 use std::time::Duration;
 
+/// Determines what happens to a subsystem that panics or fails to initialize
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RestartPolicy {
+    /// Maximum number of times the subsystem is restarted before the manager gives up
+    pub max_restarts: u32,
+
+    /// How long to wait before restarting the subsystem
+    pub restart_delay: Duration,
+}
+
 /// Subsystem manager configuration options
 pub struct ManagerConfig {
     /// Subsystem manager name
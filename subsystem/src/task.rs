@@ -15,6 +15,9 @@
 
 //! Implementation of tasks that constitute the subsystem mechanism.
 
+use std::panic::AssertUnwindSafe;
+
+use futures::FutureExt;
 use tokio::{
     sync::{mpsc, oneshot, RwLock},
     task::JoinSet,
@@ -22,9 +25,16 @@ use tokio::{
 use tracing::Instrument;
 
 use logging::log;
-use utils::{once_destructor::OnceDestructor, sync::Arc};
+use utils::{
+    once_destructor::OnceDestructor,
+    shallow_clone::ShallowClone,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
 
-use crate::{calls::Action, SubmitOnlyHandle, Subsystem};
+use crate::{calls::Action, RestartPolicy, SubmitOnlyHandle, Subsystem};
 
 /// Handle a task completion result
 pub fn handle_result(full_name: &str, task_type: &str, res: Result<(), tokio::task::JoinError>) {
@@ -63,6 +73,129 @@ pub async fn subsystem<S, IF, SF, E>(
         }
     });
 
+    let mut shutdown_requested = false;
+    run(
+        &full_name,
+        subsys_init,
+        submit_handle,
+        &mut action_rx,
+        &mut shutdown_rx,
+        &mut shutdown_requested,
+    )
+    .await;
+}
+
+/// The subsystem worker task implementation for subsystems that are restarted on failure
+#[allow(clippy::too_many_arguments)]
+pub async fn restartable_subsystem<S, IF, SF, E>(
+    full_name: String,
+    subsys_init: IF,
+    submit_handle: SubmitOnlyHandle<S::Interface>,
+    mut action_rx: mpsc::UnboundedReceiver<Action<S::Interface>>,
+    mut shutdown_rx: oneshot::Receiver<()>,
+    shutting_down_tx: mpsc::UnboundedSender<()>,
+    restart_policy: RestartPolicy,
+    restart_count: Arc<AtomicU32>,
+) where
+    IF: Fn(SubmitOnlyHandle<S::Interface>) -> SF + Send + Sync + 'static,
+    SF: std::future::IntoFuture<Output = Result<S, E>> + Send,
+    SF::IntoFuture: Send,
+    S: Subsystem,
+    E: std::error::Error,
+{
+    log::info!("Subsystem {full_name} starting");
+
+    // Make sure that we send the shutdown signal even in case of a panic.
+    let _shutdown_sender = OnceDestructor::new({
+        let full_name = &full_name;
+        move || {
+            let _ = shutting_down_tx.send(());
+            log::info!("Subsystem {full_name} terminated");
+        }
+    });
+
+    let mut shutdown_requested = false;
+    loop {
+        let result = AssertUnwindSafe(run(
+            &full_name,
+            &subsys_init,
+            submit_handle.shallow_clone(),
+            &mut action_rx,
+            &mut shutdown_rx,
+            &mut shutdown_requested,
+        ))
+        .catch_unwind()
+        .await;
+
+        match result {
+            Ok(RunOutcome::Stopped) => break,
+            Ok(RunOutcome::InitFailed) => {}
+            Err(_) => log::error!("Subsystem {full_name} panicked"),
+        }
+
+        // A failure during the shutdown is not a reason to start the subsystem again.
+        if shutdown_requested {
+            break;
+        }
+
+        let restarts = restart_count.load(Ordering::Acquire);
+        if restarts >= restart_policy.max_restarts {
+            log::error!("Subsystem {full_name} has been restarted {restarts} times, giving up");
+            break;
+        }
+        restart_count.store(restarts + 1, Ordering::Release);
+
+        log::warn!(
+            "Restarting subsystem {full_name} (restart {} of {})",
+            restarts + 1,
+            restart_policy.max_restarts
+        );
+
+        cfg_if::cfg_if! {
+            if #[cfg(all(feature = "time", not(loom)))] {
+                tokio::select! {
+                    result = (&mut shutdown_rx) => {
+                        if let Err(err) = result {
+                            log::error!("Shutdown channel for {full_name} closed prematurely: {err}");
+                        }
+                        break;
+                    }
+                    () = tokio::time::sleep(restart_policy.restart_delay) => {}
+                }
+            } else {
+                if !restart_policy.restart_delay.is_zero() {
+                    log::warn!("Restart delay for {full_name} ignored, timer support not available");
+                }
+            }
+        }
+    }
+}
+
+/// How a single run of a subsystem ended
+enum RunOutcome {
+    /// The subsystem has been initialized, run and shut down
+    Stopped,
+
+    /// The subsystem failed to initialize
+    InitFailed,
+}
+
+/// Initialize the subsystem and run its main event loop until shutdown is requested
+async fn run<S, IF, SF, E>(
+    full_name: &str,
+    subsys_init: IF,
+    submit_handle: SubmitOnlyHandle<S::Interface>,
+    action_rx: &mut mpsc::UnboundedReceiver<Action<S::Interface>>,
+    shutdown_rx: &mut oneshot::Receiver<()>,
+    shutdown_requested: &mut bool,
+) -> RunOutcome
+where
+    IF: FnOnce(SubmitOnlyHandle<S::Interface>) -> SF,
+    SF: std::future::IntoFuture<Output = Result<S, E>> + Send,
+    SF::IntoFuture: Send,
+    S: Subsystem,
+    E: std::error::Error,
+{
     // Worker task set to serve reads in parallel.
     let mut worker_tasks = JoinSet::new();
 
@@ -71,7 +204,7 @@ pub async fn subsystem<S, IF, SF, E>(
         Ok(subsys) => Arc::new(RwLock::new(subsys)),
         Err(err) => {
             log::error!("Subsystem {full_name} failed to initialize: {err}");
-            return;
+            return RunOutcome::InitFailed;
         }
     };
 
@@ -91,7 +224,8 @@ pub async fn subsystem<S, IF, SF, E>(
             biased;
 
             // We're shutting down, no point in doing anything else.
-            result = (&mut shutdown_rx) => {
+            result = &mut *shutdown_rx => {
+                *shutdown_requested = true;
                 if let Err(err) = result {
                     log::error!("Shutdown channel for {full_name} closed prematurely: {err}");
                 }
@@ -115,7 +249,7 @@ pub async fn subsystem<S, IF, SF, E>(
 
             // Clean up worker tasks.
             Some(task_result) = worker_tasks.join_next() => {
-                handle_result(&full_name, "worker", task_result);
+                handle_result(full_name, "worker", task_result);
             }
 
             // Finally, if nothing else is going on, process a unit of background work.
@@ -126,7 +260,7 @@ pub async fn subsystem<S, IF, SF, E>(
     }
 
     while let Some(task_result) = worker_tasks.join_next().await {
-        handle_result(&full_name, "worker", task_result);
+        handle_result(full_name, "worker", task_result);
     }
 
     // All worker tasks have terminated above, we are the last ones holding the subsys Arc
@@ -134,4 +268,6 @@ pub async fn subsystem<S, IF, SF, E>(
         .map_err(|_| ())
         .expect("Something else still holds the subsystem reference");
     RwLock::into_inner(subsys).shutdown().await;
+
+    RunOutcome::Stopped
 }
@@ -0,0 +1,101 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "time", not(loom)))]
+
+use std::{convert::Infallible, sync::Arc, time::Duration};
+
+use subsystem::{error::CallError, RestartPolicy};
+use utils::sync::atomic::{AtomicU32, Ordering};
+
+// A subsystem that panics on the first call it gets. The call counter is shared by all the
+// instances of the subsystem, so the instance created on restart does not panic again.
+struct Flaky {
+    call_count: Arc<AtomicU32>,
+}
+
+impl Flaky {
+    fn call(&mut self) -> u32 {
+        let call_count = self.call_count.fetch_add(1, Ordering::SeqCst) + 1;
+        assert!(call_count > 1, "First call to the flaky subsystem");
+        call_count
+    }
+}
+
+#[async_trait::async_trait]
+impl subsystem::Subsystem for Flaky {
+    type Interface = Self;
+
+    fn interface_ref(&self) -> &Self {
+        self
+    }
+
+    fn interface_mut(&mut self) -> &mut Self {
+        self
+    }
+}
+
+fn add_flaky(
+    man: &mut subsystem::Manager,
+    max_restarts: u32,
+) -> subsystem::Handle<<Flaky as subsystem::Subsystem>::Interface> {
+    let restart_policy = RestartPolicy {
+        max_restarts,
+        restart_delay: Duration::from_millis(10),
+    };
+    let call_count = Arc::new(AtomicU32::new(0));
+
+    man.add_subsystem_with_restart_policy("flaky", restart_policy, move |_| {
+        let call_count = Arc::clone(&call_count);
+        std::future::ready(Result::<_, Infallible>::Ok(Flaky { call_count }))
+    })
+}
+
+#[tokio::test]
+async fn restart_after_panic() {
+    let mut man = subsystem::Manager::new("restart_test");
+    let flaky = add_flaky(&mut man, 3);
+    let shutdown = man.make_shutdown_trigger();
+    let man = man.main_in_task();
+
+    assert_eq!(man.subsystem_restart_count("flaky"), 0);
+
+    // The call panics, so the response is lost
+    assert!(matches!(
+        flaky.call_mut(Flaky::call).await,
+        Err(CallError::Response(_))
+    ));
+
+    // The call is handled by the restarted subsystem
+    assert_eq!(flaky.call_mut(Flaky::call).await, Ok(2));
+    assert_eq!(man.subsystem_restart_count("flaky"), 1);
+    assert_eq!(man.subsystem_restart_count("unknown"), 0);
+
+    shutdown.initiate();
+    man.join().await;
+}
+
+#[tokio::test]
+async fn no_restarts_left() {
+    let mut man = subsystem::Manager::new("restart_test");
+    let flaky = add_flaky(&mut man, 0);
+    let man = man.main_in_task();
+
+    assert!(flaky.call_mut(Flaky::call).await.is_err());
+
+    // The subsystem is not restarted and the manager shuts down on its own
+    man.join().await;
+    assert!(flaky.call_mut(Flaky::call).await.is_err());
+}
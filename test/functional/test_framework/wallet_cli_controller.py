@@ -217,8 +217,8 @@ class WalletCliController:
     async def create_new_account(self, name: Optional[str] = '') -> str:
         return await self._write_command(f"account-create {name}\n")
 
-    async def rename_account(self, name: Optional[str] = '') -> str:
-        return await self._write_command(f"account-rename {name}\n")
+    async def rename_account(self, account_index: int, name: Optional[str] = '') -> str:
+        return await self._write_command(f"account-rename {account_index} {name}\n")
 
     async def standalone_address_label_rename(self, address: str, label: Optional[str] = None) -> str:
         label_str = f'--label {label}' if label else ''
@@ -250,6 +250,9 @@ class WalletCliController:
         i_know_what_i_am_doing = "i-know-what-i-am-doing" if force_reduce else ""
         return await self._write_command(f"wallet-set-lookahead-size {size} {i_know_what_i_am_doing}\n")
 
+    async def lookahead_status(self) -> str:
+        return await self._write_command("wallet-lookahead-status\n")
+
     async def new_public_key(self, address: Optional[str] = None) -> bytes:
         if address is None:
             address = await self.new_address()
@@ -509,6 +512,9 @@ class WalletCliController:
     async def abandon_transaction(self, tx_id: str) -> str:
         return await self._write_command(f"transaction-abandon {tx_id}\n")
 
+    async def child_pays_for_parent(self, parent_tx_id: str, target_fee_rate: Union[int, float, str]) -> str:
+        return await self._write_command(f"transaction-cpfp {parent_tx_id} {target_fee_rate}\n")
+
     async def make_tx_to_send_tokens_from_multisig_address(
             self, from_address: str, outputs: List[TokenTxOutput], fee_change_addr: Optional[str]):
 
@@ -207,8 +207,8 @@ class WalletRpcController:
         result = self._write_command("account_create", [name])['result']
         return f"Success, the new account index is: {result['account']}"
 
-    async def rename_account(self, name: Optional[str] = None) -> str:
-        self._write_command("account_rename", [self.account, name])
+    async def rename_account(self, account_index: int, name: Optional[str] = None) -> str:
+        self._write_command("account_rename", [account_index, name])
         return "Success, the account name has been successfully renamed"
 
     async def select_account(self, account_index: int) -> str:
@@ -354,7 +354,7 @@ class WalletRpcController:
                                 margin_ratio_per_thousand: float,
                                 decommission_key: Optional[str] = None) -> str:
         #decommission_key = decommission_key if decommission_key else 'NULL'
-        self._write_command("staking_create_pool", [self.account, {'decimal': str(amount)}, {'decimal': str(cost_per_block)}, str(margin_ratio_per_thousand), decommission_key, {'in_top_x_mb': 5}])['result']
+        self._write_command("staking_create_pool", [self.account, {'decimal': str(amount)}, {'decimal': str(cost_per_block)}, str(margin_ratio_per_thousand), decommission_key, None, {'in_top_x_mb': 5}])['result']
         return "The transaction was submitted successfully"
 
     async def decommission_stake_pool(self, pool_id: str, address: str) -> str:
@@ -123,7 +123,7 @@ class WalletAccountInfo(BitcoinTestFramework):
             assert_equal(info[1].name, acc1_name)
 
             acc0_name = ''.join(random.choice(string.ascii_letters + string.digits) for _ in range(random.randint(1, 5)))
-            assert_in("Success, the account name has been successfully renamed", await wallet.rename_account(acc0_name))
+            assert_in("Success, the account name has been successfully renamed", await wallet.rename_account(0, acc0_name))
 
             info = await wallet.wallet_info()
             assert_equal(2, len(info))
@@ -132,6 +132,15 @@ class WalletAccountInfo(BitcoinTestFramework):
             assert_equal(info[1].index, 1)
             assert_equal(info[1].name, acc1_name)
 
+            # rename an account other than the selected one
+            new_acc1_name = acc1_name + '_renamed'
+            assert_in("Success, the account name has been successfully renamed", await wallet.rename_account(1, new_acc1_name))
+
+            info = await wallet.wallet_info()
+            assert_equal(2, len(info))
+            assert_equal(info[0].name, acc0_name)
+            assert_equal(info[1].name, new_acc1_name)
+
 
 if __name__ == '__main__':
     WalletAccountInfo().main()
@@ -0,0 +1,123 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Load the peer db of a node and print the addresses from its "tried" table
+//! as BIND zone file records.
+
+use std::{path::PathBuf, sync::Arc};
+
+use clap::Parser;
+
+use common::{
+    chain::config::ChainType, primitives::user_agent::mintlayer_core_user_agent,
+    time_getter::TimeGetter,
+};
+use p2p::{
+    config::P2pConfig,
+    peer_manager::peerdb::{format_as_dns_seed_entries, open_storage, PeerDb},
+};
+use utils::default_data_dir::default_data_dir_for_chain;
+
+#[derive(clap::ValueEnum, Clone, Debug)]
+enum Network {
+    Mainnet,
+    Testnet,
+}
+
+#[derive(Parser, Debug)]
+struct Options {
+    /// Optional path to the node's data directory
+    #[clap(long)]
+    datadir: Option<PathBuf>,
+
+    /// Network
+    #[arg(long, value_enum, default_value_t = Network::Mainnet)]
+    network: Network,
+
+    /// Only addresses with the score above this value are exported.
+    /// The score is 0 for addresses without failed connection attempts and it decreases
+    /// by 1 with each consecutive failed attempt.
+    #[clap(long, default_value_t = -1, allow_negative_numbers = true)]
+    score_threshold: i32,
+}
+
+fn run(options: Options) -> anyhow::Result<()> {
+    let chain_type = match options.network {
+        Network::Mainnet => ChainType::Mainnet,
+        Network::Testnet => ChainType::Testnet,
+    };
+    let chain_config = common::chain::config::Builder::new(chain_type).build();
+
+    // The peer db is only read, so the default settings of a node are used
+    let p2p_config = Arc::new(P2pConfig {
+        bind_addresses: Default::default(),
+        socks5_proxy: Default::default(),
+        disable_noise: Default::default(),
+        boot_nodes: Default::default(),
+        reserved_nodes: Default::default(),
+        whitelisted_addresses: Default::default(),
+        trusted_peers: Default::default(),
+        min_peer_protocol_version: Default::default(),
+        ban_config: Default::default(),
+        outbound_connection_timeout: Default::default(),
+        ping_check_period: Default::default(),
+        ping_timeout: Default::default(),
+        peer_handshake_timeout: Default::default(),
+        max_clock_diff: Default::default(),
+        max_block_future_offset: Default::default(),
+        node_type: Default::default(),
+        allow_discover_private_ips: Default::default(),
+        user_agent: mintlayer_core_user_agent(),
+        sync_stalling_timeout: Default::default(),
+        peer_manager_config: Default::default(),
+        protocol_config: Default::default(),
+    });
+
+    let peerdb_data_dir = options
+        .datadir
+        .unwrap_or_else(|| default_data_dir_for_chain(chain_type.name()))
+        .join("peerdb-lmdb");
+    anyhow::ensure!(
+        peerdb_data_dir.exists(),
+        "Peer db not found at {}",
+        peerdb_data_dir.display()
+    );
+
+    let storage = open_storage(storage_lmdb::Lmdb::new(
+        peerdb_data_dir,
+        Default::default(),
+        Default::default(),
+        Default::default(),
+    ))?;
+
+    let peerdb = PeerDb::new(&chain_config, p2p_config, TimeGetter::default(), storage)?;
+
+    let addresses = peerdb.export_trusted_addresses(options.score_threshold);
+    for entry in format_as_dns_seed_entries(&addresses) {
+        println!("{entry}");
+    }
+
+    Ok(())
+}
+
+fn main() {
+    utils::rust_backtrace::enable();
+
+    let options = Options::parse();
+    run(options).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    })
+}
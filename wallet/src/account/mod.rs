@@ -32,6 +32,7 @@ use common::size_estimation::{
 };
 use common::Uint256;
 use crypto::key::hdkd::child_number::ChildNumber;
+use crypto::key::hdkd::derivation_path::DerivationPath;
 use mempool::FeeRate;
 use serialization::hex_encoded::HexEncoded;
 use utils::ensure;
@@ -57,8 +58,8 @@ use common::chain::tokens::{
     make_token_id, IsTokenUnfreezable, NftIssuance, NftIssuanceV0, RPCFungibleTokenInfo, TokenId,
 };
 use common::chain::{
-    AccountNonce, Block, ChainConfig, DelegationId, Destination, GenBlock, PoolId,
-    SignedTransaction, Transaction, TxInput, TxOutput, UtxoOutPoint,
+    AccountNonce, Block, ChainConfig, DelegationId, Destination, GenBlock, OutPointSourceId,
+    PoolId, SignedTransaction, Transaction, TxInput, TxOutput, UtxoOutPoint,
 };
 use common::primitives::{Amount, BlockHeight, Id};
 use consensus::PoSGenerateBlockInputData;
@@ -69,6 +70,7 @@ use itertools::{izip, Itertools};
 use std::cmp::Reverse;
 use std::collections::btree_map::Entry;
 use std::collections::{BTreeMap, BTreeSet};
+use std::num::NonZeroUsize;
 use std::ops::{Add, Sub};
 use std::sync::Arc;
 use wallet_storage::{
@@ -87,7 +89,10 @@ pub use self::output_cache::{
     DelegationData, FungibleTokenInfo, PoolData, TxInfo, UnconfirmedTokenInfo, UtxoWithTxOutput,
 };
 use self::output_cache::{OutputCache, TokenIssuanceData};
-use self::transaction_list::{get_transaction_list, TransactionList};
+use self::transaction_list::{
+    get_activity_summary, get_transaction_list, get_transaction_list_from_cursor, DailyActivity,
+    TransactionList, TxCursor,
+};
 use self::utxo_selector::PayFee;
 
 pub use self::utxo_selector::CoinSelectionAlgo;
@@ -111,6 +116,14 @@ impl TransactionToSign {
     }
 }
 
+/// The inputs selected for a send request, before its change outputs are added
+struct InputSelection {
+    request: SendRequest,
+    pay_fee_with_currency: currency_grouper::Currency,
+    output_currency_amounts: BTreeMap<currency_grouper::Currency, Amount>,
+    selected_inputs: BTreeMap<currency_grouper::Currency, utxo_selector::SelectionResult>,
+}
+
 pub struct Account {
     chain_config: Arc<ChainConfig>,
     key_chain: AccountKeyChainImpl,
@@ -208,6 +221,85 @@ impl Account {
         median_time: BlockTimestamp,
         fee_rates: CurrentFeeRate,
     ) -> WalletResult<SendRequest> {
+        let InputSelection {
+            request,
+            pay_fee_with_currency,
+            output_currency_amounts,
+            selected_inputs,
+        } = self.select_inputs(
+            request,
+            input_utxos,
+            selection_algo,
+            &change_addresses,
+            median_time,
+            fee_rates,
+        )?;
+
+        // Check outputs against inputs and create change
+        self.check_outputs_and_add_change(
+            &pay_fee_with_currency,
+            output_currency_amounts,
+            selected_inputs,
+            change_addresses,
+            db_tx,
+            request,
+        )
+    }
+
+    /// Estimate the fee that a transaction created from the send request would pay, selecting
+    /// its inputs the same way as `select_inputs_for_send_request` but without changing
+    /// the account, e.g. by issuing change addresses
+    pub fn estimate_send_request_fee(
+        &self,
+        request: SendRequest,
+        median_time: BlockTimestamp,
+        fee_rates: CurrentFeeRate,
+    ) -> WalletResult<Amount> {
+        // Use the change address that would be issued for the actual transaction,
+        // so that the size of the change outputs is the same
+        let change_address = self.peek_next_change_address()?;
+        let change_addresses = currency_grouper::group_outputs(
+            request.outputs().iter(),
+            |&output| output,
+            |_, _, _| Ok(()),
+            (),
+        )?
+        .into_keys()
+        .map(|currency| (currency, change_address.clone()))
+        .collect();
+
+        let InputSelection {
+            request: _,
+            pay_fee_with_currency: _,
+            output_currency_amounts,
+            selected_inputs,
+        } = self.select_inputs(
+            request,
+            SelectedInputs::Utxos(vec![]),
+            None,
+            &change_addresses,
+            median_time,
+            fee_rates,
+        )?;
+
+        // The same fees that `check_outputs_and_add_change` adds to the request
+        output_currency_amounts
+            .keys()
+            .filter_map(|currency| selected_inputs.get(currency))
+            .map(|result| result.get_total_fees())
+            .sum::<Option<Amount>>()
+            .ok_or(WalletError::OutputAmountOverflow)
+    }
+
+    fn select_inputs(
+        &self,
+        request: SendRequest,
+        input_utxos: SelectedInputs,
+        selection_algo: Option<CoinSelectionAlgo>,
+        change_addresses: &BTreeMap<Currency, Address<Destination>>,
+        median_time: BlockTimestamp,
+        fee_rates: CurrentFeeRate,
+    ) -> WalletResult<InputSelection> {
         // TODO: allow to pay fees with different currency?
         let pay_fee_with_currency = currency_grouper::Currency::Coin;
 
@@ -373,15 +465,12 @@ impl Account {
         );
         selected_inputs.insert(pay_fee_with_currency.clone(), selection_result);
 
-        // Check outputs against inputs and create change
-        self.check_outputs_and_add_change(
-            &pay_fee_with_currency,
+        Ok(InputSelection {
+            request,
+            pay_fee_with_currency,
             output_currency_amounts,
             selected_inputs,
-            change_addresses,
-            db_tx,
-            request,
-        )
+        })
     }
 
     fn check_outputs_and_add_change(
@@ -407,7 +496,7 @@ impl Account {
                 {
                     change_address
                 } else {
-                    self.key_chain.next_unused_address(db_tx, KeyPurpose::Change)?.1
+                    self.next_change_address(db_tx)?
                 };
 
                 let change_output = match currency {
@@ -550,6 +639,177 @@ impl Account {
         Ok(request.with_outputs(outputs))
     }
 
+    /// Create a send request that transfers the whole confirmed and unlocked balance
+    /// of the token to the destination.
+    ///
+    /// All such token UTXOs are preselected as inputs, so no token change is produced;
+    /// coin inputs to pay the fee are selected as usual.
+    pub fn send_all_tokens_request(
+        &self,
+        token_id: TokenId,
+        destination: Destination,
+        median_time: BlockTimestamp,
+    ) -> WalletResult<SendRequest> {
+        let token_utxos = self
+            .get_utxos(
+                UtxoType::Transfer | UtxoType::LockThenTransfer,
+                median_time,
+                UtxoState::Confirmed.into(),
+                WithLocked::Unlocked,
+            )
+            .into_iter()
+            .filter(|(_, (_, utxo_token_id))| *utxo_token_id == Some(token_id))
+            .map(|(outpoint, (txo, _))| (outpoint, txo.clone()))
+            .collect::<Vec<_>>();
+
+        let total_amount = token_utxos
+            .iter()
+            .map(|(_, txo)| match txo {
+                TxOutput::Transfer(OutputValue::TokenV1(_, amount), _)
+                | TxOutput::LockThenTransfer(OutputValue::TokenV1(_, amount), _, _) => *amount,
+                _ => Amount::ZERO,
+            })
+            .sum::<Option<Amount>>()
+            .ok_or(WalletError::OutputAmountOverflow)?;
+        ensure!(total_amount > Amount::ZERO, WalletError::NoUtxos);
+
+        let output = TxOutput::Transfer(OutputValue::TokenV1(token_id, total_amount), destination);
+
+        let pool_data_getter = |pool_id: &PoolId| self.output_cache.pool_data(*pool_id).ok();
+        SendRequest::new()
+            .with_inputs(
+                token_utxos.into_iter().map(|(outpoint, txo)| (TxInput::Utxo(outpoint), txo)),
+                &pool_data_getter,
+            )
+            .map(|request| request.with_outputs([output]))
+    }
+
+    /// Create a send request for a child transaction that spends an unconfirmed coin output
+    /// of the parent transaction, paying enough fee for the parent and the child together
+    /// to reach the target fee rate.
+    ///
+    /// The largest unspent coin output of the parent owned by this account is spent and
+    /// sent to a new change address.
+    ///
+    /// The fee paid by the parent is `parent_fee` if known (e.g. from the mempool), otherwise
+    /// it's computed from the parent's inputs. If some of them were not created by this account,
+    /// the parent is assumed to pay no fee at all, so the child pays for the whole parent.
+    pub fn create_cpfp_request(
+        &mut self,
+        db_tx: &mut impl WalletStorageWriteLocked,
+        parent_tx_id: Id<Transaction>,
+        parent_fee: Option<Amount>,
+        target_fee_rate: FeeRate,
+        median_time: BlockTimestamp,
+    ) -> WalletResult<SendRequest> {
+        let parent = self.output_cache.get_transaction(parent_tx_id)?;
+        ensure!(
+            matches!(parent.state(), TxState::InMempool(_) | TxState::Inactive(_)),
+            WalletError::CpfpParentNotUnconfirmed(parent_tx_id)
+        );
+
+        let parent_fee = match parent_fee {
+            Some(parent_fee) => parent_fee,
+            None => self.coin_fee_paid(parent.get_transaction())?.unwrap_or(Amount::ZERO),
+        };
+        let parent_size = serialization::Encode::encoded_size(parent.get_signed_transaction());
+
+        let (outpoint, output, amount) = self
+            .get_utxos(
+                UtxoType::Transfer | UtxoType::LockThenTransfer,
+                median_time,
+                UtxoState::InMempool | UtxoState::Inactive,
+                WithLocked::Unlocked,
+            )
+            .into_iter()
+            .filter(|(outpoint, _)| outpoint.source_id() == parent_tx_id.into())
+            .filter_map(|(outpoint, (txo, _))| match txo {
+                TxOutput::Transfer(OutputValue::Coin(amount), _)
+                | TxOutput::LockThenTransfer(OutputValue::Coin(amount), _, _) => {
+                    Some((outpoint, txo.clone(), *amount))
+                }
+                _ => None,
+            })
+            .max_by_key(|(_, _, amount)| *amount)
+            .ok_or(WalletError::CpfpNoSpendableParentOutput(parent_tx_id))?;
+
+        let request =
+            SendRequest::new().with_inputs([(TxInput::Utxo(outpoint), output)], &|_| None)?;
+
+        let input_fees = group_preselected_inputs(
+            &request,
+            target_fee_rate,
+            &self.chain_config,
+            self.account_info.best_block_height(),
+            Some(self),
+        )?
+        .values()
+        .map(|(_, fee)| *fee)
+        .sum::<Option<Amount>>()
+        .ok_or(WalletError::OutputAmountOverflow)?;
+
+        let change_address = self.next_change_address(db_tx)?;
+        let child_output = make_address_output(change_address.clone(), amount);
+        let child_fee: Amount = target_fee_rate
+            .compute_fee(tx_size_with_outputs(&[child_output]))
+            .map_err(|_| UtxoSelectorError::AmountArithmeticError)?
+            .into();
+        let child_fee = (child_fee + input_fees).ok_or(WalletError::OutputAmountOverflow)?;
+
+        // The child also pays for whatever the parent is missing to reach the target fee rate
+        let parent_required_fee: Amount = target_fee_rate
+            .compute_fee(parent_size)
+            .map_err(|_| UtxoSelectorError::AmountArithmeticError)?
+            .into();
+        let parent_deficit = (parent_required_fee - parent_fee).unwrap_or(Amount::ZERO);
+        let total_fee = (child_fee + parent_deficit).ok_or(WalletError::OutputAmountOverflow)?;
+
+        let change_amount =
+            (amount - total_fee).ok_or(WalletError::NotEnoughUtxo(amount, total_fee))?;
+        ensure!(
+            change_amount > Amount::ZERO,
+            WalletError::NotEnoughUtxo(amount, total_fee)
+        );
+
+        Ok(request.with_outputs([make_address_output(change_address, change_amount)]))
+    }
+
+    /// The coins spent by the inputs of the transaction that are not returned in its outputs,
+    /// or `None` if some of the spent outputs are unknown to this account
+    fn coin_fee_paid(&self, tx: &Transaction) -> WalletResult<Option<Amount>> {
+        let inputs_amount = tx
+            .inputs()
+            .iter()
+            .map(|input| match input {
+                TxInput::Utxo(outpoint) => {
+                    self.output_cache.get_txo(outpoint).map(output_coin_amount)
+                }
+                TxInput::Account(outpoint) => match outpoint.account() {
+                    AccountSpending::DelegationBalance(_, amount) => Some(*amount),
+                },
+                TxInput::AccountCommand(_, _) => Some(Amount::ZERO),
+            })
+            .collect::<Option<Vec<_>>>();
+        let inputs_amount = match inputs_amount {
+            Some(amounts) => amounts
+                .into_iter()
+                .sum::<Option<Amount>>()
+                .ok_or(WalletError::OutputAmountOverflow)?,
+            None => return Ok(None),
+        };
+
+        let outputs_amount = tx
+            .outputs()
+            .iter()
+            .map(output_coin_amount)
+            .sum::<Option<Amount>>()
+            .ok_or(WalletError::OutputAmountOverflow)?;
+
+        Ok(Some(
+            (inputs_amount - outputs_amount).unwrap_or(Amount::ZERO),
+        ))
+    }
+
     pub fn sweep_delegation(
         &mut self,
         address: Address<Destination>,
@@ -870,6 +1130,13 @@ impl Account {
             .ok_or(WalletError::DelegationNotFound(*delegation_id))
     }
 
+    /// Tokens issued with an authority that belongs to this account
+    pub fn get_token_authorities(&self) -> impl Iterator<Item = (&TokenId, &TokenIssuanceData)> {
+        self.output_cache
+            .token_ids()
+            .filter(|(_, data)| self.is_destination_mine(&data.authority))
+    }
+
     pub fn find_token(&self, token_id: &TokenId) -> WalletResult<&TokenIssuanceData> {
         self.output_cache
             .token_data(token_id)
@@ -898,7 +1165,10 @@ impl Account {
         let staker = Destination::PublicKey(
             self.key_chain.issue_key(db_tx, KeyPurpose::ReceiveFunds)?.into_public_key(),
         );
-        let vrf_public_key = self.get_vrf_public_key(db_tx)?;
+        let vrf_public_key = match &stake_pool_arguments.vrf_public_key {
+            Some(vrf_public_key) => vrf_public_key.clone(),
+            None => self.get_vrf_public_key(db_tx)?,
+        };
 
         // the first UTXO is needed in advance to calculate pool_id, so just make a dummy one
         // and then replace it with when we can calculate the pool_id
@@ -1417,6 +1687,21 @@ impl Account {
         Ok(self.key_chain.add_standalone_multisig(db_tx, challenge, label)?)
     }
 
+    /// Make this a multisig account, its balance and coin selection then use the outputs
+    /// sent to the challenge's multisig address
+    pub fn set_multisig_challenge(
+        &mut self,
+        db_tx: &mut impl WalletStorageWriteLocked,
+        challenge: ClassicMultisigChallenge,
+    ) -> WalletResult<()> {
+        Ok(self.key_chain.set_multisig_challenge(db_tx, challenge)?)
+    }
+
+    /// The public key this account contributes to the challenge of a multisig account
+    pub fn multisig_public_key(&self) -> WalletResult<PublicKey> {
+        Ok(self.key_chain.multisig_public_key()?)
+    }
+
     /// Get a new address that hasn't been used before
     pub fn get_new_address(
         &mut self,
@@ -1426,6 +1711,34 @@ impl Account {
         Ok(self.key_chain.issue_address(db_tx, purpose)?)
     }
 
+    /// Get the change address that the next created transaction would use, without issuing it
+    pub fn get_current_change_address(&self) -> WalletResult<Address<Destination>> {
+        if let Some((destination, _)) = self.key_chain.multisig_challenge() {
+            return Ok(Address::new(&self.chain_config, destination.clone())?);
+        }
+        Ok(self.key_chain.peek_next_unused_address(KeyPurpose::Change)?.1)
+    }
+
+    /// Issue the change address for a new transaction, the funds of a multisig account
+    /// always go back to its multisig address
+    fn next_change_address(
+        &mut self,
+        db_tx: &mut impl WalletStorageWriteLocked,
+    ) -> WalletResult<Address<Destination>> {
+        if let Some((destination, _)) = self.key_chain.multisig_challenge() {
+            return Ok(Address::new(&self.chain_config, destination.clone())?);
+        }
+        Ok(self.key_chain.next_unused_address(db_tx, KeyPurpose::Change)?.1)
+    }
+
+    /// The address that `next_change_address` would return, without issuing it
+    fn peek_next_change_address(&self) -> WalletResult<Address<Destination>> {
+        if let Some((destination, _)) = self.key_chain.multisig_challenge() {
+            return Ok(Address::new(&self.chain_config, destination.clone())?);
+        }
+        Ok(self.key_chain.peek_next_unused_address(KeyPurpose::Change)?.1)
+    }
+
     /// Get a new vrf key that hasn't been used before
     pub fn get_new_vrf_key(
         &mut self,
@@ -1442,6 +1755,25 @@ impl Account {
         )
     }
 
+    /// Get `count` new vrf keys that haven't been used before
+    pub fn get_new_vrf_keys(
+        &mut self,
+        db_tx: &mut impl WalletStorageWriteLocked,
+        count: u32,
+    ) -> WalletResult<Vec<(ChildNumber, Address<VRFPublicKey>)>> {
+        let keys = self.key_chain.issue_vrf_keys(db_tx, count)?;
+        Ok(keys
+            .into_iter()
+            .map(|(child_number, vrf_key)| {
+                (
+                    child_number,
+                    Address::new(&self.chain_config, vrf_key.public_key().clone())
+                        .expect("addressable"),
+                )
+            })
+            .collect())
+    }
+
     /// Get the corresponding public key for a given public key hash
     pub fn find_corresponding_pub_key(
         &self,
@@ -1456,10 +1788,47 @@ impl Account {
         self.key_chain.get_all_issued_addresses()
     }
 
+    pub fn get_derivation_path(&self, destination: &Destination) -> Option<DerivationPath> {
+        self.key_chain.get_derivation_path(destination)
+    }
+
     pub fn get_all_standalone_addresses(&self) -> StandaloneAddresses {
         self.key_chain.get_all_standalone_addresses()
     }
 
+    /// Returns the issued receive addresses that appear in the outputs of more than one
+    /// transaction known to this account, together with the number of such transactions.
+    pub fn address_reuse_report(&self) -> Vec<(Address<Destination>, usize)> {
+        let receive_chain = self.key_chain.get_leaf_key_chain(KeyPurpose::ReceiveFunds);
+
+        let mut tx_count_per_address = BTreeMap::<ChildNumber, usize>::new();
+        for tx in self.output_cache.txs_with_unconfirmed().values() {
+            if tx.state().is_abandoned() {
+                continue;
+            }
+
+            let used_in_tx: BTreeSet<ChildNumber> = tx
+                .outputs()
+                .iter()
+                .flat_map(|txo| self.collect_output_destinations(txo))
+                .filter_map(|dest| receive_chain.get_child_num_from_destination(&dest))
+                .collect();
+
+            for child_number in used_in_tx {
+                *tx_count_per_address.entry(child_number).or_default() += 1;
+            }
+        }
+
+        let issued_addresses = receive_chain.get_all_issued_addresses();
+        tx_count_per_address
+            .into_iter()
+            .filter(|(_, count)| *count > 1)
+            .filter_map(|(child_number, count)| {
+                issued_addresses.get(&child_number).map(|address| (address.clone(), count))
+            })
+            .collect()
+    }
+
     pub fn get_all_standalone_address_details(
         &self,
         address: Destination,
@@ -1515,6 +1884,10 @@ impl Account {
         self.key_chain.get_addresses_usage_state()
     }
 
+    pub fn lookahead_size(&self) -> u32 {
+        self.key_chain.lookahead_size()
+    }
+
     fn collect_output_destinations(&self, txo: &TxOutput) -> Vec<Destination> {
         match txo {
             TxOutput::Transfer(_, d)
@@ -1565,7 +1938,8 @@ impl Account {
                 | Destination::AnyoneCanSpend
                 | Destination::ScriptHash(_) => false,
                 Destination::ClassicMultisig(_) => {
-                    self.key_chain.get_multisig_challenge(destination).is_some()
+                    !self.key_chain.is_multisig_destination_mine(destination)
+                        && self.key_chain.get_multisig_challenge(destination).is_some()
                 }
             })
     }
@@ -1581,7 +1955,10 @@ impl Account {
             Destination::PublicKeyHash(pkh) => self.key_chain.is_public_key_hash_mine(pkh),
             Destination::PublicKey(pk) => self.key_chain.is_public_key_mine(pk),
             Destination::AnyoneCanSpend => false,
-            Destination::ScriptHash(_) | Destination::ClassicMultisig(_) => false,
+            Destination::ScriptHash(_) => false,
+            Destination::ClassicMultisig(_) => {
+                self.key_chain.is_multisig_destination_mine(destination)
+            }
         }
     }
 
@@ -1736,10 +2113,41 @@ impl Account {
         )
     }
 
+    pub fn get_locked_utxos(
+        &self,
+        median_time: BlockTimestamp,
+        utxo_states: UtxoStates,
+    ) -> Vec<(UtxoOutPoint, (&TxOutput, Option<TokenId>), BlockTimestamp)> {
+        let current_block_info = BlockInfo {
+            height: self.account_info.best_block_height(),
+            timestamp: median_time,
+        };
+        self.output_cache.locked_utxos_with_unlock_times(
+            current_block_info,
+            utxo_states,
+            self.chain_config.target_block_spacing(),
+            |txo| self.is_mine(txo),
+        )
+    }
+
     pub fn get_transaction_list(&self, skip: usize, count: usize) -> WalletResult<TransactionList> {
         get_transaction_list(&self.key_chain, &self.output_cache, skip, count)
     }
 
+    /// Get up to `count` transactions that follow the cursor and the cursor of the next page,
+    /// if there is one
+    pub fn get_transaction_list_from_cursor(
+        &self,
+        cursor: Option<TxCursor>,
+        count: NonZeroUsize,
+    ) -> WalletResult<(Vec<TxData>, Option<TxCursor>)> {
+        get_transaction_list_from_cursor(&self.output_cache, cursor, count)
+    }
+
+    pub fn get_activity_summary(&self, from: BlockTimestamp) -> WalletResult<Vec<DailyActivity>> {
+        get_activity_summary(&self.key_chain, &self.output_cache, from)
+    }
+
     pub fn get_transaction(&self, transaction_id: Id<Transaction>) -> WalletResult<&TxData> {
         self.output_cache.get_transaction(transaction_id)
     }
@@ -1793,7 +2201,28 @@ impl Account {
         wallet_events: &impl WalletEvents,
         tx: WalletTx,
     ) -> WalletResult<bool> {
-        let relevant_inputs = tx.inputs().iter().any(|input| match input {
+        let relevant_inputs = tx.inputs().iter().any(|input| self.is_input_relevant(input));
+        let relevant_outputs = self.mark_outputs_as_seen(db_tx, tx.outputs())?;
+        if relevant_inputs || relevant_outputs {
+            let id = AccountWalletTxId::new(self.get_account_id(), tx.id());
+            db_tx.set_transaction(&id, &tx)?;
+            wallet_events.set_transaction(self.account_index(), &tx);
+            self.output_cache.add_tx(id.into_item_id(), tx)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Return true if the transaction spends or creates anything this account owns or watches,
+    /// i.e. if the account would store it when it's scanned
+    pub fn is_tx_relevant(&self, tx: &WalletTx) -> bool {
+        tx.inputs().iter().any(|input| self.is_input_relevant(input))
+            || tx.outputs().iter().any(|output| self.is_mine_or_watched(output))
+    }
+
+    fn is_input_relevant(&self, input: &TxInput) -> bool {
+        match input {
             TxInput::Utxo(outpoint) => self
                 .output_cache
                 .get_txo(outpoint)
@@ -1816,23 +2245,61 @@ impl Account {
                     self.find_token(token_id).is_ok()
                         || self.is_destination_mine_or_watched(address)
                 }
-                // TODO(orders)
-                AccountCommand::ConcludeOrder(_) => unimplemented!(),
-                AccountCommand::FillOrder(_, _, _) => unimplemented!(),
+                // TODO(orders): the wallet doesn't track orders yet
+                AccountCommand::ConcludeOrder(_) | AccountCommand::FillOrder(_, _, _) => false,
             },
-        });
-        let relevant_outputs = self.mark_outputs_as_seen(db_tx, tx.outputs())?;
-        if relevant_inputs || relevant_outputs {
-            let id = AccountWalletTxId::new(self.get_account_id(), tx.id());
-            db_tx.set_transaction(&id, &tx)?;
-            wallet_events.set_transaction(self.account_index(), &tx);
-            self.output_cache.add_tx(id.into_item_id(), tx)?;
-            Ok(true)
-        } else {
-            Ok(false)
         }
     }
 
+    /// Find the transactions and block rewards in the given blocks that would be recognised as
+    /// belonging to this account if the blocks were scanned, without changing the account state.
+    /// Unlike the real scan, the key lookahead is not extended while looking at the blocks.
+    pub fn simulate_scan(&self, blocks: &[Block]) -> Vec<OutPointSourceId> {
+        let mut recognised_outputs = BTreeSet::new();
+        let mut recognised_sources = Vec::new();
+
+        for block in blocks {
+            let reward_id = OutPointSourceId::from(block.get_id());
+            let own_reward_outputs = self.own_outputs(&reward_id, block.block_reward().outputs());
+
+            if !own_reward_outputs.is_empty() {
+                recognised_outputs.extend(own_reward_outputs);
+                recognised_sources.push(reward_id);
+            }
+
+            for signed_tx in block.transactions() {
+                let tx = signed_tx.transaction();
+                let tx_id = OutPointSourceId::from(tx.get_id());
+
+                let relevant_inputs = tx.inputs().iter().any(|input| match input {
+                    TxInput::Utxo(outpoint) => {
+                        recognised_outputs.contains(outpoint) || self.is_input_relevant(input)
+                    }
+                    TxInput::Account(_) | TxInput::AccountCommand(_, _) => {
+                        self.is_input_relevant(input)
+                    }
+                });
+                let own_outputs = self.own_outputs(&tx_id, tx.outputs());
+
+                if relevant_inputs || !own_outputs.is_empty() {
+                    recognised_outputs.extend(own_outputs);
+                    recognised_sources.push(tx_id);
+                }
+            }
+        }
+
+        recognised_sources
+    }
+
+    fn own_outputs(&self, source_id: &OutPointSourceId, outputs: &[TxOutput]) -> Vec<UtxoOutPoint> {
+        outputs
+            .iter()
+            .enumerate()
+            .filter(|(_, output)| self.is_mine_or_watched(output))
+            .map(|(idx, _)| UtxoOutPoint::new(source_id.clone(), idx as u32))
+            .collect()
+    }
+
     pub fn scan_genesis(
         &mut self,
         db_tx: &mut impl WalletStorageWriteLocked,
@@ -1880,18 +2347,32 @@ impl Account {
                 block.transactions().iter().enumerate().try_fold(
                     new_tx_was_added,
                     |mut new_tx_was_added, (idx, signed_tx)| {
+                        let tx_id = signed_tx.transaction().get_id();
+                        if self.output_cache.is_abandoned(&tx_id.into()) {
+                            logging::log::info!(
+                                "Abandoned transaction {} was confirmed at height {}",
+                                tx_id,
+                                block_height
+                            );
+                        }
+
                         let tx_state =
                             TxState::Confirmed(block_height, block.timestamp(), idx as u64);
                         let wallet_tx = WalletTx::Tx(TxData::new(signed_tx.clone(), tx_state));
                         self.update_conflicting_txs(&wallet_tx, block, db_tx)?;
 
-                        new_tx_was_added |= self
+                        let tx_was_added = self
                             .add_wallet_tx_if_relevant_and_remove_from_user_txs(
                                 db_tx,
                                 wallet_events,
                                 wallet_tx,
-                                signed_tx.transaction().get_id(),
+                                tx_id,
                             )?;
+                        if tx_was_added {
+                            self.notify_token_transfers(wallet_events, signed_tx.transaction());
+                        }
+
+                        new_tx_was_added |= tx_was_added;
                         Ok(new_tx_was_added)
                     },
                 )
@@ -1908,6 +2389,51 @@ impl Account {
         Ok(new_tx_was_added)
     }
 
+    /// Report the net amounts of tokens that the transaction moves into or out of this account
+    fn notify_token_transfers(&self, wallet_events: &impl WalletEvents, tx: &Transaction) {
+        fn add_token_amount(amounts: &mut BTreeMap<TokenId, Amount>, output: &TxOutput) {
+            match output {
+                TxOutput::Transfer(OutputValue::TokenV1(token_id, amount), _)
+                | TxOutput::LockThenTransfer(OutputValue::TokenV1(token_id, amount), _, _) => {
+                    let total = amounts.entry(*token_id).or_insert(Amount::ZERO);
+                    *total = (*total + *amount).expect("token amounts in a tx cannot overflow");
+                }
+                _ => {}
+            }
+        }
+
+        let mut received = BTreeMap::new();
+        for output in tx.outputs().iter().filter(|output| self.is_mine(output)) {
+            add_token_amount(&mut received, output);
+        }
+
+        let mut sent = BTreeMap::new();
+        for input in tx.inputs() {
+            if let Some(output) = input
+                .utxo_outpoint()
+                .and_then(|outpoint| self.output_cache.get_txo(outpoint))
+                .filter(|output| self.is_mine(output))
+            {
+                add_token_amount(&mut sent, output);
+            }
+        }
+
+        let tx_id = tx.get_id();
+        let token_ids: BTreeSet<TokenId> = received.keys().chain(sent.keys()).copied().collect();
+        for token_id in token_ids {
+            let received = received.get(&token_id).copied().unwrap_or(Amount::ZERO);
+            let sent = sent.get(&token_id).copied().unwrap_or(Amount::ZERO);
+
+            if received > sent {
+                let amount = (received - sent).expect("checked above");
+                wallet_events.on_token_received(token_id, amount, tx_id);
+            } else if sent > received {
+                let amount = (sent - received).expect("checked above");
+                wallet_events.on_token_sent(token_id, amount, tx_id);
+            }
+        }
+    }
+
     /// Check for any conflicting txs and update the new state in the DB
     fn update_conflicting_txs<B: storage::Backend>(
         &mut self,
@@ -2044,6 +2570,53 @@ impl Account {
         Ok(())
     }
 
+    /// Store a transaction that was created outside of this wallet.
+    /// If `block_info` is set, the transaction is recorded as confirmed in that block, otherwise
+    /// it's recorded as an inactive transaction that will be broadcast again.
+    pub fn import_external_transaction(
+        &mut self,
+        db_tx: &mut impl WalletStorageWriteLocked,
+        wallet_events: &impl WalletEvents,
+        signed_tx: SignedTransaction,
+        block_info: Option<BlockInfo>,
+    ) -> WalletResult<()> {
+        let account_id = self.get_account_id();
+        let tx_id = signed_tx.transaction().get_id();
+        ensure!(
+            self.output_cache.get_transaction(tx_id).is_err(),
+            WalletError::DuplicateTransaction(tx_id)
+        );
+
+        match block_info {
+            Some(block_info) => {
+                let tx_state = TxState::Confirmed(block_info.height, block_info.timestamp, 0);
+                let wallet_tx = WalletTx::Tx(TxData::new(signed_tx, tx_state));
+                ensure!(
+                    self.add_wallet_tx_if_relevant(db_tx, wallet_events, wallet_tx)?,
+                    WalletError::TransactionNotRelevant
+                );
+            }
+            None => {
+                let counter = db_tx
+                    .get_account_unconfirmed_tx_counter(&account_id)?
+                    .ok_or(WalletError::WalletNotInitialized)?
+                    + 1;
+                let tx_state = TxState::Inactive(counter);
+                let wallet_tx = WalletTx::Tx(TxData::new(signed_tx.clone(), tx_state));
+                ensure!(
+                    self.add_wallet_tx_if_relevant(db_tx, wallet_events, wallet_tx)?,
+                    WalletError::TransactionNotRelevant
+                );
+
+                let id = AccountPrefixedId::new(account_id.clone(), tx_id);
+                db_tx.set_user_transaction(&id, &signed_tx)?;
+                db_tx.set_account_unconfirmed_tx_counter(&account_id, counter)?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn best_block(&self) -> (Id<GenBlock>, BlockHeight) {
         (
             self.account_info.best_block_id(),
@@ -2297,5 +2870,23 @@ fn coin_and_token_output_change_fees(
     ))
 }
 
+/// The amount of coins locked in the output
+fn output_coin_amount(output: &TxOutput) -> Amount {
+    match output {
+        TxOutput::Transfer(value, _)
+        | TxOutput::LockThenTransfer(value, _, _)
+        | TxOutput::Burn(value)
+        | TxOutput::Htlc(value, _) => value.coin_amount().unwrap_or(Amount::ZERO),
+        TxOutput::CreateStakePool(_, data) => data.pledge(),
+        TxOutput::DelegateStaking(amount, _) => *amount,
+        TxOutput::AnyoneCanTake(data) => data.give().coin_amount().unwrap_or(Amount::ZERO),
+        TxOutput::ProduceBlockFromStake(_, _)
+        | TxOutput::CreateDelegationId(_, _)
+        | TxOutput::IssueFungibleToken(_)
+        | TxOutput::IssueNft(_, _, _)
+        | TxOutput::DataDeposit(_) => Amount::ZERO,
+    }
+}
+
 #[cfg(test)]
 mod tests;
@@ -17,6 +17,7 @@ use std::{
     cmp::Reverse,
     collections::{btree_map::Entry, BTreeMap, BTreeSet},
     ops::Add,
+    time::Duration,
 };
 
 use common::{
@@ -24,6 +25,7 @@ use common::{
         block::timestamp::BlockTimestamp,
         output_value::OutputValue,
         stakelock::StakePoolData,
+        timelock::OutputTimeLock,
         tokens::{
             is_token_or_nft_issuance, make_token_id, IsTokenFreezable, IsTokenUnfreezable,
             RPCFungibleTokenInfo, RPCIsTokenFrozen, RPCTokenTotalSupply, TokenId, TokenIssuance,
@@ -168,7 +170,10 @@ impl TokenCurrentSupplyState {
 
     pub fn check_can_mint(&self, amount: Amount) -> WalletResult<()> {
         match self {
-            Self::Unlimited(_) | Self::Lockable(_) => Ok(()),
+            Self::Unlimited(current) | Self::Lockable(current) => {
+                current.add(amount).ok_or(WalletError::OutputAmountOverflow)?;
+                Ok(())
+            }
             Self::Fixed(max, current) => {
                 let changed = current.add(amount).ok_or(WalletError::OutputAmountOverflow)?;
                 ensure!(
@@ -590,6 +595,10 @@ impl OutputCache {
         self.delegations.get(delegation_id)
     }
 
+    pub fn token_ids(&self) -> impl Iterator<Item = (&TokenId, &TokenIssuanceData)> {
+        self.token_issuance.iter()
+    }
+
     pub fn token_data(&self, token_id: &TokenId) -> Option<&TokenIssuanceData> {
         self.token_issuance.get(token_id)
     }
@@ -743,6 +752,9 @@ impl OutputCache {
     }
 
     pub fn add_tx(&mut self, tx_id: OutPointSourceId, tx: WalletTx) -> WalletResult<()> {
+        // An abandoned tx is treated as not present, so if it reappears (e.g. someone else
+        // broadcast it and it got confirmed) all the state rolled back by abandoning it is
+        // applied again and it is counted exactly once.
         let already_present = self.txs.get(&tx_id).map_or(false, |tx| !tx.state().is_abandoned());
         let is_unconfirmed = match tx.state() {
             TxState::Inactive(_)
@@ -1197,6 +1209,35 @@ impl OutputCache {
             .collect()
     }
 
+    /// Utxos that are still locked by a timelock, together with the time at which they unlock
+    pub fn locked_utxos_with_unlock_times<F: Fn(&TxOutput) -> bool>(
+        &self,
+        current_block_info: BlockInfo,
+        utxo_states: UtxoStates,
+        target_block_spacing: Duration,
+        output_filter: F,
+    ) -> Vec<(UtxoOutPoint, (&TxOutput, Option<TokenId>), BlockTimestamp)> {
+        self.utxos_with_token_ids(
+            current_block_info,
+            utxo_states,
+            WithLocked::Locked,
+            output_filter,
+        )
+        .into_iter()
+        .filter_map(|(outpoint, (output, token_id))| {
+            let timelock = output.timelock()?;
+            let tx_block_info = self.txs.get(&outpoint.source_id()).and_then(get_block_info);
+            let unlock_time = unlock_time(
+                timelock,
+                current_block_info,
+                tx_block_info,
+                target_block_spacing,
+            );
+            Some((outpoint, (output, token_id), unlock_time))
+        })
+        .collect()
+    }
+
     pub fn pending_transactions(&self) -> Vec<WithId<&Transaction>> {
         self.txs
             .values()
@@ -1348,6 +1389,10 @@ impl OutputCache {
         Ok(all_abandoned)
     }
 
+    pub fn is_abandoned(&self, tx_id: &OutPointSourceId) -> bool {
+        self.txs.get(tx_id).map_or(false, |tx| tx.state().is_abandoned())
+    }
+
     pub fn get_transaction(&self, transaction_id: Id<Transaction>) -> WalletResult<&TxData> {
         match self.txs.get(&transaction_id.into()) {
             None | Some(WalletTx::Block(_)) => Err(WalletError::NoTransactionFound(transaction_id)),
@@ -1497,6 +1542,37 @@ fn valid_timelock(
     })
 }
 
+/// The time at which the timelock expires.
+/// For the height based timelocks it is estimated from the target block spacing, and the
+/// timelocks of unconfirmed transactions are counted from the current block.
+fn unlock_time(
+    timelock: &OutputTimeLock,
+    current_block_info: BlockInfo,
+    tx_block_info: Option<BlockInfo>,
+    target_block_spacing: Duration,
+) -> BlockTimestamp {
+    let source_block_info = tx_block_info.unwrap_or(current_block_info);
+    let add_seconds = |timestamp: BlockTimestamp, seconds: u64| {
+        BlockTimestamp::from_int_seconds(timestamp.as_int_seconds().saturating_add(seconds))
+    };
+    let time_at_height = |height: BlockHeight| {
+        let blocks_left = height.into_int().saturating_sub(current_block_info.height.into_int());
+        add_seconds(
+            current_block_info.timestamp,
+            target_block_spacing.as_secs().saturating_mul(blocks_left),
+        )
+    };
+
+    match timelock {
+        OutputTimeLock::UntilHeight(height) => time_at_height(*height),
+        OutputTimeLock::UntilTime(timestamp) => *timestamp,
+        OutputTimeLock::ForBlockCount(block_count) => time_at_height(BlockHeight::new(
+            source_block_info.height.into_int().saturating_add(*block_count),
+        )),
+        OutputTimeLock::ForSeconds(seconds) => add_seconds(source_block_info.timestamp, *seconds),
+    }
+}
+
 /// Check Tx is in the selected state Confirmed/Inactive/Abandoned...
 fn is_in_state(tx: &WalletTx, utxo_states: UtxoStates) -> bool {
     utxo_states.contains(get_utxo_state(&tx.state()))
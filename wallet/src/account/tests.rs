@@ -15,8 +15,11 @@
 
 use super::*;
 use crate::key_chain::{MasterKeyChain, LOOKAHEAD_SIZE};
-use common::chain::config::create_regtest;
+use common::chain::{config::create_regtest, OutPointSourceId};
 use crypto::key::hdkd::child_number::ChildNumber;
+use randomness::Rng;
+use rstest::rstest;
+use test_utils::random::{make_seedable_rng, Seed};
 use wallet_storage::{DefaultBackend, Store, TransactionRwUnlocked, Transactional};
 use wallet_types::account_info::DEFAULT_ACCOUNT_INDEX;
 use wallet_types::seed_phrase::StoreSeedPhrase;
@@ -102,3 +105,87 @@ fn account_addresses_lookahead() {
         Some(expected_last_derived)
     );
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn transaction_list_from_cursor(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let config = Arc::new(create_regtest());
+    let db = Arc::new(Store::new(DefaultBackend::new_in_memory()).unwrap());
+    let mut db_tx = db.transaction_rw_unlocked(None).unwrap();
+
+    let master_key_chain = MasterKeyChain::new_from_mnemonic(
+        config.clone(),
+        &mut db_tx,
+        MNEMONIC,
+        None,
+        StoreSeedPhrase::DoNotStore,
+    )
+    .unwrap();
+
+    let key_chain = master_key_chain
+        .create_account_key_chain(&mut db_tx, DEFAULT_ACCOUNT_INDEX, LOOKAHEAD_SIZE)
+        .unwrap();
+    let mut account = Account::new(config, &mut db_tx, key_chain, None).unwrap();
+
+    // Many transactions share a block, some are unconfirmed
+    let tx_count = 500;
+    let mut tx_ids = BTreeSet::new();
+    for i in 0..tx_count {
+        let tx = Transaction::new(
+            0,
+            vec![],
+            vec![TxOutput::Burn(OutputValue::Coin(Amount::from_atoms(i)))],
+        )
+        .unwrap();
+        let tx_id = tx.get_id();
+        let state = if rng.gen_bool(0.1) {
+            TxState::InMempool(i as u64)
+        } else {
+            TxState::Confirmed(
+                BlockHeight::new(rng.gen_range(1..50)),
+                BlockTimestamp::from_int_seconds(rng.gen()),
+                i as u64,
+            )
+        };
+        let tx_data = TxData::new(SignedTransaction::new(tx, vec![]).unwrap(), state);
+        account
+            .output_cache
+            .add_tx(OutPointSourceId::Transaction(tx_id), WalletTx::Tx(tx_data))
+            .unwrap();
+        tx_ids.insert(tx_id);
+    }
+
+    let page_size = NonZeroUsize::new(rng.gen_range(1..=100)).unwrap();
+    let mut listed_tx_ids = Vec::new();
+    let mut cursor = None;
+    loop {
+        let (txs, next_cursor) =
+            account.get_transaction_list_from_cursor(cursor, page_size).unwrap();
+        assert!(txs.len() <= page_size.get());
+        listed_tx_ids.extend(txs.iter().map(|tx| tx.get_transaction().get_id()));
+
+        match next_cursor {
+            Some(next_cursor) => {
+                assert_eq!(txs.len(), page_size.get());
+                cursor = Some(next_cursor);
+            }
+            None => break,
+        }
+    }
+
+    // Every transaction is listed exactly once
+    assert_eq!(listed_tx_ids.len(), tx_ids.len());
+    assert_eq!(
+        listed_tx_ids.iter().copied().collect::<BTreeSet<_>>(),
+        tx_ids
+    );
+
+    // In the same order as in the list that is paginated by skipping transactions
+    let full_list = account.get_transaction_list(0, tx_count as usize).unwrap();
+    assert_eq!(
+        listed_tx_ids,
+        full_list.txs.iter().map(|tx| tx.txid).collect::<Vec<_>>()
+    );
+}
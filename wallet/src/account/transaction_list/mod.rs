@@ -13,12 +13,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{cmp::Ordering, ops::Add};
+use std::{cmp::Ordering, collections::BTreeMap, num::NonZeroUsize, ops::Add};
 
 use common::{
     chain::{block::timestamp::BlockTimestamp, Transaction, TxInput, TxOutput},
     primitives::{Amount, BlockHeight, Id, Idable},
 };
+use rpc_description::HasValueHint;
 use wallet_types::{
     wallet_tx::{TxData, TxState},
     KeyPurpose, WalletTx,
@@ -93,14 +94,58 @@ pub struct TransactionList {
     pub txs: Vec<TransactionInfo>,
 }
 
+/// Position of a transaction in the transaction list.
+///
+/// Transactions are listed in the same order as in [`TransactionList`]: the unconfirmed ones go
+/// first, then the confirmed ones starting from the most recent block. Transactions with the same
+/// height are ordered by their ids.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub struct TxCursor {
+    /// The height of the block the transaction is confirmed in, none if it's unconfirmed
+    pub block_height: Option<BlockHeight>,
+    pub tx_id: Id<Transaction>,
+}
+
+impl TxCursor {
+    fn from_tx_data(tx_data: &TxData) -> Self {
+        Self {
+            block_height: tx_data.state().block_height(),
+            tx_id: tx_data.get_transaction().get_id(),
+        }
+    }
+}
+
+impl Ord for TxCursor {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_block_heights(self.block_height, other.block_height)
+            .then_with(|| self.tx_id.cmp(&other.tx_id))
+    }
+}
+
+impl PartialOrd for TxCursor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The number of transactions and the net change of the coin balance of an account in a day
+#[derive(Debug, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub struct DailyActivity {
+    /// The UTC date, in the YYYY-MM-DD format
+    pub date: String,
+    pub tx_count: u32,
+    /// Coins received minus coins spent by the account, in atoms
+    pub net_coin_flow: i64,
+}
+
 struct TxRef<'a> {
     block_height: Option<BlockHeight>,
     tx_data: &'a TxData,
 }
 
 // Most recent transactions go first
-fn compare_tx_ref(a: &TxRef, b: &TxRef) -> Ordering {
-    match (a.block_height, b.block_height) {
+fn compare_block_heights(a: Option<BlockHeight>, b: Option<BlockHeight>) -> Ordering {
+    match (a, b) {
         (None, None) => Ordering::Equal,
         (None, Some(_)) => Ordering::Less,
         (Some(_), None) => Ordering::Greater,
@@ -108,6 +153,10 @@ fn compare_tx_ref(a: &TxRef, b: &TxRef) -> Ordering {
     }
 }
 
+fn compare_tx_ref(a: &TxRef, b: &TxRef) -> Ordering {
+    compare_block_heights(a.block_height, b.block_height)
+}
+
 fn own_output(key_chain: &AccountKeyChainImpl, output: &TxOutput) -> bool {
     match output {
         TxOutput::Transfer(_, dest) | TxOutput::LockThenTransfer(_, dest, _) => KeyPurpose::ALL
@@ -248,3 +297,138 @@ pub fn get_transaction_list(
         txs,
     })
 }
+
+/// Get up to `count` transactions that follow the cursor, or the first ones if there is no cursor,
+/// along with the cursor of the next page if there are more transactions.
+///
+/// Unlike [`get_transaction_list`], which sorts all the transactions of the account, only the
+/// transactions of the requested page are kept while looking for them.
+pub fn get_transaction_list_from_cursor(
+    output_cache: &OutputCache,
+    cursor: Option<TxCursor>,
+    count: NonZeroUsize,
+) -> WalletResult<(Vec<TxData>, Option<TxCursor>)> {
+    let count = count.get();
+    // One extra transaction is kept to know whether there is a next page
+    let mut page = BTreeMap::new();
+    let txs =
+        output_cache
+            .txs_with_unconfirmed()
+            .values()
+            .filter_map(|wallet_tx| match wallet_tx {
+                WalletTx::Block(_) => None,
+                WalletTx::Tx(tx_data) => Some(tx_data),
+            });
+    for tx_data in txs {
+        let position = TxCursor::from_tx_data(tx_data);
+        if cursor.is_some_and(|cursor| position <= cursor) {
+            continue;
+        }
+
+        page.insert(position, tx_data);
+        if page.len() > count.saturating_add(1) {
+            page.pop_last();
+        }
+    }
+
+    let next_cursor = if page.len() > count {
+        page.pop_last();
+        page.last_key_value().map(|(position, _)| *position)
+    } else {
+        None
+    };
+    let txs = page.into_values().cloned().collect();
+
+    Ok((txs, next_cursor))
+}
+
+fn coin_amount(output: &TxOutput) -> Amount {
+    match output {
+        TxOutput::Transfer(value, _) | TxOutput::LockThenTransfer(value, _, _) => {
+            value.coin_amount().unwrap_or(Amount::ZERO)
+        }
+        TxOutput::Htlc(_, _)
+        | TxOutput::Burn(_)
+        | TxOutput::CreateStakePool(_, _)
+        | TxOutput::ProduceBlockFromStake(_, _)
+        | TxOutput::CreateDelegationId(_, _)
+        | TxOutput::DelegateStaking(_, _)
+        | TxOutput::IssueFungibleToken(_)
+        | TxOutput::IssueNft(_, _, _)
+        | TxOutput::DataDeposit(_)
+        | TxOutput::AnyoneCanTake(_) => Amount::ZERO,
+    }
+}
+
+/// Net change of the account's coin balance caused by the transaction
+fn net_coin_flow(
+    key_chain: &AccountKeyChainImpl,
+    output_cache: &OutputCache,
+    tx_data: &TxData,
+) -> WalletResult<i128> {
+    let sum = |outputs: &mut dyn Iterator<Item = &TxOutput>| {
+        outputs
+            .map(coin_amount)
+            .sum::<Option<Amount>>()
+            .ok_or(WalletError::OutputAmountOverflow)
+    };
+
+    let credit = sum(&mut tx_data
+        .get_transaction()
+        .outputs()
+        .iter()
+        .filter(|output| own_output(key_chain, output)))?;
+    let debit = sum(&mut tx_data
+        .get_transaction()
+        .inputs()
+        .iter()
+        .filter_map(|input| own_input(key_chain, output_cache, input)))?;
+
+    Ok(credit.into_atoms() as i128 - debit.into_atoms() as i128)
+}
+
+/// Group the confirmed transactions with timestamps not earlier than `from` by their UTC date
+pub fn get_activity_summary(
+    key_chain: &AccountKeyChainImpl,
+    output_cache: &OutputCache,
+    from: BlockTimestamp,
+) -> WalletResult<Vec<DailyActivity>> {
+    let mut days = BTreeMap::new();
+
+    for wallet_tx in output_cache.txs_with_unconfirmed().values() {
+        let tx_data = match wallet_tx {
+            WalletTx::Block(_) => continue,
+            WalletTx::Tx(tx_data) => tx_data,
+        };
+        let timestamp = match tx_data.state() {
+            TxState::Confirmed(_, timestamp, _) if *timestamp >= from => *timestamp,
+            TxState::Confirmed(_, _, _)
+            | TxState::Inactive(_)
+            | TxState::Conflicted(_)
+            | TxState::InMempool(_)
+            | TxState::Abandoned => continue,
+        };
+        let date = timestamp
+            .into_time()
+            .as_absolute_time()
+            .ok_or(WalletError::InvalidBlockTimestamp(timestamp))?
+            .date_naive();
+
+        let flow = net_coin_flow(key_chain, output_cache, tx_data)?;
+        let (tx_count, net_coin_flow) = days.entry(date).or_insert((0u32, 0i128));
+        *tx_count += 1;
+        *net_coin_flow += flow;
+    }
+
+    days.into_iter()
+        .map(|(date, (tx_count, net_coin_flow))| {
+            Ok(DailyActivity {
+                date: date.to_string(),
+                tx_count,
+                net_coin_flow: net_coin_flow
+                    .try_into()
+                    .map_err(|_| WalletError::OutputAmountOverflow)?,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,141 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+
+use common::chain::{
+    partially_signed_transaction::PartiallySignedTransaction, Transaction, TxInput, TxOutput,
+    UtxoOutPoint,
+};
+use serialization::{Decode, Encode};
+use utils::ensure;
+
+use crate::{WalletError, WalletResult};
+
+/// The part of a CoinJoin transaction contributed by a single participant.
+///
+/// Each participant publishes its proposal to the others (or to a coordinator), after which
+/// all the proposals are merged into one transaction that every participant signs.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct CoinJoinProposal {
+    /// The UTXOs spent by the participant
+    pub utxos: Vec<UtxoOutPoint>,
+    /// The outputs requested by the participant, including the change
+    pub outputs: Vec<TxOutput>,
+    /// The participant's part of the transaction, carrying the information needed to sign its inputs
+    pub partial_signature_template: PartiallySignedTransaction,
+}
+
+impl CoinJoinProposal {
+    pub fn new(ptx: PartiallySignedTransaction) -> WalletResult<Self> {
+        let utxos = ptx
+            .tx()
+            .inputs()
+            .iter()
+            .map(|input| match input {
+                TxInput::Utxo(outpoint) => Ok(outpoint.clone()),
+                TxInput::Account(_) | TxInput::AccountCommand(_, _) => {
+                    Err(WalletError::InvalidCoinJoinProposal)
+                }
+            })
+            .collect::<WalletResult<Vec<_>>>()?;
+        let outputs = ptx.tx().outputs().to_vec();
+
+        Ok(Self {
+            utxos,
+            outputs,
+            partial_signature_template: ptx,
+        })
+    }
+
+    /// Check that the published inputs and outputs are the ones in the template
+    fn check_consistency(&self) -> WalletResult<()> {
+        let tx = self.partial_signature_template.tx();
+
+        ensure!(
+            tx.inputs().len() == self.utxos.len()
+                && tx.inputs().iter().zip(&self.utxos).all(|(input, utxo)| match input {
+                    TxInput::Utxo(outpoint) => outpoint == utxo,
+                    TxInput::Account(_) | TxInput::AccountCommand(_, _) => false,
+                }),
+            WalletError::InvalidCoinJoinProposal
+        );
+        ensure!(
+            tx.outputs() == self.outputs.as_slice(),
+            WalletError::InvalidCoinJoinProposal
+        );
+
+        Ok(())
+    }
+}
+
+/// Merge the proposals of all the participants into a single transaction.
+///
+/// The inputs and outputs are put in a canonical order, so that every participant
+/// ends up with the same transaction regardless of the order of the proposals.
+pub fn merge_coinjoin_proposals(
+    proposals: impl IntoIterator<Item = CoinJoinProposal>,
+) -> WalletResult<PartiallySignedTransaction> {
+    let mut seen_utxos = BTreeSet::new();
+    let mut inputs = Vec::new();
+    let mut outputs = Vec::new();
+
+    for proposal in proposals {
+        proposal.check_consistency()?;
+
+        let ptx = proposal.partial_signature_template;
+        for (idx, utxo) in proposal.utxos.into_iter().enumerate() {
+            ensure!(
+                seen_utxos.insert(utxo.clone()),
+                WalletError::CoinJoinDuplicateInput(utxo)
+            );
+
+            inputs.push((
+                utxo,
+                ptx.input_utxos()[idx].clone(),
+                ptx.destinations()[idx].clone(),
+                ptx.htlc_secrets()[idx].clone(),
+            ));
+        }
+        outputs.extend(proposal.outputs);
+    }
+
+    inputs.sort_by(|a, b| a.0.cmp(&b.0));
+    outputs.sort_by_key(|output| output.encode());
+
+    let mut tx_inputs = Vec::with_capacity(inputs.len());
+    let mut input_utxos = Vec::with_capacity(inputs.len());
+    let mut destinations = Vec::with_capacity(inputs.len());
+    let mut htlc_secrets = Vec::with_capacity(inputs.len());
+    for (utxo, input_utxo, destination, htlc_secret) in inputs {
+        tx_inputs.push(TxInput::Utxo(utxo));
+        input_utxos.push(input_utxo);
+        destinations.push(destination);
+        htlc_secrets.push(htlc_secret);
+    }
+
+    // any signatures made over the individual proposals are not valid for the joint transaction
+    let witnesses = vec![None; tx_inputs.len()];
+    let tx = Transaction::new(0, tx_inputs, outputs)?;
+    let ptx = PartiallySignedTransaction::new(
+        tx,
+        witnesses,
+        input_utxos,
+        destinations,
+        Some(htlc_secrets),
+    )?;
+
+    Ok(ptx)
+}
@@ -71,6 +71,9 @@ pub struct AccountKeyChainImpl {
     /// Standalone private keys added by the user not derived from this account's chain
     standalone_private_keys: BTreeMap<Destination, (Option<String>, AccountPublicKey)>,
 
+    /// The classic multisig challenge of a multisig account and its destination
+    multisig_challenge: Option<(Destination, ClassicMultisigChallenge)>,
+
     /// The number of unused addresses that need to be checked after the last used address
     lookahead_size: ConstValue<u32>,
 }
@@ -148,6 +151,7 @@ impl AccountKeyChainImpl {
             standalone_watch_only_keys: BTreeMap::new(),
             standalone_multisig_keys: BTreeMap::new(),
             standalone_private_keys: BTreeMap::new(),
+            multisig_challenge: None,
             lookahead_size: lookahead_size.into(),
         };
 
@@ -230,6 +234,13 @@ impl AccountKeyChainImpl {
             })
             .collect();
 
+        let multisig_challenge = db_tx
+            .get_account_multisig_challenge(&AccountId::new_from_xpub(account_info.account_key()))?
+            .map(|challenge| {
+                let destination = Destination::ClassicMultisig((&challenge).into());
+                (destination, challenge)
+            });
+
         Ok(AccountKeyChainImpl {
             chain_config,
             account_index: account_info.account_index(),
@@ -240,6 +251,7 @@ impl AccountKeyChainImpl {
             standalone_watch_only_keys,
             standalone_multisig_keys,
             standalone_private_keys,
+            multisig_challenge,
             lookahead_size: account_info.lookahead_size().into(),
         })
     }
@@ -270,6 +282,14 @@ impl AccountKeyChainImpl {
         Ok((index, address))
     }
 
+    /// Return the address that `next_unused_address` would return, without changing the key chain
+    pub fn peek_next_unused_address(
+        &self,
+        purpose: KeyPurpose,
+    ) -> KeyChainResult<(ChildNumber, Address<Destination>)> {
+        self.get_leaf_key_chain(purpose).peek_next_unused()
+    }
+
     /// Issue a new address that hasn't been used before
     pub fn issue_address(
         &mut self,
@@ -303,6 +323,16 @@ impl AccountKeyChainImpl {
         self.vrf_chain.issue_new(db_tx, lookahead_size)
     }
 
+    /// Issue `count` new derived vrf keys that haven't been used before
+    pub fn issue_vrf_keys(
+        &mut self,
+        db_tx: &mut impl WalletStorageWriteLocked,
+        count: u32,
+    ) -> KeyChainResult<Vec<(ChildNumber, ExtendedVRFPublicKey)>> {
+        let lookahead_size = self.lookahead_size();
+        self.vrf_chain.issue_new_keys(db_tx, lookahead_size, count)
+    }
+
     /// Reload the sub chain keys from DB to restore the cache
     /// Should be called after issuing a new key but not using committing it to the DB
     pub fn reload_keys(&mut self, db_tx: &impl WalletStorageReadLocked) -> KeyChainResult<()> {
@@ -390,9 +420,56 @@ impl AccountKeyChainImpl {
         &self,
         destination: &Destination,
     ) -> Option<&ClassicMultisigChallenge> {
-        self.standalone_multisig_keys
-            .get(destination)
-            .map(|multisig| &multisig.challenge)
+        self.multisig_challenge
+            .as_ref()
+            .filter(|(multisig_destination, _)| multisig_destination == destination)
+            .map(|(_, challenge)| challenge)
+            .or_else(|| {
+                self.standalone_multisig_keys
+                    .get(destination)
+                    .map(|multisig| &multisig.challenge)
+            })
+    }
+
+    /// The public key this account contributes to a multisig account's challenge, which is the
+    /// first key of the receiving key chain
+    pub fn multisig_public_key(&self) -> KeyChainResult<PublicKey> {
+        self.get_leaf_key_chain(KeyPurpose::ReceiveFunds)
+            .get_derived_xpub(ChildNumber::ZERO)
+            .map(|xpub| xpub.clone().into_public_key())
+            .ok_or(KeyChainError::LookAheadExceeded)
+    }
+
+    /// Make this a multisig account for the given challenge, the challenge must contain the
+    /// account's multisig public key
+    pub fn set_multisig_challenge(
+        &mut self,
+        db_tx: &mut impl WalletStorageWriteLocked,
+        challenge: ClassicMultisigChallenge,
+    ) -> KeyChainResult<()> {
+        let public_key = self.multisig_public_key()?;
+        if !challenge.public_keys().contains(&public_key) {
+            return Err(KeyChainError::MultisigPublicKeyNotInChallenge);
+        }
+
+        db_tx.set_account_multisig_challenge(&self.get_account_id(), &challenge)?;
+        self.mark_public_key_as_used(db_tx, &public_key)?;
+        let destination = Destination::ClassicMultisig((&challenge).into());
+        self.multisig_challenge = Some((destination, challenge));
+
+        Ok(())
+    }
+
+    /// The destination and challenge of a multisig account
+    pub fn multisig_challenge(&self) -> Option<&(Destination, ClassicMultisigChallenge)> {
+        self.multisig_challenge.as_ref()
+    }
+
+    // Return true if the provided destination is the multisig of a multisig account
+    pub fn is_multisig_destination_mine(&self, destination: &Destination) -> bool {
+        self.multisig_challenge
+            .as_ref()
+            .is_some_and(|(multisig_destination, _)| multisig_destination == destination)
     }
 
     pub fn get_private_key_for_path(
@@ -641,6 +718,18 @@ impl AccountKeyChainImpl {
         Ok(false)
     }
 
+    /// Returns the full derivation path of the key behind the destination, if it belongs to
+    /// one of the account's HD key chains
+    pub fn get_derivation_path(&self, destination: &Destination) -> Option<DerivationPath> {
+        KeyPurpose::ALL.into_iter().find_map(|purpose| {
+            let leaf_key = self.get_leaf_key_chain(purpose);
+            leaf_key
+                .get_child_num_from_destination(destination)
+                .and_then(|child_num| leaf_key.get_derived_xpub(child_num))
+                .map(|xpub| xpub.get_derivation_path().clone())
+        })
+    }
+
     pub fn get_all_issued_addresses(&self) -> BTreeMap<ChildNumber, Address<Destination>> {
         self.get_leaf_key_chain(KeyPurpose::ReceiveFunds).get_all_issued_addresses()
     }
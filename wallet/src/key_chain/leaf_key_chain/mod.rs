@@ -218,6 +218,26 @@ impl LeafKeySoftChain {
         Ok((index, key, address))
     }
 
+    /// Return the address that `next_unused` would return, without deriving or issuing anything
+    pub fn peek_next_unused(&self) -> KeyChainResult<(ChildNumber, Address<Destination>)> {
+        let next_index = match self.last_used() {
+            None => U31::ZERO,
+            Some(last_used) => last_used.plus_one()?,
+        };
+        let index = ChildNumber::from_normal(next_index);
+
+        if let Some(address) = self.addresses.get(&index) {
+            return Ok((index, address.clone()));
+        }
+
+        let public_key = self.derive_key(next_index)?.into_public_key();
+        let address = Address::new(
+            &self.chain_config,
+            Destination::PublicKeyHash(PublicKeyHash::from(&public_key)),
+        )?;
+        Ok((index, address))
+    }
+
     /// Issue a new key
     pub fn issue_new(
         &mut self,
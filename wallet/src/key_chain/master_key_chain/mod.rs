@@ -14,9 +14,12 @@
 // limitations under the License.
 
 use crate::key_chain::account_key_chain::AccountKeyChainImpl;
-use crate::key_chain::{KeyChainError, KeyChainResult, DEFAULT_KEY_KIND};
-use common::chain::ChainConfig;
+use crate::key_chain::{make_account_path, KeyChainError, KeyChainResult, DEFAULT_KEY_KIND};
+use common::address::pubkeyhash::PublicKeyHash;
+use common::address::Address;
+use common::chain::{ChainConfig, Destination};
 use crypto::key::extended::ExtendedPrivateKey;
+use crypto::key::hdkd::child_number::ChildNumber;
 use crypto::key::hdkd::derivable::Derivable;
 use crypto::key::hdkd::u31::U31;
 use crypto::vrf::ExtendedVRFPrivateKey;
@@ -25,6 +28,7 @@ use wallet_storage::{
     StoreTxRwUnlocked, WalletStorageReadLocked, WalletStorageReadUnlocked,
     WalletStorageWriteUnlocked,
 };
+use wallet_types::keys::KeyPurpose;
 use wallet_types::seed_phrase::{SerializableSeedPhrase, StoreSeedPhrase};
 
 use super::DEFAULT_VRF_KEY_KIND;
@@ -59,6 +63,28 @@ impl MasterKeyChain {
         ))
     }
 
+    /// Derive the first receiving address of an account from a mnemonic and passphrase,
+    /// without storing anything
+    pub fn first_receiving_address(
+        chain_config: &ChainConfig,
+        mnemonic_str: &str,
+        passphrase: Option<&str>,
+        account_index: U31,
+    ) -> KeyChainResult<Address<Destination>> {
+        let (root_key, _, _) = Self::mnemonic_to_root_key(mnemonic_str, passphrase)?;
+        let public_key = root_key
+            .derive_absolute_path(&make_account_path(chain_config, account_index))?
+            .to_public_key()
+            .derive_child(KeyPurpose::ReceiveFunds.get_deterministic_index())?
+            .derive_child(ChildNumber::from_normal(U31::ZERO))?
+            .into_public_key();
+        let address = Address::new(
+            chain_config,
+            Destination::PublicKeyHash(PublicKeyHash::from(&public_key)),
+        )?;
+        Ok(address)
+    }
+
     pub fn new_from_mnemonic<B: storage::Backend>(
         chain_config: Arc<ChainConfig>,
         db_tx: &mut StoreTxRwUnlocked<B>,
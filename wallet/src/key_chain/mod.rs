@@ -103,6 +103,8 @@ pub enum KeyChainError {
     NoVRFPrivateKeyFound,
     #[error("No standalone address found for: {0}")]
     NoStandaloneAddressFound(RpcAddress<Destination>),
+    #[error("The multisig challenge does not contain the account's multisig public key")]
+    MultisigPublicKeyNotInChallenge,
     #[error("Standalone address already exists: {0}")]
     StandaloneAddressAlreadyExists(RpcAddress<Destination>),
 }
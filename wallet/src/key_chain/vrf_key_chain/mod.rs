@@ -165,6 +165,29 @@ impl VrfKeySoftChain {
         Ok((index, key))
     }
 
+    /// Issue `count` new keys. The lookahead is checked for the last of them before any key is
+    /// issued, so either all the keys are issued or none.
+    pub fn issue_new_keys(
+        &mut self,
+        db_tx: &mut impl WalletStorageWriteLocked,
+        lookahead_size: u32,
+        count: u32,
+    ) -> KeyChainResult<Vec<(ChildNumber, ExtendedVRFPublicKey)>> {
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let first_index = self.get_new_issued_index(lookahead_size)?;
+        let last_index = first_index
+            .into_u32()
+            .checked_add(count - 1)
+            .and_then(U31::from_u32)
+            .ok_or(KeyChainError::LookAheadExceeded)?;
+        self.check_issued_lookahead(last_index, lookahead_size)?;
+
+        (0..count).map(|_| self.issue_new(db_tx, lookahead_size)).collect()
+    }
+
     /// Persist the usage state to the database
     pub fn save_usage_state(
         &self,
@@ -14,6 +14,7 @@
 // limitations under the License.
 
 pub mod account;
+pub mod coinjoin;
 pub mod destination_getters;
 pub mod key_chain;
 pub mod send_request;
@@ -137,6 +137,9 @@ pub struct StakePoolDataArguments {
     pub margin_ratio_per_thousand: PerThousand,
     pub cost_per_block: Amount,
     pub decommission_key: Destination,
+    /// An externally held VRF public key to use for the pool instead of the one derived
+    /// from the account, e.g. for cold staking setups
+    pub vrf_public_key: Option<VRFPublicKey>,
 }
 
 pub fn make_stake_output(
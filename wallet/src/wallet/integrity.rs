@@ -0,0 +1,166 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Consistency checks of the wallet database, used to detect a database left in a
+//! partially-committed state, e.g. after a crash during a write.
+//!
+//! Only the wallet's own data is checked, the node isn't consulted. In particular, an account
+//! best block is only checked against the transactions it has confirmed, so a best block that
+//! went back without its transactions is detected, but one on a stale fork isn't.
+
+use std::collections::BTreeMap;
+
+use common::{
+    chain::{OutPointSourceId, TxInput, UtxoOutPoint},
+    primitives::BlockHeight,
+};
+use crypto::key::hdkd::u31::U31;
+use wallet_storage::WalletStorageReadLocked;
+use wallet_types::{KeyPurpose, TxState};
+
+use crate::account::Account;
+
+use super::{WalletResult, CURRENT_WALLET_VERSION};
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum IntegrityViolation {
+    #[error(
+        "Stored wallet version {0} does not match the current version {CURRENT_WALLET_VERSION}"
+    )]
+    WalletVersionMismatch(u32),
+    #[error(
+        "Transaction {tx_id:?} of account {account_index} spends a nonexistent output {outpoint:?}"
+    )]
+    MissingSpentOutput {
+        account_index: U31,
+        tx_id: OutPointSourceId,
+        outpoint: UtxoOutPoint,
+    },
+    #[error("Transaction {tx_id:?} of account {account_index} is confirmed at height {tx_height}, above the account best block height {best_block_height}")]
+    TransactionAboveBestBlock {
+        account_index: U31,
+        tx_id: OutPointSourceId,
+        tx_height: BlockHeight,
+        best_block_height: BlockHeight,
+    },
+    #[error("Last used {purpose:?} key index {last_used} of account {account_index} is above the last issued index {last_issued:?}")]
+    KeyUsageAboveIssued {
+        account_index: U31,
+        purpose: KeyPurpose,
+        last_used: U31,
+        last_issued: Option<U31>,
+    },
+    #[error("Transaction {tx_id:?} of account {account_index} uses the unconfirmed transaction counter {tx_counter}, above the account counter {account_counter:?}")]
+    UnconfirmedTxCounterBelowUsed {
+        account_index: U31,
+        tx_id: OutPointSourceId,
+        tx_counter: u64,
+        account_counter: Option<u64>,
+    },
+}
+
+/// Check that the data stored in the wallet database is consistent, returning all the
+/// violations found.
+///
+/// The loaded accounts are used to tell whether a transaction spending an output that isn't in
+/// the wallet belongs there for another reason, e.g. because it pays to the account.
+pub fn check_integrity(
+    db_tx: &impl WalletStorageReadLocked,
+    accounts: &BTreeMap<U31, Account>,
+) -> WalletResult<Vec<IntegrityViolation>> {
+    let mut violations = Vec::new();
+
+    let version = db_tx.get_storage_version()?;
+    if version != CURRENT_WALLET_VERSION {
+        violations.push(IntegrityViolation::WalletVersionMismatch(version));
+    }
+
+    for (account_id, account_info) in db_tx.get_accounts_info()? {
+        let account_index = account_info.account_index();
+        let best_block_height = account_info.best_block_height();
+        let account = accounts.get(&account_index);
+        let unconfirmed_tx_counter = db_tx.get_account_unconfirmed_tx_counter(&account_id)?;
+        let txs = db_tx.get_transactions(&account_id)?;
+
+        let outputs_count = txs
+            .iter()
+            .map(|(_, tx)| (tx.id(), tx.outputs().len()))
+            .collect::<BTreeMap<_, _>>();
+
+        for (_, tx) in &txs {
+            // A transaction is only stored if it's relevant to the account, so if it's not
+            // relevant without the outputs it spends that are missing, they were lost
+            let is_relevant = account.map_or(true, |account| account.is_tx_relevant(tx));
+
+            for input in tx.inputs() {
+                let outpoint = match input {
+                    TxInput::Utxo(outpoint) => outpoint,
+                    TxInput::Account(_) | TxInput::AccountCommand(_, _) => continue,
+                };
+                let output_exists =
+                    outputs_count.get(&outpoint.source_id()).map_or(is_relevant, |count| {
+                        (outpoint.output_index() as usize) < *count
+                    });
+                if !output_exists {
+                    violations.push(IntegrityViolation::MissingSpentOutput {
+                        account_index,
+                        tx_id: tx.id(),
+                        outpoint: outpoint.clone(),
+                    });
+                }
+            }
+
+            if let Some(tx_height) = tx.state().block_height() {
+                if tx_height > best_block_height {
+                    violations.push(IntegrityViolation::TransactionAboveBestBlock {
+                        account_index,
+                        tx_id: tx.id(),
+                        tx_height,
+                        best_block_height,
+                    });
+                }
+            }
+
+            match tx.state() {
+                TxState::InMempool(tx_counter) | TxState::Inactive(tx_counter) => {
+                    if unconfirmed_tx_counter.map_or(true, |counter| tx_counter > counter) {
+                        violations.push(IntegrityViolation::UnconfirmedTxCounterBelowUsed {
+                            account_index,
+                            tx_id: tx.id(),
+                            tx_counter,
+                            account_counter: unconfirmed_tx_counter,
+                        });
+                    }
+                }
+                TxState::Confirmed(_, _, _) | TxState::Conflicted(_) | TxState::Abandoned => {}
+            }
+        }
+
+        for (id, usage) in db_tx.get_keychain_usage_states(&account_id)? {
+            if let Some(last_used) = usage.last_used() {
+                if usage.last_issued().map_or(true, |last_issued| last_used > last_issued) {
+                    violations.push(IntegrityViolation::KeyUsageAboveIssued {
+                        account_index,
+                        purpose: *id.item_id(),
+                        last_used,
+                        last_issued: usage.last_issued(),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(violations)
+}
@@ -14,15 +14,17 @@
 // limitations under the License.
 
 use std::collections::{BTreeMap, BTreeSet};
+use std::num::{NonZeroU8, NonZeroUsize};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use crate::account::transaction_list::TransactionList;
+use crate::account::transaction_list::{DailyActivity, TransactionList, TxCursor};
 use crate::account::{
     currency_grouper::Currency, CurrentFeeRate, DelegationData, PoolData, TransactionToSign,
     UnconfirmedTokenInfo, UtxoSelectorError,
 };
 use crate::account::{CoinSelectionAlgo, TxInfo};
+use crate::coinjoin::{merge_coinjoin_proposals, CoinJoinProposal};
 use crate::key_chain::{
     make_account_path, make_path_to_vrf_key, KeyChainError, MasterKeyChain, LOOKAHEAD_SIZE,
     VRF_INDEX,
@@ -38,10 +40,12 @@ pub use bip39::{Language, Mnemonic};
 use common::address::pubkeyhash::PublicKeyHash;
 use common::address::{Address, AddressError, RpcAddress};
 use common::chain::block::timestamp::BlockTimestamp;
-use common::chain::classic_multisig::ClassicMultisigChallenge;
+use common::chain::classic_multisig::{ClassicMultisigChallenge, ClassicMultisigChallengeError};
 use common::chain::htlc::HashedTimelockContract;
 use common::chain::output_value::OutputValue;
-use common::chain::partially_signed_transaction::PartiallySignedTransaction;
+use common::chain::partially_signed_transaction::{
+    PartiallySignedTransaction, ScriptVerificationError,
+};
 use common::chain::signature::inputsig::arbitrary_message::{
     ArbitraryMessageSignature, SignArbitraryMessageError,
 };
@@ -50,8 +54,9 @@ use common::chain::tokens::{
     make_token_id, IsTokenUnfreezable, Metadata, RPCFungibleTokenInfo, TokenId, TokenIssuance,
 };
 use common::chain::{
-    AccountNonce, Block, ChainConfig, DelegationId, Destination, GenBlock, PoolId,
-    SignedTransaction, Transaction, TransactionCreationError, TxInput, TxOutput, UtxoOutPoint,
+    AccountNonce, Block, ChainConfig, DelegationId, Destination, GenBlock, OutPointSourceId,
+    PoolId, SignedTransaction, Transaction, TransactionCreationError, TxInput, TxOutput,
+    UtxoOutPoint,
 };
 use common::primitives::id::{hash_encoded, WithId};
 use common::primitives::{Amount, BlockHeight, Id, H256};
@@ -59,13 +64,15 @@ use common::size_estimation::SizeEstimationError;
 use consensus::PoSGenerateBlockInputData;
 use crypto::key::hdkd::child_number::ChildNumber;
 use crypto::key::hdkd::derivable::Derivable;
+use crypto::key::hdkd::derivation_path::DerivationPath;
 use crypto::key::hdkd::u31::U31;
 use crypto::key::{PrivateKey, PublicKey};
 use crypto::vrf::VRFPublicKey;
+pub use integrity::IntegrityViolation;
 use mempool::FeeRate;
 use pos_accounting::make_delegation_id;
 use tx_verifier::error::TokenIssuanceError;
-use tx_verifier::{check_transaction, CheckTransactionError};
+use tx_verifier::{check_transaction, CheckTransactionError, MetadataExt};
 use utils::ensure;
 pub use wallet_storage::Error;
 use wallet_storage::{
@@ -81,7 +88,10 @@ use wallet_types::utxo_types::{UtxoStates, UtxoTypes};
 use wallet_types::wallet_tx::{TxData, TxState};
 use wallet_types::wallet_type::WalletType;
 use wallet_types::with_locked::WithLocked;
-use wallet_types::{AccountId, AccountKeyPurposeId, BlockInfo, KeyPurpose, KeychainUsageState};
+use wallet_types::{
+    AccountId, AccountKeyPurposeId, AddressOrigin, BlockInfo, KeyPurpose, KeychainUsageState,
+    LookaheadStatus,
+};
 
 pub const WALLET_VERSION_UNINITIALIZED: u32 = 0;
 pub const WALLET_VERSION_V1: u32 = 1;
@@ -134,6 +144,8 @@ pub enum WalletError {
     OutputAmountOverflow,
     #[error("Fee amounts overflow")]
     FeeAmountOverflow,
+    #[error("Block timestamp {0} cannot be converted to a date")]
+    InvalidBlockTimestamp(BlockTimestamp),
     #[error("Delegation with id: {0} with duplicate AccountNonce: {1}")]
     InconsistentDelegationDuplicateNonce(DelegationId, AccountNonce),
     #[error("Inconsistent produce block from stake for pool id: {0}, missing CreateStakePool")]
@@ -152,6 +164,8 @@ pub enum WalletError {
     TransactionCreation(#[from] TransactionCreationError),
     #[error("Transaction signing error: {0}")]
     TransactionSig(#[from] DestinationSigError),
+    #[error("Witness script verification error: {0}")]
+    ScriptVerification(#[from] ScriptVerificationError),
     #[error("Delegation not found with id {0}")]
     DelegationNotFound(DelegationId),
     #[error("Not enough UTXOs amount: {0:?}, required: {1:?}")]
@@ -170,6 +184,8 @@ pub enum WalletError {
     CannotFindTransactionWithId(Id<Transaction>),
     #[error("Address error: {0}")]
     AddressError(#[from] AddressError),
+    #[error("Invalid multisig challenge: {0}")]
+    InvalidMultisigChallenge(#[from] ClassicMultisigChallengeError),
     #[error("Unknown pool id {0}")]
     UnknownPoolId(PoolId),
     #[error("Cannot find UTXO {0:?}")]
@@ -234,6 +250,22 @@ pub enum WalletError {
     StandaloneAddressNotFound(RpcAddress<Destination>),
     #[error("Signer error: {0}")]
     SignerError(#[from] SignerError),
+    #[error("The CoinJoin proposal inputs or outputs do not match its transaction")]
+    InvalidCoinJoinProposal,
+    #[error("The UTXO {0:?} is spent by more than one CoinJoin proposal")]
+    CoinJoinDuplicateInput(UtxoOutPoint),
+    #[error("Transaction {0} is not unconfirmed, there is nothing to speed up")]
+    CpfpParentNotUnconfirmed(Id<Transaction>),
+    #[error("Transaction {0} has no unspent coin output owned by this account")]
+    CpfpNoSpendableParentOutput(Id<Transaction>),
+    #[error("The transaction has no inputs or outputs that belong to this account")]
+    TransactionNotRelevant,
+    #[error("Block height {0} is above the best block height {1} of the account")]
+    BlockHeightAboveBestBlock(BlockHeight, BlockHeight),
+    #[error("The wallet is opened in read-only mode")]
+    ReadOnly,
+    #[error("The seed phrase is not stored in the wallet")]
+    SeedPhraseNotStored,
 }
 
 /// Result type used for the wallet
@@ -254,6 +286,7 @@ pub struct Wallet<B: storage::Backend> {
     accounts: BTreeMap<U31, Account>,
     latest_median_time: BlockTimestamp,
     next_unused_account: (U31, Account),
+    read_only: bool,
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
@@ -262,14 +295,52 @@ pub struct WalletSyncingState {
     pub unused_account_best_block: (Id<GenBlock>, BlockHeight),
 }
 
+/// A token whose authority is owned by the wallet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenAuthority {
+    pub token_id: TokenId,
+    pub authority: Destination,
+}
+
 pub fn open_or_create_wallet_file<P: AsRef<Path>>(path: P) -> WalletResult<Store<DefaultBackend>> {
     Ok(Store::new(DefaultBackend::new(path))?)
 }
 
+/// Open an existing wallet file without ever writing to it, to be used with `Wallet::open_read_only`
+pub fn open_wallet_file_read_only<P: AsRef<Path>>(path: P) -> WalletResult<Store<DefaultBackend>> {
+    Ok(Store::new(DefaultBackend::new_read_only(path))?)
+}
+
 pub fn create_wallet_in_memory() -> WalletResult<Store<DefaultBackend>> {
     Ok(Store::new(DefaultBackend::new_in_memory())?)
 }
 
+fn is_token_output(output: &TxOutput) -> bool {
+    match output {
+        TxOutput::Transfer(value, _)
+        | TxOutput::LockThenTransfer(value, _, _)
+        | TxOutput::Htlc(value, _) => matches!(value, OutputValue::TokenV1(_, _)),
+        _ => false,
+    }
+}
+
+/// The fee rate for transactions that transfer tokens, multiplied by the chain's token fee
+/// multiplier and rounded up
+fn token_transfer_fee_rate(chain_config: &ChainConfig, fee_rate: FeeRate) -> WalletResult<FeeRate> {
+    let multiplier = chain_config.token_fee_multiplier();
+    if multiplier == 1.0 {
+        // Avoid the precision loss of the floating point conversion
+        return Ok(fee_rate);
+    }
+
+    let atoms_per_kb = (fee_rate.atoms_per_kb() as f64 * multiplier).ceil();
+    ensure!(
+        atoms_per_kb < u128::MAX as f64,
+        WalletError::FeeAmountOverflow
+    );
+    Ok(FeeRate::from_atoms_per_kb(atoms_per_kb as u128))
+}
+
 impl<B: storage::Backend> Wallet<B> {
     pub fn create_new_wallet(
         chain_config: Arc<ChainConfig>,
@@ -361,6 +432,7 @@ impl<B: storage::Backend> Wallet<B> {
             accounts: [default_account].into(),
             latest_median_time,
             next_unused_account,
+            read_only: false,
         };
 
         Ok(wallet)
@@ -614,6 +686,7 @@ impl<B: storage::Backend> Wallet<B> {
         logging::log::info!(
             "Resetting the wallet to genesis and starting to rescan the blockchain"
         );
+        self.ensure_writable()?;
         let mut db_tx = self.db.transaction_rw(None)?;
         let mut accounts =
             Self::reset_wallet_transactions_and_load(self.chain_config.clone(), &mut db_tx)?;
@@ -718,6 +791,38 @@ impl<B: storage::Backend> Wallet<B> {
             Self::force_migrate_wallet_type(wallet_type, &db, chain_config.clone())?;
         }
 
+        Self::load_from_database(chain_config, db, wallet_type, false)
+    }
+
+    /// Open an existing wallet without ever writing to its database, e.g. to inspect the balances
+    /// while the wallet is used by another part of the process through a shared store, or when
+    /// the wallet file is opened with `open_wallet_file_read_only`.
+    /// All the methods that would change the wallet fail with `WalletError::ReadOnly`.
+    /// The wallet database must already be at the current version, as migrating it requires writing,
+    /// otherwise `WalletError::UnsupportedWalletVersion` is returned.
+    pub fn open_read_only(
+        chain_config: Arc<ChainConfig>,
+        db: Store<B>,
+        wallet_type: WalletType,
+    ) -> WalletResult<Self> {
+        let version = db.transaction_ro()?.get_storage_version()?;
+        match version {
+            WALLET_VERSION_UNINITIALIZED => return Err(WalletError::WalletNotInitialized),
+            CURRENT_WALLET_VERSION => {}
+            unsupported_version => {
+                return Err(WalletError::UnsupportedWalletVersion(unsupported_version))
+            }
+        }
+
+        Self::load_from_database(chain_config, db, wallet_type, true)
+    }
+
+    fn load_from_database(
+        chain_config: Arc<ChainConfig>,
+        db: Store<B>,
+        wallet_type: WalletType,
+        read_only: bool,
+    ) -> WalletResult<Self> {
         // Please continue to use read-only transaction here.
         // Some unit tests expect that loading the wallet does not change the DB.
         let db_tx = db.transaction_ro()?;
@@ -752,14 +857,48 @@ impl<B: storage::Backend> Wallet<B> {
             accounts,
             latest_median_time,
             next_unused_account,
+            read_only,
         })
     }
 
+    fn ensure_writable(&self) -> WalletResult<()> {
+        ensure!(!self.read_only, WalletError::ReadOnly);
+        Ok(())
+    }
+
     pub fn seed_phrase(&self) -> WalletResult<Option<SerializableSeedPhrase>> {
         self.db.transaction_ro_unlocked()?.get_seed_phrase().map_err(WalletError::from)
     }
 
+    /// Check that the stored seed phrase and the given passphrase derive an account whose first
+    /// receiving address is the expected one. The wallet itself is not modified.
+    ///
+    /// The seed phrase is read from the database, so an encrypted wallet must be unlocked first
+    /// (otherwise `WalletLocked` is returned). Wallets created without storing the seed phrase
+    /// have nothing to derive from and return `SeedPhraseNotStored`.
+    pub fn verify_passphrase(
+        &self,
+        account_index: U31,
+        passphrase: Option<&str>,
+        expected_first_address: &Address<Destination>,
+    ) -> WalletResult<bool> {
+        let mnemonic = match self.seed_phrase()?.ok_or(WalletError::SeedPhraseNotStored)? {
+            SerializableSeedPhrase::V0(_, seed_phrase)
+            | SerializableSeedPhrase::V1(_, seed_phrase, _) => {
+                zeroize::Zeroizing::new(seed_phrase.mnemonic().join(" "))
+            }
+        };
+        let first_address = MasterKeyChain::first_receiving_address(
+            &self.chain_config,
+            &mnemonic,
+            passphrase,
+            account_index,
+        )?;
+        Ok(&first_address == expected_first_address)
+    }
+
     pub fn delete_seed_phrase(&self) -> WalletResult<Option<SerializableSeedPhrase>> {
+        self.ensure_writable()?;
         let mut tx = self.db.transaction_rw_unlocked(None)?;
         let seed_phrase = tx.del_seed_phrase().map_err(WalletError::from)?;
         tx.commit()?;
@@ -776,6 +915,7 @@ impl<B: storage::Backend> Wallet<B> {
     }
 
     pub fn encrypt_wallet(&mut self, password: &Option<String>) -> WalletResult<()> {
+        self.ensure_writable()?;
         self.db.encrypt_private_keys(password).map_err(WalletError::from)
     }
 
@@ -804,6 +944,7 @@ impl<B: storage::Backend> Wallet<B> {
             );
         }
 
+        self.ensure_writable()?;
         let mut db_tx = self.db.transaction_rw(None)?;
         db_tx.set_lookahead_size(lookahead_size)?;
         let mut accounts =
@@ -829,6 +970,53 @@ impl<B: storage::Backend> Wallet<B> {
         (hash_encoded(&acc_id), names)
     }
 
+    /// Hash of the account's extended public key. Wallets restored from the same seed phrase and
+    /// passphrase have the same fingerprints, so it can be used to check that a recovery worked.
+    pub fn account_fingerprint(&self, account_index: U31) -> WalletResult<H256> {
+        let account = self.get_account(account_index)?;
+        Ok(hash_encoded(account.key_chain().account_public_key()))
+    }
+
+    /// Find the account that owns the given address, along with whether the address is derived
+    /// from the account's key chain or was added to it as a standalone one
+    pub fn find_account_for_address(
+        &self,
+        destination: Destination,
+    ) -> WalletResult<Option<(U31, AddressOrigin)>> {
+        let found = self.accounts.iter().find_map(|(account_index, account)| {
+            if account.get_derivation_path(&destination).is_some() {
+                Some((*account_index, AddressOrigin::HdDerived))
+            } else if account
+                .key_chain()
+                .get_all_standalone_address_details(destination.clone())
+                .is_some()
+            {
+                Some((*account_index, AddressOrigin::Standalone))
+            } else {
+                None
+            }
+        });
+        Ok(found)
+    }
+
+    /// Find the transactions and block rewards in the given blocks that would be recognised as
+    /// belonging to the account if the blocks were scanned, without changing the wallet state or
+    /// the database
+    pub fn simulate_scan(
+        &self,
+        account_index: U31,
+        blocks: &[Block],
+    ) -> WalletResult<Vec<OutPointSourceId>> {
+        let account = self.get_account(account_index)?;
+        Ok(account.simulate_scan(blocks))
+    }
+
+    /// Check that the wallet database is internally consistent, returns the found violations
+    pub fn check_integrity(&self) -> WalletResult<Vec<IntegrityViolation>> {
+        let db_tx = self.db.transaction_ro()?;
+        integrity::check_integrity(&db_tx, &self.accounts)
+    }
+
     fn create_next_unused_account(
         next_account_index: U31,
         chain_config: Arc<ChainConfig>,
@@ -855,6 +1043,16 @@ impl<B: storage::Backend> Wallet<B> {
     pub fn create_next_account(
         &mut self,
         name: Option<String>,
+    ) -> WalletResult<(U31, Option<String>)> {
+        self.create_next_account_with(name, |_, _| Ok(()))
+    }
+
+    /// Promotes the unused account into the used accounts, initializing it with the provided
+    /// function in the same database transaction
+    fn create_next_account_with(
+        &mut self,
+        name: Option<String>,
+        init: impl FnOnce(&mut Account, &mut StoreTxRwUnlocked<B>) -> WalletResult<()>,
     ) -> WalletResult<(U31, Option<String>)> {
         ensure!(
             self.accounts
@@ -874,6 +1072,7 @@ impl<B: storage::Backend> Wallet<B> {
                 WalletError::AbsoluteMaxNumAccountsExceeded(self.next_unused_account.0)
             })?;
 
+        self.ensure_writable()?;
         let mut db_tx = self.db.transaction_rw_unlocked(None)?;
 
         let mut next_unused_account = Self::create_next_unused_account(
@@ -884,6 +1083,8 @@ impl<B: storage::Backend> Wallet<B> {
             None,
         )?;
 
+        init(&mut self.next_unused_account.1, &mut db_tx)?;
+
         self.next_unused_account.1.set_name(name.clone(), &mut db_tx)?;
         std::mem::swap(&mut self.next_unused_account, &mut next_unused_account);
         let (next_account_index, next_account) = next_unused_account;
@@ -905,6 +1106,88 @@ impl<B: storage::Backend> Wallet<B> {
         Ok((next_account_index, name))
     }
 
+    /// Create the account with the given index even if the last account has no transaction history,
+    /// e.g. when recovering a wallet from a backup that only has some of the accounts.
+    ///
+    /// Account indexes cannot have gaps, so any missing accounts below the given index are created
+    /// as well. The accounts that haven't been scanned so far start from the genesis block,
+    /// so the next sync rescans the blockchain for them.
+    pub fn force_create_account_at_index(
+        &mut self,
+        account_index: U31,
+        name: Option<String>,
+    ) -> WalletResult<(U31, Option<String>)> {
+        ensure!(
+            !self.accounts.contains_key(&account_index),
+            WalletError::AccountAlreadyExists(account_index)
+        );
+        ensure!(
+            name.as_ref().map_or(true, |name| !name.is_empty()),
+            WalletError::EmptyAccountName
+        );
+        // Check the limit before changing anything
+        account_index
+            .plus_one()
+            .map_err(|_| WalletError::AbsoluteMaxNumAccountsExceeded(account_index))?;
+
+        self.ensure_writable()?;
+        let mut db_tx = self.db.transaction_rw_unlocked(None)?;
+        let mut new_accounts = BTreeMap::new();
+
+        loop {
+            let current_unused_index = self.next_unused_account.0;
+            let next_unused_index = current_unused_index
+                .plus_one()
+                .map_err(|_| WalletError::AbsoluteMaxNumAccountsExceeded(current_unused_index))?;
+
+            let mut next_unused_account = Self::create_next_unused_account(
+                next_unused_index,
+                self.chain_config.clone(),
+                &self.key_chain,
+                &mut db_tx,
+                None,
+            )?;
+
+            if current_unused_index == account_index {
+                self.next_unused_account.1.set_name(name.clone(), &mut db_tx)?;
+            }
+            std::mem::swap(&mut self.next_unused_account, &mut next_unused_account);
+            let (new_account_index, new_account) = next_unused_account;
+            new_accounts.insert(new_account_index, new_account);
+
+            if new_account_index == account_index {
+                break;
+            }
+        }
+
+        db_tx.commit()?;
+
+        self.accounts.extend(new_accounts);
+
+        Ok((account_index, name))
+    }
+
+    /// Create the accounts with the given indexes that don't exist yet, along with any missing
+    /// accounts below them, in a single database transaction.
+    /// Returns the indexes of all the created accounts, in ascending order.
+    pub fn recover_accounts(&mut self, account_indices: &BTreeSet<U31>) -> WalletResult<Vec<U31>> {
+        // The existing accounts have no gaps, so if the highest index is missing, all the others
+        // are created along with it
+        let last_index = match account_indices.last() {
+            Some(last_index) if !self.accounts.contains_key(last_index) => *last_index,
+            Some(_) | None => return Ok(Vec::new()),
+        };
+
+        let first_new_index = self.next_unused_account.0;
+        self.force_create_account_at_index(last_index, None)?;
+
+        Ok(self
+            .accounts
+            .range(first_new_index..=last_index)
+            .map(|(index, _)| *index)
+            .collect())
+    }
+
     pub fn set_account_name(
         &mut self,
         account_index: U31,
@@ -924,6 +1207,7 @@ impl<B: storage::Backend> Wallet<B> {
         account_index: U31,
         f: impl FnOnce(&mut Account, &mut StoreTxRw<B>) -> WalletResult<T>,
     ) -> WalletResult<T> {
+        self.ensure_writable()?;
         let mut db_tx = self.db.transaction_rw(None)?;
         let account = Self::get_account_mut(&mut self.accounts, account_index)?;
         let value = f(account, &mut db_tx)?;
@@ -940,6 +1224,7 @@ impl<B: storage::Backend> Wallet<B> {
         account_index: U31,
         f: impl FnOnce(&mut Account, &mut StoreTxRwUnlocked<B>, &ChainConfig) -> WalletResult<T>,
     ) -> WalletResult<T> {
+        self.ensure_writable()?;
         let mut db_tx = self.db.transaction_rw_unlocked(None)?;
         let account = Self::get_account_mut(&mut self.accounts, account_index)?;
         match f(account, &mut db_tx, &self.chain_config) {
@@ -1084,6 +1369,24 @@ impl<B: storage::Backend> Wallet<B> {
         Ok(utxos)
     }
 
+    /// Get the utxos that are locked by a timelock, together with the time at which they unlock.
+    /// The unlock time of the height based timelocks is an estimate.
+    pub fn get_locked_utxos(
+        &self,
+        account_index: U31,
+        utxo_states: UtxoStates,
+    ) -> WalletResult<Vec<(UtxoOutPoint, TxOutput, Option<TokenId>, BlockTimestamp)>> {
+        let account = self.get_account(account_index)?;
+        let utxos = account.get_locked_utxos(self.latest_median_time, utxo_states);
+        let utxos = utxos
+            .into_iter()
+            .map(|(outpoint, (txo, token_id), unlock_time)| {
+                (outpoint, txo.clone(), token_id, unlock_time)
+            })
+            .collect();
+        Ok(utxos)
+    }
+
     pub fn find_unspent_utxo_with_destination(
         &self,
         outpoint: &UtxoOutPoint,
@@ -1127,6 +1430,38 @@ impl<B: storage::Backend> Wallet<B> {
         })
     }
 
+    /// Import a transaction that was created outside of this wallet into the account's history,
+    /// e.g. one that was missed while syncing from a pruned node.
+    ///
+    /// If `block_height` is set, the transaction is recorded as confirmed at that height, otherwise
+    /// it is recorded as pending. The exact block timestamp is not known, so the latest median time
+    /// is used instead.
+    pub fn import_external_transaction(
+        &mut self,
+        account_index: U31,
+        transaction: SignedTransaction,
+        block_height: Option<BlockHeight>,
+        wallet_events: &impl WalletEvents,
+    ) -> WalletResult<()> {
+        let (_, best_block_height) = self.get_best_block_for_account(account_index)?;
+        let block_info = block_height
+            .map(|height| -> WalletResult<_> {
+                ensure!(
+                    height <= best_block_height,
+                    WalletError::BlockHeightAboveBestBlock(height, best_block_height)
+                );
+                Ok(BlockInfo {
+                    height,
+                    timestamp: self.latest_median_time,
+                })
+            })
+            .transpose()?;
+
+        self.for_account_rw(account_index, |account, db_tx| {
+            account.import_external_transaction(db_tx, wallet_events, transaction, block_info)
+        })
+    }
+
     pub fn get_pool_ids(
         &self,
         account_index: U31,
@@ -1145,6 +1480,19 @@ impl<B: storage::Backend> Wallet<B> {
         Ok(delegations)
     }
 
+    /// List the tokens whose authority is owned by the account, together with the authority
+    pub fn list_token_authorities(&self, account_index: U31) -> WalletResult<Vec<TokenAuthority>> {
+        let tokens = self
+            .get_account(account_index)?
+            .get_token_authorities()
+            .map(|(token_id, data)| TokenAuthority {
+                token_id: *token_id,
+                authority: data.authority.clone(),
+            })
+            .collect();
+        Ok(tokens)
+    }
+
     pub fn get_delegation(
         &self,
         account_index: U31,
@@ -1205,6 +1553,50 @@ impl<B: storage::Backend> Wallet<B> {
         })
     }
 
+    /// The public key the next created account contributes to a multisig account's challenge,
+    /// to be shared with the other cosigners before calling `create_multisig_account`
+    pub fn get_next_multisig_account_public_key(&self) -> WalletResult<PublicKey> {
+        self.next_unused_account.1.multisig_public_key()
+    }
+
+    /// Create a new M-of-N multisig account from the public keys of the other cosigners.
+    ///
+    /// The account's own multisig public key is added to the cosigner keys, which are sorted so
+    /// that all the cosigners end up with the same challenge. The balance, coin selection and
+    /// signing of the new account use the outputs sent to the multisig address,
+    /// and the change goes back to it.
+    pub fn create_multisig_account(
+        &mut self,
+        min_required_signatures: NonZeroU8,
+        cosigner_public_keys: Vec<PublicKey>,
+        name: Option<String>,
+    ) -> WalletResult<(U31, ClassicMultisigChallenge)> {
+        let mut public_keys = cosigner_public_keys;
+        public_keys.push(self.get_next_multisig_account_public_key()?);
+        public_keys.sort();
+        public_keys.dedup();
+
+        let challenge = ClassicMultisigChallenge::new(
+            &self.chain_config,
+            min_required_signatures,
+            public_keys,
+        )?;
+
+        let account_challenge = challenge.clone();
+        let (account_index, _) = self.create_next_account_with(name, |account, db_tx| {
+            account.set_multisig_challenge(db_tx, account_challenge)
+        })?;
+
+        Ok((account_index, challenge))
+    }
+
+    pub fn get_current_change_address(
+        &self,
+        account_index: U31,
+    ) -> WalletResult<Address<Destination>> {
+        self.get_account(account_index)?.get_current_change_address()
+    }
+
     pub fn get_new_address(
         &mut self,
         account_index: U31,
@@ -1223,6 +1615,18 @@ impl<B: storage::Backend> Wallet<B> {
         })
     }
 
+    /// Issue `count` new VRF keys at once; fails without issuing anything if they don't all fit
+    /// in the lookahead
+    pub fn get_n_vrf_keys(
+        &mut self,
+        account_index: U31,
+        count: u32,
+    ) -> WalletResult<Vec<(ChildNumber, Address<VRFPublicKey>)>> {
+        self.for_account_rw(account_index, |account, db_tx| {
+            account.get_new_vrf_keys(db_tx, count)
+        })
+    }
+
     pub fn find_public_key(
         &mut self,
         account_index: U31,
@@ -1248,6 +1652,29 @@ impl<B: storage::Backend> Wallet<B> {
         account.get_transaction_list(skip, count)
     }
 
+    /// Get up to `count` transactions of the account that follow the cursor and the cursor
+    /// of the next page, if there is one
+    pub fn get_transaction_list_from_cursor(
+        &self,
+        account_index: U31,
+        cursor: Option<TxCursor>,
+        count: NonZeroUsize,
+    ) -> WalletResult<(Vec<TxData>, Option<TxCursor>)> {
+        let account = self.get_account(account_index)?;
+        account.get_transaction_list_from_cursor(cursor, count)
+    }
+
+    /// Per-day number of confirmed transactions and net coin flow of the account,
+    /// starting from the specified time
+    pub fn get_account_activity_summary(
+        &self,
+        account_index: U31,
+        from: BlockTimestamp,
+    ) -> WalletResult<Vec<DailyActivity>> {
+        let account = self.get_account(account_index)?;
+        account.get_activity_summary(from)
+    }
+
     pub fn get_transaction(
         &self,
         account_index: U31,
@@ -1264,6 +1691,23 @@ impl<B: storage::Backend> Wallet<B> {
             .map_err(WalletError::DatabaseError)
     }
 
+    /// Same as `get_transactions_to_be_broadcast`, but without the transactions that are already
+    /// confirmed in any of the accounts
+    pub fn get_pending_transactions_to_be_broadcast(&self) -> WalletResult<Vec<SignedTransaction>> {
+        let txs = self.get_transactions_to_be_broadcast()?;
+        Ok(txs
+            .into_iter()
+            .filter(|tx| {
+                let tx_id = tx.transaction().get_id();
+                !self.accounts.values().any(|account| {
+                    account
+                        .get_transaction(tx_id)
+                        .is_ok_and(|tx| tx.state().block_height().is_some())
+                })
+            })
+            .collect())
+    }
+
     pub fn get_all_issued_addresses(
         &self,
         account_index: U31,
@@ -1272,6 +1716,27 @@ impl<B: storage::Backend> Wallet<B> {
         Ok(account.get_all_issued_addresses())
     }
 
+    /// Lists the receive addresses of the account that were used in more than one transaction,
+    /// which is bad for privacy, along with the number of transactions they were used in
+    pub fn address_reuse_report(
+        &self,
+        account_index: U31,
+    ) -> WalletResult<Vec<(Address<Destination>, usize)>> {
+        let account = self.get_account(account_index)?;
+        Ok(account.address_reuse_report())
+    }
+
+    /// Returns the derivation path of an address issued by the account's key chain,
+    /// or None if the address isn't one of them (e.g. a standalone or a foreign address)
+    pub fn address_derivation_path(
+        &self,
+        account_index: U31,
+        address: &Destination,
+    ) -> WalletResult<Option<DerivationPath>> {
+        let account = self.get_account(account_index)?;
+        Ok(account.get_derivation_path(address))
+    }
+
     pub fn get_all_standalone_addresses(
         &self,
         account_index: U31,
@@ -1314,6 +1779,16 @@ impl<B: storage::Backend> Wallet<B> {
         Ok(account.get_addresses_usage())
     }
 
+    /// Get the configured lookahead size and how many more receiving addresses
+    /// can be issued before it is exhausted
+    pub fn lookahead_status(&self, account_index: U31) -> WalletResult<LookaheadStatus> {
+        let account = self.get_account(account_index)?;
+        Ok(LookaheadStatus::new(
+            account.lookahead_size(),
+            account.get_addresses_usage(),
+        ))
+    }
+
     /// Creates a transaction to send funds to specified addresses.
     ///
     /// # Arguments
@@ -1343,6 +1818,12 @@ impl<B: storage::Backend> Wallet<B> {
         current_fee_rate: FeeRate,
         consolidate_fee_rate: FeeRate,
     ) -> WalletResult<SignedTransaction> {
+        let outputs = outputs.into_iter().collect::<Vec<_>>();
+        let current_fee_rate = if outputs.iter().any(is_token_output) {
+            token_transfer_fee_rate(&self.chain_config, current_fee_rate)?
+        } else {
+            current_fee_rate
+        };
         let request = SendRequest::new().with_outputs(outputs);
         let latest_median_time = self.latest_median_time;
         self.for_account_rw_unlocked_and_check_tx(account_index, |account, db_tx| {
@@ -1371,6 +1852,12 @@ impl<B: storage::Backend> Wallet<B> {
         current_fee_rate: FeeRate,
         consolidate_fee_rate: FeeRate,
     ) -> WalletResult<(PartiallySignedTransaction, BTreeMap<Currency, Amount>)> {
+        let outputs = outputs.into_iter().collect::<Vec<_>>();
+        let current_fee_rate = if outputs.iter().any(is_token_output) {
+            token_transfer_fee_rate(&self.chain_config, current_fee_rate)?
+        } else {
+            current_fee_rate
+        };
         let request = SendRequest::new().with_outputs(outputs);
         let latest_median_time = self.latest_median_time;
         self.for_account_rw(account_index, |account, db_tx| {
@@ -1389,6 +1876,64 @@ impl<B: storage::Backend> Wallet<B> {
         })
     }
 
+    /// Compute the fee of a transaction that transfers `amount` of the token to the destination,
+    /// taking the chain's token fee multiplier into account.
+    ///
+    /// The inputs are selected the same way as for an actual transfer, but neither the wallet
+    /// nor its database are changed.
+    pub fn compute_token_transfer_fee(
+        &self,
+        account_index: U31,
+        token_id: TokenId,
+        amount: Amount,
+        destination: Destination,
+        current_fee_rate: FeeRate,
+    ) -> WalletResult<Amount> {
+        let request = SendRequest::new().with_outputs([TxOutput::Transfer(
+            OutputValue::TokenV1(token_id, amount),
+            destination,
+        )]);
+        self.get_account(account_index)?.estimate_send_request_fee(
+            request,
+            self.latest_median_time,
+            CurrentFeeRate {
+                current_fee_rate: token_transfer_fee_rate(&self.chain_config, current_fee_rate)?,
+                consolidate_fee_rate: current_fee_rate,
+            },
+        )
+    }
+
+    /// Create this account's part of a CoinJoin transaction, selecting the inputs to pay for
+    /// the outputs and the fee of its share of the joint transaction.
+    pub fn create_coinjoin_proposal(
+        &mut self,
+        account_index: U31,
+        outputs: Vec<TxOutput>,
+        current_fee_rate: FeeRate,
+    ) -> WalletResult<CoinJoinProposal> {
+        let (ptx, _fees) = self.create_unsigned_transaction_to_addresses(
+            account_index,
+            outputs,
+            SelectedInputs::Utxos(vec![]),
+            None,
+            BTreeMap::new(),
+            current_fee_rate,
+            current_fee_rate,
+        )?;
+
+        CoinJoinProposal::new(ptx)
+    }
+
+    /// Merge this wallet's CoinJoin proposal with the ones received from the other participants
+    /// into the joint transaction, ready to be signed by every participant.
+    pub fn apply_coinjoin_proposal(
+        &self,
+        my_proposal: CoinJoinProposal,
+        peer_proposals: Vec<CoinJoinProposal>,
+    ) -> WalletResult<PartiallySignedTransaction> {
+        merge_coinjoin_proposals(std::iter::once(my_proposal).chain(peer_proposals))
+    }
+
     pub fn create_sweep_transaction(
         &mut self,
         account_index: U31,
@@ -1408,6 +1953,60 @@ impl<B: storage::Backend> Wallet<B> {
         })
     }
 
+    /// Create a child transaction spending an unconfirmed output of the parent transaction,
+    /// with a fee high enough for both transactions together to reach the target fee rate.
+    /// `parent_fee` is the fee paid by the parent if known, e.g. from the mempool,
+    /// see `Account::create_cpfp_request` for how it's determined otherwise.
+    pub fn create_cpfp_transaction(
+        &mut self,
+        account_index: U31,
+        parent_tx_id: Id<Transaction>,
+        parent_fee: Option<Amount>,
+        target_fee_rate: FeeRate,
+    ) -> WalletResult<SignedTransaction> {
+        let latest_median_time = self.latest_median_time;
+        self.for_account_rw_unlocked_and_check_tx(account_index, |account, db_tx| {
+            account.create_cpfp_request(
+                db_tx,
+                parent_tx_id,
+                parent_fee,
+                target_fee_rate,
+                latest_median_time,
+            )
+        })
+    }
+
+    /// Create a transaction sending the whole confirmed and unlocked balance of the token
+    /// to the address, without a token change output.
+    pub fn create_transaction_to_send_all_tokens(
+        &mut self,
+        account_index: U31,
+        token_id: TokenId,
+        address: Address<Destination>,
+        current_fee_rate: FeeRate,
+        consolidate_fee_rate: FeeRate,
+    ) -> WalletResult<SignedTransaction> {
+        let latest_median_time = self.latest_median_time;
+        self.for_account_rw_unlocked_and_check_tx(account_index, |account, db_tx| {
+            let request = account.send_all_tokens_request(
+                token_id,
+                address.into_object(),
+                latest_median_time,
+            )?;
+            account.process_send_request_and_sign(
+                db_tx,
+                request,
+                SelectedInputs::Utxos(vec![]),
+                BTreeMap::new(),
+                latest_median_time,
+                CurrentFeeRate {
+                    current_fee_rate,
+                    consolidate_fee_rate,
+                },
+            )
+        })
+    }
+
     pub fn create_sweep_from_delegation_transaction(
         &mut self,
         account_index: U31,
@@ -1466,6 +2065,19 @@ impl<B: storage::Backend> Wallet<B> {
         })
     }
 
+    /// Check that `amount` of the token can be minted by this account without building a transaction,
+    /// taking the unconfirmed transactions into account.
+    pub fn can_mint(
+        &self,
+        account_index: U31,
+        token_info: &RPCFungibleTokenInfo,
+        amount: Amount,
+    ) -> WalletResult<()> {
+        self.get_account(account_index)?
+            .get_token_unconfirmed_info(token_info)?
+            .check_can_mint(amount)
+    }
+
     pub fn unmint_tokens(
         &mut self,
         account_index: U31,
@@ -1673,6 +2285,8 @@ impl<B: storage::Backend> Wallet<B> {
         current_fee_rate: FeeRate,
         consolidate_fee_rate: FeeRate,
     ) -> WalletResult<(TokenId, SignedTransaction)> {
+        metadata.validate_sizes(&self.chain_config)?;
+
         let destination = address.into_object();
         let latest_median_time = self.latest_median_time;
 
@@ -1813,6 +2427,7 @@ impl<B: storage::Backend> Wallet<B> {
             let signer = SoftwareSigner::new(db_tx, Arc::new(chain_config.clone()), account_index);
 
             let res = signer.sign_tx(ptx, account.key_chain())?;
+            res.0.verify_scripts(chain_config)?;
             Ok(res)
         })
     }
@@ -1894,10 +2509,22 @@ impl<B: storage::Backend> Wallet<B> {
         blocks: Vec<Block>,
         wallet_events: &impl WalletEvents,
     ) -> WalletResult<()> {
+        let (_, best_block_height) = self.get_best_block_for_account(account_index)?;
+
         self.for_account_rw(account_index, |acc, db_tx| {
             acc.scan_new_blocks(db_tx, wallet_events, common_block_height, &blocks)
         })?;
 
+        if best_block_height > common_block_height {
+            let disconnected = best_block_height.into_int() - common_block_height.into_int();
+            wallet_events.reorg(
+                account_index,
+                common_block_height,
+                disconnected as usize,
+                blocks.len(),
+            );
+        }
+
         wallet_events.new_block();
         Ok(())
     }
@@ -1916,6 +2543,7 @@ impl<B: storage::Backend> Wallet<B> {
         wallet_events: &impl WalletEvents,
     ) -> WalletResult<()> {
         loop {
+            self.ensure_writable()?;
             let mut db_tx = self.db.transaction_rw(None)?;
             let added_new_tx_in_unused_acc = self.next_unused_account.1.scan_new_blocks(
                 &mut db_tx,
@@ -1944,6 +2572,7 @@ impl<B: storage::Backend> Wallet<B> {
         best_block_height: BlockHeight,
         best_block_id: Id<GenBlock>,
     ) -> WalletResult<()> {
+        self.ensure_writable()?;
         let mut db_tx = self.db.transaction_rw(None)?;
 
         for account in self.accounts.values_mut() {
@@ -1969,6 +2598,7 @@ impl<B: storage::Backend> Wallet<B> {
         transactions: &[SignedTransaction],
         wallet_events: &impl WalletEvents,
     ) -> WalletResult<()> {
+        self.ensure_writable()?;
         let mut db_tx = self.db.transaction_rw(None)?;
 
         for account in self.accounts.values_mut() {
@@ -1987,6 +2617,7 @@ impl<B: storage::Backend> Wallet<B> {
         transaction: SignedTransaction,
         wallet_events: &impl WalletEvents,
     ) -> WalletResult<()> {
+        self.ensure_writable()?;
         let mut db_tx = self.db.transaction_rw(None)?;
 
         let txs = [transaction];
@@ -2013,6 +2644,7 @@ impl<B: storage::Backend> Wallet<B> {
     }
 
     pub fn set_median_time(&mut self, median_time: BlockTimestamp) -> WalletResult<()> {
+        self.ensure_writable()?;
         self.latest_median_time = median_time;
         let mut db_tx = self.db.transaction_rw(None)?;
         db_tx.set_median_time(median_time)?;
@@ -2021,5 +2653,7 @@ impl<B: storage::Backend> Wallet<B> {
     }
 }
 
+mod integrity;
+
 #[cfg(test)]
 mod tests;
@@ -52,11 +52,11 @@ use storage::raw::DbMapId;
 use test_utils::random::{make_seedable_rng, Seed};
 use wallet_storage::{schema, WalletStorageEncryptionRead};
 use wallet_types::{
-    account_info::DEFAULT_ACCOUNT_INDEX,
+    account_info::{StandaloneAddressDetails, DEFAULT_ACCOUNT_INDEX},
     seed_phrase::PassPhrase,
     utxo_types::{UtxoState, UtxoType},
 };
-use wallet_types::{seed_phrase::SeedPhraseLanguage, AccountWalletTxId};
+use wallet_types::{seed_phrase::SeedPhraseLanguage, AccountWalletTxId, AddressOrigin};
 
 // TODO: Many of these tests require randomization...
 
@@ -546,6 +546,124 @@ fn wallet_seed_phrase_retrieval(#[case] seed: Seed) {
     assert!(seed_phrase.is_none());
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn verify_passphrase(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+
+    let wallet_passphrase = gen_random_password(&mut rng);
+    let db = create_wallet_in_memory().unwrap();
+    let genesis_block_id = chain_config.genesis_block_id();
+    let mut wallet = Wallet::create_new_wallet(
+        Arc::clone(&chain_config),
+        db,
+        MNEMONIC,
+        Some(&wallet_passphrase),
+        StoreSeedPhrase::Store,
+        (BlockHeight::new(0), genesis_block_id),
+        WalletType::Hot,
+    )
+    .unwrap();
+    let (_, first_address) = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap();
+
+    let password = gen_random_password(&mut rng);
+    wallet.encrypt_wallet(&Some(password.clone())).unwrap();
+    wallet.lock_wallet().unwrap();
+    assert_eq!(
+        wallet
+            .verify_passphrase(
+                DEFAULT_ACCOUNT_INDEX,
+                Some(&wallet_passphrase),
+                &first_address
+            )
+            .unwrap_err(),
+        WalletError::DatabaseError(wallet_storage::Error::WalletLocked)
+    );
+    wallet.unlock_wallet(&password).unwrap();
+
+    assert!(wallet
+        .verify_passphrase(
+            DEFAULT_ACCOUNT_INDEX,
+            Some(&wallet_passphrase),
+            &first_address
+        )
+        .unwrap());
+
+    let wrong_passphrase = format!("{wallet_passphrase}x");
+    assert!(!wallet
+        .verify_passphrase(
+            DEFAULT_ACCOUNT_INDEX,
+            Some(&wrong_passphrase),
+            &first_address
+        )
+        .unwrap());
+    assert!(!wallet.verify_passphrase(DEFAULT_ACCOUNT_INDEX, None, &first_address).unwrap());
+
+    // The same passphrase doesn't match the address of another account
+    assert!(!wallet
+        .verify_passphrase(U31::ONE, Some(&wallet_passphrase), &first_address)
+        .unwrap());
+
+    // Nothing to verify against without the seed phrase
+    wallet.delete_seed_phrase().unwrap();
+    assert_eq!(
+        wallet
+            .verify_passphrase(
+                DEFAULT_ACCOUNT_INDEX,
+                Some(&wallet_passphrase),
+                &first_address
+            )
+            .unwrap_err(),
+        WalletError::SeedPhraseNotStored
+    );
+}
+
+#[test]
+fn account_fingerprint() {
+    let chain_config = Arc::new(create_mainnet());
+
+    let wallet = create_wallet(chain_config.clone());
+    let fingerprint = wallet.account_fingerprint(DEFAULT_ACCOUNT_INDEX).unwrap();
+
+    // A wallet restored from the same seed phrase has the same fingerprint
+    let restored_wallet = create_wallet(chain_config.clone());
+    assert_eq!(
+        restored_wallet.account_fingerprint(DEFAULT_ACCOUNT_INDEX).unwrap(),
+        fingerprint
+    );
+
+    // A different seed phrase gives a different fingerprint
+    let other_wallet = create_wallet_with_mnemonic(chain_config.clone(), MNEMONIC2);
+    assert_ne!(
+        other_wallet.account_fingerprint(DEFAULT_ACCOUNT_INDEX).unwrap(),
+        fingerprint
+    );
+
+    // And so does the same seed phrase with a passphrase
+    let genesis_block_id = chain_config.genesis_block_id();
+    let passphrase_wallet = Wallet::create_new_wallet(
+        Arc::clone(&chain_config),
+        create_wallet_in_memory().unwrap(),
+        MNEMONIC,
+        Some("passphrase"),
+        StoreSeedPhrase::DoNotStore,
+        (BlockHeight::new(0), genesis_block_id),
+        WalletType::Hot,
+    )
+    .unwrap();
+    assert_ne!(
+        passphrase_wallet.account_fingerprint(DEFAULT_ACCOUNT_INDEX).unwrap(),
+        fingerprint
+    );
+
+    assert_eq!(
+        wallet.account_fingerprint(U31::ONE).unwrap_err(),
+        WalletError::NoAccountFoundWithIndex(U31::ONE)
+    );
+}
+
 #[test]
 fn wallet_seed_phrase_check_address() {
     let chain_config = Arc::new(create_mainnet());
@@ -1044,6 +1162,117 @@ fn wallet_recover_new_account(#[case] seed: Seed) {
     }
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn wallet_force_create_account_at_index(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+
+    let acc0 = DEFAULT_ACCOUNT_INDEX;
+    let acc1 = U31::from_u32(1).unwrap();
+    let acc2 = U31::from_u32(2).unwrap();
+
+    // The accounts 0 and 2 have received funds, but the account 1 has no transaction history
+    let acc0_amount = Amount::from_atoms(rng.gen_range(1..10000));
+    let acc2_amount = Amount::from_atoms(rng.gen_range(1..10000));
+    let receive_address = |account_index| {
+        get_address(
+            &chain_config,
+            MNEMONIC,
+            account_index,
+            KeyPurpose::ReceiveFunds,
+            0.try_into().unwrap(),
+        )
+    };
+    let block = Block::new(
+        vec![],
+        chain_config.genesis_block_id(),
+        chain_config.genesis_block().timestamp(),
+        ConsensusData::None,
+        BlockReward::new(vec![
+            make_address_output(receive_address(acc0), acc0_amount),
+            make_address_output(receive_address(acc2), acc2_amount),
+        ]),
+    )
+    .unwrap();
+
+    let mut wallet = create_wallet(chain_config.clone());
+    scan_wallet(&mut wallet, BlockHeight::new(0), vec![block.clone()]);
+
+    // Only the account 0 is discovered by the regular scan
+    assert_eq!(wallet.number_of_accounts(), 1);
+    assert_eq!(get_coin_balance_for_acc(&wallet, acc0), acc0_amount);
+    let err = wallet.create_next_account(None).err().unwrap();
+    assert_eq!(err, WalletError::EmptyLastAccount);
+
+    // Existing accounts cannot be created again
+    let err = wallet.force_create_account_at_index(acc0, None).err().unwrap();
+    assert_eq!(err, WalletError::AccountAlreadyExists(acc0));
+
+    // Forcing the account 2 creates the account 1 as well, so there is no gap
+    let res = wallet.force_create_account_at_index(acc2, None).unwrap();
+    assert_eq!(res, (acc2, None));
+    assert_eq!(wallet.number_of_accounts(), 3);
+
+    // The new accounts are rescanned from the genesis block
+    for account_index in [acc1, acc2] {
+        wallet
+            .scan_new_blocks(
+                account_index,
+                BlockHeight::new(0),
+                vec![block.clone()],
+                &WalletEventsNoOp,
+            )
+            .unwrap();
+    }
+
+    assert_eq!(get_coin_balance_for_acc(&wallet, acc0), acc0_amount);
+    assert_eq!(get_coin_balance_for_acc(&wallet, acc1), Amount::ZERO);
+    assert_eq!(get_coin_balance_for_acc(&wallet, acc2), acc2_amount);
+}
+
+#[test]
+fn wallet_recover_accounts() {
+    let chain_config = Arc::new(create_mainnet());
+    let mut wallet = create_wallet(chain_config.clone());
+    let indices = |indices: &[u32]| {
+        indices
+            .iter()
+            .map(|index| U31::from_u32(*index).unwrap())
+            .collect::<BTreeSet<_>>()
+    };
+
+    // All the accounts up to the highest index are created and returned
+    let created = wallet.recover_accounts(&indices(&[0, 2, 4])).unwrap();
+    assert_eq!(
+        created,
+        indices(&[1, 2, 3, 4]).into_iter().collect::<Vec<_>>()
+    );
+    assert_eq!(wallet.number_of_accounts(), 5);
+    assert_eq!(
+        wallet.account_indexes().copied().collect::<BTreeSet<_>>(),
+        indices(&[0, 1, 2, 3, 4])
+    );
+
+    // Nothing is created if all the accounts exist
+    assert_eq!(wallet.recover_accounts(&indices(&[1, 3])).unwrap(), vec![]);
+    assert_eq!(wallet.recover_accounts(&BTreeSet::new()).unwrap(), vec![]);
+    assert_eq!(wallet.number_of_accounts(), 5);
+
+    // The accounts are stored
+    let wallet = Wallet::load_wallet(
+        chain_config,
+        wallet.db.clone(),
+        None,
+        |_| Ok(()),
+        WalletType::Hot,
+        false,
+    )
+    .unwrap();
+    assert_eq!(wallet.number_of_accounts(), 5);
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -1255,6 +1484,248 @@ fn wallet_list_mainchain_transactions(#[case] seed: Seed) {
     assert!(txs.iter().any(|info| info.id == spend_from_tx_id));
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn wallet_account_activity_summary(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_regtest());
+
+    let mut wallet = create_wallet(chain_config.clone());
+    let block1_amount = Amount::from_atoms(rng.gen_range(100000000..1000000000));
+    let _ = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
+
+    // 2023-11-14 22:13:20 UTC
+    let base_timestamp = 1_700_000_000;
+    let day_secs = 24 * 60 * 60;
+    let days = [0, 0, 0, 1, 1, 1, 1, 2, 2, 2];
+
+    let mut expected_flows = [0i64; 3];
+    for (idx, day) in days.into_iter().enumerate() {
+        let amount = Amount::from_atoms(rng.gen_range(1..10000));
+        let tx = wallet
+            .create_transaction_to_addresses(
+                DEFAULT_ACCOUNT_INDEX,
+                [gen_random_transfer(&mut rng, amount)],
+                SelectedInputs::Utxos(vec![]),
+                BTreeMap::new(),
+                FeeRate::from_amount_per_kb(Amount::ZERO),
+                FeeRate::from_amount_per_kb(Amount::ZERO),
+            )
+            .unwrap();
+        expected_flows[day] -= amount.into_atoms() as i64;
+
+        let timestamp = base_timestamp + day as u64 * day_secs + idx as u64;
+        let block = Block::new(
+            vec![tx],
+            chain_config.genesis_block_id(),
+            BlockTimestamp::from_int_seconds(timestamp),
+            ConsensusData::None,
+            BlockReward::new(vec![]),
+        )
+        .unwrap();
+        scan_wallet(&mut wallet, BlockHeight::new(idx as u64 + 1), vec![block]);
+    }
+
+    let summary = wallet
+        .get_account_activity_summary(DEFAULT_ACCOUNT_INDEX, BlockTimestamp::from_int_seconds(0))
+        .unwrap();
+    let expected_summary = vec![
+        DailyActivity {
+            date: "2023-11-14".to_owned(),
+            tx_count: 3,
+            net_coin_flow: expected_flows[0],
+        },
+        DailyActivity {
+            date: "2023-11-15".to_owned(),
+            tx_count: 4,
+            net_coin_flow: expected_flows[1],
+        },
+        DailyActivity {
+            date: "2023-11-16".to_owned(),
+            tx_count: 3,
+            net_coin_flow: expected_flows[2],
+        },
+    ];
+    assert_eq!(summary, expected_summary);
+
+    // Transactions before the start of the period are not counted
+    let summary = wallet
+        .get_account_activity_summary(
+            DEFAULT_ACCOUNT_INDEX,
+            BlockTimestamp::from_int_seconds(base_timestamp + day_secs),
+        )
+        .unwrap();
+    assert_eq!(summary, expected_summary[1..]);
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn wallet_check_integrity(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+
+    let mut wallet = create_wallet(chain_config.clone());
+    let block1_amount = Amount::from_atoms(rng.gen_range(100000..1000000));
+    let (address, block1) = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
+
+    let tx = wallet
+        .create_transaction_to_addresses(
+            DEFAULT_ACCOUNT_INDEX,
+            [gen_random_transfer(&mut rng, Amount::from_atoms(1))],
+            SelectedInputs::Utxos(vec![]),
+            BTreeMap::new(),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap();
+    let _ = create_block(&chain_config, &mut wallet, vec![tx], Amount::ZERO, 1);
+
+    assert_eq!(wallet.check_integrity(), Ok(vec![]));
+
+    // Corrupt the database
+    let account_id = wallet.get_account(DEFAULT_ACCOUNT_INDEX).unwrap().get_account_id();
+    let best_block_height = get_best_block(&wallet).1;
+    let missing_outpoint = UtxoOutPoint::new(
+        OutPointSourceId::BlockReward(block1.get_id().into()),
+        rng.gen_range(1..100),
+    );
+    let corrupted_tx = SignedTransaction::new(
+        Transaction::new(
+            0,
+            vec![TxInput::Utxo(missing_outpoint.clone())],
+            vec![gen_random_transfer(&mut rng, Amount::from_atoms(1))],
+        )
+        .unwrap(),
+        vec![InputWitness::NoSignature(None)],
+    )
+    .unwrap();
+    let corrupted_tx_id: OutPointSourceId = corrupted_tx.transaction().get_id().into();
+    let corrupted_tx_height = best_block_height.checked_add(rng.gen_range(1..100)).unwrap();
+    let last_used = U31::from_u32(rng.gen_range(1..100)).unwrap();
+
+    // Spends an output of a transaction that isn't in the wallet and pays to someone else, so it
+    // could only have been stored because of the output it spends, which is lost
+    let dangling_outpoint = UtxoOutPoint::new(
+        OutPointSourceId::Transaction(Id::new(H256::random_using(&mut rng))),
+        rng.gen_range(0..100),
+    );
+    let dangling_tx = SignedTransaction::new(
+        Transaction::new(
+            0,
+            vec![TxInput::Utxo(dangling_outpoint.clone())],
+            vec![gen_random_transfer(&mut rng, Amount::from_atoms(1))],
+        )
+        .unwrap(),
+        vec![InputWitness::NoSignature(None)],
+    )
+    .unwrap();
+    let dangling_tx_id: OutPointSourceId = dangling_tx.transaction().get_id().into();
+    let account_counter = wallet
+        .db
+        .transaction_ro()
+        .unwrap()
+        .get_account_unconfirmed_tx_counter(&account_id)
+        .unwrap();
+    let dangling_tx_counter = account_counter.unwrap() + rng.gen_range(1..100);
+
+    // Spends an output that isn't in the wallet too, but pays to the wallet, which is fine
+    let incoming_tx = SignedTransaction::new(
+        Transaction::new(
+            0,
+            vec![TxInput::Utxo(UtxoOutPoint::new(
+                OutPointSourceId::Transaction(Id::new(H256::random_using(&mut rng))),
+                rng.gen_range(0..100),
+            ))],
+            vec![TxOutput::Transfer(
+                OutputValue::Coin(Amount::from_atoms(1)),
+                address.into_object(),
+            )],
+        )
+        .unwrap(),
+        vec![InputWitness::NoSignature(None)],
+    )
+    .unwrap();
+    let incoming_tx_id: OutPointSourceId = incoming_tx.transaction().get_id().into();
+
+    {
+        let mut db_tx = wallet.db.transaction_rw(None).unwrap();
+        db_tx
+            .set_transaction(
+                &AccountWalletTxId::new(account_id.clone(), dangling_tx_id.clone()),
+                &wallet_types::WalletTx::Tx(TxData::new(
+                    dangling_tx,
+                    TxState::Inactive(dangling_tx_counter),
+                )),
+            )
+            .unwrap();
+        db_tx
+            .set_transaction(
+                &AccountWalletTxId::new(account_id.clone(), incoming_tx_id),
+                &wallet_types::WalletTx::Tx(TxData::new(
+                    incoming_tx,
+                    TxState::Confirmed(best_block_height, BlockTimestamp::from_int_seconds(0), 1),
+                )),
+            )
+            .unwrap();
+        db_tx.set_storage_version(WALLET_VERSION_V6).unwrap();
+        db_tx
+            .set_transaction(
+                &AccountWalletTxId::new(account_id.clone(), corrupted_tx_id.clone()),
+                &wallet_types::WalletTx::Tx(TxData::new(
+                    corrupted_tx,
+                    TxState::Confirmed(corrupted_tx_height, BlockTimestamp::from_int_seconds(0), 0),
+                )),
+            )
+            .unwrap();
+        db_tx
+            .set_keychain_usage_state(
+                &AccountKeyPurposeId::new(account_id, KeyPurpose::Change),
+                &KeychainUsageState::new(Some(last_used), None),
+            )
+            .unwrap();
+        db_tx.commit().unwrap();
+    }
+
+    let violations = wallet.check_integrity().unwrap();
+    let expected_violations = [
+        IntegrityViolation::WalletVersionMismatch(WALLET_VERSION_V6),
+        IntegrityViolation::MissingSpentOutput {
+            account_index: DEFAULT_ACCOUNT_INDEX,
+            tx_id: corrupted_tx_id.clone(),
+            outpoint: missing_outpoint,
+        },
+        IntegrityViolation::TransactionAboveBestBlock {
+            account_index: DEFAULT_ACCOUNT_INDEX,
+            tx_id: corrupted_tx_id,
+            tx_height: corrupted_tx_height,
+            best_block_height,
+        },
+        IntegrityViolation::MissingSpentOutput {
+            account_index: DEFAULT_ACCOUNT_INDEX,
+            tx_id: dangling_tx_id.clone(),
+            outpoint: dangling_outpoint,
+        },
+        IntegrityViolation::UnconfirmedTxCounterBelowUsed {
+            account_index: DEFAULT_ACCOUNT_INDEX,
+            tx_id: dangling_tx_id,
+            tx_counter: dangling_tx_counter,
+            account_counter,
+        },
+        IntegrityViolation::KeyUsageAboveIssued {
+            account_index: DEFAULT_ACCOUNT_INDEX,
+            purpose: KeyPurpose::Change,
+            last_used,
+            last_issued: None,
+        },
+    ];
+    assert_eq!(violations.len(), expected_violations.len());
+    for violation in expected_violations {
+        assert!(violations.contains(&violation), "{violation:?} not found");
+    }
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -1335,32 +1806,192 @@ fn wallet_transaction_with_fees(#[case] seed: Seed) {
     assert!(coin_balance <= ((block1_amount - amount_to_transfer).unwrap() - fee.into()).unwrap());
 }
 
-#[test]
-fn lock_wallet_fail_empty_password() {
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn create_cpfp_transaction(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
     let chain_config = Arc::new(create_mainnet());
 
-    let mut wallet = create_wallet(chain_config);
-    let empty_password = Some(String::new());
+    let mut wallet = create_wallet(chain_config.clone());
+
+    let block1_amount = Amount::from_atoms(rng.gen_range(100_000_000..1_000_000_000));
+    let _ = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
+
+    // the parent pays a low fee and sends the change back to the wallet
+    let parent_fee_rate = FeeRate::from_amount_per_kb(Amount::from_atoms(rng.gen_range(0..1000)));
+    let amount_to_transfer = Amount::from_atoms(rng.gen_range(1..=block1_amount.into_atoms() / 2));
+    let parent = wallet
+        .create_transaction_to_addresses(
+            DEFAULT_ACCOUNT_INDEX,
+            [gen_random_transfer(&mut rng, amount_to_transfer)],
+            SelectedInputs::Utxos(vec![]),
+            BTreeMap::new(),
+            parent_fee_rate,
+            parent_fee_rate,
+        )
+        .unwrap();
+    let parent_id = parent.transaction().get_id();
+    wallet
+        .add_account_unconfirmed_tx(DEFAULT_ACCOUNT_INDEX, parent.clone(), &WalletEventsNoOp)
+        .unwrap();
+
+    let coin_amount = |output: &TxOutput| match output {
+        TxOutput::Transfer(value, _) | TxOutput::LockThenTransfer(value, _, _) => {
+            value.coin_amount().unwrap()
+        }
+        _ => panic!("unexpected output"),
+    };
+    let parent_outputs_amount =
+        parent.outputs().iter().map(coin_amount).sum::<Option<Amount>>().unwrap();
+    let parent_fee = (block1_amount - parent_outputs_amount).unwrap();
+
+    let target_fee_rate =
+        FeeRate::from_amount_per_kb(Amount::from_atoms(rng.gen_range(100_000..1_000_000)));
+    let child = wallet
+        .create_cpfp_transaction(DEFAULT_ACCOUNT_INDEX, parent_id, None, target_fee_rate)
+        .unwrap();
+
+    // the child spends an output of the parent
+    assert_eq!(child.inputs().len(), 1);
+    let spent_output = match &child.inputs()[0] {
+        TxInput::Utxo(outpoint) => {
+            assert_eq!(outpoint.source_id(), parent_id.into());
+            &parent.outputs()[outpoint.output_index() as usize]
+        }
+        _ => panic!("unexpected input"),
+    };
+    let child_outputs_amount =
+        child.outputs().iter().map(coin_amount).sum::<Option<Amount>>().unwrap();
+    let child_fee = (coin_amount(spent_output) - child_outputs_amount).unwrap();
+
+    // the child covers the deficit of the parent on top of its own fee
+    let parent_required_fee: Amount = target_fee_rate
+        .compute_fee(serialization::Encode::encoded_size(&parent))
+        .unwrap()
+        .into();
+    assert!(parent_fee < parent_required_fee);
+    let parent_deficit = (parent_required_fee - parent_fee).unwrap();
+    let child_min_fee: Amount = target_fee_rate
+        .compute_fee(serialization::Encode::encoded_size(child.transaction()))
+        .unwrap()
+        .into();
+    assert!(child_fee >= (parent_deficit + child_min_fee).unwrap());
+
+    // once the parent is confirmed there is nothing to speed up
+    let _ = create_block(&chain_config, &mut wallet, vec![parent], Amount::ZERO, 1);
     assert_eq!(
-        wallet.encrypt_wallet(&empty_password),
-        Err(WalletError::DatabaseError(
-            wallet_storage::Error::WalletEmptyPassword
-        ))
+        wallet.create_cpfp_transaction(DEFAULT_ACCOUNT_INDEX, parent_id, None, target_fee_rate),
+        Err(WalletError::CpfpParentNotUnconfirmed(parent_id))
     );
 }
 
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
-fn spend_from_user_specified_utxos(#[case] seed: Seed) {
+fn create_cpfp_transaction_for_foreign_parent(#[case] seed: Seed) {
     let mut rng = make_seedable_rng(seed);
     let chain_config = Arc::new(create_mainnet());
 
     let mut wallet = create_wallet(chain_config.clone());
 
-    // Generate a new block which sends reward to the wallet
-    let utxo_amount = Amount::from_atoms(rng.gen_range(100..10000));
-    let reward_outputs = (0..10)
+    // the parent is created by someone else and spends an output unknown to the wallet
+    let parent_amount = Amount::from_atoms(rng.gen_range(100_000_000..1_000_000_000));
+    let destination = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+    let parent = SignedTransaction::new(
+        Transaction::new(
+            0,
+            vec![TxInput::from_utxo(
+                OutPointSourceId::Transaction(Id::new(H256::random_using(&mut rng))),
+                0,
+            )],
+            vec![TxOutput::Transfer(OutputValue::Coin(parent_amount), destination.into_object())],
+        )
+        .unwrap(),
+        vec![InputWitness::NoSignature(None)],
+    )
+    .unwrap();
+    let parent_id = parent.transaction().get_id();
+    wallet
+        .import_external_transaction(
+            DEFAULT_ACCOUNT_INDEX,
+            parent.clone(),
+            None,
+            &WalletEventsNoOp,
+        )
+        .unwrap();
+
+    let target_fee_rate =
+        FeeRate::from_amount_per_kb(Amount::from_atoms(rng.gen_range(100_000..1_000_000)));
+    let parent_required_fee: Amount = target_fee_rate
+        .compute_fee(serialization::Encode::encoded_size(&parent))
+        .unwrap()
+        .into();
+    let child_fee = |child: &SignedTransaction| {
+        let outputs_amount = child
+            .outputs()
+            .iter()
+            .map(|output| match output {
+                TxOutput::Transfer(value, _) => value.coin_amount().unwrap(),
+                _ => panic!("unexpected output"),
+            })
+            .sum::<Option<Amount>>()
+            .unwrap();
+        (parent_amount - outputs_amount).unwrap()
+    };
+    let child_min_fee = |child: &SignedTransaction| -> Amount {
+        target_fee_rate
+            .compute_fee(serialization::Encode::encoded_size(child.transaction()))
+            .unwrap()
+            .into()
+    };
+
+    // the fee of the parent is unknown, so the child pays for the whole parent
+    let child = wallet
+        .create_cpfp_transaction(DEFAULT_ACCOUNT_INDEX, parent_id, None, target_fee_rate)
+        .unwrap();
+    let unknown_parent_fee_child_fee = child_fee(&child);
+    assert!(unknown_parent_fee_child_fee >= (parent_required_fee + child_min_fee(&child)).unwrap());
+
+    // with the fee of the parent known, e.g. from the mempool, the child only pays for itself
+    let child = wallet
+        .create_cpfp_transaction(
+            DEFAULT_ACCOUNT_INDEX,
+            parent_id,
+            Some(parent_required_fee),
+            target_fee_rate,
+        )
+        .unwrap();
+    assert!(child_fee(&child) >= child_min_fee(&child));
+    assert!(child_fee(&child) < unknown_parent_fee_child_fee);
+}
+
+#[test]
+fn lock_wallet_fail_empty_password() {
+    let chain_config = Arc::new(create_mainnet());
+
+    let mut wallet = create_wallet(chain_config);
+    let empty_password = Some(String::new());
+    assert_eq!(
+        wallet.encrypt_wallet(&empty_password),
+        Err(WalletError::DatabaseError(
+            wallet_storage::Error::WalletEmptyPassword
+        ))
+    );
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn spend_from_user_specified_utxos(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+
+    let mut wallet = create_wallet(chain_config.clone());
+
+    // Generate a new block which sends reward to the wallet
+    let utxo_amount = Amount::from_atoms(rng.gen_range(100..10000));
+    let reward_outputs = (0..10)
         .map(|idx| {
             let address = get_address(
                 &chain_config,
@@ -1514,6 +2145,7 @@ fn create_stake_pool_and_list_pool_ids(#[case] seed: Seed) {
                 margin_ratio_per_thousand: PerThousand::new_from_rng(&mut rng),
                 cost_per_block: Amount::ZERO,
                 decommission_key: decommission_key.as_object().clone(),
+                vrf_public_key: None,
             },
         )
         .unwrap();
@@ -1624,6 +2256,59 @@ fn create_stake_pool_and_list_pool_ids(#[case] seed: Seed) {
     assert_eq!(coin_balance, pool_amount,);
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn create_stake_pool_with_external_vrf_public_key(#[case] seed: Seed) {
+    use crypto::vrf::{VRFKeyKind, VRFPrivateKey};
+
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_regtest());
+
+    let mut wallet = create_wallet(chain_config.clone());
+
+    let block1_amount = Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000));
+    let _ = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
+
+    let (_, external_vrf_public_key) =
+        VRFPrivateKey::new_from_rng(&mut rng, VRFKeyKind::Schnorrkel);
+
+    let stake_pool_transaction = wallet
+        .create_stake_pool_tx(
+            DEFAULT_ACCOUNT_INDEX,
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            StakePoolDataArguments {
+                amount: block1_amount,
+                margin_ratio_per_thousand: PerThousand::new_from_rng(&mut rng),
+                cost_per_block: Amount::ZERO,
+                decommission_key: Destination::AnyoneCanSpend,
+                vrf_public_key: Some(external_vrf_public_key.clone()),
+            },
+        )
+        .unwrap();
+
+    match stake_pool_transaction.transaction().outputs().first().unwrap() {
+        TxOutput::CreateStakePool(_, data) => {
+            assert_eq!(data.vrf_public_key(), &external_vrf_public_key);
+        }
+        _ => panic!("wrong TxOutput type"),
+    };
+
+    let _ = create_block(
+        &chain_config,
+        &mut wallet,
+        vec![stake_pool_transaction],
+        Amount::ZERO,
+        1,
+    );
+
+    let pool_ids = wallet.get_pool_ids(DEFAULT_ACCOUNT_INDEX, WalletPoolsFilter::All).unwrap();
+    assert_eq!(pool_ids.len(), 1);
+    let (_, pool_data) = pool_ids.first().unwrap();
+    assert_eq!(pool_data.vrf_public_key, external_vrf_public_key);
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -1657,6 +2342,7 @@ fn reset_keys_after_failed_transaction(#[case] seed: Seed) {
             margin_ratio_per_thousand: PerThousand::new_from_rng(&mut rng),
             cost_per_block: Amount::ZERO,
             decommission_key: Destination::AnyoneCanSpend,
+            vrf_public_key: None,
         },
     );
     // check that result is an error and we last issued address is still the same
@@ -1777,6 +2463,7 @@ fn create_spend_from_delegations(#[case] seed: Seed) {
                 margin_ratio_per_thousand: PerThousand::new_from_rng(&mut rng),
                 cost_per_block: Amount::ZERO,
                 decommission_key: Destination::AnyoneCanSpend,
+                vrf_public_key: None,
             },
         )
         .unwrap();
@@ -2265,6 +2952,84 @@ fn check_tokens_v0_are_ignored(#[case] seed: Seed) {
     assert_eq!(token_balances.len(), 0);
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn list_token_authorities(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_regtest());
+
+    let mut wallet = create_wallet(chain_config.clone());
+
+    let block1_amount = (Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000))
+        + (chain_config.fungible_token_issuance_fee() * 2).unwrap())
+    .unwrap();
+    let _ = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
+
+    // issue two tokens with authorities owned by the wallet
+    let mut issued_tokens = BTreeMap::new();
+    for block_height in 1..=2 {
+        let authority = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+        let token_issuance = TokenIssuanceV1 {
+            token_ticker: "XXXX".as_bytes().to_vec(),
+            number_of_decimals: rng.gen_range(1..18),
+            metadata_uri: "http://uri".as_bytes().to_vec(),
+            total_supply: common::chain::tokens::TokenTotalSupply::Unlimited,
+            authority: authority.as_object().clone(),
+            is_freezable: common::chain::tokens::IsTokenFreezable::No,
+        };
+
+        let (token_id, token_issuance_transaction) = wallet
+            .issue_new_token(
+                DEFAULT_ACCOUNT_INDEX,
+                TokenIssuance::V1(token_issuance),
+                FeeRate::from_amount_per_kb(Amount::ZERO),
+                FeeRate::from_amount_per_kb(Amount::ZERO),
+            )
+            .unwrap();
+
+        let _ = create_block(
+            &chain_config,
+            &mut wallet,
+            vec![token_issuance_transaction],
+            Amount::ZERO,
+            block_height,
+        );
+        issued_tokens.insert(token_id, authority.into_object());
+    }
+
+    // receive some tokens issued by someone else
+    let foreign_token_id = TokenId::random_using(&mut rng);
+    let address = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+    let foreign_token_output = TxOutput::Transfer(
+        OutputValue::TokenV1(foreign_token_id, Amount::from_atoms(rng.gen_range(1..1000))),
+        address.into_object(),
+    );
+    let foreign_token_tx = SignedTransaction::new(
+        Transaction::new(0, vec![], vec![foreign_token_output]).unwrap(),
+        vec![],
+    )
+    .unwrap();
+    let _ = create_block(
+        &chain_config,
+        &mut wallet,
+        vec![foreign_token_tx],
+        Amount::ZERO,
+        3,
+    );
+
+    let (_, token_balances) = get_currency_balances(&wallet);
+    assert!(token_balances.iter().any(|(token_id, _)| *token_id == foreign_token_id));
+
+    let token_authorities = wallet
+        .list_token_authorities(DEFAULT_ACCOUNT_INDEX)
+        .unwrap()
+        .into_iter()
+        .map(|token| (token.token_id, token.authority))
+        .collect::<BTreeMap<_, _>>();
+    assert_eq!(token_authorities, issued_tokens);
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -2552,32 +3317,23 @@ fn freeze_and_unfreeze_tokens(#[case] seed: Seed) {
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
-fn change_token_supply_fixed(#[case] seed: Seed) {
+fn send_all_tokens(#[case] seed: Seed) {
     let mut rng = make_seedable_rng(seed);
     let chain_config = Arc::new(create_mainnet());
 
     let mut wallet = create_wallet(chain_config.clone());
 
-    let coin_balance = get_coin_balance(&wallet);
-    assert_eq!(coin_balance, Amount::ZERO);
-
-    // Generate a new block which sends reward to the wallet
     let block1_amount = (Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000))
         + chain_config.fungible_token_issuance_fee())
     .unwrap();
-
     let _ = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
 
-    let coin_balance = get_coin_balance(&wallet);
-    assert_eq!(coin_balance, block1_amount);
-
-    let fixed_max_amount = Amount::from_atoms(rng.gen_range(1..100000));
     let address2 = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
     let token_issuance = TokenIssuanceV1 {
         token_ticker: "XXXX".as_bytes().to_vec(),
         number_of_decimals: rng.gen_range(1..18),
         metadata_uri: "http://uri".as_bytes().to_vec(),
-        total_supply: common::chain::tokens::TokenTotalSupply::Fixed(fixed_max_amount),
+        total_supply: common::chain::tokens::TokenTotalSupply::Unlimited,
         authority: address2.as_object().clone(),
         is_freezable: common::chain::tokens::IsTokenFreezable::No,
     };
@@ -2595,7 +3351,7 @@ fn change_token_supply_fixed(#[case] seed: Seed) {
         &chain_config,
         &mut wallet,
         vec![token_issuance_transaction],
-        block1_amount,
+        block2_amount,
         1,
     );
 
@@ -2612,195 +3368,508 @@ fn change_token_supply_fixed(#[case] seed: Seed) {
         token_issuance.authority,
     );
 
+    // Mint tokens in two separate transactions, so that there are multiple token utxos
+    let mut confirmed_token_amount = Amount::ZERO;
+    for height in 2..4 {
+        let unconfirmed_token_info =
+            wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
+        let token_amount_to_mint = Amount::from_atoms(rng.gen_range(1..10000));
+        let mint_transaction = wallet
+            .mint_tokens(
+                DEFAULT_ACCOUNT_INDEX,
+                &unconfirmed_token_info,
+                token_amount_to_mint,
+                address2.clone(),
+                FeeRate::from_amount_per_kb(Amount::ZERO),
+                FeeRate::from_amount_per_kb(Amount::ZERO),
+            )
+            .unwrap();
+        let _ = create_block(
+            &chain_config,
+            &mut wallet,
+            vec![mint_transaction],
+            block2_amount,
+            height,
+        );
+        confirmed_token_amount = (confirmed_token_amount + token_amount_to_mint).unwrap();
+        token_info.circulating_supply = confirmed_token_amount;
+    }
+
+    // One more mint that stays unconfirmed and must not be spent
     let unconfirmed_token_info =
         wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
-
-    assert_eq!(
-        unconfirmed_token_info.authority().unwrap(),
-        address2.as_object()
-    );
-
-    assert_eq!(
-        unconfirmed_token_info.get_next_nonce().unwrap(),
-        AccountNonce::new(0)
-    );
-
-    assert_eq!(
-        unconfirmed_token_info.current_supply().unwrap(),
-        Amount::ZERO,
-    );
-
-    let token_amount_to_mint = Amount::from_atoms(rng.gen_range(1..=fixed_max_amount.into_atoms()));
-    let mint_transaction = wallet
+    let unconfirmed_mint_transaction = wallet
         .mint_tokens(
             DEFAULT_ACCOUNT_INDEX,
             &unconfirmed_token_info,
-            token_amount_to_mint,
+            Amount::from_atoms(rng.gen_range(1..10000)),
             address2.clone(),
             FeeRate::from_amount_per_kb(Amount::ZERO),
             FeeRate::from_amount_per_kb(Amount::ZERO),
         )
         .unwrap();
+    wallet
+        .add_unconfirmed_tx(unconfirmed_mint_transaction, &WalletEventsNoOp)
+        .unwrap();
 
-    wallet.add_unconfirmed_tx(mint_transaction.clone(), &WalletEventsNoOp).unwrap();
-
-    let unconfirmed_token_info =
-        wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
-
-    // Try to mint more then the fixed maximum
-    let leftover = (fixed_max_amount - token_amount_to_mint).unwrap();
-    let token_amount_to_mint_more_than_maximum =
-        (leftover + Amount::from_atoms(rng.gen_range(1..1000))).unwrap();
-    let err = wallet
-        .mint_tokens(
+    let destination = Destination::PublicKey(
+        crypto::key::PrivateKey::new_from_rng(&mut rng, crypto::key::KeyKind::Secp256k1Schnorr).1,
+    );
+    let address = Address::new(&chain_config, destination.clone()).unwrap();
+    let transaction = wallet
+        .create_transaction_to_send_all_tokens(
             DEFAULT_ACCOUNT_INDEX,
-            &unconfirmed_token_info,
-            token_amount_to_mint_more_than_maximum,
-            address2.clone(),
+            issued_token_id,
+            address,
             FeeRate::from_amount_per_kb(Amount::ZERO),
             FeeRate::from_amount_per_kb(Amount::ZERO),
         )
-        .unwrap_err();
+        .unwrap();
 
+    let token_outputs = transaction
+        .transaction()
+        .outputs()
+        .iter()
+        .filter(|output| match output {
+            TxOutput::Transfer(value, _) | TxOutput::LockThenTransfer(value, _, _) => {
+                value.coin_amount().is_none()
+            }
+            _ => false,
+        })
+        .collect::<Vec<_>>();
     assert_eq!(
-        err,
-        WalletError::CannotMintFixedTokenSupply(
-            fixed_max_amount,
-            token_amount_to_mint,
-            token_amount_to_mint_more_than_maximum
-        )
+        token_outputs,
+        vec![&TxOutput::Transfer(
+            OutputValue::TokenV1(issued_token_id, confirmed_token_amount),
+            destination
+        )]
     );
+}
 
+#[test]
+fn token_transfer_fee_rate_multiplier() {
+    let fee_rate_with_multiplier = |multiplier: f64, atoms_per_kb: u128| {
+        let chain_config =
+            Builder::new(ChainType::Mainnet).token_fee_multiplier(multiplier).build();
+        token_transfer_fee_rate(&chain_config, FeeRate::from_atoms_per_kb(atoms_per_kb))
+            .map(|fee_rate| fee_rate.atoms_per_kb())
+    };
+
+    assert_eq!(fee_rate_with_multiplier(1.0, 1000), Ok(1000));
+    assert_eq!(fee_rate_with_multiplier(1.25, 1000), Ok(1250));
+    assert_eq!(fee_rate_with_multiplier(2.0, 1000), Ok(2000));
+    assert_eq!(fee_rate_with_multiplier(3.5, 1000), Ok(3500));
+    assert_eq!(fee_rate_with_multiplier(10.0, 0), Ok(0));
+    // The result is rounded up
+    assert_eq!(fee_rate_with_multiplier(1.001, 1), Ok(2));
+    assert_eq!(fee_rate_with_multiplier(1.5, 1001), Ok(1502));
+
+    assert_eq!(fee_rate_with_multiplier(1.0, u128::MAX), Ok(u128::MAX));
     assert_eq!(
-        unconfirmed_token_info.get_next_nonce().unwrap(),
-        AccountNonce::new(1)
+        fee_rate_with_multiplier(2.0, u128::MAX),
+        Err(WalletError::FeeAmountOverflow)
     );
+}
 
-    assert_eq!(
-        unconfirmed_token_info.current_supply().unwrap(),
-        token_amount_to_mint,
+#[rstest]
+#[case(0.5)]
+#[case(-1.0)]
+#[case(f64::NAN)]
+#[case(f64::INFINITY)]
+#[should_panic(expected = "Invalid token fee multiplier")]
+fn invalid_token_fee_multiplier(#[case] multiplier: f64) {
+    let _ = Builder::new(ChainType::Mainnet).token_fee_multiplier(multiplier);
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn token_transfer_fee_multiplier(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let destination = Destination::PublicKey(
+        crypto::key::PrivateKey::new_from_rng(&mut rng, crypto::key::KeyKind::Secp256k1Schnorr).1,
     );
+    // A multiple of 1/4, so that the increased fee rate is exact
+    let multiplier = f64::from(rng.gen_range(6..=20u8)) / 4.0;
+    let chain_config =
+        Arc::new(Builder::new(ChainType::Mainnet).token_fee_multiplier(multiplier).build());
+    let atoms_per_kb = 100_000;
+    let fee_rate = FeeRate::from_atoms_per_kb(atoms_per_kb);
+    let token_fee_rate = FeeRate::from_atoms_per_kb((atoms_per_kb as f64 * multiplier) as u128);
 
-    // test abandoning a transaction
-    {
-        wallet
-            .abandon_transaction(
-                DEFAULT_ACCOUNT_INDEX,
-                mint_transaction.transaction().get_id(),
-            )
-            .unwrap();
+    let mut wallet = create_wallet(chain_config.clone());
 
-        let unconfirmed_token_info =
-            wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
+    let block1_amount = (Amount::from_atoms(NETWORK_FEE * 1000)
+        + chain_config.fungible_token_issuance_fee())
+    .unwrap();
+    let _ = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
 
-        assert_eq!(
-            unconfirmed_token_info.get_next_nonce().unwrap(),
-            AccountNonce::new(0)
-        );
-
-        assert_eq!(
-            unconfirmed_token_info.current_supply().unwrap(),
-            Amount::ZERO,
-        );
-    }
+    let address2 = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+    let token_issuance = TokenIssuanceV1 {
+        token_ticker: "XXXX".as_bytes().to_vec(),
+        number_of_decimals: 8,
+        metadata_uri: "http://uri".as_bytes().to_vec(),
+        total_supply: common::chain::tokens::TokenTotalSupply::Unlimited,
+        authority: address2.as_object().clone(),
+        is_freezable: common::chain::tokens::IsTokenFreezable::No,
+    };
+    let (issued_token_id, token_issuance_transaction) = wallet
+        .issue_new_token(
+            DEFAULT_ACCOUNT_INDEX,
+            TokenIssuance::V1(token_issuance.clone()),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap();
 
+    let block2_amount = chain_config.token_supply_change_fee(BlockHeight::zero());
     let _ = create_block(
         &chain_config,
         &mut wallet,
-        vec![mint_transaction],
+        vec![token_issuance_transaction],
         block2_amount,
-        2,
+        1,
     );
-    token_info.circulating_supply = unconfirmed_token_info.current_supply().unwrap();
 
+    let freezable = token_issuance.is_freezable.as_bool();
+    let token_info = RPCFungibleTokenInfo::new(
+        issued_token_id,
+        token_issuance.token_ticker,
+        token_issuance.number_of_decimals,
+        token_issuance.metadata_uri,
+        Amount::ZERO,
+        token_issuance.total_supply.into(),
+        false,
+        RPCIsTokenFrozen::NotFrozen { freezable },
+        token_issuance.authority,
+    );
     let unconfirmed_token_info =
         wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
-
-    assert_eq!(
-        unconfirmed_token_info.get_next_nonce().unwrap(),
-        AccountNonce::new(1)
-    );
-
-    assert_eq!(
-        unconfirmed_token_info.current_supply().unwrap(),
-        token_amount_to_mint,
-    );
-
-    // Try to unmint more than the current total supply
-    let token_amount_to_unmint = (token_amount_to_mint + Amount::from_atoms(1)).unwrap();
-    let err = wallet
-        .unmint_tokens(
+    let mint_transaction = wallet
+        .mint_tokens(
             DEFAULT_ACCOUNT_INDEX,
             &unconfirmed_token_info,
-            token_amount_to_unmint,
+            Amount::from_atoms(10000),
+            address2.clone(),
             FeeRate::from_amount_per_kb(Amount::ZERO),
             FeeRate::from_amount_per_kb(Amount::ZERO),
         )
-        .unwrap_err();
-    assert_eq!(
-        err,
-        WalletError::CannotUnmintTokenSupply(token_amount_to_unmint, token_amount_to_mint)
+        .unwrap();
+    let _ = create_block(
+        &chain_config,
+        &mut wallet,
+        vec![mint_transaction],
+        block2_amount,
+        2,
     );
 
-    let token_amount_to_unmint =
-        Amount::from_atoms(rng.gen_range(1..=token_amount_to_mint.into_atoms()));
-    let unmint_transaction = wallet
-        .unmint_tokens(
+    let amount = Amount::from_atoms(4000);
+    let output = TxOutput::Transfer(
+        OutputValue::TokenV1(issued_token_id, amount),
+        destination.clone(),
+    );
+
+    let estimated_fee = wallet
+        .compute_token_transfer_fee(
             DEFAULT_ACCOUNT_INDEX,
-            &unconfirmed_token_info,
-            token_amount_to_unmint,
-            FeeRate::from_amount_per_kb(Amount::ZERO),
-            FeeRate::from_amount_per_kb(Amount::ZERO),
+            issued_token_id,
+            amount,
+            destination.clone(),
+            fee_rate,
         )
         .unwrap();
 
-    wallet
-        .add_unconfirmed_tx(unmint_transaction.clone(), &WalletEventsNoOp)
+    let utxos: BTreeMap<_, _> = wallet
+        .get_utxos(
+            DEFAULT_ACCOUNT_INDEX,
+            UtxoType::Transfer | UtxoType::LockThenTransfer,
+            UtxoState::Confirmed.into(),
+            WithLocked::Unlocked,
+        )
+        .unwrap()
+        .into_iter()
+        .map(|(outpoint, output, _)| (outpoint, output))
+        .collect();
+
+    let transaction = wallet
+        .create_transaction_to_addresses(
+            DEFAULT_ACCOUNT_INDEX,
+            [output],
+            SelectedInputs::Utxos(vec![]),
+            BTreeMap::new(),
+            fee_rate,
+            fee_rate,
+        )
         .unwrap();
-    let unconfirmed_token_info =
-        wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
 
+    let coin_amount = |output: &TxOutput| match output {
+        TxOutput::Transfer(OutputValue::Coin(amount), _)
+        | TxOutput::LockThenTransfer(OutputValue::Coin(amount), _, _) => *amount,
+        _ => Amount::ZERO,
+    };
+    let inputs_amount = transaction
+        .transaction()
+        .inputs()
+        .iter()
+        .map(|input| match input {
+            TxInput::Utxo(outpoint) => coin_amount(&utxos[outpoint]),
+            TxInput::Account(_) | TxInput::AccountCommand(_, _) => panic!("unexpected input"),
+        })
+        .fold(Amount::ZERO, |total, amount| (total + amount).unwrap());
+    let outputs_amount = transaction
+        .transaction()
+        .outputs()
+        .iter()
+        .map(coin_amount)
+        .fold(Amount::ZERO, |total, amount| (total + amount).unwrap());
+    let paid_fee = (inputs_amount - outputs_amount).unwrap();
+
+    // The transaction pays the increased fee rate for its actual size
+    let tx_size = serialization::Encode::encoded_size(&transaction);
+    let normal_fee: Amount = fee_rate.compute_fee(tx_size).unwrap().into();
+    assert!(paid_fee >= token_fee_rate.compute_fee(tx_size).unwrap().into());
+
+    // The estimate is composed the same way, though its inputs may have been selected
+    // differently, so the size may differ by a few bytes
+    let token_fee: Amount = token_fee_rate.compute_fee(tx_size).unwrap().into();
+    assert!(estimated_fee > normal_fee);
+    assert!(
+        estimated_fee.into_atoms().abs_diff(token_fee.into_atoms()) < token_fee.into_atoms() / 4
+    );
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn can_mint_fixed_supply(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+
+    let mut wallet = create_wallet(chain_config.clone());
+
+    let block1_amount = (Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000))
+        + chain_config.fungible_token_issuance_fee())
+    .unwrap();
+    let _ = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
+
+    let fixed_max_amount = Amount::from_atoms(rng.gen_range(2..100000));
+    let address2 = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+    let token_issuance = TokenIssuanceV1 {
+        token_ticker: "XXXX".as_bytes().to_vec(),
+        number_of_decimals: rng.gen_range(1..18),
+        metadata_uri: "http://uri".as_bytes().to_vec(),
+        total_supply: common::chain::tokens::TokenTotalSupply::Fixed(fixed_max_amount),
+        authority: address2.as_object().clone(),
+        is_freezable: common::chain::tokens::IsTokenFreezable::No,
+    };
+    let (issued_token_id, token_issuance_transaction) = wallet
+        .issue_new_token(
+            DEFAULT_ACCOUNT_INDEX,
+            TokenIssuance::V1(token_issuance.clone()),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap();
     let _ = create_block(
         &chain_config,
         &mut wallet,
-        vec![unmint_transaction],
-        block2_amount,
-        3,
+        vec![token_issuance_transaction],
+        block1_amount,
+        1,
+    );
+
+    let freezable = token_issuance.is_freezable.as_bool();
+    let token_info = RPCFungibleTokenInfo::new(
+        issued_token_id,
+        token_issuance.token_ticker,
+        token_issuance.number_of_decimals,
+        token_issuance.metadata_uri,
+        Amount::ZERO,
+        token_issuance.total_supply.into(),
+        false,
+        RPCIsTokenFrozen::NotFrozen { freezable },
+        token_issuance.authority,
+    );
+
+    // under the cap
+    let under_cap = Amount::from_atoms(rng.gen_range(1..fixed_max_amount.into_atoms()));
+    wallet.can_mint(DEFAULT_ACCOUNT_INDEX, &token_info, under_cap).unwrap();
+
+    // exactly at the cap
+    wallet.can_mint(DEFAULT_ACCOUNT_INDEX, &token_info, fixed_max_amount).unwrap();
+
+    // over the cap
+    let over_cap = (fixed_max_amount + Amount::from_atoms(rng.gen_range(1..1000))).unwrap();
+    assert_eq!(
+        wallet.can_mint(DEFAULT_ACCOUNT_INDEX, &token_info, over_cap),
+        Err(WalletError::CannotMintFixedTokenSupply(
+            fixed_max_amount,
+            Amount::ZERO,
+            over_cap
+        ))
+    );
+
+    // way over the cap, though the new supply still fits into an amount
+    assert_eq!(
+        wallet.can_mint(DEFAULT_ACCOUNT_INDEX, &token_info, Amount::MAX),
+        Err(WalletError::CannotMintFixedTokenSupply(
+            fixed_max_amount,
+            Amount::ZERO,
+            Amount::MAX
+        ))
     );
-    token_info.circulating_supply = unconfirmed_token_info.current_supply().unwrap();
 
+    // the checks take the unconfirmed mint into account
     let unconfirmed_token_info =
         wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
+    let mint_transaction = wallet
+        .mint_tokens(
+            DEFAULT_ACCOUNT_INDEX,
+            &unconfirmed_token_info,
+            under_cap,
+            address2.clone(),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap();
+    wallet.add_unconfirmed_tx(mint_transaction, &WalletEventsNoOp).unwrap();
+
+    let leftover = (fixed_max_amount - under_cap).unwrap();
+    wallet.can_mint(DEFAULT_ACCOUNT_INDEX, &token_info, leftover).unwrap();
 
+    let over_leftover = (leftover + Amount::from_atoms(1)).unwrap();
     assert_eq!(
-        unconfirmed_token_info.get_next_nonce().unwrap(),
-        AccountNonce::new(2)
+        wallet.can_mint(DEFAULT_ACCOUNT_INDEX, &token_info, over_leftover),
+        Err(WalletError::CannotMintFixedTokenSupply(
+            fixed_max_amount,
+            under_cap,
+            over_leftover
+        ))
     );
 
+    // overflow, as the current supply is not zero anymore
+    let overflowing = (Amount::MAX - under_cap).and_then(|amount| amount + Amount::from_atoms(1));
     assert_eq!(
-        unconfirmed_token_info.current_supply().unwrap(),
-        (token_amount_to_mint - token_amount_to_unmint).unwrap(),
+        wallet.can_mint(DEFAULT_ACCOUNT_INDEX, &token_info, overflowing.unwrap()),
+        Err(WalletError::OutputAmountOverflow)
     );
     assert_eq!(
-        unconfirmed_token_info.check_can_lock().unwrap_err(),
-        WalletError::CannotLockTokenSupply("Fixed")
+        wallet.can_mint(DEFAULT_ACCOUNT_INDEX, &token_info, Amount::MAX),
+        Err(WalletError::OutputAmountOverflow)
     );
+}
+
+#[rstest]
+#[trace]
+#[case(
+    Seed::from_entropy(),
+    "ticker",
+    TokenIssuanceError::IssueErrorInvalidTickerLength
+)]
+#[case(
+    Seed::from_entropy(),
+    "name",
+    TokenIssuanceError::IssueErrorInvalidNameLength
+)]
+#[case(
+    Seed::from_entropy(),
+    "description",
+    TokenIssuanceError::IssueErrorInvalidDescriptionLength
+)]
+#[case(
+    Seed::from_entropy(),
+    "icon_uri",
+    TokenIssuanceError::IssueErrorIncorrectIconURI
+)]
+#[case(
+    Seed::from_entropy(),
+    "additional_metadata_uri",
+    TokenIssuanceError::IssueErrorIncorrectMetadataURI
+)]
+#[case(
+    Seed::from_entropy(),
+    "media_uri",
+    TokenIssuanceError::IssueErrorIncorrectMediaURI
+)]
+#[case(
+    Seed::from_entropy(),
+    "media_hash",
+    TokenIssuanceError::MediaHashTooLong
+)]
+fn issue_nft_oversized_metadata(
+    #[case] seed: Seed,
+    #[case] field: &str,
+    #[case] expected_error: TokenIssuanceError,
+) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+
+    let mut wallet = create_wallet(chain_config.clone());
+
+    let block1_amount = (Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000))
+        + chain_config.nft_issuance_fee(BlockHeight::zero()))
+    .unwrap();
+    let _ = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
+
+    let address = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+
+    let valid_metadata = Metadata {
+        creator: None,
+        name: "Name".as_bytes().to_vec(),
+        description: "SomeNFT".as_bytes().to_vec(),
+        ticker: "XXXX".as_bytes().to_vec(),
+        icon_uri: DataOrNoVec::from(Some("http://icon".as_bytes().to_vec())),
+        additional_metadata_uri: DataOrNoVec::from(Some("http://metadata".as_bytes().to_vec())),
+        media_uri: DataOrNoVec::from(Some("http://media".as_bytes().to_vec())),
+        media_hash: "123456".as_bytes().to_vec(),
+    };
+
+    let mut too_long = |max_len: usize| -> Vec<u8> {
+        let len = rng.gen_range(max_len + 1..max_len * 2 + 2);
+        "a".repeat(len).into_bytes()
+    };
+    let mut metadata = valid_metadata.clone();
+    match field {
+        "ticker" => metadata.ticker = too_long(chain_config.token_max_ticker_len()),
+        "name" => metadata.name = too_long(chain_config.token_max_name_len()),
+        "description" => metadata.description = too_long(chain_config.token_max_description_len()),
+        "icon_uri" => metadata.icon_uri = Some(too_long(chain_config.token_max_uri_len())).into(),
+        "additional_metadata_uri" => {
+            metadata.additional_metadata_uri =
+                Some(too_long(chain_config.token_max_uri_len())).into()
+        }
+        "media_uri" => metadata.media_uri = Some(too_long(chain_config.token_max_uri_len())).into(),
+        "media_hash" => metadata.media_hash = too_long(chain_config.max_hash_len()),
+        _ => unreachable!(),
+    }
 
     let err = wallet
-        .lock_token_supply(
+        .issue_new_nft(
             DEFAULT_ACCOUNT_INDEX,
-            &unconfirmed_token_info,
+            address.clone(),
+            metadata,
             FeeRate::from_amount_per_kb(Amount::ZERO),
             FeeRate::from_amount_per_kb(Amount::ZERO),
         )
         .unwrap_err();
-    assert_eq!(err, WalletError::CannotLockTokenSupply("Fixed"));
+    assert_eq!(err, WalletError::TokenIssuance(expected_error));
+
+    // the same metadata with every field within the limits is accepted
+    wallet
+        .issue_new_nft(
+            DEFAULT_ACCOUNT_INDEX,
+            address,
+            valid_metadata,
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap();
 }
 
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
-fn change_token_supply_unlimited(#[case] seed: Seed) {
+fn change_token_supply_fixed(#[case] seed: Seed) {
     let mut rng = make_seedable_rng(seed);
     let chain_config = Arc::new(create_mainnet());
 
@@ -2819,12 +3888,13 @@ fn change_token_supply_unlimited(#[case] seed: Seed) {
     let coin_balance = get_coin_balance(&wallet);
     assert_eq!(coin_balance, block1_amount);
 
+    let fixed_max_amount = Amount::from_atoms(rng.gen_range(1..100000));
     let address2 = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
     let token_issuance = TokenIssuanceV1 {
         token_ticker: "XXXX".as_bytes().to_vec(),
         number_of_decimals: rng.gen_range(1..18),
         metadata_uri: "http://uri".as_bytes().to_vec(),
-        total_supply: common::chain::tokens::TokenTotalSupply::Unlimited,
+        total_supply: common::chain::tokens::TokenTotalSupply::Fixed(fixed_max_amount),
         authority: address2.as_object().clone(),
         is_freezable: common::chain::tokens::IsTokenFreezable::No,
     };
@@ -2838,12 +3908,11 @@ fn change_token_supply_unlimited(#[case] seed: Seed) {
         .unwrap();
 
     let block2_amount = chain_config.token_supply_change_fee(BlockHeight::zero());
-
     let _ = create_block(
         &chain_config,
         &mut wallet,
         vec![token_issuance_transaction],
-        block2_amount,
+        block1_amount,
         1,
     );
 
@@ -2878,7 +3947,7 @@ fn change_token_supply_unlimited(#[case] seed: Seed) {
         Amount::ZERO,
     );
 
-    let token_amount_to_mint = Amount::from_atoms(rng.gen_range(1..10000));
+    let token_amount_to_mint = Amount::from_atoms(rng.gen_range(1..=fixed_max_amount.into_atoms()));
     let mint_transaction = wallet
         .mint_tokens(
             DEFAULT_ACCOUNT_INDEX,
@@ -2891,27 +3960,85 @@ fn change_token_supply_unlimited(#[case] seed: Seed) {
         .unwrap();
 
     wallet.add_unconfirmed_tx(mint_transaction.clone(), &WalletEventsNoOp).unwrap();
-    let unconfirmed_token_info =
-        wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
-
-    let _ = create_block(
-        &chain_config,
-        &mut wallet,
-        vec![mint_transaction],
-        block2_amount,
-        2,
-    );
-    token_info.circulating_supply = unconfirmed_token_info.current_supply().unwrap();
 
     let unconfirmed_token_info =
         wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
 
-    assert_eq!(
-        unconfirmed_token_info.get_next_nonce().unwrap(),
-        AccountNonce::new(1)
-    );
-
-    assert_eq!(
+    // Try to mint more then the fixed maximum
+    let leftover = (fixed_max_amount - token_amount_to_mint).unwrap();
+    let token_amount_to_mint_more_than_maximum =
+        (leftover + Amount::from_atoms(rng.gen_range(1..1000))).unwrap();
+    let err = wallet
+        .mint_tokens(
+            DEFAULT_ACCOUNT_INDEX,
+            &unconfirmed_token_info,
+            token_amount_to_mint_more_than_maximum,
+            address2.clone(),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err,
+        WalletError::CannotMintFixedTokenSupply(
+            fixed_max_amount,
+            token_amount_to_mint,
+            token_amount_to_mint_more_than_maximum
+        )
+    );
+
+    assert_eq!(
+        unconfirmed_token_info.get_next_nonce().unwrap(),
+        AccountNonce::new(1)
+    );
+
+    assert_eq!(
+        unconfirmed_token_info.current_supply().unwrap(),
+        token_amount_to_mint,
+    );
+
+    // test abandoning a transaction
+    {
+        wallet
+            .abandon_transaction(
+                DEFAULT_ACCOUNT_INDEX,
+                mint_transaction.transaction().get_id(),
+            )
+            .unwrap();
+
+        let unconfirmed_token_info =
+            wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
+
+        assert_eq!(
+            unconfirmed_token_info.get_next_nonce().unwrap(),
+            AccountNonce::new(0)
+        );
+
+        assert_eq!(
+            unconfirmed_token_info.current_supply().unwrap(),
+            Amount::ZERO,
+        );
+    }
+
+    let _ = create_block(
+        &chain_config,
+        &mut wallet,
+        vec![mint_transaction],
+        block2_amount,
+        2,
+    );
+    token_info.circulating_supply = unconfirmed_token_info.current_supply().unwrap();
+
+    let unconfirmed_token_info =
+        wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
+
+    assert_eq!(
+        unconfirmed_token_info.get_next_nonce().unwrap(),
+        AccountNonce::new(1)
+    );
+
+    assert_eq!(
         unconfirmed_token_info.current_supply().unwrap(),
         token_amount_to_mint,
     );
@@ -2943,6 +4070,7 @@ fn change_token_supply_unlimited(#[case] seed: Seed) {
             FeeRate::from_amount_per_kb(Amount::ZERO),
         )
         .unwrap();
+
     wallet
         .add_unconfirmed_tx(unmint_transaction.clone(), &WalletEventsNoOp)
         .unwrap();
@@ -2972,7 +4100,7 @@ fn change_token_supply_unlimited(#[case] seed: Seed) {
     );
     assert_eq!(
         unconfirmed_token_info.check_can_lock().unwrap_err(),
-        WalletError::CannotLockTokenSupply("Unlimited")
+        WalletError::CannotLockTokenSupply("Fixed")
     );
 
     let err = wallet
@@ -2983,13 +4111,13 @@ fn change_token_supply_unlimited(#[case] seed: Seed) {
             FeeRate::from_amount_per_kb(Amount::ZERO),
         )
         .unwrap_err();
-    assert_eq!(err, WalletError::CannotLockTokenSupply("Unlimited"));
+    assert_eq!(err, WalletError::CannotLockTokenSupply("Fixed"));
 }
 
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
-fn change_and_lock_token_supply_lockable(#[case] seed: Seed) {
+fn change_token_supply_unlimited(#[case] seed: Seed) {
     let mut rng = make_seedable_rng(seed);
     let chain_config = Arc::new(create_mainnet());
 
@@ -3013,7 +4141,7 @@ fn change_and_lock_token_supply_lockable(#[case] seed: Seed) {
         token_ticker: "XXXX".as_bytes().to_vec(),
         number_of_decimals: rng.gen_range(1..18),
         metadata_uri: "http://uri".as_bytes().to_vec(),
-        total_supply: common::chain::tokens::TokenTotalSupply::Lockable,
+        total_supply: common::chain::tokens::TokenTotalSupply::Unlimited,
         authority: address2.as_object().clone(),
         is_freezable: common::chain::tokens::IsTokenFreezable::No,
     };
@@ -3067,7 +4195,7 @@ fn change_and_lock_token_supply_lockable(#[case] seed: Seed) {
         Amount::ZERO,
     );
 
-    let token_amount_to_mint = Amount::from_atoms(rng.gen_range(2..100));
+    let token_amount_to_mint = Amount::from_atoms(rng.gen_range(1..10000));
     let mint_transaction = wallet
         .mint_tokens(
             DEFAULT_ACCOUNT_INDEX,
@@ -3078,6 +4206,7 @@ fn change_and_lock_token_supply_lockable(#[case] seed: Seed) {
             FeeRate::from_amount_per_kb(Amount::ZERO),
         )
         .unwrap();
+
     wallet.add_unconfirmed_tx(mint_transaction.clone(), &WalletEventsNoOp).unwrap();
     let unconfirmed_token_info =
         wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
@@ -3131,7 +4260,6 @@ fn change_and_lock_token_supply_lockable(#[case] seed: Seed) {
             FeeRate::from_amount_per_kb(Amount::ZERO),
         )
         .unwrap();
-
     wallet
         .add_unconfirmed_tx(unmint_transaction.clone(), &WalletEventsNoOp)
         .unwrap();
@@ -3159,45 +4287,105 @@ fn change_and_lock_token_supply_lockable(#[case] seed: Seed) {
         unconfirmed_token_info.current_supply().unwrap(),
         (token_amount_to_mint - token_amount_to_unmint).unwrap(),
     );
-    assert!(unconfirmed_token_info.check_can_lock().is_ok());
+    assert_eq!(
+        unconfirmed_token_info.check_can_lock().unwrap_err(),
+        WalletError::CannotLockTokenSupply("Unlimited")
+    );
 
-    let lock_transaction = wallet
+    let err = wallet
         .lock_token_supply(
             DEFAULT_ACCOUNT_INDEX,
             &unconfirmed_token_info,
             FeeRate::from_amount_per_kb(Amount::ZERO),
             FeeRate::from_amount_per_kb(Amount::ZERO),
         )
+        .unwrap_err();
+    assert_eq!(err, WalletError::CannotLockTokenSupply("Unlimited"));
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn change_and_lock_token_supply_lockable(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+
+    let mut wallet = create_wallet(chain_config.clone());
+
+    let coin_balance = get_coin_balance(&wallet);
+    assert_eq!(coin_balance, Amount::ZERO);
+
+    // Generate a new block which sends reward to the wallet
+    let block1_amount = (Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000))
+        + chain_config.fungible_token_issuance_fee())
+    .unwrap();
+
+    let _ = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
+
+    let coin_balance = get_coin_balance(&wallet);
+    assert_eq!(coin_balance, block1_amount);
+
+    let address2 = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+    let token_issuance = TokenIssuanceV1 {
+        token_ticker: "XXXX".as_bytes().to_vec(),
+        number_of_decimals: rng.gen_range(1..18),
+        metadata_uri: "http://uri".as_bytes().to_vec(),
+        total_supply: common::chain::tokens::TokenTotalSupply::Lockable,
+        authority: address2.as_object().clone(),
+        is_freezable: common::chain::tokens::IsTokenFreezable::No,
+    };
+    let (issued_token_id, token_issuance_transaction) = wallet
+        .issue_new_token(
+            DEFAULT_ACCOUNT_INDEX,
+            TokenIssuance::V1(token_issuance.clone()),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
         .unwrap();
 
+    let block2_amount = chain_config.token_supply_change_fee(BlockHeight::zero());
+
     let _ = create_block(
         &chain_config,
         &mut wallet,
-        vec![lock_transaction],
+        vec![token_issuance_transaction],
         block2_amount,
-        4,
+        1,
+    );
+
+    let freezable = token_issuance.is_freezable.as_bool();
+    let mut token_info = RPCFungibleTokenInfo::new(
+        issued_token_id,
+        token_issuance.token_ticker,
+        token_issuance.number_of_decimals,
+        token_issuance.metadata_uri,
+        Amount::ZERO,
+        token_issuance.total_supply.into(),
+        false,
+        RPCIsTokenFrozen::NotFrozen { freezable },
+        token_issuance.authority,
     );
-    token_info.is_locked = true;
 
     let unconfirmed_token_info =
         wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
 
     assert_eq!(
-        unconfirmed_token_info.get_next_nonce().unwrap(),
-        AccountNonce::new(3)
+        unconfirmed_token_info.authority().unwrap(),
+        address2.as_object()
     );
 
     assert_eq!(
-        unconfirmed_token_info.current_supply().unwrap(),
-        (token_amount_to_mint - token_amount_to_unmint).unwrap(),
+        unconfirmed_token_info.get_next_nonce().unwrap(),
+        AccountNonce::new(0)
     );
 
     assert_eq!(
-        unconfirmed_token_info.check_can_lock(),
-        Err(WalletError::CannotLockTokenSupply("Locked"))
+        unconfirmed_token_info.current_supply().unwrap(),
+        Amount::ZERO,
     );
 
-    let err = wallet
+    let token_amount_to_mint = Amount::from_atoms(rng.gen_range(2..100));
+    let mint_transaction = wallet
         .mint_tokens(
             DEFAULT_ACCOUNT_INDEX,
             &unconfirmed_token_info,
@@ -3206,8 +4394,35 @@ fn change_and_lock_token_supply_lockable(#[case] seed: Seed) {
             FeeRate::from_amount_per_kb(Amount::ZERO),
             FeeRate::from_amount_per_kb(Amount::ZERO),
         )
-        .unwrap_err();
-    assert_eq!(err, WalletError::CannotChangeLockedTokenSupply);
+        .unwrap();
+    wallet.add_unconfirmed_tx(mint_transaction.clone(), &WalletEventsNoOp).unwrap();
+    let unconfirmed_token_info =
+        wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
+
+    let _ = create_block(
+        &chain_config,
+        &mut wallet,
+        vec![mint_transaction],
+        block2_amount,
+        2,
+    );
+    token_info.circulating_supply = unconfirmed_token_info.current_supply().unwrap();
+
+    let unconfirmed_token_info =
+        wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
+
+    assert_eq!(
+        unconfirmed_token_info.get_next_nonce().unwrap(),
+        AccountNonce::new(1)
+    );
+
+    assert_eq!(
+        unconfirmed_token_info.current_supply().unwrap(),
+        token_amount_to_mint,
+    );
+
+    // Try to unmint more than the current total supply
+    let token_amount_to_unmint = (token_amount_to_mint + Amount::from_atoms(1)).unwrap();
     let err = wallet
         .unmint_tokens(
             DEFAULT_ACCOUNT_INDEX,
@@ -3217,41 +4432,143 @@ fn change_and_lock_token_supply_lockable(#[case] seed: Seed) {
             FeeRate::from_amount_per_kb(Amount::ZERO),
         )
         .unwrap_err();
-    assert_eq!(err, WalletError::CannotChangeLockedTokenSupply);
+    assert_eq!(
+        err,
+        WalletError::CannotUnmintTokenSupply(token_amount_to_unmint, token_amount_to_mint)
+    );
 
-    let err = wallet
-        .lock_token_supply(
+    let token_amount_to_unmint =
+        Amount::from_atoms(rng.gen_range(1..=token_amount_to_mint.into_atoms()));
+    let unmint_transaction = wallet
+        .unmint_tokens(
             DEFAULT_ACCOUNT_INDEX,
             &unconfirmed_token_info,
+            token_amount_to_unmint,
             FeeRate::from_amount_per_kb(Amount::ZERO),
             FeeRate::from_amount_per_kb(Amount::ZERO),
         )
-        .unwrap_err();
-    assert_eq!(err, WalletError::CannotLockTokenSupply("Locked"));
-}
-
-#[rstest]
-#[trace]
-#[case(Seed::from_entropy())]
-fn lock_then_transfer(#[case] seed: Seed) {
-    let mut rng = make_seedable_rng(seed);
-    let chain_config = Arc::new(create_mainnet());
+        .unwrap();
 
-    let mut wallet = create_wallet(chain_config.clone());
+    wallet
+        .add_unconfirmed_tx(unmint_transaction.clone(), &WalletEventsNoOp)
+        .unwrap();
+    let unconfirmed_token_info =
+        wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
 
-    let coin_balance = get_coin_balance(&wallet);
-    assert_eq!(coin_balance, Amount::ZERO);
+    let _ = create_block(
+        &chain_config,
+        &mut wallet,
+        vec![unmint_transaction],
+        block2_amount,
+        3,
+    );
+    token_info.circulating_supply = unconfirmed_token_info.current_supply().unwrap();
 
-    let timestamp = chain_config.genesis_block().timestamp().add_int_seconds(10).unwrap();
+    let unconfirmed_token_info =
+        wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
 
-    // Generate a new block which sends reward to the wallet
-    let block1_amount = Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000));
-    let address = get_address(
-        &chain_config,
-        MNEMONIC,
-        DEFAULT_ACCOUNT_INDEX,
-        KeyPurpose::ReceiveFunds,
-        0.try_into().unwrap(),
+    assert_eq!(
+        unconfirmed_token_info.get_next_nonce().unwrap(),
+        AccountNonce::new(2)
+    );
+
+    assert_eq!(
+        unconfirmed_token_info.current_supply().unwrap(),
+        (token_amount_to_mint - token_amount_to_unmint).unwrap(),
+    );
+    assert!(unconfirmed_token_info.check_can_lock().is_ok());
+
+    let lock_transaction = wallet
+        .lock_token_supply(
+            DEFAULT_ACCOUNT_INDEX,
+            &unconfirmed_token_info,
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap();
+
+    let _ = create_block(
+        &chain_config,
+        &mut wallet,
+        vec![lock_transaction],
+        block2_amount,
+        4,
+    );
+    token_info.is_locked = true;
+
+    let unconfirmed_token_info =
+        wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
+
+    assert_eq!(
+        unconfirmed_token_info.get_next_nonce().unwrap(),
+        AccountNonce::new(3)
+    );
+
+    assert_eq!(
+        unconfirmed_token_info.current_supply().unwrap(),
+        (token_amount_to_mint - token_amount_to_unmint).unwrap(),
+    );
+
+    assert_eq!(
+        unconfirmed_token_info.check_can_lock(),
+        Err(WalletError::CannotLockTokenSupply("Locked"))
+    );
+
+    let err = wallet
+        .mint_tokens(
+            DEFAULT_ACCOUNT_INDEX,
+            &unconfirmed_token_info,
+            token_amount_to_mint,
+            address2.clone(),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap_err();
+    assert_eq!(err, WalletError::CannotChangeLockedTokenSupply);
+    let err = wallet
+        .unmint_tokens(
+            DEFAULT_ACCOUNT_INDEX,
+            &unconfirmed_token_info,
+            token_amount_to_unmint,
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap_err();
+    assert_eq!(err, WalletError::CannotChangeLockedTokenSupply);
+
+    let err = wallet
+        .lock_token_supply(
+            DEFAULT_ACCOUNT_INDEX,
+            &unconfirmed_token_info,
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap_err();
+    assert_eq!(err, WalletError::CannotLockTokenSupply("Locked"));
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn lock_then_transfer(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+
+    let mut wallet = create_wallet(chain_config.clone());
+
+    let coin_balance = get_coin_balance(&wallet);
+    assert_eq!(coin_balance, Amount::ZERO);
+
+    let timestamp = chain_config.genesis_block().timestamp().add_int_seconds(10).unwrap();
+
+    // Generate a new block which sends reward to the wallet
+    let block1_amount = Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000));
+    let address = get_address(
+        &chain_config,
+        MNEMONIC,
+        DEFAULT_ACCOUNT_INDEX,
+        KeyPurpose::ReceiveFunds,
+        0.try_into().unwrap(),
     );
     let block1 = Block::new(
         vec![],
@@ -3366,6 +4683,124 @@ fn lock_then_transfer(#[case] seed: Seed) {
     );
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn locked_utxos_unlock_times(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+    let block_spacing = chain_config.target_block_spacing().as_secs();
+
+    let mut wallet = create_wallet(chain_config.clone());
+
+    let timestamp = chain_config.genesis_block().timestamp().add_int_seconds(10).unwrap();
+
+    // Generate a new block which sends reward to the wallet
+    let block1_amount = Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000));
+    let address = get_address(
+        &chain_config,
+        MNEMONIC,
+        DEFAULT_ACCOUNT_INDEX,
+        KeyPurpose::ReceiveFunds,
+        0.try_into().unwrap(),
+    );
+    let block1 = Block::new(
+        vec![],
+        chain_config.genesis_block_id(),
+        timestamp,
+        ConsensusData::None,
+        BlockReward::new(vec![make_address_output(address, block1_amount)]),
+    )
+    .unwrap();
+    let block1_id = block1.get_id();
+    // not important that it is not the actual median
+    wallet.set_median_time(timestamp).unwrap();
+    scan_wallet(&mut wallet, BlockHeight::new(0), vec![block1]);
+
+    let block2_timestamp = timestamp.add_int_seconds(rng.gen_range(1..1000)).unwrap();
+    let lock_for_blocks = rng.gen_range(1..1000);
+    let lock_for_seconds = rng.gen_range(1..100_000);
+    let lock_until_time = block2_timestamp.add_int_seconds(rng.gen_range(1..100_000)).unwrap();
+    let destination = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1.into_object();
+    let outputs = [
+        OutputTimeLock::ForBlockCount(lock_for_blocks),
+        OutputTimeLock::UntilHeight(BlockHeight::new(2 + lock_for_blocks)),
+        OutputTimeLock::ForSeconds(lock_for_seconds),
+        OutputTimeLock::UntilTime(lock_until_time),
+    ]
+    .into_iter()
+    .map(|timelock| {
+        TxOutput::LockThenTransfer(
+            OutputValue::Coin(Amount::from_atoms(rng.gen_range(1..100))),
+            destination.clone(),
+            timelock,
+        )
+    })
+    .collect_vec();
+    let outputs_count = outputs.len();
+
+    let transaction = wallet
+        .create_transaction_to_addresses(
+            DEFAULT_ACCOUNT_INDEX,
+            outputs,
+            SelectedInputs::Utxos(vec![]),
+            BTreeMap::new(),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap();
+    wallet.add_unconfirmed_tx(transaction.clone(), &WalletEventsNoOp).unwrap();
+
+    // The timelocks of an unconfirmed transaction are counted from the current block
+    let locked_utxos = wallet
+        .get_locked_utxos(DEFAULT_ACCOUNT_INDEX, UtxoState::Inactive.into())
+        .unwrap();
+    assert_eq!(locked_utxos.len(), outputs_count);
+    for (_, output, _, unlock_time) in locked_utxos {
+        let expected_unlock_time = match output.timelock().unwrap() {
+            OutputTimeLock::ForBlockCount(_) => {
+                timestamp.add_int_seconds(block_spacing * lock_for_blocks).unwrap()
+            }
+            OutputTimeLock::UntilHeight(_) => {
+                timestamp.add_int_seconds(block_spacing * (lock_for_blocks + 1)).unwrap()
+            }
+            OutputTimeLock::ForSeconds(_) => timestamp.add_int_seconds(lock_for_seconds).unwrap(),
+            OutputTimeLock::UntilTime(_) => lock_until_time,
+        };
+        assert_eq!(unlock_time, expected_unlock_time);
+    }
+
+    let block2 = Block::new(
+        vec![transaction],
+        block1_id.into(),
+        block2_timestamp,
+        ConsensusData::None,
+        BlockReward::new(vec![]),
+    )
+    .unwrap();
+    // not important that it is not the actual median
+    wallet.set_median_time(block2_timestamp).unwrap();
+    scan_wallet(&mut wallet, BlockHeight::new(1), vec![block2]);
+
+    // Once confirmed, the timelocks are counted from the block of the transaction
+    let locked_utxos = wallet
+        .get_locked_utxos(DEFAULT_ACCOUNT_INDEX, UtxoState::Confirmed.into())
+        .unwrap();
+    assert_eq!(locked_utxos.len(), outputs_count);
+    for (_, output, _, unlock_time) in locked_utxos {
+        let expected_unlock_time = match output.timelock().unwrap() {
+            OutputTimeLock::ForBlockCount(_) | OutputTimeLock::UntilHeight(_) => {
+                block2_timestamp.add_int_seconds(block_spacing * lock_for_blocks).unwrap()
+            }
+            OutputTimeLock::ForSeconds(_) => {
+                block2_timestamp.add_int_seconds(lock_for_seconds).unwrap()
+            }
+            OutputTimeLock::UntilTime(_) => lock_until_time,
+        };
+        assert_eq!(unlock_time, expected_unlock_time);
+    }
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -3742,98 +5177,741 @@ fn wallet_abandone_transactions(#[case] seed: Seed) {
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
-fn wallet_address_usage(#[case] seed: Seed) {
+fn wallet_abandoned_transaction_confirmed_later(#[case] seed: Seed) {
     let mut rng = make_seedable_rng(seed);
-    let chain_config = Arc::new(create_regtest());
+    let chain_config = Arc::new(create_mainnet());
+
     let mut wallet = create_wallet(chain_config.clone());
 
-    let usage = wallet.get_addresses_usage(DEFAULT_ACCOUNT_INDEX).unwrap();
-    assert_eq!(usage.last_used(), None);
-    assert_eq!(usage.last_issued(), None);
+    let block1_amount = Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000));
+    let _ = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
 
-    // issue some new address
-    let addresses_to_issue = rng.gen_range(1..10);
-    for _ in 0..=addresses_to_issue {
-        let _ = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap();
-    }
+    let coin_balance = get_coin_balance(&wallet);
+    assert_eq!(coin_balance, block1_amount);
 
-    let usage = wallet.get_addresses_usage(DEFAULT_ACCOUNT_INDEX).unwrap();
-    assert_eq!(usage.last_used(), None);
+    let amount_to_transfer = Amount::from_atoms(rng.gen_range(1..=NETWORK_FEE));
+    let new_output = TxOutput::Transfer(
+        OutputValue::Coin(amount_to_transfer),
+        Destination::PublicKey(
+            crypto::key::PrivateKey::new_from_rng(&mut rng, crypto::key::KeyKind::Secp256k1Schnorr)
+                .1,
+        ),
+    );
+
+    let transaction = wallet
+        .create_transaction_to_addresses(
+            DEFAULT_ACCOUNT_INDEX,
+            [new_output],
+            SelectedInputs::Utxos(vec![]),
+            BTreeMap::new(),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap();
+    let transaction_id = transaction.transaction().get_id();
+    wallet
+        .add_account_unconfirmed_tx(
+            DEFAULT_ACCOUNT_INDEX,
+            transaction.clone(),
+            &WalletEventsNoOp,
+        )
+        .unwrap();
+
+    let balance_after_tx = (block1_amount - amount_to_transfer).unwrap();
+    let coin_balance = get_coin_balance_with_inactive(&wallet);
+    assert_eq!(coin_balance, balance_after_tx);
+
+    wallet.abandon_transaction(DEFAULT_ACCOUNT_INDEX, transaction_id).unwrap();
+    let coin_balance = get_coin_balance_with_inactive(&wallet);
+    assert_eq!(coin_balance, block1_amount);
     assert_eq!(
-        usage.last_issued(),
-        Some(addresses_to_issue.try_into().unwrap())
+        *wallet.get_transaction(DEFAULT_ACCOUNT_INDEX, transaction_id).unwrap().state(),
+        TxState::Abandoned
+    );
+
+    // someone else broadcast the abandoned transaction and it got into a block
+    let block2_amount = Amount::from_atoms(rng.gen_range(1..10000));
+    let _ = create_block(
+        &chain_config,
+        &mut wallet,
+        vec![transaction],
+        block2_amount,
+        1,
     );
 
-    let block1_amount = Amount::from_atoms(10000);
-    let _ = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
+    let expected_balance = (balance_after_tx + block2_amount).unwrap();
+    let coin_balance = get_coin_balance(&wallet);
+    assert_eq!(coin_balance, expected_balance);
+    let coin_balance = get_coin_balance_with_inactive(&wallet);
+    assert_eq!(coin_balance, expected_balance);
+
+    let tx_state = *wallet.get_transaction(DEFAULT_ACCOUNT_INDEX, transaction_id).unwrap().state();
+    assert!(matches!(tx_state, TxState::Confirmed(height, _, _) if height == BlockHeight::new(2)));
+    assert!(wallet.pending_transactions(DEFAULT_ACCOUNT_INDEX).unwrap().is_empty());
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn wallet_import_external_transaction(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+
+    let mut wallet = create_wallet(chain_config.clone());
+
+    let block1_amount = Amount::from_atoms(rng.gen_range(1..10000));
+    let _ = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
+
+    let mut make_external_tx = |destination: Destination| {
+        let amount = Amount::from_atoms(rng.gen_range(1..10000));
+        let transaction = Transaction::new(
+            0,
+            vec![TxInput::from_utxo(
+                OutPointSourceId::Transaction(Id::new(H256::random_using(&mut rng))),
+                0,
+            )],
+            vec![TxOutput::Transfer(OutputValue::Coin(amount), destination)],
+        )
+        .unwrap();
+        let tx =
+            SignedTransaction::new(transaction, vec![InputWitness::NoSignature(None)]).unwrap();
+        (tx, amount)
+    };
+
+    let mut own_destination = || wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+    let (pending_tx, pending_amount) = make_external_tx(own_destination().into_object());
+    let (confirmed_tx, confirmed_amount) = make_external_tx(own_destination().into_object());
+    let (above_best_tx, _) = make_external_tx(own_destination().into_object());
+    let (foreign_tx, _) = make_external_tx(Destination::AnyoneCanSpend);
+
+    wallet
+        .import_external_transaction(
+            DEFAULT_ACCOUNT_INDEX,
+            pending_tx.clone(),
+            None,
+            &WalletEventsNoOp,
+        )
+        .unwrap();
+    wallet
+        .import_external_transaction(
+            DEFAULT_ACCOUNT_INDEX,
+            confirmed_tx.clone(),
+            Some(BlockHeight::new(1)),
+            &WalletEventsNoOp,
+        )
+        .unwrap();
+
+    let tx_states = wallet
+        .get_transaction_list(DEFAULT_ACCOUNT_INDEX, 0, 10)
+        .unwrap()
+        .txs
+        .into_iter()
+        .map(|tx| (tx.txid, tx.state))
+        .collect::<BTreeMap<_, _>>();
+    assert_eq!(tx_states.len(), 2);
+    assert!(matches!(
+        tx_states[&pending_tx.transaction().get_id()],
+        TxState::Inactive(_)
+    ));
+    assert!(matches!(
+        tx_states[&confirmed_tx.transaction().get_id()],
+        TxState::Confirmed(height, _, _) if height == BlockHeight::new(1)
+    ));
+
+    assert_eq!(
+        get_coin_balance(&wallet),
+        (block1_amount + confirmed_amount).unwrap()
+    );
+    assert_eq!(
+        get_coin_balance_with_inactive(&wallet),
+        (block1_amount + confirmed_amount + pending_amount).unwrap()
+    );
+    assert_eq!(
+        wallet.pending_transactions(DEFAULT_ACCOUNT_INDEX).unwrap().len(),
+        1
+    );
+
+    // Nothing in the transaction belongs to the wallet
+    assert_eq!(
+        wallet.import_external_transaction(
+            DEFAULT_ACCOUNT_INDEX,
+            foreign_tx,
+            None,
+            &WalletEventsNoOp,
+        ),
+        Err(WalletError::TransactionNotRelevant)
+    );
+
+    // The transaction is already known
+    assert_eq!(
+        wallet.import_external_transaction(
+            DEFAULT_ACCOUNT_INDEX,
+            pending_tx.clone(),
+            None,
+            &WalletEventsNoOp,
+        ),
+        Err(WalletError::DuplicateTransaction(
+            pending_tx.transaction().get_id()
+        ))
+    );
+
+    // The block height is above the wallet's best block
+    assert_eq!(
+        wallet.import_external_transaction(
+            DEFAULT_ACCOUNT_INDEX,
+            above_best_tx,
+            Some(BlockHeight::new(2)),
+            &WalletEventsNoOp,
+        ),
+        Err(WalletError::BlockHeightAboveBestBlock(
+            BlockHeight::new(2),
+            BlockHeight::new(1)
+        ))
+    );
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn wallet_address_usage(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_regtest());
+    let mut wallet = create_wallet(chain_config.clone());
+
+    let usage = wallet.get_addresses_usage(DEFAULT_ACCOUNT_INDEX).unwrap();
+    assert_eq!(usage.last_used(), None);
+    assert_eq!(usage.last_issued(), None);
+
+    // issue some new address
+    let addresses_to_issue = rng.gen_range(1..10);
+    for _ in 0..=addresses_to_issue {
+        let _ = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap();
+    }
+
+    let usage = wallet.get_addresses_usage(DEFAULT_ACCOUNT_INDEX).unwrap();
+    assert_eq!(usage.last_used(), None);
+    assert_eq!(
+        usage.last_issued(),
+        Some(addresses_to_issue.try_into().unwrap())
+    );
+
+    let block1_amount = Amount::from_atoms(10000);
+    let _ = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
+
+    let last_used = addresses_to_issue + 1;
+    let usage = wallet.get_addresses_usage(DEFAULT_ACCOUNT_INDEX).unwrap();
+    assert_eq!(usage.last_used(), Some(last_used.try_into().unwrap()));
+    assert_eq!(usage.last_issued(), Some(last_used.try_into().unwrap()));
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn wallet_address_reuse_report(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_regtest());
+    let mut wallet = create_wallet(chain_config.clone());
+
+    assert!(wallet.address_reuse_report(DEFAULT_ACCOUNT_INDEX).unwrap().is_empty());
+
+    let reused_address = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+    let single_use_address = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+
+    let block1_amount = Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000));
+    let block1 = Block::new(
+        vec![],
+        chain_config.genesis_block_id(),
+        chain_config.genesis_block().timestamp(),
+        ConsensusData::None,
+        BlockReward::new(vec![make_address_output(
+            reused_address.clone(),
+            block1_amount,
+        )]),
+    )
+    .unwrap();
+    scan_wallet(&mut wallet, BlockHeight::new(0), vec![block1.clone()]);
+
+    // the address is used only once so far
+    assert!(wallet.address_reuse_report(DEFAULT_ACCOUNT_INDEX).unwrap().is_empty());
+
+    // send some coins back to the same address in a transaction
+    let amount_to_transfer = Amount::from_atoms(rng.gen_range(1..=NETWORK_FEE));
+    let transaction = wallet
+        .create_transaction_to_addresses(
+            DEFAULT_ACCOUNT_INDEX,
+            [make_address_output(reused_address.clone(), amount_to_transfer)],
+            SelectedInputs::Utxos(vec![]),
+            BTreeMap::new(),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap();
+
+    let block2_amount = Amount::from_atoms(rng.gen_range(1..10000));
+    let block2 = Block::new(
+        vec![transaction],
+        block1.get_id().into(),
+        chain_config.genesis_block().timestamp(),
+        ConsensusData::None,
+        BlockReward::new(vec![make_address_output(
+            single_use_address.clone(),
+            block2_amount,
+        )]),
+    )
+    .unwrap();
+    scan_wallet(&mut wallet, BlockHeight::new(1), vec![block2]);
+
+    let report = wallet.address_reuse_report(DEFAULT_ACCOUNT_INDEX).unwrap();
+    assert_eq!(report, vec![(reused_address, 2)]);
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn wallet_address_derivation_path(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_regtest());
+    let mut wallet = create_wallet(chain_config.clone());
+
+    for _ in 0..rng.gen_range(1..10) {
+        let (child_number, address) = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap();
+
+        let mut expected_path = make_account_path(&chain_config, DEFAULT_ACCOUNT_INDEX).into_vec();
+        expected_path.push(KeyPurpose::ReceiveFunds.get_deterministic_index());
+        expected_path.push(child_number);
+        let expected_path = DerivationPath::try_from(expected_path).unwrap();
+
+        let path = wallet
+            .address_derivation_path(DEFAULT_ACCOUNT_INDEX, address.as_object())
+            .unwrap();
+        assert_eq!(path, Some(expected_path));
+    }
+
+    // a standalone private key has no HD derivation path
+    let (private_key, pub_key) =
+        crypto::key::PrivateKey::new_from_rng(&mut rng, crypto::key::KeyKind::Secp256k1Schnorr);
+    wallet
+        .add_standalone_private_key(DEFAULT_ACCOUNT_INDEX, private_key, None)
+        .unwrap();
+    let standalone_address = Destination::PublicKeyHash((&pub_key).into());
+    assert_eq!(
+        wallet
+            .address_derivation_path(DEFAULT_ACCOUNT_INDEX, &standalone_address)
+            .unwrap(),
+        None
+    );
+
+    // neither does an address that doesn't belong to the wallet
+    let (_, foreign_pub_key) =
+        crypto::key::PrivateKey::new_from_rng(&mut rng, crypto::key::KeyKind::Secp256k1Schnorr);
+    let foreign_address = Destination::PublicKey(foreign_pub_key);
+    assert_eq!(
+        wallet.address_derivation_path(DEFAULT_ACCOUNT_INDEX, &foreign_address).unwrap(),
+        None
+    );
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn wallet_set_lookahead_size(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_regtest());
+    let mut wallet = create_wallet(chain_config.clone());
+
+    let usage = wallet.get_addresses_usage(DEFAULT_ACCOUNT_INDEX).unwrap();
+    assert_eq!(usage.last_used(), None);
+    assert_eq!(usage.last_issued(), None);
+
+    // issue some new address
+    let addresses_to_issue = rng.gen_range(1..10);
+    for _ in 0..=addresses_to_issue {
+        let _ = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap();
+    }
+
+    let usage = wallet.get_addresses_usage(DEFAULT_ACCOUNT_INDEX).unwrap();
+    assert_eq!(usage.last_used(), None);
+    assert_eq!(
+        usage.last_issued(),
+        Some(addresses_to_issue.try_into().unwrap())
+    );
+
+    let block1_amount = Amount::from_atoms(10000);
+    let (_, block1) = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
+
+    let last_used = addresses_to_issue + 1;
+    let usage = wallet.get_addresses_usage(DEFAULT_ACCOUNT_INDEX).unwrap();
+    assert_eq!(usage.last_used(), Some(last_used.try_into().unwrap()));
+    assert_eq!(usage.last_issued(), Some(last_used.try_into().unwrap()));
+
+    let coins = get_coin_balance_for_acc(&wallet, DEFAULT_ACCOUNT_INDEX);
+    assert_eq!(coins, block1_amount);
+
+    let less_than_last_used = rng.gen_range(1..=last_used);
+    let err = wallet.set_lookahead_size(less_than_last_used, false).unwrap_err();
+    assert_eq!(
+        err,
+        WalletError::ReducedLookaheadSize(less_than_last_used, last_used)
+    );
+
+    wallet.set_lookahead_size(less_than_last_used, true).unwrap();
+
+    scan_wallet(&mut wallet, BlockHeight::new(0), vec![block1.clone()]);
+    let coins = get_coin_balance_for_acc(&wallet, DEFAULT_ACCOUNT_INDEX);
+    assert_eq!(coins, Amount::ZERO);
+    let usage = wallet.get_addresses_usage(DEFAULT_ACCOUNT_INDEX).unwrap();
+    assert_eq!(usage.last_used(), None);
+    assert_eq!(usage.last_issued(), None);
+
+    let more_than_last_used = rng.gen_range(last_used + 1..100);
+    wallet.set_lookahead_size(more_than_last_used, false).unwrap();
+
+    scan_wallet(&mut wallet, BlockHeight::new(0), vec![block1.clone()]);
+    let coins = get_coin_balance_for_acc(&wallet, DEFAULT_ACCOUNT_INDEX);
+    assert_eq!(coins, block1_amount);
+    let usage = wallet.get_addresses_usage(DEFAULT_ACCOUNT_INDEX).unwrap();
+    assert_eq!(usage.last_used(), Some(last_used.try_into().unwrap()));
+    assert_eq!(usage.last_issued(), Some(last_used.try_into().unwrap()));
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn wallet_lookahead_status(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_regtest());
+    let mut wallet = create_wallet(chain_config.clone());
+
+    let status = wallet.lookahead_status(DEFAULT_ACCOUNT_INDEX).unwrap();
+    assert_eq!(
+        status,
+        LookaheadStatus {
+            configured: LOOKAHEAD_SIZE,
+            last_used: None,
+            gap_remaining: LOOKAHEAD_SIZE,
+        }
+    );
+
+    // issue some addresses, leaving at least one for the block reward below
+    let addresses_to_issue = rng.gen_range(1..LOOKAHEAD_SIZE);
+    for _ in 0..addresses_to_issue {
+        let _ = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap();
+    }
+
+    let status = wallet.lookahead_status(DEFAULT_ACCOUNT_INDEX).unwrap();
+    assert_eq!(status.configured, LOOKAHEAD_SIZE);
+    assert_eq!(status.last_used, None);
+    assert_eq!(status.gap_remaining, LOOKAHEAD_SIZE - addresses_to_issue);
+
+    // using the last issued address in the blockchain frees up a whole lookahead again
+    let block1_amount = Amount::from_atoms(10000);
+    let _ = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
+
+    let status = wallet.lookahead_status(DEFAULT_ACCOUNT_INDEX).unwrap();
+    assert_eq!(status.last_used, Some(addresses_to_issue));
+    assert_eq!(status.gap_remaining, LOOKAHEAD_SIZE);
+
+    // issue addresses up to the lookahead
+    let addresses_to_issue = rng.gen_range(1..=LOOKAHEAD_SIZE);
+    for _ in 0..addresses_to_issue {
+        let _ = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap();
+    }
+    let status = wallet.lookahead_status(DEFAULT_ACCOUNT_INDEX).unwrap();
+    assert_eq!(status.gap_remaining, LOOKAHEAD_SIZE - addresses_to_issue);
+
+    for _ in addresses_to_issue..LOOKAHEAD_SIZE {
+        let _ = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap();
+    }
+    let status = wallet.lookahead_status(DEFAULT_ACCOUNT_INDEX).unwrap();
+    assert_eq!(status.gap_remaining, 0);
+    assert_eq!(
+        wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap_err(),
+        WalletError::KeyChainError(KeyChainError::LookAheadExceeded)
+    );
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn wallet_get_n_vrf_keys(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_regtest());
+    let mut wallet = create_wallet(chain_config);
+
+    // more keys than the lookahead allows are rejected and none of them is issued
+    let too_many = rng.gen_range(LOOKAHEAD_SIZE + 1..100);
+    assert_eq!(
+        wallet.get_n_vrf_keys(DEFAULT_ACCOUNT_INDEX, too_many).unwrap_err(),
+        WalletError::KeyChainError(KeyChainError::LookAheadExceeded)
+    );
+    assert!(wallet.get_all_issued_vrf_public_keys(DEFAULT_ACCOUNT_INDEX).unwrap().is_empty());
+
+    let key_count = 50;
+    wallet.set_lookahead_size(key_count, false).unwrap();
+    let keys = wallet.get_n_vrf_keys(DEFAULT_ACCOUNT_INDEX, key_count).unwrap();
+    assert_eq!(keys.len(), key_count as usize);
+
+    let distinct_keys = keys.iter().map(|(_, key)| key).collect::<BTreeSet<_>>();
+    assert_eq!(distinct_keys.len(), keys.len());
+    assert!(keys.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+    // the issued keys are persisted and the next one continues after them
+    let issued = wallet.get_all_issued_vrf_public_keys(DEFAULT_ACCOUNT_INDEX).unwrap();
+    assert_eq!(
+        issued
+            .into_iter()
+            .map(|(child_number, (key, _))| (child_number, key))
+            .collect::<Vec<_>>(),
+        keys
+    );
+    assert_eq!(
+        wallet.get_n_vrf_keys(DEFAULT_ACCOUNT_INDEX, 1).unwrap_err(),
+        WalletError::KeyChainError(KeyChainError::LookAheadExceeded)
+    );
+    assert_eq!(
+        wallet.get_n_vrf_keys(DEFAULT_ACCOUNT_INDEX, 0).unwrap(),
+        vec![]
+    );
+}
+
+#[derive(Default)]
+struct ReorgRecordingEvents {
+    reorgs: std::cell::RefCell<Vec<(U31, BlockHeight, usize, usize)>>,
+}
+
+impl WalletEvents for ReorgRecordingEvents {
+    fn new_block(&self) {}
+    fn set_transaction(&self, _id: U31, _tx: &wallet_types::WalletTx) {}
+    fn del_transaction(&self, _id: U31, _source: OutPointSourceId) {}
+
+    fn reorg(&self, id: U31, common_height: BlockHeight, disconnected: usize, connected: usize) {
+        self.reorgs.borrow_mut().push((id, common_height, disconnected, connected));
+    }
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn wallet_reorg_event(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_regtest());
+    let mut wallet = create_wallet(chain_config.clone());
+    let events = ReorgRecordingEvents::default();
+
+    let mut make_blocks = |wallet: &mut DefaultWallet, count: usize| -> Vec<Block> {
+        (0..count)
+            .map(|_| {
+                let address = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+                let reward = Amount::from_atoms(rng.gen_range(1..10000));
+                Block::new(
+                    vec![],
+                    chain_config.genesis_block_id(),
+                    chain_config.genesis_block().timestamp(),
+                    ConsensusData::None,
+                    BlockReward::new(vec![make_address_output(address, reward)]),
+                )
+                .unwrap()
+            })
+            .collect()
+    };
+
+    let main_len = rng.gen_range(2..10);
+    let blocks = make_blocks(&mut wallet, main_len);
+    wallet
+        .scan_new_blocks(DEFAULT_ACCOUNT_INDEX, BlockHeight::new(0), blocks, &events)
+        .unwrap();
+    assert!(events.reorgs.borrow().is_empty());
+
+    // extending the best block is not a reorg
+    let blocks = make_blocks(&mut wallet, 1);
+    wallet
+        .scan_new_blocks(
+            DEFAULT_ACCOUNT_INDEX,
+            BlockHeight::new(main_len as u64),
+            blocks,
+            &events,
+        )
+        .unwrap();
+    let main_len = main_len + 1;
+    assert!(events.reorgs.borrow().is_empty());
+
+    let common_height = rng.gen_range(0..main_len);
+    let connected = rng.gen_range(1..10);
+    let blocks = make_blocks(&mut wallet, connected);
+    wallet
+        .scan_new_blocks(
+            DEFAULT_ACCOUNT_INDEX,
+            BlockHeight::new(common_height as u64),
+            blocks,
+            &events,
+        )
+        .unwrap();
+
+    assert_eq!(
+        *events.reorgs.borrow(),
+        vec![(
+            DEFAULT_ACCOUNT_INDEX,
+            BlockHeight::new(common_height as u64),
+            main_len - common_height,
+            connected
+        )]
+    );
+    assert_eq!(
+        wallet.get_best_block_for_account(DEFAULT_ACCOUNT_INDEX).unwrap().1,
+        BlockHeight::new((common_height + connected) as u64)
+    );
+}
+
+#[derive(Default)]
+struct TokenRecordingEvents {
+    received: std::cell::RefCell<Vec<(TokenId, Amount, Id<Transaction>)>>,
+    sent: std::cell::RefCell<Vec<(TokenId, Amount, Id<Transaction>)>>,
+}
+
+impl WalletEvents for TokenRecordingEvents {
+    fn new_block(&self) {}
+    fn set_transaction(&self, _id: U31, _tx: &wallet_types::WalletTx) {}
+    fn del_transaction(&self, _id: U31, _source: OutPointSourceId) {}
 
-    let last_used = addresses_to_issue + 1;
-    let usage = wallet.get_addresses_usage(DEFAULT_ACCOUNT_INDEX).unwrap();
-    assert_eq!(usage.last_used(), Some(last_used.try_into().unwrap()));
-    assert_eq!(usage.last_issued(), Some(last_used.try_into().unwrap()));
+    fn on_token_received(&self, token_id: TokenId, amount: Amount, tx_id: Id<Transaction>) {
+        self.received.borrow_mut().push((token_id, amount, tx_id));
+    }
+
+    fn on_token_sent(&self, token_id: TokenId, amount: Amount, tx_id: Id<Transaction>) {
+        self.sent.borrow_mut().push((token_id, amount, tx_id));
+    }
 }
 
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
-fn wallet_set_lookahead_size(#[case] seed: Seed) {
+fn wallet_token_events(#[case] seed: Seed) {
     let mut rng = make_seedable_rng(seed);
-    let chain_config = Arc::new(create_regtest());
+    let chain_config = Arc::new(create_mainnet());
     let mut wallet = create_wallet(chain_config.clone());
+    let events = TokenRecordingEvents::default();
 
-    let usage = wallet.get_addresses_usage(DEFAULT_ACCOUNT_INDEX).unwrap();
-    assert_eq!(usage.last_used(), None);
-    assert_eq!(usage.last_issued(), None);
+    let scan_with_events = |wallet: &mut DefaultWallet, height: u64, block: Block| {
+        let height = BlockHeight::new(height);
+        wallet
+            .scan_new_blocks(DEFAULT_ACCOUNT_INDEX, height, vec![block.clone()], &events)
+            .unwrap();
+        wallet
+            .scan_new_blocks_unused_account(height, vec![block], &WalletEventsNoOp)
+            .unwrap();
+    };
 
-    // issue some new address
-    let addresses_to_issue = rng.gen_range(1..10);
-    for _ in 0..=addresses_to_issue {
-        let _ = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap();
-    }
+    let block1_amount = (Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000))
+        + chain_config.fungible_token_issuance_fee())
+    .unwrap();
+    let _ = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
 
-    let usage = wallet.get_addresses_usage(DEFAULT_ACCOUNT_INDEX).unwrap();
-    assert_eq!(usage.last_used(), None);
-    assert_eq!(
-        usage.last_issued(),
-        Some(addresses_to_issue.try_into().unwrap())
-    );
+    let address2 = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+    let token_issuance = TokenIssuanceV1 {
+        token_ticker: "XXXX".as_bytes().to_vec(),
+        number_of_decimals: rng.gen_range(1..18),
+        metadata_uri: "http://uri".as_bytes().to_vec(),
+        total_supply: common::chain::tokens::TokenTotalSupply::Unlimited,
+        authority: address2.as_object().clone(),
+        is_freezable: common::chain::tokens::IsTokenFreezable::No,
+    };
+    let (issued_token_id, token_issuance_transaction) = wallet
+        .issue_new_token(
+            DEFAULT_ACCOUNT_INDEX,
+            TokenIssuance::V1(token_issuance.clone()),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap();
 
-    let block1_amount = Amount::from_atoms(10000);
-    let (_, block1) = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
+    let block2_amount = chain_config.token_supply_change_fee(BlockHeight::zero());
+    let _ = create_block(
+        &chain_config,
+        &mut wallet,
+        vec![token_issuance_transaction],
+        block2_amount,
+        1,
+    );
+    // Issuing a token doesn't move any amount of it
+    assert!(events.received.borrow().is_empty());
 
-    let last_used = addresses_to_issue + 1;
-    let usage = wallet.get_addresses_usage(DEFAULT_ACCOUNT_INDEX).unwrap();
-    assert_eq!(usage.last_used(), Some(last_used.try_into().unwrap()));
-    assert_eq!(usage.last_issued(), Some(last_used.try_into().unwrap()));
+    let freezable = token_issuance.is_freezable.as_bool();
+    let token_info = RPCFungibleTokenInfo::new(
+        issued_token_id,
+        token_issuance.token_ticker,
+        token_issuance.number_of_decimals,
+        token_issuance.metadata_uri,
+        Amount::ZERO,
+        token_issuance.total_supply.into(),
+        false,
+        RPCIsTokenFrozen::NotFrozen { freezable },
+        token_issuance.authority,
+    );
+    let unconfirmed_token_info =
+        wallet.get_token_unconfirmed_info(DEFAULT_ACCOUNT_INDEX, &token_info).unwrap();
+    let amount_to_mint = Amount::from_atoms(rng.gen_range(2..100000));
+    let mint_transaction = wallet
+        .mint_tokens(
+            DEFAULT_ACCOUNT_INDEX,
+            &unconfirmed_token_info,
+            amount_to_mint,
+            address2,
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap();
+    let mint_tx_id = mint_transaction.transaction().get_id();
 
-    let coins = get_coin_balance_for_acc(&wallet, DEFAULT_ACCOUNT_INDEX);
-    assert_eq!(coins, block1_amount);
+    let reward_address = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+    let block3 = Block::new(
+        vec![mint_transaction],
+        chain_config.genesis_block_id(),
+        chain_config.genesis_block().timestamp(),
+        ConsensusData::None,
+        BlockReward::new(vec![make_address_output(reward_address, block2_amount)]),
+    )
+    .unwrap();
+    scan_with_events(&mut wallet, 2, block3);
 
-    let less_than_last_used = rng.gen_range(1..=last_used);
-    let err = wallet.set_lookahead_size(less_than_last_used, false).unwrap_err();
     assert_eq!(
-        err,
-        WalletError::ReducedLookaheadSize(less_than_last_used, last_used)
+        *events.received.borrow(),
+        vec![(issued_token_id, amount_to_mint, mint_tx_id)]
     );
+    assert!(events.sent.borrow().is_empty());
 
-    wallet.set_lookahead_size(less_than_last_used, true).unwrap();
-
-    scan_wallet(&mut wallet, BlockHeight::new(0), vec![block1.clone()]);
-    let coins = get_coin_balance_for_acc(&wallet, DEFAULT_ACCOUNT_INDEX);
-    assert_eq!(coins, Amount::ZERO);
-    let usage = wallet.get_addresses_usage(DEFAULT_ACCOUNT_INDEX).unwrap();
-    assert_eq!(usage.last_used(), None);
-    assert_eq!(usage.last_issued(), None);
+    // The change goes back to the wallet, so only the transferred amount is reported as sent
+    let tokens_to_transfer = Amount::from_atoms(rng.gen_range(1..amount_to_mint.into_atoms()));
+    let transfer_transaction = wallet
+        .create_transaction_to_addresses(
+            DEFAULT_ACCOUNT_INDEX,
+            [TxOutput::Transfer(
+                OutputValue::TokenV1(issued_token_id, tokens_to_transfer),
+                Destination::PublicKeyHash(PublicKeyHash::from_low_u64_be(1)),
+            )],
+            SelectedInputs::Utxos(vec![]),
+            BTreeMap::new(),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap();
+    let transfer_tx_id = transfer_transaction.transaction().get_id();
 
-    let more_than_last_used = rng.gen_range(last_used + 1..100);
-    wallet.set_lookahead_size(more_than_last_used, false).unwrap();
+    let reward_address = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+    let block4 = Block::new(
+        vec![transfer_transaction],
+        chain_config.genesis_block_id(),
+        chain_config.genesis_block().timestamp(),
+        ConsensusData::None,
+        BlockReward::new(vec![make_address_output(reward_address, block2_amount)]),
+    )
+    .unwrap();
+    scan_with_events(&mut wallet, 3, block4);
 
-    scan_wallet(&mut wallet, BlockHeight::new(0), vec![block1.clone()]);
-    let coins = get_coin_balance_for_acc(&wallet, DEFAULT_ACCOUNT_INDEX);
-    assert_eq!(coins, block1_amount);
-    let usage = wallet.get_addresses_usage(DEFAULT_ACCOUNT_INDEX).unwrap();
-    assert_eq!(usage.last_used(), Some(last_used.try_into().unwrap()));
-    assert_eq!(usage.last_issued(), Some(last_used.try_into().unwrap()));
+    assert_eq!(events.received.borrow().len(), 1);
+    assert_eq!(
+        *events.sent.borrow(),
+        vec![(issued_token_id, tokens_to_transfer, transfer_tx_id)]
+    );
 }
 
 #[rstest]
@@ -3878,6 +5956,7 @@ fn decommission_pool_wrong_account(#[case] seed: Seed) {
                 margin_ratio_per_thousand: PerThousand::new_from_rng(&mut rng),
                 cost_per_block: Amount::ZERO,
                 decommission_key: decommission_key.into_object(),
+                vrf_public_key: None,
             },
         )
         .unwrap();
@@ -3971,6 +6050,7 @@ fn decommission_pool_request_wrong_account(#[case] seed: Seed) {
                 margin_ratio_per_thousand: PerThousand::new_from_rng(&mut rng),
                 cost_per_block: Amount::ZERO,
                 decommission_key: decommission_key.into_object(),
+                vrf_public_key: None,
             },
         )
         .unwrap();
@@ -4057,6 +6137,7 @@ fn sign_decommission_pool_request_between_accounts(#[case] seed: Seed) {
                 margin_ratio_per_thousand: PerThousand::new_from_rng(&mut rng),
                 cost_per_block: Amount::ZERO,
                 decommission_key: decommission_key.into_object(),
+                vrf_public_key: None,
             },
         )
         .unwrap();
@@ -4165,6 +6246,7 @@ fn sign_decommission_pool_request_cold_wallet(#[case] seed: Seed) {
                 margin_ratio_per_thousand: PerThousand::new_from_rng(&mut rng),
                 cost_per_block: Amount::ZERO,
                 decommission_key: decommission_key.into_object(),
+                vrf_public_key: None,
             },
         )
         .unwrap();
@@ -4225,6 +6307,139 @@ fn sign_decommission_pool_request_cold_wallet(#[case] seed: Seed) {
     assert_eq!(coin_balance, pool_amount,);
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn coinjoin_three_parties(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_regtest());
+
+    let mnemonics = [
+        MNEMONIC,
+        "legal winner thank year wave sausage worth useful legal winner thank yellow",
+        "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+    ];
+    let mut wallets = mnemonics
+        .iter()
+        .map(|mnemonic| create_wallet_with_mnemonic(chain_config.clone(), mnemonic))
+        .collect_vec();
+
+    let coinjoin_amount = Amount::from_atoms(rng.gen_range(1..1000));
+    let mut initial_balances = vec![];
+    for wallet in wallets.iter_mut() {
+        let block1_amount = Amount::from_atoms(rng.gen_range(1000..10000));
+        let _ = create_block(&chain_config, wallet, vec![], block1_amount, 0);
+        initial_balances.push(block1_amount);
+    }
+
+    // every participant sends the same amount to a fresh destination
+    let proposals = wallets
+        .iter_mut()
+        .map(|wallet| {
+            let destination = Destination::PublicKey(
+                crypto::key::PrivateKey::new_from_rng(
+                    &mut rng,
+                    crypto::key::KeyKind::Secp256k1Schnorr,
+                )
+                .1,
+            );
+            let output = TxOutput::Transfer(OutputValue::Coin(coinjoin_amount), destination);
+            wallet
+                .create_coinjoin_proposal(
+                    DEFAULT_ACCOUNT_INDEX,
+                    vec![output],
+                    FeeRate::from_amount_per_kb(Amount::ZERO),
+                )
+                .unwrap()
+        })
+        .collect_vec();
+
+    // each participant arrives at the same joint transaction
+    let joint_txs = wallets
+        .iter()
+        .enumerate()
+        .map(|(idx, wallet)| {
+            let peer_proposals = proposals
+                .iter()
+                .enumerate()
+                .filter(|(peer_idx, _)| *peer_idx != idx)
+                .map(|(_, proposal)| proposal.clone())
+                .collect();
+            wallet.apply_coinjoin_proposal(proposals[idx].clone(), peer_proposals).unwrap()
+        })
+        .collect_vec();
+    assert!(joint_txs.iter().all(|ptx| *ptx == joint_txs[0]));
+
+    let mut ptx = joint_txs[0].clone();
+    let num_inputs = proposals.iter().map(|p| p.utxos.len()).sum::<usize>();
+    let num_outputs = proposals.iter().map(|p| p.outputs.len()).sum::<usize>();
+    assert_eq!(ptx.tx().inputs().len(), num_inputs);
+    assert_eq!(ptx.tx().outputs().len(), num_outputs);
+
+    // the transaction is only complete once every participant has signed its inputs
+    for wallet in wallets.iter_mut() {
+        assert!(!ptx.all_signatures_available());
+        ptx = wallet
+            .sign_raw_transaction(DEFAULT_ACCOUNT_INDEX, TransactionToSign::Partial(ptx))
+            .unwrap()
+            .0;
+    }
+    assert!(ptx.all_signatures_available());
+    let signed_tx = ptx.into_signed_tx().unwrap();
+
+    // each participant gets its change back
+    for (wallet, initial_balance) in wallets.iter_mut().zip(initial_balances) {
+        let _ = create_block(
+            &chain_config,
+            wallet,
+            vec![signed_tx.clone()],
+            Amount::ZERO,
+            1,
+        );
+        assert_eq!(
+            get_coin_balance(wallet),
+            (initial_balance - coinjoin_amount).unwrap()
+        );
+    }
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn coinjoin_duplicate_proposal(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_regtest());
+
+    let mut wallet = create_wallet(chain_config.clone());
+    let block1_amount = Amount::from_atoms(rng.gen_range(1000..10000));
+    let _ = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
+
+    let amount = Amount::from_atoms(rng.gen_range(1..1000));
+    let output = gen_random_transfer(&mut rng, amount);
+    let proposal = wallet
+        .create_coinjoin_proposal(
+            DEFAULT_ACCOUNT_INDEX,
+            vec![output],
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap();
+
+    // the same UTXOs cannot be spent twice
+    let err = wallet
+        .apply_coinjoin_proposal(proposal.clone(), vec![proposal.clone()])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        WalletError::CoinJoinDuplicateInput(proposal.utxos[0].clone())
+    );
+
+    // the published outputs must match the template
+    let mut tampered = proposal.clone();
+    tampered.outputs.pop();
+    let err = wallet.apply_coinjoin_proposal(tampered, vec![]).unwrap_err();
+    assert_eq!(err, WalletError::InvalidCoinJoinProposal);
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -4266,6 +6481,7 @@ fn filter_pools(#[case] seed: Seed) {
                 margin_ratio_per_thousand: PerThousand::new_from_rng(&mut rng),
                 cost_per_block: Amount::ZERO,
                 decommission_key: decommission_key.into_object(),
+                vrf_public_key: None,
             },
         )
         .unwrap();
@@ -4521,7 +6737,120 @@ fn test_add_standalone_private_key(#[case] seed: Seed) {
         .get_transaction(DEFAULT_ACCOUNT_INDEX, tx.transaction().get_id())
         .unwrap();
 
-    assert_eq!(tx_data.get_transaction(), tx.transaction());
+    assert_eq!(tx_data.get_transaction(), tx.transaction());
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn find_account_for_address(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_regtest());
+
+    let mut wallet = create_wallet(chain_config.clone());
+
+    // A new account can only be created once the last one has transactions
+    let block1_amount = Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000));
+    let (hd_address, _) = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
+    let (account_index, _) = wallet.create_next_account(None).unwrap();
+
+    // HD address of the default account
+    assert_eq!(
+        wallet.find_account_for_address(hd_address.into_object()).unwrap(),
+        Some((DEFAULT_ACCOUNT_INDEX, AddressOrigin::HdDerived))
+    );
+
+    // HD address of the new account
+    let (_, hd_address) = wallet.get_new_address(account_index).unwrap();
+    assert_eq!(
+        wallet.find_account_for_address(hd_address.into_object()).unwrap(),
+        Some((account_index, AddressOrigin::HdDerived))
+    );
+
+    // Standalone address of the new account
+    let (private_key, pub_key) =
+        crypto::key::PrivateKey::new_from_rng(&mut rng, crypto::key::KeyKind::Secp256k1Schnorr);
+    wallet.add_standalone_private_key(account_index, private_key, None).unwrap();
+    assert_eq!(
+        wallet
+            .find_account_for_address(Destination::PublicKeyHash((&pub_key).into()))
+            .unwrap(),
+        Some((account_index, AddressOrigin::Standalone))
+    );
+
+    // Address that doesn't belong to the wallet
+    let (_, foreign_pub_key) =
+        crypto::key::PrivateKey::new_from_rng(&mut rng, crypto::key::KeyKind::Secp256k1Schnorr);
+    assert_eq!(
+        wallet
+            .find_account_for_address(Destination::PublicKeyHash((&foreign_pub_key).into()))
+            .unwrap(),
+        None
+    );
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn test_add_standalone_watch_only_address(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_regtest());
+
+    let mut wallet = create_wallet(chain_config.clone());
+
+    // add a random address unrelated to the wallet as watch only
+    let (_, pub_key) =
+        crypto::key::PrivateKey::new_from_rng(&mut rng, crypto::key::KeyKind::Secp256k1Schnorr);
+    let pub_key_hash = PublicKeyHash::from(&pub_key);
+    wallet
+        .add_standalone_address(DEFAULT_ACCOUNT_INDEX, pub_key_hash, Some("watched".into()))
+        .unwrap();
+    let address = Address::new(&chain_config, Destination::PublicKeyHash(pub_key_hash)).unwrap();
+
+    // Generate a new block with 3 outputs to the watched address
+    let amounts = (0..3).map(|_| Amount::from_atoms(rng.gen_range(1..10000))).collect::<Vec<_>>();
+    let outputs = amounts
+        .iter()
+        .map(|amount| make_address_output(address.clone(), *amount))
+        .collect();
+    let tx = SignedTransaction::new(Transaction::new(0, vec![], outputs).unwrap(), vec![]).unwrap();
+
+    let block1 = Block::new(
+        vec![tx],
+        chain_config.genesis_block_id(),
+        chain_config.genesis_block().timestamp(),
+        ConsensusData::None,
+        BlockReward::new(vec![]),
+    )
+    .unwrap();
+
+    scan_wallet(&mut wallet, BlockHeight::new(0), vec![block1]);
+
+    // The coins don't count towards the wallet's own balance
+    let coin_balance = get_coin_balance(&wallet);
+    assert_eq!(coin_balance, Amount::ZERO);
+
+    // but they are tracked for the watched address
+    let (destination, balances, details) = wallet
+        .get_all_standalone_address_details(
+            DEFAULT_ACCOUNT_INDEX,
+            Destination::PublicKeyHash(pub_key_hash),
+        )
+        .unwrap();
+    assert_eq!(destination, Destination::PublicKeyHash(pub_key_hash));
+    let expected_balance = amounts.into_iter().sum::<Option<Amount>>().unwrap();
+    assert_eq!(
+        balances,
+        BTreeMap::from([(Currency::Coin, expected_balance)])
+    );
+    match details {
+        StandaloneAddressDetails::WatchOnly(details) => {
+            assert_eq!(details.label, Some("watched".into()));
+        }
+        StandaloneAddressDetails::PrivateKey(_) | StandaloneAddressDetails::Multisig(_) => {
+            panic!("wrong address details type")
+        }
+    }
 }
 
 #[rstest]
@@ -4640,6 +6969,133 @@ fn test_add_standalone_multisig(#[case] seed: Seed) {
     assert!(statuses.iter().all(|s| *s == SignatureStatus::FullySigned));
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn test_create_multisig_account(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_regtest());
+
+    let mut wallet1 = create_wallet_with_mnemonic(chain_config.clone(), MNEMONIC);
+    let mut wallet2 = create_wallet_with_mnemonic(chain_config.clone(), MNEMONIC2);
+
+    // A new account can only be created once the last one has transactions
+    let address1 = wallet1.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+    let address2 = wallet2.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+    let block1_amount = Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000));
+    let block1 = Block::new(
+        vec![],
+        chain_config.genesis_block_id(),
+        chain_config.genesis_block().timestamp(),
+        ConsensusData::None,
+        BlockReward::new(vec![
+            make_address_output(address1, block1_amount),
+            make_address_output(address2, block1_amount),
+        ]),
+    )
+    .unwrap();
+    scan_wallet(&mut wallet1, BlockHeight::new(0), vec![block1.clone()]);
+    scan_wallet(&mut wallet2, BlockHeight::new(0), vec![block1.clone()]);
+
+    // The cosigners exchange the public keys of their next accounts
+    let pub_key1 = wallet1.get_next_multisig_account_public_key().unwrap();
+    let pub_key2 = wallet2.get_next_multisig_account_public_key().unwrap();
+
+    let min_required_signatures = 2;
+    let (account_index1, challenge1) = wallet1
+        .create_multisig_account(
+            NonZeroU8::new(min_required_signatures).unwrap(),
+            vec![pub_key2],
+            Some("multisig".into()),
+        )
+        .unwrap();
+    let (account_index2, challenge2) = wallet2
+        .create_multisig_account(
+            NonZeroU8::new(min_required_signatures).unwrap(),
+            vec![pub_key1],
+            None,
+        )
+        .unwrap();
+    assert_eq!(account_index1, U31::ONE);
+    assert_eq!(account_index2, U31::ONE);
+    assert_eq!(challenge1, challenge2);
+    let multisig_destination = Destination::ClassicMultisig((&challenge1).into());
+    assert_eq!(
+        wallet1.get_current_change_address(account_index1).unwrap().into_object(),
+        multisig_destination
+    );
+
+    // Send some coins to the multisig address
+    let block2_amount = Amount::from_atoms(rng.gen_range(2..10000));
+    let output = make_address_output(
+        Address::new(&chain_config, multisig_destination.clone()).unwrap(),
+        block2_amount,
+    );
+    let tx =
+        SignedTransaction::new(Transaction::new(0, vec![], vec![output]).unwrap(), vec![]).unwrap();
+    let block2 = Block::new(
+        vec![tx.clone()],
+        block1.get_id().into(),
+        chain_config.genesis_block().timestamp(),
+        ConsensusData::None,
+        BlockReward::new(vec![]),
+    )
+    .unwrap();
+    scan_wallet(&mut wallet1, BlockHeight::new(1), vec![block2.clone()]);
+    scan_wallet(&mut wallet2, BlockHeight::new(1), vec![block2]);
+
+    // The funds are the balance of the multisig account in both wallets
+    assert_eq!(
+        get_coin_balance_for_acc(&wallet1, account_index1),
+        block2_amount
+    );
+    assert_eq!(
+        get_coin_balance_for_acc(&wallet2, account_index2),
+        block2_amount
+    );
+    assert_eq!(get_coin_balance(&wallet1), block1_amount);
+
+    // Coin selection spends the multisig funds and sends the change back to the multisig address
+    let to_send = Amount::from_atoms(rng.gen_range(1..block2_amount.into_atoms()));
+    let (ptx, _) = wallet1
+        .create_unsigned_transaction_to_addresses(
+            account_index1,
+            [TxOutput::Transfer(OutputValue::Coin(to_send), Destination::AnyoneCanSpend)],
+            SelectedInputs::Utxos(vec![]),
+            None,
+            BTreeMap::new(),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap();
+    assert_eq!(
+        ptx.tx().inputs(),
+        &[TxInput::from_utxo(OutPointSourceId::Transaction(tx.transaction().get_id()), 0)]
+    );
+    assert!(ptx.tx().outputs().contains(&TxOutput::Transfer(
+        OutputValue::Coin((block2_amount - to_send).unwrap()),
+        multisig_destination,
+    )));
+
+    // Each cosigner contributes a single signature
+    let (ptx, _, statuses) = wallet1
+        .sign_raw_transaction(account_index1, TransactionToSign::Partial(ptx))
+        .unwrap();
+    assert_eq!(
+        statuses,
+        vec![SignatureStatus::PartialMultisig {
+            required_signatures: min_required_signatures,
+            num_signatures: 1,
+        }]
+    );
+
+    let (ptx, _, statuses) = wallet2
+        .sign_raw_transaction(account_index2, TransactionToSign::Partial(ptx))
+        .unwrap();
+    assert!(ptx.all_signatures_available());
+    assert_eq!(statuses, vec![SignatureStatus::FullySigned]);
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -4938,3 +7394,338 @@ fn create_htlc_and_refund(#[case] seed: Seed) {
     assert_eq!(get_coin_balance(&wallet1), coin_balance);
     assert_eq!(get_coin_balance(&wallet2), Amount::ZERO);
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn pending_transactions_to_be_broadcast(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+
+    let mut wallet = create_wallet(chain_config.clone());
+
+    for block_height in 0..3 {
+        let reward = Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000));
+        let _ = create_block(&chain_config, &mut wallet, vec![], reward, block_height);
+    }
+
+    let txs = (0..3)
+        .map(|_| {
+            let address = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+            let tx = wallet
+                .create_transaction_to_addresses(
+                    DEFAULT_ACCOUNT_INDEX,
+                    [TxOutput::Transfer(
+                        OutputValue::Coin(Amount::from_atoms(rng.gen_range(1..100))),
+                        address.into_object(),
+                    )],
+                    SelectedInputs::Utxos(vec![]),
+                    BTreeMap::new(),
+                    FeeRate::from_amount_per_kb(Amount::ZERO),
+                    FeeRate::from_amount_per_kb(Amount::ZERO),
+                )
+                .unwrap();
+            wallet.add_unconfirmed_tx(tx.clone(), &WalletEventsNoOp).unwrap();
+            tx
+        })
+        .collect::<Vec<_>>();
+
+    let tx_ids = |txs: &[SignedTransaction]| {
+        txs.iter().map(|tx| tx.transaction().get_id()).collect::<BTreeSet<_>>()
+    };
+    assert_eq!(
+        tx_ids(&wallet.get_pending_transactions_to_be_broadcast().unwrap()),
+        tx_ids(&txs)
+    );
+
+    // Once the first transaction is confirmed only the other two are left to be broadcast
+    let _ = create_block(
+        &chain_config,
+        &mut wallet,
+        vec![txs[0].clone()],
+        Amount::ZERO,
+        3,
+    );
+    assert_eq!(
+        tx_ids(&wallet.get_pending_transactions_to_be_broadcast().unwrap()),
+        tx_ids(&txs[1..])
+    );
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn simulate_scan(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+
+    let mut wallet = create_wallet(chain_config.clone());
+
+    let reward = Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000));
+    let _ = create_block(&chain_config, &mut wallet, vec![], reward, 0);
+    let balance = get_coin_balance(&wallet);
+
+    // Spends the wallet's utxo
+    let spending_tx = wallet
+        .create_transaction_to_addresses(
+            DEFAULT_ACCOUNT_INDEX,
+            [gen_random_transfer(&mut rng, Amount::from_atoms(1))],
+            SelectedInputs::Utxos(vec![]),
+            BTreeMap::new(),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap();
+
+    // Sends coins to the wallet
+    let address = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+    let receiving_tx = Transaction::new(
+        0,
+        vec![TxInput::from_utxo(
+            OutPointSourceId::Transaction(Id::new(H256::random_using(&mut rng))),
+            0,
+        )],
+        vec![make_address_output(address, Amount::from_atoms(rng.gen_range(1..1000)))],
+    )
+    .unwrap();
+    let receiving_tx =
+        SignedTransaction::new(receiving_tx, vec![InputWitness::NoSignature(None)]).unwrap();
+
+    // Unrelated to the wallet
+    let foreign_tx = Transaction::new(
+        0,
+        vec![TxInput::from_utxo(
+            OutPointSourceId::Transaction(Id::new(H256::random_using(&mut rng))),
+            0,
+        )],
+        vec![gen_random_transfer(&mut rng, Amount::from_atoms(rng.gen_range(1..1000)))],
+    )
+    .unwrap();
+    let foreign_tx =
+        SignedTransaction::new(foreign_tx, vec![InputWitness::NoSignature(None)]).unwrap();
+
+    let block = Block::new(
+        vec![foreign_tx.clone(), spending_tx.clone(), receiving_tx.clone()],
+        chain_config.genesis_block_id(),
+        chain_config.genesis_block().timestamp(),
+        ConsensusData::None,
+        BlockReward::new(vec![gen_random_transfer(&mut rng, reward)]),
+    )
+    .unwrap();
+
+    // The reward of the next block goes to the wallet and is spent in the block after it
+    let reward_address = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+    let reward_block = Block::new(
+        vec![],
+        block.get_id().into(),
+        chain_config.genesis_block().timestamp(),
+        ConsensusData::None,
+        BlockReward::new(vec![make_address_output(reward_address, reward)]),
+    )
+    .unwrap();
+    let reward_spending_tx = Transaction::new(
+        0,
+        vec![TxInput::from_utxo(reward_block.get_id().into(), 0)],
+        vec![gen_random_transfer(&mut rng, reward)],
+    )
+    .unwrap();
+    let reward_spending_tx =
+        SignedTransaction::new(reward_spending_tx, vec![InputWitness::NoSignature(None)]).unwrap();
+    let last_block = Block::new(
+        vec![reward_spending_tx.clone()],
+        reward_block.get_id().into(),
+        chain_config.genesis_block().timestamp(),
+        ConsensusData::None,
+        BlockReward::new(vec![gen_random_transfer(&mut rng, reward)]),
+    )
+    .unwrap();
+
+    let expected: Vec<OutPointSourceId> = vec![
+        spending_tx.transaction().get_id().into(),
+        receiving_tx.transaction().get_id().into(),
+        reward_block.get_id().into(),
+        reward_spending_tx.transaction().get_id().into(),
+    ];
+    let recognised = wallet
+        .simulate_scan(DEFAULT_ACCOUNT_INDEX, &[block, reward_block, last_block])
+        .unwrap();
+    assert_eq!(recognised, expected);
+
+    // The wallet is not changed
+    assert_eq!(get_coin_balance(&wallet), balance);
+    for tx in [foreign_tx, spending_tx, receiving_tx] {
+        assert!(wallet
+            .get_transaction(DEFAULT_ACCOUNT_INDEX, tx.transaction().get_id())
+            .is_err());
+    }
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn open_read_only(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+
+    let mut wallet = create_wallet(chain_config.clone());
+
+    let reward = Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000));
+    let (address, _) = create_block(&chain_config, &mut wallet, vec![], reward, 0);
+
+    let mut read_only_wallet =
+        Wallet::open_read_only(chain_config.clone(), wallet.db.clone(), WalletType::Hot).unwrap();
+
+    // Reading works
+    assert_eq!(get_coin_balance(&read_only_wallet), reward);
+    assert_eq!(
+        read_only_wallet.find_account_for_address(address.as_object().clone()).unwrap(),
+        Some((DEFAULT_ACCOUNT_INDEX, AddressOrigin::HdDerived))
+    );
+
+    // Changing the wallet doesn't
+    let err = read_only_wallet
+        .create_transaction_to_addresses(
+            DEFAULT_ACCOUNT_INDEX,
+            [gen_random_transfer(&mut rng, Amount::from_atoms(1))],
+            SelectedInputs::Utxos(vec![]),
+            BTreeMap::new(),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap_err();
+    assert_eq!(err, WalletError::ReadOnly);
+    assert_eq!(
+        read_only_wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap_err(),
+        WalletError::ReadOnly
+    );
+    let median_time = read_only_wallet.latest_median_time;
+    assert_eq!(
+        read_only_wallet
+            .set_median_time(median_time.add_int_seconds(1).unwrap())
+            .unwrap_err(),
+        WalletError::ReadOnly
+    );
+    assert_eq!(read_only_wallet.latest_median_time, median_time);
+    assert_eq!(get_coin_balance(&read_only_wallet), reward);
+
+    // The primary wallet is still usable
+    wallet
+        .create_transaction_to_addresses(
+            DEFAULT_ACCOUNT_INDEX,
+            [gen_random_transfer(&mut rng, Amount::from_atoms(1))],
+            SelectedInputs::Utxos(vec![]),
+            BTreeMap::new(),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap();
+
+    // An outdated database can't be opened, as it would have to be migrated first
+    {
+        let mut db_tx = wallet.db.transaction_rw(None).unwrap();
+        db_tx.set_storage_version(WALLET_VERSION_V6).unwrap();
+        db_tx.commit().unwrap();
+    }
+    assert_eq!(
+        Wallet::open_read_only(chain_config, wallet.db.clone(), WalletType::Hot).unwrap_err(),
+        WalletError::UnsupportedWalletVersion(WALLET_VERSION_V6)
+    );
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn open_read_only_wallet_file(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("wallet.sqlite");
+
+    // A missing file is not created
+    assert!(open_wallet_file_read_only(&file_path).is_err());
+    assert!(!file_path.exists());
+
+    let reward = Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000));
+    {
+        let db = open_or_create_wallet_file(&file_path).unwrap();
+        let mut wallet = Wallet::create_new_wallet(
+            chain_config.clone(),
+            db,
+            MNEMONIC,
+            None,
+            StoreSeedPhrase::DoNotStore,
+            (BlockHeight::new(0), chain_config.genesis_block_id()),
+            WalletType::Hot,
+        )
+        .unwrap();
+        create_block(&chain_config, &mut wallet, vec![], reward, 0);
+    }
+    let file_contents = std::fs::read(&file_path).unwrap();
+
+    let db = open_wallet_file_read_only(&file_path).unwrap();
+    let wallet = Wallet::open_read_only(chain_config.clone(), db, WalletType::Hot).unwrap();
+    assert_eq!(get_coin_balance(&wallet), reward);
+
+    // The storage itself rejects writes, not only the wallet
+    let mut db_tx = wallet.db.transaction_rw(None).unwrap();
+    assert!(db_tx.set_storage_version(CURRENT_WALLET_VERSION).is_err());
+    drop(db_tx);
+    drop(wallet);
+
+    assert_eq!(std::fs::read(&file_path).unwrap(), file_contents);
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn current_change_address(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+
+    let mut wallet = create_wallet(chain_config.clone());
+
+    let block1_amount = Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000));
+    let _ = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
+
+    // Looking at the change address doesn't issue it
+    let change_address = wallet.get_current_change_address(DEFAULT_ACCOUNT_INDEX).unwrap();
+    for _ in 0..10 {
+        assert_eq!(
+            wallet.get_current_change_address(DEFAULT_ACCOUNT_INDEX).unwrap(),
+            change_address
+        );
+    }
+
+    // And the next transaction sends the change to it
+    let tx = wallet
+        .create_transaction_to_addresses(
+            DEFAULT_ACCOUNT_INDEX,
+            [gen_random_transfer(&mut rng, Amount::from_atoms(1))],
+            SelectedInputs::Utxos(vec![]),
+            BTreeMap::new(),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+        )
+        .unwrap();
+    let change_outputs = tx
+        .transaction()
+        .outputs()
+        .iter()
+        .filter(|output| match output {
+            TxOutput::Transfer(_, destination) => destination == change_address.as_object(),
+            _ => false,
+        })
+        .count();
+    assert_eq!(change_outputs, 1);
+    assert_eq!(
+        wallet.get_current_change_address(DEFAULT_ACCOUNT_INDEX).unwrap(),
+        change_address
+    );
+
+    // Once the transaction is added, the change address is used and the next one is returned
+    wallet.add_unconfirmed_tx(tx, &WalletEventsNoOp).unwrap();
+    assert_ne!(
+        wallet.get_current_change_address(DEFAULT_ACCOUNT_INDEX).unwrap(),
+        change_address
+    );
+}
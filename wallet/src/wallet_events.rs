@@ -13,7 +13,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use common::chain::OutPointSourceId;
+use common::{
+    chain::{tokens::TokenId, OutPointSourceId, Transaction},
+    primitives::{Amount, BlockHeight, Id},
+};
 use crypto::key::hdkd::u31::U31;
 use wallet_types::WalletTx;
 
@@ -29,6 +32,27 @@ pub trait WalletEvents {
 
     /// The transaction is removed from the DB
     fn del_transaction(&self, id: U31, source_id: OutPointSourceId);
+
+    /// Previously scanned blocks of the account above `common_height` were replaced because of
+    /// a reorg, `disconnected` blocks were rolled back and `connected` new blocks were scanned instead
+    fn reorg(
+        &self,
+        _id: U31,
+        _common_height: BlockHeight,
+        _disconnected: usize,
+        _connected: usize,
+    ) {
+    }
+
+    /// A batch of blocks was scanned while syncing the wallet (e.g. during a rescan from genesis),
+    /// the wallet is now at the `current` height and the node's tip is at `tip`
+    fn rescan_progress(&self, _current: BlockHeight, _tip: BlockHeight) {}
+
+    /// A confirmed transaction has increased the account's balance of the token
+    fn on_token_received(&self, _token_id: TokenId, _amount: Amount, _tx_id: Id<Transaction>) {}
+
+    /// A confirmed transaction has decreased the account's balance of the token
+    fn on_token_sent(&self, _token_id: TokenId, _amount: Amount, _tx_id: Id<Transaction>) {}
 }
 
 pub struct WalletEventsNoOp;
@@ -22,7 +22,10 @@ use crate::{
 };
 use common::{
     address::Address,
-    chain::{block::timestamp::BlockTimestamp, Destination, SignedTransaction},
+    chain::{
+        block::timestamp::BlockTimestamp, classic_multisig::ClassicMultisigChallenge, Destination,
+        SignedTransaction,
+    },
 };
 use crypto::{
     kdf::KdfChallenge,
@@ -286,6 +289,13 @@ macro_rules! impl_read_ops {
                     .map(|iter| iter.map(|(key, value)| (key, value.label)).collect())
             }
 
+            fn get_account_multisig_challenge(
+                &self,
+                account_id: &AccountId,
+            ) -> crate::Result<Option<ClassicMultisigChallenge>> {
+                self.read::<db::DBAccountMultisigChallenges, _, _>(account_id)
+            }
+
             fn get_keychain_usage_state(
                 &self,
                 id: &AccountKeyPurposeId,
@@ -531,6 +541,14 @@ macro_rules! impl_write_ops {
                 self.write::<db::DBStandaloneMultisigKeys, _, _, _>(id, key)
             }
 
+            fn set_account_multisig_challenge(
+                &mut self,
+                account_id: &AccountId,
+                challenge: &ClassicMultisigChallenge,
+            ) -> crate::Result<()> {
+                self.write::<db::DBAccountMultisigChallenges, _, _, _>(account_id, challenge)
+            }
+
             fn set_account(&mut self, id: &AccountId, tx: &AccountInfo) -> crate::Result<()> {
                 self.write::<db::DBAccounts, _, _, _>(id, tx)
             }
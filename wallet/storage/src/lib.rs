@@ -21,7 +21,10 @@ pub mod schema;
 
 use common::{
     address::{Address, AddressError},
-    chain::{block::timestamp::BlockTimestamp, Destination, SignedTransaction},
+    chain::{
+        block::timestamp::BlockTimestamp, classic_multisig::ClassicMultisigChallenge, Destination,
+        SignedTransaction,
+    },
 };
 use crypto::{
     kdf::KdfChallenge,
@@ -95,6 +98,10 @@ pub trait WalletStorageReadLocked {
         &self,
         account_id: &AccountId,
     ) -> Result<Vec<(AccountPublicKey, Option<String>)>>;
+    fn get_account_multisig_challenge(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Option<ClassicMultisigChallenge>>;
     fn get_accounts_info(&self) -> crate::Result<BTreeMap<AccountId, AccountInfo>>;
     fn get_address(&self, id: &AccountDerivationPathId) -> Result<Option<String>>;
     fn get_addresses(
@@ -167,6 +174,11 @@ pub trait WalletStorageWriteLocked: WalletStorageReadLocked {
         id: &AccountAddress,
         key: &StandaloneMultisig,
     ) -> Result<()>;
+    fn set_account_multisig_challenge(
+        &mut self,
+        account_id: &AccountId,
+        challenge: &ClassicMultisigChallenge,
+    ) -> Result<()>;
     fn set_account(&mut self, id: &AccountId, content: &AccountInfo) -> Result<()>;
     fn del_account(&mut self, id: &AccountId) -> Result<()>;
     fn set_address(
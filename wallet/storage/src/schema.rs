@@ -15,7 +15,7 @@
 
 //! Wallet database schema
 
-use common::chain::SignedTransaction;
+use common::chain::{classic_multisig::ClassicMultisigChallenge, SignedTransaction};
 use crypto::key::extended::ExtendedPublicKey;
 use utils::maybe_encrypted::MaybeEncrypted;
 use wallet_types::{
@@ -62,5 +62,7 @@ storage::decl_schema! {
         pub DBStandaloneMultisigKeys: Map<AccountAddress, StandaloneMultisig>,
         /// Store for standalone private keys added to accounts
         pub DBStandalonePrivateKeys: Map<AccountPublicKey, StandalonePrivateKey>,
+        /// Store for the classic multisig challenge of the multisig accounts
+        pub DBAccountMultisigChallenges: Map<AccountId, ClassicMultisigChallenge>,
     }
 }
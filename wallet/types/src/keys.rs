@@ -18,6 +18,7 @@ use crypto::key::extended::ExtendedPrivateKey;
 use crypto::key::hdkd::child_number::ChildNumber;
 use crypto::key::hdkd::u31::U31;
 use crypto::vrf::ExtendedVRFPrivateKey;
+use rpc_description::HasValueHint;
 use serialization::{Decode, Encode};
 
 /// The index of the receiving key hierarchy
@@ -119,6 +120,44 @@ impl KeychainUsageState {
     }
 }
 
+/// How an address owned by an account came to be part of it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub enum AddressOrigin {
+    /// Derived from the account's HD key chain
+    HdDerived,
+    /// Added to the account as a standalone watch-only address, private key or multisig
+    Standalone,
+}
+
+/// The configured lookahead size of an account and how much of it is left to issue addresses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub struct LookaheadStatus {
+    /// The configured lookahead size
+    pub configured: u32,
+    /// The last receiving address index used in the blockchain
+    pub last_used: Option<u32>,
+    /// The number of receiving addresses that can still be issued before reaching the lookahead limit
+    pub gap_remaining: u32,
+}
+
+impl LookaheadStatus {
+    pub fn new(lookahead_size: u32, usage: &KeychainUsageState) -> Self {
+        let last_used = usage.last_used().map(U31::into_u32);
+        // Addresses can be issued up to the last used index + lookahead size,
+        // or up to the lookahead size (exclusive) if no address has been used yet
+        let issuable_end = last_used.map_or(lookahead_size, |last_used| {
+            last_used.saturating_add(lookahead_size).saturating_add(1)
+        });
+        let next_to_issue = usage.last_issued().map_or(0, |last_issued| last_issued.into_u32() + 1);
+
+        Self {
+            configured: lookahead_size,
+            last_used,
+            gap_remaining: issuable_end.saturating_sub(next_to_issue),
+        }
+    }
+}
+
 /// Just an empty struct used as key for the DB table
 /// It only represents a single value as there can be only one root key
 #[derive(PartialEq, Eq, PartialOrd, Ord, Encode, Decode)]
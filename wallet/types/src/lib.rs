@@ -29,5 +29,5 @@ pub use account_id::{
     AccountWalletTxId,
 };
 pub use account_info::AccountInfo;
-pub use keys::{KeyPurpose, KeychainUsageState, RootKeys};
+pub use keys::{AddressOrigin, KeyPurpose, KeychainUsageState, LookaheadStatus, RootKeys};
 pub use wallet_tx::{BlockInfo, WalletTx};
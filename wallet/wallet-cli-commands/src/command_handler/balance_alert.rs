@@ -0,0 +1,255 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    collections::BTreeMap,
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+use common::primitives::{Amount, BlockHeight};
+use crypto::key::hdkd::u31::U31;
+
+/// The minimum number of blocks between two low balance warnings for the same account
+pub const BALANCE_ALERT_INTERVAL_BLOCKS: u64 = 10;
+
+const CONFIG_FILE_SUFFIX: &str = ".config.json";
+const BALANCE_ALERTS_KEY: &str = "balance_alerts";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BalanceAlert {
+    threshold: Amount,
+    last_warning_height: Option<BlockHeight>,
+}
+
+/// Per account thresholds below which a warning is printed when a new block arrives.
+///
+/// The thresholds are stored in the wallet config file, which lives next to the wallet file.
+#[derive(Debug, Default)]
+pub struct BalanceAlerts {
+    alerts: BTreeMap<U31, BalanceAlert>,
+    config_file: Option<PathBuf>,
+    last_checked_height: Option<BlockHeight>,
+}
+
+impl BalanceAlerts {
+    /// Load the thresholds from the config file of the wallet at `wallet_path`.
+    /// A missing or malformed config file results in no alerts being set.
+    pub fn load(wallet_path: &Path) -> Self {
+        let config_file = config_file_path(wallet_path);
+
+        let alerts = match std::fs::read_to_string(&config_file) {
+            Ok(contents) => parse_thresholds(&contents).unwrap_or_else(|| {
+                logging::log::error!(
+                    "Ignoring malformed wallet config file {}",
+                    config_file.display()
+                );
+                BTreeMap::new()
+            }),
+            Err(_) => BTreeMap::new(),
+        }
+        .into_iter()
+        .map(|(account, threshold)| {
+            let alert = BalanceAlert {
+                threshold,
+                last_warning_height: None,
+            };
+            (account, alert)
+        })
+        .collect();
+
+        Self {
+            alerts,
+            config_file: Some(config_file),
+            last_checked_height: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.alerts.is_empty()
+    }
+
+    pub fn accounts(&self) -> impl Iterator<Item = U31> + '_ {
+        self.alerts.keys().copied()
+    }
+
+    /// Set the threshold of an account and save all the thresholds to the config file
+    pub fn set_threshold(&mut self, account: U31, threshold: Amount) -> std::io::Result<()> {
+        self.alerts.insert(
+            account,
+            BalanceAlert {
+                threshold,
+                last_warning_height: None,
+            },
+        );
+
+        match &self.config_file {
+            Some(config_file) => std::fs::write(config_file, self.to_config_json()),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns true only the first time it is called for a given block height
+    pub fn is_new_block(&mut self, height: BlockHeight) -> bool {
+        let is_new = self.last_checked_height != Some(height);
+        self.last_checked_height = Some(height);
+        is_new
+    }
+
+    /// Check the balance of an account at the given height.
+    ///
+    /// Returns the threshold if the balance is below it and no warning was issued for this account
+    /// in the last `BALANCE_ALERT_INTERVAL_BLOCKS` blocks.
+    pub fn check(&mut self, account: U31, height: BlockHeight, balance: Amount) -> Option<Amount> {
+        let alert = self.alerts.get_mut(&account)?;
+
+        if balance >= alert.threshold {
+            return None;
+        }
+
+        let rate_limited = alert.last_warning_height.is_some_and(|last_height| {
+            last_height
+                .checked_add(BALANCE_ALERT_INTERVAL_BLOCKS)
+                .map_or(true, |next_warning_height| height < next_warning_height)
+        });
+        if rate_limited {
+            return None;
+        }
+
+        alert.last_warning_height = Some(height);
+        Some(alert.threshold)
+    }
+
+    fn to_config_json(&self) -> String {
+        let thresholds = self
+            .alerts
+            .iter()
+            .map(|(account, alert)| {
+                (
+                    account.into_u32().to_string(),
+                    serde_json::Value::String(alert.threshold.into_atoms().to_string()),
+                )
+            })
+            .collect::<serde_json::Map<_, _>>();
+
+        serde_json::json!({ BALANCE_ALERTS_KEY: thresholds }).to_string()
+    }
+}
+
+fn config_file_path(wallet_path: &Path) -> PathBuf {
+    let mut path = OsString::from(wallet_path.as_os_str());
+    path.push(CONFIG_FILE_SUFFIX);
+    path.into()
+}
+
+fn parse_thresholds(contents: &str) -> Option<BTreeMap<U31, Amount>> {
+    let config: serde_json::Value = serde_json::from_str(contents).ok()?;
+
+    config
+        .get(BALANCE_ALERTS_KEY)?
+        .as_object()?
+        .iter()
+        .map(|(account, threshold)| {
+            let account = U31::from_u32(account.parse().ok()?)?;
+            let threshold = Amount::from_atoms(threshold.as_str()?.parse().ok()?);
+            Some((account, threshold))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use randomness::Rng;
+    use test_utils::random::{make_seedable_rng, Seed};
+
+    use super::*;
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn one_warning_per_interval(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let account = U31::from_u32(rng.gen_range(0..10)).unwrap();
+        let threshold = Amount::from_atoms(rng.gen_range(1000..100_000));
+        let below = Amount::from_atoms(rng.gen_range(0..threshold.into_atoms()));
+        let above = Amount::from_atoms(rng.gen_range(threshold.into_atoms()..1_000_000));
+
+        let mut alerts = BalanceAlerts::default();
+        alerts.set_threshold(account, threshold).unwrap();
+
+        // no warnings while the balance is above the threshold
+        for height in 0..BALANCE_ALERT_INTERVAL_BLOCKS {
+            assert_eq!(alerts.check(account, BlockHeight::new(height), above), None);
+        }
+
+        // exactly one warning in each window while the balance stays below the threshold
+        let start = BALANCE_ALERT_INTERVAL_BLOCKS;
+        let num_windows = rng.gen_range(1..5);
+        for window in 0..num_windows {
+            let window_start = start + window * BALANCE_ALERT_INTERVAL_BLOCKS;
+            let warnings = (window_start..window_start + BALANCE_ALERT_INTERVAL_BLOCKS)
+                .filter_map(|height| alerts.check(account, BlockHeight::new(height), below))
+                .collect::<Vec<_>>();
+            assert_eq!(warnings, vec![threshold]);
+        }
+
+        // going above the threshold and back below doesn't bypass the rate limit
+        let last_warning = start + (num_windows - 1) * BALANCE_ALERT_INTERVAL_BLOCKS;
+        let height = last_warning + BALANCE_ALERT_INTERVAL_BLOCKS - 1;
+        assert_eq!(alerts.check(account, BlockHeight::new(height), above), None);
+        assert_eq!(alerts.check(account, BlockHeight::new(height), below), None);
+        assert_eq!(
+            alerts.check(account, BlockHeight::new(height + 1), below),
+            Some(threshold)
+        );
+
+        // other accounts have no alerts
+        let other_account = U31::from_u32(account.into_u32() + 1).unwrap();
+        assert_eq!(
+            alerts.check(other_account, BlockHeight::new(height + 1), below),
+            None
+        );
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn config_roundtrip(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let thresholds = (0..rng.gen_range(1..10))
+            .map(|_| {
+                (
+                    U31::from_u32(rng.gen_range(0..100)).unwrap(),
+                    Amount::from_atoms(rng.gen()),
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let mut alerts = BalanceAlerts::default();
+        for (account, threshold) in &thresholds {
+            alerts.set_threshold(*account, *threshold).unwrap();
+        }
+
+        let parsed = parse_thresholds(&alerts.to_config_json()).unwrap();
+        assert_eq!(parsed, thresholds);
+
+        assert_eq!(parse_thresholds("{}"), None);
+        assert_eq!(parse_thresholds("not json"), None);
+    }
+}
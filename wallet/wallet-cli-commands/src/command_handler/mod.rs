@@ -13,18 +13,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod balance_alert;
 mod local_state;
 
-use std::{fmt::Write, str::FromStr};
+use std::{fmt::Write, num::NonZeroU8, str::FromStr};
 
 use common::{
     address::Address,
     chain::{
+        classic_multisig::ClassicMultisigChallenge,
         config::checkpoints_data::print_block_heights_ids_as_checkpoints_data,
-        partially_signed_transaction::PartiallySignedTransaction, ChainConfig, Destination,
+        partially_signed_transaction::PartiallySignedTransaction, Block, ChainConfig, Destination,
         SignedTransaction, TxOutput, UtxoOutPoint,
     },
-    primitives::H256,
+    primitives::{Amount, DecimalAmount, H256},
     text_summary::TextSummary,
 };
 use crypto::key::hdkd::u31::U31;
@@ -34,25 +36,29 @@ use node_comm::node_traits::NodeInterface;
 use serialization::{hex::HexEncode, hex_encoded::HexEncoded};
 use utils::qrcode::{QrCode, QrCodeError};
 use wallet::version::get_version;
-use wallet_controller::types::GenericTokenTransfer;
-use wallet_rpc_client::wallet_rpc_traits::{PartialOrSignedTx, WalletInterface};
+use wallet_controller::{types::GenericTokenTransfer, PeerLimits};
+use wallet_rpc_client::wallet_rpc_traits::{
+    PartialOrSignedTx, SignRawTransactionResult, WalletInterface,
+};
 use wallet_rpc_lib::types::{
     Balances, ComposedTransaction, ControllerConfig, MnemonicInfo, NewTransaction, NftMetadata,
-    RpcInspectTransaction, RpcSignatureStats, RpcSignatureStatus, RpcStandaloneAddressDetails,
-    RpcValidatedSignatures, TokenMetadata,
+    RpcInspectTransaction, RpcOutPointSourceId, RpcSignatureStats, RpcSignatureStatus,
+    RpcStandaloneAddressDetails, RpcValidatedSignatures, TokenMetadata,
 };
+use wallet_types::{utxo_types::UtxoState, with_locked::WithLocked, AddressOrigin};
 
 use crate::{
     errors::WalletCliCommandError, helper_types::parse_generic_token_transfer,
     ManageableWalletCommand, WalletManagementCommand,
 };
 
-use self::local_state::WalletWithState;
+use self::{balance_alert::BalanceAlerts, local_state::WalletWithState};
 
 use super::{
     helper_types::{
-        format_delegation_info, format_pool_info, parse_coin_output, parse_token_supply,
-        parse_utxo_outpoint, CliForceReduce, CliUtxoState,
+        format_delegation_info, format_pool_info, format_staking_pool_report, format_tx_cursor,
+        parse_coin_output, parse_token_supply, parse_tx_cursor, parse_utxo_outpoint,
+        peer_latency_table, utxo_summary, CliForceReduce, CliUtxoState, CliUtxoTypes,
     },
     ColdWalletCommand, ConsoleCommand, WalletCommand,
 };
@@ -61,6 +67,8 @@ pub struct CommandHandler<W> {
     config: ControllerConfig,
 
     wallet: WalletWithState<W>,
+
+    balance_alerts: BalanceAlerts,
 }
 
 impl<W, E> CommandHandler<W>
@@ -71,7 +79,47 @@ where
         CommandHandler {
             config,
             wallet: WalletWithState::new(wallet).await,
+            balance_alerts: BalanceAlerts::default(),
+        }
+    }
+
+    /// Print a warning for every account whose balance has dropped below its alert threshold.
+    /// Does nothing unless a new block has arrived since the last check.
+    pub async fn check_balance_alerts<N: NodeInterface>(
+        &mut self,
+        chain_config: &ChainConfig,
+    ) -> Result<(), WalletCliCommandError<N>>
+    where
+        WalletCliCommandError<N>: From<E>,
+    {
+        if self.balance_alerts.is_empty() {
+            return Ok(());
+        }
+
+        let wallet = self.wallet.get_wallet().await?;
+        let height = wallet.best_block().await?.height;
+        if !self.balance_alerts.is_new_block(height) {
+            return Ok(());
+        }
+
+        for account in self.balance_alerts.accounts().collect::<Vec<_>>() {
+            let balance = wallet
+                .get_balance(account, vec![UtxoState::Confirmed], WithLocked::Unlocked)
+                .await?
+                .coins()
+                .amount();
+
+            if let Some(threshold) = self.balance_alerts.check(account, height, balance) {
+                let decimals = chain_config.coin_decimals();
+                let balance = DecimalAmount::from_amount_no_padding(balance, decimals);
+                let threshold = DecimalAmount::from_amount_no_padding(threshold, decimals);
+                logging::log::warn!(
+                    "Low balance in account {account} at block height {height}: {balance} is below the alert threshold of {threshold}"
+                );
+            }
         }
+
+        Ok(())
     }
 
     pub async fn rpc_completed(&self) {
@@ -150,7 +198,7 @@ where
                     .wallet()
                     .await?
                     .create_wallet(
-                        wallet_path,
+                        wallet_path.clone(),
                         whether_to_store_seed_phrase.to_bool(),
                         mnemonic,
                         passphrase,
@@ -158,6 +206,7 @@ where
                     .await?;
 
                 self.wallet.update_wallet::<N>().await;
+                self.balance_alerts = BalanceAlerts::load(&wallet_path);
 
                 let msg = match newly_generated_mnemonic.mnemonic {
                     MnemonicInfo::NewlyGenerated {
@@ -196,12 +245,13 @@ where
                 self.wallet()
                     .await?
                     .open_wallet(
-                        wallet_path,
+                        wallet_path.clone(),
                         encryption_password,
                         Some(force_change_wallet_type),
                     )
                     .await?;
                 self.wallet.update_wallet::<N>().await;
+                self.balance_alerts = BalanceAlerts::load(&wallet_path);
 
                 Ok(ConsoleCommand::SetStatus {
                     status: self.repl_status().await?,
@@ -212,6 +262,7 @@ where
             WalletManagementCommand::CloseWallet => {
                 self.wallet().await?.close_wallet().await?;
                 self.wallet.update_wallet::<N>().await;
+                self.balance_alerts = BalanceAlerts::default();
 
                 Ok(ConsoleCommand::SetStatus {
                     status: self.repl_status().await?,
@@ -238,6 +289,12 @@ where
         WalletCliCommandError<N>: From<E>,
     {
         match command {
+            ColdWalletCommand::AccountFingerprint => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let fingerprint = wallet.account_fingerprint(selected_account).await?;
+                Ok(ConsoleCommand::Print(id_to_hex_string(fingerprint)))
+            }
+
             ColdWalletCommand::WalletInfo => {
                 let info = self.non_empty_wallet().await?.wallet_info().await?;
                 let names = info
@@ -253,6 +310,15 @@ where
                 Ok(ConsoleCommand::Print(format!("Wallet Accounts:\n{names}")))
             }
 
+            ColdWalletCommand::CheckIntegrity => {
+                let violations = self.non_empty_wallet().await?.check_integrity().await?;
+                if violations.is_empty() {
+                    Ok(ConsoleCommand::Print("OK".to_owned()))
+                } else {
+                    Ok(ConsoleCommand::Print(violations.join("\n")))
+                }
+            }
+
             ColdWalletCommand::EncryptPrivateKeys { password } => {
                 self.non_empty_wallet().await?.encrypt_private_keys(password).await?;
 
@@ -345,6 +411,19 @@ where
                 ))
             }
 
+            ColdWalletCommand::LookaheadStatus => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let status = wallet.lookahead_status(selected_account).await?;
+
+                let last_used = status
+                    .last_used
+                    .map_or_else(|| "None".to_owned(), |last_used| last_used.to_string());
+                Ok(ConsoleCommand::Print(format!(
+                    "Lookahead size: {}\nLast used address index: {last_used}\nAddresses that can still be issued: {}",
+                    status.configured, status.gap_remaining
+                )))
+            }
+
             ColdWalletCommand::AddressQRCode { address } => {
                 let addr: Address<Destination> = Address::from_string(chain_config, address)
                     .map_err(|_| {
@@ -361,6 +440,12 @@ where
                 Ok(ConsoleCommand::Print(address.address))
             }
 
+            ColdWalletCommand::CurrentChangeAddress => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let address = wallet.get_current_change_address(selected_account).await?;
+                Ok(ConsoleCommand::Print(address))
+            }
+
             ColdWalletCommand::RevealPublicKey { public_key_hash } => {
                 let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
                 let public_key =
@@ -439,6 +524,54 @@ where
                 Ok(ConsoleCommand::Print(addresses_table.to_string()))
             }
 
+            ColdWalletCommand::ListStandaloneAddresses => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let addresses = wallet.get_standalone_addresses(selected_account).await?;
+
+                let watch_only = addresses
+                    .watch_only_addresses
+                    .into_iter()
+                    .map(|info| (info.address, "Watch Only", info.label));
+                let multisig = addresses
+                    .multisig_addresses
+                    .into_iter()
+                    .map(|info| (info.address, "Multisig", info.label));
+                let private_key = addresses.private_key_addresses.into_iter().flat_map(|info| {
+                    [
+                        (info.public_key_hash, "From Private key", info.label.clone()),
+                        (info.public_key, "From Private key", info.label),
+                    ]
+                });
+
+                let mut addresses_table = prettytable::Table::new();
+                addresses_table
+                    .set_titles(prettytable::row!["Address", "Type", "Label", "Balances"]);
+
+                for (address, address_type, label) in watch_only.chain(multisig).chain(private_key)
+                {
+                    let details = wallet
+                        .get_standalone_address_details(selected_account, address.to_string())
+                        .await?;
+
+                    let (coins, tokens) = details.balances.into_coins_and_tokens();
+                    let balances =
+                        std::iter::once(format!("Coins: {}", coins.decimal()))
+                            .chain(tokens.into_iter().map(|(token_id, amount)| {
+                                format!("{token_id}: {}", amount.decimal())
+                            }))
+                            .join("\n");
+
+                    addresses_table.add_row(prettytable::row![
+                        address,
+                        address_type,
+                        label.unwrap_or_default(),
+                        balances
+                    ]);
+                }
+
+                Ok(ConsoleCommand::Print(addresses_table.to_string()))
+            }
+
             ColdWalletCommand::ShowStandaloneAddressDetails { address } => {
                 let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
                 let addr_details =
@@ -491,6 +624,56 @@ where
                 Ok(ConsoleCommand::Print(output))
             }
 
+            ColdWalletCommand::AddressLookup { address } => {
+                let owner = self
+                    .non_empty_wallet()
+                    .await?
+                    .find_account_for_address(address.clone())
+                    .await?
+                    .ok_or(WalletCliCommandError::AddressNotFound(address))?;
+
+                let origin = match owner.origin {
+                    AddressOrigin::HdDerived => "derived from the account's key chain",
+                    AddressOrigin::Standalone => "standalone",
+                };
+                Ok(ConsoleCommand::Print(format!(
+                    "The address belongs to account {} ({origin})",
+                    owner.account
+                )))
+            }
+
+            ColdWalletCommand::ShowAddressDerivationPath { address } => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let path = wallet.get_address_derivation_path(selected_account, address).await?;
+
+                let output = match path {
+                    Some(path) => path,
+                    None => "The address is not derived from the wallet's key chain".to_owned(),
+                };
+                Ok(ConsoleCommand::Print(output))
+            }
+
+            ColdWalletCommand::SimulateScan { block_file } => {
+                let blocks = read_block_file(&block_file)?;
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let source_ids = wallet.simulate_scan(selected_account, blocks).await?;
+
+                let output = if source_ids.is_empty() {
+                    "No transactions would be recognised".to_owned()
+                } else {
+                    source_ids
+                        .iter()
+                        .map(|source_id| match source_id {
+                            RpcOutPointSourceId::Transaction { tx_id } => tx_id.to_string(),
+                            RpcOutPointSourceId::BlockReward { block_id } => {
+                                format!("Block reward {block_id}")
+                            }
+                        })
+                        .join("\n")
+                };
+                Ok(ConsoleCommand::Print(output))
+            }
+
             ColdWalletCommand::NewVrfPublicKey => {
                 let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
                 let vrf_public_key = wallet.new_vrf_public_key(selected_account).await?;
@@ -500,6 +683,18 @@ where
                 )))
             }
 
+            ColdWalletCommand::GenerateVrfKeys {
+                count,
+                account_index,
+            } => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let account_index = account_index.unwrap_or(selected_account);
+                let vrf_public_keys = wallet.new_vrf_public_keys(account_index, count).await?;
+                Ok(ConsoleCommand::Print(
+                    serde_json::to_string(&vrf_public_keys).expect("ok"),
+                ))
+            }
+
             ColdWalletCommand::GetVrfPublicKey => {
                 let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
                 let addresses_with_usage = wallet.get_vrf_public_key(selected_account).await?;
@@ -532,49 +727,59 @@ where
                 let result =
                     wallet.sign_raw_transaction(selected_account, transaction, self.config).await?;
 
-                let output_str = match result.transaction {
-                    PartialOrSignedTx::Signed(signed_tx) => {
-                        let summary = signed_tx.transaction().text_summary(chain_config);
-                        let result_hex: HexEncoded<SignedTransaction> = signed_tx.into();
+                Ok(ConsoleCommand::Print(format_sign_raw_transaction_result(
+                    result,
+                    chain_config,
+                )))
+            }
 
-                        let qr_code_string = qrcode_or_error_string(&result_hex.to_string());
+            ColdWalletCommand::SignTxFile {
+                input_path,
+                output_path,
+            } => {
+                let transaction = read_transaction_file(&input_path)?;
+                // The result is written to a temporary file that replaces the output file once
+                // it's complete, so that an existing output file is never truncated on failure.
+                // Creating it first makes sure the result can be stored before doing any work.
+                let tmp_path = tmp_file_path(&output_path);
+                std::fs::File::create(&tmp_path)
+                    .map_err(|err| WalletCliCommandError::TransactionFile(tmp_path.clone(), err))?;
 
-                        format!(
-                            "The transaction has been fully signed and is ready to be broadcast to network. \
-                             You can use the command `node-submit-transaction` in a wallet connected to the internet (this one or elsewhere). \
-                             Pass the following data to the wallet to broadcast:\n\n{result_hex}\n\n\
-                             Or scan the Qr code with it:\n\n{qr_code_string}\n\n{summary}")
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let result = match wallet
+                    .sign_raw_transaction(selected_account, transaction, self.config)
+                    .await
+                {
+                    Ok(result) => result,
+                    Err(err) => {
+                        let _ = std::fs::remove_file(&tmp_path);
+                        return Err(err.into());
                     }
-                    PartialOrSignedTx::Partial(partially_signed_tx) => {
-                        let result_hex: HexEncoded<PartiallySignedTransaction> =
-                            partially_signed_tx.into();
-
-                        let qr_code_string = qrcode_or_error_string(&result_hex.to_string());
-
-                        let prev_sigs = result
-                            .previous_signatures
-                            .iter()
-                            .enumerate()
-                            .map(format_signature_status)
-                            .join(", ");
-                        let current_sigs = result
-                            .current_signatures
-                            .iter()
-                            .enumerate()
-                            .map(format_signature_status)
-                            .join(", ");
+                };
 
+                let (result_hex, status) = match result.transaction {
+                    PartialOrSignedTx::Signed(signed_tx) => (
+                        HexEncoded::new(signed_tx).to_string(),
                         format!(
-                            "Not all transaction inputs have been signed. This wallet does not have all the keys for that.\n\
-                             The signatures states before signing were:\n{prev_sigs}\n\
-                             and the current signature states are:\n{current_sigs}\n\
-                             Pass the following string into the wallet that has appropriate keys for the inputs to sign what is left:\n\n{result_hex}\n\n\
-                             Or scan the Qr code with it:\n\n{qr_code_string}"
-                        )
-                    }
+                            "{FULLY_SIGNED_STATUS} You can use the command `transaction-submit-file` to broadcast it."
+                        ),
+                    ),
+                    PartialOrSignedTx::Partial(partially_signed_tx) => (
+                        HexEncoded::new(partially_signed_tx).to_string(),
+                        PARTIALLY_SIGNED_STATUS.to_owned(),
+                    ),
                 };
+                std::fs::write(&tmp_path, result_hex)
+                    .and_then(|()| std::fs::rename(&tmp_path, &output_path))
+                    .map_err(|err| {
+                        let _ = std::fs::remove_file(&tmp_path);
+                        WalletCliCommandError::TransactionFile(output_path.clone(), err)
+                    })?;
 
-                Ok(ConsoleCommand::Print(output_str))
+                Ok(ConsoleCommand::Print(format!(
+                    "{status}\nThe result was written to {}",
+                    output_path.display()
+                )))
             }
 
             ColdWalletCommand::SignChallegeHex {
@@ -761,13 +966,74 @@ where
                 })
             }
 
-            WalletCommand::RenameAccount { name } => {
-                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
-                wallet.rename_account(selected_account, name).await?;
+            WalletCommand::RecoverAccounts { account_indices } => {
+                let new_accounts =
+                    self.non_empty_wallet().await?.recover_accounts(account_indices).await?;
+
+                let print_message = if new_accounts.is_empty() {
+                    "All the specified accounts already exist".to_owned()
+                } else {
+                    let indices = new_accounts
+                        .iter()
+                        .map(|acc| acc.account.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!(
+                        "Success, the created account indexes are: {indices}. \
+                         They will be rescanned from the genesis block"
+                    )
+                };
+
+                Ok(ConsoleCommand::SetStatus {
+                    status: self.repl_status().await?,
+                    print_message,
+                })
+            }
+
+            WalletCommand::ShowNextMultisigAccountPublicKey => {
+                let public_key =
+                    self.non_empty_wallet().await?.next_multisig_account_public_key().await?;
+                Ok(ConsoleCommand::Print(
+                    public_key.public_key_address.to_string(),
+                ))
+            }
+
+            WalletCommand::CreateMultisigAccount {
+                min_required_signatures,
+                cosigner_public_keys,
+                name,
+            } => {
+                let new_acc = self
+                    .non_empty_wallet()
+                    .await?
+                    .create_multisig_account(min_required_signatures, cosigner_public_keys, name)
+                    .await?;
 
                 Ok(ConsoleCommand::SetStatus {
                     status: self.repl_status().await?,
-                    print_message: "Success, the account name has been successfully renamed".into(),
+                    print_message: format!(
+                        "Success, the new account index is: {}\nThe multisig address of the account is: {}",
+                        new_acc.account, new_acc.multisig_address,
+                    ),
+                })
+            }
+
+            WalletCommand::RenameAccount {
+                account_index,
+                name,
+            } => {
+                self.non_empty_wallet().await?.rename_account(account_index, name).await?;
+
+                let status = self.repl_status().await?;
+                let mut print_message =
+                    "Success, the account name has been successfully renamed".to_owned();
+                if !status.is_empty() {
+                    print_message.push_str(&format!("\nStatus: {status}"));
+                }
+
+                Ok(ConsoleCommand::SetStatus {
+                    status,
+                    print_message,
                 })
             }
 
@@ -886,6 +1152,29 @@ where
                 })
             }
 
+            WalletCommand::SetBalanceAlert {
+                account_index,
+                threshold,
+            } => {
+                let state = self.wallet.get_mut_state().await?;
+                if account_index.into_u32() as usize >= state.num_accounts() {
+                    return Err(WalletCliCommandError::AccountNotFound(account_index));
+                }
+
+                let threshold_amount = threshold.to_amount(chain_config.coin_decimals()).ok_or(
+                    WalletCliCommandError::InvalidInput(format!(
+                        "Invalid coin amount: {threshold}"
+                    )),
+                )?;
+                self.balance_alerts
+                    .set_threshold(account_index, threshold_amount)
+                    .map_err(WalletCliCommandError::WalletConfigFile)?;
+
+                Ok(ConsoleCommand::Print(format!(
+                    "A warning will be printed when the balance of account {account_index} drops below {threshold}"
+                )))
+            }
+
             WalletCommand::StartStaking => {
                 let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
                 wallet.start_staking(selected_account).await?;
@@ -919,6 +1208,25 @@ where
                 }
             }
 
+            WalletCommand::StakingPoolInfo { pool_id } => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let pool_info = wallet
+                    .list_staking_pools(selected_account)
+                    .await?
+                    .into_iter()
+                    .find(|pool_info| pool_info.pool_id.as_str() == pool_id)
+                    .ok_or_else(|| WalletCliCommandError::StakingPoolNotOwned(pool_id.clone()))?;
+                let delegations_count = wallet.stake_pool_delegations_count(pool_id).await?;
+                let created_blocks = wallet.list_created_blocks_ids(selected_account).await?;
+
+                Ok(ConsoleCommand::Print(format_staking_pool_report(
+                    &pool_info,
+                    delegations_count,
+                    &created_blocks,
+                    chain_config,
+                )))
+            }
+
             WalletCommand::SubmitBlock { block } => {
                 self.wallet().await?.submit_block(block).await?;
                 Ok(ConsoleCommand::Print(
@@ -978,6 +1286,52 @@ where
                 ))
             }
 
+            WalletCommand::RebroadcastAllTransactions => {
+                let results = self.non_empty_wallet().await?.rebroadcast_all_transactions().await?;
+                if results.is_empty() {
+                    return Ok(ConsoleCommand::Print(
+                        "There are no pending transactions to rebroadcast".to_owned(),
+                    ));
+                }
+
+                let lines = results
+                    .into_iter()
+                    .map(|result| match result.error {
+                        None => format!("{}: OK", result.tx_id),
+                        Some(error) => format!("{}: failed: {error}", result.tx_id),
+                    })
+                    .collect::<Vec<_>>();
+                Ok(ConsoleCommand::Print(lines.join("\n")))
+            }
+
+            WalletCommand::ChildPaysForParent {
+                parent_tx_id,
+                target_fee_rate,
+            } => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let new_tx = wallet
+                    .create_cpfp_transaction(
+                        selected_account,
+                        parent_tx_id.take(),
+                        target_fee_rate,
+                        self.config,
+                    )
+                    .await?;
+
+                Ok(Self::new_tx_submitted_command(new_tx))
+            }
+
+            WalletCommand::ImportTransaction {
+                raw_tx,
+                block_height,
+            } => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                wallet.import_transaction(selected_account, raw_tx.take(), block_height).await?;
+                Ok(ConsoleCommand::Print(
+                    "The transaction was imported successfully".to_owned(),
+                ))
+            }
+
             WalletCommand::IssueNewToken {
                 token_ticker,
                 number_of_decimals,
@@ -1011,6 +1365,54 @@ where
                 )))
             }
 
+            WalletCommand::IssueTokenWithMultisigAuthority {
+                token_ticker,
+                number_of_decimals,
+                metadata_uri,
+                token_supply,
+                is_freezable,
+                required_sigs,
+                authority_keys,
+            } => {
+                let token_supply = parse_token_supply(&token_supply, number_of_decimals)?;
+                let required_sigs = NonZeroU8::new(required_sigs).ok_or_else(|| {
+                    WalletCliCommandError::InvalidInput(
+                        "The number of required signatures cannot be zero".to_string(),
+                    )
+                })?;
+                let public_keys = authority_keys.into_iter().map(HexEncoded::take).collect();
+                let challenge =
+                    ClassicMultisigChallenge::new(chain_config, required_sigs, public_keys)
+                        .map_err(|err| WalletCliCommandError::InvalidInput(err.to_string()))?;
+                let authority = Address::new(
+                    chain_config,
+                    Destination::ClassicMultisig((&challenge).into()),
+                )
+                .expect("addressable");
+
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let new_token = wallet
+                    .issue_new_token(
+                        selected_account,
+                        authority.to_string(),
+                        TokenMetadata {
+                            token_ticker: token_ticker.into(),
+                            number_of_decimals,
+                            metadata_uri: metadata_uri.into(),
+                            token_supply,
+                            is_freezable: is_freezable.to_bool(),
+                        },
+                        self.config,
+                    )
+                    .await?;
+
+                Ok(ConsoleCommand::Print(format!(
+                    "A new token has been issued with ID: {} in tx: {}\nThe token authority is the multisig address: {authority}",
+                    new_token.token_id,
+                    id_to_hex_string(*new_token.tx_id.as_hash())
+                )))
+            }
+
             WalletCommand::IssueNewNft {
                 destination_address,
                 media_hash,
@@ -1163,6 +1565,27 @@ where
                 Ok(ConsoleCommand::Print(output))
             }
 
+            WalletCommand::ListOwnedTokens => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let owned_tokens = wallet.list_owned_tokens(selected_account).await?;
+
+                let output = owned_tokens
+                    .into_iter()
+                    .map(|token| {
+                        format!(
+                            "Token: {} ({}) amount: {}, decimals: {}, frozen: {}",
+                            token.token_id,
+                            token.ticker,
+                            token.amount.decimal(),
+                            token.number_of_decimals,
+                            token.is_frozen,
+                        )
+                    })
+                    .join("\n");
+
+                Ok(ConsoleCommand::Print(output))
+            }
+
             WalletCommand::ListUtxo {
                 utxo_type,
                 utxo_states,
@@ -1183,12 +1606,81 @@ where
                 ))
             }
 
+            WalletCommand::ListLockedUtxo { utxo_states } => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let utxos = wallet
+                    .get_locked_utxos(
+                        selected_account,
+                        CliUtxoState::to_wallet_states(utxo_states),
+                    )
+                    .await
+                    .map(serde_json::Value::Array)?;
+                Ok(ConsoleCommand::Print(
+                    serde_json::to_string(&utxos).expect("ok"),
+                ))
+            }
+
+            WalletCommand::UtxoSummary { account_index } => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let account_index = account_index.unwrap_or(selected_account);
+
+                let get_utxos = move |with_locked| {
+                    wallet.get_utxos(
+                        account_index,
+                        CliUtxoTypes::All.to_wallet_types(),
+                        vec![UtxoState::Confirmed],
+                        with_locked,
+                    )
+                };
+                let unlocked = get_utxos(WithLocked::Unlocked).await?;
+                let locked = get_utxos(WithLocked::Locked).await?;
+
+                let summary = utxo_summary(&unlocked, &locked, chain_config);
+                Ok(ConsoleCommand::Print(
+                    serde_json::to_string(&summary).expect("ok"),
+                ))
+            }
+
             WalletCommand::ListPendingTransactions => {
                 let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
                 let utxos = wallet.list_pending_transactions(selected_account).await?;
                 Ok(ConsoleCommand::Print(format!("{utxos:#?}")))
             }
 
+            WalletCommand::ListTransactions { cursor, count } => {
+                let cursor = cursor.map(|cursor| parse_tx_cursor(&cursor)).transpose()?;
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let page = wallet.list_transactions(selected_account, cursor, count).await?;
+
+                let table = {
+                    let mut table = prettytable::Table::new();
+                    table.set_titles(prettytable::row![
+                        "Id",
+                        "State",
+                        "BlockHeight",
+                        "BlockTimestamp"
+                    ]);
+
+                    table.extend(page.transactions.into_iter().map(|tx| {
+                        prettytable::row![
+                            id_to_hex_string(*tx.tx_id.as_hash()),
+                            tx.state,
+                            tx.block_height.map_or(String::new(), |height| height.to_string()),
+                            tx.timestamp.map_or(String::new(), |timestamp| timestamp.to_string())
+                        ]
+                    }));
+
+                    table
+                };
+
+                let next_page = match page.next_cursor {
+                    Some(cursor) => format!("Next cursor: {}", format_tx_cursor(&cursor)),
+                    None => "No more transactions".to_owned(),
+                };
+
+                Ok(ConsoleCommand::Print(format!("{table}{next_page}")))
+            }
+
             WalletCommand::ListMainchainTransactions { address, limit } => {
                 let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
                 let txs =
@@ -1212,6 +1704,29 @@ where
                 Ok(ConsoleCommand::Print(table.to_string()))
             }
 
+            WalletCommand::AccountActivitySummary { days } => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let activity = wallet.get_account_activity_summary(selected_account, days).await?;
+
+                let table = {
+                    let mut table = prettytable::Table::new();
+                    table.set_titles(prettytable::row!["Date", "Transactions", "Net coin flow"]);
+
+                    table.extend(activity.into_iter().map(|day| {
+                        let sign = if day.net_coin_flow < 0 { "-" } else { "" };
+                        let amount = DecimalAmount::from_amount_no_padding(
+                            Amount::from_atoms(day.net_coin_flow.unsigned_abs().into()),
+                            chain_config.coin_decimals(),
+                        );
+                        prettytable::row![day.date, day.tx_count, format!("{sign}{amount}")]
+                    }));
+
+                    table
+                };
+
+                Ok(ConsoleCommand::Print(table.to_string()))
+            }
+
             WalletCommand::GetTransaction { transaction_id } => {
                 let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
                 let tx = wallet
@@ -1400,6 +1915,27 @@ where
                 Ok(Self::new_tx_submitted_command(new_tx))
             }
 
+            WalletCommand::SendAllTokensToAddress { token_id, address } => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let new_tx = wallet
+                    .send_all_tokens(selected_account, token_id, address, self.config)
+                    .await?;
+
+                Ok(Self::new_tx_submitted_command(new_tx))
+            }
+
+            WalletCommand::ListTokenAuthorities => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let tokens = wallet.list_token_authorities(selected_account).await?;
+
+                let output = tokens
+                    .into_iter()
+                    .map(|info| format!("Token: {}, authority: {}", info.token_id, info.authority))
+                    .join("\n");
+
+                Ok(ConsoleCommand::Print(output))
+            }
+
             WalletCommand::MakeTxToSendTokensFromMultisigAddress {
                 from_address,
                 fee_change_address,
@@ -1439,7 +1975,7 @@ where
                     let result_hex: HexEncoded<SignedTransaction> = signed_tx.into();
 
                     format!(
-                        "The transaction has been fully signed and is ready to be broadcast to network. \
+                        "{FULLY_SIGNED_STATUS} \
                         You can use the command `node-submit-transaction` in a wallet connected to the internet (this one or elsewhere). \
                         Pass the following data to the wallet to broadcast:\n\n{result_hex}\n\n{summary}"
                     )
@@ -1452,7 +1988,7 @@ where
                         .join(", ");
 
                     format!(
-                        "Not all transaction inputs have been signed. This wallet does not have all the keys for that.\n\
+                        "{PARTIALLY_SIGNED_STATUS}\n\
                         The current signature states are:\n{current_sigs}.\n\
                         Pass the following string into the wallet that has appropriate keys for the inputs to sign what is left:\n\n\
                         {transaction}\n\n{summary}",
@@ -1518,6 +2054,7 @@ where
                 cost_per_block,
                 margin_ratio_per_thousand,
                 decommission_address,
+                vrf_public_key,
             } => {
                 let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
                 let new_tx = wallet
@@ -1527,6 +2064,7 @@ where
                         cost_per_block,
                         margin_ratio_per_thousand,
                         decommission_address,
+                        vrf_public_key,
                         self.config,
                     )
                     .await?;
@@ -1574,6 +2112,70 @@ where
                 Ok(ConsoleCommand::Print(output_str))
             }
 
+            WalletCommand::SignRequest { request, broadcast } => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let result = wallet
+                    .sign_raw_transaction(selected_account, request.to_string(), self.config)
+                    .await?;
+
+                match &result.transaction {
+                    PartialOrSignedTx::Signed(signed_tx) if broadcast => {
+                        let summary = signed_tx.transaction().text_summary(chain_config);
+                        let new_tx = self
+                            .non_empty_wallet()
+                            .await?
+                            .submit_raw_transaction(
+                                signed_tx.clone().into(),
+                                false,
+                                TxOptionsOverrides::default(),
+                            )
+                            .await?;
+
+                        Ok(ConsoleCommand::Print(format!(
+                            "The transaction has been fully signed and was submitted successfully with ID:\n{}\n\n{summary}",
+                            id_to_hex_string(*new_tx.tx_id.as_hash())
+                        )))
+                    }
+                    // Nothing to do on top of what the plain signing command does
+                    PartialOrSignedTx::Signed(_) | PartialOrSignedTx::Partial(_) => {
+                        Ok(ConsoleCommand::Print(format_sign_raw_transaction_result(
+                            result,
+                            chain_config,
+                        )))
+                    }
+                }
+            }
+
+            WalletCommand::ExportUnsignedTx { tx, output_path } => {
+                std::fs::write(&output_path, tx.to_string()).map_err(|err| {
+                    WalletCliCommandError::TransactionFile(output_path.clone(), err)
+                })?;
+
+                Ok(ConsoleCommand::Print(format!(
+                    "The transaction was written to {}",
+                    output_path.display()
+                )))
+            }
+
+            WalletCommand::SubmitSignedTxFile { input_path } => {
+                let transaction = read_transaction_file(&input_path)?;
+                let signed_tx = match HexEncoded::<SignedTransaction>::from_str(&transaction) {
+                    Ok(signed_tx) => signed_tx.take(),
+                    Err(_) => HexEncoded::<PartiallySignedTransaction>::from_str(&transaction)
+                        .map_err(|err| WalletCliCommandError::InvalidInput(err.to_string()))?
+                        .take()
+                        .into_signed_tx()
+                        .map_err(|err| WalletCliCommandError::InvalidInput(err.to_string()))?,
+                };
+
+                let new_tx = self
+                    .non_empty_wallet()
+                    .await?
+                    .submit_raw_transaction(signed_tx.into(), false, TxOptionsOverrides::default())
+                    .await?;
+                Ok(Self::new_tx_submitted_command(new_tx))
+            }
+
             WalletCommand::DepositData { hex_data } => {
                 let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
                 let new_tx = wallet.deposit_data(selected_account, hex_data, self.config).await?;
@@ -1703,6 +2305,10 @@ where
                 let peers_json = serde_json::to_string(&peers)?;
                 Ok(ConsoleCommand::Print(peers_json))
             }
+            WalletCommand::PeerLatency => {
+                let peers = self.wallet().await?.connected_peers().await?;
+                Ok(ConsoleCommand::Print(peer_latency_table(peers).to_string()))
+            }
             WalletCommand::ReservedPeers => {
                 let peers = self.wallet().await?.reserved_peers().await?;
                 Ok(ConsoleCommand::Print(format!("{peers:#?}")))
@@ -1715,6 +2321,21 @@ where
                 self.wallet().await?.remove_reserved_peer(address).await?;
                 Ok(ConsoleCommand::Print("Success".to_owned()))
             }
+            WalletCommand::GetPeerLimits => {
+                let limits = self.wallet().await?.peer_limits().await?;
+                Ok(ConsoleCommand::Print(format!(
+                    "Max inbound: {}, max outbound: {}",
+                    limits.max_inbound, limits.max_outbound
+                )))
+            }
+            WalletCommand::SetPeerLimits { inbound, outbound } => {
+                let limits = PeerLimits {
+                    max_inbound: inbound,
+                    max_outbound: outbound,
+                };
+                self.wallet().await?.set_peer_limits(limits).await?;
+                Ok(ConsoleCommand::Print("Success".to_owned()))
+            }
         }
     }
 
@@ -1737,6 +2358,91 @@ where
     }
 }
 
+/// The path of the temporary file that is written before replacing the file at `path`
+fn tmp_file_path(path: &std::path::Path) -> std::path::PathBuf {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    tmp_path.into()
+}
+
+fn read_transaction_file<N: NodeInterface>(
+    path: &std::path::Path,
+) -> Result<String, WalletCliCommandError<N>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|err| WalletCliCommandError::TransactionFile(path.to_owned(), err))?;
+    Ok(contents.trim().to_owned())
+}
+
+fn read_block_file<N: NodeInterface>(
+    path: &std::path::Path,
+) -> Result<Vec<Block>, WalletCliCommandError<N>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|err| WalletCliCommandError::BlockFile(path.to_owned(), err))?;
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            HexEncoded::<Block>::from_str(line)
+                .map(HexEncoded::take)
+                .map_err(|err| WalletCliCommandError::InvalidInput(err.to_string()))
+        })
+        .collect()
+}
+
+const FULLY_SIGNED_STATUS: &str =
+    "The transaction has been fully signed and is ready to be broadcast to network.";
+const PARTIALLY_SIGNED_STATUS: &str =
+    "Not all transaction inputs have been signed. This wallet does not have all the keys for that.";
+
+/// Describe the result of signing a raw transaction, with the data to pass on to whoever
+/// needs to sign or broadcast it next
+fn format_sign_raw_transaction_result(
+    result: SignRawTransactionResult,
+    chain_config: &ChainConfig,
+) -> String {
+    match result.transaction {
+        PartialOrSignedTx::Signed(signed_tx) => {
+            let summary = signed_tx.transaction().text_summary(chain_config);
+            let result_hex: HexEncoded<SignedTransaction> = signed_tx.into();
+
+            let qr_code_string = qrcode_or_error_string(&result_hex.to_string());
+
+            format!(
+                "{FULLY_SIGNED_STATUS} \
+                 You can use the command `node-submit-transaction` in a wallet connected to the internet (this one or elsewhere). \
+                 Pass the following data to the wallet to broadcast:\n\n{result_hex}\n\n\
+                 Or scan the Qr code with it:\n\n{qr_code_string}\n\n{summary}")
+        }
+        PartialOrSignedTx::Partial(partially_signed_tx) => {
+            let result_hex: HexEncoded<PartiallySignedTransaction> = partially_signed_tx.into();
+
+            let qr_code_string = qrcode_or_error_string(&result_hex.to_string());
+
+            let prev_sigs = result
+                .previous_signatures
+                .iter()
+                .enumerate()
+                .map(format_signature_status)
+                .join(", ");
+            let current_sigs = result
+                .current_signatures
+                .iter()
+                .enumerate()
+                .map(format_signature_status)
+                .join(", ");
+
+            format!(
+                "{PARTIALLY_SIGNED_STATUS}\n\
+                 The signatures states before signing were:\n{prev_sigs}\n\
+                 and the current signature states are:\n{current_sigs}\n\
+                 Pass the following string into the wallet that has appropriate keys for the inputs to sign what is left:\n\n{result_hex}\n\n\
+                 Or scan the Qr code with it:\n\n{qr_code_string}"
+            )
+        }
+    }
+}
+
 fn format_signature_status((idx, status): (usize, &RpcSignatureStatus)) -> String {
     let status = match status {
         RpcSignatureStatus::FullySigned => "FullySigned".to_owned(),
@@ -13,6 +13,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::path::PathBuf;
+
 use crypto::key::hdkd::u31::U31;
 use node_comm::node_traits::NodeInterface;
 use utils::qrcode::QrCodeError;
@@ -50,4 +52,14 @@ pub enum WalletCliCommandError<N: NodeInterface> {
     ExistingWalletWasClosed,
     #[error("Invalid tx output: {0}")]
     InvalidTxOutput(GenericCurrencyTransferToTxOutputConversionError),
+    #[error("Failed to write the wallet config file: {0}")]
+    WalletConfigFile(std::io::Error),
+    #[error("Failed to access the transaction file {}: {}", .0.display(), .1)]
+    TransactionFile(PathBuf, std::io::Error),
+    #[error("Failed to read the block file {}: {}", .0.display(), .1)]
+    BlockFile(PathBuf, std::io::Error),
+    #[error("Staking pool {0} is not owned by the selected account")]
+    StakingPoolNotOwned(String),
+    #[error("Address {0} doesn't belong to any account of the wallet")]
+    AddressNotFound(String),
 }
@@ -13,17 +13,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{fmt::Display, str::FromStr};
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
 
 use clap::ValueEnum;
 
 use common::{
     address::Address,
     chain::{ChainConfig, OutPointSourceId, TxOutput, UtxoOutPoint},
-    primitives::{DecimalAmount, Id, H256},
+    primitives::{time::Time, Amount, BlockHeight, DecimalAmount, Id, H256},
 };
-use wallet_controller::types::{GenericCurrencyTransfer, GenericTokenTransfer};
-use wallet_rpc_lib::types::{NodeInterface, PoolInfo, TokenTotalSupply};
+use wallet::account::transaction_list::TxCursor;
+use wallet_controller::{
+    types::{CreatedBlockInfo, GenericCurrencyTransfer, GenericTokenTransfer},
+    ConnectedPeer,
+};
+use wallet_rpc_lib::types::{NodeInterface, PoolInfo, RpcAmountOut, TokenTotalSupply};
 use wallet_types::{
     utxo_types::{UtxoState, UtxoType},
     with_locked::WithLocked,
@@ -106,6 +110,53 @@ pub fn format_pool_info(pool_info: PoolInfo) -> String {
     )
 }
 
+/// Format a detailed report about a staking pool.
+///
+/// `delegations_count` is `None` if the node couldn't find the pool.
+pub fn format_staking_pool_report(
+    pool_info: &PoolInfo,
+    delegations_count: Option<usize>,
+    created_blocks: &[CreatedBlockInfo],
+    chain_config: &ChainConfig,
+) -> String {
+    let delegated_balance =
+        (pool_info.balance.amount() - pool_info.pledge.amount()).unwrap_or(Amount::ZERO);
+    let delegated_balance =
+        DecimalAmount::from_amount_no_padding(delegated_balance, chain_config.coin_decimals());
+    let delegations_count = delegations_count.map_or_else(
+        || "Not found on chain".to_owned(),
+        |count| count.to_string(),
+    );
+
+    let pool_blocks = created_blocks
+        .iter()
+        .filter(|block| block.pool_id == pool_info.pool_id.as_str())
+        .collect::<Vec<_>>();
+    let last_block_height = pool_blocks
+        .iter()
+        .map(|block| block.height)
+        .max()
+        .map_or_else(|| "None".to_owned(), |height| height.to_string());
+
+    [
+        format!("Pool Id: {}", pool_info.pool_id),
+        format!("Total balance: {}", pool_info.balance.decimal()),
+        format!("Staker balance: {}", pool_info.pledge.decimal()),
+        format!("Delegated balance: {}", delegated_balance),
+        format!("Delegations: {}", delegations_count),
+        format!("Margin ratio: {}", pool_info.margin_ratio_per_thousand),
+        format!("Cost per block: {}", pool_info.cost_per_block.decimal()),
+        format!("Blocks created by this wallet: {}", pool_blocks.len()),
+        format!("Last created block height: {}", last_block_height),
+        format!("Creation block height: {}", pool_info.height),
+        format!("Creation block timestamp: {}", pool_info.block_timestamp),
+        format!("Staker: {}", pool_info.staker),
+        format!("Decommission key: {}", pool_info.decommission_key),
+        format!("VRF public key: {}", pool_info.vrf_public_key),
+    ]
+    .join("\n")
+}
+
 pub fn format_delegation_info(delegation_id: String, balance: String) -> String {
     format!("Delegation Id: {}, Balance: {}", delegation_id, balance,)
 }
@@ -196,6 +247,53 @@ pub fn parse_utxo_outpoint<N: NodeInterface>(
     Ok(UtxoOutPoint::new(source_id, output_index))
 }
 
+/// Parses a transaction list cursor, as printed by `format_tx_cursor`.
+/// The string format is expected to be
+/// confirmed(u64,H256) or unconfirmed(H256)
+///
+/// e.g confirmed(1234,000000000000000000059fa50103b9683e51e5aba83b8a34c9b98ce67d66136c)
+/// e.g unconfirmed(000000000000000000059fa50103b9683e51e5aba83b8a34c9b98ce67d66136c)
+pub fn parse_tx_cursor<N: NodeInterface>(
+    input: &str,
+) -> Result<TxCursor, WalletCliCommandError<N>> {
+    let (name, mut args) = parse_funclike_expr(input).ok_or(
+        WalletCliCommandError::<N>::InvalidInput("Invalid cursor format".into()),
+    )?;
+
+    let (block_height, h256_str) = match (name, args.next(), args.next(), args.next()) {
+        ("confirmed", Some(height_str), Some(h256_str), None) => {
+            let height = u64::from_str(height_str)
+                .map_err(|err| WalletCliCommandError::<N>::InvalidInput(err.to_string()))?;
+            (Some(BlockHeight::new(height)), h256_str)
+        }
+        ("unconfirmed", Some(h256_str), None, None) => (None, h256_str),
+        (_, _, _, _) => {
+            return Err(WalletCliCommandError::<N>::InvalidInput(
+                "Invalid cursor format".into(),
+            ));
+        }
+    };
+
+    let h256 = H256::from_str(h256_str)
+        .map_err(|err| WalletCliCommandError::<N>::InvalidInput(err.to_string()))?;
+
+    Ok(TxCursor {
+        block_height,
+        tx_id: Id::new(h256),
+    })
+}
+
+pub fn format_tx_cursor(cursor: &TxCursor) -> String {
+    match cursor.block_height {
+        Some(height) => format!(
+            "confirmed({},{:x})",
+            height.into_int(),
+            cursor.tx_id.to_hash()
+        ),
+        None => format!("unconfirmed({:x})", cursor.tx_id.to_hash()),
+    }
+}
+
 /// Parses a string into `GenericCurrencyTransfer`.
 /// The string format is expected to be `transfer(address,amount)`
 /// e.g `transfer(tmt1qy7y8ra99sgmt97lu2kn249yds23pnp7xsv62p77,10.1)`.
@@ -434,6 +532,95 @@ impl YesNo {
     }
 }
 
+/// A table of the connected peers sorted by the last ping time, the peers without a completed
+/// ping go last
+pub fn peer_latency_table(mut peers: Vec<ConnectedPeer>) -> prettytable::Table {
+    peers.sort_by_key(|peer| (peer.ping_last.is_none(), peer.ping_last));
+
+    let mut table = prettytable::Table::new();
+    table.set_titles(prettytable::row![
+        "Peer id",
+        "Address",
+        "Role",
+        "Last ping, ms",
+        "Min ping, ms",
+        "Last message",
+    ]);
+
+    table.extend(peers.into_iter().map(|peer| {
+        prettytable::row![
+            peer.peer_id,
+            peer.address,
+            format!("{:?}", peer.peer_role),
+            peer.ping_last.map_or(String::new(), |ping| ping.to_string()),
+            peer.ping_min.map_or(String::new(), |ping| ping.to_string()),
+            peer.last_message_time.map_or(String::new(), |time| {
+                Time::from_secs_since_epoch(time).to_string()
+            }),
+        ]
+    }));
+
+    table
+}
+
+/// The coin amount of a utxo in the format returned by `get_utxos`: the value of a transfer or
+/// the pledge of a stake pool. Token transfers and the other outputs don't have one.
+fn utxo_coin_amount(utxo: &serde_json::Value) -> Option<Amount> {
+    let content = &utxo["output"]["content"];
+    let amount = if content["value"]["type"] == "Coin" {
+        &content["value"]["content"]["amount"]
+    } else {
+        &content["data"]["pledge"]
+    };
+    serde_json::from_value::<RpcAmountOut>(amount.clone())
+        .ok()
+        .map(|amount| amount.amount())
+}
+
+/// Group the utxos by the output type, with the number of utxos and their total coin amount
+/// for each type, both overall and for the unlocked and the locked utxos separately
+pub fn utxo_summary(
+    unlocked: &[serde_json::Value],
+    locked: &[serde_json::Value],
+    chain_config: &ChainConfig,
+) -> serde_json::Value {
+    // (count, total amount) of the unlocked and the locked utxos of each type
+    let mut groups = BTreeMap::<String, [(usize, Amount); 2]>::new();
+    for (index, utxos) in [unlocked, locked].into_iter().enumerate() {
+        for utxo in utxos {
+            let output_type = utxo["output"]["type"].as_str().unwrap_or_default().to_owned();
+            let (count, total) =
+                &mut groups.entry(output_type).or_insert([(0, Amount::ZERO); 2])[index];
+            *count += 1;
+            *total = (*total + utxo_coin_amount(utxo).unwrap_or(Amount::ZERO))
+                .expect("Total coin amount cannot overflow");
+        }
+    }
+
+    let decimals = chain_config.coin_decimals();
+    let group_json = |count: usize, total: Amount| {
+        serde_json::json!({
+            "count": count,
+            "total_amount": DecimalAmount::from_amount_no_padding(total, decimals).to_string(),
+        })
+    };
+
+    groups
+        .into_iter()
+        .map(
+            |(output_type, [(unlocked_count, unlocked_total), (locked_count, locked_total)])| {
+                let total =
+                    (unlocked_total + locked_total).expect("Total coin amount cannot overflow");
+                let mut summary = group_json(unlocked_count + locked_count, total);
+                summary["type"] = output_type.into();
+                summary["unlocked"] = group_json(unlocked_count, unlocked_total);
+                summary["locked"] = group_json(locked_count, locked_total);
+                summary
+            },
+        )
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
@@ -546,6 +733,29 @@ mod tests {
         }
     }
 
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn test_parse_tx_cursor(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        for _ in 0..10 {
+            let cursor = TxCursor {
+                block_height: rng.gen::<bool>().then(|| BlockHeight::new(rng.gen())),
+                tx_id: Id::new(H256::random_using(&mut rng)),
+            };
+            let parsed = parse_tx_cursor::<ColdWalletClient>(&format_tx_cursor(&cursor)).unwrap();
+            assert_eq!(parsed, cursor);
+        }
+
+        for input in ["confirmed(abc)", "unconfirmed(1,abc)", "tx(abc,1)", "confirmed"] {
+            assert_matches!(
+                parse_tx_cursor::<ColdWalletClient>(input).unwrap_err(),
+                WalletCliCommandError::<ColdWalletClient>::InvalidInput(_)
+            );
+        }
+    }
+
     #[rstest]
     #[trace]
     #[case(Seed::from_entropy())]
@@ -643,4 +853,271 @@ mod tests {
             parse_assert_error(&format!("transfer {token_id_as_addr},{addr},{amount}"));
         }
     }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy(), Some(0))]
+    #[case(Seed::from_entropy(), Some(1))]
+    #[case(Seed::from_entropy(), Some(123))]
+    #[case(Seed::from_entropy(), None)]
+    fn test_format_staking_pool_report(
+        #[case] seed: Seed,
+        #[case] delegations_count: Option<usize>,
+    ) {
+        use common::{
+            chain::{block::timestamp::BlockTimestamp, GenBlock, PoolId},
+            primitives::{per_thousand::PerThousand, BlockHeight},
+        };
+        use wallet_rpc_lib::types::{RpcAddress, RpcAmountOut};
+
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = chain::config::create_unit_test_config();
+        let decimals = chain_config.coin_decimals();
+
+        let make_pool_id = |rng: &mut _| {
+            Address::new(&chain_config, PoolId::new(H256::random_using(rng)))
+                .unwrap()
+                .into_string()
+        };
+        let make_destination = |rng: &mut _| {
+            Address::new(
+                &chain_config,
+                Destination::PublicKeyHash(PublicKeyHash::random_using(rng)),
+            )
+            .unwrap()
+            .into_string()
+        };
+
+        let pool_id = make_pool_id(&mut rng);
+        let other_pool_id = make_pool_id(&mut rng);
+        let staker = make_destination(&mut rng);
+        let decommission_key = make_destination(&mut rng);
+
+        let pledge = Amount::from_atoms(rng.gen_range(1..1_000_000_000));
+        let delegated = if delegations_count.unwrap_or(0) == 0 {
+            Amount::ZERO
+        } else {
+            Amount::from_atoms(rng.gen_range(1..1_000_000_000))
+        };
+        let balance = (pledge + delegated).unwrap();
+        let cost_per_block = Amount::from_atoms(rng.gen_range(0..1_000_000));
+        let margin_ratio = PerThousand::new_from_rng(&mut rng);
+        let creation_height = BlockHeight::new(rng.gen_range(0..1000));
+        let creation_timestamp = BlockTimestamp::from_int_seconds(rng.gen_range(0..1_000_000));
+
+        let pool_info = PoolInfo {
+            pool_id: RpcAddress::from(pool_id.clone()),
+            pledge: RpcAmountOut::from_amount_no_padding(pledge, decimals),
+            balance: RpcAmountOut::from_amount_no_padding(balance, decimals),
+            height: creation_height,
+            block_timestamp: creation_timestamp,
+            vrf_public_key: RpcAddress::from(
+                "rvrfpk1qregu4v895mchautf84u46nsf9xel2507a37ksaf3stmuw44y3m4vc2kzme".to_owned(),
+            ),
+            decommission_key: RpcAddress::from(decommission_key.clone()),
+            staker: RpcAddress::from(staker.clone()),
+            margin_ratio_per_thousand: margin_ratio,
+            cost_per_block: RpcAmountOut::from_amount_no_padding(cost_per_block, decimals),
+        };
+
+        let make_block = |pool_id: &str, height: u64| CreatedBlockInfo {
+            id: Id::<GenBlock>::new(H256::zero()),
+            height: BlockHeight::new(height),
+            pool_id: pool_id.to_owned(),
+        };
+        let created_blocks = [
+            make_block(&pool_id, 10),
+            make_block(&other_pool_id, 20),
+            make_block(&pool_id, 15),
+        ];
+
+        let report = format_staking_pool_report(
+            &pool_info,
+            delegations_count,
+            &created_blocks,
+            &chain_config,
+        );
+        let lines = report.lines().collect::<Vec<_>>();
+
+        let expected_delegations = match delegations_count {
+            Some(count) => format!("Delegations: {count}"),
+            None => "Delegations: Not found on chain".to_owned(),
+        };
+        let expected_lines = [
+            format!("Pool Id: {pool_id}"),
+            format!(
+                "Total balance: {}",
+                DecimalAmount::from_amount_no_padding(balance, decimals)
+            ),
+            format!(
+                "Staker balance: {}",
+                DecimalAmount::from_amount_no_padding(pledge, decimals)
+            ),
+            format!(
+                "Delegated balance: {}",
+                DecimalAmount::from_amount_no_padding(delegated, decimals)
+            ),
+            expected_delegations,
+            format!("Margin ratio: {margin_ratio}"),
+            format!(
+                "Cost per block: {}",
+                DecimalAmount::from_amount_no_padding(cost_per_block, decimals)
+            ),
+            "Blocks created by this wallet: 2".to_owned(),
+            "Last created block height: 15".to_owned(),
+            format!("Creation block height: {creation_height}"),
+            format!("Creation block timestamp: {creation_timestamp}"),
+            format!("Staker: {staker}"),
+            format!("Decommission key: {decommission_key}"),
+            "VRF public key: rvrfpk1qregu4v895mchautf84u46nsf9xel2507a37ksaf3stmuw44y3m4vc2kzme"
+                .to_owned(),
+        ];
+        assert_eq!(lines, expected_lines);
+
+        // A pool that hasn't created any blocks yet
+        let report = format_staking_pool_report(&pool_info, delegations_count, &[], &chain_config);
+        assert!(report.contains("Blocks created by this wallet: 0"));
+        assert!(report.contains("Last created block height: None"));
+    }
+
+    #[test]
+    fn connected_peer_latency_fields() {
+        let peer: ConnectedPeer = serde_json::from_str(
+            r#"{
+                "peer_id": 1,
+                "address": "127.0.0.1:3031",
+                "peer_role": "Inbound",
+                "ban_score": 0,
+                "user_agent": "MintlayerCore",
+                "software_version": "1.0.0",
+                "ping_wait": null,
+                "ping_last": 250,
+                "ping_min": 120,
+                "last_message_time": 1700000000
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(peer.ping_wait, None);
+        assert_eq!(peer.ping_last, Some(250));
+        assert_eq!(peer.ping_min, Some(120));
+        assert_eq!(peer.last_message_time, Some(1700000000));
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn peer_latency_table_sorted(#[case] seed: Seed) {
+        use p2p_types::{peer_id::PeerId, socket_address::SocketAddress};
+
+        let mut rng = make_seedable_rng(seed);
+
+        let peers = (0..rng.gen_range(1..20))
+            .map(|i| {
+                let ping_last = rng.gen_bool(0.8).then(|| rng.gen_range(0..10_000));
+                ConnectedPeer {
+                    peer_id: PeerId::new(),
+                    address: SocketAddress::new(([127, 0, 0, 1], 3000 + i).into()),
+                    peer_role: p2p::net::types::PeerRole::Inbound,
+                    ban_score: 0,
+                    user_agent: "MintlayerCore".to_owned(),
+                    software_version: "1.0.0".to_owned(),
+                    ping_wait: None,
+                    ping_last,
+                    ping_min: ping_last.map(|ping| rng.gen_range(0..=ping)),
+                    last_message_time: rng.gen_bool(0.8).then(|| rng.gen_range(0..2_000_000_000)),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let table = peer_latency_table(peers.clone());
+        assert_eq!(table.len(), peers.len());
+
+        let rendered_pings = (0..table.len())
+            .map(|i| table.get_row(i).unwrap().get_cell(3).unwrap().get_content())
+            .collect::<Vec<_>>();
+        let mut expected_pings = peers.iter().map(|peer| peer.ping_last).collect::<Vec<_>>();
+        expected_pings.sort_by_key(|ping| (ping.is_none(), *ping));
+        let expected_pings = expected_pings
+            .into_iter()
+            .map(|ping| ping.map_or(String::new(), |ping| ping.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(rendered_pings, expected_pings);
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn test_utxo_summary(#[case] seed: Seed) {
+        use common::chain::{output_value::OutputValue, timelock::OutputTimeLock};
+        use wallet_rpc_lib::types::UtxoInfo;
+
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = chain::config::create_unit_test_config();
+        let decimals = chain_config.coin_decimals();
+
+        let mut make_utxo = |lock: Option<OutputTimeLock>| {
+            let amount = Amount::from_atoms(rng.gen_range(1..1_000_000_000));
+            let value = OutputValue::Coin(amount);
+            let destination = Destination::PublicKeyHash(PublicKeyHash::random_using(&mut rng));
+            let output = match lock {
+                Some(lock) => TxOutput::LockThenTransfer(value, destination, lock),
+                None => TxOutput::Transfer(value, destination),
+            };
+            let outpoint = UtxoOutPoint::new(
+                OutPointSourceId::Transaction(Id::new(H256::random_using(&mut rng))),
+                rng.gen_range(0..10),
+            );
+            let utxo = UtxoInfo::new(outpoint, output, &chain_config).unwrap();
+            (serde_json::to_value(utxo).unwrap(), amount)
+        };
+        let sum = |utxos: &[(serde_json::Value, Amount)]| {
+            let total = utxos.iter().map(|(_, amount)| *amount).sum::<Option<Amount>>().unwrap();
+            DecimalAmount::from_amount_no_padding(total, decimals).to_string()
+        };
+
+        let transfers = (0..5).map(|_| make_utxo(None)).collect::<Vec<_>>();
+        let unlocked_lock_then_transfers = vec![make_utxo(Some(OutputTimeLock::ForBlockCount(1)))];
+        let locked_lock_then_transfers = (0..2)
+            .map(|_| make_utxo(Some(OutputTimeLock::UntilHeight(BlockHeight::new(1000)))))
+            .collect::<Vec<_>>();
+        let all_lock_then_transfers = unlocked_lock_then_transfers
+            .iter()
+            .chain(&locked_lock_then_transfers)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let unlocked = transfers
+            .iter()
+            .chain(&unlocked_lock_then_transfers)
+            .map(|(utxo, _)| utxo.clone())
+            .collect::<Vec<_>>();
+        let locked = locked_lock_then_transfers
+            .iter()
+            .map(|(utxo, _)| utxo.clone())
+            .collect::<Vec<_>>();
+
+        let summary = utxo_summary(&unlocked, &locked, &chain_config);
+        assert_eq!(
+            summary,
+            serde_json::json!([
+                {
+                    "type": "LockThenTransfer",
+                    "count": 3,
+                    "total_amount": sum(&all_lock_then_transfers),
+                    "unlocked": { "count": 1, "total_amount": sum(&unlocked_lock_then_transfers) },
+                    "locked": { "count": 2, "total_amount": sum(&locked_lock_then_transfers) },
+                },
+                {
+                    "type": "Transfer",
+                    "count": 5,
+                    "total_amount": sum(&transfers),
+                    "unlocked": { "count": 5, "total_amount": sum(&transfers) },
+                    "locked": { "count": 0, "total_amount": sum(&[]) },
+                },
+            ])
+        );
+
+        assert_eq!(utxo_summary(&[], &[], &chain_config), serde_json::json!([]));
+    }
 }
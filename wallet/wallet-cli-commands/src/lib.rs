@@ -28,7 +28,10 @@ use std::{fmt::Debug, num::NonZeroUsize, path::PathBuf, time::Duration};
 use clap::{Command, FromArgMatches, Parser, Subcommand};
 
 use common::{
-    chain::{Block, SignedTransaction, Transaction},
+    chain::{
+        partially_signed_transaction::PartiallySignedTransaction, Block, SignedTransaction,
+        Transaction,
+    },
     primitives::{BlockHeight, DecimalAmount, Id},
 };
 use crypto::key::{hdkd::u31::U31, PrivateKey, PublicKey};
@@ -92,6 +95,15 @@ pub enum ColdWalletCommand {
     #[clap(name = "wallet-info")]
     WalletInfo,
 
+    /// Show the fingerprint of the selected account, a hash of its extended public key.
+    /// Compare the fingerprints to check that a wallet restored from a seed phrase matches the original.
+    #[clap(name = "wallet-account-fingerprint")]
+    AccountFingerprint,
+
+    /// Check that the wallet database is internally consistent, e.g. after a crash
+    #[clap(name = "wallet-check-integrity")]
+    CheckIntegrity,
+
     #[clap(name = "wallet-encrypt-private-keys")]
     EncryptPrivateKeys {
         /// The new encryption password
@@ -126,6 +138,11 @@ pub enum ColdWalletCommand {
         i_know_what_i_am_doing: Option<CliForceReduce>,
     },
 
+    /// Show the lookahead size of the selected account, the last used receive-address
+    /// and how many more receive-addresses can be issued before the lookahead size is exhausted
+    #[clap(name = "wallet-lookahead-status")]
+    LookaheadStatus,
+
     /// Creates a QR code of the provided address
     #[clap(name = "address-qrcode")]
     AddressQRCode {
@@ -136,6 +153,11 @@ pub enum ColdWalletCommand {
     #[clap(name = "address-new")]
     NewAddress,
 
+    /// Show the change address that the next transaction created by the selected account would use,
+    /// without issuing a new address
+    #[clap(name = "address-current-change")]
+    CurrentChangeAddress,
+
     /// Reveal the public key behind this address in hex encoding
     #[clap(name = "address-reveal-public-key-as-hex")]
     RevealPublicKeyHex {
@@ -156,14 +178,52 @@ pub enum ColdWalletCommand {
     #[clap(name = "standalone-address-show")]
     ShowStandaloneAddresses,
 
+    /// List the standalone addresses of the selected account with their types, labels and balances
+    #[clap(name = "account-list-standalone")]
+    ListStandaloneAddresses,
+
     #[clap(name = "standalone-address-details")]
     ShowStandaloneAddressDetails {
         address: String,
     },
 
+    /// Find which account of the wallet owns the given address and whether the address is derived
+    /// from the account's key chain or was added to it as a standalone address
+    #[clap(name = "address-lookup")]
+    AddressLookup {
+        /// The address to look up
+        address: String,
+    },
+
+    /// Show the derivation path of an address issued by the selected account
+    #[clap(name = "address-derivation-path")]
+    ShowAddressDerivationPath {
+        /// The address to look up
+        address: String,
+    },
+
+    /// Show the transactions in the given blocks that would be recognised as belonging to the
+    /// selected account if the blocks were scanned, without changing the wallet
+    #[clap(name = "wallet-simulate-scan")]
+    SimulateScan {
+        /// The file with the hex encoded blocks, one per line
+        #[arg(long = "block-file")]
+        block_file: PathBuf,
+    },
+
     #[clap(name = "staking-new-vrf-public-key")]
     NewVrfPublicKey,
 
+    /// Issue several new VRF public keys at once and print them together with their child
+    /// numbers. Fails without issuing any key if they don't all fit in the key lookahead.
+    #[clap(name = "staking-new-vrf-public-keys")]
+    GenerateVrfKeys {
+        /// The number of keys to issue
+        count: u32,
+        /// The index of the account, the selected account is used if not specified
+        account_index: Option<U31>,
+    },
+
     #[clap(name = "staking-show-vrf-public-keys")]
     GetVrfPublicKey,
 
@@ -176,6 +236,18 @@ pub enum ColdWalletCommand {
         transaction: String,
     },
 
+    /// Sign the transaction stored in a file, e.g. one written by `transaction-export-to-file`,
+    /// and write the result to another file.
+    /// The result is a signed transaction if all the inputs are fully signed after signing,
+    /// otherwise it's a PartiallySignedTransaction that can be signed further.
+    #[clap(name = "transaction-sign-file")]
+    SignTxFile {
+        /// The file with the hex encoded transaction or PartiallySignedTransaction
+        input_path: PathBuf,
+        /// The file to write the hex encoded result to
+        output_path: PathBuf,
+    },
+
     #[clap(name = "challenge-sign-hex")]
     #[clap(hide = true)]
     SignChallegeHex {
@@ -238,13 +310,62 @@ pub enum WalletCommand {
     #[clap(name = "account-create")]
     CreateNewAccount { name: Option<String> },
 
+    /// Create the accounts with the given indexes, even if the previous accounts have no transaction history.
+    /// Use this when only some of the accounts of a wallet need to be recovered; the new accounts
+    /// are rescanned from the genesis block.
+    #[clap(name = "account-recover")]
+    RecoverAccounts {
+        /// The indexes of the accounts to recover
+        #[arg(required = true)]
+        account_indices: Vec<U31>,
+    },
+
+    /// Show the public key the next created account contributes to a multisig account.
+    /// Share it with the other cosigners, so they can create the same multisig account.
+    #[clap(name = "account-next-multisig-public-key")]
+    ShowNextMultisigAccountPublicKey,
+
+    /// Create a new M-of-N multisig account from the public keys of the other cosigners,
+    /// as shown by `account-next-multisig-public-key` in their wallets.
+    /// The balance and the transactions of the new account use the funds sent to the multisig
+    /// address, which also receives the change.
+    #[clap(name = "account-create-multisig")]
+    CreateMultisigAccount {
+        /// The minimum required signatures out of all the public keys, including this account's one
+        min_required_signatures: u8,
+
+        /// The public keys of the other cosigners
+        #[arg(required = true)]
+        cosigner_public_keys: Vec<String>,
+
+        /// Optionally specify a name for the new account
+        #[arg(long = "name")]
+        name: Option<String>,
+    },
+
+    /// Rename an account of the wallet. If no name is given, the name of the account is cleared.
     #[clap(name = "account-rename")]
-    RenameAccount { name: Option<String> },
+    RenameAccount {
+        /// The index of the account to rename
+        account_index: U31,
+        /// The new name of the account
+        name: Option<String>,
+    },
 
     /// Switch to a given wallet account.
     #[clap(name = "account-select")]
     SelectAccount { account_index: U31 },
 
+    /// Print a warning, at most once per 10 blocks, whenever the balance of the given account
+    /// drops below the threshold. The threshold is stored in the wallet config file.
+    #[clap(name = "account-balance-threshold-alert")]
+    SetBalanceAlert {
+        /// The index of the account to watch
+        account_index: U31,
+        /// The coin amount below which a warning is printed
+        threshold: DecimalAmount,
+    },
+
     #[clap(name = "account-utxos")]
     ListUtxo {
         /// The type of utxo to be listed. Default is "all".
@@ -258,6 +379,24 @@ pub enum WalletCommand {
         utxo_states: Vec<CliUtxoState>,
     },
 
+    /// List the utxos that are still locked by a timelock, together with the time at which
+    /// they unlock. For the block height based timelocks, the time is an estimate.
+    #[clap(name = "account-locked-utxos")]
+    ListLockedUtxo {
+        /// The state of the utxos; e.g., confirmed, unconfirmed, etc.
+        #[arg(default_values_t = vec![CliUtxoState::Confirmed])]
+        utxo_states: Vec<CliUtxoState>,
+    },
+
+    /// Print the number of the confirmed utxos of each type together with their total coin amount,
+    /// both overall and split into the unlocked and the locked ones.
+    /// Token amounts are not included in the totals.
+    #[clap(name = "account-utxo-summary")]
+    UtxoSummary {
+        /// The index of the account, the selected account is used if not specified
+        account_index: Option<U31>,
+    },
+
     #[clap(name = "account-balance")]
     GetBalance {
         /// Whether to include locked outputs (outputs that cannot be spend and need time to mature)
@@ -268,6 +407,11 @@ pub enum WalletCommand {
         utxo_states: Vec<CliUtxoState>,
     },
 
+    /// List the tokens owned by the selected account, with their tickers, number of decimals
+    /// and whether they are frozen. Only confirmed and unlocked utxos are counted.
+    #[clap(name = "account-token-balances")]
+    ListOwnedTokens,
+
     #[clap(name = "standalone-address-label-rename")]
     StandaloneAddressLabelRename {
         /// The existing standalone address
@@ -373,6 +517,27 @@ pub enum WalletCommand {
         is_freezable: CliIsFreezable,
     },
 
+    /// Issue a new token whose authority is a classic multisig address made of the given public keys.
+    /// The authority is set by the issuance itself, so unlike issuing a token and changing its authority
+    /// afterwards, there is no window in which a single key controls the token.
+    #[clap(name = "token-create-and-delegate-authority")]
+    IssueTokenWithMultisigAuthority {
+        /// The ticker/symbol of the token created
+        token_ticker: String,
+        /// The maximum number of digits after the decimal points
+        number_of_decimals: u8,
+        /// URI for data related to the token (website, media, etc)
+        metadata_uri: String,
+        /// The total supply of this token
+        token_supply: String,
+        /// Whether it's possible to centrally freeze this token for all users (due to migration requirements, for example)
+        is_freezable: CliIsFreezable,
+        /// The minimum required signatures of the authority out of the specified public keys
+        required_sigs: u8,
+        /// Hex encoded public keys from which to create the multisig authority
+        authority_keys: Vec<HexEncoded<PublicKey>>,
+    },
+
     #[clap(name = "token-change-authority")]
     ChangeTokenAuthority { token_id: String, address: String },
 
@@ -430,6 +595,21 @@ pub enum WalletCommand {
         amount: DecimalAmount,
     },
 
+    /// Send the whole confirmed and unlocked balance of the token to the given address.
+    /// No token change is returned to the wallet.
+    #[clap(name = "token-send-all")]
+    SendAllTokensToAddress {
+        /// The token id of the tokens to be sent
+        token_id: String,
+        /// The destination address receiving the tokens
+        address: String,
+    },
+
+    /// List the tokens whose authority is owned by the selected account,
+    /// i.e. the tokens this account can mint, freeze, etc.
+    #[clap(name = "token-list-owned")]
+    ListTokenAuthorities,
+
     /// Create a transaction for sending tokens from a multisig address to other addresses, returning the change to
     /// the original multisig address.
     ///
@@ -554,6 +734,11 @@ pub enum WalletCommand {
     #[clap(name = "staking-pool-balance")]
     StakePoolBalance { pool_id: String },
 
+    /// Print detailed information about a staking pool owned by the selected account,
+    /// including its delegations and the blocks it has created
+    #[clap(name = "staking-pool-info")]
+    StakingPoolInfo { pool_id: String },
+
     #[clap(name = "staking-list-created-block-ids")]
     ListCreatedBlocksIds,
 
@@ -579,6 +764,12 @@ pub enum WalletCommand {
 
         /// The key that can decommission the pool. It's recommended to keep the decommission key in a cold storage.
         decommission_address: String,
+
+        /// Optionally, an externally held VRF public key to be used by the pool instead of the one derived
+        /// from this account. This is useful for cold staking setups, where the machine running this wallet
+        /// shouldn't be able to produce blocks on its own.
+        #[arg(long = "vrf-public-key")]
+        vrf_public_key: Option<String>,
     },
 
     #[clap(name = "staking-decommission-pool")]
@@ -598,6 +789,36 @@ pub enum WalletCommand {
         output_address: String,
     },
 
+    /// Sign a request from another wallet, same as `account-sign-raw-transaction` does,
+    /// and optionally broadcast the result if it's fully signed.
+    #[clap(name = "transaction-sign-request")]
+    SignRequest {
+        /// Hex encoded PartiallySignedTransaction, e.g. the output of `staking-decommission-pool-request`.
+        request: HexEncoded<PartiallySignedTransaction>,
+        /// Broadcast the transaction to the network if it is fully signed after signing
+        #[arg(long = "broadcast", default_value_t = false)]
+        broadcast: bool,
+    },
+
+    /// Write a PartiallySignedTransaction to a file, so it can be moved to an offline
+    /// machine and signed there with `transaction-sign-file`.
+    #[clap(name = "transaction-export-to-file")]
+    ExportUnsignedTx {
+        /// Hex encoded PartiallySignedTransaction
+        tx: HexEncoded<PartiallySignedTransaction>,
+        /// The file to write the transaction to
+        output_path: PathBuf,
+    },
+
+    /// Broadcast the fully signed transaction stored in a file, e.g. one written by
+    /// `transaction-sign-file`.
+    #[clap(name = "transaction-submit-file")]
+    SubmitSignedTxFile {
+        /// The file with the hex encoded signed transaction or fully signed
+        /// PartiallySignedTransaction
+        input_path: PathBuf,
+    },
+
     #[clap(name = "wallet-rescan")]
     Rescan,
 
@@ -650,6 +871,11 @@ pub enum WalletCommand {
     #[clap(hide = true)]
     ConnectedPeersJson,
 
+    /// List the connected peers sorted by their ping latency, together with the time
+    /// when a message was last received from each of them
+    #[clap(name = "node-peer-latency")]
+    PeerLatency,
+
     #[clap(name = "node-list-reserved-peers")]
     ReservedPeers,
 
@@ -659,6 +885,22 @@ pub enum WalletCommand {
     #[clap(name = "node-remove-reserved-peer")]
     RemoveReservedPeer { address: IpOrSocketAddress },
 
+    /// Show the maximum numbers of inbound and automatic outbound connections of the node
+    #[clap(name = "node-get-peer-limits")]
+    GetPeerLimits,
+
+    /// Change the maximum numbers of inbound and automatic outbound connections of the node.
+    /// Existing connections are kept, the new limits apply to new connections only.
+    #[clap(name = "node-set-peer-limits")]
+    SetPeerLimits {
+        /// The maximum number of inbound connections
+        #[arg(long)]
+        inbound: usize,
+        /// The maximum number of automatic outbound connections, must not be zero
+        #[arg(long)]
+        outbound: usize,
+    },
+
     #[clap(name = "node-submit-block")]
     SubmitBlock {
         /// Hex encoded block
@@ -757,9 +999,49 @@ pub enum WalletCommand {
         transaction_id: HexEncoded<Id<Transaction>>,
     },
 
+    /// Submit all the transactions created by the wallet that are not confirmed yet to the node again
+    #[clap(name = "transaction-rebroadcast-all")]
+    RebroadcastAllTransactions,
+
+    /// Speed up an unconfirmed transaction by creating a child transaction (child pays for parent)
+    /// that spends one of its outputs owned by the selected account.
+    /// The child pays enough fee for both transactions together to reach the target fee rate.
+    #[clap(name = "transaction-cpfp")]
+    ChildPaysForParent {
+        /// The id of the unconfirmed parent transaction, in hex.
+        parent_tx_id: HexEncoded<Id<Transaction>>,
+        /// The target fee rate of the parent and the child together, in coins per 1000 bytes.
+        target_fee_rate: DecimalAmount,
+    },
+
+    /// Import a transaction that was created outside of this wallet into the selected account's history,
+    /// e.g. one that was missed while syncing from a pruned node.
+    /// At least one of its inputs or outputs must belong to the selected account.
+    #[clap(name = "transaction-import")]
+    ImportTransaction {
+        /// Hex encoded transaction.
+        raw_tx: HexEncoded<SignedTransaction>,
+        /// The height of the block that includes the transaction.
+        /// If not specified, the transaction is recorded as pending.
+        block_height: Option<BlockHeight>,
+    },
+
     #[clap(name = "transaction-list-pending")]
     ListPendingTransactions,
 
+    /// List the transactions of the selected account, starting with the unconfirmed ones and then
+    /// from the most recent block. The last line contains the cursor to pass to get the next page.
+    #[clap(name = "transaction-list")]
+    ListTransactions {
+        /// Continue listing after the transaction this cursor points to,
+        /// e.g. confirmed(1234,<tx id>) or unconfirmed(<tx id>)
+        #[arg(long = "cursor")]
+        cursor: Option<String>,
+        /// The number of transactions to print, default is 50
+        #[arg(long = "count", default_value = "50")]
+        count: NonZeroUsize,
+    },
+
     #[clap(name = "transaction-list-by-address")]
     ListMainchainTransactions {
         /// Address to filter by
@@ -769,6 +1051,15 @@ pub enum WalletCommand {
         limit: usize,
     },
 
+    /// Print the number of confirmed transactions and the net coin flow of the selected account
+    /// for each day of the given period
+    #[clap(name = "account-activity-summary")]
+    AccountActivitySummary {
+        /// The number of days to summarize, ending today. Default is 90
+        #[arg(long = "days", default_value_t = 90)]
+        days: u32,
+    },
+
     #[clap(name = "transaction-get")]
     GetTransaction {
         /// Transaction id, encoded in hex
@@ -16,17 +16,68 @@
 use std::{fmt::Debug, sync::Arc};
 
 use common::chain::ChainConfig;
+use futures::{stream::BoxStream, StreamExt};
 use tokio::sync::{mpsc, oneshot};
 use wallet_cli_commands::{CommandHandler, ConsoleCommand, ManageableWalletCommand};
 use wallet_rpc_client::{handles_client::WalletRpcHandlesClient, rpc_client::ClientWalletRpc};
-use wallet_rpc_lib::types::{ControllerConfig, NodeInterface};
+use wallet_rpc_lib::types::NodeInterface;
 use wallet_rpc_lib::{
-    config::WalletRpcConfig, ColdWalletRpcServer, WalletEventsRpcServer, WalletRpc,
-    WalletRpcServer, WalletService,
+    config::{WalletRpcConfig, WalletTxConfig},
+    ColdWalletRpcServer, Event as WalletEvent, EventStream, WalletEventsRpcClient,
+    WalletEventsRpcServer, WalletRpc, WalletRpcServer, WalletService,
 };
 
 use crate::errors::WalletCliError;
 
+/// Notifications about the new blocks processed by the wallet, after which the balance alerts
+/// are checked
+type NewBlocks = BoxStream<'static, ()>;
+
+fn is_new_block(event: WalletEvent) -> std::future::Ready<Option<()>> {
+    std::future::ready(matches!(event, WalletEvent::NewBlock {}).then_some(()))
+}
+
+fn local_new_blocks(wallet_events: EventStream) -> NewBlocks {
+    futures::stream::unfold(wallet_events, |mut wallet_events| async move {
+        wallet_events.recv().await.map(|event| (event, wallet_events))
+    })
+    .filter_map(is_new_block)
+    .boxed()
+}
+
+/// Subscribe to the events of the remote wallet over a separate websocket connection,
+/// as the main connection to it only supports plain requests
+async fn remote_new_blocks(remote_socket_address: &str, rpc_auth: rpc::RpcAuthData) -> NewBlocks {
+    let subscription = async {
+        let ws_client =
+            rpc::new_ws_client(format!("ws://{remote_socket_address}"), rpc_auth).await?;
+        let wallet_events = WalletEventsRpcClient::subscribe_wallet_events(&ws_client).await?;
+        Ok::<_, rpc::ClientError>((ws_client, wallet_events))
+    };
+
+    match subscription.await {
+        // The client is kept alongside the subscription, which ends when the client is dropped
+        Ok(subscription) => {
+            futures::stream::unfold(subscription, |(ws_client, mut wallet_events)| async move {
+                wallet_events.next().await.map(|event| (event, (ws_client, wallet_events)))
+            })
+            .filter_map(|event| async move {
+                match event {
+                    Ok(event) => is_new_block(event).await,
+                    Err(_) => None,
+                }
+            })
+            .boxed()
+        }
+        Err(err) => {
+            logging::log::warn!(
+                "Failed to subscribe to the remote wallet events, balance alerts will not be checked: {err}"
+            );
+            futures::stream::pending().boxed()
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Event<N: NodeInterface> {
     HandleCommand {
@@ -50,6 +101,7 @@ pub async fn run<N: NodeInterface + Clone + Send + Sync + Debug + 'static>(
     chain_config: &Arc<ChainConfig>,
     mut event_rx: mpsc::UnboundedReceiver<Event<N>>,
     in_top_x_mb: usize,
+    tx_config: WalletTxConfig,
     wallet_type: WalletType<N>,
     cold_wallet: bool,
 ) -> Result<(), WalletCliError<N>> {
@@ -64,10 +116,19 @@ pub async fn run<N: NodeInterface + Clone + Send + Sync + Debug + 'static>(
                     .map_err(|err| WalletCliError::InvalidConfig(err.to_string()))?;
 
             let wallet_handle = wallet_service.handle();
+            let wallet_events = wallet_handle
+                .subscribe()
+                .await
+                .map_err(|err| WalletCliError::InvalidConfig(err.to_string()))?;
             let node_rpc = wallet_service.node_rpc().clone();
             let chain_config = wallet_service.chain_config().clone();
 
-            let wallet_rpc = WalletRpc::new(wallet_handle, node_rpc.clone(), chain_config.clone());
+            let wallet_rpc = WalletRpc::new(
+                wallet_handle,
+                node_rpc.clone(),
+                chain_config.clone(),
+                tx_config,
+            );
             let server_rpc = if let Some(rpc_config) = wallet_rpc_config {
                 let builder = rpc::Builder::new(rpc_config.bind_addr, rpc_config.auth_credentials)
                     .with_method_list("list_methods")
@@ -89,14 +150,10 @@ pub async fn run<N: NodeInterface + Clone + Send + Sync + Debug + 'static>(
             };
             let wallet = WalletRpcHandlesClient::new(wallet_rpc, server_rpc);
 
-            let mut command_handler = CommandHandler::new(
-                ControllerConfig {
-                    in_top_x_mb,
-                    broadcast_to_mempool: true,
-                },
-                wallet,
-            )
-            .await;
+            let mut command_handler =
+                CommandHandler::new(tx_config.controller_config(in_top_x_mb), wallet).await;
+
+            let mut new_blocks = local_new_blocks(wallet_events);
 
             loop {
                 tokio::select! {
@@ -108,6 +165,11 @@ pub async fn run<N: NodeInterface + Clone + Send + Sync + Debug + 'static>(
                             return Ok(());
                         }
                     }
+                    Some(()) = new_blocks.next() => {
+                        if let Err(err) = command_handler.check_balance_alerts::<N>(&chain_config).await {
+                            logging::log::error!("Failed to check the balance alerts: {err}");
+                        }
+                    }
                     _ = command_handler.rpc_completed() => {
                             return Ok(());
                     }
@@ -118,16 +180,17 @@ pub async fn run<N: NodeInterface + Clone + Send + Sync + Debug + 'static>(
             rpc_auth,
             remote_socket_address,
         } => {
-            let wallet = ClientWalletRpc::new(remote_socket_address, rpc_auth).await?;
+            let wallet =
+                ClientWalletRpc::new(remote_socket_address.clone(), rpc_auth.clone()).await?;
+            // A cold wallet doesn't provide any events and has no balance alerts to check
+            let mut new_blocks = if cold_wallet {
+                futures::stream::pending().boxed()
+            } else {
+                remote_new_blocks(&remote_socket_address, rpc_auth).await
+            };
 
-            let mut command_handler = CommandHandler::new(
-                ControllerConfig {
-                    in_top_x_mb,
-                    broadcast_to_mempool: true,
-                },
-                wallet,
-            )
-            .await;
+            let mut command_handler =
+                CommandHandler::new(tx_config.controller_config(in_top_x_mb), wallet).await;
 
             loop {
                 tokio::select! {
@@ -139,6 +202,11 @@ pub async fn run<N: NodeInterface + Clone + Send + Sync + Debug + 'static>(
                             return Ok(());
                         }
                     }
+                    Some(()) = new_blocks.next() => {
+                        if let Err(err) = command_handler.check_balance_alerts::<N>(chain_config).await {
+                            logging::log::error!("Failed to check the balance alerts: {err}");
+                        }
+                    }
                     _ = command_handler.rpc_completed() => {
                             return Ok(());
                     }
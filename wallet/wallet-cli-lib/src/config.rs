@@ -20,6 +20,7 @@ use common::chain::config::{regtest_options::ChainConfigOptions, ChainType};
 use crypto::key::hdkd::u31::U31;
 use utils::clap_utils;
 use utils_networking::NetworkAddressWithPort;
+use wallet_rpc_lib::cmdline::WalletTxArgs;
 
 #[derive(Subcommand, Clone, Debug)]
 pub enum Network {
@@ -132,6 +133,9 @@ pub struct CliArgs {
     #[arg(long, default_value_t = 5)]
     pub in_top_x_mb: usize,
 
+    #[clap(flatten)]
+    pub tx_options: WalletTxArgs,
+
     /// use the wallet without a connection to a node
     #[arg(long, conflicts_with_all(["node_rpc_address", "node_rpc_cookie_file", "node_rpc_username", "node_rpc_password"]))]
     pub cold_wallet: bool,
@@ -164,7 +168,7 @@ pub struct CliArgs {
     /// So, you can start an RPC daemon, and make this CLI connect to it to control it.
     /// This is useful for servers, where the RPC wallet can be left staking,
     /// and the wallet CLI is used to control its state.
-    #[arg(long, requires("remote_rpc_auth"), conflicts_with_all(["wallet_file", "wallet_rpc_password", "wallet_rpc_username", "wallet_rpc_cookie_file", "wallet_rpc_no_authentication", "wallet_rpc_bind_address", "node_rpc_address", "node_rpc_cookie_file", "node_rpc_username", "node_rpc_password"]))]
+    #[arg(long, requires("remote_rpc_auth"), conflicts_with_all(["wallet_file", "wallet_rpc_password", "wallet_rpc_username", "wallet_rpc_cookie_file", "wallet_rpc_no_authentication", "wallet_rpc_bind_address", "node_rpc_address", "node_rpc_cookie_file", "node_rpc_username", "node_rpc_password", "broadcast_max_attempts", "broadcast_retry_delay", "min_relay_fee_rate", "max_sync_lag"]))]
     pub remote_rpc_wallet_address: Option<String>,
 
     /// For a remote RPC wallet, this is the path to the RPC cookie file. If not set, the value is read from the default cookie file location.
@@ -38,7 +38,10 @@ use tokio::sync::mpsc;
 use utils::{cookie::COOKIE_FILENAME, default_data_dir::default_data_dir_for_chain, ensure};
 use wallet_cli_commands::{ManageableWalletCommand, WalletCommand, WalletManagementCommand};
 use wallet_rpc_lib::types::NodeInterface;
-use wallet_rpc_lib::{cmdline::make_wallet_config, config::WalletRpcConfig};
+use wallet_rpc_lib::{
+    cmdline::make_wallet_config,
+    config::{WalletRpcConfig, WalletTxConfig},
+};
 
 enum Mode {
     Interactive {
@@ -155,6 +158,7 @@ async fn start_hot_wallet(
         cli_args.node_rpc_address.clone().unwrap_or(default_addr)
     };
 
+    let tx_config = cli_args.tx_options.clone().into_config();
     let (repl_handle, wallet_rpc_config) =
         setup_events_and_repl(cli_args, mode, output, input, event_tx, chain_type)?;
 
@@ -163,6 +167,7 @@ async fn start_hot_wallet(
         &chain_config.clone(),
         event_rx,
         in_top_x_mb,
+        tx_config,
         cli_event_loop::WalletType::Local {
             node_rpc,
             wallet_rpc_config,
@@ -183,6 +188,7 @@ async fn start_cold_wallet(
 ) -> Result<(), Box<dyn std::error::Error>> {
     let (event_tx, event_rx) = mpsc::unbounded_channel();
 
+    let tx_config = cli_args.tx_options.clone().into_config();
     let (repl_handle, wallet_rpc_config) = setup_events_and_repl(
         cli_args,
         mode,
@@ -196,6 +202,7 @@ async fn start_cold_wallet(
         &chain_config.clone(),
         event_rx,
         in_top_x_mb,
+        tx_config,
         cli_event_loop::WalletType::Local {
             node_rpc: make_cold_wallet_rpc_client(chain_config),
             wallet_rpc_config,
@@ -242,6 +249,8 @@ async fn connect_to_rpc_wallet(
     };
 
     let remote_socket_address = cli_args.remote_rpc_wallet_address.clone().expect("checked");
+    // The transactions are created and broadcast by the remote wallet according to its own options
+    let tx_config = WalletTxConfig::default();
     let (repl_handle, _wallet_rpc_config) =
         setup_events_and_repl(cli_args, mode, output, input, event_tx, chain_type)?;
 
@@ -249,6 +258,7 @@ async fn connect_to_rpc_wallet(
         &chain_config.clone(),
         event_rx,
         in_top_x_mb,
+        tx_config,
         cli_event_loop::WalletType::<ColdWalletClient>::Remote {
             remote_socket_address,
             rpc_auth,
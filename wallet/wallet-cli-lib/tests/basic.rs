@@ -15,10 +15,21 @@
 
 mod cli_test_framework;
 
+use std::num::NonZeroU8;
+
 use randomness::Rng;
 
-use common::{address::Address, chain::PoolId, primitives::H256};
+use common::{
+    address::Address,
+    chain::{
+        classic_multisig::ClassicMultisigChallenge, tokens::TokenIssuance, Destination, PoolId,
+        SignedTransaction, TxInput, TxOutput,
+    },
+    primitives::H256,
+};
+use crypto::key::{KeyKind, PrivateKey};
 use rstest::rstest;
+use serialization::hex::{HexDecode, HexEncode};
 use test_utils::random::{make_seedable_rng, Seed};
 
 use crate::cli_test_framework::CliTestFramework;
@@ -179,3 +190,314 @@ async fn produce_blocks_decommission_genesis_pool(#[case] seed: Seed) {
 
     test.shutdown().await;
 }
+
+#[rstest]
+#[case(test_utils::random::Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn sign_decommission_request(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let test = CliTestFramework::setup(&mut rng).await;
+
+    // the staker creates the decommission request for the genesis pool
+    test.create_genesis_wallet();
+    let address = test.exec("address-new");
+    let pool_id: PoolId = H256::zero().into();
+    let output = test.exec(&format!(
+        "staking-decommission-pool-request {} {address}",
+        Address::new(&test.chain_config, pool_id).unwrap(),
+    ));
+    let req = output.lines().nth(2).unwrap().to_owned();
+
+    // the staker doesn't have the decommission key
+    assert!(test
+        .exec(&format!("transaction-sign-request {req}"))
+        .starts_with("Not all transaction inputs have been signed"));
+    assert_eq!(test.exec("wallet-close"), "Successfully closed the wallet.");
+
+    // the wallet with the decommission key signs the request to completion
+    test.create_genesis_cold_wallet();
+    let output = test.exec(&format!("transaction-sign-request {req}"));
+    // the output is the same as the one of the plain signing command
+    let raw_output = test.exec(&format!("account-sign-raw-transaction {req}"));
+    for output in [output, raw_output] {
+        assert!(output.starts_with(
+            "The transaction has been fully signed and is ready to be broadcast to network"
+        ));
+        assert!(output.contains("node-submit-transaction"));
+    }
+
+    assert_eq!(test.exec("wallet-sync"), "Success");
+    let output = test.exec(&format!("transaction-sign-request {req} --broadcast"));
+    assert!(output.starts_with(
+        "The transaction has been fully signed and was submitted successfully with ID"
+    ));
+    assert!(output.contains("Transaction summary:"));
+    assert_eq!(test.exec("wallet-close"), "Successfully closed the wallet.");
+
+    test.shutdown().await;
+}
+
+#[rstest]
+#[case(test_utils::random::Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn sign_transaction_files(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let test = CliTestFramework::setup(&mut rng).await;
+
+    // Use dir name with spaces to make sure quoting works as expected
+    let tx_dir = test.test_root.fresh_test_dir("tx files");
+    let unsigned_tx_file = tx_dir.as_ref().join("unsigned_tx").to_str().unwrap().to_owned();
+    let signed_tx_file = tx_dir.as_ref().join("signed_tx").to_str().unwrap().to_owned();
+
+    // the online wallet exports the decommission request for the genesis pool
+    test.create_genesis_wallet();
+    let address = test.exec("address-new");
+    let pool_id: PoolId = H256::zero().into();
+    let output = test.exec(&format!(
+        "staking-decommission-pool-request {} {address}",
+        Address::new(&test.chain_config, pool_id).unwrap(),
+    ));
+    let req = output.lines().nth(2).unwrap().to_owned();
+    assert_eq!(
+        test.exec(&format!(
+            "transaction-export-to-file {req} \"{unsigned_tx_file}\""
+        )),
+        format!("The transaction was written to {unsigned_tx_file}")
+    );
+    assert_eq!(test.exec("wallet-close"), "Successfully closed the wallet.");
+
+    // the offline wallet with the decommission key signs it
+    test.create_genesis_cold_wallet();
+    assert!(test
+        .exec(&format!(
+            "transaction-sign-file \"{unsigned_tx_file}\" \"{}\"",
+            tx_dir.as_ref().join("missing").join("signed_tx").to_str().unwrap()
+        ))
+        .contains("Failed to access the transaction file"));
+    let output = test.exec(&format!(
+        "transaction-sign-file \"{unsigned_tx_file}\" \"{signed_tx_file}\""
+    ));
+    assert!(output.starts_with(
+        "The transaction has been fully signed and is ready to be broadcast to network"
+    ));
+    assert!(output.ends_with(&format!("The result was written to {signed_tx_file}")));
+    assert_eq!(test.exec("wallet-close"), "Successfully closed the wallet.");
+
+    // the online wallet broadcasts the signed transaction
+    test.create_genesis_wallet();
+    assert_eq!(test.exec("wallet-sync"), "Success");
+    assert!(test
+        .exec(&format!("transaction-submit-file \"{signed_tx_file}\""))
+        .starts_with("The transaction was submitted successfully with ID"));
+    assert_eq!(test.exec("wallet-close"), "Successfully closed the wallet.");
+
+    test.shutdown().await;
+}
+
+#[rstest]
+#[case(test_utils::random::Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn list_standalone_addresses(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let test = CliTestFramework::setup(&mut rng).await;
+
+    test.create_genesis_wallet();
+
+    let make_address =
+        |destination| Address::new(&test.chain_config, destination).unwrap().into_string();
+
+    let (private_key, private_key_pub_key) =
+        PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+    assert!(test
+        .exec(&format!(
+            "standalone-add-private-key-from-hex {} --label key_label --no-rescan true",
+            private_key.hex_encode()
+        ))
+        .starts_with("Success"));
+
+    let (_, watch_only_pub_key) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+    let watch_only_address = make_address(Destination::PublicKeyHash((&watch_only_pub_key).into()));
+    assert!(test
+        .exec(&format!(
+            "standalone-add-watch-only-address {watch_only_address} --label watch_label --no-rescan true"
+        ))
+        .starts_with("Success"));
+
+    let multisig_keys = (0..2)
+        .map(|_| {
+            let (_, pub_key) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+            make_address(Destination::PublicKey(pub_key))
+        })
+        .collect::<Vec<_>>();
+    let output = test.exec(&format!(
+        "standalone-add-multisig 1 {} --label multisig_label --no-rescan true",
+        multisig_keys.join(" ")
+    ));
+    let multisig_address = output.lines().nth(1).unwrap().to_owned();
+
+    let output = test.exec("account-list-standalone");
+    let expected_rows = [
+        (
+            make_address(Destination::PublicKeyHash((&private_key_pub_key).into())),
+            "From Private key",
+            "key_label",
+        ),
+        (
+            make_address(Destination::PublicKey(private_key_pub_key)),
+            "From Private key",
+            "key_label",
+        ),
+        (watch_only_address, "Watch Only", "watch_label"),
+        (multisig_address, "Multisig", "multisig_label"),
+    ];
+    for (address, address_type, label) in expected_rows {
+        let row = output.lines().find(|line| line.contains(&address)).unwrap();
+        assert!(row.contains(address_type));
+        assert!(row.contains(label));
+        assert!(row.contains("Coins: 0"));
+    }
+
+    test.shutdown().await;
+}
+
+#[rstest]
+#[case(test_utils::random::Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn rename_account(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let test = CliTestFramework::setup(&mut rng).await;
+
+    test.create_genesis_wallet();
+
+    // The status line is only shown once there are multiple accounts
+    assert_eq!(
+        test.exec("account-create"),
+        "Success, the new account index is: 1"
+    );
+
+    assert_eq!(
+        test.exec("account-rename 0 savings"),
+        "Success, the account name has been successfully renamed\nStatus: (Account savings)"
+    );
+    assert_eq!(
+        test.exec("account-rename 0"),
+        "Success, the account name has been successfully renamed\nStatus: (Account No. 0)"
+    );
+
+    // Renaming another account doesn't change the status of the selected one
+    assert_eq!(
+        test.exec("account-rename 1 spending"),
+        "Success, the account name has been successfully renamed\nStatus: (Account No. 0)"
+    );
+    assert_eq!(test.exec("account-select 1"), "Success");
+    assert_eq!(
+        test.exec("account-rename 1"),
+        "Success, the account name has been successfully renamed\nStatus: (Account No. 1)"
+    );
+
+    test.shutdown().await;
+}
+
+#[rstest]
+#[case(test_utils::random::Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn issue_token_with_multisig_authority(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let test = CliTestFramework::setup(&mut rng).await;
+
+    test.create_genesis_wallet();
+
+    let public_keys = (0..3)
+        .map(|_| PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr).1)
+        .collect::<Vec<_>>();
+    let output = test.exec(&format!(
+        "token-create-and-delegate-authority XXXX 2 http://uri unlimited freezable 2 {}",
+        public_keys.iter().map(|key| key.hex_encode()).collect::<Vec<_>>().join(" ")
+    ));
+    assert!(output.starts_with("A new token has been issued"));
+
+    let challenge =
+        ClassicMultisigChallenge::new(&test.chain_config, NonZeroU8::new(2).unwrap(), public_keys)
+            .unwrap();
+    let authority = Destination::ClassicMultisig((&challenge).into());
+    let authority_address = Address::new(&test.chain_config, authority.clone()).unwrap();
+    assert_eq!(
+        output.lines().nth(1).unwrap(),
+        format!("The token authority is the multisig address: {authority_address}")
+    );
+
+    // The issuance and the multisig authority are in the same transaction
+    let tx_id = output.lines().next().unwrap().rsplit(' ').next().unwrap();
+    let tx = test.exec(&format!("transaction-get-signed-raw {tx_id}"));
+    let tx = SignedTransaction::hex_decode_all(tx).unwrap();
+    assert!(tx.inputs().iter().all(|input| matches!(input, TxInput::Utxo(_))));
+    let issuances = tx
+        .outputs()
+        .iter()
+        .filter_map(|output| match output {
+            TxOutput::IssueFungibleToken(issuance) => Some(issuance.as_ref()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    match issuances.as_slice() {
+        [TokenIssuance::V1(issuance)] => assert_eq!(issuance.authority, authority),
+        _ => panic!("Unexpected token issuances: {issuances:?}"),
+    }
+
+    assert_eq!(test.exec("node-generate-blocks 1"), "Success");
+
+    test.shutdown().await;
+}
+
+#[rstest]
+#[case(test_utils::random::Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn list_owned_tokens(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let test = CliTestFramework::setup(&mut rng).await;
+
+    test.create_genesis_wallet();
+    let address = test.exec("address-new");
+
+    assert_eq!(test.exec("account-token-balances"), "");
+
+    let tokens = [("TKNA", 2), ("TKNB", 8)];
+    let mut expected_lines = Vec::new();
+    for (ticker, number_of_decimals) in tokens {
+        let output = test.exec(&format!(
+            "token-issue-new {ticker} {number_of_decimals} http://uri {address} unlimited not-freezable"
+        ));
+        let token_id = output
+            .strip_prefix("A new token has been issued with ID: ")
+            .and_then(|rest| rest.split_whitespace().next())
+            .unwrap()
+            .to_owned();
+        assert_eq!(test.exec("node-generate-blocks 1"), "Success");
+
+        let amount = rng.gen_range(1..1000);
+        assert!(test
+            .exec(&format!("token-mint {token_id} {address} {amount}"))
+            .starts_with("The transaction was submitted successfully with ID"));
+        assert_eq!(test.exec("node-generate-blocks 1"), "Success");
+
+        expected_lines.push(format!(
+            "Token: {token_id} ({ticker}) amount: {amount}, decimals: {number_of_decimals}, frozen: false"
+        ));
+    }
+    expected_lines.sort();
+
+    let output = test.exec("account-token-balances");
+    let mut lines = output.lines().map(str::to_owned).collect::<Vec<_>>();
+    lines.sort();
+    assert_eq!(lines, expected_lines);
+
+    assert_eq!(test.exec("wallet-close"), "Successfully closed the wallet.");
+
+    test.shutdown().await;
+}
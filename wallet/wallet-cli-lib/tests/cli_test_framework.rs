@@ -109,6 +109,7 @@ impl CliTestFramework {
                     exit_on_error: None,
                     vi_mode: false,
                     in_top_x_mb: 5,
+                    tx_options: Default::default(),
                     cold_wallet: false,
                     enable_wallet_rpc_interface: false,
                     wallet_rpc_bind_address: None,
@@ -138,6 +139,7 @@ impl CliTestFramework {
                 exit_on_error: None,
                 vi_mode: false,
                 in_top_x_mb: 5,
+                tx_options: Default::default(),
                 cold_wallet: false,
                 enable_wallet_rpc_interface: false,
                 wallet_rpc_bind_address: None,
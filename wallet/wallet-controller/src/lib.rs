@@ -37,24 +37,27 @@ use node_comm::rpc_client::ColdWalletClient;
 use std::{
     collections::{BTreeMap, BTreeSet},
     fs,
+    num::NonZeroU8,
     ops::Add,
     path::{Path, PathBuf},
     sync::Arc,
     time::Duration,
 };
 use types::{
-    Balances, GenericCurrencyTransferToTxOutputConversionError, InspectTransaction,
+    Balances, GenericCurrencyTransferToTxOutputConversionError, InspectTransaction, OwnedTokenInfo,
     SeedWithPassPhrase, SignatureStats, TransactionToInspect, ValidatedSignatures, WalletInfo,
 };
 
 use read::ReadOnlyController;
 use sync::InSync;
+pub use sync::{get_blocks_by_height_range, MAX_BLOCKS_PER_CALL};
 use synced_controller::SyncedController;
 
 use common::{
     address::{AddressError, RpcAddress},
     chain::{
         block::timestamp::BlockTimestamp,
+        classic_multisig::ClassicMultisigChallenge,
         htlc::HtlcSecret,
         partially_signed_transaction::PartiallySignedTransaction,
         signature::{inputsig::InputWitness, DestinationSigError, Transactable},
@@ -69,10 +72,15 @@ use common::{
     },
 };
 use consensus::{GenerateBlockInputData, PoSTimestampSearchInputData};
-use crypto::{ephemeral_e2e::EndToEndPrivateKey, key::hdkd::u31::U31};
+use crypto::{
+    ephemeral_e2e::EndToEndPrivateKey,
+    key::{hdkd::u31::U31, PublicKey},
+};
 use logging::log;
-use mempool::tx_accumulator::PackingStrategy;
-pub use node_comm::node_traits::{ConnectedPeer, NodeInterface, PeerId};
+use mempool::{tx_accumulator::PackingStrategy, FeeRate};
+pub use node_comm::node_traits::{
+    ConnectedPeer, NodeInterface, NodeInterfaceError, PeerId, PeerLimits,
+};
 pub use node_comm::{
     handles_client::WalletHandlesClient, make_cold_wallet_rpc_client, make_rpc_client,
     rpc_client::NodeRpcClient,
@@ -84,7 +92,7 @@ use wallet::{
         TransactionToSign,
     },
     destination_getters::{get_tx_output_destination, HtlcSpendingCondition},
-    wallet::WalletPoolsFilter,
+    wallet::{IntegrityViolation, WalletPoolsFilter},
     wallet_events::WalletEvents,
     DefaultWallet, WalletError, WalletResult,
 };
@@ -94,7 +102,7 @@ pub use wallet_types::{
 };
 use wallet_types::{
     seed_phrase::StoreSeedPhrase, signature_status::SignatureStatus, wallet_type::WalletType,
-    with_locked::WithLocked,
+    with_locked::WithLocked, AddressOrigin,
 };
 
 #[derive(thiserror::Error, Debug)]
@@ -103,6 +111,8 @@ pub enum ControllerError<T: NodeInterface> {
     NodeCallError(T::Error),
     #[error("Wallet sync error: {0}")]
     SyncError(String),
+    #[error("The node has pruned the blocks starting from height {0}, which the wallet needs to sync; a node that keeps all the blocks is required")]
+    NodeBlocksPruned(BlockHeight),
     #[error("Synchronization is paused until the node has {0} blocks ({1} blocks currently)")]
     NotEnoughBlockHeight(BlockHeight, BlockHeight),
     #[error("Wallet file {0} error: {1}")]
@@ -141,6 +151,8 @@ pub enum ControllerError<T: NodeInterface> {
     InvalidTxOutput(GenericCurrencyTransferToTxOutputConversionError),
     #[error("The specified token {0} is not a fungible token")]
     NotFungibleToken(TokenId),
+    #[error("The wallet is not synced with the node (wallet height: {0}, node height: {1})")]
+    WalletNotSynced(BlockHeight, BlockHeight),
 }
 
 #[derive(Clone, Copy)]
@@ -153,6 +165,39 @@ pub struct ControllerConfig {
     /// Should the controller broadcast the created transactions to the mempool
     /// Set to False by the GUI wallet to allow for a confirmation dialog before broadcasting
     pub broadcast_to_mempool: bool,
+
+    /// The minimum fee rate that will be used when creating transactions
+    /// Transactions paying less than the node's minimum relay fee rate are rejected by its mempool,
+    /// so if the estimated fee rate is lower, it's bumped up to this value
+    pub min_relay_fee_rate: Option<FeeRate>,
+
+    /// The maximum number of blocks the wallet can lag behind the node's tip when creating
+    /// transactions, as the wallet could otherwise spend outputs that have already been spent.
+    /// If set, a failed wallet sync is tolerated as long as the lag stays within the limit.
+    /// None requires the wallet to be fully synced instead
+    pub max_sync_lag: Option<u64>,
+
+    /// How to retry submitting a transaction to the node if the node can't be reached,
+    /// e.g. because it's restarting
+    pub broadcast_retry: BroadcastRetryConfig,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BroadcastRetryConfig {
+    /// The maximum number of attempts to submit a transaction, including the first one
+    pub max_attempts: u32,
+
+    /// The delay before the first retry, doubled after each failed attempt
+    pub base_delay: Duration,
+}
+
+impl Default for BroadcastRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_secs(1),
+        }
+    }
 }
 
 pub struct Controller<T, W> {
@@ -335,6 +380,28 @@ impl<T: NodeInterface + Clone + Send + Sync + 'static, W: WalletEvents> Controll
         Ok(wallet)
     }
 
+    /// Open an existing wallet file for reading only, without migrating or otherwise changing it.
+    /// The file can't be opened while it's in use by a read-write wallet, which locks it exclusively.
+    pub fn open_wallet_read_only(
+        chain_config: Arc<ChainConfig>,
+        file_path: impl AsRef<Path>,
+        wallet_type: WalletType,
+    ) -> Result<DefaultWallet, ControllerError<T>> {
+        utils::ensure!(
+            file_path.as_ref().exists(),
+            ControllerError::WalletFileError(
+                file_path.as_ref().to_owned(),
+                "File does not exist".to_owned()
+            )
+        );
+
+        let db = wallet::wallet::open_wallet_file_read_only(&file_path)
+            .map_err(ControllerError::WalletError)?;
+
+        wallet::Wallet::open_read_only(chain_config, db, wallet_type)
+            .map_err(ControllerError::WalletError)
+    }
+
     pub fn seed_phrase(&self) -> Result<Option<SeedWithPassPhrase>, ControllerError<T>> {
         self.wallet
             .seed_phrase()
@@ -420,6 +487,30 @@ impl<T: NodeInterface + Clone + Send + Sync + 'static, W: WalletEvents> Controll
         }
     }
 
+    /// Find the account that owns the given address and how the address came to be part of it
+    pub fn find_account_for_address(
+        &self,
+        address: Destination,
+    ) -> Result<Option<(U31, AddressOrigin)>, ControllerError<T>> {
+        self.wallet
+            .find_account_for_address(address)
+            .map_err(ControllerError::WalletError)
+    }
+
+    /// Get the transactions created by the wallet that are not confirmed yet
+    pub fn get_pending_transactions_to_be_broadcast(
+        &self,
+    ) -> Result<Vec<SignedTransaction>, ControllerError<T>> {
+        self.wallet
+            .get_pending_transactions_to_be_broadcast()
+            .map_err(ControllerError::WalletError)
+    }
+
+    /// Check the consistency of the wallet database
+    pub fn check_integrity(&self) -> Result<Vec<IntegrityViolation>, ControllerError<T>> {
+        self.wallet.check_integrity().map_err(ControllerError::WalletError)
+    }
+
     pub async fn get_token_number_of_decimals(
         &self,
         token_id: TokenId,
@@ -583,6 +674,39 @@ impl<T: NodeInterface + Clone + Send + Sync + 'static, W: WalletEvents> Controll
         self.wallet.create_next_account(name).map_err(ControllerError::WalletError)
     }
 
+    /// The public key the next created account contributes to a multisig account
+    pub fn next_multisig_account_public_key(&self) -> Result<PublicKey, ControllerError<T>> {
+        self.wallet
+            .get_next_multisig_account_public_key()
+            .map_err(ControllerError::WalletError)
+    }
+
+    /// Create a new M-of-N multisig account from the public keys of the other cosigners
+    pub fn create_multisig_account(
+        &mut self,
+        min_required_signatures: NonZeroU8,
+        cosigner_public_keys: Vec<PublicKey>,
+        name: Option<String>,
+    ) -> Result<(U31, ClassicMultisigChallenge), ControllerError<T>> {
+        self.wallet
+            .create_multisig_account(min_required_signatures, cosigner_public_keys, name)
+            .map_err(ControllerError::WalletError)
+    }
+
+    /// Create the accounts with the given indexes that don't exist yet, without requiring
+    /// the previous accounts to have a transaction history.
+    /// The new accounts are rescanned from the genesis block on the next sync.
+    /// Returns the indexes of all the created accounts, including the missing ones below
+    /// the given indexes.
+    pub fn recover_accounts(
+        &mut self,
+        account_indices: BTreeSet<U31>,
+    ) -> Result<Vec<U31>, ControllerError<T>> {
+        self.wallet
+            .recover_accounts(&account_indices)
+            .map_err(ControllerError::WalletError)
+    }
+
     pub fn update_account_name(
         &mut self,
         account_index: U31,
@@ -687,7 +811,15 @@ impl<T: NodeInterface + Clone + Send + Sync + 'static, W: WalletEvents> Controll
         account_index: U31,
         config: ControllerConfig,
     ) -> Result<SyncedController<T, W>, ControllerError<T>> {
-        self.sync_once().await?;
+        match config.max_sync_lag {
+            None => self.sync_once().await?,
+            // The lag is checked before creating any transaction
+            Some(_) => {
+                if let Err(err) = self.sync_once().await {
+                    log::warn!("Wallet sync failed, continuing within the allowed sync lag: {err}");
+                }
+            }
+        }
         Ok(SyncedController::new(
             &mut self.wallet,
             self.rpc_client.clone(),
@@ -1171,3 +1303,27 @@ pub async fn into_balances<T: NodeInterface>(
 
     Ok(Balances::new(coins, tasks.try_collect().await?))
 }
+
+pub async fn into_owned_tokens<T: NodeInterface>(
+    rpc_client: &T,
+    chain_config: &ChainConfig,
+    balances: BTreeMap<Currency, Amount>,
+) -> Result<Vec<OwnedTokenInfo>, ControllerError<T>> {
+    let token_balances: BTreeMap<TokenId, Amount> = balances
+        .into_iter()
+        .filter_map(|(currency, amount)| match currency {
+            Currency::Coin => None,
+            Currency::Token(token_id) => Some((token_id, amount)),
+        })
+        .collect();
+
+    let tasks: FuturesUnordered<_> = token_balances
+        .keys()
+        .map(|token_id| fetch_token_info(rpc_client, *token_id))
+        .collect();
+    let token_infos: Vec<_> = tasks.try_collect().await?;
+
+    let mut owned_tokens = OwnedTokenInfo::join(chain_config, &token_balances, &token_infos)?;
+    owned_tokens.sort_by(|a, b| a.token_id.cmp(&b.token_id));
+    Ok(owned_tokens)
+}
@@ -15,15 +15,18 @@
 
 //! Read operations for the wallet
 
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, num::NonZeroUsize, time::Duration};
 
 use common::{
     address::Address,
-    chain::{ChainConfig, DelegationId, Destination, PoolId, Transaction, TxOutput, UtxoOutPoint},
-    primitives::{id::WithId, Amount, Id},
+    chain::{
+        block::timestamp::BlockTimestamp, Block, ChainConfig, DelegationId, Destination,
+        OutPointSourceId, PoolId, Transaction, TxOutput, UtxoOutPoint,
+    },
+    primitives::{id::WithId, time::get_time, Amount, Id, H256},
 };
 use crypto::{
-    key::hdkd::{child_number::ChildNumber, u31::U31},
+    key::hdkd::{child_number::ChildNumber, derivation_path::DerivationPath, u31::U31},
     vrf::VRFPublicKey,
 };
 use futures::{stream::FuturesUnordered, FutureExt, TryStreamExt};
@@ -31,10 +34,11 @@ use node_comm::node_traits::NodeInterface;
 use utils::tap_log::TapLog;
 use wallet::{
     account::{
-        currency_grouper::Currency, transaction_list::TransactionList, DelegationData, PoolData,
-        TxInfo,
+        currency_grouper::Currency,
+        transaction_list::{DailyActivity, TransactionList, TxCursor},
+        DelegationData, PoolData, TxInfo,
     },
-    wallet::WalletPoolsFilter,
+    wallet::{TokenAuthority, WalletPoolsFilter},
     DefaultWallet,
 };
 use wallet_types::{
@@ -42,11 +46,11 @@ use wallet_types::{
     utxo_types::{UtxoStates, UtxoTypes},
     wallet_tx::TxData,
     with_locked::WithLocked,
-    KeychainUsageState,
+    KeychainUsageState, LookaheadStatus,
 };
 
 use crate::{
-    types::{AccountStandaloneKeyDetails, Balances, CreatedBlockInfo},
+    types::{AccountStandaloneKeyDetails, Balances, CreatedBlockInfo, OwnedTokenInfo},
     ControllerError,
 };
 
@@ -98,6 +102,15 @@ impl<'a, T: NodeInterface> ReadOnlyController<'a, T> {
         super::into_balances(&self.rpc_client, self.chain_config, balances).await
     }
 
+    pub async fn get_owned_tokens(
+        &self,
+        utxo_states: UtxoStates,
+        with_locked: WithLocked,
+    ) -> Result<Vec<OwnedTokenInfo>, ControllerError<T>> {
+        let balances = self.get_balance(utxo_states, with_locked)?;
+        super::into_owned_tokens(&self.rpc_client, self.chain_config, balances).await
+    }
+
     pub fn get_multisig_utxos(
         &self,
         utxo_types: UtxoTypes,
@@ -126,6 +139,21 @@ impl<'a, T: NodeInterface> ReadOnlyController<'a, T> {
             .map_err(ControllerError::WalletError)
     }
 
+    pub fn get_locked_utxos(
+        &self,
+        utxo_states: UtxoStates,
+    ) -> Result<Vec<(UtxoOutPoint, TxOutput, BlockTimestamp)>, ControllerError<T>> {
+        self.wallet
+            .get_locked_utxos(self.account_index, utxo_states)
+            .map(|utxos| {
+                utxos
+                    .into_iter()
+                    .map(|(outpoint, output, _, unlock_time)| (outpoint, output, unlock_time))
+                    .collect()
+            })
+            .map_err(ControllerError::WalletError)
+    }
+
     pub fn pending_transactions(&self) -> Result<Vec<WithId<&'a Transaction>>, ControllerError<T>> {
         self.wallet
             .pending_transactions(self.account_index)
@@ -152,6 +180,31 @@ impl<'a, T: NodeInterface> ReadOnlyController<'a, T> {
             .map_err(ControllerError::WalletError)
     }
 
+    pub fn get_transaction_list_from_cursor(
+        &self,
+        cursor: Option<TxCursor>,
+        count: NonZeroUsize,
+    ) -> Result<(Vec<TxData>, Option<TxCursor>), ControllerError<T>> {
+        self.wallet
+            .get_transaction_list_from_cursor(self.account_index, cursor, count)
+            .map_err(ControllerError::WalletError)
+    }
+
+    /// Per-day activity of the account for the given number of days up to the current time
+    pub fn get_activity_summary(
+        &self,
+        days: u32,
+    ) -> Result<Vec<DailyActivity>, ControllerError<T>> {
+        let period = Duration::from_secs(days as u64 * 24 * 60 * 60);
+        let from = (get_time() - period).map_or(
+            BlockTimestamp::from_int_seconds(0),
+            BlockTimestamp::from_time,
+        );
+        self.wallet
+            .get_account_activity_summary(self.account_index, from)
+            .map_err(ControllerError::WalletError)
+    }
+
     pub fn get_transaction(
         &self,
         transaction_id: Id<Transaction>,
@@ -169,6 +222,39 @@ impl<'a, T: NodeInterface> ReadOnlyController<'a, T> {
             .map_err(ControllerError::WalletError)
     }
 
+    pub fn account_fingerprint(&self) -> Result<H256, ControllerError<T>> {
+        self.wallet
+            .account_fingerprint(self.account_index)
+            .map_err(ControllerError::WalletError)
+    }
+
+    pub fn get_current_change_address(&self) -> Result<Address<Destination>, ControllerError<T>> {
+        self.wallet
+            .get_current_change_address(self.account_index)
+            .map_err(ControllerError::WalletError)
+    }
+
+    /// Get the derivation path of an address issued by the account's key chain
+    pub fn get_address_derivation_path(
+        &self,
+        address: &Destination,
+    ) -> Result<Option<DerivationPath>, ControllerError<T>> {
+        self.wallet
+            .address_derivation_path(self.account_index, address)
+            .map_err(ControllerError::WalletError)
+    }
+
+    /// Get the transactions and block rewards in the given blocks that would be recognised by
+    /// the account, without changing the wallet
+    pub fn simulate_scan(
+        &self,
+        blocks: &[Block],
+    ) -> Result<Vec<OutPointSourceId>, ControllerError<T>> {
+        self.wallet
+            .simulate_scan(self.account_index, blocks)
+            .map_err(ControllerError::WalletError)
+    }
+
     pub fn get_all_issued_vrf_public_keys(
         &self,
     ) -> Result<MapAddressWithUsage<VRFPublicKey>, ControllerError<T>> {
@@ -189,6 +275,12 @@ impl<'a, T: NodeInterface> ReadOnlyController<'a, T> {
             .map_err(ControllerError::WalletError)
     }
 
+    pub fn get_lookahead_status(&self) -> Result<LookaheadStatus, ControllerError<T>> {
+        self.wallet
+            .lookahead_status(self.account_index)
+            .map_err(ControllerError::WalletError)
+    }
+
     /// Get all addresses with usage information
     /// The boolean in the BTreeMap's value is true if the address is used, false is otherwise
     /// Note that the usage statistics follow strictly the rules of the wallet. For example,
@@ -334,6 +426,12 @@ impl<'a, T: NodeInterface> ReadOnlyController<'a, T> {
         Ok(delegations)
     }
 
+    pub fn get_token_authorities(&self) -> Result<Vec<TokenAuthority>, ControllerError<T>> {
+        self.wallet
+            .list_token_authorities(self.account_index)
+            .map_err(ControllerError::WalletError)
+    }
+
     pub fn get_created_blocks(&self) -> Result<Vec<CreatedBlockInfo>, ControllerError<T>> {
         self.wallet
             .get_created_blocks(self.account_index)
@@ -29,7 +29,10 @@ use wallet::{
 
 use crate::ControllerError;
 
-const MAX_FETCH_BLOCK_COUNT: usize = 100;
+/// The maximum number of blocks requested from the node in a single call
+pub const MAX_BLOCKS_PER_CALL: u32 = 100;
+
+const MAX_FETCH_BLOCK_COUNT: usize = MAX_BLOCKS_PER_CALL as usize;
 
 pub trait SyncingWallet {
     fn syncing_state(&self) -> WalletSyncingState;
@@ -100,6 +103,8 @@ enum FetchBlockError<T: NodeInterface> {
     NoNewBlocksFound,
     #[error("Invalid prev block id: {0}, expected: {1}")]
     InvalidPrevBlockId(Id<GenBlock>, Id<GenBlock>),
+    #[error("The node has pruned the block at height {0}")]
+    BlocksPruned(BlockHeight),
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -193,6 +198,7 @@ pub async fn sync_once<T: NodeInterface>(
                 &mut current,
                 next.0,
                 next.1,
+                chain_info.best_block_height,
                 rpc_client,
                 wallet,
                 wallet_events,
@@ -205,6 +211,7 @@ pub async fn sync_once<T: NodeInterface>(
         fetch_and_sync(
             &current,
             MAX_FETCH_BLOCK_COUNT,
+            chain_info.best_block_height,
             rpc_client,
             wallet,
             wallet_events,
@@ -217,6 +224,7 @@ async fn fetch_and_sync_to_next_group<T: NodeInterface>(
     current: &mut (NextBlockInfo, Vec<AccountType>),
     next_group_block_info: NextBlockInfo,
     mut next_group_accounts: Vec<AccountType>,
+    tip_height: BlockHeight,
     rpc_client: &T,
     wallet: &mut impl SyncingWallet,
     wallet_events: &impl WalletEvents,
@@ -224,7 +232,15 @@ async fn fetch_and_sync_to_next_group<T: NodeInterface>(
     let block_to_fetch = (next_group_block_info.common_block_height - current.0.common_block_height)
         .expect("already sorted")
         .to_int() as usize;
-    fetch_and_sync(&*current, block_to_fetch, rpc_client, wallet, wallet_events).await?;
+    fetch_and_sync(
+        &*current,
+        block_to_fetch,
+        tip_height,
+        rpc_client,
+        wallet,
+        wallet_events,
+    )
+    .await?;
 
     // once the current group accounts are synced up to the next group join them
     next_group_accounts.append(&mut current.1);
@@ -234,6 +250,7 @@ async fn fetch_and_sync_to_next_group<T: NodeInterface>(
 async fn fetch_and_sync<T: NodeInterface>(
     accounts: &(NextBlockInfo, Vec<AccountType>),
     block_to_fetch: usize,
+    tip_height: BlockHeight,
     rpc_client: &T,
     wallet: &mut impl SyncingWallet,
     wallet_events: &impl WalletEvents,
@@ -243,7 +260,10 @@ async fn fetch_and_sync<T: NodeInterface>(
         common_block_height,
     } = fetch_next_blocks(&accounts.0, block_to_fetch, rpc_client)
         .await
-        .map_err(|e| ControllerError::SyncError(e.to_string()))?;
+        .map_err(|e| match e {
+            FetchBlockError::BlocksPruned(height) => ControllerError::NodeBlocksPruned(height),
+            e => ControllerError::SyncError(e.to_string()),
+        })?;
     let block_id = blocks.last().expect("blocks must not be empty").header().block_id();
     let new_height = common_block_height.into_int() + blocks.len() as u64;
     for account in accounts.1.iter() {
@@ -257,6 +277,7 @@ async fn fetch_and_sync<T: NodeInterface>(
             wallet_events,
         )?;
     }
+    wallet_events.rescan_progress(BlockHeight::new(new_height), tip_height);
 
     Ok(())
 }
@@ -298,15 +319,36 @@ fn scan_new_blocks<T: NodeInterface>(
     Ok(())
 }
 
+/// Fetch the mainchain blocks starting at height `from` in a single call to the node.
+/// The number of blocks is capped at `MAX_BLOCKS_PER_CALL`, and fewer blocks are returned
+/// if the node's best block is reached.
+pub async fn get_blocks_by_height_range<T: NodeInterface>(
+    rpc_client: &T,
+    from: BlockHeight,
+    count: u32,
+) -> Result<Vec<Block>, T::Error> {
+    let count = std::cmp::min(count, MAX_BLOCKS_PER_CALL);
+    rpc_client.get_mainchain_blocks(from, count as usize).await
+}
+
 async fn fetch_next_blocks<T: NodeInterface>(
     current: &NextBlockInfo,
     block_to_fetch: usize,
     rpc_client: &T,
 ) -> Result<FetchedBlocks, FetchBlockError<T>> {
-    let blocks = rpc_client
-        .get_mainchain_blocks(current.common_block_height.next_height(), block_to_fetch)
-        .await
-        .map_err(FetchBlockError::UnexpectedRpcError)?;
+    let from = current.common_block_height.next_height();
+    let blocks = match rpc_client.get_mainchain_blocks(from, block_to_fetch).await {
+        Ok(blocks) => blocks,
+        Err(err) => {
+            // Blocks are pruned from the bottom up, so it's enough to check the first one
+            let is_pruned = is_block_body_pruned(rpc_client, from).await.unwrap_or(false);
+            return Err(if is_pruned {
+                FetchBlockError::BlocksPruned(from)
+            } else {
+                FetchBlockError::UnexpectedRpcError(err)
+            });
+        }
+    };
     match blocks.first() {
         Some(block) => utils::ensure!(
             *block.header().prev_block_id() == current.common_block_id,
@@ -324,6 +366,20 @@ async fn fetch_next_blocks<T: NodeInterface>(
     })
 }
 
+/// Whether the node has the mainchain block at the given height, but not its body.
+async fn is_block_body_pruned<T: NodeInterface>(
+    rpc_client: &T,
+    height: BlockHeight,
+) -> Result<bool, T::Error> {
+    let block_id = match rpc_client.get_block_id_at_height(height).await? {
+        Some(block_id) => block_id,
+        None => return Ok(false),
+    };
+    // Note: the genesis is never at a non-zero height
+    let block = rpc_client.get_block(Id::new(block_id.to_hash())).await?;
+    Ok(block.is_none())
+}
+
 /// Group the accounts by the latest fork block from the mainchain.
 /// Meaning: If the account's best block is now not in the mainchain,
 /// this function will return the latest ancestor that's in the mainchain.
@@ -406,4 +462,4 @@ async fn get_common_block_info<T: NodeInterface>(
 }
 
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
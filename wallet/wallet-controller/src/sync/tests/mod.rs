@@ -15,38 +15,45 @@
 
 use std::{
     num::NonZeroUsize,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
     time::Duration,
 };
 
 use blockprod::TimestampSearchData;
-use chainstate::ChainInfo;
-use chainstate_test_framework::TestFramework;
+use chainstate::{ChainFork, ChainInfo};
+use chainstate_test_framework::{TestFramework, TransactionBuilder};
 use common::{
     chain::{
+        output_value::OutputValue,
+        signature::inputsig::InputWitness,
         tokens::{RPCTokenInfo, TokenId},
-        DelegationId, PoolId, SignedTransaction, Transaction,
+        DelegationId, Destination, OutPointSourceId, PoolId, SignedTransaction, Transaction,
+        TxOutput, UtxoOutPoint,
     },
-    primitives::{time::Time, Amount},
+    primitives::{time::Time, Amount, Idable},
 };
 use consensus::GenerateBlockInputData;
 use crypto::ephemeral_e2e::EndToEndPublicKey;
 use futures::executor::block_on;
 use logging::log;
-use mempool::{tx_accumulator::PackingStrategy, FeeRate};
+use mempool::{tx_accumulator::PackingStrategy, FeeRate, MempoolTxInfo};
 use mempool_types::tx_options::TxOptionsOverrides;
 use node_comm::{
-    node_traits::{ConnectedPeer, PeerId},
+    node_traits::{ConnectedPeer, PeerId, PeerLimits},
     rpc_client::NodeRpcError,
 };
 use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress};
 use randomness::{seq::IteratorRandom, CryptoRng, Rng};
+use rpc::ClientError;
 use rstest::rstest;
 use test_utils::random::{make_seedable_rng, Seed};
 use tokio::sync::mpsc;
 use utils_networking::IpOrSocketAddress;
 use wallet::wallet_events::WalletEventsNoOp;
-use wallet_types::{account_info::DEFAULT_ACCOUNT_INDEX, wallet_type::WalletType};
+use wallet_types::{account_info::DEFAULT_ACCOUNT_INDEX, wallet_type::WalletType, WalletTx};
 
 use super::*;
 
@@ -187,14 +194,57 @@ impl SyncingWallet for MockWallet {
 }
 
 #[derive(Clone)]
-struct MockNode {
+pub(crate) struct MockNode {
     tf: Arc<Mutex<TestFramework>>,
+    /// The number of the next transaction submissions that fail because the node can't be reached
+    pub(crate) submit_failures: Arc<AtomicUsize>,
+    /// Whether the transaction submissions are rejected by the node
+    pub(crate) submit_rejected: Arc<AtomicBool>,
+    /// Whether the node fails to return the mainchain blocks, so the wallet can't sync
+    pub(crate) blocks_unavailable: Arc<AtomicBool>,
+    pub(crate) submit_calls: Arc<AtomicUsize>,
+    pub(crate) mempool: Arc<Mutex<Vec<SignedTransaction>>>,
 }
 
 impl MockNode {
-    fn new(rng: &mut (impl Rng + CryptoRng)) -> Self {
+    pub(crate) fn new(rng: &mut (impl Rng + CryptoRng)) -> Self {
         let tf = Arc::new(Mutex::new(TestFramework::builder(rng).build()));
-        Self { tf }
+        Self {
+            tf,
+            submit_failures: Arc::new(AtomicUsize::new(0)),
+            submit_rejected: Arc::new(AtomicBool::new(false)),
+            blocks_unavailable: Arc::new(AtomicBool::new(false)),
+            submit_calls: Arc::new(AtomicUsize::new(0)),
+            mempool: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub(crate) fn chain_config(&self) -> Arc<ChainConfig> {
+        Arc::clone(self.tf.lock().unwrap().chain_config())
+    }
+
+    /// Add a block with a transaction that sends the given amount from the genesis premine
+    /// to the destination; can only be used once, as the whole premine output is spent
+    pub(crate) fn fund_destination(
+        &self,
+        rng: &mut (impl Rng + CryptoRng),
+        destination: Destination,
+        amount: Amount,
+    ) {
+        let mut tf = self.tf.lock().unwrap();
+        let genesis_outpoint = UtxoOutPoint::new(tf.chain_config().genesis_block_id().into(), 0);
+        let tx = TransactionBuilder::new()
+            .add_input(genesis_outpoint.into(), InputWitness::NoSignature(None))
+            .add_output(TxOutput::Transfer(OutputValue::Coin(amount), destination))
+            .build();
+        tf.make_block_builder().add_transaction(tx).build_and_process(rng).unwrap();
+    }
+
+    pub(crate) fn add_empty_blocks(&self, rng: &mut (impl Rng + CryptoRng), count: usize) {
+        let mut tf = self.tf.lock().unwrap();
+        for _ in 0..count {
+            tf.make_block_builder().build_and_process(rng).unwrap();
+        }
     }
 }
 
@@ -220,6 +270,9 @@ impl NodeInterface for MockNode {
         from: BlockHeight,
         max_count: usize,
     ) -> Result<Vec<Block>, Self::Error> {
+        if self.blocks_unavailable.load(Ordering::SeqCst) {
+            return Err(NodeRpcError::ResponseError(ClientError::RequestTimeout));
+        }
         Ok(self
             .tf
             .lock()
@@ -243,7 +296,7 @@ impl NodeInterface for MockNode {
             .unwrap())
     }
     async fn get_best_block_height(&self) -> Result<BlockHeight, Self::Error> {
-        unreachable!()
+        Ok(self.tf.lock().unwrap().best_block_index().block_height())
     }
     async fn get_block_id_at_height(
         &self,
@@ -264,6 +317,10 @@ impl NodeInterface for MockNode {
             .last_common_ancestor_by_id(&first_block, &second_block)
             .unwrap())
     }
+
+    async fn get_chain_forks(&self) -> Result<Vec<ChainFork>, Self::Error> {
+        unreachable!()
+    }
     async fn get_stake_pool_balance(
         &self,
         _pool_id: PoolId,
@@ -275,6 +332,13 @@ impl NodeInterface for MockNode {
         unreachable!()
     }
 
+    async fn get_stake_pool_delegations_count(
+        &self,
+        _pool_id: PoolId,
+    ) -> Result<Option<usize>, Self::Error> {
+        unreachable!()
+    }
+
     async fn get_delegation_share(
         &self,
         _pool_id: PoolId,
@@ -318,9 +382,10 @@ impl NodeInterface for MockNode {
 
     async fn get_utxo(
         &self,
-        _outpoint: common::chain::UtxoOutPoint,
+        outpoint: common::chain::UtxoOutPoint,
     ) -> Result<Option<common::chain::TxOutput>, Self::Error> {
-        unreachable!()
+        let utxo = self.tf.lock().unwrap().chainstate.utxo(&outpoint).unwrap();
+        Ok(utxo.map(|utxo| utxo.output().clone()))
     }
 
     async fn generate_block(
@@ -337,10 +402,24 @@ impl NodeInterface for MockNode {
     }
     async fn submit_transaction(
         &self,
-        _tx: SignedTransaction,
+        tx: SignedTransaction,
         _options: TxOptionsOverrides,
     ) -> Result<(), Self::Error> {
-        unreachable!()
+        self.submit_calls.fetch_add(1, Ordering::SeqCst);
+        let failed = self
+            .submit_failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok();
+        if failed {
+            return Err(NodeRpcError::ResponseError(ClientError::RequestTimeout));
+        }
+        if self.submit_rejected.load(Ordering::SeqCst) {
+            return Err(NodeRpcError::ResponseError(ClientError::Custom(
+                "Transaction rejected".to_owned(),
+            )));
+        }
+        self.mempool.lock().unwrap().push(tx);
+        Ok(())
     }
 
     async fn node_shutdown(&self) -> Result<(), Self::Error> {
@@ -393,6 +472,12 @@ impl NodeInterface for MockNode {
     ) -> Result<(), Self::Error> {
         unreachable!()
     }
+    async fn p2p_get_peer_limits(&self) -> Result<PeerLimits, Self::Error> {
+        unreachable!()
+    }
+    async fn p2p_set_peer_limits(&self, _limits: PeerLimits) -> Result<(), Self::Error> {
+        unreachable!()
+    }
 
     async fn mempool_get_fee_rate(&self, _in_top_x_mb: usize) -> Result<FeeRate, Self::Error> {
         Ok(FeeRate::from_amount_per_kb(Amount::ZERO))
@@ -404,6 +489,18 @@ impl NodeInterface for MockNode {
             FeeRate::from_amount_per_kb(Amount::from_atoms(1)),
         )])
     }
+
+    async fn mempool_get_transaction(
+        &self,
+        tx_id: Id<Transaction>,
+    ) -> Result<Option<SignedTransaction>, Self::Error> {
+        let mempool = self.mempool.lock().unwrap();
+        Ok(mempool.iter().find(|tx| tx.transaction().get_id() == tx_id).cloned())
+    }
+
+    async fn mempool_get_all_tx_info(&self) -> Result<Vec<MempoolTxInfo>, Self::Error> {
+        unreachable!()
+    }
 }
 
 fn create_chain(node: &MockNode, rng: &mut (impl Rng + CryptoRng), parent: u64, count: usize) {
@@ -592,3 +689,99 @@ async fn account_out_of_sync(#[case] seed: Seed) {
         }
     }
 }
+
+#[derive(Default)]
+struct ProgressRecordingEvents {
+    progress: Mutex<Vec<(BlockHeight, BlockHeight)>>,
+}
+
+impl WalletEvents for ProgressRecordingEvents {
+    fn new_block(&self) {}
+    fn set_transaction(&self, _id: U31, _tx: &WalletTx) {}
+    fn del_transaction(&self, _id: U31, _source: OutPointSourceId) {}
+
+    fn rescan_progress(&self, current: BlockHeight, tip: BlockHeight) {
+        self.progress.lock().unwrap().push((current, tip));
+    }
+}
+
+#[rstest]
+#[trace]
+#[case(test_utils::random::Seed::from_entropy())]
+#[tokio::test]
+async fn rescan_progress(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let node = MockNode::new(&mut rng);
+    let chain_config = Arc::clone(node.tf.lock().unwrap().chainstate.get_chain_config());
+    let (new_tip_tx, _new_tip_rx) = mpsc::channel(100);
+    let mut wallet = MockWallet::new(&chain_config, new_tip_tx);
+
+    let check_progress = |events: &ProgressRecordingEvents, tip: BlockHeight| {
+        let progress = events.progress.lock().unwrap();
+        assert!(!progress.is_empty());
+        assert!(progress.iter().all(|(_, progress_tip)| *progress_tip == tip));
+        assert!(progress.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert_eq!(progress.last().unwrap().0, tip);
+    };
+
+    // Sync from genesis
+    let first_len = rng.gen_range(2..20);
+    create_chain(&node, &mut rng, 0, first_len);
+    let events = ProgressRecordingEvents::default();
+    sync_once(&chain_config, &node, &mut wallet, &events).await.unwrap();
+    check_progress(&events, BlockHeight::new(first_len as u64));
+
+    // The unused account is behind the other one, so they are synced in two steps
+    wallet.reset_unused_account_to_height(rng.gen_range(1..first_len));
+    let second_len = rng.gen_range(1..20);
+    create_chain(&node, &mut rng, first_len as u64, second_len);
+    let events = ProgressRecordingEvents::default();
+    sync_once(&chain_config, &node, &mut wallet, &events).await.unwrap();
+    check_progress(&events, BlockHeight::new((first_len + second_len) as u64));
+}
+
+#[rstest]
+#[trace]
+#[case(test_utils::random::Seed::from_entropy())]
+#[tokio::test]
+async fn blocks_by_height_range(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let node = MockNode::new(&mut rng);
+
+    let chain_len = MAX_BLOCKS_PER_CALL as u64 + rng.gen_range(10..50);
+    create_chain(&node, &mut rng, 0, chain_len as usize);
+
+    let check_contiguous = |from: u64, blocks: &[Block]| {
+        let tf = node.tf.lock().unwrap();
+        let mut prev_id =
+            tf.chainstate.get_block_id_from_height(&(from - 1).into()).unwrap().unwrap();
+        for (height, block) in (from..).zip(blocks) {
+            assert_eq!(*block.header().prev_block_id(), prev_id);
+            let expected_id =
+                tf.chainstate.get_block_id_from_height(&height.into()).unwrap().unwrap();
+            assert_eq!(expected_id, block.get_id().into());
+            prev_id = expected_id;
+        }
+    };
+
+    // a range within the chain returns exactly the requested blocks
+    let from = rng.gen_range(1..chain_len - 10);
+    let count = rng.gen_range(1..=std::cmp::min(chain_len - from, MAX_BLOCKS_PER_CALL as u64));
+    let blocks = get_blocks_by_height_range(&node, from.into(), count as u32).await.unwrap();
+    assert_eq!(blocks.len() as u64, count);
+    check_contiguous(from, &blocks);
+
+    // the count is clamped to the cap
+    let count = rng.gen_range(MAX_BLOCKS_PER_CALL + 1..=MAX_BLOCKS_PER_CALL * 10);
+    let blocks = get_blocks_by_height_range(&node, BlockHeight::new(1), count).await.unwrap();
+    assert_eq!(blocks.len(), MAX_BLOCKS_PER_CALL as usize);
+    check_contiguous(1, &blocks);
+
+    // the range stops at the best block
+    let from = chain_len - rng.gen_range(0..10);
+    let blocks = get_blocks_by_height_range(&node, from.into(), MAX_BLOCKS_PER_CALL)
+        .await
+        .unwrap();
+    assert_eq!(blocks.len() as u64, chain_len - from + 1);
+    check_contiguous(from, &blocks);
+}
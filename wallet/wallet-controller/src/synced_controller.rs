@@ -30,7 +30,7 @@ use common::{
         ChainConfig, DelegationId, Destination, PoolId, SignedTransaction, Transaction, TxOutput,
         UtxoOutPoint,
     },
-    primitives::{per_thousand::PerThousand, Amount, Id},
+    primitives::{per_thousand::PerThousand, Amount, BlockHeight, Id, Idable},
 };
 use crypto::{
     key::{
@@ -41,8 +41,8 @@ use crypto::{
 };
 use futures::{stream::FuturesUnordered, TryStreamExt};
 use logging::log;
-use mempool::FeeRate;
-use node_comm::node_traits::NodeInterface;
+use mempool::{tx_options::TxOptionsOverrides, FeeRate};
+use node_comm::node_traits::{NodeInterface, NodeInterfaceError};
 use utils::ensure;
 use wallet::{
     account::{
@@ -66,7 +66,7 @@ use wallet_types::{
 use crate::{
     into_balances,
     types::{Balances, GenericCurrencyTransfer},
-    ControllerConfig, ControllerError,
+    BroadcastRetryConfig, ControllerConfig, ControllerError,
 };
 
 pub struct SyncedController<'a, T, W> {
@@ -77,6 +77,9 @@ pub struct SyncedController<'a, T, W> {
     staking_started: &'a mut BTreeSet<U31>,
     account_index: U31,
     config: ControllerConfig,
+    /// If set, the created transactions are only marked as unconfirmed in the wallet and
+    /// it's up to the caller to submit them to the node.
+    defer_submission: bool,
 }
 
 impl<'a, T: NodeInterface, W: WalletEvents> SyncedController<'a, T, W> {
@@ -97,9 +100,18 @@ impl<'a, T: NodeInterface, W: WalletEvents> SyncedController<'a, T, W> {
             staking_started,
             account_index,
             config,
+            defer_submission: false,
         }
     }
 
+    /// Only mark the created transactions as unconfirmed in the wallet, leaving their submission
+    /// to the caller, e.g. so that it can be retried without blocking other wallet commands.
+    /// Since the outputs are marked as spent right away, other commands can't spend them again.
+    pub fn with_deferred_submission(mut self) -> Self {
+        self.defer_submission = true;
+        self
+    }
+
     pub async fn get_token_info(
         &self,
         token_id: TokenId,
@@ -259,6 +271,15 @@ impl<'a, T: NodeInterface, W: WalletEvents> SyncedController<'a, T, W> {
             .map_err(ControllerError::WalletError)
     }
 
+    pub fn new_vrf_keys(
+        &mut self,
+        count: u32,
+    ) -> Result<Vec<(ChildNumber, Address<VRFPublicKey>)>, ControllerError<T>> {
+        self.wallet
+            .get_n_vrf_keys(self.account_index, count)
+            .map_err(ControllerError::WalletError)
+    }
+
     pub async fn issue_new_token(
         &mut self,
         address: Address<Destination>,
@@ -319,6 +340,13 @@ impl<'a, T: NodeInterface, W: WalletEvents> SyncedController<'a, T, W> {
         amount: Amount,
         address: Address<Destination>,
     ) -> Result<SignedTransaction, ControllerError<T>> {
+        // fail fast, before any fee estimation or UTXO selection takes place
+        if let RPCTokenInfo::FungibleToken(token_info) = &token_info {
+            self.wallet
+                .can_mint(self.account_index, token_info, amount)
+                .map_err(ControllerError::WalletError)?;
+        }
+
         self.create_and_send_token_tx(
             &token_info,
             move |current_fee_rate: FeeRate,
@@ -585,6 +613,54 @@ impl<'a, T: NodeInterface, W: WalletEvents> SyncedController<'a, T, W> {
         .await
     }
 
+    /// Create a child transaction that spends an unconfirmed output of the parent transaction,
+    /// paying enough fee to bring both to the target fee rate, and broadcast it to the mempool.
+    ///
+    /// The fee paid by the parent is taken from the node's mempool if the parent is there,
+    /// as it may spend outputs that are not known to the wallet.
+    pub async fn create_cpfp_transaction(
+        &mut self,
+        parent_tx_id: Id<Transaction>,
+        target_fee_rate: FeeRate,
+    ) -> Result<SignedTransaction, ControllerError<T>> {
+        let parent_fee = self
+            .rpc_client
+            .mempool_get_all_tx_info()
+            .await
+            .map_err(ControllerError::NodeCallError)?
+            .into_iter()
+            .find(|info| info.tx_id == parent_tx_id)
+            .map(|info| info.fee_rate.compute_fee(info.size))
+            .transpose()
+            .map_err(|_| ControllerError::WalletError(WalletError::OutputAmountOverflow))?
+            .map(Amount::from);
+
+        let tx = self
+            .wallet
+            .create_cpfp_transaction(
+                self.account_index,
+                parent_tx_id,
+                parent_fee,
+                target_fee_rate,
+            )
+            .map_err(ControllerError::WalletError)?;
+
+        self.broadcast_to_mempool_if_needed(tx).await
+    }
+
+    /// Import a transaction that was created outside of this wallet into the account's history.
+    /// If `block_height` is set, the transaction is recorded as confirmed at that height,
+    /// otherwise it is recorded as pending.
+    pub fn import_external_transaction(
+        &mut self,
+        tx: SignedTransaction,
+        block_height: Option<BlockHeight>,
+    ) -> Result<(), ControllerError<T>> {
+        self.wallet
+            .import_external_transaction(self.account_index, tx, block_height, self.wallet_events)
+            .map_err(ControllerError::WalletError)
+    }
+
     /// Create a transaction that transfers all the coins from a delegation to the destination address
     /// and broadcast it to the mempool.
     pub async fn sweep_delegation(
@@ -945,6 +1021,33 @@ impl<'a, T: NodeInterface, W: WalletEvents> SyncedController<'a, T, W> {
         .await
     }
 
+    /// Send the whole confirmed and unlocked balance of the token to the address
+    pub async fn send_all_tokens_to_address(
+        &mut self,
+        token_info: RPCTokenInfo,
+        address: Address<Destination>,
+    ) -> Result<SignedTransaction, ControllerError<T>> {
+        let token_id = token_info.token_id();
+        self.create_and_send_token_tx(
+            &token_info,
+            move |current_fee_rate: FeeRate,
+                  consolidate_fee_rate: FeeRate,
+                  wallet: &mut DefaultWallet,
+                  account_index: U31,
+                  token_info: &UnconfirmedTokenInfo| {
+                token_info.check_can_be_used()?;
+                wallet.create_transaction_to_send_all_tokens(
+                    account_index,
+                    token_id,
+                    address,
+                    current_fee_rate,
+                    consolidate_fee_rate,
+                )
+            },
+        )
+        .await
+    }
+
     /// Creates a transaction that creates a new stake pool and broadcasts it to the mempool.
     pub async fn create_stake_pool_tx(
         &mut self,
@@ -952,6 +1055,7 @@ impl<'a, T: NodeInterface, W: WalletEvents> SyncedController<'a, T, W> {
         decommission_key: Destination,
         margin_ratio_per_thousand: PerThousand,
         cost_per_block: Amount,
+        vrf_public_key: Option<VRFPublicKey>,
     ) -> Result<SignedTransaction, ControllerError<T>> {
         self.create_and_send_tx(
             move |current_fee_rate: FeeRate,
@@ -967,6 +1071,7 @@ impl<'a, T: NodeInterface, W: WalletEvents> SyncedController<'a, T, W> {
                         margin_ratio_per_thousand,
                         cost_per_block,
                         decommission_key,
+                        vrf_public_key,
                     },
                 )
             },
@@ -1100,14 +1205,41 @@ impl<'a, T: NodeInterface, W: WalletEvents> SyncedController<'a, T, W> {
             .map_err(ControllerError::WalletError)
     }
 
+    /// Make sure the wallet doesn't lag behind the node by more than allowed by the config
+    async fn ensure_synced_with_node(&self) -> Result<(), ControllerError<T>> {
+        let max_sync_lag = match self.config.max_sync_lag {
+            Some(max_sync_lag) => max_sync_lag,
+            None => return Ok(()),
+        };
+
+        let (_, wallet_height) = self
+            .wallet
+            .get_best_block_for_account(self.account_index)
+            .map_err(ControllerError::WalletError)?;
+        let node_height = self
+            .rpc_client
+            .get_best_block_height()
+            .await
+            .map_err(ControllerError::NodeCallError)?;
+
+        check_sync_lag(wallet_height, node_height, max_sync_lag)
+    }
+
     async fn get_current_and_consolidation_fee_rate(
         &mut self,
     ) -> Result<(mempool::FeeRate, mempool::FeeRate), ControllerError<T>> {
+        // All the transactions are created after getting the fee rates
+        self.ensure_synced_with_node().await?;
+
         let current_fee_rate = self
             .rpc_client
             .mempool_get_fee_rate(self.config.in_top_x_mb)
             .await
             .map_err(ControllerError::NodeCallError)?;
+        let current_fee_rate =
+            self.config.min_relay_fee_rate.map_or(current_fee_rate, |min_fee_rate| {
+                std::cmp::max(current_fee_rate, min_fee_rate)
+            });
         let consolidate_fee_rate = current_fee_rate;
         Ok((current_fee_rate, consolidate_fee_rate))
     }
@@ -1122,10 +1254,14 @@ impl<'a, T: NodeInterface, W: WalletEvents> SyncedController<'a, T, W> {
             .add_account_unconfirmed_tx(self.account_index, tx.clone(), self.wallet_events)
             .map_err(ControllerError::WalletError)?;
 
-        self.rpc_client
-            .submit_transaction(tx.clone(), Default::default())
-            .await
-            .map_err(ControllerError::NodeCallError)?;
+        submit_transaction_with_retry(
+            &self.rpc_client,
+            tx.clone(),
+            Default::default(),
+            self.config.broadcast_retry,
+        )
+        .await
+        .map_err(ControllerError::NodeCallError)?;
 
         Ok(tx)
     }
@@ -1136,10 +1272,15 @@ impl<'a, T: NodeInterface, W: WalletEvents> SyncedController<'a, T, W> {
         &mut self,
         tx: SignedTransaction,
     ) -> Result<SignedTransaction, ControllerError<T>> {
-        if self.config.broadcast_to_mempool {
-            self.broadcast_to_mempool(tx).await
-        } else {
+        if !self.config.broadcast_to_mempool {
             Ok(tx)
+        } else if self.defer_submission {
+            self.wallet
+                .add_account_unconfirmed_tx(self.account_index, tx.clone(), self.wallet_events)
+                .map_err(ControllerError::WalletError)?;
+            Ok(tx)
+        } else {
+            self.broadcast_to_mempool(tx).await
         }
     }
 
@@ -1241,3 +1382,353 @@ impl<'a, T: NodeInterface, W: WalletEvents> SyncedController<'a, T, W> {
         )))
     }
 }
+
+fn check_sync_lag<T: NodeInterface>(
+    wallet_height: BlockHeight,
+    node_height: BlockHeight,
+    max_sync_lag: u64,
+) -> Result<(), ControllerError<T>> {
+    let sync_lag = node_height.into_int().saturating_sub(wallet_height.into_int());
+    ensure!(
+        sync_lag <= max_sync_lag,
+        ControllerError::WalletNotSynced(wallet_height, node_height)
+    );
+    Ok(())
+}
+
+/// Submit a transaction to the node, retrying with an exponential backoff if the node can't be reached.
+/// Errors returned by the node itself, e.g. the transaction being rejected by the mempool,
+/// are not retried. The retries stop early if the node turns out to already have the transaction,
+/// e.g. when a previous attempt succeeded but its response was lost.
+pub async fn submit_transaction_with_retry<T: NodeInterface>(
+    rpc_client: &T,
+    tx: SignedTransaction,
+    options: TxOptionsOverrides,
+    retry_config: BroadcastRetryConfig,
+) -> Result<(), T::Error> {
+    let tx_id = tx.transaction().get_id();
+    let mut delay = retry_config.base_delay;
+    let mut attempt = 1;
+
+    loop {
+        let error = match rpc_client.submit_transaction(tx.clone(), options.clone()).await {
+            Ok(()) => return Ok(()),
+            Err(error) => error,
+        };
+
+        if !error.is_connection_error() || attempt >= retry_config.max_attempts {
+            return Err(error);
+        }
+
+        log::warn!(
+            "Submitting tx {tx_id} failed (attempt {attempt}), retrying in {delay:?}: {error}"
+        );
+        tokio::time::sleep(delay).await;
+
+        if is_tx_known_to_node(rpc_client, &tx).await {
+            log::info!("Tx {tx_id} is already known to the node, not retrying");
+            return Ok(());
+        }
+
+        delay = delay.saturating_mul(2);
+        attempt += 1;
+    }
+}
+
+/// Check if the transaction is in the node's mempool or has been confirmed,
+/// in which case its first output would be in the UTXO set
+async fn is_tx_known_to_node<T: NodeInterface>(rpc_client: &T, tx: &SignedTransaction) -> bool {
+    let tx_id = tx.transaction().get_id();
+    if let Ok(Some(_)) = rpc_client.mempool_get_transaction(tx_id).await {
+        return true;
+    }
+
+    if tx.outputs().is_empty() {
+        return false;
+    }
+    let outpoint = UtxoOutPoint::new(tx_id.into(), 0);
+    matches!(rpc_client.get_utxo(outpoint).await, Ok(Some(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{atomic::Ordering, Arc},
+        time::Duration,
+    };
+
+    use crypto::key::{KeyKind, PrivateKey};
+    use node_comm::rpc_client::ColdWalletClient;
+    use randomness::{CryptoRng, Rng};
+    use rstest::rstest;
+    use test_utils::random::{make_seedable_rng, Seed};
+    use wallet::{wallet::create_wallet_in_memory, wallet_events::WalletEventsNoOp};
+    use wallet_types::{
+        account_info::DEFAULT_ACCOUNT_INDEX, seed_phrase::StoreSeedPhrase, wallet_type::WalletType,
+    };
+
+    use crate::{sync::tests::MockNode, Controller};
+
+    use super::*;
+
+    const MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn make_tx(rng: &mut impl Rng) -> SignedTransaction {
+        let tx = Transaction::new(rng.gen(), vec![], vec![]).unwrap();
+        SignedTransaction::new(tx, vec![]).unwrap()
+    }
+
+    const RETRY_CONFIG: BroadcastRetryConfig = BroadcastRetryConfig {
+        max_attempts: 3,
+        base_delay: Duration::from_millis(1),
+    };
+
+    fn controller_config() -> ControllerConfig {
+        ControllerConfig {
+            in_top_x_mb: 5,
+            broadcast_to_mempool: true,
+            min_relay_fee_rate: None,
+            max_sync_lag: None,
+            broadcast_retry: RETRY_CONFIG,
+        }
+    }
+
+    /// Create a controller for a hot wallet that receives the given amount in a block on the node
+    async fn create_funded_controller(
+        rng: &mut (impl Rng + CryptoRng),
+        node: &MockNode,
+        amount: Amount,
+    ) -> Controller<MockNode, WalletEventsNoOp> {
+        let chain_config = node.chain_config();
+        let mut wallet = DefaultWallet::create_new_wallet(
+            Arc::clone(&chain_config),
+            create_wallet_in_memory().unwrap(),
+            MNEMONIC,
+            None,
+            StoreSeedPhrase::DoNotStore,
+            (BlockHeight::zero(), chain_config.genesis_block_id()),
+            WalletType::Hot,
+        )
+        .unwrap();
+
+        let address = wallet.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap().1;
+        node.fund_destination(rng, address.into_object(), amount);
+
+        Controller::new(chain_config, node.clone(), wallet, WalletEventsNoOp)
+            .await
+            .unwrap()
+    }
+
+    fn random_address(
+        rng: &mut (impl Rng + CryptoRng),
+        chain_config: &ChainConfig,
+    ) -> Address<Destination> {
+        let (_, public_key) = PrivateKey::new_from_rng(rng, KeyKind::Secp256k1Schnorr);
+        Address::new(chain_config, Destination::PublicKey(public_key)).unwrap()
+    }
+
+    fn coin_outputs_sum(tx: &SignedTransaction) -> Amount {
+        tx.outputs()
+            .iter()
+            .map(|output| match output {
+                TxOutput::Transfer(OutputValue::Coin(amount), _) => *amount,
+                _ => Amount::ZERO,
+            })
+            .sum::<Option<Amount>>()
+            .unwrap()
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    #[tokio::test]
+    async fn submit_retry(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let node = MockNode::new(&mut rng);
+        let tx = make_tx(&mut rng);
+
+        // The node fails twice and then accepts the transaction
+        node.submit_failures.store(2, Ordering::SeqCst);
+        submit_transaction_with_retry(&node, tx.clone(), Default::default(), RETRY_CONFIG)
+            .await
+            .unwrap();
+        assert_eq!(node.submit_calls.load(Ordering::SeqCst), 3);
+        assert_eq!(*node.mempool.lock().unwrap(), vec![tx]);
+
+        // Giving up after the last attempt
+        let node = MockNode::new(&mut rng);
+        node.submit_failures.store(3, Ordering::SeqCst);
+        let result = submit_transaction_with_retry(
+            &node,
+            make_tx(&mut rng),
+            Default::default(),
+            RETRY_CONFIG,
+        )
+        .await;
+        assert!(result.is_err());
+        assert_eq!(node.submit_calls.load(Ordering::SeqCst), 3);
+        assert!(node.mempool.lock().unwrap().is_empty());
+
+        // The transaction being rejected by the node is not retried
+        let node = MockNode::new(&mut rng);
+        node.submit_rejected.store(true, Ordering::SeqCst);
+        let result = submit_transaction_with_retry(
+            &node,
+            make_tx(&mut rng),
+            Default::default(),
+            RETRY_CONFIG,
+        )
+        .await;
+        assert!(result.is_err());
+        assert_eq!(node.submit_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    #[tokio::test]
+    async fn submit_retry_tx_already_known(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let node = MockNode::new(&mut rng);
+        let tx = make_tx(&mut rng);
+
+        // The submission keeps failing, but the transaction has made it to the mempool anyway
+        node.submit_failures.store(usize::MAX, Ordering::SeqCst);
+        node.mempool.lock().unwrap().push(tx.clone());
+        submit_transaction_with_retry(&node, tx, Default::default(), RETRY_CONFIG)
+            .await
+            .unwrap();
+        assert_eq!(node.submit_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    #[tokio::test]
+    async fn fee_rate_bumped_to_min_relay_fee_rate(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let min_relay_fee_rate =
+            FeeRate::from_amount_per_kb(Amount::from_atoms(rng.gen_range(1_000..100_000)));
+
+        // The mock node estimates a zero fee rate, so only the configured minimum is paid
+        for min_relay_fee_rate in [None, Some(min_relay_fee_rate)] {
+            let node = MockNode::new(&mut rng);
+            let funds = Amount::from_atoms(rng.gen_range(100_000_000..1_000_000_000));
+            let mut controller = create_funded_controller(&mut rng, &node, funds).await;
+
+            let config = ControllerConfig {
+                min_relay_fee_rate,
+                ..controller_config()
+            };
+            let address = random_address(&mut rng, &node.chain_config());
+            let amount = Amount::from_atoms(rng.gen_range(1..funds.into_atoms() / 2));
+            let tx = controller
+                .synced_controller(DEFAULT_ACCOUNT_INDEX, config)
+                .await
+                .unwrap()
+                .send_to_address(address, amount, vec![])
+                .await
+                .unwrap();
+            assert_eq!(*node.mempool.lock().unwrap(), vec![tx.clone()]);
+
+            let paid_fee = (funds - coin_outputs_sum(&tx)).unwrap();
+            match min_relay_fee_rate {
+                None => assert_eq!(paid_fee, Amount::ZERO),
+                Some(min_relay_fee_rate) => {
+                    let tx_size = serialization::Encode::encoded_size(&tx);
+                    let min_fee: Amount = min_relay_fee_rate.compute_fee(tx_size).unwrap().into();
+                    assert!(paid_fee >= min_fee);
+                }
+            }
+        }
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    #[tokio::test]
+    async fn lagging_wallet_refuses_to_send(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let node = MockNode::new(&mut rng);
+        let funds = Amount::from_atoms(rng.gen_range(100_000_000..1_000_000_000));
+        let mut controller = create_funded_controller(&mut rng, &node, funds).await;
+
+        // The node advances, but the wallet can't get the new blocks from it
+        let lag = rng.gen_range(1..10);
+        node.add_empty_blocks(&mut rng, lag as usize);
+        node.blocks_unavailable.store(true, Ordering::SeqCst);
+
+        let address = random_address(&mut rng, &node.chain_config());
+        let amount = Amount::from_atoms(rng.gen_range(1..funds.into_atoms() / 2));
+
+        // Without an allowed lag the wallet must be fully synced
+        let result = controller.synced_controller(DEFAULT_ACCOUNT_INDEX, controller_config()).await;
+        assert!(result.is_err());
+
+        // Lagging behind more than allowed
+        let config = ControllerConfig {
+            max_sync_lag: Some(lag - 1),
+            ..controller_config()
+        };
+        let result = controller
+            .synced_controller(DEFAULT_ACCOUNT_INDEX, config)
+            .await
+            .unwrap()
+            .send_to_address(address.clone(), amount, vec![])
+            .await;
+        assert!(matches!(
+            result,
+            Err(ControllerError::WalletNotSynced(wallet_height, node_height))
+                if wallet_height == BlockHeight::new(1) && node_height == BlockHeight::new(1 + lag)
+        ));
+        assert!(node.mempool.lock().unwrap().is_empty());
+
+        // Within the tolerance
+        let config = ControllerConfig {
+            max_sync_lag: Some(lag),
+            ..controller_config()
+        };
+        let tx = controller
+            .synced_controller(DEFAULT_ACCOUNT_INDEX, config)
+            .await
+            .unwrap()
+            .send_to_address(address, amount, vec![])
+            .await
+            .unwrap();
+        assert_eq!(*node.mempool.lock().unwrap(), vec![tx]);
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn sync_lag(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let max_sync_lag = rng.gen_range(0..10);
+        let node_height = BlockHeight::new(rng.gen_range(100..1000));
+
+        // Within the tolerance
+        let lag = rng.gen_range(0..=max_sync_lag);
+        let wallet_height = BlockHeight::new(node_height.into_int() - lag);
+        assert!(
+            check_sync_lag::<ColdWalletClient>(wallet_height, node_height, max_sync_lag).is_ok()
+        );
+
+        // The wallet is ahead of the node, e.g. if the node has just switched to a shorter chain
+        let wallet_height = BlockHeight::new(node_height.into_int() + rng.gen_range(1..10));
+        assert!(
+            check_sync_lag::<ColdWalletClient>(wallet_height, node_height, max_sync_lag).is_ok()
+        );
+
+        // Lagging behind too much
+        let lag = rng.gen_range(max_sync_lag + 1..100);
+        let wallet_height = BlockHeight::new(node_height.into_int() - lag);
+        let result = check_sync_lag::<ColdWalletClient>(wallet_height, node_height, max_sync_lag);
+        assert!(matches!(
+            result,
+            Err(ControllerError::WalletNotSynced(wallet, node))
+                if wallet == wallet_height && node == node_height
+        ));
+    }
+}
@@ -15,7 +15,14 @@
 
 use std::collections::BTreeMap;
 
-use common::{address::RpcAddress, chain::tokens::TokenId, primitives::amount::RpcAmountOut};
+use common::{
+    address::{AddressError, RpcAddress},
+    chain::{
+        tokens::{RPCIsTokenFrozen, RPCTokenInfo, TokenId},
+        ChainConfig,
+    },
+    primitives::{amount::RpcAmountOut, Amount},
+};
 
 /// Balances of coins and tokens
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, rpc_description::HasValueHint)]
@@ -44,3 +51,148 @@ impl Balances {
         (coins, tokens)
     }
 }
+
+/// A token owned by an account, together with its metadata
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, rpc_description::HasValueHint)]
+pub struct OwnedTokenInfo {
+    pub token_id: RpcAddress<TokenId>,
+    pub amount: RpcAmountOut,
+    pub ticker: String,
+    pub number_of_decimals: u8,
+    pub is_frozen: bool,
+}
+
+impl OwnedTokenInfo {
+    pub fn new(
+        chain_config: &ChainConfig,
+        token_info: &RPCTokenInfo,
+        amount: Amount,
+    ) -> Result<Self, AddressError> {
+        let number_of_decimals = token_info.token_number_of_decimals();
+        let (ticker, is_frozen) = match token_info {
+            RPCTokenInfo::FungibleToken(info) => (
+                &info.token_ticker,
+                matches!(info.frozen, RPCIsTokenFrozen::Frozen { .. }),
+            ),
+            RPCTokenInfo::NonFungibleToken(info) => (&info.metadata.ticker, false),
+        };
+        let ticker = String::from_utf8_lossy(ticker.as_ref()).into_owned();
+
+        Ok(Self {
+            token_id: RpcAddress::new(chain_config, token_info.token_id())?,
+            amount: RpcAmountOut::from_amount_no_padding(amount, number_of_decimals),
+            ticker,
+            number_of_decimals,
+            is_frozen,
+        })
+    }
+
+    /// Join the token balances with the corresponding token infos.
+    /// Tokens without a matching info are skipped.
+    pub fn join(
+        chain_config: &ChainConfig,
+        token_balances: &BTreeMap<TokenId, Amount>,
+        token_infos: &[RPCTokenInfo],
+    ) -> Result<Vec<Self>, AddressError> {
+        token_infos
+            .iter()
+            .filter_map(|info| {
+                token_balances
+                    .get(&info.token_id())
+                    .map(|amount| Self::new(chain_config, info, *amount))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::chain::{
+        config::create_unit_test_config,
+        tokens::{RPCFungibleTokenInfo, RPCTokenTotalSupply},
+        Destination,
+    };
+    use randomness::Rng;
+    use rstest::rstest;
+    use test_utils::random::{make_seedable_rng, Seed};
+
+    use super::*;
+
+    fn fungible_token_info(
+        token_id: TokenId,
+        ticker: &str,
+        number_of_decimals: u8,
+        frozen: RPCIsTokenFrozen,
+    ) -> RPCTokenInfo {
+        RPCTokenInfo::new_fungible(RPCFungibleTokenInfo::new(
+            token_id,
+            ticker.as_bytes().to_vec(),
+            number_of_decimals,
+            Vec::new(),
+            Amount::ZERO,
+            RPCTokenTotalSupply::Unlimited,
+            false,
+            frozen,
+            Destination::AnyoneCanSpend,
+        ))
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn join_owned_tokens(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = create_unit_test_config();
+
+        let token_id1 = TokenId::random_using(&mut rng);
+        let token_id2 = TokenId::random_using(&mut rng);
+        let unknown_token_id = TokenId::random_using(&mut rng);
+
+        let amount1 = Amount::from_atoms(rng.gen_range(1..1_000_000));
+        let amount2 = Amount::from_atoms(rng.gen_range(1..1_000_000));
+
+        let token_balances = BTreeMap::from([
+            (token_id1, amount1),
+            (token_id2, amount2),
+            (
+                unknown_token_id,
+                Amount::from_atoms(rng.gen_range(1..1_000_000)),
+            ),
+        ]);
+        let token_infos = [
+            fungible_token_info(
+                token_id1,
+                "TKNA",
+                2,
+                RPCIsTokenFrozen::NotFrozen { freezable: true },
+            ),
+            fungible_token_info(
+                token_id2,
+                "TKNB",
+                8,
+                RPCIsTokenFrozen::Frozen { unfreezable: true },
+            ),
+        ];
+
+        let owned = OwnedTokenInfo::join(&chain_config, &token_balances, &token_infos).unwrap();
+        assert_eq!(owned.len(), 2);
+
+        assert_eq!(
+            owned[0].token_id.decode_object(&chain_config).unwrap(),
+            token_id1
+        );
+        assert_eq!(owned[0].amount.amount(), amount1);
+        assert_eq!(owned[0].ticker, "TKNA");
+        assert_eq!(owned[0].number_of_decimals, 2);
+        assert!(!owned[0].is_frozen);
+
+        assert_eq!(
+            owned[1].token_id.decode_object(&chain_config).unwrap(),
+            token_id2
+        );
+        assert_eq!(owned[1].amount.amount(), amount2);
+        assert_eq!(owned[1].ticker, "TKNB");
+        assert_eq!(owned[1].number_of_decimals, 8);
+        assert!(owned[1].is_frozen);
+    }
+}
@@ -21,7 +21,7 @@ mod seed_phrase;
 mod standalone_key;
 mod transaction;
 
-pub use balances::Balances;
+pub use balances::{Balances, OwnedTokenInfo};
 pub use block_info::{BlockInfo, CreatedBlockInfo};
 pub use common::primitives::amount::RpcAmountOut;
 use common::{
@@ -16,7 +16,7 @@
 use std::{num::NonZeroUsize, time::Duration};
 
 use blockprod::{BlockProductionError, BlockProductionHandle, TimestampSearchData};
-use chainstate::{BlockSource, ChainInfo, ChainstateError, ChainstateHandle};
+use chainstate::{BlockSource, ChainFork, ChainInfo, ChainstateError, ChainstateHandle};
 use common::{
     chain::{
         tokens::{RPCTokenInfo, TokenId},
@@ -28,10 +28,11 @@ use consensus::GenerateBlockInputData;
 use crypto::ephemeral_e2e::EndToEndPublicKey;
 use mempool::{
     tx_accumulator::PackingStrategy, tx_options::TxOptionsOverrides, FeeRate, MempoolHandle,
+    MempoolTxInfo,
 };
 use p2p::{
     error::P2pError,
-    interface::types::ConnectedPeer,
+    interface::types::{ConnectedPeer, PeerLimits},
     types::{bannable_address::BannableAddress, peer_id::PeerId, socket_address::SocketAddress},
     P2pHandle,
 };
@@ -39,7 +40,7 @@ use serialization::hex::HexError;
 use utils_networking::IpOrSocketAddress;
 use wallet_types::wallet_type::WalletType;
 
-use crate::node_traits::NodeInterface;
+use crate::node_traits::{NodeInterface, NodeInterfaceError};
 
 #[derive(Clone)]
 pub struct WalletHandlesClient {
@@ -73,6 +74,13 @@ pub enum WalletHandlesClientError {
     AttemptedExit,
 }
 
+impl NodeInterfaceError for WalletHandlesClientError {
+    fn is_connection_error(&self) -> bool {
+        // The node subsystems are called directly, there is no connection that could fail
+        false
+    }
+}
+
 impl WalletHandlesClient {
     pub async fn new(
         chainstate: ChainstateHandle,
@@ -174,6 +182,11 @@ impl NodeInterface for WalletHandlesClient {
         Ok(result)
     }
 
+    async fn get_chain_forks(&self) -> Result<Vec<ChainFork>, Self::Error> {
+        let result = self.chainstate.call(move |this| chainstate::get_chain_forks(this)).await??;
+        Ok(result)
+    }
+
     async fn get_stake_pool_balance(&self, pool_id: PoolId) -> Result<Option<Amount>, Self::Error> {
         let result =
             self.chainstate.call(move |this| this.get_stake_pool_balance(pool_id)).await??;
@@ -195,6 +208,23 @@ impl NodeInterface for WalletHandlesClient {
         Ok(result)
     }
 
+    async fn get_stake_pool_delegations_count(
+        &self,
+        pool_id: PoolId,
+    ) -> Result<Option<usize>, Self::Error> {
+        let result = self
+            .chainstate
+            .call(move |this| -> Result<_, ChainstateError> {
+                if this.get_stake_pool_data(pool_id)?.is_none() {
+                    return Ok(None);
+                }
+                let shares = this.get_stake_pool_delegations_shares(pool_id)?;
+                Ok(Some(shares.map_or(0, |shares| shares.len())))
+            })
+            .await??;
+        Ok(result)
+    }
+
     async fn get_delegation_share(
         &self,
         pool_id: PoolId,
@@ -383,6 +413,14 @@ impl NodeInterface for WalletHandlesClient {
             .await??;
         Ok(())
     }
+    async fn p2p_get_peer_limits(&self) -> Result<PeerLimits, Self::Error> {
+        let limits = self.p2p.call_async(move |this| this.get_peer_limits()).await??;
+        Ok(limits)
+    }
+    async fn p2p_set_peer_limits(&self, limits: PeerLimits) -> Result<(), Self::Error> {
+        self.p2p.call_async_mut(move |this| this.set_peer_limits(limits)).await??;
+        Ok(())
+    }
 
     async fn mempool_get_fee_rate(&self, in_top_x_mb: usize) -> Result<FeeRate, Self::Error> {
         let res = self.mempool.call(move |this| this.get_fee_rate(in_top_x_mb)).await?;
@@ -395,4 +433,17 @@ impl NodeInterface for WalletHandlesClient {
         let res = self.mempool.call(move |this| this.get_fee_rate_points(NUM_POINTS)).await??;
         Ok(res)
     }
+
+    async fn mempool_get_transaction(
+        &self,
+        tx_id: Id<Transaction>,
+    ) -> Result<Option<SignedTransaction>, Self::Error> {
+        let res = self.mempool.call(move |this| this.transaction(&tx_id)).await?;
+        Ok(res)
+    }
+
+    async fn mempool_get_all_tx_info(&self) -> Result<Vec<MempoolTxInfo>, Self::Error> {
+        let res = self.mempool.call(move |this| this.get_all_tx_info()).await?;
+        Ok(res)
+    }
 }
@@ -15,7 +15,7 @@
 
 use std::{num::NonZeroUsize, time::Duration};
 
-use chainstate::ChainInfo;
+use chainstate::{ChainFork, ChainInfo};
 use common::{
     chain::{
         tokens::{RPCTokenInfo, TokenId},
@@ -27,15 +27,26 @@ use common::{
 
 use consensus::GenerateBlockInputData;
 use crypto::ephemeral_e2e::EndToEndPublicKey;
-use mempool::{tx_accumulator::PackingStrategy, tx_options::TxOptionsOverrides, FeeRate};
+use mempool::{
+    tx_accumulator::PackingStrategy, tx_options::TxOptionsOverrides, FeeRate, MempoolTxInfo,
+};
 use p2p::types::{bannable_address::BannableAddress, socket_address::SocketAddress};
-pub use p2p::{interface::types::ConnectedPeer, types::peer_id::PeerId};
+pub use p2p::{
+    interface::types::{ConnectedPeer, PeerLimits},
+    types::peer_id::PeerId,
+};
 use utils_networking::IpOrSocketAddress;
 use wallet_types::wallet_type::WalletType;
 
+pub trait NodeInterfaceError: std::error::Error + Send + Sync + 'static {
+    /// Whether the call failed because the node couldn't be reached, e.g. because it's restarting,
+    /// as opposed to the node handling the call and returning an error
+    fn is_connection_error(&self) -> bool;
+}
+
 #[async_trait::async_trait]
 pub trait NodeInterface {
-    type Error: std::error::Error + Send + Sync + 'static;
+    type Error: NodeInterfaceError;
 
     fn is_cold_wallet_node(&self) -> WalletType;
 
@@ -63,8 +74,13 @@ pub trait NodeInterface {
         first_block: Id<GenBlock>,
         second_block: Id<GenBlock>,
     ) -> Result<Option<(Id<GenBlock>, BlockHeight)>, Self::Error>;
+    async fn get_chain_forks(&self) -> Result<Vec<ChainFork>, Self::Error>;
     async fn get_stake_pool_balance(&self, pool_id: PoolId) -> Result<Option<Amount>, Self::Error>;
     async fn get_staker_balance(&self, pool_id: PoolId) -> Result<Option<Amount>, Self::Error>;
+    async fn get_stake_pool_delegations_count(
+        &self,
+        pool_id: PoolId,
+    ) -> Result<Option<usize>, Self::Error>;
     async fn get_delegation_share(
         &self,
         pool_id: PoolId,
@@ -122,9 +138,17 @@ pub trait NodeInterface {
     async fn p2p_add_reserved_node(&self, address: IpOrSocketAddress) -> Result<(), Self::Error>;
     async fn p2p_remove_reserved_node(&self, address: IpOrSocketAddress)
         -> Result<(), Self::Error>;
+    async fn p2p_get_peer_limits(&self) -> Result<PeerLimits, Self::Error>;
+    async fn p2p_set_peer_limits(&self, limits: PeerLimits) -> Result<(), Self::Error>;
 
     async fn mempool_get_fee_rate(&self, in_top_x_mb: usize) -> Result<FeeRate, Self::Error>;
     async fn mempool_get_fee_rate_points(&self) -> Result<Vec<(usize, FeeRate)>, Self::Error>;
+    /// Returns the transaction if it's in the mempool (orphans are not included)
+    async fn mempool_get_transaction(
+        &self,
+        tx_id: Id<Transaction>,
+    ) -> Result<Option<SignedTransaction>, Self::Error>;
+    async fn mempool_get_all_tx_info(&self) -> Result<Vec<MempoolTxInfo>, Self::Error>;
 
     async fn get_utxo(&self, outpoint: UtxoOutPoint) -> Result<Option<TxOutput>, Self::Error>;
 }
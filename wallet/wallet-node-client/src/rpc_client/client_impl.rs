@@ -16,7 +16,7 @@
 use std::{num::NonZeroUsize, time::Duration};
 
 use blockprod::{rpc::BlockProductionRpcClient, TimestampSearchData};
-use chainstate::{rpc::ChainstateRpcClient, ChainInfo};
+use chainstate::{rpc::ChainstateRpcClient, ChainFork, ChainInfo};
 use common::{
     address::Address,
     chain::{
@@ -29,10 +29,11 @@ use common::{
 use consensus::GenerateBlockInputData;
 use crypto::ephemeral_e2e::EndToEndPublicKey;
 use mempool::{
-    rpc::MempoolRpcClient, tx_accumulator::PackingStrategy, tx_options::TxOptionsOverrides, FeeRate,
+    rpc::MempoolRpcClient, tx_accumulator::PackingStrategy, tx_options::TxOptionsOverrides,
+    FeeRate, MempoolTxInfo, TxStatus,
 };
 use p2p::{
-    interface::types::ConnectedPeer,
+    interface::types::{ConnectedPeer, PeerLimits},
     rpc::P2pRpcClient,
     types::{bannable_address::BannableAddress, peer_id::PeerId, socket_address::SocketAddress},
 };
@@ -127,6 +128,12 @@ impl NodeInterface for NodeRpcClient {
         .map_err(NodeRpcError::ResponseError)
     }
 
+    async fn get_chain_forks(&self) -> Result<Vec<ChainFork>, Self::Error> {
+        ChainstateRpcClient::fork_detection(&self.http_client)
+            .await
+            .map_err(NodeRpcError::ResponseError)
+    }
+
     async fn get_stake_pool_balance(&self, pool_id: PoolId) -> Result<Option<Amount>, Self::Error> {
         let pool_address = Address::new(&self.chain_config, pool_id)?;
         ChainstateRpcClient::stake_pool_balance(&self.http_client, pool_address.into_string())
@@ -141,6 +148,19 @@ impl NodeInterface for NodeRpcClient {
             .map_err(NodeRpcError::ResponseError)
     }
 
+    async fn get_stake_pool_delegations_count(
+        &self,
+        pool_id: PoolId,
+    ) -> Result<Option<usize>, Self::Error> {
+        let pool_address = Address::new(&self.chain_config, pool_id)?;
+        ChainstateRpcClient::stake_pool_delegations_count(
+            &self.http_client,
+            pool_address.into_string(),
+        )
+        .await
+        .map_err(NodeRpcError::ResponseError)
+    }
+
     async fn get_delegation_share(
         &self,
         pool_id: PoolId,
@@ -329,6 +349,16 @@ impl NodeInterface for NodeRpcClient {
             .await
             .map_err(NodeRpcError::ResponseError)
     }
+    async fn p2p_get_peer_limits(&self) -> Result<PeerLimits, Self::Error> {
+        P2pRpcClient::get_peer_limits(&self.http_client)
+            .await
+            .map_err(NodeRpcError::ResponseError)
+    }
+    async fn p2p_set_peer_limits(&self, limits: PeerLimits) -> Result<(), Self::Error> {
+        P2pRpcClient::set_peer_limits(&self.http_client, limits)
+            .await
+            .map_err(NodeRpcError::ResponseError)
+    }
 
     async fn mempool_get_fee_rate(&self, in_top_x_mb: usize) -> Result<FeeRate, Self::Error> {
         MempoolRpcClient::get_fee_rate(&self.http_client, in_top_x_mb)
@@ -342,6 +372,24 @@ impl NodeInterface for NodeRpcClient {
             .map_err(NodeRpcError::ResponseError)
     }
 
+    async fn mempool_get_transaction(
+        &self,
+        tx_id: Id<Transaction>,
+    ) -> Result<Option<SignedTransaction>, Self::Error> {
+        let response = MempoolRpcClient::get_transaction(&self.http_client, tx_id)
+            .await
+            .map_err(NodeRpcError::ResponseError)?;
+        Ok(response
+            .filter(|response| response.status() == TxStatus::InMempool)
+            .map(|response| response.into_transaction()))
+    }
+
+    async fn mempool_get_all_tx_info(&self) -> Result<Vec<MempoolTxInfo>, Self::Error> {
+        MempoolRpcClient::get_all_transactions_info(&self.http_client)
+            .await
+            .map_err(NodeRpcError::ResponseError)
+    }
+
     async fn get_utxo(&self, outpoint: UtxoOutPoint) -> Result<Option<TxOutput>, Self::Error> {
         ChainstateRpcClient::get_utxo(&self.http_client, outpoint.into())
             .await
@@ -16,7 +16,7 @@
 use std::{num::NonZeroUsize, time::Duration};
 
 use blockprod::TimestampSearchData;
-use chainstate::ChainInfo;
+use chainstate::{ChainFork, ChainInfo};
 use common::{
     chain::{
         tokens::{RPCTokenInfo, TokenId},
@@ -26,15 +26,17 @@ use common::{
 };
 use consensus::GenerateBlockInputData;
 use crypto::ephemeral_e2e::EndToEndPublicKey;
-use mempool::{tx_accumulator::PackingStrategy, tx_options::TxOptionsOverrides, FeeRate};
+use mempool::{
+    tx_accumulator::PackingStrategy, tx_options::TxOptionsOverrides, FeeRate, MempoolTxInfo,
+};
 use p2p::{
-    interface::types::ConnectedPeer,
+    interface::types::{ConnectedPeer, PeerLimits},
     types::{bannable_address::BannableAddress, socket_address::SocketAddress, PeerId},
 };
 use utils_networking::IpOrSocketAddress;
 use wallet_types::wallet_type::WalletType;
 
-use crate::node_traits::NodeInterface;
+use crate::node_traits::{NodeInterface, NodeInterfaceError};
 
 use super::ColdWalletClient;
 
@@ -44,6 +46,12 @@ pub enum ColdWalletRpcError {
     NotAvailable,
 }
 
+impl NodeInterfaceError for ColdWalletRpcError {
+    fn is_connection_error(&self) -> bool {
+        false
+    }
+}
+
 #[async_trait::async_trait]
 impl NodeInterface for ColdWalletClient {
     type Error = ColdWalletRpcError;
@@ -107,6 +115,10 @@ impl NodeInterface for ColdWalletClient {
         Err(ColdWalletRpcError::NotAvailable)
     }
 
+    async fn get_chain_forks(&self) -> Result<Vec<ChainFork>, Self::Error> {
+        Err(ColdWalletRpcError::NotAvailable)
+    }
+
     async fn get_stake_pool_balance(
         &self,
         _pool_id: PoolId,
@@ -118,6 +130,13 @@ impl NodeInterface for ColdWalletClient {
         Err(ColdWalletRpcError::NotAvailable)
     }
 
+    async fn get_stake_pool_delegations_count(
+        &self,
+        _pool_id: PoolId,
+    ) -> Result<Option<usize>, Self::Error> {
+        Err(ColdWalletRpcError::NotAvailable)
+    }
+
     async fn get_delegation_share(
         &self,
         _pool_id: PoolId,
@@ -244,6 +263,14 @@ impl NodeInterface for ColdWalletClient {
         Err(ColdWalletRpcError::NotAvailable)
     }
 
+    async fn p2p_get_peer_limits(&self) -> Result<PeerLimits, Self::Error> {
+        Err(ColdWalletRpcError::NotAvailable)
+    }
+
+    async fn p2p_set_peer_limits(&self, _limits: PeerLimits) -> Result<(), Self::Error> {
+        Err(ColdWalletRpcError::NotAvailable)
+    }
+
     async fn mempool_get_fee_rate(&self, _in_top_x_mb: usize) -> Result<FeeRate, Self::Error> {
         Err(ColdWalletRpcError::NotAvailable)
     }
@@ -252,6 +279,17 @@ impl NodeInterface for ColdWalletClient {
         Err(ColdWalletRpcError::NotAvailable)
     }
 
+    async fn mempool_get_transaction(
+        &self,
+        _tx_id: Id<Transaction>,
+    ) -> Result<Option<SignedTransaction>, Self::Error> {
+        Err(ColdWalletRpcError::NotAvailable)
+    }
+
+    async fn mempool_get_all_tx_info(&self) -> Result<Vec<MempoolTxInfo>, Self::Error> {
+        Err(ColdWalletRpcError::NotAvailable)
+    }
+
     async fn get_utxo(
         &self,
         _outpoint: common::chain::UtxoOutPoint,
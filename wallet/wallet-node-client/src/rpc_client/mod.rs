@@ -25,7 +25,7 @@ use rpc::ClientError;
 use rpc::RpcAuthData;
 use rpc::RpcHttpClient;
 
-use crate::node_traits::NodeInterface;
+use crate::node_traits::{NodeInterface, NodeInterfaceError};
 
 #[derive(thiserror::Error, Debug)]
 pub enum NodeRpcError {
@@ -41,6 +41,23 @@ pub enum NodeRpcError {
     AddressError(#[from] AddressError),
 }
 
+impl NodeInterfaceError for NodeRpcError {
+    fn is_connection_error(&self) -> bool {
+        match self {
+            NodeRpcError::InitializationError(err) => err.is_connection_error(),
+            NodeRpcError::ResponseError(err) => matches!(
+                err,
+                ClientError::Transport(_)
+                    | ClientError::RestartNeeded(_)
+                    | ClientError::RequestTimeout
+            ),
+            NodeRpcError::DecodingError(_)
+            | NodeRpcError::ClientCreationError(_)
+            | NodeRpcError::AddressError(_) => false,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ColdWalletClient {
     chain_config: Arc<ChainConfig>,
@@ -17,7 +17,7 @@ use std::{collections::BTreeMap, fmt::Debug, num::NonZeroUsize, path::PathBuf, s
 
 use chainstate::ChainInfo;
 use common::{
-    address::{dehexify::dehexify_all_addresses, AddressError},
+    address::{dehexify::dehexify_all_addresses, AddressError, RpcAddress},
     chain::{
         block::timestamp::BlockTimestamp, partially_signed_transaction::PartiallySignedTransaction,
         tokens::IsTokenUnfreezable, Block, GenBlock, SignedTransaction, Transaction, TxOutput,
@@ -31,20 +31,27 @@ use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress
 use rpc::types::RpcHexString;
 use serialization::{hex::HexEncode, hex_encoded::HexEncoded, json_encoded::JsonEncoded};
 use utils_networking::IpOrSocketAddress;
-use wallet::{account::TxInfo, version::get_version};
+use wallet::{
+    account::{
+        transaction_list::{DailyActivity, TxCursor},
+        TxInfo,
+    },
+    version::get_version,
+};
 use wallet_controller::{
     types::{CreatedBlockInfo, GenericTokenTransfer, SeedWithPassPhrase, WalletInfo},
-    ConnectedPeer, ControllerConfig, UtxoState, UtxoType,
+    ConnectedPeer, ControllerConfig, PeerLimits, UtxoState, UtxoType,
 };
 use wallet_rpc_lib::{
     types::{
-        AddressInfo, AddressWithUsageInfo, Balances, BlockInfo, ComposedTransaction, CreatedWallet,
-        DelegationInfo, LegacyVrfPublicKeyInfo, NewAccountInfo, NewDelegation, NewTransaction,
-        NftMetadata, NodeVersion, PoolInfo, PublicKeyInfo, RpcHashedTimelockContract,
-        RpcInspectTransaction, RpcStandaloneAddresses, RpcTokenId,
-        SendTokensFromMultisigAddressResult, StakePoolBalance, StakingStatus,
-        StandaloneAddressWithDetails, TokenMetadata, TxOptionsOverrides, UtxoInfo,
-        VrfPublicKeyInfo,
+        AddressInfo, AddressOwnerInfo, AddressWithUsageInfo, Balances, BlockInfo,
+        ComposedTransaction, CreatedWallet, DelegationInfo, LegacyVrfPublicKeyInfo, LockedUtxoInfo,
+        LookaheadStatus, NewAccountInfo, NewDelegation, NewMultisigAccountInfo, NewTransaction,
+        NftMetadata, NodeVersion, OwnedTokenInfo, PoolInfo, PublicKeyInfo, RebroadcastResult,
+        RpcHashedTimelockContract, RpcInspectTransaction, RpcOutPointSourceId,
+        RpcStandaloneAddresses, RpcTokenId, SendTokensFromMultisigAddressResult, StakePoolBalance,
+        StakingStatus, StandaloneAddressWithDetails, TokenAuthorityInfo, TokenMetadata,
+        TransactionListPage, TxOptionsOverrides, UtxoInfo, VrfPublicKeyInfo,
     },
     RpcError, WalletRpc,
 };
@@ -163,6 +170,20 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletInterface
             .map_err(WalletRpcHandlesClientError::WalletRpcError)
     }
 
+    async fn account_fingerprint(&self, account_index: U31) -> Result<H256, Self::Error> {
+        self.wallet_rpc
+            .account_fingerprint(account_index)
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
+    async fn check_integrity(&self) -> Result<Vec<String>, Self::Error> {
+        self.wallet_rpc
+            .check_integrity()
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
     async fn sync(&self) -> Result<(), Self::Error> {
         self.wallet_rpc
             .sync()
@@ -202,6 +223,13 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletInterface
             .map_err(WalletRpcHandlesClientError::WalletRpcError)
     }
 
+    async fn lookahead_status(&self, account_index: U31) -> Result<LookaheadStatus, Self::Error> {
+        self.wallet_rpc
+            .lookahead_status(account_index)
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
     async fn encrypt_private_keys(&self, password: String) -> Result<(), Self::Error> {
         self.wallet_rpc
             .encrypt_private_keys(password)
@@ -244,6 +272,39 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletInterface
             .map_err(WalletRpcHandlesClientError::WalletRpcError)
     }
 
+    async fn recover_accounts(
+        &self,
+        account_indices: Vec<U31>,
+    ) -> Result<Vec<NewAccountInfo>, Self::Error> {
+        self.wallet_rpc
+            .recover_accounts(account_indices)
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
+    async fn next_multisig_account_public_key(&self) -> Result<PublicKeyInfo, Self::Error> {
+        self.wallet_rpc
+            .next_multisig_account_public_key()
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
+    async fn create_multisig_account(
+        &self,
+        min_required_signatures: u8,
+        cosigner_public_keys: Vec<String>,
+        name: Option<String>,
+    ) -> Result<NewMultisigAccountInfo, Self::Error> {
+        self.wallet_rpc
+            .create_multisig_account(
+                min_required_signatures,
+                cosigner_public_keys.into_iter().map(Into::into).collect(),
+                name,
+            )
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
     async fn rename_account(
         &self,
         account_index: U31,
@@ -344,6 +405,46 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletInterface
             .map_err(WalletRpcHandlesClientError::WalletRpcError)
     }
 
+    async fn get_address_derivation_path(
+        &self,
+        account_index: U31,
+        address: String,
+    ) -> Result<Option<String>, Self::Error> {
+        self.wallet_rpc
+            .get_address_derivation_path(account_index, address.into())
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
+    async fn find_account_for_address(
+        &self,
+        address: String,
+    ) -> Result<Option<AddressOwnerInfo>, Self::Error> {
+        self.wallet_rpc
+            .find_account_for_address(address.into())
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
+    async fn simulate_scan(
+        &self,
+        account_index: U31,
+        blocks: Vec<Block>,
+    ) -> Result<Vec<RpcOutPointSourceId>, Self::Error> {
+        self.wallet_rpc
+            .simulate_scan(account_index, blocks)
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
+    async fn get_current_change_address(&self, account_index: U31) -> Result<String, Self::Error> {
+        self.wallet_rpc
+            .get_current_change_address(account_index)
+            .await
+            .map(RpcAddress::into_string)
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
     async fn issue_address(&self, account_index: U31) -> Result<AddressInfo, Self::Error> {
         self.wallet_rpc
             .issue_address(account_index)
@@ -378,6 +479,16 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletInterface
             .map_err(WalletRpcHandlesClientError::WalletRpcError)
     }
 
+    async fn list_owned_tokens(
+        &self,
+        account_index: U31,
+    ) -> Result<Vec<OwnedTokenInfo>, Self::Error> {
+        self.wallet_rpc
+            .list_owned_tokens(account_index)
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
     async fn get_multisig_utxos(
         &self,
         account_index: U31,
@@ -436,6 +547,36 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletInterface
             .map_err(WalletRpcHandlesClientError::SerializationError)
     }
 
+    async fn get_locked_utxos(
+        &self,
+        account_index: U31,
+        utxo_states: Vec<UtxoState>,
+    ) -> Result<Vec<serde_json::Value>, Self::Error> {
+        let utxos = self
+            .wallet_rpc
+            .get_locked_utxos(
+                account_index,
+                (&utxo_states).try_into().unwrap_or(UtxoState::Confirmed.into()),
+            )
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)?;
+
+        utxos
+            .into_iter()
+            .map(|(utxo_outpoint, tx_ouput, unlock_time)| {
+                LockedUtxoInfo::new(
+                    utxo_outpoint,
+                    tx_ouput,
+                    unlock_time,
+                    self.wallet_rpc.chain_config(),
+                )
+                .map(serde_json::to_value)
+            })
+            .collect::<Result<Result<Vec<_>, _>, _>>()
+            .map_err(WalletRpcHandlesClientError::AddressError)?
+            .map_err(WalletRpcHandlesClientError::SerializationError)
+    }
+
     async fn submit_raw_transaction(
         &self,
         tx: HexEncoded<SignedTransaction>,
@@ -448,6 +589,13 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletInterface
             .map_err(WalletRpcHandlesClientError::WalletRpcError)
     }
 
+    async fn rebroadcast_all_transactions(&self) -> Result<Vec<RebroadcastResult>, Self::Error> {
+        self.wallet_rpc
+            .rebroadcast_all_transactions()
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
     async fn sign_challenge(
         &self,
         account_index: U31,
@@ -624,6 +772,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletInterface
         cost_per_block: DecimalAmount,
         margin_ratio_per_thousand: String,
         decommission_address: String,
+        vrf_public_key: Option<String>,
         config: ControllerConfig,
     ) -> Result<NewTransaction, Self::Error> {
         self.wallet_rpc
@@ -633,6 +782,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletInterface
                 cost_per_block.into(),
                 margin_ratio_per_thousand,
                 decommission_address.into(),
+                vrf_public_key.map(Into::into),
                 config,
             )
             .await
@@ -776,6 +926,16 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletInterface
             .map_err(WalletRpcHandlesClientError::WalletRpcError)
     }
 
+    async fn stake_pool_delegations_count(
+        &self,
+        pool_id: String,
+    ) -> Result<Option<usize>, Self::Error> {
+        self.wallet_rpc
+            .stake_pool_delegations_count(pool_id.into())
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
     async fn list_delegation_ids(
         &self,
         account_index: U31,
@@ -806,6 +966,17 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletInterface
             .map_err(WalletRpcHandlesClientError::WalletRpcError)
     }
 
+    async fn new_vrf_public_keys(
+        &self,
+        account_index: U31,
+        count: u32,
+    ) -> Result<Vec<VrfPublicKeyInfo>, Self::Error> {
+        self.wallet_rpc
+            .issue_vrf_keys(account_index, count)
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
     async fn get_vrf_public_key(
         &self,
         account_index: U31,
@@ -994,6 +1165,29 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletInterface
             .map_err(WalletRpcHandlesClientError::WalletRpcError)
     }
 
+    async fn send_all_tokens(
+        &self,
+        account_index: U31,
+        token_id: String,
+        address: String,
+        config: ControllerConfig,
+    ) -> Result<NewTransaction, Self::Error> {
+        self.wallet_rpc
+            .send_all_tokens(account_index, token_id.into(), address.into(), config)
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
+    async fn list_token_authorities(
+        &self,
+        account_index: U31,
+    ) -> Result<Vec<TokenAuthorityInfo>, Self::Error> {
+        self.wallet_rpc
+            .list_token_authorities(account_index)
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
     async fn make_tx_to_send_tokens_from_multisig_address(
         &self,
         account_index: U31,
@@ -1163,6 +1357,20 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletInterface
             .map_err(WalletRpcHandlesClientError::WalletRpcError)
     }
 
+    async fn peer_limits(&self) -> Result<PeerLimits, Self::Error> {
+        self.wallet_rpc
+            .peer_limits()
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
+    async fn set_peer_limits(&self, limits: PeerLimits) -> Result<(), Self::Error> {
+        self.wallet_rpc
+            .set_peer_limits(limits)
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
     async fn submit_block(&self, block: HexEncoded<Block>) -> Result<(), Self::Error> {
         self.wallet_rpc
             .submit_block(block)
@@ -1188,6 +1396,31 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletInterface
             .map_err(WalletRpcHandlesClientError::WalletRpcError)
     }
 
+    async fn create_cpfp_transaction(
+        &self,
+        account_index: U31,
+        parent_tx_id: Id<Transaction>,
+        target_fee_rate: DecimalAmount,
+        config: ControllerConfig,
+    ) -> Result<NewTransaction, Self::Error> {
+        self.wallet_rpc
+            .create_cpfp_transaction(account_index, parent_tx_id, target_fee_rate.into(), config)
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
+    async fn import_transaction(
+        &self,
+        account_index: U31,
+        transaction: SignedTransaction,
+        block_height: Option<BlockHeight>,
+    ) -> Result<(), Self::Error> {
+        self.wallet_rpc
+            .import_transaction(account_index, transaction, block_height)
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
     async fn list_pending_transactions(
         &self,
         account_index: U31,
@@ -1211,6 +1444,29 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletInterface
             .map_err(WalletRpcHandlesClientError::WalletRpcError)
     }
 
+    async fn list_transactions(
+        &self,
+        account_index: U31,
+        cursor: Option<TxCursor>,
+        count: NonZeroUsize,
+    ) -> Result<TransactionListPage, Self::Error> {
+        self.wallet_rpc
+            .get_transaction_list_from_cursor(account_index, cursor, count)
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
+    async fn get_account_activity_summary(
+        &self,
+        account_index: U31,
+        days: u32,
+    ) -> Result<Vec<DailyActivity>, Self::Error> {
+        self.wallet_rpc
+            .get_account_activity_summary(account_index, days)
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
     async fn get_transaction(
         &self,
         account_index: U31,
@@ -21,11 +21,12 @@ use super::{ClientWalletRpc, WalletRpcError};
 
 use chainstate::ChainInfo;
 use common::{
+    address::RpcAddress,
     chain::{
         block::timestamp::BlockTimestamp, partially_signed_transaction::PartiallySignedTransaction,
         Block, GenBlock, SignedTransaction, Transaction, TxOutput, UtxoOutPoint,
     },
-    primitives::{BlockHeight, DecimalAmount, Id},
+    primitives::{BlockHeight, DecimalAmount, Id, H256},
 };
 use crypto::key::{hdkd::u31::U31, PrivateKey};
 use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress, PeerId};
@@ -33,20 +34,27 @@ use rpc::types::RpcHexString;
 use serialization::hex_encoded::HexEncoded;
 use serialization::DecodeAll;
 use utils_networking::IpOrSocketAddress;
-use wallet::account::TxInfo;
+use wallet::account::{
+    transaction_list::{DailyActivity, TxCursor},
+    TxInfo,
+};
 use wallet_controller::{
-    types::{Balances, CreatedBlockInfo, GenericTokenTransfer, SeedWithPassPhrase, WalletInfo},
-    ConnectedPeer, ControllerConfig, UtxoState, UtxoType,
+    types::{
+        Balances, CreatedBlockInfo, GenericTokenTransfer, OwnedTokenInfo, SeedWithPassPhrase,
+        WalletInfo,
+    },
+    ConnectedPeer, ControllerConfig, PeerLimits, UtxoState, UtxoType,
 };
 use wallet_rpc_lib::{
     types::{
-        AddressInfo, AddressWithUsageInfo, BlockInfo, ComposedTransaction, CreatedWallet,
-        DelegationInfo, LegacyVrfPublicKeyInfo, NewAccountInfo, NewDelegation, NewTransaction,
-        NftMetadata, NodeVersion, PoolInfo, PublicKeyInfo, RpcHashedTimelockContract,
-        RpcInspectTransaction, RpcStandaloneAddresses, RpcTokenId,
+        AddressInfo, AddressOwnerInfo, AddressWithUsageInfo, BlockInfo, ComposedTransaction,
+        CreatedWallet, DelegationInfo, LegacyVrfPublicKeyInfo, LookaheadStatus, NewAccountInfo,
+        NewDelegation, NewMultisigAccountInfo, NewTransaction, NftMetadata, NodeVersion, PoolInfo,
+        PublicKeyInfo, RebroadcastResult, RpcHashedTimelockContract, RpcInspectTransaction,
+        RpcOutPointSourceId, RpcStandaloneAddresses, RpcTokenId,
         SendTokensFromMultisigAddressResult, StakePoolBalance, StakingStatus,
-        StandaloneAddressWithDetails, TokenMetadata, TransactionOptions, TxOptionsOverrides,
-        VrfPublicKeyInfo,
+        StandaloneAddressWithDetails, TokenAuthorityInfo, TokenMetadata, TransactionListPage,
+        TransactionOptions, TxOptionsOverrides, VrfPublicKeyInfo,
     },
     ColdWalletRpcClient, WalletRpcClient,
 };
@@ -122,6 +130,18 @@ impl WalletInterface for ClientWalletRpc {
             .map_err(WalletRpcError::ResponseError)
     }
 
+    async fn account_fingerprint(&self, account_index: U31) -> Result<H256, Self::Error> {
+        ColdWalletRpcClient::account_fingerprint(&self.http_client, account_index.into())
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
+    async fn check_integrity(&self) -> Result<Vec<String>, Self::Error> {
+        ColdWalletRpcClient::check_integrity(&self.http_client)
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
     async fn sync(&self) -> Result<(), Self::Error> {
         WalletRpcClient::sync(&self.http_client)
             .await
@@ -160,6 +180,12 @@ impl WalletInterface for ClientWalletRpc {
         .map_err(WalletRpcError::ResponseError)
     }
 
+    async fn lookahead_status(&self, account_index: U31) -> Result<LookaheadStatus, Self::Error> {
+        ColdWalletRpcClient::lookahead_status(&self.http_client, account_index.into())
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
     async fn encrypt_private_keys(&self, password: String) -> Result<(), Self::Error> {
         ColdWalletRpcClient::encrypt_private_keys(&self.http_client, password)
             .await
@@ -196,6 +222,38 @@ impl WalletInterface for ClientWalletRpc {
             .map_err(WalletRpcError::ResponseError)
     }
 
+    async fn recover_accounts(
+        &self,
+        account_indices: Vec<U31>,
+    ) -> Result<Vec<NewAccountInfo>, Self::Error> {
+        let accounts = account_indices.into_iter().map(Into::into).collect();
+        WalletRpcClient::recover_accounts(&self.http_client, accounts)
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
+    async fn next_multisig_account_public_key(&self) -> Result<PublicKeyInfo, Self::Error> {
+        WalletRpcClient::next_multisig_account_public_key(&self.http_client)
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
+    async fn create_multisig_account(
+        &self,
+        min_required_signatures: u8,
+        cosigner_public_keys: Vec<String>,
+        name: Option<String>,
+    ) -> Result<NewMultisigAccountInfo, Self::Error> {
+        WalletRpcClient::create_multisig_account(
+            &self.http_client,
+            min_required_signatures,
+            cosigner_public_keys.into_iter().map(Into::into).collect(),
+            name,
+        )
+        .await
+        .map_err(WalletRpcError::ResponseError)
+    }
+
     async fn rename_account(
         &self,
         account_index: U31,
@@ -310,6 +368,47 @@ impl WalletInterface for ClientWalletRpc {
         .map_err(WalletRpcError::ResponseError)
     }
 
+    async fn get_address_derivation_path(
+        &self,
+        account_index: U31,
+        address: String,
+    ) -> Result<Option<String>, Self::Error> {
+        ColdWalletRpcClient::get_address_derivation_path(
+            &self.http_client,
+            account_index.into(),
+            address.into(),
+        )
+        .await
+        .map_err(WalletRpcError::ResponseError)
+    }
+
+    async fn find_account_for_address(
+        &self,
+        address: String,
+    ) -> Result<Option<AddressOwnerInfo>, Self::Error> {
+        ColdWalletRpcClient::find_account_for_address(&self.http_client, address.into())
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
+    async fn simulate_scan(
+        &self,
+        account_index: U31,
+        blocks: Vec<Block>,
+    ) -> Result<Vec<RpcOutPointSourceId>, Self::Error> {
+        let blocks = blocks.into_iter().map(HexEncoded::new).collect();
+        ColdWalletRpcClient::simulate_scan(&self.http_client, account_index.into(), blocks)
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
+    async fn get_current_change_address(&self, account_index: U31) -> Result<String, Self::Error> {
+        ColdWalletRpcClient::get_current_change_address(&self.http_client, account_index.into())
+            .await
+            .map(RpcAddress::into_string)
+            .map_err(WalletRpcError::ResponseError)
+    }
+
     async fn issue_address(&self, account_index: U31) -> Result<AddressInfo, Self::Error> {
         ColdWalletRpcClient::issue_address(&self.http_client, account_index.into())
             .await
@@ -346,6 +445,15 @@ impl WalletInterface for ClientWalletRpc {
         .map_err(WalletRpcError::ResponseError)
     }
 
+    async fn list_owned_tokens(
+        &self,
+        account_index: U31,
+    ) -> Result<Vec<OwnedTokenInfo>, Self::Error> {
+        WalletRpcClient::list_owned_tokens(&self.http_client, account_index.into())
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
     async fn get_multisig_utxos(
         &self,
         account_index: U31,
@@ -376,6 +484,16 @@ impl WalletInterface for ClientWalletRpc {
             .map_err(WalletRpcError::ResponseError)
     }
 
+    async fn get_locked_utxos(
+        &self,
+        account_index: U31,
+        _utxo_states: Vec<UtxoState>,
+    ) -> Result<Vec<serde_json::Value>, Self::Error> {
+        WalletRpcClient::get_locked_utxos(&self.http_client, account_index.into())
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
     async fn submit_raw_transaction(
         &self,
         tx: HexEncoded<SignedTransaction>,
@@ -387,6 +505,12 @@ impl WalletInterface for ClientWalletRpc {
             .map_err(WalletRpcError::ResponseError)
     }
 
+    async fn rebroadcast_all_transactions(&self) -> Result<Vec<RebroadcastResult>, Self::Error> {
+        WalletRpcClient::rebroadcast_all_transactions(&self.http_client)
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
     async fn send_coins(
         &self,
         account_index: U31,
@@ -486,6 +610,7 @@ impl WalletInterface for ClientWalletRpc {
         cost_per_block: DecimalAmount,
         margin_ratio_per_thousand: String,
         decommission_address: String,
+        vrf_public_key: Option<String>,
         config: ControllerConfig,
     ) -> Result<NewTransaction, Self::Error> {
         let options = TransactionOptions::from_controller_config(&config);
@@ -496,6 +621,7 @@ impl WalletInterface for ClientWalletRpc {
             cost_per_block.into(),
             margin_ratio_per_thousand,
             decommission_address.into(),
+            vrf_public_key.map(Into::into),
             options,
         )
         .await
@@ -638,6 +764,15 @@ impl WalletInterface for ClientWalletRpc {
             .map_err(WalletRpcError::ResponseError)
     }
 
+    async fn stake_pool_delegations_count(
+        &self,
+        pool_id: String,
+    ) -> Result<Option<usize>, Self::Error> {
+        WalletRpcClient::stake_pool_delegations_count(&self.http_client, pool_id.into())
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
     async fn list_delegation_ids(
         &self,
         account_index: U31,
@@ -665,6 +800,16 @@ impl WalletInterface for ClientWalletRpc {
             .map_err(WalletRpcError::ResponseError)
     }
 
+    async fn new_vrf_public_keys(
+        &self,
+        account_index: U31,
+        count: u32,
+    ) -> Result<Vec<VrfPublicKeyInfo>, Self::Error> {
+        ColdWalletRpcClient::new_vrf_public_keys(&self.http_client, account_index.into(), count)
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
     async fn get_vrf_public_key(
         &self,
         account_index: U31,
@@ -873,6 +1018,34 @@ impl WalletInterface for ClientWalletRpc {
         .map_err(WalletRpcError::ResponseError)
     }
 
+    async fn send_all_tokens(
+        &self,
+        account_index: U31,
+        token_id: String,
+        address: String,
+        config: ControllerConfig,
+    ) -> Result<NewTransaction, Self::Error> {
+        let options = TransactionOptions::from_controller_config(&config);
+        WalletRpcClient::send_all_tokens(
+            &self.http_client,
+            account_index.into(),
+            token_id.into(),
+            address.into(),
+            options,
+        )
+        .await
+        .map_err(WalletRpcError::ResponseError)
+    }
+
+    async fn list_token_authorities(
+        &self,
+        account_index: U31,
+    ) -> Result<Vec<TokenAuthorityInfo>, Self::Error> {
+        WalletRpcClient::list_token_authorities(&self.http_client, account_index.into())
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
     async fn make_tx_to_send_tokens_from_multisig_address(
         &self,
         account_index: U31,
@@ -1024,6 +1197,18 @@ impl WalletInterface for ClientWalletRpc {
             .map_err(WalletRpcError::ResponseError)
     }
 
+    async fn peer_limits(&self) -> Result<PeerLimits, Self::Error> {
+        WalletRpcClient::peer_limits(&self.http_client)
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
+    async fn set_peer_limits(&self, limits: PeerLimits) -> Result<(), Self::Error> {
+        WalletRpcClient::set_peer_limits(&self.http_client, limits.max_inbound, limits.max_outbound)
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
     async fn submit_block(&self, block: HexEncoded<Block>) -> Result<(), Self::Error> {
         WalletRpcClient::submit_block(&self.http_client, block)
             .await
@@ -1050,6 +1235,41 @@ impl WalletInterface for ClientWalletRpc {
         .map_err(WalletRpcError::ResponseError)
     }
 
+    async fn create_cpfp_transaction(
+        &self,
+        account_index: U31,
+        parent_tx_id: Id<Transaction>,
+        target_fee_rate: DecimalAmount,
+        config: ControllerConfig,
+    ) -> Result<NewTransaction, Self::Error> {
+        let options = TransactionOptions::from_controller_config(&config);
+        WalletRpcClient::create_cpfp_transaction(
+            &self.http_client,
+            account_index.into(),
+            HexEncoded::new(parent_tx_id),
+            target_fee_rate.into(),
+            options,
+        )
+        .await
+        .map_err(WalletRpcError::ResponseError)
+    }
+
+    async fn import_transaction(
+        &self,
+        account_index: U31,
+        transaction: SignedTransaction,
+        block_height: Option<BlockHeight>,
+    ) -> Result<(), Self::Error> {
+        WalletRpcClient::import_transaction(
+            &self.http_client,
+            account_index.into(),
+            HexEncoded::new(transaction),
+            block_height,
+        )
+        .await
+        .map_err(WalletRpcError::ResponseError)
+    }
+
     async fn list_pending_transactions(
         &self,
         account_index: U31,
@@ -1075,6 +1295,27 @@ impl WalletInterface for ClientWalletRpc {
         .map_err(WalletRpcError::ResponseError)
     }
 
+    async fn list_transactions(
+        &self,
+        account_index: U31,
+        cursor: Option<TxCursor>,
+        count: NonZeroUsize,
+    ) -> Result<TransactionListPage, Self::Error> {
+        WalletRpcClient::list_transactions(&self.http_client, account_index.into(), cursor, count)
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
+    async fn get_account_activity_summary(
+        &self,
+        account_index: U31,
+        days: u32,
+    ) -> Result<Vec<DailyActivity>, Self::Error> {
+        WalletRpcClient::get_account_activity_summary(&self.http_client, account_index.into(), days)
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
     async fn get_transaction(
         &self,
         account_index: U31,
@@ -21,24 +21,29 @@ use common::{
         block::timestamp::BlockTimestamp, partially_signed_transaction::PartiallySignedTransaction,
         Block, GenBlock, SignedTransaction, Transaction, TxOutput, UtxoOutPoint,
     },
-    primitives::{BlockHeight, DecimalAmount, Id},
+    primitives::{BlockHeight, DecimalAmount, Id, H256},
 };
 use crypto::key::{hdkd::u31::U31, PrivateKey};
 use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress, PeerId};
 use serialization::hex_encoded::HexEncoded;
 use utils_networking::IpOrSocketAddress;
-use wallet::account::TxInfo;
+use wallet::account::{
+    transaction_list::{DailyActivity, TxCursor},
+    TxInfo,
+};
 use wallet_controller::{
     types::{CreatedBlockInfo, GenericTokenTransfer, SeedWithPassPhrase, WalletInfo},
-    ConnectedPeer, ControllerConfig, UtxoState, UtxoType,
+    ConnectedPeer, ControllerConfig, PeerLimits, UtxoState, UtxoType,
 };
 use wallet_rpc_lib::types::{
-    AddressInfo, AddressWithUsageInfo, Balances, BlockInfo, ComposedTransaction, CreatedWallet,
-    DelegationInfo, LegacyVrfPublicKeyInfo, NewAccountInfo, NewDelegation, NewTransaction,
-    NftMetadata, NodeVersion, PoolInfo, PublicKeyInfo, RpcHashedTimelockContract,
-    RpcInspectTransaction, RpcSignatureStatus, RpcStandaloneAddresses, RpcTokenId,
-    SendTokensFromMultisigAddressResult, StakePoolBalance, StakingStatus,
-    StandaloneAddressWithDetails, TokenMetadata, TxOptionsOverrides, VrfPublicKeyInfo,
+    AddressInfo, AddressOwnerInfo, AddressWithUsageInfo, Balances, BlockInfo, ComposedTransaction,
+    CreatedWallet, DelegationInfo, LegacyVrfPublicKeyInfo, LookaheadStatus, NewAccountInfo,
+    NewDelegation, NewMultisigAccountInfo, NewTransaction, NftMetadata, NodeVersion,
+    OwnedTokenInfo, PoolInfo, PublicKeyInfo, RebroadcastResult, RpcHashedTimelockContract,
+    RpcInspectTransaction, RpcOutPointSourceId, RpcSignatureStatus, RpcStandaloneAddresses,
+    RpcTokenId, SendTokensFromMultisigAddressResult, StakePoolBalance, StakingStatus,
+    StandaloneAddressWithDetails, TokenAuthorityInfo, TokenMetadata, TransactionListPage,
+    TxOptionsOverrides, VrfPublicKeyInfo,
 };
 use wallet_types::with_locked::WithLocked;
 
@@ -84,6 +89,10 @@ pub trait WalletInterface {
 
     async fn wallet_info(&self) -> Result<WalletInfo, Self::Error>;
 
+    async fn account_fingerprint(&self, account_index: U31) -> Result<H256, Self::Error>;
+
+    async fn check_integrity(&self) -> Result<Vec<String>, Self::Error>;
+
     async fn sync(&self) -> Result<(), Self::Error>;
 
     async fn rescan(&self) -> Result<(), Self::Error>;
@@ -98,6 +107,8 @@ pub trait WalletInterface {
         i_know_what_i_am_doing: bool,
     ) -> Result<(), Self::Error>;
 
+    async fn lookahead_status(&self, account_index: U31) -> Result<LookaheadStatus, Self::Error>;
+
     async fn encrypt_private_keys(&self, password: String) -> Result<(), Self::Error>;
 
     async fn remove_private_key_encryption(&self) -> Result<(), Self::Error>;
@@ -110,6 +121,20 @@ pub trait WalletInterface {
 
     async fn create_account(&self, name: Option<String>) -> Result<NewAccountInfo, Self::Error>;
 
+    async fn recover_accounts(
+        &self,
+        account_indices: Vec<U31>,
+    ) -> Result<Vec<NewAccountInfo>, Self::Error>;
+
+    async fn next_multisig_account_public_key(&self) -> Result<PublicKeyInfo, Self::Error>;
+
+    async fn create_multisig_account(
+        &self,
+        min_required_signatures: u8,
+        cosigner_public_keys: Vec<String>,
+        name: Option<String>,
+    ) -> Result<NewMultisigAccountInfo, Self::Error>;
+
     async fn rename_account(
         &self,
         account_index: U31,
@@ -164,6 +189,25 @@ pub trait WalletInterface {
         address: String,
     ) -> Result<StandaloneAddressWithDetails, Self::Error>;
 
+    async fn get_address_derivation_path(
+        &self,
+        account_index: U31,
+        address: String,
+    ) -> Result<Option<String>, Self::Error>;
+
+    async fn find_account_for_address(
+        &self,
+        address: String,
+    ) -> Result<Option<AddressOwnerInfo>, Self::Error>;
+
+    async fn simulate_scan(
+        &self,
+        account_index: U31,
+        blocks: Vec<Block>,
+    ) -> Result<Vec<RpcOutPointSourceId>, Self::Error>;
+
+    async fn get_current_change_address(&self, account_index: U31) -> Result<String, Self::Error>;
+
     async fn issue_address(&self, account_index: U31) -> Result<AddressInfo, Self::Error>;
 
     async fn reveal_public_key(
@@ -179,6 +223,11 @@ pub trait WalletInterface {
         with_locked: WithLocked,
     ) -> Result<Balances, Self::Error>;
 
+    async fn list_owned_tokens(
+        &self,
+        account_index: U31,
+    ) -> Result<Vec<OwnedTokenInfo>, Self::Error>;
+
     async fn get_multisig_utxos(
         &self,
         account_index: U31,
@@ -195,6 +244,12 @@ pub trait WalletInterface {
         with_locked: WithLocked,
     ) -> Result<Vec<serde_json::Value>, Self::Error>;
 
+    async fn get_locked_utxos(
+        &self,
+        account_index: U31,
+        utxo_states: Vec<UtxoState>,
+    ) -> Result<Vec<serde_json::Value>, Self::Error>;
+
     async fn submit_raw_transaction(
         &self,
         tx: HexEncoded<SignedTransaction>,
@@ -202,6 +257,8 @@ pub trait WalletInterface {
         options: TxOptionsOverrides,
     ) -> Result<NewTransaction, Self::Error>;
 
+    async fn rebroadcast_all_transactions(&self) -> Result<Vec<RebroadcastResult>, Self::Error>;
+
     async fn sign_challenge(
         &self,
         account_index: U31,
@@ -285,6 +342,7 @@ pub trait WalletInterface {
         cost_per_block: DecimalAmount,
         margin_ratio_per_thousand: String,
         decommission_address: String,
+        vrf_public_key: Option<String>,
         config: ControllerConfig,
     ) -> Result<NewTransaction, Self::Error>;
 
@@ -344,6 +402,11 @@ pub trait WalletInterface {
 
     async fn stake_pool_balance(&self, pool_id: String) -> Result<StakePoolBalance, Self::Error>;
 
+    async fn stake_pool_delegations_count(
+        &self,
+        pool_id: String,
+    ) -> Result<Option<usize>, Self::Error>;
+
     async fn list_delegation_ids(
         &self,
         account_index: U31,
@@ -357,6 +420,12 @@ pub trait WalletInterface {
     async fn new_vrf_public_key(&self, account_index: U31)
         -> Result<VrfPublicKeyInfo, Self::Error>;
 
+    async fn new_vrf_public_keys(
+        &self,
+        account_index: U31,
+        count: u32,
+    ) -> Result<Vec<VrfPublicKeyInfo>, Self::Error>;
+
     async fn get_vrf_public_key(
         &self,
         account_index: U31,
@@ -447,6 +516,19 @@ pub trait WalletInterface {
         config: ControllerConfig,
     ) -> Result<NewTransaction, Self::Error>;
 
+    async fn send_all_tokens(
+        &self,
+        account_index: U31,
+        token_id: String,
+        address: String,
+        config: ControllerConfig,
+    ) -> Result<NewTransaction, Self::Error>;
+
+    async fn list_token_authorities(
+        &self,
+        account_index: U31,
+    ) -> Result<Vec<TokenAuthorityInfo>, Self::Error>;
+
     async fn make_tx_to_send_tokens_from_multisig_address(
         &self,
         account_index: U31,
@@ -508,6 +590,10 @@ pub trait WalletInterface {
 
     async fn remove_reserved_peer(&self, address: IpOrSocketAddress) -> Result<(), Self::Error>;
 
+    async fn peer_limits(&self) -> Result<PeerLimits, Self::Error>;
+
+    async fn set_peer_limits(&self, limits: PeerLimits) -> Result<(), Self::Error>;
+
     async fn submit_block(&self, block: HexEncoded<Block>) -> Result<(), Self::Error>;
 
     async fn chainstate_info(&self) -> Result<ChainInfo, Self::Error>;
@@ -518,6 +604,21 @@ pub trait WalletInterface {
         transaction_id: Id<Transaction>,
     ) -> Result<(), Self::Error>;
 
+    async fn create_cpfp_transaction(
+        &self,
+        account_index: U31,
+        parent_tx_id: Id<Transaction>,
+        target_fee_rate: DecimalAmount,
+        config: ControllerConfig,
+    ) -> Result<NewTransaction, Self::Error>;
+
+    async fn import_transaction(
+        &self,
+        account_index: U31,
+        transaction: SignedTransaction,
+        block_height: Option<BlockHeight>,
+    ) -> Result<(), Self::Error>;
+
     async fn list_pending_transactions(
         &self,
         account_index: U31,
@@ -530,6 +631,19 @@ pub trait WalletInterface {
         limit: usize,
     ) -> Result<Vec<TxInfo>, Self::Error>;
 
+    async fn list_transactions(
+        &self,
+        account_index: U31,
+        cursor: Option<TxCursor>,
+        count: NonZeroUsize,
+    ) -> Result<TransactionListPage, Self::Error>;
+
+    async fn get_account_activity_summary(
+        &self,
+        account_index: U31,
+        days: u32,
+    ) -> Result<Vec<DailyActivity>, Self::Error>;
+
     async fn get_transaction(
         &self,
         account_index: U31,
@@ -13,10 +13,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::path::PathBuf;
+use std::{num::NonZeroU32, path::PathBuf, time::Duration};
 
-use common::chain::config::{regtest_options::ChainConfigOptions, ChainType};
+use common::{
+    chain::config::{regtest_options::ChainConfigOptions, ChainType},
+    primitives::Amount,
+};
 use crypto::key::hdkd::u31::U31;
+use mempool::FeeRate;
 use rpc::{
     rpc_creds::{RpcCreds, RpcCredsError},
     RpcAuthData,
@@ -26,7 +30,54 @@ use utils::{
 };
 use utils_networking::NetworkAddressWithPort;
 
-use crate::config::{WalletRpcConfig, WalletServiceConfig};
+use crate::config::{WalletRpcConfig, WalletServiceConfig, WalletTxConfig};
+
+/// Options for creating and broadcasting transactions
+#[derive(clap::Args, Clone, Debug, Default)]
+pub struct WalletTxArgs {
+    /// The maximum number of attempts to submit a transaction to the node, including the first one.
+    /// Only the attempts that fail because the node can't be reached are retried.
+    #[arg(long, value_name("COUNT"))]
+    pub broadcast_max_attempts: Option<NonZeroU32>,
+
+    /// The delay before retrying a failed transaction submission (in seconds),
+    /// doubled after each failed attempt.
+    #[arg(long, value_name("DELAY"))]
+    pub broadcast_retry_delay: Option<u64>,
+
+    /// The minimum fee rate of the created transactions (in atoms per 1000 bytes).
+    /// Should match the node's minimum transaction relay fee rate if it has been changed.
+    #[arg(long, value_name("VAL"))]
+    pub min_relay_fee_rate: Option<u64>,
+
+    /// Allow creating transactions when the wallet can't sync with the node,
+    /// as long as it lags behind the node's tip by at most this number of blocks.
+    #[arg(long, value_name("BLOCKS"))]
+    pub max_sync_lag: Option<u64>,
+}
+
+impl WalletTxArgs {
+    pub fn into_config(self) -> WalletTxConfig {
+        let Self {
+            broadcast_max_attempts,
+            broadcast_retry_delay,
+            min_relay_fee_rate,
+            max_sync_lag,
+        } = self;
+
+        let mut config = WalletTxConfig::default();
+        if let Some(max_attempts) = broadcast_max_attempts {
+            config.broadcast_retry.max_attempts = max_attempts.get();
+        }
+        if let Some(delay) = broadcast_retry_delay {
+            config.broadcast_retry.base_delay = Duration::from_secs(delay);
+        }
+        config.min_relay_fee_rate = min_relay_fee_rate
+            .map(|val| FeeRate::from_amount_per_kb(Amount::from_atoms(val.into())));
+        config.max_sync_lag = max_sync_lag;
+        config
+    }
+}
 
 /// Service providing an RPC interface to a wallet
 #[derive(clap::Parser)]
@@ -149,6 +200,9 @@ pub struct WalletRpcDaemonChainArgs {
     #[arg(long, conflicts_with_all(["rpc_password", "rpc_username", "rpc_cookie_file"]))]
     rpc_no_authentication: bool,
 
+    #[clap(flatten)]
+    tx_args: WalletTxArgs,
+
     #[clap(flatten)]
     force_allow_run_as_root: utils::root_user::ForceRunAsRootOptions,
 }
@@ -172,6 +226,7 @@ impl WalletRpcDaemonChainArgs {
             rpc_password,
             rpc_no_authentication,
             cold_wallet,
+            tx_args,
             force_allow_run_as_root,
         } = self;
 
@@ -185,7 +240,8 @@ impl WalletRpcDaemonChainArgs {
                 wallet_file,
                 force_change_wallet_type,
                 start_staking_for_account,
-            );
+            )
+            .with_tx_config(tx_args.into_config());
 
             if cold_wallet {
                 service
@@ -20,7 +20,9 @@ use common::chain::config::{
     ChainConfig, ChainType,
 };
 use crypto::key::hdkd::u31::U31;
+use mempool::FeeRate;
 use rpc::{rpc_creds::RpcCreds, RpcAuthData};
+use wallet_controller::{BroadcastRetryConfig, ControllerConfig};
 
 #[derive(Clone)]
 pub enum NodeRpc {
@@ -50,6 +52,9 @@ pub struct WalletServiceConfig {
 
     /// Node rpc settings
     pub node_rpc: NodeRpc,
+
+    /// How the wallet creates and broadcasts transactions
+    pub tx_config: WalletTxConfig,
 }
 
 impl WalletServiceConfig {
@@ -65,9 +70,15 @@ impl WalletServiceConfig {
             force_change_wallet_type,
             start_staking_for_account,
             node_rpc: NodeRpc::ColdWallet,
+            tx_config: WalletTxConfig::default(),
         }
     }
 
+    pub fn with_tx_config(mut self, tx_config: WalletTxConfig) -> Self {
+        self.tx_config = tx_config;
+        self
+    }
+
     pub fn with_regtest_options(self, options: ChainConfigOptions) -> anyhow::Result<Self> {
         Ok(self.with_custom_chain_config(Arc::new(regtest_chain_config(&options)?)))
     }
@@ -127,6 +138,39 @@ impl WalletServiceConfig {
     }
 }
 
+/// Options for creating and broadcasting transactions, applied to all the wallet commands
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WalletTxConfig {
+    /// How to retry submitting a transaction to the node if the node can't be reached
+    pub broadcast_retry: BroadcastRetryConfig,
+
+    /// The minimum fee rate used for the created transactions, in case the fee rate estimated
+    /// by the node is lower than what its mempool accepts
+    pub min_relay_fee_rate: Option<FeeRate>,
+
+    /// The maximum number of blocks the wallet can lag behind the node when creating transactions
+    /// if it fails to sync; the wallet must be fully synced if not set
+    pub max_sync_lag: Option<u64>,
+}
+
+impl WalletTxConfig {
+    /// Make a controller config for a command that broadcasts the created transactions
+    pub fn controller_config(&self, in_top_x_mb: usize) -> ControllerConfig {
+        let Self {
+            broadcast_retry,
+            min_relay_fee_rate,
+            max_sync_lag,
+        } = *self;
+        ControllerConfig {
+            in_top_x_mb,
+            broadcast_to_mempool: true,
+            min_relay_fee_rate,
+            max_sync_lag,
+            broadcast_retry,
+        }
+    }
+}
+
 /// Configuration options for the wallet RPC interface
 pub struct WalletRpcConfig {
     /// Address to listen on
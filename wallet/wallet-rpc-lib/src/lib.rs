@@ -20,10 +20,11 @@ mod service;
 
 pub use rpc::{
     types, ColdWalletRpcClient, ColdWalletRpcDescription, ColdWalletRpcServer, RpcCreds, RpcError,
-    WalletEventsRpcServer, WalletRpc, WalletRpcClient, WalletRpcDescription, WalletRpcServer,
+    WalletEventsRpcClient, WalletEventsRpcServer, WalletRpc, WalletRpcClient, WalletRpcDescription,
+    WalletRpcServer,
 };
 pub use service::{
-    CreatedWallet, Event, EventStream, TxState, WalletHandle,
+    AccountEvent, CreatedWallet, Event, EventStream, TxState, WalletHandle,
     /* WalletResult, */ WalletService,
 };
 use wallet_controller::{NodeInterface, NodeRpcClient};
@@ -121,6 +122,7 @@ where
             node_rpc,
             rpc_config,
             chain_config,
+            wallet_config.tx_config,
             cold_wallet,
         )
         .await
@@ -22,33 +22,41 @@ use common::{
         transaction::partially_signed_transaction::PartiallySignedTransaction, Block, DelegationId,
         Destination, GenBlock, PoolId, SignedTransaction, Transaction, TxOutput,
     },
-    primitives::{BlockHeight, Id},
+    primitives::{BlockHeight, Id, H256},
 };
-use crypto::key::PrivateKey;
+use crypto::{key::PrivateKey, vrf::VRFPublicKey};
 use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress};
 use rpc::types::RpcHexString;
-use wallet::account::TxInfo;
+use wallet::account::{
+    transaction_list::{DailyActivity, TxCursor},
+    TxInfo,
+};
 use wallet_controller::{
     types::{BlockInfo, CreatedBlockInfo, GenericTokenTransfer, SeedWithPassPhrase, WalletInfo},
-    ConnectedPeer,
+    ConnectedPeer, PeerLimits,
 };
 use wallet_types::with_locked::WithLocked;
 
 use crate::types::{
-    AccountArg, AddressInfo, AddressWithUsageInfo, Balances, ChainInfo, ComposedTransaction,
-    CreatedWallet, DelegationInfo, HexEncoded, JsonValue, LegacyVrfPublicKeyInfo,
-    MaybeSignedTransaction, NewAccountInfo, NewDelegation, NewTransaction, NftMetadata,
-    NodeVersion, PoolInfo, PublicKeyInfo, RpcAmountIn, RpcHashedTimelockContract,
-    RpcInspectTransaction, RpcStandaloneAddresses, RpcTokenId, RpcUtxoOutpoint, RpcUtxoState,
-    RpcUtxoType, SendTokensFromMultisigAddressResult, StakePoolBalance, StakingStatus,
-    StandaloneAddressWithDetails, TokenMetadata, TransactionOptions, TxOptionsOverrides,
-    VrfPublicKeyInfo,
+    AccountArg, AddressInfo, AddressOwnerInfo, AddressWithUsageInfo, Balances, ChainInfo,
+    ComposedTransaction, CreatedWallet, DelegationInfo, HexEncoded, JsonValue,
+    LegacyVrfPublicKeyInfo, LookaheadStatus, MaybeSignedTransaction, NewAccountInfo, NewDelegation,
+    NewMultisigAccountInfo, NewTransaction, NftMetadata, NodeVersion, OwnedTokenInfo, PoolInfo,
+    PublicKeyInfo, RebroadcastResult, RpcAmountIn, RpcHashedTimelockContract,
+    RpcInspectTransaction, RpcOutPointSourceId, RpcStandaloneAddresses, RpcTokenId,
+    RpcUtxoOutpoint, RpcUtxoState, RpcUtxoType, SendTokensFromMultisigAddressResult,
+    StakePoolBalance, StakingStatus, StandaloneAddressWithDetails, TokenAuthorityInfo,
+    TokenMetadata, TransactionListPage, TransactionOptions, TxOptionsOverrides, VrfPublicKeyInfo,
 };
 
-#[rpc::rpc(server)]
+#[rpc::rpc(server, client)]
 trait WalletEventsRpc {
     #[subscription(name = "subscribe_wallet_events", item = Event)]
     async fn subscribe_wallet_events(&self) -> rpc::subscription::Reply;
+
+    /// Subscribe to the events of a single account, including changes of its confirmed balance
+    #[subscription(name = "subscribe_account_events", item = AccountEvent)]
+    async fn subscribe_account_events(&self, account: AccountArg) -> rpc::subscription::Reply;
 }
 
 /// RPC methods available in the cold wallet mode.
@@ -89,6 +97,16 @@ trait ColdWalletRpc {
     #[method(name = "wallet_info")]
     async fn wallet_info(&self) -> rpc::RpcResult<WalletInfo>;
 
+    /// Show the fingerprint of the selected account, a hash of its extended public key.
+    /// Wallets restored from the same seed phrase and passphrase have the same fingerprints.
+    #[method(name = "wallet_account_fingerprint")]
+    async fn account_fingerprint(&self, account: AccountArg) -> rpc::RpcResult<H256>;
+
+    /// Check that the wallet database is internally consistent.
+    /// Returns the descriptions of the found inconsistencies, empty if there are none.
+    #[method(name = "wallet_check_integrity")]
+    async fn check_integrity(&self) -> rpc::RpcResult<Vec<String>>;
+
     /// Encrypts the private keys with a new password, expects the wallet to be unlocked
     #[method(name = "wallet_encrypt_private_keys")]
     async fn encrypt_private_keys(&self, password: String) -> rpc::RpcResult<()>;
@@ -127,6 +145,11 @@ trait ColdWalletRpc {
         i_know_what_i_am_doing: bool,
     ) -> rpc::RpcResult<()>;
 
+    /// Show the configured lookahead size of the account, the last used receive-address index
+    /// and how many more receive-addresses can be issued before the lookahead size is exhausted.
+    #[method(name = "wallet_lookahead_status")]
+    async fn lookahead_status(&self, account: AccountArg) -> rpc::RpcResult<LookaheadStatus>;
+
     /// Show receive-addresses with their usage state.
     /// Note that whether an address is used isn't based on the wallet,
     /// but on the blockchain. So if an address is used in a transaction,
@@ -153,6 +176,43 @@ trait ColdWalletRpc {
         address: RpcAddress<Destination>,
     ) -> rpc::RpcResult<StandaloneAddressWithDetails>;
 
+    /// Show the derivation path of an address issued by the selected account.
+    /// Returns null if the address is not derived from the wallet's key chain,
+    /// e.g. if it's a standalone address or doesn't belong to the wallet.
+    #[method(name = "address_derivation_path")]
+    async fn get_address_derivation_path(
+        &self,
+        account: AccountArg,
+        address: RpcAddress<Destination>,
+    ) -> rpc::RpcResult<Option<String>>;
+
+    /// Find which account of the wallet owns the given address and whether the address is derived
+    /// from the account's key chain or was added to it as a standalone address.
+    /// Returns null if no account owns the address.
+    #[method(name = "address_lookup")]
+    async fn find_account_for_address(
+        &self,
+        address: RpcAddress<Destination>,
+    ) -> rpc::RpcResult<Option<AddressOwnerInfo>>;
+
+    /// Find the transactions and block rewards in the given blocks that would be recognised as
+    /// belonging to the selected account if the blocks were scanned.
+    /// Neither the wallet nor its database are changed.
+    #[method(name = "wallet_simulate_scan")]
+    async fn simulate_scan(
+        &self,
+        account: AccountArg,
+        blocks: Vec<HexEncoded<Block>>,
+    ) -> rpc::RpcResult<Vec<RpcOutPointSourceId>>;
+
+    /// Show the change address that the next transaction created by the selected account would use.
+    /// Unlike address_new, this doesn't issue a new address.
+    #[method(name = "address_current_change")]
+    async fn get_current_change_address(
+        &self,
+        account: AccountArg,
+    ) -> rpc::RpcResult<RpcAddress<Destination>>;
+
     /// Generate a new unused address
     #[method(name = "address_new")]
     async fn issue_address(&self, account: AccountArg) -> rpc::RpcResult<AddressInfo>;
@@ -175,6 +235,15 @@ trait ColdWalletRpc {
     #[method(name = "staking_new_vrf_public_key")]
     async fn new_vrf_public_key(&self, account: AccountArg) -> rpc::RpcResult<VrfPublicKeyInfo>;
 
+    /// Issue the given number of new staking VRF keys for this account at once.
+    /// Either all the keys are issued or, if they don't fit in the key lookahead, none of them.
+    #[method(name = "staking_new_vrf_public_keys")]
+    async fn new_vrf_public_keys(
+        &self,
+        account: AccountArg,
+        count: u32,
+    ) -> rpc::RpcResult<Vec<VrfPublicKeyInfo>>;
+
     /// Shows the legacy VRF key that uses an abandoned derivation mechanism.
     /// This will not be used for new pools and should be avoided
     #[method(name = "staking_show_legacy_vrf_key")]
@@ -262,6 +331,35 @@ trait WalletRpc {
     #[method(name = "account_create")]
     async fn create_account(&self, name: Option<String>) -> rpc::RpcResult<NewAccountInfo>;
 
+    /// Creates the accounts with the specified indexes, even if the previous accounts do not have
+    /// a transaction history, e.g. when only some of the accounts of a wallet need to be recovered.
+    /// Any missing accounts with lower indexes are created as well, as account indexes cannot have gaps.
+    /// The new accounts are rescanned from the genesis block.
+    /// Returns all the created accounts, including the missing ones with lower indexes.
+    #[method(name = "account_recover")]
+    async fn recover_accounts(
+        &self,
+        accounts: Vec<AccountArg>,
+    ) -> rpc::RpcResult<Vec<NewAccountInfo>>;
+
+    /// Returns the public key the next created account contributes to a multisig account.
+    /// Share it with the other cosigners, so they can create the same multisig account.
+    #[method(name = "account_next_multisig_public_key")]
+    async fn next_multisig_account_public_key(&self) -> rpc::RpcResult<PublicKeyInfo>;
+
+    /// Creates a new M-of-N multisig account from the public keys of the other cosigners,
+    /// as returned by `account_next_multisig_public_key` in their wallets.
+    /// The balance and the transactions of the new account use the funds sent to the returned
+    /// multisig address, which also receives the change.
+    /// Returns an error if the last created account does not have a transaction history.
+    #[method(name = "account_create_multisig")]
+    async fn create_multisig_account(
+        &self,
+        min_required_signatures: u8,
+        cosigner_public_keys: Vec<RpcAddress<Destination>>,
+        name: Option<String>,
+    ) -> rpc::RpcResult<NewMultisigAccountInfo>;
+
     /// Renames the selected account with an optional name.
     /// If the name is not specified, it will remove any existing name for the account.
     #[method(name = "account_rename")]
@@ -333,10 +431,21 @@ trait WalletRpc {
         with_locked: Option<WithLocked>,
     ) -> rpc::RpcResult<Balances>;
 
+    /// List the tokens owned by the selected account, together with the ticker, the number of
+    /// decimals and the frozen state of each token. Only confirmed and unlocked utxos are counted.
+    #[method(name = "account_token_balances")]
+    async fn list_owned_tokens(&self, account: AccountArg) -> rpc::RpcResult<Vec<OwnedTokenInfo>>;
+
     /// Lists all the utxos owned by this account
     #[method(name = "account_utxos")]
     async fn get_utxos(&self, account: AccountArg) -> rpc::RpcResult<Vec<JsonValue>>;
 
+    /// Lists the utxos owned by this account that are still locked by a timelock, together with
+    /// the time at which they unlock. For the block height based timelocks, the time is estimated
+    /// from the target block spacing.
+    #[method(name = "account_locked_utxos")]
+    async fn get_locked_utxos(&self, account: AccountArg) -> rpc::RpcResult<Vec<JsonValue>>;
+
     /// Submits a transaction to mempool, and if it is valid, broadcasts it to the network
     #[method(name = "node_submit_transaction")]
     async fn submit_raw_transaction(
@@ -346,6 +455,11 @@ trait WalletRpc {
         options: TxOptionsOverrides,
     ) -> rpc::RpcResult<NewTransaction>;
 
+    /// Submit all the transactions created by the wallet that are not confirmed yet to the node
+    /// again. Returns the outcome for each of the transactions.
+    #[method(name = "transaction_rebroadcast_all")]
+    async fn rebroadcast_all_transactions(&self) -> rpc::RpcResult<Vec<RebroadcastResult>>;
+
     /// Send a given coin amount to a given address. The wallet will automatically calculate the required information
     /// Optionally, one can also mention the utxos to be used.
     #[method(name = "address_send")]
@@ -415,6 +529,8 @@ trait WalletRpc {
     /// and handed to the staking pool. After subtracting the cost per block, a fraction equal to
     /// margin ratio is taken from what is left, and given to the staking pool. Finally, what is left
     /// is distributed among delegators, pro-rata, based on their delegation amounts.
+    /// Optionally, an externally held VRF public key can be given to be used by the pool instead of
+    /// the one derived from the account. This is useful for cold staking setups.
     #[method(name = "staking_create_pool")]
     async fn create_stake_pool(
         &self,
@@ -423,6 +539,7 @@ trait WalletRpc {
         cost_per_block: RpcAmountIn,
         margin_ratio_per_thousand: String,
         decommission_address: RpcAddress<Destination>,
+        vrf_public_key: Option<RpcAddress<VRFPublicKey>>,
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewTransaction>;
 
@@ -515,6 +632,13 @@ trait WalletRpc {
         pool_id: RpcAddress<PoolId>,
     ) -> rpc::RpcResult<StakePoolBalance>;
 
+    /// Print the number of delegations in a staking pool, or null if the pool is not found
+    #[method(name = "staking_pool_delegations_count")]
+    async fn stake_pool_delegations_count(
+        &self,
+        pool_id: RpcAddress<PoolId>,
+    ) -> rpc::RpcResult<Option<usize>>;
+
     /// List delegation ids controlled by the selected account in this wallet with their balances
     #[method(name = "delegation_list_ids")]
     async fn list_delegation_ids(&self, account: AccountArg)
@@ -638,6 +762,25 @@ trait WalletRpc {
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewTransaction>;
 
+    /// Send the whole confirmed and unlocked balance of a given token to a given address.
+    /// All such token utxos are spent, so no token change is returned to the wallet.
+    #[method(name = "token_send_all")]
+    async fn send_all_tokens(
+        &self,
+        account: AccountArg,
+        token_id: RpcAddress<TokenId>,
+        address: RpcAddress<Destination>,
+        options: TransactionOptions,
+    ) -> rpc::RpcResult<NewTransaction>;
+
+    /// List the tokens whose authority is owned by the selected account in this wallet,
+    /// together with the authority address
+    #[method(name = "token_list_owned")]
+    async fn list_token_authorities(
+        &self,
+        account: AccountArg,
+    ) -> rpc::RpcResult<Vec<TokenAuthorityInfo>>;
+
     /// Create a transaction for sending tokens from a multisig address to other addresses, returning the change to
     /// the original multisig address.
     ///
@@ -740,6 +883,16 @@ trait WalletRpc {
     #[method(name = "node_remove_reserved_peer")]
     async fn remove_reserved_peer(&self, address: String) -> rpc::RpcResult<()>;
 
+    /// Get the maximum numbers of inbound and automatic outbound connections of the node
+    #[method(name = "node_get_peer_limits")]
+    async fn peer_limits(&self) -> rpc::RpcResult<PeerLimits>;
+
+    /// Change the maximum numbers of inbound and automatic outbound connections of the node.
+    /// Existing connections are kept, the new limits apply to new connections only.
+    /// The outbound limit must not be zero.
+    #[method(name = "node_set_peer_limits")]
+    async fn set_peer_limits(&self, max_inbound: usize, max_outbound: usize) -> rpc::RpcResult<()>;
+
     /// Submit a block to be included in the chain
     #[method(name = "node_submit_block")]
     async fn submit_block(&self, block: HexEncoded<Block>) -> rpc::RpcResult<()>;
@@ -758,6 +911,30 @@ trait WalletRpc {
         transaction_id: HexEncoded<Id<Transaction>>,
     ) -> rpc::RpcResult<()>;
 
+    /// Speed up an unconfirmed transaction by creating a child transaction that spends one of its outputs
+    /// owned by the wallet (child pays for parent). The child pays enough fee for both transactions together
+    /// to reach the target fee rate, given in coins per 1000 bytes.
+    #[method(name = "transaction_cpfp")]
+    async fn create_cpfp_transaction(
+        &self,
+        account: AccountArg,
+        parent_transaction_id: HexEncoded<Id<Transaction>>,
+        target_fee_rate: RpcAmountIn,
+        options: TransactionOptions,
+    ) -> rpc::RpcResult<NewTransaction>;
+
+    /// Import a transaction that was created outside of this wallet into the account's history,
+    /// e.g. one that was missed while syncing from a pruned node. At least one of its inputs or outputs
+    /// must belong to the account. If the block height is specified, the transaction is recorded
+    /// as confirmed at that height, otherwise it is recorded as pending.
+    #[method(name = "transaction_import")]
+    async fn import_transaction(
+        &self,
+        account: AccountArg,
+        transaction: HexEncoded<SignedTransaction>,
+        block_height: Option<BlockHeight>,
+    ) -> rpc::RpcResult<()>;
+
     /// List the pending transactions that can be abandoned
     #[method(name = "transaction_list_pending")]
     async fn list_pending_transactions(
@@ -774,6 +951,26 @@ trait WalletRpc {
         limit: usize,
     ) -> rpc::RpcResult<Vec<TxInfo>>;
 
+    /// List the transactions of the account, starting with the unconfirmed ones and then from
+    /// the most recent block. Returns up to `count` transactions following the cursor, or the
+    /// first ones if no cursor is given, and the cursor to get the next page with.
+    #[method(name = "transaction_list")]
+    async fn list_transactions(
+        &self,
+        account: AccountArg,
+        cursor: Option<TxCursor>,
+        count: NonZeroUsize,
+    ) -> rpc::RpcResult<TransactionListPage>;
+
+    /// Get the number of confirmed transactions and the net coin flow of the account
+    /// for each day of the given period, ending at the current time
+    #[method(name = "account_activity_summary")]
+    async fn get_account_activity_summary(
+        &self,
+        account: AccountArg,
+        days: u32,
+    ) -> rpc::RpcResult<Vec<DailyActivity>>;
+
     /// Get a transaction from the wallet, if present
     #[method(name = "transaction_get")]
     async fn get_transaction(
@@ -866,6 +1063,15 @@ trait WalletRpc {
     #[method(name = "node_get_block")]
     async fn node_block(&self, block_id: Id<Block>) -> rpc::RpcResult<Option<HexEncoded<Block>>>;
 
+    /// Get consecutive mainchain blocks starting at the given height, represented with hex encoded bytes.
+    /// At most 100 blocks are returned per call; fewer are returned if the tip of the chain is reached.
+    #[method(name = "node_get_blocks_by_height_range")]
+    async fn node_blocks_by_height_range(
+        &self,
+        from: BlockHeight,
+        count: u32,
+    ) -> rpc::RpcResult<Vec<HexEncoded<Block>>>;
+
     /// Returns mainchain block ids with heights in the range start_height..end_height using
     /// the given step.
     #[method(name = "node_get_block_ids_as_checkpoints")]
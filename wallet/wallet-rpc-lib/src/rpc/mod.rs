@@ -27,19 +27,25 @@ use std::{
 };
 
 use chainstate::{tx_verifier::check_transaction, ChainInfo, TokenIssuanceError};
-use crypto::key::{hdkd::u31::U31, PrivateKey, PublicKey};
-use mempool::tx_accumulator::PackingStrategy;
+use crypto::{
+    key::{hdkd::u31::U31, PrivateKey, PublicKey},
+    vrf::VRFPublicKey,
+};
+use mempool::{tx_accumulator::PackingStrategy, FeeRate};
 use mempool_types::tx_options::TxOptionsOverrides;
 use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress, PeerId};
 use serialization::{hex_encoded::HexEncoded, Decode, DecodeAll};
+use tokio::sync::mpsc;
 use types::RpcHashedTimelockContract;
 use utils::{ensure, shallow_clone::ShallowClone};
 use utils_networking::IpOrSocketAddress;
 use wallet::{
     account::{
-        currency_grouper::Currency, transaction_list::TransactionList, PoolData, TransactionToSign,
-        TxInfo,
+        currency_grouper::Currency,
+        transaction_list::{DailyActivity, TransactionList, TxCursor},
+        PoolData, TransactionToSign, TxInfo,
     },
+    wallet::TokenAuthority,
     WalletError,
 };
 
@@ -59,36 +65,43 @@ use common::{
         Transaction, TxOutput, UtxoOutPoint,
     },
     primitives::{
-        id::WithId, per_thousand::PerThousand, time::Time, Amount, BlockHeight, Id, Idable,
+        id::WithId, per_thousand::PerThousand, time::Time, Amount, BlockHeight, Id, Idable, H256,
     },
 };
 pub use interface::{
-    ColdWalletRpcClient, ColdWalletRpcDescription, ColdWalletRpcServer, WalletEventsRpcServer,
-    WalletRpcClient, WalletRpcDescription, WalletRpcServer,
+    ColdWalletRpcClient, ColdWalletRpcDescription, ColdWalletRpcServer, WalletEventsRpcClient,
+    WalletEventsRpcServer, WalletRpcClient, WalletRpcDescription, WalletRpcServer,
 };
 pub use rpc::{rpc_creds::RpcCreds, Rpc};
 use wallet_controller::{
+    synced_controller::submit_transaction_with_retry,
     types::{
         Balances, BlockInfo, CreatedBlockInfo, GenericTokenTransfer, InspectTransaction,
-        SeedWithPassPhrase, TransactionToInspect, WalletInfo,
+        OwnedTokenInfo, SeedWithPassPhrase, TransactionToInspect, WalletInfo,
     },
-    ConnectedPeer, ControllerConfig, ControllerError, NodeInterface, UtxoState, UtxoStates,
-    UtxoType, UtxoTypes, DEFAULT_ACCOUNT_INDEX,
+    ConnectedPeer, ControllerConfig, ControllerError, NodeInterface, PeerLimits, UtxoState,
+    UtxoStates, UtxoType, UtxoTypes, DEFAULT_ACCOUNT_INDEX,
 };
 use wallet_types::{
     account_info::StandaloneAddressDetails, seed_phrase::StoreSeedPhrase,
     signature_status::SignatureStatus, wallet_tx::TxData, with_locked::WithLocked,
 };
 
-use crate::{service::CreatedWallet, WalletHandle, WalletRpcConfig};
+use crate::{
+    config::WalletTxConfig,
+    service::{AccountEvent, CreatedWallet, Event},
+    WalletHandle, WalletRpcConfig,
+};
 
 pub use self::types::RpcError;
 use self::types::{
-    AddressInfo, AddressWithUsageInfo, DelegationInfo, LegacyVrfPublicKeyInfo, NewAccountInfo,
-    NewTransaction, PoolInfo, PublicKeyInfo, RpcAddress, RpcAmountIn, RpcHexString,
+    AddressInfo, AddressOwnerInfo, AddressWithUsageInfo, DelegationInfo, LegacyVrfPublicKeyInfo,
+    LookaheadStatus, NewAccountInfo, NewMultisigAccountInfo, NewTransaction, PoolInfo,
+    PublicKeyInfo, RebroadcastResult, RpcAddress, RpcAmountIn, RpcHexString, RpcOutPointSourceId,
     RpcStandaloneAddress, RpcStandaloneAddressDetails, RpcStandaloneAddresses,
     RpcStandalonePrivateKeyAddress, RpcTokenId, RpcUtxoOutpoint, StakingStatus,
-    StandaloneAddressWithDetails, VrfPublicKeyInfo,
+    StandaloneAddressWithDetails, TokenAuthorityInfo, TransactionListEntry, TransactionListPage,
+    VrfPublicKeyInfo,
 };
 
 #[derive(Clone)]
@@ -96,16 +109,26 @@ pub struct WalletRpc<N: Clone> {
     wallet: WalletHandle<N>,
     node: N,
     chain_config: Arc<ChainConfig>,
+    tx_config: WalletTxConfig,
 }
 
 type WRpcResult<T, N> = Result<T, RpcError<N>>;
 
+/// How many account events can be buffered before the subscriber receives them
+const ACCOUNT_EVENTS_CHANNEL_CAPACITY: usize = 100;
+
 impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
-    pub fn new(wallet: WalletHandle<N>, node: N, chain_config: Arc<ChainConfig>) -> Self {
+    pub fn new(
+        wallet: WalletHandle<N>,
+        node: N,
+        chain_config: Arc<ChainConfig>,
+        tx_config: WalletTxConfig,
+    ) -> Self {
         Self {
             wallet,
             node,
             chain_config,
+            tx_config,
         }
     }
 
@@ -117,6 +140,10 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         &self.chain_config
     }
 
+    pub fn controller_config(&self, in_top_x_mb: usize) -> ControllerConfig {
+        self.tx_config.controller_config(in_top_x_mb)
+    }
+
     pub fn shutdown(&self) -> WRpcResult<(), N> {
         self.wallet.shallow_clone().stop().map_err(RpcError::SubmitError)
     }
@@ -177,6 +204,16 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             .await?
     }
 
+    pub async fn lookahead_status(&self, account_index: U31) -> WRpcResult<LookaheadStatus, N> {
+        let status = self
+            .wallet
+            .call(move |controller| {
+                controller.readonly_controller(account_index).get_lookahead_status()
+            })
+            .await??;
+        Ok(status)
+    }
+
     pub async fn encrypt_private_keys(&self, password: String) -> WRpcResult<(), N> {
         self.wallet.call(|w| w.encrypt_wallet(&Some(password))).await?
     }
@@ -258,6 +295,73 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         Ok(NewAccountInfo::new(num, name))
     }
 
+    pub async fn next_multisig_account_public_key(&self) -> WRpcResult<PublicKeyInfo, N> {
+        let public_key = self.wallet.call(|w| w.next_multisig_account_public_key()).await??;
+        Ok(PublicKeyInfo::new(public_key, &self.chain_config))
+    }
+
+    pub async fn create_multisig_account(
+        &self,
+        min_required_signatures: u8,
+        cosigner_public_keys: Vec<RpcAddress<Destination>>,
+        name: Option<String>,
+    ) -> WRpcResult<NewMultisigAccountInfo, N> {
+        let min_required_signatures =
+            NonZeroU8::new(min_required_signatures).ok_or(RpcError::InvalidMultisigMinSignature)?;
+        let cosigner_public_keys = self.decode_multisig_public_keys(cosigner_public_keys)?;
+
+        let account_name = name.clone();
+        let (num, challenge) = self
+            .wallet
+            .call(move |w| {
+                w.create_multisig_account(min_required_signatures, cosigner_public_keys, name)
+            })
+            .await??;
+        Ok(NewMultisigAccountInfo::new(
+            num,
+            account_name,
+            &challenge,
+            &self.chain_config,
+        ))
+    }
+
+    fn decode_multisig_public_keys(
+        &self,
+        public_keys: Vec<RpcAddress<Destination>>,
+    ) -> WRpcResult<Vec<PublicKey>, N> {
+        public_keys
+            .into_iter()
+            .enumerate()
+            .map(|(idx, addr)| {
+                addr.decode_object(&self.chain_config)
+                    .map_err(|_| RpcError::MultisigNotPublicKey(idx))
+                    .and_then(|dest| match dest {
+                        Destination::PublicKey(pk) => Ok(pk),
+                        Destination::PublicKeyHash(_)
+                        | Destination::AnyoneCanSpend
+                        | Destination::ScriptHash(_)
+                        | Destination::ClassicMultisig(_) => {
+                            Err(RpcError::MultisigNotPublicKey(idx))
+                        }
+                    })
+            })
+            .collect()
+    }
+
+    pub async fn recover_accounts(
+        &self,
+        account_indices: Vec<U31>,
+    ) -> WRpcResult<Vec<NewAccountInfo>, N> {
+        let new_accounts = self
+            .wallet
+            .call(move |w| w.recover_accounts(account_indices.into_iter().collect()))
+            .await??;
+        Ok(new_accounts
+            .into_iter()
+            .map(|account_index| NewAccountInfo::new(account_index, None))
+            .collect())
+    }
+
     pub async fn update_account_name(
         &self,
         account_index: U31,
@@ -280,6 +384,9 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         let config = ControllerConfig {
             in_top_x_mb: 5,
             broadcast_to_mempool: true,
+            min_relay_fee_rate: None,
+            max_sync_lag: None,
+            broadcast_retry: Default::default(),
         }; // irrelevant for issuing addresses
         self.wallet
             .call_async(move |w| {
@@ -314,6 +421,9 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         let config = ControllerConfig {
             in_top_x_mb: 5,
             broadcast_to_mempool: true,
+            min_relay_fee_rate: None,
+            max_sync_lag: None,
+            broadcast_retry: Default::default(),
         }; // irrelevant for issuing addresses
         self.wallet
             .call_async(move |w| {
@@ -344,6 +454,9 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         let config = ControllerConfig {
             in_top_x_mb: 5,
             broadcast_to_mempool: true,
+            min_relay_fee_rate: None,
+            max_sync_lag: None,
+            broadcast_retry: Default::default(),
         }; // irrelevant for issuing addresses
         self.wallet
             .call_async(move |w| {
@@ -375,27 +488,14 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         let config = ControllerConfig {
             in_top_x_mb: 5,
             broadcast_to_mempool: true,
+            min_relay_fee_rate: None,
+            max_sync_lag: None,
+            broadcast_retry: Default::default(),
         }; // irrelevant for issuing addresses
         let min_required_signatures =
             NonZeroU8::new(min_required_signatures).ok_or(RpcError::InvalidMultisigMinSignature)?;
 
-        let public_keys = public_keys
-            .into_iter()
-            .enumerate()
-            .map(|(idx, addr)| {
-                addr.decode_object(&self.chain_config)
-                    .map_err(|_| RpcError::MultisigNotPublicKey(idx))
-                    .and_then(|dest| match dest {
-                        Destination::PublicKey(pk) => Ok(pk),
-                        Destination::PublicKeyHash(_)
-                        | Destination::AnyoneCanSpend
-                        | Destination::ScriptHash(_)
-                        | Destination::ClassicMultisig(_) => {
-                            Err(RpcError::MultisigNotPublicKey(idx))
-                        }
-                    })
-            })
-            .collect::<WRpcResult<Vec<PublicKey>, N>>()?;
+        let public_keys = self.decode_multisig_public_keys(public_keys)?;
 
         let challenge = ClassicMultisigChallenge::new(
             &self.chain_config,
@@ -433,6 +533,9 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         let config = ControllerConfig {
             in_top_x_mb: 5,
             broadcast_to_mempool: true,
+            min_relay_fee_rate: None,
+            max_sync_lag: None,
+            broadcast_retry: Default::default(),
         }; // irrelevant for issuing addresses
         let (child_number, destination) = self
             .wallet
@@ -453,6 +556,9 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         let config = ControllerConfig {
             in_top_x_mb: 5,
             broadcast_to_mempool: true,
+            min_relay_fee_rate: None,
+            max_sync_lag: None,
+            broadcast_retry: Default::default(),
         }; // irrelevant for issuing addresses
         let address = address
             .decode_object(&self.chain_config)
@@ -489,6 +595,9 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         let config = ControllerConfig {
             in_top_x_mb: 5,
             broadcast_to_mempool: true,
+            min_relay_fee_rate: None,
+            max_sync_lag: None,
+            broadcast_retry: Default::default(),
         }; // irrelevant for issuing addresses
         self.wallet
             .call_async(move |w| {
@@ -500,6 +609,32 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             .map(|(child_number, vrf_key)| VrfPublicKeyInfo::new(vrf_key, child_number, false))
     }
 
+    pub async fn issue_vrf_keys(
+        &self,
+        account_index: U31,
+        count: u32,
+    ) -> WRpcResult<Vec<VrfPublicKeyInfo>, N> {
+        let config = ControllerConfig {
+            in_top_x_mb: 5,
+            broadcast_to_mempool: true,
+            min_relay_fee_rate: None,
+            max_sync_lag: None,
+            broadcast_retry: Default::default(),
+        }; // irrelevant for issuing addresses
+        let keys = self
+            .wallet
+            .call_async(move |w| {
+                Box::pin(async move {
+                    w.synced_controller(account_index, config).await?.new_vrf_keys(count)
+                })
+            })
+            .await??;
+        Ok(keys
+            .into_iter()
+            .map(|(child_number, vrf_key)| VrfPublicKeyInfo::new(vrf_key, child_number, false))
+            .collect())
+    }
+
     pub async fn get_vrf_key_usage(
         &self,
         account_index: U31,
@@ -535,6 +670,26 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         Ok(txs)
     }
 
+    pub async fn get_transaction_list_from_cursor(
+        &self,
+        account_index: U31,
+        cursor: Option<TxCursor>,
+        count: NonZeroUsize,
+    ) -> WRpcResult<TransactionListPage, N> {
+        let (txs, next_cursor) = self
+            .wallet
+            .call(move |controller| {
+                controller
+                    .readonly_controller(account_index)
+                    .get_transaction_list_from_cursor(cursor, count)
+            })
+            .await??;
+        Ok(TransactionListPage {
+            transactions: txs.iter().map(TransactionListEntry::new).collect(),
+            next_cursor,
+        })
+    }
+
     pub async fn get_issued_addresses(
         &self,
         account_index: U31,
@@ -585,6 +740,53 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         Ok(result)
     }
 
+    pub async fn get_current_change_address(
+        &self,
+        account_index: U31,
+    ) -> WRpcResult<RpcAddress<Destination>, N> {
+        let address = self
+            .wallet
+            .call(move |controller| {
+                controller.readonly_controller(account_index).get_current_change_address()
+            })
+            .await??;
+        Ok(address.into())
+    }
+
+    pub async fn get_address_derivation_path(
+        &self,
+        account_index: U31,
+        address: RpcAddress<Destination>,
+    ) -> WRpcResult<Option<String>, N> {
+        let address = address
+            .decode_object(&self.chain_config)
+            .map_err(|_| RpcError::InvalidAddress)?;
+
+        let path = self
+            .wallet
+            .call(move |controller| {
+                controller
+                    .readonly_controller(account_index)
+                    .get_address_derivation_path(&address)
+            })
+            .await??;
+        Ok(path.map(|path| path.to_string()))
+    }
+
+    pub async fn simulate_scan(
+        &self,
+        account_index: U31,
+        blocks: Vec<Block>,
+    ) -> WRpcResult<Vec<RpcOutPointSourceId>, N> {
+        let source_ids = self
+            .wallet
+            .call(move |controller| {
+                controller.readonly_controller(account_index).simulate_scan(&blocks)
+            })
+            .await??;
+        Ok(source_ids.into_iter().map(RpcOutPointSourceId::from).collect())
+    }
+
     pub async fn get_standalone_address_details(
         &self,
         account_index: U31,
@@ -674,6 +876,120 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         Ok(balances)
     }
 
+    async fn get_confirmed_balances(
+        &self,
+        account_index: U31,
+    ) -> WRpcResult<BTreeMap<Currency, Amount>, N> {
+        self.wallet
+            .call(move |w| {
+                w.readonly_controller(account_index)
+                    .get_balance(UtxoState::Confirmed.into(), WithLocked::Unlocked)
+            })
+            .await?
+    }
+
+    /// Subscribe to the events of the given account.
+    ///
+    /// The confirmed balance of the account is checked again every time the wallet scans a block
+    /// or updates the account, and a `BalanceChanged` event is sent for each currency whose balance
+    /// differs from the previous one. The events stop once the receiver is dropped.
+    pub async fn subscribe_account_events(
+        &self,
+        account_index: U31,
+    ) -> WRpcResult<mpsc::Receiver<AccountEvent>, N> {
+        let mut wallet_events = self.wallet.subscribe().await?;
+        let mut balances = self.get_confirmed_balances(account_index).await?;
+        let (event_tx, event_rx) = mpsc::channel(ACCOUNT_EVENTS_CHANNEL_CAPACITY);
+        let this = self.clone();
+        let account_idx = account_index.into_u32();
+
+        tokio::spawn(async move {
+            let mut seen_txs = BTreeSet::new();
+
+            while let Some(event) = wallet_events.recv().await {
+                let mut account_events = Vec::new();
+
+                match event {
+                    Event::NewBlock {} => match this.best_block().await {
+                        Ok(best_block) => account_events.push(AccountEvent::BlockScanned {
+                            height: best_block.height,
+                        }),
+                        Err(err) => account_events.push(AccountEvent::Error {
+                            message: err.to_string(),
+                        }),
+                    },
+                    Event::TxUpdated {
+                        account_idx: idx,
+                        tx_id,
+                        state: _,
+                    } if idx == account_idx => {
+                        if seen_txs.insert(tx_id) {
+                            account_events.push(AccountEvent::NewTransaction { tx_id });
+                        }
+                    }
+                    Event::TxDropped {
+                        account_idx: idx, ..
+                    }
+                    | Event::RewardAdded {
+                        account_idx: idx, ..
+                    }
+                    | Event::RewardDropped {
+                        account_idx: idx, ..
+                    } if idx == account_idx => {}
+                    Event::TxUpdated { .. }
+                    | Event::TxDropped { .. }
+                    | Event::RewardAdded { .. }
+                    | Event::RewardDropped { .. } => continue,
+                }
+
+                match this.get_confirmed_balances(account_index).await {
+                    Ok(new_balances) => {
+                        let currencies =
+                            balances.keys().chain(new_balances.keys()).collect::<BTreeSet<_>>();
+                        for currency in currencies {
+                            let amount =
+                                new_balances.get(currency).copied().unwrap_or(Amount::ZERO);
+                            if balances.get(currency).copied().unwrap_or(Amount::ZERO) != amount {
+                                account_events.push(AccountEvent::BalanceChanged {
+                                    currency: currency.clone(),
+                                    amount,
+                                });
+                            }
+                        }
+                        balances = new_balances;
+                    }
+                    Err(err) => account_events.push(AccountEvent::Error {
+                        message: err.to_string(),
+                    }),
+                }
+
+                for account_event in account_events {
+                    if event_tx.send(account_event).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(event_rx)
+    }
+
+    pub async fn list_owned_tokens(
+        &self,
+        account_index: U31,
+    ) -> WRpcResult<Vec<OwnedTokenInfo>, N> {
+        let owned_tokens = self
+            .wallet
+            .call_async(move |w| {
+                Box::pin(async move {
+                    let c = w.readonly_controller(account_index);
+                    c.get_owned_tokens(UtxoState::Confirmed.into(), WithLocked::Unlocked).await
+                })
+            })
+            .await??;
+        Ok(owned_tokens)
+    }
+
     pub async fn get_multisig_utxos(
         &self,
         account_index: U31,
@@ -707,6 +1023,16 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             .await?
     }
 
+    pub async fn get_locked_utxos(
+        &self,
+        account_index: U31,
+        utxo_states: UtxoStates,
+    ) -> WRpcResult<Vec<(UtxoOutPoint, TxOutput, BlockTimestamp)>, N> {
+        self.wallet
+            .call(move |w| w.readonly_controller(account_index).get_locked_utxos(utxo_states))
+            .await?
+    }
+
     pub async fn get_transaction(
         &self,
         account_index: U31,
@@ -753,6 +1079,36 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             .await?
     }
 
+    pub async fn get_account_activity_summary(
+        &self,
+        account_index: U31,
+        days: u32,
+    ) -> WRpcResult<Vec<DailyActivity>, N> {
+        self.wallet
+            .call(move |w| w.readonly_controller(account_index).get_activity_summary(days))
+            .await?
+    }
+
+    /// Submit all the transactions created by the wallet that are not confirmed yet to the node again
+    pub async fn rebroadcast_all_transactions(&self) -> WRpcResult<Vec<RebroadcastResult>, N> {
+        let txs = self
+            .wallet
+            .call(|controller| controller.get_pending_transactions_to_be_broadcast())
+            .await??;
+
+        let mut results = Vec::with_capacity(txs.len());
+        for tx in txs {
+            let tx_id = tx.transaction().get_id();
+            let error = self
+                .submit_raw_transaction(HexEncoded::new(tx), true, TxOptionsOverrides::default())
+                .await
+                .err()
+                .map(|err| err.to_string());
+            results.push(RebroadcastResult { tx_id, error });
+        }
+        Ok(results)
+    }
+
     pub async fn submit_raw_transaction(
         &self,
         tx: HexEncoded<SignedTransaction>,
@@ -767,16 +1123,23 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             ))
         })?;
         let tx_id = tx.transaction().get_id();
-        self.node
-            .submit_transaction(tx.clone(), options)
-            .await
-            .map_err(RpcError::RpcError)?;
+        submit_transaction_with_retry(
+            &self.node,
+            tx.clone(),
+            options,
+            self.tx_config.broadcast_retry,
+        )
+        .await
+        .map_err(RpcError::RpcError)?;
 
         let store_tx_in_wallet = !do_not_store;
         if store_tx_in_wallet {
             let config = ControllerConfig {
                 in_top_x_mb: 5,
                 broadcast_to_mempool: true,
+                min_relay_fee_rate: None,
+                max_sync_lag: None,
+                broadcast_retry: Default::default(),
             }; // irrelevant for issuing addresses
             self.wallet
                 .call_async(move |controller| {
@@ -840,6 +1203,9 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         let config = ControllerConfig {
             in_top_x_mb: 5,
             broadcast_to_mempool: true,
+            min_relay_fee_rate: None,
+            max_sync_lag: None,
+            broadcast_retry: Default::default(),
         }; // irrelevant for issuing addresses
         let destination = address
             .decode_object(&self.chain_config)
@@ -875,6 +1241,31 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         Ok(())
     }
 
+    /// Submit a transaction created by a synced controller with deferred submission to the node,
+    /// unless broadcasting is disabled in the config.
+    ///
+    /// The synced controller has already marked the transaction as unconfirmed in the same
+    /// wallet command that created it, so only the submission happens here, after the command
+    /// has finished; this way the wallet can handle other commands while it's being retried.
+    async fn submit_if_needed(
+        &self,
+        config: ControllerConfig,
+        tx: &SignedTransaction,
+    ) -> WRpcResult<(), N> {
+        if !config.broadcast_to_mempool {
+            return Ok(());
+        }
+
+        submit_transaction_with_retry(
+            &self.node,
+            tx.clone(),
+            TxOptionsOverrides::default(),
+            config.broadcast_retry,
+        )
+        .await
+        .map_err(RpcError::RpcError)
+    }
+
     pub async fn sweep_addresses(
         &self,
         account_index: U31,
@@ -891,19 +1282,23 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             .map(|a| a.decode_object(&self.chain_config).map_err(|_| RpcError::InvalidAddress))
             .collect::<Result<BTreeSet<Destination>, _>>()?;
 
-        self.wallet
+        let tx = self
+            .wallet
             .call_async(move |controller| {
                 Box::pin(async move {
                     controller
                         .synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .sweep_addresses(destination_address, from_addresses)
                         .await
                         .map_err(RpcError::Controller)
-                        .map(NewTransaction::new)
                 })
             })
-            .await?
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(NewTransaction::new(tx))
     }
 
     pub async fn sweep_delegation(
@@ -920,19 +1315,23 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             .into_address(self.chain_config())
             .map_err(|_| RpcError::InvalidAddress)?;
 
-        self.wallet
+        let tx = self
+            .wallet
             .call_async(move |controller| {
                 Box::pin(async move {
                     controller
                         .synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .sweep_delegation(destination_address, delegation_id)
                         .await
                         .map_err(RpcError::Controller)
-                        .map(NewTransaction::new)
                 })
             })
-            .await?
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(NewTransaction::new(tx))
     }
 
     pub async fn send_coins(
@@ -948,18 +1347,23 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         let address =
             address.into_address(&self.chain_config).map_err(|_| RpcError::InvalidAddress)?;
 
-        self.wallet
+        let tx = self
+            .wallet
             .call_async(move |controller| {
                 Box::pin(async move {
                     controller
                         .synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .send_to_address(address, amount, selected_utxos)
                         .await
                         .map_err(RpcError::Controller)
                 })
             })
-            .await?
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(tx)
     }
 
     pub async fn request_send_coins(
@@ -1038,7 +1442,8 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         let address =
             address.into_address(&self.chain_config).map_err(|_| RpcError::InvalidAddress)?;
 
-        self.wallet
+        let tx = self
+            .wallet
             .call_async(move |controller| {
                 Box::pin(async move {
                     let token_info = controller.get_token_info(token_id).await?;
@@ -1049,13 +1454,50 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
                     controller
                         .synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .send_tokens_to_address(token_info, address, amount)
                         .await
                         .map_err(RpcError::Controller)
-                        .map(NewTransaction::new)
                 })
             })
-            .await?
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(NewTransaction::new(tx))
+    }
+
+    pub async fn send_all_tokens(
+        &self,
+        account_index: U31,
+        token_id: RpcAddress<TokenId>,
+        address: RpcAddress<Destination>,
+        config: ControllerConfig,
+    ) -> WRpcResult<NewTransaction, N> {
+        let token_id = token_id
+            .decode_object(&self.chain_config)
+            .map_err(|_| RpcError::InvalidTokenId)?;
+        let address =
+            address.into_address(&self.chain_config).map_err(|_| RpcError::InvalidAddress)?;
+
+        let tx = self
+            .wallet
+            .call_async(move |controller| {
+                Box::pin(async move {
+                    let token_info = controller.get_token_info(token_id).await?;
+
+                    controller
+                        .synced_controller(account_index, config)
+                        .await?
+                        .with_deferred_submission()
+                        .send_all_tokens_to_address(token_info, address)
+                        .await
+                        .map_err(RpcError::Controller)
+                })
+            })
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(NewTransaction::new(tx))
     }
 
     pub async fn make_tx_to_send_tokens_from_multisig_address(
@@ -1184,6 +1626,7 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         cost_per_block: RpcAmountIn,
         margin_ratio_per_thousand: String,
         decommission_address: RpcAddress<Destination>,
+        vrf_public_key: Option<RpcAddress<VRFPublicKey>>,
         config: ControllerConfig,
     ) -> WRpcResult<SignedTransaction, N> {
         let decimals = self.chain_config.coin_decimals();
@@ -1198,23 +1641,37 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             .decode_object(&self.chain_config)
             .map_err(|_| RpcError::InvalidAddress)?;
 
-        self.wallet
+        let vrf_public_key = vrf_public_key
+            .map(|vrf_public_key| {
+                vrf_public_key
+                    .decode_object(&self.chain_config)
+                    .map_err(|_| RpcError::InvalidVrfPublicKey)
+            })
+            .transpose()?;
+
+        let tx = self
+            .wallet
             .call_async(move |controller| {
                 Box::pin(async move {
                     controller
                         .synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .create_stake_pool_tx(
                             amount,
                             decommission_destination,
                             margin_ratio_per_thousand,
                             cost_per_block,
+                            vrf_public_key,
                         )
                         .await
                         .map_err(RpcError::Controller)
                 })
             })
-            .await?
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(tx)
     }
 
     pub async fn decommission_stake_pool(
@@ -1231,18 +1688,23 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             .map(|a| a.decode_object(&self.chain_config).map_err(|_| RpcError::InvalidAddress))
             .transpose()?;
 
-        self.wallet
+        let tx = self
+            .wallet
             .call_async(move |controller| {
                 Box::pin(async move {
                     controller
                         .synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .decommission_stake_pool(pool_id, output_address)
                         .await
                         .map_err(RpcError::Controller)
                 })
             })
-            .await?
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(tx)
     }
 
     pub async fn decommission_stake_pool_request(
@@ -1286,25 +1748,25 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         let pool_id =
             pool_id.decode_object(&self.chain_config).map_err(|_| RpcError::InvalidPoolId)?;
 
-        self.wallet
+        let (tx, delegation_id) = self
+            .wallet
             .call_async(move |controller| {
                 Box::pin(async move {
                     controller
                         .synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .create_delegation(address, pool_id)
                         .await
                         .map_err(RpcError::Controller)
                 })
             })
-            .await?
-            .map(|(tx, delegation_id)| {
-                (
-                    tx,
-                    RpcAddress::new(&self.chain_config, delegation_id)
-                        .expect("addressable delegation id"),
-                )
-            })
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        let delegation_id =
+            RpcAddress::new(&self.chain_config, delegation_id).expect("addressable delegation id");
+        Ok((tx, delegation_id))
     }
 
     pub async fn delegate_staking(
@@ -1321,18 +1783,23 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             .decode_object(&self.chain_config)
             .map_err(|_| RpcError::InvalidDelegationId)?;
 
-        self.wallet
+        let tx = self
+            .wallet
             .call_async(move |controller| {
                 Box::pin(async move {
                     controller
                         .synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .delegate_staking(amount, delegation_id)
                         .await
                         .map_err(RpcError::Controller)
                 })
             })
-            .await?
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(tx)
     }
 
     pub async fn withdraw_from_delegation(
@@ -1351,24 +1818,32 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             .decode_object(&self.chain_config)
             .map_err(|_| RpcError::InvalidDelegationId)?;
 
-        self.wallet
+        let tx = self
+            .wallet
             .call_async(move |controller| {
                 Box::pin(async move {
                     controller
                         .synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .send_to_address_from_delegation(address, amount, delegation_id)
                         .await
                         .map_err(RpcError::Controller)
                 })
             })
-            .await?
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(tx)
     }
 
     pub async fn start_staking(&self, account_index: U31) -> WRpcResult<(), N> {
         let config = ControllerConfig {
             in_top_x_mb: 5,
             broadcast_to_mempool: true,
+            min_relay_fee_rate: None,
+            max_sync_lag: None,
+            broadcast_retry: Default::default(),
         }; // irrelevant for issuing addresses
 
         self.wallet
@@ -1432,7 +1907,8 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             .transpose()?;
         let coin_decimals = self.chain_config.coin_decimals();
 
-        self.wallet
+        let tx = self
+            .wallet
             .call_async(move |controller| {
                 Box::pin(async move {
                     let value = match token_id {
@@ -1454,12 +1930,16 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
                     controller
                         .synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .create_htlc_tx(value, htlc)
                         .await
                         .map_err(RpcError::Controller)
                 })
             })
-            .await?
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(tx)
     }
 
     pub async fn compose_transaction(
@@ -1507,6 +1987,9 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         let config = ControllerConfig {
             in_top_x_mb: 5,
             broadcast_to_mempool: true,
+            min_relay_fee_rate: None,
+            max_sync_lag: None,
+            broadcast_retry: Default::default(),
         }; // irrelevant for issuing addresses
         self.wallet
             .call_async(move |w| {
@@ -1519,25 +2002,84 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             .await?
     }
 
+    pub async fn create_cpfp_transaction(
+        &self,
+        account_index: U31,
+        parent_tx_id: Id<Transaction>,
+        target_fee_rate: RpcAmountIn,
+        config: ControllerConfig,
+    ) -> WRpcResult<NewTransaction, N> {
+        let decimals = self.chain_config.coin_decimals();
+        let target_fee_rate = FeeRate::from_amount_per_kb(
+            target_fee_rate.to_amount(decimals).ok_or(RpcError::InvalidCoinAmount)?,
+        );
+
+        let tx = self
+            .wallet
+            .call_async(move |controller| {
+                Box::pin(async move {
+                    controller
+                        .synced_controller(account_index, config)
+                        .await?
+                        .with_deferred_submission()
+                        .create_cpfp_transaction(parent_tx_id, target_fee_rate)
+                        .await
+                        .map_err(RpcError::Controller)
+                })
+            })
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(NewTransaction::new(tx))
+    }
+
+    pub async fn import_transaction(
+        &self,
+        account_index: U31,
+        transaction: SignedTransaction,
+        block_height: Option<BlockHeight>,
+    ) -> WRpcResult<(), N> {
+        let config = ControllerConfig {
+            in_top_x_mb: 5,
+            broadcast_to_mempool: true,
+            min_relay_fee_rate: None,
+            max_sync_lag: None,
+            broadcast_retry: Default::default(),
+        }; // irrelevant for importing transactions
+        self.wallet
+            .call_async(move |w| {
+                Box::pin(async move {
+                    w.synced_controller(account_index, config)
+                        .await?
+                        .import_external_transaction(transaction, block_height)
+                })
+            })
+            .await?
+    }
+
     pub async fn deposit_data(
         &self,
         account_index: U31,
         data: Vec<u8>,
         config: ControllerConfig,
     ) -> WRpcResult<NewTransaction, N> {
-        self.wallet
+        let tx = self
+            .wallet
             .call_async(move |controller| {
                 Box::pin(async move {
                     controller
                         .synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .deposit_data(data)
                         .await
                         .map_err(RpcError::Controller)
-                        .map(NewTransaction::new)
                 })
             })
-            .await?
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(NewTransaction::new(tx))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -1563,11 +2105,13 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             .into_address(&self.chain_config)
             .map_err(|_| RpcError::InvalidAddress)?;
 
-        self.wallet
+        let (tx, token_id) = self
+            .wallet
             .call_async(move |w| {
                 Box::pin(async move {
                     w.synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .issue_new_token(
                             destination_address,
                             token_ticker,
@@ -1579,12 +2123,14 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
                         .await
                 })
             })
-            .await?
-            .map(|(tx, token_id)| RpcTokenId {
-                tx_id: tx.transaction().get_id(),
-                token_id: RpcAddress::new(&self.chain_config, token_id)
-                    .expect("Encoding token id should never fail"),
-            })
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(RpcTokenId {
+            tx_id: tx.transaction().get_id(),
+            token_id: RpcAddress::new(&self.chain_config, token_id)
+                .expect("Encoding token id should never fail"),
+        })
     }
 
     pub async fn issue_new_nft(
@@ -1596,21 +2142,25 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
     ) -> WRpcResult<RpcTokenId, N> {
         let address =
             address.into_address(&self.chain_config).map_err(|_| RpcError::InvalidAddress)?;
-        self.wallet
+        let (tx, token_id) = self
+            .wallet
             .call_async(move |w| {
                 Box::pin(async move {
                     w.synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .issue_new_nft(address, metadata)
                         .await
                 })
             })
-            .await?
-            .map(|(tx, token_id)| RpcTokenId {
-                tx_id: tx.transaction().get_id(),
-                token_id: RpcAddress::new(&self.chain_config, token_id)
-                    .expect("Encoding token id should never fail"),
-            })
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(RpcTokenId {
+            tx_id: tx.transaction().get_id(),
+            token_id: RpcAddress::new(&self.chain_config, token_id)
+                .expect("Encoding token id should never fail"),
+        })
     }
 
     pub async fn mint_tokens(
@@ -1627,7 +2177,8 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         let address =
             address.into_address(&self.chain_config).map_err(|_| RpcError::InvalidAddress)?;
 
-        self.wallet
+        let tx = self
+            .wallet
             .call_async(move |w| {
                 Box::pin(async move {
                     let token_info = w.get_token_info(token_id).await?;
@@ -1638,13 +2189,16 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
 
                     w.synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .mint_tokens(token_info, amount, address)
                         .await
                         .map_err(RpcError::Controller)
-                        .map(NewTransaction::new)
                 })
             })
-            .await?
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(NewTransaction::new(tx))
     }
 
     pub async fn unmint_tokens(
@@ -1658,7 +2212,8 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             .decode_object(&self.chain_config)
             .map_err(|_| RpcError::InvalidTokenId)?;
 
-        self.wallet
+        let tx = self
+            .wallet
             .call_async(move |w| {
                 Box::pin(async move {
                     let token_info = w.get_token_info(token_id).await?;
@@ -1669,13 +2224,16 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
 
                     w.synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .unmint_tokens(token_info, amount)
                         .await
                         .map_err(RpcError::Controller)
-                        .map(NewTransaction::new)
                 })
             })
-            .await?
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(NewTransaction::new(tx))
     }
 
     pub async fn lock_token_supply(
@@ -1687,20 +2245,24 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         let token_id = token_id
             .decode_object(&self.chain_config)
             .map_err(|_| RpcError::InvalidTokenId)?;
-        self.wallet
+        let tx = self
+            .wallet
             .call_async(move |w| {
                 Box::pin(async move {
                     let token_info = w.get_token_info(token_id).await?;
 
                     w.synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .lock_token_supply(token_info)
                         .await
                         .map_err(RpcError::Controller)
-                        .map(NewTransaction::new)
                 })
             })
-            .await?
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(NewTransaction::new(tx))
     }
 
     pub async fn freeze_token(
@@ -1713,20 +2275,24 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         let token_id = token_id
             .decode_object(&self.chain_config)
             .map_err(|_| RpcError::InvalidTokenId)?;
-        self.wallet
+        let tx = self
+            .wallet
             .call_async(move |w| {
                 Box::pin(async move {
                     let token_info = w.get_token_info(token_id).await?;
 
                     w.synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .freeze_token(token_info, is_unfreezable)
                         .await
                         .map_err(RpcError::Controller)
-                        .map(NewTransaction::new)
                 })
             })
-            .await?
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(NewTransaction::new(tx))
     }
 
     pub async fn unfreeze_token(
@@ -1738,20 +2304,24 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         let token_id = token_id
             .decode_object(&self.chain_config)
             .map_err(|_| RpcError::InvalidTokenId)?;
-        self.wallet
+        let tx = self
+            .wallet
             .call_async(move |w| {
                 Box::pin(async move {
                     let token_info = w.get_token_info(token_id).await?;
 
                     w.synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .unfreeze_token(token_info)
                         .await
                         .map_err(RpcError::Controller)
-                        .map(NewTransaction::new)
                 })
             })
-            .await?
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(NewTransaction::new(tx))
     }
 
     pub async fn change_token_authority(
@@ -1766,20 +2336,24 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             .map_err(|_| RpcError::InvalidTokenId)?;
         let address =
             address.into_address(&self.chain_config).map_err(|_| RpcError::InvalidAddress)?;
-        self.wallet
+        let tx = self
+            .wallet
             .call_async(move |w| {
                 Box::pin(async move {
                     let token_info = w.get_token_info(token_id).await?;
 
                     w.synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .change_token_authority(token_info, address)
                         .await
                         .map_err(RpcError::Controller)
-                        .map(NewTransaction::new)
                 })
             })
-            .await?
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(NewTransaction::new(tx))
     }
 
     pub async fn change_token_metadata_uri(
@@ -1792,20 +2366,24 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         let token_id = token_id
             .decode_object(&self.chain_config)
             .map_err(|_| RpcError::InvalidTokenId)?;
-        self.wallet
+        let tx = self
+            .wallet
             .call_async(move |w| {
                 Box::pin(async move {
                     let token_info = w.get_token_info(token_id).await?;
 
                     w.synced_controller(account_index, config)
                         .await?
+                        .with_deferred_submission()
                         .change_token_metadata_uri(token_info, metadata_uri.into_bytes())
                         .await
                         .map_err(RpcError::Controller)
-                        .map(NewTransaction::new)
                 })
             })
-            .await?
+            .await??;
+
+        self.submit_if_needed(config, &tx).await?;
+        Ok(NewTransaction::new(tx))
     }
 
     pub async fn rescan(&self) -> WRpcResult<(), N> {
@@ -1885,6 +2463,25 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             })
     }
 
+    pub async fn list_token_authorities(
+        &self,
+        account_index: U31,
+    ) -> WRpcResult<Vec<TokenAuthorityInfo>, N> {
+        self.wallet
+            .call(move |controller| {
+                controller.readonly_controller(account_index).get_token_authorities()
+            })
+            .await?
+            .map(|tokens: Vec<TokenAuthority>| {
+                tokens
+                    .into_iter()
+                    .map(|token| {
+                        TokenAuthorityInfo::new(token.token_id, token.authority, &self.chain_config)
+                    })
+                    .collect()
+            })
+    }
+
     pub async fn list_created_blocks_ids(
         &self,
         account_index: U31,
@@ -1910,6 +2507,34 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             .await?
     }
 
+    pub async fn account_fingerprint(&self, account_index: U31) -> WRpcResult<H256, N> {
+        self.wallet
+            .call(move |controller| {
+                controller.readonly_controller(account_index).account_fingerprint()
+            })
+            .await?
+    }
+
+    pub async fn find_account_for_address(
+        &self,
+        address: RpcAddress<Destination>,
+    ) -> WRpcResult<Option<AddressOwnerInfo>, N> {
+        let address = address
+            .decode_object(&self.chain_config)
+            .map_err(|_| RpcError::InvalidAddress)?;
+
+        let owner = self
+            .wallet
+            .call(move |controller| controller.find_account_for_address(address))
+            .await??;
+        Ok(owner.map(|(account, origin)| AddressOwnerInfo::new(account, origin)))
+    }
+
+    pub async fn check_integrity(&self) -> WRpcResult<Vec<String>, N> {
+        let violations = self.wallet.call(move |controller| controller.check_integrity()).await??;
+        Ok(violations.into_iter().map(|violation| violation.to_string()).collect())
+    }
+
     pub async fn stake_pool_balance(
         &self,
         pool_id: RpcAddress<PoolId>,
@@ -1924,6 +2549,18 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
             .map(|balance| balance.into_fixedpoint_str(self.chain_config.coin_decimals())))
     }
 
+    pub async fn stake_pool_delegations_count(
+        &self,
+        pool_id: RpcAddress<PoolId>,
+    ) -> WRpcResult<Option<usize>, N> {
+        let pool_id =
+            pool_id.decode_object(&self.chain_config).map_err(|_| RpcError::InvalidPoolId)?;
+        self.node
+            .get_stake_pool_delegations_count(pool_id)
+            .await
+            .map_err(RpcError::RpcError)
+    }
+
     pub async fn node_version(&self) -> WRpcResult<String, N> {
         self.node.node_version().await.map_err(RpcError::RpcError)
     }
@@ -1984,6 +2621,14 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         self.node.p2p_remove_reserved_node(address).await.map_err(RpcError::RpcError)
     }
 
+    pub async fn peer_limits(&self) -> WRpcResult<PeerLimits, N> {
+        self.node.p2p_get_peer_limits().await.map_err(RpcError::RpcError)
+    }
+
+    pub async fn set_peer_limits(&self, limits: PeerLimits) -> WRpcResult<(), N> {
+        self.node.p2p_set_peer_limits(limits).await.map_err(RpcError::RpcError)
+    }
+
     pub async fn submit_block(&self, block: HexEncoded<Block>) -> WRpcResult<(), N> {
         self.node.submit_block(block.take()).await.map_err(RpcError::RpcError)
     }
@@ -2011,6 +2656,18 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletRpc<N> {
         self.node.get_block(block_id).await.map_err(RpcError::RpcError)
     }
 
+    /// Get up to `count` consecutive mainchain blocks starting at height `from`,
+    /// capped at `MAX_BLOCKS_PER_CALL` blocks per call
+    pub async fn get_blocks_by_height_range(
+        &self,
+        from: BlockHeight,
+        count: u32,
+    ) -> WRpcResult<Vec<Block>, N> {
+        wallet_controller::get_blocks_by_height_range(&self.node, from, count)
+            .await
+            .map_err(RpcError::RpcError)
+    }
+
     pub async fn node_get_block_ids_as_checkpoints(
         &self,
         start_height: BlockHeight,
@@ -2029,6 +2686,7 @@ pub async fn start<N: NodeInterface + Clone + Send + Sync + Debug + 'static>(
     node_rpc: N,
     config: WalletRpcConfig,
     chain_config: Arc<ChainConfig>,
+    tx_config: WalletTxConfig,
     cold_wallet: bool,
 ) -> anyhow::Result<rpc::Rpc> {
     let WalletRpcConfig {
@@ -2036,7 +2694,7 @@ pub async fn start<N: NodeInterface + Clone + Send + Sync + Debug + 'static>(
         auth_credentials,
     } = config;
 
-    let wallet_rpc = WalletRpc::new(wallet_handle, node_rpc, chain_config);
+    let wallet_rpc = WalletRpc::new(wallet_handle, node_rpc, chain_config, tx_config);
     let builder = rpc::Builder::new(bind_addr, auth_credentials)
         .with_method_list("list_methods")
         .register(ColdWalletRpcServer::into_rpc(wallet_rpc.clone()));
@@ -24,16 +24,22 @@ use common::{
         Block, DelegationId, Destination, GenBlock, PoolId, SignedTransaction, Transaction,
         TxOutput,
     },
-    primitives::{time::Time, BlockHeight, Id, Idable},
+    primitives::{time::Time, BlockHeight, Id, Idable, H256},
 };
-use crypto::key::PrivateKey;
+use crypto::{key::PrivateKey, vrf::VRFPublicKey};
 use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress, PeerId};
 use serialization::{hex::HexEncode, json_encoded::JsonEncoded};
 use utils_networking::IpOrSocketAddress;
-use wallet::{account::TxInfo, version::get_version};
+use wallet::{
+    account::{
+        transaction_list::{DailyActivity, TxCursor},
+        TxInfo,
+    },
+    version::get_version,
+};
 use wallet_controller::{
     types::{BlockInfo, CreatedBlockInfo, GenericTokenTransfer, SeedWithPassPhrase, WalletInfo},
-    ConnectedPeer, ControllerConfig, NodeInterface, UtxoState, UtxoStates, UtxoType, UtxoTypes,
+    ConnectedPeer, NodeInterface, PeerLimits, UtxoState, UtxoStates, UtxoType, UtxoTypes,
 };
 use wallet_types::{
     seed_phrase::StoreSeedPhrase, signature_status::SignatureStatus, with_locked::WithLocked,
@@ -42,14 +48,16 @@ use wallet_types::{
 use crate::{
     rpc::{ColdWalletRpcServer, WalletEventsRpcServer, WalletRpc, WalletRpcServer},
     types::{
-        AccountArg, AddressInfo, AddressWithUsageInfo, Balances, ChainInfo, ComposedTransaction,
-        CreatedWallet, DelegationInfo, HexEncoded, JsonValue, LegacyVrfPublicKeyInfo,
-        MaybeSignedTransaction, NewAccountInfo, NewDelegation, NewTransaction, NftMetadata,
-        NodeVersion, PoolInfo, PublicKeyInfo, RpcAddress, RpcAmountIn, RpcHexString,
-        RpcInspectTransaction, RpcStandaloneAddresses, RpcTokenId, RpcUtxoOutpoint, RpcUtxoState,
-        RpcUtxoType, SendTokensFromMultisigAddressResult, StakePoolBalance, StakingStatus,
-        StandaloneAddressWithDetails, TokenMetadata, TransactionOptions, TxOptionsOverrides,
-        UtxoInfo, VrfPublicKeyInfo,
+        AccountArg, AddressInfo, AddressOwnerInfo, AddressWithUsageInfo, Balances, ChainInfo,
+        ComposedTransaction, CreatedWallet, DelegationInfo, HexEncoded, JsonValue,
+        LegacyVrfPublicKeyInfo, LockedUtxoInfo, LookaheadStatus, MaybeSignedTransaction,
+        NewAccountInfo, NewDelegation, NewMultisigAccountInfo, NewTransaction, NftMetadata,
+        NodeVersion, OwnedTokenInfo, PoolInfo, PublicKeyInfo, RebroadcastResult, RpcAddress,
+        RpcAmountIn, RpcHexString, RpcInspectTransaction, RpcOutPointSourceId,
+        RpcStandaloneAddresses, RpcTokenId, RpcUtxoOutpoint, RpcUtxoState, RpcUtxoType,
+        SendTokensFromMultisigAddressResult, StakePoolBalance, StakingStatus,
+        StandaloneAddressWithDetails, TokenAuthorityInfo, TokenMetadata, TransactionListPage,
+        TransactionOptions, TxOptionsOverrides, UtxoInfo, VrfPublicKeyInfo,
     },
     RpcError,
 };
@@ -67,6 +75,21 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletEventsRpcSe
         let wallet_events = self.wallet.subscribe().await?;
         rpc::subscription::connect_broadcast(wallet_events, pending).await
     }
+
+    async fn subscribe_account_events(
+        &self,
+        pending: rpc::subscription::Pending,
+        account: AccountArg,
+    ) -> rpc::subscription::Reply {
+        let mut account_events = self.subscribe_account_events(account.index::<N>()?).await?;
+        let subscription = rpc::subscription::accept(pending).await?;
+
+        while let Some(event) = account_events.recv().await {
+            subscription.send(&event).await?;
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -130,6 +153,14 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> ColdWalletRpcServ
         rpc::handle_result(self.wallet_info().await)
     }
 
+    async fn account_fingerprint(&self, account_arg: AccountArg) -> rpc::RpcResult<H256> {
+        rpc::handle_result(self.account_fingerprint(account_arg.index::<N>()?).await)
+    }
+
+    async fn check_integrity(&self) -> rpc::RpcResult<Vec<String>> {
+        rpc::handle_result(self.check_integrity().await)
+    }
+
     async fn get_seed_phrase(&self) -> rpc::RpcResult<Option<SeedWithPassPhrase>> {
         rpc::handle_result(self.get_seed_phrase().await)
     }
@@ -146,6 +177,10 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> ColdWalletRpcServ
         rpc::handle_result(self.set_lookahead_size(lookahead_size, i_know_what_i_am_doing).await)
     }
 
+    async fn lookahead_status(&self, account_arg: AccountArg) -> rpc::RpcResult<LookaheadStatus> {
+        rpc::handle_result(self.lookahead_status(account_arg.index::<N>()?).await)
+    }
+
     async fn encrypt_private_keys(&self, password: String) -> rpc::RpcResult<()> {
         rpc::handle_result(self.encrypt_private_keys(password).await)
     }
@@ -162,6 +197,13 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> ColdWalletRpcServ
         rpc::handle_result(self.lock_private_keys().await)
     }
 
+    async fn get_current_change_address(
+        &self,
+        account_arg: AccountArg,
+    ) -> rpc::RpcResult<RpcAddress<Destination>> {
+        rpc::handle_result(self.get_current_change_address(account_arg.index::<N>()?).await)
+    }
+
     async fn issue_address(&self, account_arg: AccountArg) -> rpc::RpcResult<AddressInfo> {
         rpc::handle_result(self.issue_address(account_arg.index::<N>()?).await)
     }
@@ -191,6 +233,32 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> ColdWalletRpcServ
         )
     }
 
+    async fn get_address_derivation_path(
+        &self,
+        account_arg: AccountArg,
+        address: RpcAddress<Destination>,
+    ) -> rpc::RpcResult<Option<String>> {
+        rpc::handle_result(
+            self.get_address_derivation_path(account_arg.index::<N>()?, address).await,
+        )
+    }
+
+    async fn find_account_for_address(
+        &self,
+        address: RpcAddress<Destination>,
+    ) -> rpc::RpcResult<Option<AddressOwnerInfo>> {
+        rpc::handle_result(self.find_account_for_address(address).await)
+    }
+
+    async fn simulate_scan(
+        &self,
+        account_arg: AccountArg,
+        blocks: Vec<HexEncoded<Block>>,
+    ) -> rpc::RpcResult<Vec<RpcOutPointSourceId>> {
+        let blocks = blocks.into_iter().map(HexEncoded::take).collect();
+        rpc::handle_result(self.simulate_scan(account_arg.index::<N>()?, blocks).await)
+    }
+
     async fn get_issued_addresses(
         &self,
         account_arg: AccountArg,
@@ -205,6 +273,14 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> ColdWalletRpcServ
         rpc::handle_result(self.issue_vrf_key(account_arg.index::<N>()?).await)
     }
 
+    async fn new_vrf_public_keys(
+        &self,
+        account_arg: AccountArg,
+        count: u32,
+    ) -> rpc::RpcResult<Vec<VrfPublicKeyInfo>> {
+        rpc::handle_result(self.issue_vrf_keys(account_arg.index::<N>()?, count).await)
+    }
+
     async fn get_vrf_public_key(
         &self,
         account_arg: AccountArg,
@@ -225,10 +301,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> ColdWalletRpcServ
         raw_tx: RpcHexString,
         options: TransactionOptions,
     ) -> rpc::RpcResult<MaybeSignedTransaction> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
         rpc::handle_result(
             self.sign_raw_transaction(account_arg.index::<N>()?, raw_tx, config).await.map(
                 |(tx, prev_signatures, cur_signatures)| {
@@ -320,6 +393,33 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         rpc::handle_result(self.create_account(name).await)
     }
 
+    async fn recover_accounts(
+        &self,
+        accounts: Vec<AccountArg>,
+    ) -> rpc::RpcResult<Vec<NewAccountInfo>> {
+        let account_indices = accounts
+            .iter()
+            .map(|account_arg| account_arg.index::<N>())
+            .collect::<Result<Vec<_>, _>>()?;
+        rpc::handle_result(self.recover_accounts(account_indices).await)
+    }
+
+    async fn next_multisig_account_public_key(&self) -> rpc::RpcResult<PublicKeyInfo> {
+        rpc::handle_result(self.next_multisig_account_public_key().await)
+    }
+
+    async fn create_multisig_account(
+        &self,
+        min_required_signatures: u8,
+        cosigner_public_keys: Vec<RpcAddress<Destination>>,
+        name: Option<String>,
+    ) -> rpc::RpcResult<NewMultisigAccountInfo> {
+        rpc::handle_result(
+            self.create_multisig_account(min_required_signatures, cosigner_public_keys, name)
+                .await,
+        )
+    }
+
     async fn rename_account(
         &self,
         account_arg: AccountArg,
@@ -416,6 +516,13 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         )
     }
 
+    async fn list_owned_tokens(
+        &self,
+        account_arg: AccountArg,
+    ) -> rpc::RpcResult<Vec<OwnedTokenInfo>> {
+        rpc::handle_result(self.list_owned_tokens(account_arg.index::<N>()?).await)
+    }
+
     async fn get_multisig_utxos(
         &self,
         account_arg: AccountArg,
@@ -474,6 +581,22 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         rpc::handle_result(result)
     }
 
+    async fn get_locked_utxos(&self, account_arg: AccountArg) -> rpc::RpcResult<Vec<JsonValue>> {
+        let utxos = self.get_locked_utxos(account_arg.index::<N>()?, UtxoStates::ALL).await?;
+
+        let result = utxos
+            .into_iter()
+            .map(|(utxo_outpoint, tx_ouput, unlock_time)| {
+                let result =
+                    LockedUtxoInfo::new(utxo_outpoint, tx_ouput, unlock_time, &self.chain_config)
+                        .map(serde_json::to_value);
+                rpc::handle_result(result)
+            })
+            .collect::<Result<Vec<_>, _>>();
+
+        rpc::handle_result(result)
+    }
+
     async fn submit_raw_transaction(
         &self,
         tx: HexEncoded<SignedTransaction>,
@@ -483,6 +606,10 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         rpc::handle_result(self.submit_raw_transaction(tx, do_not_store, options).await)
     }
 
+    async fn rebroadcast_all_transactions(&self) -> rpc::RpcResult<Vec<RebroadcastResult>> {
+        rpc::handle_result(self.rebroadcast_all_transactions().await)
+    }
+
     async fn send_coins(
         &self,
         account_arg: AccountArg,
@@ -491,10 +618,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         selected_utxos: Vec<RpcUtxoOutpoint>,
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewTransaction> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
         rpc::handle_result(
             self.send_coins(
                 account_arg.index::<N>()?,
@@ -515,10 +639,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         from_addresses: Vec<RpcAddress<Destination>>,
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewTransaction> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
         rpc::handle_result(
             self.sweep_addresses(
                 account.index::<N>()?,
@@ -537,10 +658,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         delegation_id: RpcAddress<DelegationId>,
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewTransaction> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
         rpc::handle_result(
             self.sweep_delegation(
                 account.index::<N>()?,
@@ -561,10 +679,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         change_address: Option<RpcAddress<Destination>>,
         options: TransactionOptions,
     ) -> rpc::RpcResult<ComposedTransaction> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
         rpc::handle_result(
             self.request_send_coins(
                 account_arg.index::<N>()?,
@@ -598,12 +713,10 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         cost_per_block: RpcAmountIn,
         margin_ratio_per_thousand: String,
         decommission_address: RpcAddress<Destination>,
+        vrf_public_key: Option<RpcAddress<VRFPublicKey>>,
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewTransaction> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
         rpc::handle_result(
             self.create_stake_pool(
                 account_arg.index::<N>()?,
@@ -611,6 +724,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
                 cost_per_block,
                 margin_ratio_per_thousand,
                 decommission_address,
+                vrf_public_key,
                 config,
             )
             .await
@@ -625,10 +739,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         output_address: Option<RpcAddress<Destination>>,
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewTransaction> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
         rpc::handle_result(
             self.decommission_stake_pool(
                 account_arg.index::<N>()?,
@@ -648,10 +759,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         output_address: Option<RpcAddress<Destination>>,
         options: TransactionOptions,
     ) -> rpc::RpcResult<HexEncoded<PartiallySignedTransaction>> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
         rpc::handle_result(
             self.decommission_stake_pool_request(
                 account_arg.index::<N>()?,
@@ -671,10 +779,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         pool_id: RpcAddress<PoolId>,
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewDelegation> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
         rpc::handle_result(
             self.create_delegation(account_arg.index::<N>()?, address, pool_id, config)
                 .await
@@ -692,10 +797,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         delegation_id: RpcAddress<DelegationId>,
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewTransaction> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
         rpc::handle_result(
             self.delegate_staking(account_arg.index::<N>()?, amount, delegation_id, config)
                 .await
@@ -711,10 +813,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         delegation_id: RpcAddress<DelegationId>,
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewTransaction> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
         rpc::handle_result(
             self.withdraw_from_delegation(
                 account_arg.index::<N>()?,
@@ -772,10 +871,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         metadata: NftMetadata,
         options: TransactionOptions,
     ) -> rpc::RpcResult<RpcTokenId> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
 
         rpc::handle_result(
             self.issue_new_nft(
@@ -795,10 +891,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         metadata: TokenMetadata,
         options: TransactionOptions,
     ) -> rpc::RpcResult<RpcTokenId> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
 
         let token_supply = metadata.token_supply::<N>()?;
         let is_freezable = metadata.is_freezable();
@@ -824,10 +917,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         address: RpcAddress<Destination>,
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewTransaction> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
 
         rpc::handle_result(
             self.change_token_authority(account_arg.index::<N>()?, token_id, address, config)
@@ -842,10 +932,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         metadata_uri: RpcHexString,
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewTransaction> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
 
         rpc::handle_result(
             self.change_token_metadata_uri(
@@ -866,10 +953,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         amount: RpcAmountIn,
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewTransaction> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
 
         rpc::handle_result(
             self.mint_tokens(account_arg.index::<N>()?, token_id, address, amount, config)
@@ -884,10 +968,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         amount: RpcAmountIn,
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewTransaction> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
 
         rpc::handle_result(
             self.unmint_tokens(account_arg.index::<N>()?, token_id, amount, config).await,
@@ -900,10 +981,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         token_id: RpcAddress<TokenId>,
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewTransaction> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
 
         rpc::handle_result(
             self.lock_token_supply(account_arg.index::<N>()?, token_id, config).await,
@@ -917,10 +995,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         is_unfreezable: bool,
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewTransaction> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
 
         let is_unfreezable = if is_unfreezable {
             IsTokenUnfreezable::Yes
@@ -940,10 +1015,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         token_id: RpcAddress<TokenId>,
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewTransaction> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
 
         rpc::handle_result(self.unfreeze_token(account_arg.index::<N>()?, token_id, config).await)
     }
@@ -956,10 +1028,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         amount: RpcAmountIn,
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewTransaction> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
 
         rpc::handle_result(
             self.send_tokens(account_arg.index::<N>()?, token_id, address, amount, config)
@@ -967,6 +1036,27 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         )
     }
 
+    async fn send_all_tokens(
+        &self,
+        account_arg: AccountArg,
+        token_id: RpcAddress<TokenId>,
+        address: RpcAddress<Destination>,
+        options: TransactionOptions,
+    ) -> rpc::RpcResult<NewTransaction> {
+        let config = self.controller_config(options.in_top_x_mb());
+
+        rpc::handle_result(
+            self.send_all_tokens(account_arg.index::<N>()?, token_id, address, config).await,
+        )
+    }
+
+    async fn list_token_authorities(
+        &self,
+        account_arg: AccountArg,
+    ) -> rpc::RpcResult<Vec<TokenAuthorityInfo>> {
+        rpc::handle_result(self.list_token_authorities(account_arg.index::<N>()?).await)
+    }
+
     async fn make_tx_to_send_tokens_from_multisig_address(
         &self,
         account_arg: AccountArg,
@@ -975,10 +1065,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         outputs: Vec<GenericTokenTransfer>,
         options: TransactionOptions,
     ) -> rpc::RpcResult<SendTokensFromMultisigAddressResult> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
         rpc::handle_result(
             self.make_tx_to_send_tokens_from_multisig_address(
                 account_arg.index::<N>()?,
@@ -1004,10 +1091,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         data: RpcHexString,
         options: TransactionOptions,
     ) -> rpc::RpcResult<NewTransaction> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
 
         rpc::handle_result(
             self.deposit_data(account_arg.index::<N>()?, data.into_bytes(), config).await,
@@ -1022,10 +1106,7 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         htlc: RpcHashedTimelockContract,
         options: TransactionOptions,
     ) -> rpc::RpcResult<HexEncoded<SignedTransaction>> {
-        let config = ControllerConfig {
-            in_top_x_mb: options.in_top_x_mb(),
-            broadcast_to_mempool: true,
-        };
+        let config = self.controller_config(options.in_top_x_mb());
 
         rpc::handle_result(
             self.create_htlc_transaction(account_arg.index::<N>()?, amount, token_id, htlc, config)
@@ -1045,6 +1126,13 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         )
     }
 
+    async fn stake_pool_delegations_count(
+        &self,
+        pool_id: RpcAddress<PoolId>,
+    ) -> rpc::RpcResult<Option<usize>> {
+        rpc::handle_result(self.stake_pool_delegations_count(pool_id).await)
+    }
+
     async fn node_version(&self) -> rpc::RpcResult<NodeVersion> {
         rpc::handle_result(self.node_version().await.map(|version| NodeVersion { version }))
     }
@@ -1111,6 +1199,18 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         rpc::handle_result(self.remove_reserved_peer(address).await)
     }
 
+    async fn peer_limits(&self) -> rpc::RpcResult<PeerLimits> {
+        rpc::handle_result(self.peer_limits().await)
+    }
+
+    async fn set_peer_limits(&self, max_inbound: usize, max_outbound: usize) -> rpc::RpcResult<()> {
+        let limits = PeerLimits {
+            max_inbound,
+            max_outbound,
+        };
+        rpc::handle_result(self.set_peer_limits(limits).await)
+    }
+
     async fn submit_block(&self, block: HexEncoded<Block>) -> rpc::RpcResult<()> {
         rpc::handle_result(self.submit_block(block).await)
     }
@@ -1129,6 +1229,38 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         )
     }
 
+    async fn create_cpfp_transaction(
+        &self,
+        account_arg: AccountArg,
+        parent_transaction_id: HexEncoded<Id<Transaction>>,
+        target_fee_rate: RpcAmountIn,
+        options: TransactionOptions,
+    ) -> rpc::RpcResult<NewTransaction> {
+        let config = self.controller_config(options.in_top_x_mb());
+
+        rpc::handle_result(
+            self.create_cpfp_transaction(
+                account_arg.index::<N>()?,
+                parent_transaction_id.take(),
+                target_fee_rate,
+                config,
+            )
+            .await,
+        )
+    }
+
+    async fn import_transaction(
+        &self,
+        account_arg: AccountArg,
+        transaction: HexEncoded<SignedTransaction>,
+        block_height: Option<BlockHeight>,
+    ) -> rpc::RpcResult<()> {
+        rpc::handle_result(
+            self.import_transaction(account_arg.index::<N>()?, transaction.take(), block_height)
+                .await,
+        )
+    }
+
     async fn list_pending_transactions(
         &self,
         account_arg: AccountArg,
@@ -1151,6 +1283,26 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         )
     }
 
+    async fn list_transactions(
+        &self,
+        account_arg: AccountArg,
+        cursor: Option<TxCursor>,
+        count: NonZeroUsize,
+    ) -> rpc::RpcResult<TransactionListPage> {
+        rpc::handle_result(
+            self.get_transaction_list_from_cursor(account_arg.index::<N>()?, cursor, count)
+                .await,
+        )
+    }
+
+    async fn get_account_activity_summary(
+        &self,
+        account_arg: AccountArg,
+        days: u32,
+    ) -> rpc::RpcResult<Vec<DailyActivity>> {
+        rpc::handle_result(self.get_account_activity_summary(account_arg.index::<N>()?, days).await)
+    }
+
     async fn get_transaction(
         &self,
         account_arg: AccountArg,
@@ -1270,6 +1422,18 @@ impl<N: NodeInterface + Clone + Send + Sync + Debug + 'static> WalletRpcServer f
         )
     }
 
+    async fn node_blocks_by_height_range(
+        &self,
+        from: BlockHeight,
+        count: u32,
+    ) -> rpc::RpcResult<Vec<HexEncoded<Block>>> {
+        rpc::handle_result(
+            self.get_blocks_by_height_range(from, count)
+                .await
+                .map(|blocks| blocks.into_iter().map(HexEncoded::new).collect::<Vec<_>>()),
+        )
+    }
+
     async fn node_get_block_ids_as_checkpoints(
         &self,
         start_height: BlockHeight,
@@ -19,7 +19,7 @@ use common::{
     address::{pubkeyhash::PublicKeyHash, Address, AddressError},
     chain::{
         block::timestamp::BlockTimestamp,
-        classic_multisig::ClassicMultisigChallengeError,
+        classic_multisig::{ClassicMultisigChallenge, ClassicMultisigChallengeError},
         partially_signed_transaction::PartiallySignedTransaction,
         signature::DestinationSigError,
         timelock::OutputTimeLock,
@@ -37,10 +37,10 @@ use crypto::{
     vrf::VRFPublicKey,
 };
 use rpc::description::HasValueHint;
-use wallet::account::PoolData;
+use wallet::account::{transaction_list::TxCursor, PoolData};
 
 pub use chainstate::{
-    rpc::{RpcSignedTransaction, RpcTxOutput, RpcUtxoOutpoint},
+    rpc::{RpcOutPointSourceId, RpcSignedTransaction, RpcTxOutput, RpcUtxoOutpoint},
     ChainInfo,
 };
 pub use common::{
@@ -52,11 +52,12 @@ pub use rpc::types::{RpcHexString, RpcString};
 pub use serde_json::Value as JsonValue;
 pub use serialization::hex_encoded::HexEncoded;
 pub use wallet_controller::types::{
-    Balances, BlockInfo, InspectTransaction, SignatureStats, ValidatedSignatures,
+    Balances, BlockInfo, InspectTransaction, OwnedTokenInfo, SignatureStats, ValidatedSignatures,
 };
 pub use wallet_controller::{ControllerConfig, NodeInterface};
 use wallet_controller::{UtxoState, UtxoType};
-use wallet_types::signature_status::SignatureStatus;
+use wallet_types::{signature_status::SignatureStatus, wallet_tx::TxData};
+pub use wallet_types::{AddressOrigin, LookaheadStatus};
 
 use crate::service::SubmitError;
 
@@ -81,6 +82,9 @@ pub enum RpcError<N: NodeInterface> {
     #[error("Invalid pool ID")]
     InvalidPoolId,
 
+    #[error("Invalid VRF public key")]
+    InvalidVrfPublicKey,
+
     #[error("Invalid delegation ID")]
     InvalidDelegationId,
 
@@ -335,6 +339,28 @@ impl UtxoInfo {
     }
 }
 
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LockedUtxoInfo {
+    pub outpoint: RpcUtxoOutpoint,
+    pub output: RpcTxOutput,
+    pub unlock_time: BlockTimestamp,
+}
+
+impl LockedUtxoInfo {
+    pub fn new(
+        outpoint: UtxoOutPoint,
+        output: TxOutput,
+        unlock_time: BlockTimestamp,
+        chain_config: &ChainConfig,
+    ) -> Result<Self, AddressError> {
+        Ok(Self {
+            output: RpcTxOutput::new(chain_config, output)?,
+            outpoint: RpcUtxoOutpoint::new(outpoint),
+            unlock_time,
+        })
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
 pub struct NewAccountInfo {
     pub account: u32,
@@ -348,6 +374,45 @@ impl NewAccountInfo {
     }
 }
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub struct NewMultisigAccountInfo {
+    pub account: u32,
+    pub name: Option<String>,
+    pub multisig_address: RpcAddress<Destination>,
+}
+
+impl NewMultisigAccountInfo {
+    pub fn new(
+        account: U31,
+        name: Option<String>,
+        challenge: &ClassicMultisigChallenge,
+        chain_config: &ChainConfig,
+    ) -> Self {
+        let account = account.into_u32();
+        let multisig_address =
+            RpcAddress::new(chain_config, Destination::ClassicMultisig(challenge.into()))
+                .expect("addressable");
+        Self {
+            account,
+            name,
+            multisig_address,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub struct AddressOwnerInfo {
+    pub account: u32,
+    pub origin: AddressOrigin,
+}
+
+impl AddressOwnerInfo {
+    pub fn new(account: U31, origin: AddressOrigin) -> Self {
+        let account = account.into_u32();
+        Self { account, origin }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
 pub struct TransactionOptions {
     pub in_top_x_mb: Option<usize>,
@@ -443,6 +508,21 @@ impl DelegationInfo {
     }
 }
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub struct TokenAuthorityInfo {
+    pub token_id: RpcAddress<TokenId>,
+    pub authority: RpcAddress<Destination>,
+}
+
+impl TokenAuthorityInfo {
+    pub fn new(token_id: TokenId, authority: Destination, chain_config: &ChainConfig) -> Self {
+        Self {
+            token_id: RpcAddress::new(chain_config, token_id).expect("addressable"),
+            authority: RpcAddress::new(chain_config, authority).expect("addressable"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
 pub struct NftMetadata {
     pub media_hash: String,
@@ -610,6 +690,39 @@ impl NewTransaction {
     }
 }
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub struct RebroadcastResult {
+    pub tx_id: Id<Transaction>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub struct TransactionListEntry {
+    pub tx_id: Id<Transaction>,
+    /// The state of the transaction, e.g. Confirmed or InMempool
+    pub state: String,
+    pub block_height: Option<BlockHeight>,
+    pub timestamp: Option<BlockTimestamp>,
+}
+
+impl TransactionListEntry {
+    pub fn new(tx_data: &TxData) -> Self {
+        Self {
+            tx_id: tx_data.get_transaction().get_id(),
+            state: tx_data.state().short_name().to_owned(),
+            block_height: tx_data.state().block_height(),
+            timestamp: tx_data.state().timestamp(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub struct TransactionListPage {
+    pub transactions: Vec<TransactionListEntry>,
+    /// The cursor to get the next page with, none if there are no more transactions
+    pub next_cursor: Option<TxCursor>,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
 pub struct NodeVersion {
     pub version: String,
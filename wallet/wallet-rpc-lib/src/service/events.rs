@@ -15,11 +15,12 @@
 
 use common::{
     chain::{block::timestamp::BlockTimestamp, GenBlock, OutPointSourceId, Transaction},
-    primitives::{BlockHeight, Id, Idable},
+    primitives::{Amount, BlockHeight, Id, Idable},
 };
 use crypto::key::hdkd::u31::U31;
 use logging::log;
 use tokio::sync::mpsc;
+use wallet::account::currency_grouper::Currency;
 use wallet_types::wallet_tx::{self, BlockData};
 
 /// Events that can be emitted.
@@ -56,6 +57,22 @@ pub enum Event {
     },
 }
 
+/// Events concerning a single wallet account
+#[derive(Eq, PartialEq, serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub enum AccountEvent {
+    /// A transaction of the account has been seen by the wallet for the first time
+    NewTransaction { tx_id: Id<Transaction> },
+
+    /// Confirmed balance of the account in given currency has changed
+    BalanceChanged { currency: Currency, amount: Amount },
+
+    /// The wallet has scanned blocks up to given height
+    BlockScanned { height: BlockHeight },
+
+    /// Failed to query the state of the account
+    Error { message: String },
+}
+
 /// Transaction state
 #[derive(
     Eq, PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize, rpc_description::HasValueHint,
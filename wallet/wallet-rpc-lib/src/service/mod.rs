@@ -23,7 +23,7 @@ use common::chain::ChainConfig;
 use crypto::key::hdkd::u31::U31;
 use utils::shallow_clone::ShallowClone;
 
-pub use events::{Event, TxState};
+pub use events::{AccountEvent, Event, TxState};
 pub use handle::{EventStream, SubmitError, WalletHandle};
 use wallet_controller::{ControllerConfig, NodeInterface};
 pub use worker::{CreatedWallet, WalletController, WalletControllerError};
@@ -87,6 +87,9 @@ impl<N: NodeInterface + Clone + Send + Sync + 'static> WalletService<N> {
                 let config = ControllerConfig {
                     in_top_x_mb: 5,
                     broadcast_to_mempool: true,
+                    min_relay_fee_rate: None,
+                    max_sync_lag: None,
+                    broadcast_retry: Default::default(),
                 };
                 controller.synced_controller(account_index, config).await?.start_staking()?;
             }
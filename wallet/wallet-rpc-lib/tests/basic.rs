@@ -23,15 +23,16 @@ use common::{
     primitives::{Amount, BlockHeight, Id},
 };
 use utils::{
-    make_seedable_rng, ClientT, JsonValue, Seed, Subscription, SubscriptionClientT, ACCOUNT0_ARG,
-    ACCOUNT1_ARG,
+    make_seedable_rng, ClientT, JsonValue, Rng, Seed, Subscription, SubscriptionClientT,
+    ACCOUNT0_ARG, ACCOUNT1_ARG,
 };
+use wallet::account::currency_grouper::Currency;
 use wallet_rpc_lib::{
     types::{
         AddressInfo, Balances, BlockInfo, NewAccountInfo, NewTransaction, RpcAmountIn,
         RpcUtxoState, TransactionOptions,
     },
-    TxState,
+    AccountEvent, TxState,
 };
 
 #[rstest]
@@ -232,6 +233,80 @@ async fn stake_and_send_coins_to_acct1(#[case] seed: Seed) {
     tf.stop().await;
 }
 
+#[rstest]
+#[trace]
+#[case(test_utils::random::Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn account_events_balance_changed(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let tf = utils::TestFramework::start(&mut rng).await;
+
+    let wallet_rpc = tf.rpc_client_ws().await;
+
+    let new_acct: NewAccountInfo =
+        wallet_rpc.request("account_create", Vec::<u32>::new()).await.unwrap();
+    assert_eq!(new_acct.account, 1);
+    let acct1_addr: AddressInfo = wallet_rpc.request("address_new", [ACCOUNT1_ARG]).await.unwrap();
+
+    let mut account_events: Subscription<AccountEvent> = wallet_rpc
+        .subscribe(
+            "subscribe_account_events",
+            [ACCOUNT1_ARG],
+            "unsubscribe_account_events",
+        )
+        .await
+        .unwrap();
+
+    let to_send_amount = Amount::from_atoms(rng.gen_range(1000..1_000_000));
+    let new_tx: NewTransaction = {
+        let params = (
+            ACCOUNT0_ARG,
+            acct1_addr.address,
+            RpcAmountIn::from_atoms(to_send_amount),
+            Vec::<UtxoOutPoint>::new(),
+            TransactionOptions {
+                in_top_x_mb: Some(3),
+            },
+        );
+        wallet_rpc.request("address_send", params).await.unwrap()
+    };
+
+    // The new block picks the transaction from the mempool
+    let _: JsonValue = wallet_rpc
+        .request("node_generate_block", (ACCOUNT0_ARG, [(); 0]))
+        .await
+        .unwrap();
+
+    let mut seen_tx = false;
+    let balance = loop {
+        let event = tokio::time::timeout(std::time::Duration::from_secs(30), account_events.next())
+            .await
+            .expect("No BalanceChanged event received")
+            .unwrap()
+            .unwrap();
+        log::debug!("Account event: {event:?}");
+
+        match event {
+            AccountEvent::NewTransaction { tx_id } => {
+                assert_eq!(tx_id, new_tx.tx_id);
+                seen_tx = true;
+            }
+            AccountEvent::BalanceChanged { currency, amount } => {
+                assert_eq!(currency, Currency::Coin);
+                break amount;
+            }
+            AccountEvent::BlockScanned { .. } => {}
+            AccountEvent::Error { message } => panic!("Unexpected error event: {message}"),
+        }
+    };
+    assert!(seen_tx);
+    assert_eq!(balance, to_send_amount);
+
+    std::mem::drop(account_events);
+    std::mem::drop(wallet_rpc);
+    tf.stop().await;
+}
+
 #[rstest]
 #[trace]
 #[case(test_utils::random::Seed::from_entropy())]